@@ -28,6 +28,10 @@ interface OcrConfig {
   min_confidence: number;
   region: { x: number; y: number; width: number; height: number } | null;
   target_window: number | null;
+  target_window_title?: string | null;
+  frame_change_threshold?: number;
+  static_refresh_ms?: number;
+  stabilization_frames?: number;
 }
 
 const translationCache = new Map<string, string>();
@@ -65,6 +69,9 @@ export default function OcrTranslatorPage() {
     min_confidence: 0.5,
     region: null,
     target_window: null,
+    target_window_title: null,
+    static_refresh_ms: 5000,
+    stabilization_frames: 5,
   });
   const [windows, setWindows] = useState<WindowInfo[]>([]);
   const [isTranslating, setIsTranslating] = useState(false);
@@ -299,7 +306,11 @@ export default function OcrTranslatorPage() {
               <select 
                 className="w-full h-9 px-3 rounded-lg border bg-background text-sm hover:border-blue-500/50 transition-colors"
                 value={config.target_window ?? ''}
-                onChange={(e) => setConfig({...config, target_window: e.target.value ? Number(e.target.value) : null})}
+                onChange={(e) => {
+                  const hwnd = e.target.value ? Number(e.target.value) : null;
+                  const title = windows.find(w => w.hwnd === hwnd)?.title ?? null;
+                  setConfig({...config, target_window: hwnd, target_window_title: title});
+                }}
                 disabled={isRunning}
               >
                 <option value="">🖥️ {t('ocrTranslator.fullScreen')}</option>
@@ -197,10 +197,17 @@ export function GlossaryManager({
 
     try {
       const text = await file.text();
-      const result = await invoke<GameGlossary>('import_glossary', { jsonContent: text });
-      setGlossary(result);
-      onGlossaryChange?.(result);
-      toast.success(`Glossary imported: ${result.entries.length} entries`);
+      const result = await invoke<{ glossary: GameGlossary; added: number; conflicts: unknown[] }>(
+        'import_glossary',
+        { jsonContent: text }
+      );
+      setGlossary(result.glossary);
+      onGlossaryChange?.(result.glossary);
+      if (result.conflicts.length > 0) {
+        toast.warning(`Glossary imported: ${result.added} new entries, ${result.conflicts.length} conflicts kept existing`);
+      } else {
+        toast.success(`Glossary imported: ${result.glossary.entries.length} entries`);
+      }
     } catch (error) {
       toast.error('Import error');
       console.error(error);
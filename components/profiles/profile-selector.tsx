@@ -158,7 +158,7 @@ function ProfileCard({ profile, isSelected, isCurrentProfile = false }: ProfileC
     setIsAuthenticating(true);
     setAuthError(null);
 
-    const success = await authenticateProfile(profile.name, password);
+    const success = await authenticateProfile(profile.id, password);
     
     if (success) {
       console.log('✅ Login successful for:', profile.name);
@@ -520,7 +520,7 @@ export function ProfileSelector({ onCreateProfile }: ProfileSelectorProps) {
         const password = atob(savedPassword); // Decode base64
         
         // Execute auto-login using profile NAME, not ID
-        authenticateProfile(profile.name, password).then(success => {
+        authenticateProfile(profile.id, password).then(success => {
           setIsAutoLoggingIn(false);
           if (success) {
             console.log('✅ Auto-login completed after language change');
@@ -14,6 +14,8 @@ interface SharedGame {
   name: string;
   owner_steam_id: string;
   owner_account_name: string;
+  owner_persona_name?: string | null;
+  owner_avatar_url?: string | null;
   is_shared: boolean;
 }
 
@@ -361,7 +363,10 @@ export function SteamFamilySharing() {
                         </div>
                         <div>
                           <p className="font-medium text-sm">{game.name}</p>
-                          <p className="text-xs text-muted-foreground">
+                          <p className="text-xs text-muted-foreground flex items-center gap-1">
+                            {game.owner_avatar_url && (
+                              <img src={game.owner_avatar_url} alt="" className="h-4 w-4 rounded-full" />
+                            )}
                             Shared by {game.owner_account_name}
                           </p>
                         </div>
@@ -158,13 +158,13 @@ export function useProfiles(): UseProfilesReturn {
   }, [loadProfiles]);
 
   // Autentica profilo con transizione fluida
-  const authenticateProfile = useCallback(async (name: string, password: string): Promise<boolean> => {
+  const authenticateProfile = useCallback(async (profileId: string, password: string): Promise<boolean> => {
     try {
       setError(null);
-      console.log('🔐 useProfiles: Tentativo autenticazione per:', name);
+      console.log('🔐 useProfiles: Tentativo autenticazione per:', profileId);
       
       const response = await invoke<ProfileResponse<UserProfile>>('authenticate_profile', { 
-        name, 
+        profileId, 
         password 
       });
       
@@ -209,13 +209,13 @@ export function useProfiles(): UseProfilesReturn {
   }, [loadProfiles]);
 
   // Cambia profilo con transizione fluida
-  const switchProfile = useCallback(async (name: string, password: string): Promise<boolean> => {
+  const switchProfile = useCallback(async (profileId: string, password: string): Promise<boolean> => {
     try {
       setError(null);
-      console.log('🔄 useProfiles: Cambio profilo a:', name);
+      console.log('🔄 useProfiles: Cambio profilo a:', profileId);
       
       const response = await invoke<ProfileResponse<UserProfile>>('switch_profile', { 
-        name, 
+        profileId, 
         password 
       });
       
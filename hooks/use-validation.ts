@@ -32,6 +32,16 @@ export interface PasswordStrength {
   has_digit: boolean;
   has_special: boolean;
   is_long_enough: boolean;
+  entropy_bits?: number;
+  crack_time_seconds?: number;
+  crack_time_display?: string;
+  hints?: string[];
+}
+
+export interface SanitizedInput {
+  value: string;
+  removed: { code_point: string; position: number; reason: 'control' | 'zero_width' | 'bidi_control' }[];
+  normalized: boolean;
 }
 
 export function useValidation() {
@@ -132,7 +142,11 @@ export function useValidation() {
   const sanitizeInput = useCallback(async (input: string): Promise<string> => {
     try {
       // Try backend sanitization first
-      return await invoke<string>('sanitize_input', { input });
+      const response = await invoke<{ success: boolean; data?: SanitizedInput; error?: string }>('sanitize_input', { input });
+      if (response.success && response.data) {
+        return response.data.value;
+      }
+      throw new Error(response.error ?? 'Sanitization failed');
     } catch (error) {
       console.error('Error sanitizing input via backend, using frontend fallback:', error);
       // Fallback to frontend sanitization
@@ -1,6 +1,7 @@
 'use client';
 
-import { createContext, useContext, useEffect, useState, ReactNode } from 'react';
+import { createContext, useContext, useEffect, useRef, useState, ReactNode } from 'react';
+import { toast } from 'sonner';
 import { useProfiles } from '@/hooks/use-profiles';
 import { useProfileSettings } from '@/hooks/use-profile-settings';
 import { UserProfile } from '@/types/profiles';
@@ -17,6 +18,19 @@ interface ProfileAuthContextType {
 
 const ProfileAuthContext = createContext<ProfileAuthContextType | undefined>(undefined);
 
+/** Stato sessione restituito da touch_session e dagli eventi del monitor backend */
+interface SessionStatus {
+  active: boolean;
+  expired: boolean;
+  near_expiry: boolean;
+  seconds_remaining: number | null;
+  expiry_reason: 'idle' | 'max_duration' | null;
+}
+
+/** Intervallo minimo tra due touch_session (il backend conta l'inattività in minuti) */
+const SESSION_TOUCH_THROTTLE_MS = 60_000;
+const ACTIVITY_EVENTS = ['pointerdown', 'keydown', 'wheel', 'mousemove', 'touchstart'] as const;
+
 interface ProfileAuthProviderProps {
   children: ReactNode;
 }
@@ -49,6 +63,77 @@ export function ProfileAuthProvider({ children }: ProfileAuthProviderProps) {
   const [sessionTimeRemaining, setSessionTimeRemaining] = useState<number | null>(null);
   const [isSessionExpired, setIsSessionExpired] = useState(false);
 
+  // Attività utente -> touch_session, così il monitor backend non scade una sessione in uso
+  const lastTouchRef = useRef(0);
+  useEffect(() => {
+    if (!currentProfile || typeof window === 'undefined') return;
+    setIsSessionExpired(false);
+
+    const touch = async () => {
+      const now = Date.now();
+      if (now - lastTouchRef.current < SESSION_TOUCH_THROTTLE_MS) return;
+      lastTouchRef.current = now;
+      try {
+        const { invoke } = await import('@/lib/tauri-api');
+        const response = await invoke<{ success: boolean; data?: SessionStatus }>('touch_session');
+        if (response.success && response.data) {
+          setSessionTimeRemaining(response.data.seconds_remaining ?? null);
+        }
+      } catch (error) {
+        console.warn('touch_session error:', error);
+      }
+    };
+
+    lastTouchRef.current = 0;
+    touch();
+    ACTIVITY_EVENTS.forEach(event => window.addEventListener(event, touch, { passive: true }));
+    return () => {
+      ACTIVITY_EVENTS.forEach(event => window.removeEventListener(event, touch));
+    };
+  }, [currentProfile?.id]);
+
+  // Eventi del monitor sessione backend
+  useEffect(() => {
+    if (typeof window === 'undefined') return;
+    let disposed = false;
+    const unlisteners: Array<() => void> = [];
+
+    (async () => {
+      try {
+        const { listen } = await import('@tauri-apps/api/event');
+        const onExpiring = await listen<SessionStatus>('profile-session-expiring', ({ payload }) => {
+          setSessionTimeRemaining(payload.seconds_remaining ?? null);
+          const minutes = Math.max(1, Math.round((payload.seconds_remaining ?? 60) / 60));
+          toast.warning(`La sessione scadrà tra ${minutes} min`, {
+            description: payload.expiry_reason === 'max_duration'
+              ? 'Durata massima della sessione raggiunta a breve'
+              : 'Nessuna attività rilevata',
+          });
+        });
+        const onExpired = await listen<SessionStatus>('profile-session-expired', () => {
+          // Il backend ha già chiuso la sessione: riallinea lo stato locale
+          setIsSessionExpired(true);
+          setSessionTimeRemaining(null);
+          toast.error('Sessione scaduta', { description: 'Effettua di nuovo l\'accesso al profilo' });
+          window.dispatchEvent(new Event('profile-auth-changed'));
+        });
+        if (disposed) {
+          onExpiring();
+          onExpired();
+        } else {
+          unlisteners.push(onExpiring, onExpired);
+        }
+      } catch (error) {
+        console.warn('Session event listeners unavailable:', error);
+      }
+    })();
+
+    return () => {
+      disposed = true;
+      unlisteners.forEach(unlisten => unlisten());
+    };
+  }, []);
+
   // Check session status periodically - DISABILITATO per evitare conflitti con authentication semplice
   // useEffect(() => {
   //   if (!currentProfile) {
@@ -30,6 +30,37 @@ export interface BridgeResponse<T> {
   error: string | null;
 }
 
+export interface HandshakeResponse {
+  accepted: boolean;
+  protocol_version: number;
+  capabilities: number;
+  error: string | null;
+}
+
+export interface BridgeStatus {
+  running: boolean;
+  protocol_version: number;
+  plugin: {
+    request: { magic: number; protocol_version: number; capabilities: number; plugin_version: string | null };
+    response: HandshakeResponse;
+    at: string;
+  } | null;
+  compatible: boolean;
+  message: string | null;
+}
+
+export interface DictionaryReload {
+  source: string;
+  target: string;
+  old_entries: number;
+  new_entries: number;
+}
+
+export interface BulkAddResult {
+  added: number;
+  duplicates_skipped: number;
+}
+
 export interface TranslationPair {
   original: string;
   translated: string;
@@ -77,8 +108,8 @@ export class TranslationBridgeClient {
    */
   async isRunning(): Promise<boolean> {
     try {
-      const response = await invoke<BridgeResponse<boolean>>('translation_bridge_status');
-      this.isConnected = response.data ?? false;
+      const response = await invoke<BridgeResponse<BridgeStatus>>('translation_bridge_status');
+      this.isConnected = response.data?.running ?? false;
       return this.isConnected;
     } catch (error) {
       console.error('[TranslationBridge] Failed to check status:', error);
@@ -86,6 +117,19 @@ export class TranslationBridgeClient {
     }
   }
 
+  /**
+   * Get bridge status, including plugin protocol compatibility
+   */
+  async getStatus(): Promise<BridgeStatus | null> {
+    try {
+      const response = await invoke<BridgeResponse<BridgeStatus>>('translation_bridge_status');
+      return response.data;
+    } catch (error) {
+      console.error('[TranslationBridge] Failed to get status:', error);
+      return null;
+    }
+  }
+
   /**
    * Get bridge statistics
    */
@@ -150,6 +194,24 @@ export class TranslationBridgeClient {
     }
   }
 
+  /**
+   * Reload a JSON dictionary, swapping it in atomically (safe during gameplay)
+   */
+  async reloadFromFile(path: string): Promise<DictionaryReload | null> {
+    try {
+      const response = await invoke<BridgeResponse<DictionaryReload>>('translation_bridge_reload_from_file', {
+        path,
+      });
+      if (!response.success) {
+        console.error('[TranslationBridge] Reload failed:', response.error);
+      }
+      return response.data;
+    } catch (error) {
+      console.error('[TranslationBridge] Failed to reload dictionary:', error);
+      return null;
+    }
+  }
+
   /**
    * Set active language pair
    */
@@ -182,6 +244,21 @@ export class TranslationBridgeClient {
     }
   }
 
+  /**
+   * Add many translations in a single call (duplicates are skipped)
+   */
+  async addTranslations(pairs: TranslationPair[]): Promise<BulkAddResult | null> {
+    try {
+      const response = await invoke<BridgeResponse<BulkAddResult>>('translation_bridge_add_translations', {
+        pairs: pairs.map(p => [p.original, p.translated]),
+      });
+      return response.data;
+    } catch (error) {
+      console.error('[TranslationBridge] Failed to add translations:', error);
+      return null;
+    }
+  }
+
   /**
    * Get translation for a text
    */
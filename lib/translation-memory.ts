@@ -44,6 +44,7 @@ export interface TranslationMemory {
   stats: TMStats;
   createdAt: string;
   updatedAt: string;
+  loadedAt?: string;          // Istante del caricamento dal backend (per il merge del journal)
 }
 
 export interface TMStats {
@@ -528,7 +529,8 @@ export class TranslationMemoryManager {
           byContext: calculatedStats.byContext || {}
         },
         createdAt: this.memory.createdAt,
-        updatedAt: new Date().toISOString()
+        updatedAt: new Date().toISOString(),
+        loadedAt: this.memory.loadedAt
       };
 
       console.log('[TM] Payload pronto. Stats:', JSON.stringify(memoryPayload.stats));
@@ -75,12 +75,35 @@ namespace GameStringer.Satellite
     /// </summary>
     public class TranslationBridge : IDisposable
     {
-        private const string SHMEM_NAME = "GameStringer_TranslationBridge_v1";
+        // Must match SHARED_MEMORY_NAME / PROTOCOL_VERSION in src-tauri/src/translation_bridge/protocol.rs
+        private const string SHMEM_NAME = "GameStringer_TranslationBridge_v2";
         private const int BUFFER_SIZE = 4 * 1024 * 1024; // 4MB
+        private const uint MAGIC_NUMBER = 0x47535452; // "GSTR"
+        private const byte PROTOCOL_VERSION = 2;
+
+        // Capabilities (CAP_* in protocol.rs)
+        private const uint CAP_CONTEXT = 1 << 0;
+        private const uint CAP_SOURCE_LANGUAGE = 1 << 1;
+        private const uint CAP_HEADER_STATS = 1 << 2;
+        private const uint CAP_HOT_RELOAD = 1 << 3;
+        private const uint CLIENT_CAPABILITIES = CAP_CONTEXT;
+
+        // SharedMemoryHeader field offsets
+        private const int OFFSET_MAGIC = 0;
+        private const int OFFSET_VERSION = 4;
+        private const int OFFSET_CLIENT_VERSION = 6;
+        private const int OFFSET_HANDSHAKE_STATUS = 7;
+        private const int OFFSET_CLIENT_CAPABILITIES = 24;
+        private const int OFFSET_NEGOTIATED_CAPABILITIES = 28;
+
+        private const byte HANDSHAKE_PENDING = 0;
+        private const byte HANDSHAKE_ACCEPTED = 1;
+        private const int HANDSHAKE_TIMEOUT_MS = 2000;
 
         private MemoryMappedFile _sharedMemory;
         private MemoryMappedViewAccessor _accessor;
         private bool _isConnected;
+        private uint _capabilities;
         private Dictionary<ulong, string> _localCache;
 
         public TranslationBridge()
@@ -95,8 +118,10 @@ namespace GameStringer.Satellite
                 // Try to open existing shared memory created by Rust backend
                 _sharedMemory = MemoryMappedFile.OpenExisting(SHMEM_NAME);
                 _accessor = _sharedMemory.CreateViewAccessor();
-                _isConnected = true;
-                return true;
+                _isConnected = Handshake();
+                if (!_isConnected)
+                    Disconnect();
+                return _isConnected;
             }
             catch (FileNotFoundException)
             {
@@ -112,6 +137,52 @@ namespace GameStringer.Satellite
             }
         }
 
+        /// <summary>
+        /// Announce protocol version and capabilities in the shared header and wait for
+        /// the backend verdict. Incompatible versions must not exchange translations.
+        /// </summary>
+        private bool Handshake()
+        {
+            uint magic = _accessor.ReadUInt32(OFFSET_MAGIC);
+            byte serverVersion = _accessor.ReadByte(OFFSET_VERSION);
+            if (magic != MAGIC_NUMBER)
+            {
+                Plugin.Log.LogError($"[TranslationBridge] Invalid shared memory (magic 0x{magic:X8})");
+                return false;
+            }
+            if (serverVersion != PROTOCOL_VERSION)
+            {
+                Plugin.Log.LogError($"[TranslationBridge] Protocol mismatch: plugin {PROTOCOL_VERSION}, GameStringer {serverVersion}. Update the older side.");
+                return false;
+            }
+
+            // Capabilities first: the backend reads them once client_version is set
+            _accessor.Write(OFFSET_CLIENT_CAPABILITIES, CLIENT_CAPABILITIES);
+            Thread.MemoryBarrier();
+            _accessor.Write(OFFSET_HANDSHAKE_STATUS, HANDSHAKE_PENDING);
+            _accessor.Write(OFFSET_CLIENT_VERSION, PROTOCOL_VERSION);
+
+            int waited = 0;
+            byte status;
+            while ((status = _accessor.ReadByte(OFFSET_HANDSHAKE_STATUS)) == HANDSHAKE_PENDING && waited < HANDSHAKE_TIMEOUT_MS)
+            {
+                Thread.Sleep(50);
+                waited += 50;
+            }
+
+            if (status != HANDSHAKE_ACCEPTED)
+            {
+                Plugin.Log.LogError(status == HANDSHAKE_PENDING
+                    ? "[TranslationBridge] Handshake timed out, backend not answering"
+                    : "[TranslationBridge] Handshake rejected by GameStringer, check the app log");
+                return false;
+            }
+
+            _capabilities = _accessor.ReadUInt32(OFFSET_NEGOTIATED_CAPABILITIES);
+            Plugin.Log.LogInfo($"[TranslationBridge] Handshake OK (protocol {PROTOCOL_VERSION}, capabilities 0x{_capabilities:X})");
+            return true;
+        }
+
         public void Disconnect()
         {
             _accessor?.Dispose();
@@ -6,5 +6,5 @@
 pub mod models;
 pub mod storage;
 
-pub use models::{Activity, ActivityType, ActivityFilter, ActivityPage};
+pub use models::{Activity, ActivityType, ActivityFilter, ActivityPage, RetentionPolicy};
 pub use storage::ActivityStorage;
@@ -102,6 +102,32 @@ impl Activity {
         }
     }
 
+    /// Verifica se l'attività contiene il testo cercato (già in minuscolo)
+    pub fn matches_text(&self, needle: &str) -> bool {
+        if needle.is_empty() {
+            return true;
+        }
+        if self.title.to_lowercase().contains(needle) {
+            return true;
+        }
+        if let Some(ref desc) = self.description {
+            if desc.to_lowercase().contains(needle) {
+                return true;
+            }
+        }
+        if let Some(ref game) = self.game_name {
+            if game.to_lowercase().contains(needle) {
+                return true;
+            }
+        }
+        if let Some(ref meta) = self.metadata {
+            if metadata_contains(meta, needle) {
+                return true;
+            }
+        }
+        false
+    }
+
     /// Builder: aggiungi descrizione
     pub fn with_description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
@@ -122,6 +148,20 @@ impl Activity {
     }
 }
 
+/// Cerca ricorsivamente il testo nei valori (e nelle chiavi) dei metadata
+fn metadata_contains(value: &serde_json::Value, needle: &str) -> bool {
+    match value {
+        serde_json::Value::String(s) => s.to_lowercase().contains(needle),
+        serde_json::Value::Number(n) => n.to_string().contains(needle),
+        serde_json::Value::Bool(b) => b.to_string() == needle,
+        serde_json::Value::Array(items) => items.iter().any(|v| metadata_contains(v, needle)),
+        serde_json::Value::Object(map) => map.iter().any(|(k, v)| {
+            k.to_lowercase().contains(needle) || metadata_contains(v, needle)
+        }),
+        serde_json::Value::Null => false,
+    }
+}
+
 /// Filtro per le attività
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ActivityFilter {
@@ -133,6 +173,9 @@ pub struct ActivityFilter {
     pub from_date: Option<DateTime<Utc>>,
     /// Data fine (inclusa)
     pub to_date: Option<DateTime<Utc>>,
+    /// Ricerca testuale (case-insensitive) su titolo, descrizione, gioco e metadata
+    #[serde(default)]
+    pub search: Option<String>,
     /// Limite risultati
     pub limit: Option<usize>,
     /// Offset per paginazione
@@ -153,3 +196,21 @@ pub struct ActivityPage {
     /// Ci sono altre pagine?
     pub has_more: bool,
 }
+
+/// Politica di retention dello storico
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Numero massimo di attività da mantenere (None = illimitato)
+    pub max_entries: Option<usize>,
+    /// Età massima in giorni (None = nessun limite)
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_entries: Some(1000),
+            max_age_days: None,
+        }
+    }
+}
@@ -5,20 +5,22 @@
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
+use chrono::{Duration, Utc};
 use parking_lot::RwLock;
-use tracing::info;
+use tracing::{info, warn};
 
-use super::models::{Activity, ActivityFilter, ActivityPage};
-
-/// Numero massimo di attività da mantenere
-const MAX_ACTIVITIES: usize = 1000;
+use super::models::{Activity, ActivityFilter, ActivityPage, RetentionPolicy};
 
 /// Storage per le attività
 pub struct ActivityStorage {
     /// Path del file di storage
     path: PathBuf,
+    /// Path del file con la politica di retention
+    retention_path: PathBuf,
     /// Cache in memoria
     activities: Arc<RwLock<Vec<Activity>>>,
+    /// Politica di retention corrente
+    retention: RwLock<RetentionPolicy>,
 }
 
 impl ActivityStorage {
@@ -45,10 +47,75 @@ impl ActivityStorage {
         
         info!("[ActivityStorage] Caricate {} attività", activities.len());
         
-        Ok(Self {
+        // Carica politica di retention
+        let retention_path = data_dir.join("activity_retention.json");
+        let retention = fs::read_to_string(&retention_path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        
+        let storage = Self {
             path,
+            retention_path,
             activities: Arc::new(RwLock::new(activities)),
-        })
+            retention: RwLock::new(retention),
+        };
+        
+        // Pruning all'avvio
+        match storage.prune() {
+            Ok(removed) if removed > 0 => {
+                info!("[ActivityStorage] Rimosse {} attività obsolete all'avvio", removed);
+            }
+            Ok(_) => {}
+            Err(e) => warn!("[ActivityStorage] Pruning all'avvio fallito: {}", e),
+        }
+        
+        Ok(storage)
+    }
+    
+    /// Politica di retention corrente
+    pub fn retention(&self) -> RetentionPolicy {
+        self.retention.read().clone()
+    }
+    
+    /// Imposta la politica di retention, la salva e applica subito il pruning.
+    /// Ritorna il numero di attività rimosse.
+    pub fn set_retention(&self, policy: RetentionPolicy) -> Result<usize, String> {
+        let json = serde_json::to_string_pretty(&policy)
+            .map_err(|e| e.to_string())?;
+        write_atomic(&self.retention_path, &json)?;
+        
+        *self.retention.write() = policy;
+        self.prune()
+    }
+    
+    /// Rimuove le attività più vecchie secondo la politica di retention.
+    /// Ritorna il numero di attività rimosse.
+    pub fn prune(&self) -> Result<usize, String> {
+        let removed = self.prune_in_memory();
+        if removed > 0 {
+            self.save()?;
+        }
+        Ok(removed)
+    }
+    
+    /// Applica la retention alla cache senza salvare
+    fn prune_in_memory(&self) -> usize {
+        let policy = self.retention.read().clone();
+        let mut activities = self.activities.write();
+        let len_before = activities.len();
+        
+        // Le attività sono ordinate dalla più recente: quelle oltre il limite
+        // sono le più vecchie
+        if let Some(days) = policy.max_age_days {
+            let cutoff = Utc::now() - Duration::days(days as i64);
+            activities.retain(|a| a.timestamp >= cutoff);
+        }
+        if let Some(max) = policy.max_entries {
+            activities.truncate(max);
+        }
+        
+        len_before - activities.len()
     }
     
     /// Aggiungi una nuova attività
@@ -57,23 +124,38 @@ impl ActivityStorage {
         
         // Aggiungi in testa (più recente prima)
         activities.insert(0, activity);
+        drop(activities);
         
-        // Limita il numero di attività
-        if activities.len() > MAX_ACTIVITIES {
-            activities.truncate(MAX_ACTIVITIES);
+        // Applica la retention
+        let removed = self.prune_in_memory();
+        if removed > 0 {
+            info!("[ActivityStorage] Rimosse {} attività obsolete", removed);
         }
         
         // Salva su disco
-        drop(activities);
         self.save()
     }
     
     /// Ottieni attività con filtri e paginazione
+    ///
+    /// Le attività vengono filtrate per riferimento: solo quelle della pagina
+    /// richiesta vengono clonate, così anche storici con migliaia di voci
+    /// non vengono duplicati in memoria.
     pub fn get(&self, filter: ActivityFilter) -> ActivityPage {
         let activities = self.activities.read();
         
+        let search = filter.search
+            .as_ref()
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty());
+        let page_size = filter.limit.unwrap_or(20).max(1);
+        let offset = filter.offset.unwrap_or(0);
+        
+        let mut total = 0;
+        let mut paginated: Vec<Activity> = Vec::with_capacity(page_size);
+        
         // Applica filtri
-        let filtered: Vec<&Activity> = activities.iter()
+        let filtered = activities.iter()
             .filter(|a| {
                 // Filtro per tipo
                 if let Some(ref t) = filter.activity_type {
@@ -99,23 +181,24 @@ impl ActivityStorage {
                         return false;
                     }
                 }
+                // Ricerca full-text
+                if let Some(ref needle) = search {
+                    if !a.matches_text(needle) {
+                        return false;
+                    }
+                }
                 true
-            })
-            .collect();
-        
-        let total = filtered.len();
-        let page_size = filter.limit.unwrap_or(20);
-        let offset = filter.offset.unwrap_or(0);
-        let page = offset / page_size;
+            });
         
-        // Applica paginazione
-        let paginated: Vec<Activity> = filtered
-            .into_iter()
-            .skip(offset)
-            .take(page_size)
-            .cloned()
-            .collect();
+        // Applica paginazione contando il totale in un solo passaggio
+        for activity in filtered {
+            if total >= offset && paginated.len() < page_size {
+                paginated.push(activity.clone());
+            }
+            total += 1;
+        }
         
+        let page = offset / page_size;
         let has_more = offset + paginated.len() < total;
         
         ActivityPage {
@@ -174,11 +257,21 @@ impl ActivityStorage {
         let activities = self.activities.read();
         let json = serde_json::to_string_pretty(&*activities)
             .map_err(|e| e.to_string())?;
-        fs::write(&self.path, json).map_err(|e| e.to_string())?;
-        Ok(())
+        write_atomic(&self.path, &json)
     }
 }
 
+/// Scrive su un file temporaneo e lo rinomina, così un crash a metà
+/// scrittura non lascia mai un file troncato
+fn write_atomic(path: &PathBuf, contents: &str) -> Result<(), String> {
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, contents).map_err(|e| e.to_string())?;
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        e.to_string()
+    })
+}
+
 impl Default for ActivityStorage {
     fn default() -> Self {
         let data_dir = dirs::data_local_dir()
@@ -70,6 +70,26 @@ pub struct AntiCheatDetection {
     pub details: HashMap<String, String>,
 }
 
+/// Errore restituito quando un anti-cheat protetto blocca l'injection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AntiCheatBlocked {
+    pub process_id: u32,
+    pub detected_systems: Vec<String>,
+    pub risk_assessment: RiskLevel,
+}
+
+impl std::fmt::Display for AntiCheatBlocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AntiCheatBlocked: {} rilevato per il processo {} (rischio {:?}). Injection rifiutata per evitare ban; usa l'override forzato solo a tuo rischio",
+            self.detected_systems.join(", "), self.process_id, self.risk_assessment
+        )
+    }
+}
+
+impl Error for AntiCheatBlocked {}
+
 #[derive(Debug)]
 pub struct AntiCheatManager {
     known_systems: HashMap<String, AntiCheatInfo>,
@@ -249,6 +269,27 @@ impl AntiCheatManager {
         !matches!(detection.recommended_mode, CompatibilityMode::Disabled)
     }
 
+    /// Gate obbligatorio prima di ogni injection: rifiuta se è presente un anti-cheat
+    /// protetto, a meno di override forzato (che viene loggato in modo evidente)
+    pub fn check_injection_gate(&self, pid: u32, force: bool) -> Result<AntiCheatDetection, Box<dyn Error>> {
+        let detection = self.detect_anti_cheat(pid)?;
+        if self.is_injection_safe(&detection) {
+            return Ok(detection);
+        }
+        
+        if force {
+            log::error!("🚨🚨 OVERRIDE FORZATO: injection nel PID {} nonostante anti-cheat {} (rischio {:?}). Possibile BAN dell'account!",
+                pid, detection.detected_systems.join(", "), detection.risk_assessment);
+            return Ok(detection);
+        }
+        
+        Err(Box::new(AntiCheatBlocked {
+            process_id: pid,
+            detected_systems: detection.detected_systems.clone(),
+            risk_assessment: detection.risk_assessment.clone(),
+        }))
+    }
+
     #[allow(dead_code)] // Calcolo delay injection - essenziale per timing sicuro
     pub fn get_injection_delay(&self, detection: &AntiCheatDetection) -> Option<u64> {
         match detection.recommended_mode {
@@ -0,0 +1,442 @@
+// 📖 Parser di appcache/appinfo.vdf (KeyValues binario di Steam)
+// Steam tiene qui i metadati di tutte le app note all'account: nome, tipo, cartella di
+// installazione, eseguibili. Leggerlo permette di risolvere i nomi anche offline.
+// Versioni supportate: 27, 28 (aggiunge lo SHA1 dei dati binari) e 29 (chiavi in una
+// tabella di stringhe in fondo al file). Le voci corrotte vengono saltate usando la
+// dimensione dichiarata, così un file parzialmente scritto resta leggibile.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use crate::vdf::{VdfObject, VdfValue};
+
+const MAGIC_V27: u32 = 0x0756_4427;
+const MAGIC_V28: u32 = 0x0756_4428;
+const MAGIC_V29: u32 = 0x0756_4429;
+
+// Tipi dei nodi KeyValues binari
+const TYPE_OBJECT: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT32: u8 = 0x02;
+const TYPE_FLOAT32: u8 = 0x03;
+const TYPE_POINTER: u8 = 0x04;
+const TYPE_WIDESTRING: u8 = 0x05;
+const TYPE_COLOR: u8 = 0x06;
+const TYPE_UINT64: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+const TYPE_INT64: u8 = 0x0A;
+const TYPE_END_ALT: u8 = 0x0B;
+
+/// Metadati di un'app letti da appinfo.vdf
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppInfo {
+    pub appid: u32,
+    pub name: Option<String>,
+    /// Tipo in minuscolo: "game", "dlc", "tool", "application", "demo", ...
+    pub app_type: Option<String>,
+    pub parent_appid: Option<u32>,
+    pub install_dir: Option<String>,
+    /// Eseguibile della prima configurazione di avvio Windows (relativo a install_dir)
+    pub executable: Option<String>,
+    pub last_updated: u32,
+    pub change_number: u32,
+}
+
+impl AppInfo {
+    pub fn is_game(&self) -> bool {
+        matches!(self.app_type.as_deref(), None | Some("game"))
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or("Fine dati inattesa")?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn cstring(&mut self) -> Result<String, String> {
+        let rest = &self.data[self.pos..];
+        let len = rest.iter().position(|b| *b == 0).ok_or("Stringa non terminata")?;
+        let value = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.pos += len + 1;
+        Ok(value)
+    }
+
+    fn wide_cstring(&mut self) -> Result<String, String> {
+        let mut units = Vec::new();
+        loop {
+            let unit = u16::from_le_bytes(self.take(2)?.try_into().unwrap());
+            if unit == 0 {
+                return Ok(String::from_utf16_lossy(&units));
+            }
+            units.push(unit);
+        }
+    }
+}
+
+/// Legge un albero KeyValues binario; con `keys` (v29) le chiavi sono indici nella tabella
+fn read_binary_object(reader: &mut Reader, keys: Option<&[String]>) -> Result<VdfObject, String> {
+    let mut object = VdfObject::default();
+
+    loop {
+        let kind = reader.u8()?;
+        if kind == TYPE_END || kind == TYPE_END_ALT {
+            return Ok(object);
+        }
+
+        let key = match keys {
+            Some(table) => {
+                let index = reader.u32()? as usize;
+                table.get(index).cloned().ok_or_else(|| format!("Indice chiave non valido: {}", index))?
+            }
+            None => reader.cstring()?,
+        };
+
+        let value = match kind {
+            TYPE_OBJECT => VdfValue::Obj(read_binary_object(reader, keys)?),
+            TYPE_STRING => VdfValue::Str(reader.cstring()?),
+            TYPE_WIDESTRING => VdfValue::Str(reader.wide_cstring()?),
+            TYPE_INT32 | TYPE_POINTER | TYPE_COLOR => VdfValue::Str((reader.u32()? as i32).to_string()),
+            TYPE_FLOAT32 => VdfValue::Str(f32::from_bits(reader.u32()?).to_string()),
+            TYPE_UINT64 => VdfValue::Str(reader.u64()?.to_string()),
+            TYPE_INT64 => VdfValue::Str((reader.u64()? as i64).to_string()),
+            other => return Err(format!("Tipo KeyValues sconosciuto: 0x{:02x}", other)),
+        };
+        object.push(key, value);
+    }
+}
+
+/// Tabella delle chiavi (solo v29): u32 conteggio + stringhe terminate da zero
+fn read_key_table(data: &[u8], offset: u64) -> Result<Vec<String>, String> {
+    let offset = usize::try_from(offset).ok()
+        .filter(|o| *o < data.len())
+        .ok_or("Offset tabella stringhe non valido")?;
+    let mut reader = Reader::new(&data[offset..]);
+    let count = reader.u32()?;
+    (0..count).map(|_| reader.cstring()).collect()
+}
+
+fn app_info_from_tree(appid: u32, last_updated: u32, change_number: u32, tree: &VdfObject) -> AppInfo {
+    let root = tree.get_obj("appinfo").unwrap_or(tree);
+    let common = root.get_obj("common");
+    let config = root.get_obj("config");
+    let text = |obj: Option<&VdfObject>, key: &str| {
+        obj.and_then(|o| o.get_str(key))
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+
+    // Prima configurazione di avvio per Windows (o senza oslist)
+    let executable = config
+        .and_then(|c| c.get_obj("launch"))
+        .and_then(|launch| {
+            launch.iter()
+                .filter_map(|(_, entry)| entry.as_obj())
+                .find(|entry| runs_on_windows(entry.get_obj("config").and_then(|c| c.get_str("oslist"))))
+                .and_then(|entry| entry.get_str("executable"))
+                .filter(|exe| !exe.is_empty())
+                .map(str::to_string)
+        });
+
+    AppInfo {
+        appid,
+        name: text(common, "name"),
+        app_type: text(common, "type").map(|t| t.to_lowercase()),
+        parent_appid: common.and_then(|c| c.get_u64("parent")).and_then(|p| u32::try_from(p).ok()),
+        install_dir: text(config, "installdir"),
+        executable,
+        last_updated,
+        change_number,
+    }
+}
+
+/// Configurazione di avvio valida su Windows (oslist assente o contenente "windows")
+fn runs_on_windows(oslist: Option<&str>) -> bool {
+    match oslist {
+        None => true,
+        Some(list) => list.is_empty() || list.split(',').any(|os| os.trim().eq_ignore_ascii_case("windows")),
+    }
+}
+
+/// Parsa il contenuto di appinfo.vdf
+pub fn parse_appinfo(data: &[u8]) -> Result<Vec<AppInfo>, String> {
+    let mut reader = Reader::new(data);
+    let magic = reader.u32()?;
+    let _universe = reader.u32()?;
+
+    let (has_data_sha1, keys) = match magic {
+        MAGIC_V27 => (false, None),
+        MAGIC_V28 => (true, None),
+        MAGIC_V29 => {
+            let table_offset = reader.u64()?;
+            (true, Some(read_key_table(data, table_offset)?))
+        }
+        other => return Err(format!("Versione appinfo.vdf non supportata (magic 0x{:08x})", other)),
+    };
+
+    let mut apps = Vec::new();
+    let mut skipped = 0;
+
+    loop {
+        let appid = match reader.u32() {
+            Ok(0) => break,
+            Ok(appid) => appid,
+            // File troncato: teniamo quanto letto finora
+            Err(_) => break,
+        };
+        let Ok(size) = reader.u32() else { break };
+        let Ok(entry) = reader.take(size as usize) else {
+            warn!("[APPINFO] ⚠️ Voce {} troncata, lettura interrotta", appid);
+            break;
+        };
+
+        let mut entry_reader = Reader::new(entry);
+        let parsed = (|| {
+            let _info_state = entry_reader.u32()?;
+            let last_updated = entry_reader.u32()?;
+            let _access_token = entry_reader.u64()?;
+            let _sha1 = entry_reader.take(20)?;
+            let change_number = entry_reader.u32()?;
+            if has_data_sha1 {
+                entry_reader.take(20)?;
+            }
+            let tree = read_binary_object(&mut entry_reader, keys.as_deref())?;
+            Ok::<_, String>(app_info_from_tree(appid, last_updated, change_number, &tree))
+        })();
+
+        match parsed {
+            Ok(info) => apps.push(info),
+            Err(_) => skipped += 1,
+        }
+    }
+
+    if skipped > 0 {
+        warn!("[APPINFO] ⚠️ {} voci non leggibili saltate", skipped);
+    }
+    Ok(apps)
+}
+
+pub fn appinfo_path(steam_path: &Path) -> PathBuf {
+    steam_path.join("appcache").join("appinfo.vdf")
+}
+
+type AppInfoMap = Arc<HashMap<u32, AppInfo>>;
+
+// Ultimo appinfo.vdf letto, invalidato quando cambia la data di modifica
+static APPINFO_CACHE: Lazy<Mutex<Option<(PathBuf, SystemTime, AppInfoMap)>>> = Lazy::new(|| Mutex::new(None));
+
+/// Metadati per appid dall'appinfo.vdf di Steam (vuoto se il file manca o è illeggibile)
+pub fn load_app_infos(steam_path: &Path) -> AppInfoMap {
+    let path = appinfo_path(steam_path);
+    let Some(modified) = std::fs::metadata(&path).and_then(|m| m.modified()).ok() else {
+        return AppInfoMap::default();
+    };
+
+    let mut cache = APPINFO_CACHE.lock();
+    if let Some((cached_path, cached_modified, apps)) = cache.as_ref() {
+        if *cached_path == path && *cached_modified == modified {
+            return apps.clone();
+        }
+    }
+
+    let apps: HashMap<u32, AppInfo> = match std::fs::read(&path).map_err(|e| e.to_string()).and_then(|data| parse_appinfo(&data)) {
+        Ok(apps) => apps.into_iter().map(|app| (app.appid, app)).collect(),
+        Err(e) => {
+            warn!("[APPINFO] ⚠️ Impossibile leggere {}: {}", path.display(), e);
+            return AppInfoMap::default();
+        }
+    };
+    info!("[APPINFO] 📖 Letti metadati di {} app da appinfo.vdf", apps.len());
+
+    let apps = Arc::new(apps);
+    *cache = Some((path, modified, apps.clone()));
+    apps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Costruisce un nodo binario con chiavi testuali (v27/v28) o indicizzate (v29)
+    enum Node<'a> {
+        Str(&'a str, &'a str),
+        Int(&'a str, i32),
+        Obj(&'a str, Vec<Node<'a>>),
+    }
+
+    fn write_key(key: &str, keys: &mut Option<Vec<String>>, out: &mut Vec<u8>) {
+        match keys {
+            Some(table) => {
+                let index = table.iter().position(|k| k == key).unwrap_or_else(|| {
+                    table.push(key.to_string());
+                    table.len() - 1
+                });
+                out.extend((index as u32).to_le_bytes());
+            }
+            None => {
+                out.extend(key.as_bytes());
+                out.push(0);
+            }
+        }
+    }
+
+    fn encode(nodes: &[Node], keys: &mut Option<Vec<String>>, out: &mut Vec<u8>) {
+        for node in nodes {
+            match node {
+                Node::Str(key, value) => {
+                    out.push(TYPE_STRING);
+                    write_key(key, keys, out);
+                    out.extend(value.as_bytes());
+                    out.push(0);
+                }
+                Node::Int(key, value) => {
+                    out.push(TYPE_INT32);
+                    write_key(key, keys, out);
+                    out.extend(value.to_le_bytes());
+                }
+                Node::Obj(key, children) => {
+                    out.push(TYPE_OBJECT);
+                    write_key(key, keys, out);
+                    encode(children, keys, out);
+                }
+            }
+        }
+        out.push(TYPE_END);
+    }
+
+    fn sample_tree(name: &str) -> Vec<Node<'_>> {
+        vec![Node::Obj("appinfo", vec![
+            Node::Int("appid", 620),
+            Node::Obj("common", vec![
+                Node::Str("name", name),
+                Node::Str("type", "Game"),
+            ]),
+            Node::Obj("config", vec![
+                Node::Str("installdir", "Portal 2"),
+                Node::Obj("launch", vec![
+                    Node::Obj("0", vec![
+                        Node::Str("executable", "portal2.sh"),
+                        Node::Obj("config", vec![Node::Str("oslist", "linux")]),
+                    ]),
+                    Node::Obj("1", vec![
+                        Node::Str("executable", "portal2.exe"),
+                        Node::Obj("config", vec![Node::Str("oslist", "windows")]),
+                    ]),
+                ]),
+            ]),
+        ])]
+    }
+
+    fn build_file(magic: u32, apps: &[(u32, &str)]) -> Vec<u8> {
+        let mut keys = (magic == MAGIC_V29).then(Vec::new);
+        let mut body = Vec::new();
+
+        for (appid, name) in apps {
+            let mut entry = Vec::new();
+            entry.extend(2u32.to_le_bytes()); // info_state
+            entry.extend(1_700_000_000u32.to_le_bytes()); // last_updated
+            entry.extend(0u64.to_le_bytes()); // access token
+            entry.extend([0u8; 20]); // sha1
+            entry.extend(42u32.to_le_bytes()); // change number
+            if magic != MAGIC_V27 {
+                entry.extend([0u8; 20]); // sha1 dati binari
+            }
+            encode(&sample_tree(name), &mut keys, &mut entry);
+
+            body.extend(appid.to_le_bytes());
+            body.extend((entry.len() as u32).to_le_bytes());
+            body.extend(entry);
+        }
+        body.extend(0u32.to_le_bytes());
+
+        let mut file = Vec::new();
+        file.extend(magic.to_le_bytes());
+        file.extend(1u32.to_le_bytes());
+        if let Some(table) = keys {
+            let header_len = 8 + 8;
+            file.extend(((header_len + body.len()) as u64).to_le_bytes());
+            file.extend(body);
+            file.extend((table.len() as u32).to_le_bytes());
+            for key in table {
+                file.extend(key.as_bytes());
+                file.push(0);
+            }
+        } else {
+            file.extend(body);
+        }
+        file
+    }
+
+    #[test]
+    fn parses_all_supported_versions() {
+        for magic in [MAGIC_V27, MAGIC_V28, MAGIC_V29] {
+            let apps = parse_appinfo(&build_file(magic, &[(620, "Portal 2"), (400, "Portal")])).unwrap();
+            assert_eq!(apps.len(), 2, "magic 0x{:08x}", magic);
+            let portal2 = &apps[0];
+            assert_eq!(portal2.appid, 620);
+            assert_eq!(portal2.name.as_deref(), Some("Portal 2"));
+            assert_eq!(portal2.app_type.as_deref(), Some("game"));
+            assert_eq!(portal2.install_dir.as_deref(), Some("Portal 2"));
+            assert_eq!(portal2.executable.as_deref(), Some("portal2.exe"));
+            assert_eq!(portal2.change_number, 42);
+            assert!(portal2.is_game());
+            assert_eq!(apps[1].name.as_deref(), Some("Portal"));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let mut data = build_file(MAGIC_V28, &[(620, "Portal 2")]);
+        data[0] = 0x26;
+        assert!(parse_appinfo(&data).is_err());
+    }
+
+    #[test]
+    fn keeps_apps_read_before_truncation() {
+        let data = build_file(MAGIC_V28, &[(620, "Portal 2"), (400, "Portal")]);
+        let apps = parse_appinfo(&data[..data.len() - 30]).unwrap();
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].appid, 620);
+    }
+
+    #[test]
+    fn skips_corrupted_entries() {
+        let mut data = build_file(MAGIC_V28, &[(620, "Portal 2"), (400, "Portal")]);
+        // Tipo di nodo non valido all'inizio dell'albero della prima voce
+        let tree_start = 8 + 8 + 60;
+        data[tree_start] = 0x42;
+        let apps = parse_appinfo(&data).unwrap();
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps[0].appid, 400);
+    }
+}
@@ -3,11 +3,12 @@
 //! Espone le funzionalità dello storico attività al frontend.
 
 use std::sync::Arc;
+use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
-use crate::activity_history::{Activity, ActivityFilter, ActivityPage, ActivityStorage, ActivityType};
+use crate::activity_history::{Activity, ActivityFilter, ActivityPage, ActivityStorage, ActivityType, RetentionPolicy};
 
 /// Stato globale dell'Activity Storage
 pub struct ActivityHistoryState {
@@ -72,10 +73,22 @@ pub struct AddActivityParams {
 pub struct FilterParams {
     pub activity_type: Option<String>,
     pub game_id: Option<String>,
+    /// Data inizio (RFC 3339)
+    pub from_date: Option<String>,
+    /// Data fine (RFC 3339)
+    pub to_date: Option<String>,
+    /// Testo da cercare in titolo, descrizione e metadata
+    pub search: Option<String>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 }
 
+fn parse_date(s: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|d| d.with_timezone(&Utc))
+        .map_err(|e| format!("Data non valida '{}': {}", s, e))
+}
+
 fn parse_activity_type(s: &str) -> ActivityType {
     match s.to_lowercase().as_str() {
         "translation" => ActivityType::Translation,
@@ -126,11 +139,21 @@ pub async fn activity_get(
     state: State<'_, ActivityHistoryState>,
     params: FilterParams,
 ) -> Result<ActivityResponse<ActivityPage>, String> {
+    let from_date = match params.from_date.as_deref().map(parse_date).transpose() {
+        Ok(d) => d,
+        Err(e) => return Ok(ActivityResponse::err(e)),
+    };
+    let to_date = match params.to_date.as_deref().map(parse_date).transpose() {
+        Ok(d) => d,
+        Err(e) => return Ok(ActivityResponse::err(e)),
+    };
+    
     let filter = ActivityFilter {
         activity_type: params.activity_type.map(|s| parse_activity_type(&s)),
         game_id: params.game_id,
-        from_date: None,
-        to_date: None,
+        from_date,
+        to_date,
+        search: params.search,
         limit: params.limit,
         offset: params.offset,
     };
@@ -188,3 +211,32 @@ pub async fn activity_clear(
         Err(e) => Ok(ActivityResponse::err(e)),
     }
 }
+
+/// Imposta la politica di retention dello storico.
+/// Ritorna il numero di attività rimosse dal pruning immediato.
+#[tauri::command]
+pub async fn set_activity_retention(
+    state: State<'_, ActivityHistoryState>,
+    max_entries: Option<usize>,
+    max_age_days: Option<u32>,
+) -> Result<ActivityResponse<usize>, String> {
+    let policy = RetentionPolicy {
+        max_entries,
+        max_age_days,
+    };
+    
+    let storage = state.storage.lock();
+    match storage.set_retention(policy) {
+        Ok(removed) => Ok(ActivityResponse::ok(removed)),
+        Err(e) => Ok(ActivityResponse::err(e)),
+    }
+}
+
+/// Ottieni la politica di retention corrente
+#[tauri::command]
+pub async fn get_activity_retention(
+    state: State<'_, ActivityHistoryState>,
+) -> Result<ActivityResponse<RetentionPolicy>, String> {
+    let storage = state.storage.lock();
+    Ok(ActivityResponse::ok(storage.retention()))
+}
@@ -6,6 +6,7 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use crate::commands::library::InstalledGame;
+use crate::commands::store_detection::{ConnectionCheck, StoreConnectionState};
 use log::{info, warn};
 
 // ============================================================================
@@ -282,15 +283,21 @@ pub async fn is_amazon_games_installed() -> bool {
     get_amazon_db_path().is_some()
 }
 
-/// Test connessione Amazon Games
-#[tauri::command]
-pub async fn test_amazon_connection() -> Result<String, String> {
+/// Test connessione Amazon Games, con esito strutturato per la dashboard degli store
+pub(crate) async fn check_amazon_connection() -> Result<ConnectionCheck, String> {
     if let Some(_db_path) = get_amazon_db_path() {
         match get_amazon_installed_games().await {
-            Ok(games) => Ok(format!("✅ Amazon Games trovato - {} giochi installati", games.len())),
+            Ok(games) => Ok(ConnectionCheck::new(StoreConnectionState::Connected, format!("✅ Amazon Games trovato - {} giochi installati", games.len()))
+                .with_local_games(games.len())),
             Err(e) => Err(format!("❌ Errore lettura database: {}", e))
         }
     } else {
         Err("Amazon Games non installato o database non trovato".to_string())
     }
 }
+
+/// Test connessione Amazon Games
+#[tauri::command]
+pub async fn test_amazon_connection() -> Result<String, String> {
+    check_amazon_connection().await.map(|check| check.message)
+}
@@ -5,6 +5,7 @@ use std::fs;
 use winreg::enums::*;
 use winreg::RegKey;
 use crate::commands::library::InstalledGame;
+use crate::commands::store_detection::{ConnectionCheck, StoreConnectionState};
 use std::path::PathBuf;
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
 use aes_gcm::aead::Aead;
@@ -21,6 +22,22 @@ pub struct BattlenetGame {
     pub platform: String, // "Battle.net"
     pub size_bytes: Option<u64>,
     pub last_modified: Option<u64>,
+    /// Codice prodotto Battle.net (es. "wow", "pro", "fenris"), se letto da product.db
+    pub product_code: Option<String>,
+    /// Lingua del testo selezionata nel client (es. "itIT")
+    pub text_language: Option<String>,
+}
+
+/// Prodotto installato letto da `product.db` dell'agent Battle.net
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BattlenetProduct {
+    /// UID dell'installazione (es. "wow", "wow_classic", "hs_beta")
+    pub uid: String,
+    pub product_code: String,
+    pub name: String,
+    pub install_path: String,
+    pub text_language: Option<String>,
+    pub executable: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -62,6 +79,11 @@ struct BattlenetApiGame {
 pub async fn get_battlenet_installed_games() -> Result<Vec<InstalledGame>, String> {
     let mut games = Vec::new();
     
+    // 0. Database prodotti dell'agent (fonte più affidabile: percorsi e codici reali)
+    if let Ok(products) = read_battlenet_products().await {
+        games.extend(products.iter().map(product_to_installed_game));
+    }
+    
     // 1. Scansiona giochi Battle.net dal registro
     if let Ok(battlenet_games) = scan_battlenet_registry().await {
         games.extend(battlenet_games);
@@ -77,12 +99,13 @@ pub async fn get_battlenet_installed_games() -> Result<Vec<InstalledGame>, Strin
         games.extend(blizzard_games);
     }
     
-    // Rimuovi duplicati basandosi sul nome del gioco
+    // Rimuovi duplicati per cartella di installazione: lo stesso gioco trovato da più fonti
+    // ha nomi diversi, due installazioni distinte (es. PTR) hanno lo stesso nome
     let mut unique_games = Vec::new();
-    let mut seen_names = std::collections::HashSet::new();
+    let mut seen_paths = std::collections::HashSet::new();
     
     for game in games {
-        if seen_names.insert(game.name.clone()) {
+        if seen_paths.insert(install_path_key(&game)) {
             unique_games.push(game);
         }
     }
@@ -90,6 +113,17 @@ pub async fn get_battlenet_installed_games() -> Result<Vec<InstalledGame>, Strin
     Ok(unique_games)
 }
 
+/// Chiave di deduplica: percorso normalizzato (separatori, maiuscole, slash finali);
+/// il nome solo per voci senza percorso
+fn install_path_key(game: &InstalledGame) -> String {
+    let path = game.path.trim().replace('\\', "/").trim_end_matches('/').to_lowercase();
+    if path.is_empty() {
+        format!("name:{}", game.name.to_lowercase())
+    } else {
+        path
+    }
+}
+
 /// Scansiona giochi Battle.net dal registro
 async fn scan_battlenet_registry() -> Result<Vec<InstalledGame>, String> {
     let mut games = Vec::new();
@@ -218,9 +252,8 @@ async fn scan_blizzard_specific_games() -> Result<Vec<InstalledGame>, String> {
     Ok(games)
 }
 
-/// Test della connessione Battle.net
-#[tauri::command]
-pub async fn test_battlenet_connection() -> Result<String, String> {
+/// Test della connessione Battle.net, con esito strutturato per la dashboard degli store
+pub(crate) async fn check_battlenet_connection() -> Result<ConnectionCheck, String> {
     println!("[BATTLENET] Test connessione");
     
     // Prima prova a caricare credenziali salvate
@@ -236,36 +269,66 @@ pub async fn test_battlenet_connection() -> Result<String, String> {
                 match test_battlenet_auth(email, password).await {
                     Ok(user) => {
                         let games = get_battlenet_installed_games().await?;
-                        Ok(format!("✅ Connesso come '{}' - {} giochi locali trovati", 
-                                  user.username, games.len()))
+                        Ok(ConnectionCheck::new(StoreConnectionState::Connected, format!("✅ Connesso come '{}' - {} giochi locali trovati", 
+                                  user.username, games.len())).with_local_games(games.len()))
                     }
                     Err(e) => {
                         println!("[BATTLENET] Errore autenticazione salvata: {}", e);
                         // Fallback a scansione locale
                         let games = get_battlenet_installed_games().await?;
-                        Ok(format!("❌ Account disconnesso (credenziali non valide) - {} giochi locali trovati", games.len()))
+                        Ok(ConnectionCheck::new(StoreConnectionState::InvalidCredentials, format!("❌ Account disconnesso (credenziali non valide) - {} giochi locali trovati", games.len())).with_local_games(games.len()))
                     }
                 }
             } else {
                 // Fallback a scansione locale
                 let games = get_battlenet_installed_games().await?;
-                Ok(format!("Scansione Battle.net completata: {} giochi trovati", games.len()))
+                Ok(ConnectionCheck::new(StoreConnectionState::LocalOnly, format!("Scansione Battle.net completata: {} giochi trovati", games.len())).with_local_games(games.len()))
             }
         }
         Err(_) => {
             // Nessuna credenziale salvata - scansione locale
             println!("[BATTLENET] Nessuna credenziale salvata");
             let games = get_battlenet_installed_games().await?;
-            Ok(format!("Scansione Battle.net completata: {} giochi trovati", games.len()))
+            Ok(ConnectionCheck::new(StoreConnectionState::LocalOnly, format!("Scansione Battle.net completata: {} giochi trovati", games.len())).with_local_games(games.len()))
         }
     }
 }
 
-/// Recupera informazioni su un gioco Battle.net specifico
+/// Test della connessione Battle.net
+#[tauri::command]
+pub async fn test_battlenet_connection() -> Result<String, String> {
+    check_battlenet_connection().await.map(|check| check.message)
+}
+
+/// Recupera informazioni su un gioco Battle.net specifico.
+/// `game_id` può essere l'id della libreria ("battlenet_wow"), l'UID o il codice prodotto.
 #[tauri::command]
 pub async fn get_battlenet_game_info(game_id: String) -> Result<BattlenetGame, String> {
     println!("[BATTLENET] Recupero informazioni per: {}", game_id);
     
+    if !is_battlenet_installed() {
+        return Err("Battle.net non è installato su questo sistema".to_string());
+    }
+    
+    let wanted = game_id.trim_start_matches("battlenet_").to_lowercase();
+    if let Ok(products) = read_battlenet_products().await {
+        if let Some(product) = products.iter().find(|p| p.uid.to_lowercase() == wanted || p.product_code.to_lowercase() == wanted) {
+            let game = product_to_installed_game(product);
+            return Ok(BattlenetGame {
+                id: game.id,
+                title: game.name,
+                install_path: Some(game.path),
+                executable: game.executable,
+                platform: game.platform,
+                size_bytes: game.size_bytes,
+                last_modified: game.last_modified,
+                product_code: Some(product.product_code.clone()),
+                text_language: product.text_language.clone(),
+            });
+        }
+    }
+    
+    // Fallback: registro e cartelle note
     let games = get_battlenet_installed_games().await?;
     
     for game in games {
@@ -278,6 +341,8 @@ pub async fn get_battlenet_game_info(game_id: String) -> Result<BattlenetGame, S
                 platform: game.platform,
                 size_bytes: game.size_bytes,
                 last_modified: game.last_modified,
+                product_code: None,
+                text_language: None,
             });
         }
     }
@@ -285,6 +350,20 @@ pub async fn get_battlenet_game_info(game_id: String) -> Result<BattlenetGame, S
     Err(format!("Gioco '{}' non trovato", game_id))
 }
 
+/// Elenca i prodotti Blizzard installati leggendo `product.db` dell'agent Battle.net
+#[tauri::command]
+pub async fn get_battlenet_installed_products() -> Result<Vec<BattlenetProduct>, String> {
+    println!("[BATTLENET] Lettura product.db");
+    
+    if !is_battlenet_installed() {
+        return Err("Battle.net non è installato su questo sistema".to_string());
+    }
+    
+    let products = read_battlenet_products().await?;
+    println!("[BATTLENET] ✅ {} prodotti installati trovati", products.len());
+    Ok(products)
+}
+
 /// Recupera le copertine per i giochi Battle.net (placeholder)
 #[tauri::command]
 pub async fn get_battlenet_covers_batch(game_ids: Vec<String>) -> Result<HashMap<String, String>, String> {
@@ -300,6 +379,243 @@ pub async fn get_battlenet_covers_batch(game_ids: Vec<String>) -> Result<HashMap
 
 // Funzioni helper private
 
+/// Percorso di `product.db` dell'agent Battle.net (%ProgramData%\Battle.net\Agent)
+fn battlenet_product_db_path() -> PathBuf {
+    let program_data = std::env::var("ProgramData").unwrap_or_else(|_| r"C:\ProgramData".to_string());
+    Path::new(&program_data).join("Battle.net").join("Agent").join("product.db")
+}
+
+/// Battle.net è installato se esiste il database dell'agent, la chiave di registro o il client
+fn is_battlenet_installed() -> bool {
+    if battlenet_product_db_path().exists() {
+        return true;
+    }
+    
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let registry_found = [
+        "SOFTWARE\\WOW6432Node\\Blizzard Entertainment\\Battle.net",
+        "SOFTWARE\\Blizzard Entertainment\\Battle.net",
+    ].iter().any(|key| hklm.open_subkey(key).is_ok());
+    
+    registry_found || [
+        r"C:\Program Files (x86)\Battle.net\Battle.net.exe",
+        r"C:\Program Files\Battle.net\Battle.net.exe",
+    ].iter().any(|p| Path::new(p).exists())
+}
+
+/// Nome leggibile di un codice prodotto Battle.net
+fn battlenet_product_name(code: &str) -> Option<&'static str> {
+    let name = match code.to_lowercase().as_str() {
+        "wow" => "World of Warcraft",
+        "wow_classic" => "World of Warcraft Classic",
+        "wow_classic_era" => "World of Warcraft Classic Era",
+        "wow_beta" | "wowt" => "World of Warcraft (PTR/Beta)",
+        "pro" => "Overwatch 2",
+        "hs_beta" => "Hearthstone",
+        "s1" => "StarCraft Remastered",
+        "s2" => "StarCraft II",
+        "d3" => "Diablo III",
+        "fenris" => "Diablo IV",
+        "osi" => "Diablo II: Resurrected",
+        "anbs" => "Diablo Immortal",
+        "hero" => "Heroes of the Storm",
+        "w3" => "Warcraft III: Reforged",
+        "rtro" => "Blizzard Arcade Collection",
+        "wlby" => "Crash Bandicoot 4: It's About Time",
+        "viper" => "Call of Duty: Black Ops 4",
+        "odin" => "Call of Duty: Modern Warfare",
+        "zeus" => "Call of Duty: Black Ops Cold War",
+        "fore" => "Call of Duty: Vanguard",
+        "auks" => "Call of Duty",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Legge e decodifica `product.db` (protobuf) dell'agent Battle.net
+async fn read_battlenet_products() -> Result<Vec<BattlenetProduct>, String> {
+    let db_path = battlenet_product_db_path();
+    let data = fs::read(&db_path)
+        .map_err(|e| format!("Impossibile leggere {}: {}", db_path.display(), e))?;
+    
+    let mut products = Vec::new();
+    for install in parse_product_db(&data)? {
+        // Il client stesso e voci senza percorso non sono giochi
+        if install.install_path.is_empty() || matches!(install.product_code.as_str(), "agent" | "bna" | "battle.net") {
+            continue;
+        }
+        
+        let game_path = Path::new(&install.install_path);
+        if !game_path.exists() {
+            continue;
+        }
+        
+        let name = battlenet_product_name(&install.uid)
+            .or_else(|| battlenet_product_name(&install.product_code))
+            .map(str::to_string)
+            .or_else(|| game_path.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| install.uid.clone());
+        
+        products.push(BattlenetProduct {
+            executable: find_main_executable(game_path).await,
+            uid: install.uid,
+            product_code: install.product_code,
+            name,
+            install_path: install.install_path,
+            text_language: install.text_language,
+        });
+    }
+    
+    Ok(products)
+}
+
+fn product_to_installed_game(product: &BattlenetProduct) -> InstalledGame {
+    let metadata = Path::new(&product.install_path).metadata().ok();
+    
+    InstalledGame {
+        id: format!("battlenet_{}", product.uid.to_lowercase()),
+        name: product.name.clone(),
+        path: product.install_path.clone(),
+        executable: product.executable.clone(),
+        size_bytes: metadata.as_ref().map(|m| m.len()),
+        last_modified: metadata.and_then(|m| {
+            m.modified().ok().and_then(|t| {
+                t.duration_since(std::time::UNIX_EPOCH).ok().map(|d| d.as_secs())
+            })
+        }),
+        platform: "Battle.net".to_string(),
+    }
+}
+
+/// Voce `ProductInstall` di product.db (solo i campi che ci servono)
+struct ProductInstall {
+    uid: String,
+    product_code: String,
+    install_path: String,
+    text_language: Option<String>,
+}
+
+/// Decodifica il messaggio `Database` di product.db:
+/// Database { repeated ProductInstall product_install = 1; ... }
+/// ProductInstall { string uid = 1; string product_code = 2; UserSettings settings = 3; ... }
+/// UserSettings { string install_path = 1; ...; string selected_text_language = 6; ... }
+fn parse_product_db(data: &[u8]) -> Result<Vec<ProductInstall>, String> {
+    let mut installs = Vec::new();
+    
+    for field in ProtoReader::new(data) {
+        let (1, ProtoValue::Bytes(install_bytes)) = field? else { continue };
+        
+        let mut install = ProductInstall {
+            uid: String::new(),
+            product_code: String::new(),
+            install_path: String::new(),
+            text_language: None,
+        };
+        
+        for install_field in ProtoReader::new(install_bytes) {
+            match install_field? {
+                (1, ProtoValue::Bytes(b)) => install.uid = String::from_utf8_lossy(b).to_string(),
+                (2, ProtoValue::Bytes(b)) => install.product_code = String::from_utf8_lossy(b).to_string(),
+                (3, ProtoValue::Bytes(settings)) => {
+                    for settings_field in ProtoReader::new(settings) {
+                        match settings_field? {
+                            (1, ProtoValue::Bytes(b)) => install.install_path = String::from_utf8_lossy(b).to_string(),
+                            (6, ProtoValue::Bytes(b)) if !b.is_empty() => {
+                                install.text_language = Some(String::from_utf8_lossy(b).to_string());
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        
+        if !install.uid.is_empty() {
+            installs.push(install);
+        }
+    }
+    
+    Ok(installs)
+}
+
+/// Valore di un campo protobuf (wire format); gli scalari vengono solo saltati
+enum ProtoValue<'a> {
+    Scalar,
+    Bytes(&'a [u8]),
+}
+
+/// Lettore minimale del wire format protobuf: itera (numero campo, valore)
+struct ProtoReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ProtoReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+    
+    fn read_varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = *self.data.get(self.pos).ok_or("product.db troncato (varint)")?;
+            self.pos += 1;
+            // Il decimo byte può portare solo il bit 63
+            if shift == 63 && byte & 0x7E != 0 {
+                return Err("product.db non valido (varint oltre 64 bit)".to_string());
+            }
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("product.db non valido (varint troppo lungo)".to_string())
+    }
+    
+    fn read_field(&mut self) -> Result<(u32, ProtoValue<'a>), String> {
+        let key = self.read_varint()?;
+        let field = (key >> 3) as u32;
+        let value = match key & 0x7 {
+            0 => { self.read_varint()?; ProtoValue::Scalar }
+            1 => { self.take(8)?; ProtoValue::Scalar }
+            2 => {
+                let len = self.read_varint()? as usize;
+                ProtoValue::Bytes(self.take(len)?)
+            }
+            5 => { self.take(4)?; ProtoValue::Scalar }
+            wire => return Err(format!("product.db: wire type {} non supportato", wire)),
+        };
+        Ok((field, value))
+    }
+    
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.data.len())
+            .ok_or("product.db troncato")?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+}
+
+impl<'a> Iterator for ProtoReader<'a> {
+    type Item = Result<(u32, ProtoValue<'a>), String>;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.data.len() {
+            return None;
+        }
+        
+        let result = self.read_field();
+        
+        // Dopo un errore non ha senso continuare a leggere
+        if result.is_err() {
+            self.pos = self.data.len();
+        }
+        Some(result)
+    }
+}
+
 async fn parse_battlenet_registry_entry(game_key: &RegKey, game_id: &str) -> Result<InstalledGame, String> {
     // Prova a leggere diversi campi possibili per Battle.net
     let name = game_key.get_value::<String, _>("DisplayName")
@@ -458,7 +774,7 @@ async fn find_main_executable(game_path: &Path) -> Option<String> {
 
 // Funzioni per gestione credenziali Battle.net
 
-fn get_battlenet_credentials_path() -> Result<PathBuf, String> {
+pub(crate) fn get_battlenet_credentials_path() -> Result<PathBuf, String> {
     let mut path = std::env::current_dir()
         .map_err(|e| format!("Errore getting current dir: {}", e))?;
     path.push(".cache");
@@ -708,3 +1024,123 @@ pub async fn disconnect_battlenet() -> Result<String, String> {
     
     Ok("Battle.net disconnesso con successo".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn varint(mut value: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return out;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn bytes_field(field: u32, payload: &[u8]) -> Vec<u8> {
+        let mut out = varint(u64::from(field) << 3 | 2);
+        out.extend(varint(payload.len() as u64));
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn install(uid: &str, code: &str, path: &str, language: &str) -> Vec<u8> {
+        let mut settings = bytes_field(1, path.as_bytes());
+        // Campo scalare sconosciuto tra i due stringa: va saltato
+        settings.extend(varint(2 << 3));
+        settings.extend(varint(1));
+        settings.extend(bytes_field(6, language.as_bytes()));
+
+        let mut message = bytes_field(1, uid.as_bytes());
+        message.extend(bytes_field(2, code.as_bytes()));
+        message.extend(bytes_field(3, &settings));
+        bytes_field(1, &message)
+    }
+
+    fn read_varint(data: &[u8]) -> Result<u64, String> {
+        ProtoReader::new(data).read_varint()
+    }
+
+    #[test]
+    fn varint_edge_cases() {
+        assert_eq!(read_varint(&[0x00]).unwrap(), 0);
+        assert_eq!(read_varint(&[0x7F]).unwrap(), 127);
+        assert_eq!(read_varint(&[0xAC, 0x02]).unwrap(), 300);
+        assert_eq!(read_varint(&varint(u64::MAX)).unwrap(), u64::MAX);
+        assert_eq!(varint(u64::MAX).len(), 10);
+
+        // Troncato a metà, più di 10 byte, decimo byte oltre il bit 63
+        assert!(read_varint(&[]).is_err());
+        assert!(read_varint(&[0x80, 0x80]).is_err());
+        assert!(read_varint(&[0xFF; 11]).is_err());
+        let mut overflow = vec![0xFF; 9];
+        overflow.push(0x02);
+        assert!(read_varint(&overflow).is_err());
+    }
+
+    #[test]
+    fn parses_installs_and_skips_unknown_fields() {
+        let mut data = install("wow", "wow", "C:\\Games\\World of Warcraft", "itIT");
+        // Campo fixed64 e fixed32 di primo livello ignorati
+        data.extend(varint(2 << 3 | 1));
+        data.extend([0u8; 8]);
+        data.extend(varint(3 << 3 | 5));
+        data.extend([0u8; 4]);
+        data.extend(install("pro", "pro", "D:\\Overwatch", ""));
+        // Voce senza uid scartata
+        data.extend(bytes_field(1, &bytes_field(2, b"agent")));
+
+        let installs = parse_product_db(&data).unwrap();
+        assert_eq!(installs.len(), 2);
+        assert_eq!(installs[0].uid, "wow");
+        assert_eq!(installs[0].install_path, "C:\\Games\\World of Warcraft");
+        assert_eq!(installs[0].text_language.as_deref(), Some("itIT"));
+        assert_eq!(installs[1].product_code, "pro");
+        assert!(installs[1].text_language.is_none());
+        assert!(parse_product_db(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn truncated_product_db_is_an_error() {
+        let data = install("wow", "wow", "C:\\Games\\World of Warcraft", "itIT");
+        for len in [1, 2, data.len() / 2, data.len() - 1] {
+            assert!(parse_product_db(&data[..len]).is_err(), "troncato a {} byte", len);
+        }
+
+        // Lunghezza dichiarata enorme: nessun overflow né panic
+        let mut huge = varint(1 << 3 | 2);
+        huge.extend(varint(u64::MAX));
+        assert!(parse_product_db(&huge).is_err());
+
+        // Wire type non supportato (3 = start group)
+        assert!(parse_product_db(&varint(1 << 3 | 3)).is_err());
+    }
+
+    #[test]
+    fn install_path_key_normalizes_paths() {
+        let game = |name: &str, path: &str| InstalledGame {
+            id: String::new(),
+            name: name.to_string(),
+            path: path.to_string(),
+            executable: None,
+            size_bytes: None,
+            last_modified: None,
+            platform: "Battle.net".to_string(),
+        };
+
+        assert_eq!(
+            install_path_key(&game("World of Warcraft", "C:\\Games\\World of Warcraft\\")),
+            install_path_key(&game("wow", "c:/games/world of warcraft"))
+        );
+        assert_ne!(
+            install_path_key(&game("Overwatch", "C:\\Overwatch")),
+            install_path_key(&game("Overwatch", "C:\\Overwatch PTR"))
+        );
+        assert_eq!(install_path_key(&game("Diablo IV", "")), "name:diablo iv");
+    }
+}
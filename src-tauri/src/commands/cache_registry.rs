@@ -0,0 +1,247 @@
+// 🗄️ Registro centralizzato delle cache moka
+// Ogni cache in memoria è avvolta in un `TrackedCache` che conta hit/miss/evizioni
+// e stima la memoria occupata, così `get_cache_stats` e `clear_cache` possono
+// mostrare/svuotare tutte le cache (o una sola per nome).
+
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use log::info;
+use moka::future::Cache;
+use moka::notification::RemovalCause;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// Stima della dimensione di una voce (chiave + valore) in byte
+pub type SizeEstimator<K, V> = fn(&K, &V) -> usize;
+
+/// Stima basata sulla lunghezza della serializzazione JSON (approssimativa ma stabile)
+pub fn json_size<K: Serialize, V: Serialize>(key: &K, value: &V) -> usize {
+    let key_size = serde_json::to_vec(key).map(|v| v.len()).unwrap_or(std::mem::size_of::<K>());
+    let value_size = serde_json::to_vec(value).map(|v| v.len()).unwrap_or(std::mem::size_of::<V>());
+    key_size + value_size
+}
+
+/// Contatori condivisi con l'eviction listener di moka
+#[derive(Default)]
+struct CacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    inserts: AtomicU64,
+    evictions: AtomicU64,
+    approx_bytes: AtomicU64,
+    last_insert_at: Mutex<Option<DateTime<Utc>>>,
+    last_cleared_at: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl CacheCounters {
+    fn release_bytes(&self, bytes: u64) {
+        let _ = self.approx_bytes.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+            Some(current.saturating_sub(bytes))
+        });
+    }
+}
+
+/// Statistiche di una singola cache registrata
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub entry_count: u64,
+    pub max_capacity: u64,
+    pub ttl_secs: u64,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_ratio: f64,
+    pub inserts: u64,
+    pub evictions: u64,
+    pub approx_memory_bytes: u64,
+    pub last_insert_at: Option<DateTime<Utc>>,
+    pub last_cleared_at: Option<DateTime<Utc>>,
+}
+
+/// Cache moka con contatori di hit/miss e stima della memoria
+pub struct TrackedCache<K, V> {
+    name: &'static str,
+    description: &'static str,
+    max_capacity: u64,
+    ttl: Duration,
+    cache: Cache<K, V>,
+    counters: Arc<CacheCounters>,
+    size_of: SizeEstimator<K, V>,
+}
+
+impl<K, V> TrackedCache<K, V>
+where
+    K: Hash + Eq + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new(
+        name: &'static str,
+        description: &'static str,
+        max_capacity: u64,
+        ttl: Duration,
+        size_of: SizeEstimator<K, V>,
+    ) -> Self {
+        let counters = Arc::new(CacheCounters::default());
+        let listener_counters = counters.clone();
+
+        let cache = Cache::builder()
+            .max_capacity(max_capacity)
+            .time_to_live(ttl)
+            .eviction_listener(move |key: Arc<K>, value: V, cause: RemovalCause| {
+                listener_counters.release_bytes(size_of(&key, &value) as u64);
+                if cause.was_evicted() {
+                    listener_counters.evictions.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+            .build();
+
+        Self { name, description, max_capacity, ttl, cache, counters, size_of }
+    }
+
+    /// Legge una voce aggiornando i contatori hit/miss
+    pub async fn get(&self, key: &K) -> Option<V> {
+        let value = self.cache.get(key).await;
+        let counter = if value.is_some() { &self.counters.hits } else { &self.counters.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    /// Inserisce (o sostituisce) una voce; la voce sostituita viene scalata dal listener
+    pub async fn insert(&self, key: K, value: V) {
+        let bytes = (self.size_of)(&key, &value) as u64;
+        self.cache.insert(key, value).await;
+        self.counters.approx_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.counters.inserts.fetch_add(1, Ordering::Relaxed);
+        *self.counters.last_insert_at.lock() = Some(Utc::now());
+    }
+
+    /// Svuota la cache (le voci vengono rimosse in modo lazy da moka)
+    pub fn invalidate_all(&self) {
+        self.cache.invalidate_all();
+        *self.counters.last_cleared_at.lock() = Some(Utc::now());
+    }
+
+    pub fn entry_count(&self) -> u64 {
+        self.cache.entry_count()
+    }
+
+    pub async fn run_pending_tasks(&self) {
+        self.cache.run_pending_tasks().await;
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.counters.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.counters.misses.load(Ordering::Relaxed)
+    }
+}
+
+/// Interfaccia object-safe usata dal registro
+pub trait CacheHandle: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// Applica le operazioni pendenti di moka (scadenze, invalidazioni) prima di leggere le statistiche
+    fn sync(&self) -> BoxFuture<'_, ()>;
+    fn stats(&self) -> CacheStats;
+    fn clear(&self);
+}
+
+impl<K, V> CacheHandle for TrackedCache<K, V>
+where
+    K: Hash + Eq + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn sync(&self) -> BoxFuture<'_, ()> {
+        Box::pin(self.run_pending_tasks())
+    }
+
+    fn stats(&self) -> CacheStats {
+        let hits = self.hits();
+        let misses = self.misses();
+        let lookups = hits + misses;
+
+        CacheStats {
+            name: self.name,
+            description: self.description,
+            entry_count: self.entry_count(),
+            max_capacity: self.max_capacity,
+            ttl_secs: self.ttl.as_secs(),
+            hits,
+            misses,
+            hit_ratio: if lookups > 0 { hits as f64 / lookups as f64 } else { 0.0 },
+            inserts: self.counters.inserts.load(Ordering::Relaxed),
+            evictions: self.counters.evictions.load(Ordering::Relaxed),
+            approx_memory_bytes: self.counters.approx_bytes.load(Ordering::Relaxed),
+            last_insert_at: *self.counters.last_insert_at.lock(),
+            last_cleared_at: *self.counters.last_cleared_at.lock(),
+        }
+    }
+
+    fn clear(&self) {
+        self.invalidate_all();
+    }
+}
+
+/// Tutte le cache moka registrate
+fn registered_caches() -> Vec<&'static dyn CacheHandle> {
+    let caches: [&'static dyn CacheHandle; 4] = [
+        &*super::steam::GAME_CACHE,
+        &*super::steam::DETAILS_CACHE,
+        &*super::hltb_manager::HLTB_CACHE,
+        &*super::steamgriddb::ARTWORK_CACHE,
+    ];
+    caches.to_vec()
+}
+
+/// Nomi delle cache registrate
+pub fn cache_names() -> Vec<&'static str> {
+    registered_caches().iter().map(|c| c.name()).collect()
+}
+
+/// Statistiche aggiornate di tutte le cache
+pub async fn collect_stats() -> Vec<CacheStats> {
+    let mut stats = Vec::new();
+    for cache in registered_caches() {
+        cache.sync().await;
+        stats.push(cache.stats());
+    }
+    stats
+}
+
+/// Svuota una cache per nome, oppure tutte se `name` è None; restituisce i nomi svuotati
+pub async fn clear_caches(name: Option<&str>) -> Result<Vec<&'static str>, String> {
+    let targets: Vec<_> = registered_caches()
+        .into_iter()
+        .filter(|cache| match name {
+            Some(n) => cache.name() == n,
+            None => true,
+        })
+        .collect();
+
+    if targets.is_empty() {
+        return Err(format!(
+            "Cache '{}' non trovata. Disponibili: {}",
+            name.unwrap_or_default(),
+            cache_names().join(", ")
+        ));
+    }
+
+    let mut cleared = Vec::new();
+    for cache in targets {
+        cache.clear();
+        cache.sync().await;
+        info!("🧹 Cache '{}' svuotata", cache.name());
+        cleared.push(cache.name());
+    }
+    Ok(cleared)
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use log::{info, warn, error, debug};
 use crate::commands::steam;
 use crate::commands::epic;
@@ -33,9 +33,48 @@ pub struct GameDLCStats {
     pub installed_dlc_count: u32,
     pub total_dlc_size_bytes: u64,
     pub completion_percentage: f32,
+    /// Il gioco base è installato (DLC installati senza gioco base vanno segnalati)
+    #[serde(default)]
+    pub base_game_installed: bool,
     pub dlc_list: Vec<DLCInfo>,
 }
 
+/// 🔗 Stesso DLC posseduto su più store, raggruppato per nome canonico
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DLCGroup {
+    pub canonical_key: String,
+    pub name: String,
+    pub parent_game_name: String,
+    pub stores: Vec<String>,
+    pub dlc_ids: Vec<String>,
+    pub owned_in: Vec<String>,
+    pub installed_in: Vec<String>,
+}
+
+/// ⚠️ DLC installato ma senza gioco base installato
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedDLC {
+    pub dlc_id: String,
+    pub name: String,
+    pub store: String,
+    pub parent_game_id: String,
+    pub parent_game_name: String,
+}
+
+/// 🧮 Conteggi DLC al netto dei duplicati cross-store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DLCDeduplication {
+    pub total_dlc: u32,
+    pub unique_dlc: u32,
+    pub unique_owned: u32,
+    pub unique_installed: u32,
+    /// Copie in store aggiuntivi (per gruppo: store distinti - 1); i doppioni nello stesso store non contano
+    pub cross_store_duplicates: u32,
+    /// Solo i gruppi presenti in più di uno store
+    pub cross_store_groups: Vec<DLCGroup>,
+    pub orphaned_installed: Vec<OrphanedDLC>,
+}
+
 /// 🌐 Risultato scansione DLC cross-store
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DLCScanResult {
@@ -125,6 +164,12 @@ pub async fn scan_all_dlc(
         }
     }
     
+    // Gioco base installato: stesso criterio per tutti gli store
+    match crate::commands::library::get_library_games().await {
+        Ok(installed) => mark_installed_base_games(&mut all_games_stats, &installed),
+        Err(e) => warn!("⚠️ Giochi installati non disponibili, stato del gioco base dagli store: {}", e),
+    }
+    
     let scan_duration = start_time.elapsed().as_millis() as u64;
     let total_games_with_dlc = all_games_stats.len() as u32;
     
@@ -222,6 +267,7 @@ async fn scan_steam_dlc(
                 installed_dlc_count: installed_count,
                 total_dlc_size_bytes: total_size,
                 completion_percentage,
+                base_game_installed: game.is_installed,
                 dlc_list,
             });
         }
@@ -378,6 +424,7 @@ async fn scan_epic_dlc() -> Result<Vec<GameDLCStats>, String> {
                     installed_dlc_count: installed_count,
                     total_dlc_size_bytes: total_size,
                     completion_percentage,
+                    base_game_installed: true, // aggiornato da mark_installed_base_games
                     dlc_list: processed_dlc,
                 });
             }
@@ -543,6 +590,7 @@ async fn scan_ubisoft_dlc() -> Result<Vec<GameDLCStats>, String> {
                 installed_dlc_count: installed_count,
                 total_dlc_size_bytes: total_size,
                 completion_percentage,
+                base_game_installed: true, // aggiornato da mark_installed_base_games
                 dlc_list,
             });
         }
@@ -735,6 +783,124 @@ pub async fn get_game_dlc(
     }
 }
 
+/// Normalizza un nome per il confronto cross-store: minuscolo, senza simboli
+/// di marchio e punteggiatura, spazi compattati
+fn canonical_dlc_name(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Chiave canonica gioco base + DLC. Il prefisso con il nome del gioco
+/// ("Game - Expansion" vs "Expansion") viene rimosso perché gli store lo usano in modo diverso
+fn canonical_dlc_key(parent_game_name: &str, dlc_name: &str) -> String {
+    let parent = canonical_dlc_name(parent_game_name);
+    let dlc = canonical_dlc_name(dlc_name);
+    let dlc = dlc.strip_prefix(&parent).map(|rest| rest.trim().to_string())
+        .filter(|rest| !rest.is_empty())
+        .unwrap_or(dlc);
+    format!("{}::{}", parent, dlc)
+}
+
+/// Store normalizzato per il confronto con `InstalledGame::platform` ("Uplay" è il vecchio nome di Ubisoft Connect)
+fn store_key(store: &str) -> String {
+    let store = store.trim().to_lowercase();
+    if store == "uplay" { "ubisoft connect".to_string() } else { store }
+}
+
+/// Imposta `base_game_installed` di ogni gioco dall'elenco dei giochi installati:
+/// stesso id (per Steam `steam_<appid>`) oppure stesso store e nome canonico
+fn mark_installed_base_games(games_stats: &mut [GameDLCStats], installed: &[crate::commands::library::InstalledGame]) {
+    let installed_ids: HashSet<&str> = installed.iter().map(|g| g.id.as_str()).collect();
+    let installed_names: HashSet<(String, String)> = installed.iter()
+        .map(|g| (store_key(&g.platform), canonical_dlc_name(&g.name)))
+        .collect();
+    
+    for game in games_stats {
+        let steam_id = format!("steam_{}", game.game_id);
+        game.base_game_installed = installed_ids.contains(game.game_id.as_str())
+            || installed_ids.contains(steam_id.as_str())
+            || installed_names.contains(&(store_key(&game.store), canonical_dlc_name(&game.game_name)));
+    }
+}
+
+/// 🔗 Raggruppa i DLC per nome canonico e segnala quelli installati senza gioco base
+pub fn deduplicate_dlc(games_stats: &[GameDLCStats]) -> DLCDeduplication {
+    let mut groups: HashMap<String, DLCGroup> = HashMap::new();
+    let mut orphaned_installed = Vec::new();
+    let mut total_dlc = 0u32;
+    
+    for game in games_stats {
+        for dlc in &game.dlc_list {
+            total_dlc += 1;
+            
+            if dlc.is_installed && !game.base_game_installed {
+                orphaned_installed.push(OrphanedDLC {
+                    dlc_id: dlc.id.clone(),
+                    name: dlc.name.clone(),
+                    store: dlc.store.clone(),
+                    parent_game_id: dlc.parent_game_id.clone(),
+                    parent_game_name: dlc.parent_game_name.clone(),
+                });
+            }
+            
+            let key = canonical_dlc_key(&dlc.parent_game_name, &dlc.name);
+            let group = groups.entry(key.clone()).or_insert_with(|| DLCGroup {
+                canonical_key: key,
+                name: dlc.name.clone(),
+                parent_game_name: dlc.parent_game_name.clone(),
+                stores: Vec::new(),
+                dlc_ids: Vec::new(),
+                owned_in: Vec::new(),
+                installed_in: Vec::new(),
+            });
+            if !group.stores.contains(&dlc.store) {
+                group.stores.push(dlc.store.clone());
+            }
+            group.dlc_ids.push(dlc.id.clone());
+            if dlc.is_owned && !group.owned_in.contains(&dlc.store) {
+                group.owned_in.push(dlc.store.clone());
+            }
+            if dlc.is_installed && !group.installed_in.contains(&dlc.store) {
+                group.installed_in.push(dlc.store.clone());
+            }
+        }
+    }
+    
+    let unique_dlc = groups.len() as u32;
+    let unique_owned = groups.values().filter(|g| !g.owned_in.is_empty()).count() as u32;
+    let unique_installed = groups.values().filter(|g| !g.installed_in.is_empty()).count() as u32;
+    
+    let mut cross_store_groups: Vec<DLCGroup> = groups.into_values()
+        .filter(|g| g.stores.len() > 1)
+        .collect();
+    cross_store_groups.sort_by(|a, b| a.canonical_key.cmp(&b.canonical_key));
+    let cross_store_duplicates = cross_store_groups.iter()
+        .map(|g| g.stores.len() as u32 - 1)
+        .sum();
+    
+    if !cross_store_groups.is_empty() {
+        info!("🔗 {} DLC presenti su più store", cross_store_groups.len());
+    }
+    if !orphaned_installed.is_empty() {
+        warn!("⚠️ {} DLC installati senza gioco base", orphaned_installed.len());
+    }
+    
+    DLCDeduplication {
+        total_dlc,
+        unique_dlc,
+        unique_owned,
+        unique_installed,
+        cross_store_duplicates,
+        cross_store_groups,
+        orphaned_installed,
+    }
+}
+
 /// 📊 Comando per ottenere statistiche aggregate DLC
 #[tauri::command]
 pub async fn get_dlc_statistics(
@@ -767,10 +933,16 @@ pub async fn get_dlc_statistics(
                 }));
             }
             
+            let deduplication = deduplicate_dlc(&scan_result.games_stats);
+            
             Ok(serde_json::json!({
                 "overview": {
                     "total_games_with_dlc": scan_result.total_games_with_dlc,
                     "total_dlc_found": scan_result.total_dlc_found,
+                    "unique_dlc_found": deduplication.unique_dlc,
+                    "unique_dlc_owned": deduplication.unique_owned,
+                    "unique_dlc_installed": deduplication.unique_installed,
+                    "cross_store_duplicates": deduplication.cross_store_duplicates,
                     "total_dlc_owned": scan_result.total_dlc_owned,
                     "total_dlc_installed": scan_result.total_dlc_installed,
                     "overall_completion_rate": if scan_result.total_dlc_found > 0 {
@@ -779,9 +951,149 @@ pub async fn get_dlc_statistics(
                     "scan_duration_ms": scan_result.scan_duration_ms
                 },
                 "by_store": stats_by_store,
+                "cross_store_dlc": deduplication.cross_store_groups,
+                "orphaned_installed_dlc": deduplication.orphaned_installed,
                 "stores_scanned": scan_result.stores_scanned
             }))
         }
         Err(e) => Err(e)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::library::InstalledGame;
+
+    fn dlc(id: &str, name: &str, parent: &str, store: &str, owned: bool, installed: bool) -> DLCInfo {
+        DLCInfo {
+            id: id.to_string(),
+            name: name.to_string(),
+            parent_game_id: format!("{}_base", store),
+            parent_game_name: parent.to_string(),
+            store: store.to_string(),
+            is_installed: installed,
+            is_owned: owned,
+            release_date: None,
+            price: None,
+            description: None,
+            cover_url: None,
+            size_bytes: None,
+            last_updated: None,
+        }
+    }
+
+    fn game(id: &str, name: &str, store: &str, base_installed: bool, dlc_list: Vec<DLCInfo>) -> GameDLCStats {
+        GameDLCStats {
+            game_id: id.to_string(),
+            game_name: name.to_string(),
+            store: store.to_string(),
+            total_dlc_count: dlc_list.len() as u32,
+            owned_dlc_count: dlc_list.iter().filter(|d| d.is_owned).count() as u32,
+            installed_dlc_count: dlc_list.iter().filter(|d| d.is_installed).count() as u32,
+            total_dlc_size_bytes: 0,
+            completion_percentage: 0.0,
+            base_game_installed: base_installed,
+            dlc_list,
+        }
+    }
+
+    fn installed(id: &str, name: &str, platform: &str) -> InstalledGame {
+        InstalledGame {
+            id: id.to_string(),
+            name: name.to_string(),
+            path: String::new(),
+            executable: None,
+            size_bytes: None,
+            last_modified: None,
+            platform: platform.to_string(),
+        }
+    }
+
+    #[test]
+    fn canonical_key_ignores_game_prefix_and_symbols() {
+        assert_eq!(
+            canonical_dlc_key("Borderlands 3", "Borderlands® 3: Moxxi's Heist"),
+            canonical_dlc_key("Borderlands 3", "Moxxi's Heist")
+        );
+        assert_ne!(
+            canonical_dlc_key("Borderlands 3", "Moxxi's Heist"),
+            canonical_dlc_key("Borderlands 2", "Moxxi's Heist")
+        );
+    }
+
+    #[test]
+    fn same_dlc_on_two_stores_is_one_cross_store_group() {
+        let stats = vec![
+            game("397540", "Borderlands 3", "Steam", true, vec![
+                dlc("steam_1", "Borderlands 3: Moxxi's Heist", "Borderlands 3", "Steam", true, true),
+            ]),
+            game("epic_catnip", "Borderlands 3", "Epic Games", true, vec![
+                dlc("epic_1", "Moxxi's Heist", "Borderlands 3", "Epic Games", true, false),
+                dlc("epic_2", "Bounty of Blood", "Borderlands 3", "Epic Games", false, false),
+            ]),
+        ];
+
+        let result = deduplicate_dlc(&stats);
+        assert_eq!(result.total_dlc, 3);
+        assert_eq!(result.unique_dlc, 2);
+        assert_eq!(result.unique_owned, 1);
+        assert_eq!(result.unique_installed, 1);
+        assert_eq!(result.cross_store_duplicates, 1);
+        assert_eq!(result.cross_store_groups.len(), 1);
+        let group = &result.cross_store_groups[0];
+        assert_eq!(group.stores, vec!["Steam", "Epic Games"]);
+        assert_eq!(group.dlc_ids, vec!["steam_1", "epic_1"]);
+        assert_eq!(group.installed_in, vec!["Steam"]);
+    }
+
+    #[test]
+    fn duplicates_within_one_store_are_not_cross_store() {
+        let stats = vec![game("1", "Far Cry 6", "Ubisoft Connect", true, vec![
+            dlc("ubisoft_a", "Season Pass", "Far Cry 6", "Ubisoft Connect", true, false),
+            dlc("ubisoft_b", "Season Pass", "Far Cry 6", "Ubisoft Connect", true, false),
+        ])];
+
+        let result = deduplicate_dlc(&stats);
+        assert_eq!(result.total_dlc, 2);
+        assert_eq!(result.unique_dlc, 1);
+        assert_eq!(result.cross_store_duplicates, 0);
+        assert!(result.cross_store_groups.is_empty());
+    }
+
+    #[test]
+    fn installed_dlc_without_base_game_is_orphaned() {
+        let stats = vec![
+            game("epic_fn", "Fortnite", "Epic Games", false, vec![
+                dlc("epic_bp", "Battle Pass", "Fortnite", "Epic Games", true, true),
+            ]),
+            game("570", "Dota 2", "Steam", true, vec![
+                dlc("steam_2", "Soundtrack", "Dota 2", "Steam", true, true),
+            ]),
+        ];
+
+        let result = deduplicate_dlc(&stats);
+        assert_eq!(result.orphaned_installed.len(), 1);
+        assert_eq!(result.orphaned_installed[0].dlc_id, "epic_bp");
+    }
+
+    #[test]
+    fn base_game_installed_is_derived_for_every_store() {
+        let mut stats = vec![
+            game("570", "Dota 2", "Steam", false, vec![]),
+            game("epic_fn", "Fortnite", "Epic Games", true, vec![]),
+            game("uplay_1", "Far Cry® 6", "Ubisoft Connect", true, vec![]),
+            game("uplay_2", "The Division 2", "Ubisoft Connect", true, vec![]),
+        ];
+        let installed = vec![
+            installed("steam_570", "Dota 2", "Steam"),
+            installed("uplay_99", "Far Cry 6", "Uplay"),
+            // Stesso nome ma su un altro store: non conta
+            installed("steam_2221490", "The Division 2", "Steam"),
+        ];
+
+        mark_installed_base_games(&mut stats, &installed);
+        let flags: Vec<bool> = stats.iter().map(|g| g.base_game_installed).collect();
+        assert_eq!(flags, vec![true, false, true, false]);
+    }
+}
@@ -26,19 +26,16 @@ use base64::{Engine as _, engine::general_purpose};
 use aes_gcm::{Aes256Gcm, Nonce, aead::{Aead, KeyInit}};
 use rand::{RngCore, rngs::OsRng};
 use chrono;
+use crate::profiles::secure_memory::{SecureBytes, SecureString};
 
 // ============================================================================
 // SEZIONE 1: STRUTTURE DATI
 // ============================================================================
 
-// Client HTTP globale per Epic Games
-static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
-    Client::builder()
-        .timeout(Duration::from_secs(30))
-        .user_agent("GameStringer/1.0")
-        .build()
-        .expect("Failed to create HTTP client")
-});
+// Client HTTP per Epic Games (timeout dalle preferenze di rete)
+fn http_client() -> Client {
+    crate::http::client_for("epic")
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EpicGame {
@@ -95,99 +92,369 @@ pub struct EpicCredentials {
 // SEZIONE 2: GAME DETAILS & COVERS API
 // ============================================================================
 
+// Endpoint Epic usati per rate limiting e backoff (chiave del limiter)
+const EPIC_GRAPHQL_ENDPOINT: &str = "graphql";
+const EPIC_GRAPHQL_URL: &str = "https://graphql.epicgames.com/graphql";
+
+
+/// Rate limiter per le API pubbliche di Epic Games Store: limiti più prudenti di quelli
+/// Steam, l'endpoint statico di Epic risponde 429 già con pochi burst
+static EPIC_RATE_LIMITER: Lazy<crate::http::RateLimiter> = Lazy::new(|| crate::http::RateLimiter::new("Epic", 4, 60));
+
+/// Errore di una richiesta Epic: `retryable` indica se ha senso riprovare
+/// (429, 5xx, errori di rete) o se il risultato è definitivo (gioco non trovato)
+#[derive(Debug, Clone)]
+struct EpicRequestError {
+    message: String,
+    retryable: bool,
+}
+
+impl EpicRequestError {
+    fn transient(message: String) -> Self {
+        Self { message, retryable: true }
+    }
+    
+    fn permanent(message: String) -> Self {
+        Self { message, retryable: false }
+    }
+}
+
+/// Richiesta rate-limited verso Epic con retry su 429/5xx ed errori di rete.
+/// `build` ricrea la richiesta a ogni tentativo (GET o POST GraphQL); ogni tentativo
+/// passa dal rate limiter. Rispetta l'header Retry-After quando presente.
+async fn make_epic_rate_limited_request<F>(build: F, endpoint: &str) -> Result<serde_json::Value, EpicRequestError>
+where
+    F: Fn() -> reqwest::RequestBuilder,
+{
+    let policy = crate::http::RetryPolicy::new(6, Duration::from_millis(1000), Duration::from_millis(30000));
+    
+    let prepare = || async {
+        EPIC_RATE_LIMITER.acquire(endpoint).await;
+        build()
+    };
+    
+    match crate::http::send_with_retry(prepare, endpoint, &policy).await {
+        Ok(response) if response.status().is_success() => response.json::<serde_json::Value>().await
+            .map_err(|e| EpicRequestError::transient(format!("Errore parsing JSON Epic API: {}", e))),
+        Ok(response) => Err(EpicRequestError::permanent(format!("Errore HTTP Epic API: {}", response.status()))),
+        Err(e) => Err(EpicRequestError::transient(format!("Errore Epic API {}", e))),
+    }
+}
+
+/// Dati essenziali di un manifest `.item` del launcher Epic (gioco posseduto)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EpicLocalManifest {
+    pub app_name: String,
+    pub display_name: String,
+    pub catalog_namespace: String,
+    pub catalog_item_id: String,
+    pub install_location: Option<String>,
+    pub launch_executable: Option<String>,
+    /// Nome del gioco base se il manifest è un DLC
+    pub main_game_app_name: Option<String>,
+    pub incomplete_install: bool,
+}
+
+/// Esito della ricerca di un gioco Epic
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EpicLookupStatus {
+    /// Posseduto (manifest locale) e presente nel catalogo dello store
+    Found,
+    /// Presente nel catalogo ma senza manifest locale: non posseduto/installato
+    NotOwned,
+    /// Posseduto ma assente dal catalogo pubblico (es. rimosso dallo store)
+    NotInCatalog,
+    /// Né manifest locale né voce di catalogo
+    NotFound,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EpicGameLookup {
+    pub query: String,
+    pub status: EpicLookupStatus,
+    pub owned: bool,
+    pub in_catalog: bool,
+    pub manifest: Option<EpicLocalManifest>,
+    pub game: Option<EpicGame>,
+    pub message: String,
+}
+
+/// Cartella dei manifest `.item` del launcher Epic (rispetta %PROGRAMDATA%)
+fn epic_manifests_dir() -> std::path::PathBuf {
+    let program_data = std::env::var("PROGRAMDATA").unwrap_or_else(|_| "C:\\ProgramData".to_string());
+    std::path::Path::new(&program_data).join("Epic").join("EpicGamesLauncher").join("Data").join("Manifests")
+}
+
+/// Estrae i campi utili da un manifest; None se manca AppName
+fn parse_epic_local_manifest(manifest: &serde_json::Value) -> Option<EpicLocalManifest> {
+    let non_empty = |key: &str| manifest[key].as_str().map(|s| s.trim()).filter(|s| !s.is_empty()).map(|s| s.to_string());
+    
+    let app_name = non_empty("AppName")?;
+    Some(EpicLocalManifest {
+        display_name: non_empty("DisplayName").unwrap_or_else(|| app_name.clone()),
+        catalog_namespace: non_empty("CatalogNamespace").unwrap_or_default(),
+        catalog_item_id: non_empty("CatalogItemId").unwrap_or_default(),
+        install_location: non_empty("InstallLocation"),
+        launch_executable: non_empty("LaunchExecutable"),
+        main_game_app_name: non_empty("MainGameAppName").filter(|main| *main != app_name),
+        incomplete_install: manifest["bIsIncompleteInstall"].as_bool().unwrap_or(false),
+        app_name,
+    })
+}
+
+/// Legge i manifest `.item` del launcher Epic, ignorando quelli illeggibili o parziali
+fn read_epic_local_manifests() -> Vec<EpicLocalManifest> {
+    let mut manifests = Vec::new();
+    let Ok(entries) = fs::read_dir(epic_manifests_dir()) else {
+        return manifests;
+    };
+    
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("item") {
+            continue;
+        }
+        let parsed = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|json| parse_epic_local_manifest(&json));
+        match parsed {
+            Some(manifest) => manifests.push(manifest),
+            None => log::warn!("[EPIC] ⚠️ Manifest ignorato (illeggibile o senza AppName): {}", path.display()),
+        }
+    }
+    manifests
+}
+
+/// Cerca un manifest locale per AppName, CatalogItemId o DisplayName
+fn find_epic_local_manifest(query: &str) -> Option<EpicLocalManifest> {
+    let query_lower = query.to_lowercase();
+    read_epic_local_manifests().into_iter().find(|m| {
+        m.app_name.eq_ignore_ascii_case(query)
+            || m.catalog_item_id.eq_ignore_ascii_case(query)
+            || m.display_name.to_lowercase() == query_lower
+    })
+}
+
+/// Cerca nel catalogo dello store via GraphQL `Catalog.searchStore`.
+/// Se è noto il namespace del manifest, la corrispondenza per namespace ha la precedenza sul titolo.
+async fn search_epic_catalog(keywords: &str, namespace: Option<&str>) -> Result<Option<EpicGame>, EpicRequestError> {
+    const SEARCH_STORE_QUERY: &str = r#"query searchStoreQuery($keywords: String, $country: String!, $locale: String, $count: Int) {
+  Catalog {
+    searchStore(keywords: $keywords, country: $country, locale: $locale, count: $count) {
+      elements {
+        id title namespace description effectiveDate
+        keyImages { type url }
+        seller { name }
+        categories { path }
+        tags { id name }
+        price(country: $country) { totalPrice { currencyCode originalPrice discountPrice } }
+      }
+    }
+  }
+}"#;
+    
+    let body = serde_json::json!({
+        "query": SEARCH_STORE_QUERY,
+        "variables": { "keywords": keywords, "country": "IT", "locale": "it", "count": 20 }
+    });
+    let data = make_epic_rate_limited_request(
+        || http_client().post(EPIC_GRAPHQL_URL).json(&body),
+        EPIC_GRAPHQL_ENDPOINT,
+    ).await?;
+    
+    if let Some(errors) = data["errors"].as_array() {
+        if data["data"]["Catalog"].is_null() && !errors.is_empty() {
+            let message = errors[0]["message"].as_str().unwrap_or("errore sconosciuto");
+            return Err(EpicRequestError::transient(format!("Errore GraphQL Epic: {}", message)));
+        }
+    }
+    
+    let empty = Vec::new();
+    let elements = data["data"]["Catalog"]["searchStore"]["elements"].as_array().unwrap_or(&empty);
+    let keywords_lower = keywords.to_lowercase();
+    
+    let matched = namespace
+        .filter(|ns| !ns.is_empty())
+        .and_then(|ns| elements.iter().find(|e| e["namespace"].as_str() == Some(ns)))
+        .or_else(|| elements.iter().find(|e| e["title"].as_str().map(|t| t.to_lowercase()) == Some(keywords_lower.clone())))
+        .or_else(|| elements.iter().find(|e| e["id"].as_str() == Some(keywords)));
+    
+    matched.map(parse_epic_game_data).transpose().map_err(EpicRequestError::permanent)
+}
+
+/// Gioco minimale costruito dal manifest quando lo store non ha una voce di catalogo
+fn epic_game_from_manifest(manifest: &EpicLocalManifest) -> EpicGame {
+    EpicGame {
+        id: manifest.catalog_item_id.clone(),
+        title: manifest.display_name.clone(),
+        description: None,
+        developer: None,
+        publisher: None,
+        release_date: None,
+        price: None,
+        images: Vec::new(),
+        categories: Vec::new(),
+        tags: Vec::new(),
+        rating: None,
+    }
+}
+
+async fn lookup_epic_game_inner(query: &str) -> Result<EpicGameLookup, EpicRequestError> {
+    let manifest = find_epic_local_manifest(query);
+    let keywords = manifest.as_ref().map(|m| m.display_name.as_str()).unwrap_or(query);
+    let namespace = manifest.as_ref().map(|m| m.catalog_namespace.as_str());
+    
+    let catalog_game = search_epic_catalog(keywords, namespace).await?;
+    
+    let (status, message) = match (&manifest, &catalog_game) {
+        (Some(_), Some(_)) => (EpicLookupStatus::Found, "Gioco posseduto e presente nel catalogo Epic".to_string()),
+        (None, Some(_)) => (EpicLookupStatus::NotOwned, format!("'{}' è nel catalogo Epic ma non risulta posseduto su questo PC", query)),
+        (Some(m), None) => (EpicLookupStatus::NotInCatalog, format!("'{}' è posseduto ma non è presente nel catalogo pubblico dello store", m.display_name)),
+        (None, None) => (EpicLookupStatus::NotFound, format!("'{}' non trovato né nel catalogo Epic né nei manifest locali", query)),
+    };
+    
+    let game = catalog_game.or_else(|| manifest.as_ref().map(epic_game_from_manifest));
+    Ok(EpicGameLookup {
+        query: query.to_string(),
+        status,
+        owned: manifest.is_some(),
+        in_catalog: matches!(status, EpicLookupStatus::Found | EpicLookupStatus::NotOwned),
+        manifest,
+        game,
+        message,
+    })
+}
+
+/// Risolve un gioco Epic distinguendo "non posseduto" da "non presente nel catalogo"
+#[tauri::command]
+pub async fn lookup_epic_game(app_name: String) -> Result<EpicGameLookup, String> {
+    println!("[EPIC] Ricerca catalogo per: {}", app_name);
+    lookup_epic_game_inner(&app_name).await.map_err(|e| e.message)
+}
+
+async fn fetch_epic_game_details(app_name: &str) -> Result<EpicGame, EpicRequestError> {
+    let lookup = lookup_epic_game_inner(app_name).await?;
+    match lookup.game {
+        Some(game) => Ok(game),
+        None => Err(EpicRequestError::permanent(lookup.message)),
+    }
+}
+
 #[tauri::command]
 pub async fn get_epic_game_details(app_name: String) -> Result<EpicGame, String> {
     println!("[EPIC] Recupero dettagli per: {}", app_name);
+    fetch_epic_game_details(&app_name).await.map_err(|e| e.message)
+}
+
+async fn fetch_epic_game_cover(app_name: &str) -> Result<String, EpicRequestError> {
+    let game = fetch_epic_game_details(app_name).await?;
     
-    // Epic Games Store API endpoint (pubblico)
-    let url = format!(
-        "https://store-site-backend-static.ak.epicgames.com/freeGamesPromotions?locale=it&country=IT&allowCountries=IT"
-    );
-    
-    match HTTP_CLIENT.get(&url).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.json::<serde_json::Value>().await {
-                    Ok(data) => {
-                        // Cerca il gioco specifico nei dati
-                        if let Some(games) = data["data"]["Catalog"]["searchStore"]["elements"].as_array() {
-                            for game in games {
-                                if let Some(id) = game["id"].as_str() {
-                                    if id == app_name || game["title"].as_str().unwrap_or("").to_lowercase().contains(&app_name.to_lowercase()) {
-                                        return parse_epic_game_data(game);
-                                    }
-                                }
-                            }
-                        }
-                        Err(format!("Gioco '{}' non trovato nell'Epic Games Store", app_name))
-                    }
-                    Err(e) => Err(format!("Errore parsing JSON Epic API: {}", e)),
-                }
-            } else {
-                Err(format!("Errore HTTP Epic API: {}", response.status()))
-            }
+    // Cerca l'immagine di tipo "DieselStoreFrontWide" o "OfferImageWide"
+    for image in &game.images {
+        if image.r#type == "DieselStoreFrontWide" || image.r#type == "OfferImageWide" {
+            return Ok(image.url.clone());
         }
-        Err(e) => Err(format!("Errore connessione Epic API: {}", e)),
     }
+    
+    // Se non troviamo l'immagine specifica, prendiamo la prima disponibile
+    game.images.first()
+        .map(|image| image.url.clone())
+        .ok_or_else(|| EpicRequestError::permanent("Nessuna immagine disponibile per questo gioco".to_string()))
+}
+
+type CoverResultCell = std::sync::Arc<tokio::sync::OnceCell<Result<String, EpicRequestError>>>;
+
+// Richieste copertina in corso: chiamate concorrenti per lo stesso gioco condividono il risultato
+static COVER_REQUESTS_IN_FLIGHT: Lazy<std::sync::Mutex<HashMap<String, CoverResultCell>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+async fn fetch_epic_game_cover_deduped(app_name: &str) -> Result<String, EpicRequestError> {
+    let cell = {
+        let mut in_flight = COVER_REQUESTS_IN_FLIGHT.lock().unwrap();
+        in_flight.entry(app_name.to_string()).or_default().clone()
+    };
+    
+    let result = cell.get_or_init(|| fetch_epic_game_cover(app_name)).await.clone();
+    
+    // Rimuove la voce solo se è ancora quella che abbiamo usato
+    let mut in_flight = COVER_REQUESTS_IN_FLIGHT.lock().unwrap();
+    if in_flight.get(app_name).map(|c| std::sync::Arc::ptr_eq(c, &cell)).unwrap_or(false) {
+        in_flight.remove(app_name);
+    }
+    result
 }
 
 /// Recupera l'URL della copertina di un gioco Epic Games
 #[tauri::command]
 pub async fn get_epic_game_cover(app_name: String) -> Result<String, String> {
     println!("[EPIC] Recupero copertina per: {}", app_name);
-    
-    match get_epic_game_details(app_name).await {
-        Ok(game) => {
-            // Cerca l'immagine di tipo "DieselStoreFrontWide" o "OfferImageWide"
-            for image in &game.images {
-                if image.r#type == "DieselStoreFrontWide" || image.r#type == "OfferImageWide" {
-                    return Ok(image.url.clone());
-                }
-            }
-            
-            // Se non troviamo l'immagine specifica, prendiamo la prima disponibile
-            if let Some(first_image) = game.images.first() {
-                Ok(first_image.url.clone())
-            } else {
-                Err("Nessuna immagine disponibile per questo gioco".to_string())
-            }
-        }
-        Err(e) => Err(e),
-    }
+    fetch_epic_game_cover_deduped(&app_name).await.map_err(|e| e.message)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EpicCoverFailure {
+    pub app_name: String,
+    pub error: String,
+    /// true se l'errore è temporaneo (429, rete): la UI può riprovare solo questi
+    pub retryable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct EpicCoversBatchResult {
+    pub covers: HashMap<String, String>,
+    pub failed: Vec<EpicCoverFailure>,
 }
 
 /// Recupera più copertine Epic Games in batch
 #[tauri::command]
-pub async fn get_epic_covers_batch(app_names: Vec<String>) -> Result<HashMap<String, String>, String> {
+pub async fn get_epic_covers_batch(app_names: Vec<String>) -> Result<EpicCoversBatchResult, String> {
+    // Deduplica i nomi mantenendo l'ordine
+    let mut seen = std::collections::HashSet::new();
+    let app_names: Vec<String> = app_names.into_iter().filter(|n| seen.insert(n.clone())).collect();
     println!("[EPIC] Recupero copertine batch per {} giochi", app_names.len());
     
-    let mut covers = HashMap::new();
+    let mut result = EpicCoversBatchResult::default();
     
-    // Per evitare di sovraccaricare l'API, processiamo in batch di 5
+    // Concorrenza limitata a 5: il ritmo effettivo lo decide il rate limiter
     for chunk in app_names.chunks(5) {
         let mut tasks = Vec::new();
         
         for app_name in chunk {
             let app_name_clone = app_name.clone();
             tasks.push(tokio::spawn(async move {
-                (app_name_clone.clone(), get_epic_game_cover(app_name_clone).await)
+                let cover = fetch_epic_game_cover_deduped(&app_name_clone).await;
+                (app_name_clone, cover)
             }));
         }
         
-        // Aspetta che tutti i task del batch completino
-        for task in tasks {
-            if let Ok((app_name, result)) = task.await {
-                if let Ok(cover_url) = result {
-                    covers.insert(app_name, cover_url);
+        for (task, app_name) in tasks.into_iter().zip(chunk) {
+            match task.await {
+                Ok((name, Ok(cover_url))) => {
+                    result.covers.insert(name, cover_url);
+                }
+                Ok((name, Err(e))) => {
+                    result.failed.push(EpicCoverFailure { app_name: name, error: e.message, retryable: e.retryable });
+                }
+                Err(e) => {
+                    result.failed.push(EpicCoverFailure {
+                        app_name: app_name.clone(),
+                        error: format!("Task interrotto: {}", e),
+                        retryable: true,
+                    });
                 }
             }
         }
-        
-        // Pausa tra i batch per rispettare i rate limits
-        tokio::time::sleep(Duration::from_millis(500)).await;
     }
     
-    Ok(covers)
+    let retryable = result.failed.iter().filter(|f| f.retryable).count();
+    if retryable > 0 {
+        log::warn!("[EPIC] ⚠️ {} copertine fallite per errori temporanei (riprovabili)", retryable);
+    }
+    info!("[EPIC] ✅ Copertine: {} trovate, {} fallite", result.covers.len(), result.failed.len());
+    
+    Ok(result)
 }
 
 // ============================================================================
@@ -449,7 +716,7 @@ pub async fn test_epic_connection() -> Result<serde_json::Value, String> {
     // 1. Test connessione all'API Epic Games Store pubblica
     let url = "https://store-site-backend-static.ak.epicgames.com/freeGamesPromotions";
     
-    let api_connected = match HTTP_CLIENT.get(url).send().await {
+    let api_connected = match http_client().get(url).send().await {
         Ok(response) => {
             if response.status().is_success() {
                 println!("[EPIC] ✅ API pubblica connessa con successo");
@@ -812,9 +1079,69 @@ async fn try_legendary_library_direct() -> Result<Vec<GameInfo>, String> {
 
 /// Ottiene giochi Epic Games installati localmente
 async fn get_epic_installed_local() -> Result<Vec<GameInfo>, String> {
-    // Implementazione base per giochi installati localmente
-    // TODO: Scansione più dettagliata dei manifest Epic Games
-    Ok(vec![])
+    scan_epic_installed_from_manifests().await
+}
+
+/// Gioco da un manifest locale; None per DLC e componenti di Unreal Engine.
+/// Il manifest non registra l'ultima sessione di gioco: `last_played` resta vuoto.
+fn epic_manifest_game(manifest: &EpicLocalManifest) -> Option<GameInfo> {
+    if manifest.main_game_app_name.is_some() {
+        debug!("[EPIC] DLC ignorato: {}", manifest.display_name);
+        return None;
+    }
+    if manifest.app_name.starts_with("UE_") {
+        debug!("[EPIC] 🔧 Unreal Engine ignorato: {}", manifest.display_name);
+        return None;
+    }
+    
+    let install_dir = manifest.install_location.as_ref().map(std::path::PathBuf::from);
+    let is_installed = !manifest.incomplete_install
+        && install_dir.as_ref().map(|dir| dir.exists()).unwrap_or(false);
+    let executable_path = match (&install_dir, &manifest.launch_executable) {
+        (Some(dir), Some(exe)) => Some(dir.join(exe).to_string_lossy().to_string()),
+        _ => None,
+    };
+    let title_lower = manifest.display_name.to_lowercase();
+    
+    Some(GameInfo {
+        id: format!("epic_{}", manifest.app_name),
+        title: manifest.display_name.clone(),
+        platform: "Epic Games".to_string(),
+        install_path: manifest.install_location.clone(),
+        executable_path,
+        icon: None,
+        image_url: None,
+        header_image: None,
+        is_installed,
+        steam_app_id: None,
+        is_vr: title_lower.contains(" vr") || title_lower.contains("virtual reality"),
+        engine: None,
+        last_played: None,
+        is_shared: false,
+        supported_languages: None,
+        genres: None,
+        added_date: None,
+    })
+}
+
+/// Giochi Epic installati letti dai manifest `.item` del launcher.
+/// Non richiede login né Legendary: funziona anche offline.
+/// DLC e installazioni di Unreal Engine vengono esclusi.
+#[tauri::command]
+pub async fn scan_epic_installed_from_manifests() -> Result<Vec<GameInfo>, String> {
+    let manifests_dir = epic_manifests_dir();
+    if !manifests_dir.exists() {
+        info!("[EPIC] Cartella manifest non trovata: {}", manifests_dir.display());
+        return Ok(Vec::new());
+    }
+    
+    let games: Vec<GameInfo> = read_epic_local_manifests()
+        .iter()
+        .filter_map(epic_manifest_game)
+        .collect();
+    
+    info!("[EPIC] ✅ {} giochi trovati nei manifest locali", games.len());
+    Ok(games)
 }
 
 // ============================================================================
@@ -1431,7 +1758,7 @@ fn extract_games_from_file_content(content: &str) -> Vec<String> {
 async fn try_epic_free_games_api() -> Result<Vec<String>, String> {
     let url = "https://store-site-backend-static.ak.epicgames.com/freeGamesPromotions?locale=it&country=IT";
     
-    match HTTP_CLIENT.get(url).send().await {
+    match http_client().get(url).send().await {
         Ok(response) => {
             if response.status().is_success() {
                 match response.json::<serde_json::Value>().await {
@@ -1778,7 +2105,13 @@ fn parse_epic_game_data(game_data: &serde_json::Value) -> Result<EpicGame, Strin
     }
     
     // Parsing del prezzo (con controlli sicuri)
-    let price = if let Some(price_data) = game_data["price"].as_object() {
+    // Il catalogo GraphQL annida i valori in price.totalPrice
+    let price_value = if game_data["price"]["totalPrice"].is_object() {
+        &game_data["price"]["totalPrice"]
+    } else {
+        &game_data["price"]
+    };
+    let price = if let Some(price_data) = price_value.as_object() {
         Some(EpicPrice {
             currency: price_data.get("currencyCode")
                 .and_then(|v| v.as_str())
@@ -1803,8 +2136,12 @@ fn parse_epic_game_data(game_data: &serde_json::Value) -> Result<EpicGame, Strin
         title,
         description,
         developer: game_data["developer"].as_str().map(|s| s.to_string()),
-        publisher: game_data["publisher"].as_str().map(|s| s.to_string()),
-        release_date: game_data["releaseDate"].as_str().map(|s| s.to_string()),
+        publisher: game_data["publisher"].as_str()
+            .or_else(|| game_data["seller"]["name"].as_str())
+            .map(|s| s.to_string()),
+        release_date: game_data["releaseDate"].as_str()
+            .or_else(|| game_data["effectiveDate"].as_str())
+            .map(|s| s.to_string()),
         price,
         images,
         categories,
@@ -1995,7 +2332,7 @@ async fn create_epic_guest_session() -> Result<EpicAuthData, String> {
         ("client_secret", client_secret),
     ];
     
-    match HTTP_CLIENT.post(auth_url)
+    match http_client().post(auth_url)
         .form(&params)
         .header("User-Agent", "GameStringer/1.0 UELauncher/11.0.1")
         .send()
@@ -2042,7 +2379,7 @@ async fn get_epic_owned_games_api(auth_data: &EpicAuthData) -> Result<Vec<String
     // Endpoint per ottenere la libreria dell'utente
     let library_url = "https://library-service.live.use1a.on.epicgames.com/library/api/public/items";
     
-    match HTTP_CLIENT.get(library_url)
+    match http_client().get(library_url)
         .header("Authorization", format!("Bearer {}", auth_data.access_token))
         .header("User-Agent", "GameStringer/1.0 UELauncher/11.0.1")
         .header("Accept", "application/json")
@@ -2442,7 +2779,7 @@ async fn sync_epic_games_to_library(epic_games: &[String]) -> Result<usize, Stri
 // === EPIC CREDENTIALS MANAGEMENT ===
 
 /// Ottiene il percorso per salvare le credenziali Epic Games
-fn get_epic_credentials_path() -> Result<std::path::PathBuf, String> {
+pub(crate) fn get_epic_credentials_path() -> Result<std::path::PathBuf, String> {
     // SECURITY FIX: Validate APPDATA environment variable
     let app_data = std::env::var("APPDATA")
         .map_err(|_| "APPDATA environment variable not found".to_string())?;
@@ -2475,6 +2812,11 @@ fn get_epic_credentials_path() -> Result<std::path::PathBuf, String> {
     Ok(canonical_app_dir.join("epic_credentials.json"))
 }
 
+/// Credenziali Epic Games salvate da GameStringer (usato dalla diagnostica launcher)
+pub(crate) fn has_saved_credentials() -> bool {
+    get_epic_credentials_path().map(|path| path.exists()).unwrap_or(false)
+}
+
 /// Genera una chiave di crittografia basata sulla macchina (machine-specific key)
 fn get_machine_key() -> Result<[u8; 32], String> {
     // SECURITY FIX: Enhanced key derivation with multiple entropy sources
@@ -2583,9 +2925,7 @@ fn encrypt_epic_credentials(username: &str, password: &str) -> Result<(String, S
 }
 
 /// Decripta username e password usando AES-256-GCM
-/// FUTURE USE: Will be used for decrypting stored Epic Games credentials
-#[allow(dead_code)]
-fn decrypt_epic_credentials(username_encrypted: &str, password_encrypted: &str, nonce_b64: &str) -> Result<(String, String), String> {
+fn decrypt_epic_credentials(username_encrypted: &str, password_encrypted: &str, nonce_b64: &str) -> Result<(String, SecureString), String> {
     // SECURITY FIX: Validate input parameters
     if username_encrypted.is_empty() || password_encrypted.is_empty() || nonce_b64.is_empty() {
         return Err("Encrypted data and nonce cannot be empty".to_string());
@@ -2628,15 +2968,15 @@ fn decrypt_epic_credentials(username_encrypted: &str, password_encrypted: &str,
     let decrypted_username = cipher.decrypt(nonce, encrypted_username.as_slice())
         .map_err(|e| format!("Username decryption failed: {}", e))?;
     
-    // Decrypt password
-    let decrypted_password = cipher.decrypt(nonce, encrypted_password.as_slice())
-        .map_err(|e| format!("Password decryption failed: {}", e))?;
+    // Decrypt password (plaintext azzerato al Drop)
+    let decrypted_password = SecureBytes::new(cipher.decrypt(nonce, encrypted_password.as_slice())
+        .map_err(|e| format!("Password decryption failed: {}", e))?);
     
     // SECURITY FIX: Parse timestamp for integrity check
     let username_payload = String::from_utf8(decrypted_username)
         .map_err(|e| format!("Username UTF-8 decode failed: {}", e))?;
     
-    let password_payload = String::from_utf8(decrypted_password)
+    let password_payload = std::str::from_utf8(decrypted_password.as_slice())
         .map_err(|e| format!("Password UTF-8 decode failed: {}", e))?;
     
     // Extract username and timestamp
@@ -2651,7 +2991,7 @@ fn decrypt_epic_credentials(username_encrypted: &str, password_encrypted: &str,
     }
     
     let username = username_parts[0].to_string();
-    let password = password_parts[0].to_string();
+    let password = SecureString::from_str_copy(password_parts[0]);
     
     // SECURITY FIX: Verify timestamp integrity (basic check)
     let _username_timestamp = username_parts[1].parse::<u64>()
@@ -2741,10 +3081,8 @@ pub async fn clear_epic_credentials() -> Result<String, String> {
     }
 }
 
-// 🔒 Funzione helper per ottenere le credenziali decriptate (uso interno)
-/// FUTURE USE: Internal helper for getting decrypted Epic Games credentials
-#[allow(dead_code)]
-async fn get_decrypted_epic_credentials() -> Result<(String, String), String> {
+// 🔒 Funzione helper per ottenere le credenziali decriptate (usata dalla migrazione legacy)
+pub(crate) async fn get_decrypted_epic_credentials() -> Result<(String, SecureString), String> {
     // SECURITY FIX: Use secure credential loading with integrity verification
     let credentials = load_epic_credentials().await?;
     
@@ -2774,3 +3112,66 @@ async fn get_decrypted_epic_credentials() -> Result<(String, String), String> {
     debug!("[RUST] 🔓 Credenziali Epic decriptate per uso interno");
     Ok((username, password))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_local_manifest_fields() {
+        let install_dir = tempfile::TempDir::new().unwrap();
+        let manifest = json!({
+            "FormatVersion": 0,
+            "bIsIncompleteInstall": false,
+            "AppName": "Fortnite",
+            "DisplayName": " Fortnite ",
+            "CatalogNamespace": "fn",
+            "CatalogItemId": "4fe75bbc5a674f4f9b356b5c90567da5",
+            "InstallLocation": install_dir.path().to_string_lossy(),
+            "LaunchExecutable": "FortniteGame/Binaries/Win64/FortniteLauncher.exe",
+            "MainGameAppName": "Fortnite"
+        });
+
+        let parsed = parse_epic_local_manifest(&manifest).unwrap();
+        assert_eq!(parsed.app_name, "Fortnite");
+        assert_eq!(parsed.display_name, "Fortnite");
+        assert_eq!(parsed.catalog_namespace, "fn");
+        assert_eq!(parsed.catalog_item_id, "4fe75bbc5a674f4f9b356b5c90567da5");
+        assert_eq!(parsed.launch_executable.as_deref(), Some("FortniteGame/Binaries/Win64/FortniteLauncher.exe"));
+        // Il gioco base ha MainGameAppName uguale al proprio AppName: non è un DLC
+        assert!(parsed.main_game_app_name.is_none());
+        assert!(!parsed.incomplete_install);
+
+        let game = epic_manifest_game(&parsed).unwrap();
+        assert_eq!(game.id, "epic_Fortnite");
+        assert!(game.is_installed);
+        assert!(game.executable_path.unwrap().ends_with("FortniteLauncher.exe"));
+        assert!(game.last_played.is_none());
+    }
+
+    #[test]
+    fn manifest_defaults_and_skipped_entries() {
+        // Senza AppName il manifest non è utilizzabile
+        assert!(parse_epic_local_manifest(&json!({ "DisplayName": "Senza nome" })).is_none());
+        assert!(parse_epic_local_manifest(&json!({ "AppName": "  " })).is_none());
+
+        // DisplayName assente: si usa AppName; installazione incompleta o cartella mancante
+        let partial = parse_epic_local_manifest(&json!({
+            "AppName": "Sugar",
+            "InstallLocation": "Z:\\non\\esiste",
+            "bIsIncompleteInstall": true
+        })).unwrap();
+        assert_eq!(partial.display_name, "Sugar");
+        let game = epic_manifest_game(&partial).unwrap();
+        assert!(!game.is_installed);
+        assert!(game.executable_path.is_none());
+
+        // DLC e Unreal Engine esclusi
+        let dlc = parse_epic_local_manifest(&json!({ "AppName": "Sugar_DLC1", "MainGameAppName": "Sugar" })).unwrap();
+        assert_eq!(dlc.main_game_app_name.as_deref(), Some("Sugar"));
+        assert!(epic_manifest_game(&dlc).is_none());
+        let engine = parse_epic_local_manifest(&json!({ "AppName": "UE_5.3" })).unwrap();
+        assert!(epic_manifest_game(&engine).is_none());
+    }
+}
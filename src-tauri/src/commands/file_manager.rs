@@ -1,28 +1,59 @@
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use chrono::Local;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use sha2::{Digest, Sha256};
 use tauri::command;
 
-/// Salva un file creando automaticamente un backup dell'originale
+/// Numero di backup mantenuti per file se non specificato
+const DEFAULT_MAX_BACKUPS: usize = 10;
+
+/// Estensione del file sidecar con l'hash SHA-256 di un backup
+const HASH_SIDECAR_EXT: &str = "sha256";
+
+/// Valore di `DecodedFile::encoding` quando l'encoding non è riconoscibile senza override
+pub(crate) const UNKNOWN_ENCODING: &str = "unknown";
+
+/// Prefisso dei nomi dei backup: `<timestamp>_<nome file>`
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+/// Marcatore dei backup di sicurezza creati da `restore_backup`: `<timestamp>_prerestore_<nome file>`
+const PRE_RESTORE_TAG: &str = "prerestore_";
+
+/// Salva un file creando automaticamente un backup dell'originale.
+///
+/// La scrittura è atomica (file temporaneo + rename) e vengono mantenuti al
+/// massimo `max_backups` backup per file, eliminando i più vecchi.
+/// Il file mantiene encoding e BOM dell'originale, salvo `encoding`/`with_bom` espliciti.
 #[command]
 pub async fn save_file_with_backup(
     file_path: String,
     content: String,
     create_backup: bool,
+    max_backups: Option<usize>,
+    encoding: Option<String>,
+    with_bom: Option<bool>,
 ) -> Result<SaveResult, String> {
     let path = Path::new(&file_path);
     let mut backup_path: Option<String> = None;
     
+    // Encoding da rispettare: va letto prima di sovrascrivere l'originale
+    let existing = if path.exists() {
+        Some(fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?)
+    } else {
+        None
+    };
+    let bytes = encode_for_save(&content, existing.as_deref(), encoding.as_deref(), with_bom)?;
+    
     // Se il file esiste e create_backup è true, crea un backup
     if path.exists() && create_backup {
-        let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+        let timestamp = Local::now().format(BACKUP_TIMESTAMP_FORMAT);
         let file_name = path.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("file");
         
-        let backup_dir = path.parent()
-            .map(|p| p.join(".gamestringer_backups"))
-            .ok_or("Cannot determine backup directory")?;
+        let backup_dir = backup_dir_for(path)?;
         
         // Crea la directory di backup se non esiste
         if !backup_dir.exists() {
@@ -32,17 +63,20 @@ pub async fn save_file_with_backup(
         
         let backup_file = backup_dir.join(format!("{}_{}", timestamp, file_name));
         
-        // Copia il file originale nel backup
-        fs::copy(path, &backup_file)
-            .map_err(|e| format!("Failed to create backup: {}", e))?;
+        // Copia il file originale nel backup, con hash sidecar
+        create_backup_copy(path, &backup_file)?;
         
         backup_path = Some(backup_file.to_string_lossy().to_string());
         println!("[FILE MANAGER] ✅ Backup creato: {}", backup_file.display());
+        
+        let removed = rotate_backups(&backup_dir, file_name, max_backups.unwrap_or(DEFAULT_MAX_BACKUPS))?;
+        if removed > 0 {
+            println!("[FILE MANAGER] 🗑️ Rimossi {} backup obsoleti", removed);
+        }
     }
     
     // Scrivi il nuovo contenuto
-    fs::write(path, &content)
-        .map_err(|e| format!("Failed to write file: {}", e))?;
+    write_atomic(path, &bytes)?;
     
     println!("[FILE MANAGER] ✅ File salvato: {}", file_path);
     
@@ -54,11 +88,263 @@ pub async fn save_file_with_backup(
     })
 }
 
-/// Legge un file
+/// Codifica il contenuto da salvare: encoding e BOM espliciti, altrimenti quelli del file
+/// esistente (UTF-8/UTF-16, BOM), altrimenti UTF-8 senza BOM.
+/// Un file esistente in un codepage legacy richiede `encoding` esplicito: indovinarlo
+/// rischierebbe di riscriverlo in mojibake.
+fn encode_for_save(content: &str, existing: Option<&[u8]>, encoding: Option<&str>, with_bom: Option<bool>) -> Result<Vec<u8>, String> {
+    let (detected, detected_bom) = match existing {
+        Some(bytes) => {
+            let (enc, bom_len) = detect_encoding(&bytes[..bytes.len().min(64 * 1024)]);
+            (enc, bom_len > 0)
+        }
+        None => (Some(UTF_8), false),
+    };
+    let label = match (encoding, detected) {
+        (Some(label), _) => label,
+        (None, Some(enc)) => enc.name(),
+        (None, None) => return Err(
+            "Cannot determine the file encoding: specify `encoding` (e.g. \"shift_jis\", \"windows-1252\")".to_string()
+        ),
+    };
+    encode_text(content, label, with_bom.unwrap_or(detected_bom))
+}
+
+/// Scrive su un file temporaneo nella stessa directory e lo rinomina sul
+/// file finale, così un'interruzione non lascia mai il file troncato
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let file_name = path.file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid file path")?;
+    let tmp_path = path.with_file_name(format!(".{}.tmp", file_name));
+    
+    {
+        let mut tmp = fs::File::create(&tmp_path)
+            .map_err(|e| format!("Failed to create temp file: {}", e))?;
+        tmp.write_all(bytes)
+            .and_then(|_| tmp.sync_all())
+            .map_err(|e| {
+                let _ = fs::remove_file(&tmp_path);
+                format!("Failed to write file: {}", e)
+            })?;
+    }
+    
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to replace file: {}", e)
+    })
+}
+
+/// Copia un file nella posizione di backup e salva l'hash accanto
+fn create_backup_copy(source: &Path, backup_file: &Path) -> Result<(), String> {
+    let bytes = fs::read(source)
+        .map_err(|e| format!("Failed to create backup: {}", e))?;
+    fs::write(backup_file, &bytes)
+        .map_err(|e| format!("Failed to create backup: {}", e))?;
+    fs::write(hash_sidecar_path(backup_file), sha256_hex(&bytes))
+        .map_err(|e| format!("Failed to write backup hash: {}", e))?;
+    Ok(())
+}
+
+/// Percorso del sidecar con l'hash di un backup
+fn hash_sidecar_path(backup_file: &Path) -> PathBuf {
+    let mut name = backup_file.as_os_str().to_owned();
+    name.push(".");
+    name.push(HASH_SIDECAR_EXT);
+    PathBuf::from(name)
+}
+
+/// Vero se `backup_name` è un backup di `file_name`: timestamp valido seguito esattamente dal nome,
+/// eventualmente con il marcatore dei backup di sicurezza pre-ripristino
+/// (i backup di `text.json` non devono comprendere quelli di `subtext.json` o `en_text.json`)
+fn is_backup_of(backup_name: &str, file_name: &str) -> bool {
+    backup_suffix(backup_name).is_some_and(|rest| {
+        rest == file_name || rest.strip_prefix(PRE_RESTORE_TAG) == Some(file_name)
+    })
+}
+
+/// Vero se `backup_name` è un backup di sicurezza pre-ripristino
+fn is_pre_restore_backup(backup_name: &str, file_name: &str) -> bool {
+    backup_suffix(backup_name).and_then(|rest| rest.strip_prefix(PRE_RESTORE_TAG)) == Some(file_name)
+}
+
+/// Parte del nome che segue `<timestamp>_`, se il timestamp è valido
+fn backup_suffix(backup_name: &str) -> Option<&str> {
+    let timestamp_len = "YYYYMMDD_HHMMSS".len();
+    let timestamp = backup_name.get(..timestamp_len)?;
+    chrono::NaiveDateTime::parse_from_str(timestamp, BACKUP_TIMESTAMP_FORMAT).ok()?;
+    backup_name.get(timestamp_len..)?.strip_prefix('_')
+}
+
+/// Elimina i backup più vecchi di un file oltre il limite. Ritorna quanti ne ha rimossi.
+fn rotate_backups(backup_dir: &Path, file_name: &str, max_backups: usize) -> Result<usize, String> {
+    let mut backups: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(backup_dir)
+        .map_err(|e| format!("Failed to read backup directory: {}", e))?
+        .flatten()
+        .filter(|e| is_backup_of(&e.file_name().to_string_lossy(), file_name))
+        .filter_map(|e| {
+            let modified = e.metadata().and_then(|m| m.modified()).ok()?;
+            Some((modified, e.path()))
+        })
+        .collect();
+    
+    if backups.len() <= max_backups {
+        return Ok(0);
+    }
+    
+    // Più vecchi prima (a parità di data, il nome con timestamp decide)
+    backups.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+    let excess = backups.len() - max_backups;
+    
+    for (_, path) in backups.iter().take(excess) {
+        fs::remove_file(path)
+            .map_err(|e| format!("Failed to remove old backup: {}", e))?;
+        let _ = fs::remove_file(hash_sidecar_path(path));
+    }
+    
+    Ok(excess)
+}
+
+/// Legge un file di testo rilevando automaticamente l'encoding
 #[command]
 pub async fn read_file_content(file_path: String) -> Result<String, String> {
-    fs::read_to_string(&file_path)
-        .map_err(|e| format!("Failed to read file: {}", e))
+    let bytes = fs::read(&file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    Ok(decode_text(&bytes, None)?.content)
+}
+
+/// Legge un file di testo restituendo anche l'encoding rilevato.
+///
+/// `encoding` forza un encoding specifico (es. "shift_jis", "windows-1252"),
+/// altrimenti vengono riconosciuti solo BOM, UTF-8 valido e UTF-16: per gli altri
+/// file `encoding` vale "unknown" e l'interfaccia deve chiedere quale usare.
+#[command]
+pub async fn read_file_with_encoding(
+    file_path: String,
+    encoding: Option<String>,
+) -> Result<DecodedFile, String> {
+    let bytes = fs::read(&file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    decode_text(&bytes, encoding.as_deref())
+}
+
+/// Scrive un file di testo nell'encoding indicato (tipicamente quello
+/// restituito da `read_file_with_encoding`), con BOM opzionale
+#[command]
+pub async fn write_file_with_encoding(
+    file_path: String,
+    content: String,
+    encoding: String,
+    with_bom: bool,
+) -> Result<SaveResult, String> {
+    let bytes = encode_text(&content, &encoding, with_bom)?;
+    write_atomic(Path::new(&file_path), &bytes)?;
+    
+    println!("[FILE MANAGER] ✅ File salvato ({}): {}", encoding, file_path);
+    
+    Ok(SaveResult {
+        success: true,
+        file_path,
+        backup_path: None,
+        message: format!("File saved as {} ({} bytes)", encoding, bytes.len()),
+    })
+}
+
+/// Decodifica bytes di testo: override esplicito, poi BOM, poi euristica
+/// (UTF-8 valido, UTF-16 senza BOM). I codepage legacy (Shift_JIS, Windows-1252...)
+/// non vengono indovinati: senza override il testo è decodificato come UTF-8 con
+/// sostituzioni e `encoding` vale "unknown".
+pub(crate) fn decode_text(bytes: &[u8], encoding_override: Option<&str>) -> Result<DecodedFile, String> {
+    let (encoding, bom_len) = match encoding_override {
+        Some(label) => {
+            let enc = Encoding::for_label(label.trim().as_bytes())
+                .ok_or_else(|| format!("Unsupported encoding: {}", label))?;
+            let bom_len = match Encoding::for_bom(bytes) {
+                Some((bom_enc, len)) if bom_enc == enc => len,
+                _ => 0,
+            };
+            (Some(enc), bom_len)
+        }
+        None => detect_encoding(bytes),
+    };
+    
+    let (content, had_errors) = encoding.unwrap_or(UTF_8).decode_without_bom_handling(&bytes[bom_len..]);
+    
+    Ok(DecodedFile {
+        content: content.into_owned(),
+        encoding: encoding.map_or(UNKNOWN_ENCODING, |enc| enc.name()).to_string(),
+        has_bom: bom_len > 0,
+        had_errors,
+    })
+}
+
+/// Encoding da BOM o, in mancanza, dall'euristica; restituisce anche la lunghezza del BOM.
+/// `None` se il testo non è UTF-8/UTF-16 (codepage legacy: serve un override).
+/// Basta un campione iniziale del file (es. i primi 64KB).
+pub(crate) fn detect_encoding(bytes: &[u8]) -> (Option<&'static Encoding>, usize) {
+    match Encoding::for_bom(bytes) {
+        Some((enc, len)) => (Some(enc), len),
+        None => (sniff_encoding(bytes), 0),
+    }
+}
+
+/// Riconosce un testo senza BOM solo se è UTF-8 valido o UTF-16 valido
+fn sniff_encoding(bytes: &[u8]) -> Option<&'static Encoding> {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => return Some(UTF_8),
+        // Sequenza incompleta solo in fondo (file letto troncato): resta UTF-8
+        Err(e) if e.error_len().is_none() => return Some(UTF_8),
+        Err(_) => {}
+    }
+    
+    // UTF-16 senza BOM: molti byte nulli in posizioni alternate
+    let sample = &bytes[..bytes.len().min(4096)];
+    if sample.len() >= 4 {
+        let pairs = sample.len() / 2;
+        let zeros_odd = sample.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+        let zeros_even = sample.iter().step_by(2).filter(|b| **b == 0).count();
+        let candidate = if zeros_odd * 10 > pairs * 3 && zeros_even * 10 < pairs {
+            Some(UTF_16LE)
+        } else if zeros_even * 10 > pairs * 3 && zeros_odd * 10 < pairs {
+            Some(UTF_16BE)
+        } else {
+            None
+        };
+        // Un byte dispari finale è un campione troncato, non un errore
+        let even_len = bytes.len() & !1;
+        if let Some(enc) = candidate.filter(|enc| !enc.decode_without_bom_handling(&bytes[..even_len]).1) {
+            return Some(enc);
+        }
+    }
+    
+    None
+}
+
+/// Codifica il testo nell'encoding indicato
+pub(crate) fn encode_text(content: &str, encoding_label: &str, with_bom: bool) -> Result<Vec<u8>, String> {
+    let encoding = Encoding::for_label(encoding_label.trim().as_bytes())
+        .ok_or_else(|| format!("Unsupported encoding: {}", encoding_label))?;
+    
+    // encoding_rs non codifica in UTF-16: lo gestiamo a mano
+    let mut bytes = Vec::with_capacity(content.len() + 3);
+    if encoding == UTF_16LE {
+        if with_bom { bytes.extend_from_slice(&[0xFF, 0xFE]); }
+        bytes.extend(content.encode_utf16().flat_map(|u| u.to_le_bytes()));
+    } else if encoding == UTF_16BE {
+        if with_bom { bytes.extend_from_slice(&[0xFE, 0xFF]); }
+        bytes.extend(content.encode_utf16().flat_map(|u| u.to_be_bytes()));
+    } else {
+        if with_bom && encoding == UTF_8 {
+            bytes.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
+        }
+        let (encoded, _, had_unmappable) = encoding.encode(content);
+        if had_unmappable {
+            return Err(format!("Text contains characters not representable in {}", encoding.name()));
+        }
+        bytes.extend_from_slice(&encoded);
+    }
+    
+    Ok(bytes)
 }
 
 /// Lista i backup disponibili per un file
@@ -69,9 +355,7 @@ pub async fn list_file_backups(file_path: String) -> Result<Vec<BackupInfo>, Str
         .and_then(|n| n.to_str())
         .unwrap_or("file");
     
-    let backup_dir = path.parent()
-        .map(|p| p.join(".gamestringer_backups"))
-        .ok_or("Cannot determine backup directory")?;
+    let backup_dir = backup_dir_for(path)?;
     
     if !backup_dir.exists() {
         return Ok(vec![]);
@@ -82,12 +366,18 @@ pub async fn list_file_backups(file_path: String) -> Result<Vec<BackupInfo>, Str
     if let Ok(entries) = fs::read_dir(&backup_dir) {
         for entry in entries.flatten() {
             let entry_name = entry.file_name().to_string_lossy().to_string();
-            if entry_name.ends_with(file_name) {
+            if is_backup_of(&entry_name, file_name) {
                 if let Ok(metadata) = entry.metadata() {
+                    let sha256 = fs::read_to_string(hash_sidecar_path(&entry.path()))
+                        .ok()
+                        .map(|h| h.trim().to_string())
+                        .or_else(|| fs::read(entry.path()).ok().map(|b| sha256_hex(&b)));
                     backups.push(BackupInfo {
                         path: entry.path().to_string_lossy().to_string(),
+                        pre_restore: is_pre_restore_backup(&entry_name, file_name),
                         name: entry_name,
                         size: metadata.len(),
+                        sha256,
                         created: metadata.modified()
                             .map(|t| t.duration_since(std::time::UNIX_EPOCH)
                                 .map(|d| d.as_secs())
@@ -105,30 +395,102 @@ pub async fn list_file_backups(file_path: String) -> Result<Vec<BackupInfo>, Str
     Ok(backups)
 }
 
-/// Ripristina un backup
+/// Directory dei backup associata a un file
+fn backup_dir_for(path: &Path) -> Result<PathBuf, String> {
+    path.parent()
+        .map(|p| p.join(".gamestringer_backups"))
+        .ok_or_else(|| "Cannot determine backup directory".to_string())
+}
+
+/// Hash SHA-256 (hex) di un contenuto
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Ripristina una specifica versione di backup.
+///
+/// `backup_id` è il `name` restituito da `list_file_backups`. Prima di
+/// sovrascrivere il file viene verificata l'integrità del backup e viene
+/// creato un backup di sicurezza dello stato corrente, il cui percorso è
+/// restituito in `backup_path` per poter annullare il ripristino. Anche i
+/// backup di sicurezza rientrano nella rotazione (`max_backups`).
 #[command]
-pub async fn restore_backup(backup_path: String, target_path: String) -> Result<SaveResult, String> {
-    // Prima crea un backup del file attuale
-    let result = save_file_with_backup(
-        target_path.clone(),
-        fs::read_to_string(&target_path).unwrap_or_default(),
-        true,
-    ).await?;
-    
-    // Poi ripristina il backup
-    let backup_content = fs::read_to_string(&backup_path)
+pub async fn restore_backup(
+    target_path: String,
+    backup_id: String,
+    expected_size: Option<u64>,
+    expected_sha256: Option<String>,
+    max_backups: Option<usize>,
+) -> Result<SaveResult, String> {
+    let target = Path::new(&target_path);
+    let file_name = target.file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid target path")?;
+    
+    // L'identificativo deve essere un nome file nella directory di backup
+    if !is_backup_of(&backup_id, file_name) {
+        return Err(format!("Invalid backup identifier for {}: {}", file_name, backup_id));
+    }
+    
+    let backup_dir = backup_dir_for(target)?;
+    let backup_file = backup_dir.join(&backup_id);
+    if !backup_file.is_file() {
+        return Err(format!("Backup not found: {}", backup_id));
+    }
+    
+    // Verifica integrità prima di toccare il file corrente
+    let backup_bytes = fs::read(&backup_file)
         .map_err(|e| format!("Failed to read backup: {}", e))?;
+    if let Some(size) = expected_size {
+        if backup_bytes.len() as u64 != size {
+            return Err(format!(
+                "Backup integrity check failed: expected {} bytes, found {}",
+                size, backup_bytes.len()
+            ));
+        }
+    }
+    // Senza hash esplicito si usa quello salvato accanto al backup
+    let expected_sha256 = expected_sha256.or_else(|| {
+        fs::read_to_string(hash_sidecar_path(&backup_file))
+            .ok()
+            .map(|h| h.trim().to_string())
+    });
+    if let Some(ref expected) = expected_sha256 {
+        let actual = sha256_hex(&backup_bytes);
+        if !actual.eq_ignore_ascii_case(expected) {
+            return Err(format!(
+                "Backup integrity check failed: hash mismatch (expected {}, found {})",
+                expected, actual
+            ));
+        }
+    }
+    
+    // Backup di sicurezza dello stato corrente
+    let mut safety_backup: Option<String> = None;
+    if target.exists() {
+        let timestamp = Local::now().format(BACKUP_TIMESTAMP_FORMAT);
+        let safety_file = backup_dir.join(format!("{}_{}{}", timestamp, PRE_RESTORE_TAG, file_name));
+        create_backup_copy(target, &safety_file)?;
+        safety_backup = Some(safety_file.to_string_lossy().to_string());
+        println!("[FILE MANAGER] ✅ Backup di sicurezza creato: {}", safety_file.display());
+        
+        let removed = rotate_backups(&backup_dir, file_name, max_backups.unwrap_or(DEFAULT_MAX_BACKUPS))?;
+        if removed > 0 {
+            println!("[FILE MANAGER] 🗑️ Rimossi {} backup obsoleti", removed);
+        }
+    }
     
-    fs::write(&target_path, &backup_content)
-        .map_err(|e| format!("Failed to restore backup: {}", e))?;
+    write_atomic(target, &backup_bytes)?;
     
-    println!("[FILE MANAGER] ✅ Backup ripristinato: {} -> {}", backup_path, target_path);
+    println!("[FILE MANAGER] ✅ Backup ripristinato: {} -> {}", backup_file.display(), target_path);
     
     Ok(SaveResult {
         success: true,
         file_path: target_path,
-        backup_path: result.backup_path,
-        message: "Backup ripristinato con successo".to_string(),
+        backup_path: safety_backup,
+        message: format!("Backup {} ripristinato con successo", backup_id),
     })
 }
 
@@ -234,7 +596,131 @@ pub struct SaveResult {
 #[derive(serde::Serialize)]
 pub struct BackupInfo {
     pub path: String,
+    /// Identificativo da passare a `restore_backup`
     pub name: String,
+    /// true per i backup di sicurezza creati prima di un ripristino
+    pub pre_restore: bool,
     pub size: u64,
+    pub sha256: Option<String>,
     pub created: u64,
 }
+
+#[derive(serde::Serialize)]
+pub struct DecodedFile {
+    pub content: String,
+    /// Nome dell'encoding (es. "UTF-8", "UTF-16LE", "Shift_JIS")
+    pub encoding: String,
+    pub has_bom: bool,
+    /// true se sono stati sostituiti caratteri non validi
+    pub had_errors: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_names_match_exactly() {
+        assert!(is_backup_of("20240105_101500_text.json", "text.json"));
+        assert!(!is_backup_of("20240105_101500_subtext.json", "text.json"));
+        assert!(!is_backup_of("20240105_101500_en_text.json", "text.json"));
+        assert!(!is_backup_of("20240105_101500_text.json.sha256", "text.json"));
+        assert!(!is_backup_of("notadate_12345_text.json", "text.json"));
+        assert!(!is_backup_of("text.json", "text.json"));
+        assert!(!is_backup_of("../20240105_101500_text.json", "text.json"));
+    }
+
+    #[test]
+    fn pre_restore_backups_are_listed_and_rotated() {
+        assert!(is_backup_of("20240105_101500_prerestore_text.json", "text.json"));
+        assert!(is_pre_restore_backup("20240105_101500_prerestore_text.json", "text.json"));
+        assert!(!is_pre_restore_backup("20240105_101500_text.json", "text.json"));
+        assert!(!is_backup_of("20240105_101500_prerestore_en_text.json", "text.json"));
+
+        let dir = tempfile::TempDir::new().unwrap();
+        for name in ["20240101_101500_prerestore_text.json", "20240102_101500_text.json", "20240103_101500_prerestore_text.json"] {
+            create_backup_copy_from_bytes(&dir.path().join(name), name.as_bytes());
+        }
+        assert_eq!(rotate_backups(dir.path(), "text.json", 2).unwrap(), 1);
+        assert!(!dir.path().join("20240101_101500_prerestore_text.json").exists());
+    }
+
+    #[test]
+    fn rotation_keeps_backups_of_other_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        for (i, name) in ["text.json", "text.json", "text.json", "subtext.json", "en_text.json"].iter().enumerate() {
+            let backup = dir.path().join(format!("2024010{}_101500_{}", i + 1, name));
+            create_backup_copy_from_bytes(&backup, name.as_bytes());
+        }
+
+        assert_eq!(rotate_backups(dir.path(), "text.json", 1).unwrap(), 2);
+        let mut left: Vec<String> = fs::read_dir(dir.path()).unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|n| !n.ends_with(".sha256"))
+            .collect();
+        left.sort();
+        assert_eq!(left, vec!["20240103_101500_text.json", "20240104_101500_subtext.json", "20240105_101500_en_text.json"]);
+    }
+
+    #[test]
+    fn save_keeps_original_encoding_and_bom() {
+        let text = "こんにちは\nHello=Ciao";
+        for (label, bom) in [("UTF-16LE", true), ("UTF-16BE", true), ("UTF-8", true), ("UTF-8", false)] {
+            let original = encode_text("元のテキスト", label, bom).unwrap();
+            let saved = encode_for_save(text, Some(&original), None, None).unwrap();
+            let decoded = decode_text(&saved, None).unwrap();
+            assert_eq!(decoded.content, text, "{}", label);
+            assert_eq!(decoded.encoding, Encoding::for_label(label.as_bytes()).unwrap().name(), "{}", label);
+            assert_eq!(decoded.has_bom, bom, "{}", label);
+        }
+
+        // Codepage legacy: solo con encoding esplicito
+        let original = encode_text("元のテキスト", "Shift_JIS", false).unwrap();
+        assert!(encode_for_save(text, Some(&original), None, None).is_err());
+        let saved = encode_for_save(text, Some(&original), Some("shift_jis"), None).unwrap();
+        assert_eq!(decode_text(&saved, Some("shift_jis")).unwrap().content, text);
+
+        // File nuovo: UTF-8 senza BOM, salvo richiesta esplicita
+        assert_eq!(encode_for_save("abc", None, None, None).unwrap(), b"abc");
+        assert_eq!(encode_for_save("abc", None, Some("utf-8"), Some(true)).unwrap(), b"\xEF\xBB\xBFabc");
+    }
+
+    #[tokio::test]
+    async fn restore_rejects_backups_of_other_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let target = dir.path().join("text.json");
+        fs::write(&target, b"current").unwrap();
+        let backup_dir = backup_dir_for(&target).unwrap();
+        fs::create_dir_all(&backup_dir).unwrap();
+        create_backup_copy_from_bytes(&backup_dir.join("20240105_101500_en_text.json"), b"english");
+
+        let target_path = target.to_string_lossy().to_string();
+        let result = restore_backup(target_path, "20240105_101500_en_text.json".to_string(), None, None, None).await;
+        assert!(result.is_err());
+        assert_eq!(fs::read(&target).unwrap(), b"current");
+    }
+
+    #[test]
+    fn legacy_codepages_are_not_guessed() {
+        // "Café à über" in Windows-1252: sarebbe Shift_JIS valido
+        let latin1 = encode_text("Caf\u{e9} \u{e0} \u{fc}ber=Ciao", "windows-1252", false).unwrap();
+        let decoded = decode_text(&latin1, None).unwrap();
+        assert_eq!(decoded.encoding, UNKNOWN_ENCODING);
+        assert!(decoded.had_errors);
+
+        let decoded = decode_text(&latin1, Some("windows-1252")).unwrap();
+        assert_eq!(decoded.content, "Caf\u{e9} \u{e0} \u{fc}ber=Ciao");
+        assert!(!decoded.had_errors);
+
+        let utf16 = encode_text("Hello=Ciao", "utf-16le", false).unwrap();
+        assert_eq!(decode_text(&utf16, None).unwrap().encoding, "UTF-16LE");
+    }
+
+    fn create_backup_copy_from_bytes(backup: &Path, bytes: &[u8]) {
+        fs::write(backup, bytes).unwrap();
+        fs::write(hash_sidecar_path(backup), sha256_hex(bytes)).unwrap();
+        // Date di modifica distinte: l'ordine di rotazione le usa prima del nome
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+}
@@ -65,9 +65,9 @@ pub fn is_vr_game(game_name: &str) -> bool {
 
 // Funzione helper per caricare giochi Steam dal file JSON (fallback)
 async fn load_steam_games_from_json() -> Result<Vec<GameInfo>, String> {
-    let steam_games_path = r"C:\dev\GameStringer\steam_owned_games.json";
+    let steam_games_path = super::steam::owned_games_json_path();
     
-    match tokio::fs::read_to_string(steam_games_path).await {
+    match tokio::fs::read_to_string(&steam_games_path).await {
         Ok(data) => {
             match serde_json::from_str::<Vec<serde_json::Value>>(&data) {
                 Ok(steam_games) => {
@@ -208,18 +208,22 @@ pub async fn detect_engine_for_game(
 ) -> Result<DetectEngineResult, String> {
     log::info!("🔍 Rilevamento motore per: {} (path: {:?})", game_name, install_path);
     
-    // 1. Prima prova rilevamento locale (file system + database nomi)
-    let mut engine = detect_game_engine_smart(&game_name, install_path.as_ref());
+    // 1. Prima prova rilevamento locale (file firma su disco, poi database nomi)
+    let mut detection = crate::engine_detector::detect_engine_with_evidence(
+        &game_name,
+        install_path.as_deref().map(Path::new),
+    );
     
     // 2. Se non trovato, cerca su PCGamingWiki
-    if engine.is_none() || engine.as_ref().map(|e| e == "Unknown").unwrap_or(false) {
+    if detection.engine_name == "Unknown" {
         log::info!("🌐 Ricerca engine su PCGamingWiki per: {}", game_name);
         if let Some(web_engine) = search_engine_on_pcgamingwiki(&game_name).await {
-            engine = Some(web_engine);
+            detection.engine_name = web_engine;
+            detection.artifact = Some("pcgamingwiki".to_string());
         }
     }
     
-    let engine_str = engine.unwrap_or_else(|| "Unknown".to_string());
+    let engine_str = detection.engine_name.clone();
     
     // Determina suggerimenti in base al motore
     let (can_patch, patch_tool, patch_description) = match engine_str.as_str() {
@@ -260,13 +264,17 @@ pub async fn detect_engine_for_game(
         ),
     };
     
-    log::info!("✅ Motore rilevato: {} (can_patch: {})", engine_str, can_patch);
+    log::info!("✅ Motore rilevato: {} (can_patch: {}, confidenza: {:?}, artefatto: {:?})",
+        engine_str, can_patch, detection.confidence, detection.artifact);
     
     Ok(DetectEngineResult {
         engine: engine_str,
         can_patch,
         patch_tool,
         patch_description,
+        confidence: detection.confidence,
+        artifact: detection.artifact,
+        scripting_backend: detection.scripting_backend,
     })
 }
 
@@ -276,6 +284,11 @@ pub struct DetectEngineResult {
     pub can_patch: bool,
     pub patch_tool: Option<String>,
     pub patch_description: Option<String>,
+    /// Affidabilità del rilevamento (high = file firma trovato su disco)
+    pub confidence: crate::engine_detector::DetectionConfidence,
+    /// File, cartella o fonte che ha determinato il rilevamento
+    pub artifact: Option<String>,
+    pub scripting_backend: Option<String>,
 }
 
 #[derive(serde::Serialize)]
@@ -422,7 +435,7 @@ pub async fn force_refresh_all_games(
             log::info!("🎮 FORCE REFRESH: Trovati {} giochi Epic Games", epic_games.len());
             let epic_app_names: Vec<String> = epic_games.iter().map(|g| g.name.clone()).collect();
             let epic_covers = match epic::get_epic_covers_batch(epic_app_names).await {
-                Ok(covers) => covers,
+                Ok(batch) => batch.covers,
                 Err(e) => {
                     log::warn!("⚠️ Errore recupero copertine Epic: {}", e);
                     HashMap::new()
@@ -955,7 +968,7 @@ pub async fn get_games(
                             
                             // Recupera le copertine Epic in batch
                             let epic_covers = match epic::get_epic_covers_batch(epic_app_names).await {
-                                Ok(covers) => covers,
+                                Ok(batch) => batch.covers,
                                 Err(e) => {
                                     log::warn!("⚠️ Errore recupero copertine Epic: {}", e);
                                     HashMap::new()
@@ -1460,16 +1473,8 @@ async fn get_steam_library_folders() -> Result<Vec<String>, String> {
     
     match fs::read_to_string(&library_folders_vdf).await {
         Ok(data) => {
-            for line in data.lines() {
-                if let Some(captures) = regex::Regex::new(r#""path"\s+"(.+?)""#)
-                    .unwrap()
-                    .captures(line) 
-                {
-                    if let Some(path) = captures.get(1) {
-                        let normalized_path = path.as_str().replace("\\\\", "/");
-                        library_paths.push(normalized_path);
-                    }
-                }
+            for folder in crate::vdf::parse_library_folders(&data) {
+                library_paths.push(folder.path.replace('\\', "/"));
             }
         }
         Err(e) => {
@@ -1523,83 +1528,171 @@ async fn parse_steam_manifest(manifest_path: &Path) -> Result<Option<GameScanRes
     let content = fs::read_to_string(manifest_path).await
         .map_err(|e| format!("Errore lettura manifest: {}", e))?;
     
-    let mut app_id: Option<String> = None;
-    let mut name = None;
-    let mut install_dir = None;
-    let mut state_flags: Option<u32> = None;
+    let Ok(manifest) = crate::vdf::AppManifest::parse(&content) else {
+        return Ok(None);
+    };
     
-    for line in content.lines() {
-        let line = line.trim();
-        
-        if line.starts_with("\"appid\"") {
-            if let Some(captures) = regex::Regex::new(r#""appid"\s+"(\d+)""#)
-                .unwrap()
-                .captures(line) 
-            {
-                app_id = captures.get(1).and_then(|m| m.as_str().parse().ok());
-            }
-        } else if line.starts_with("\"name\"") {
-            if let Some(captures) = regex::Regex::new(r#""name"\s+"(.+?)""#)
-                .unwrap()
-                .captures(line) 
-            {
-                name = captures.get(1).map(|m| m.as_str().to_string());
-            }
-        } else if line.starts_with("\"installdir\"") {
-            if let Some(captures) = regex::Regex::new(r#""installdir"\s+"(.+?)""#)
-                .unwrap()
-                .captures(line) 
-            {
-                install_dir = captures.get(1).map(|m| m.as_str().to_string());
-            }
-        } else if line.starts_with("\"StateFlags\"") {
-            if let Some(captures) = regex::Regex::new(r#""StateFlags"\s+"(\d+)""#)
-                .unwrap()
-                .captures(line) 
-            {
-                state_flags = captures.get(1).and_then(|m| m.as_str().parse().ok());
-            }
-        }
+    // Verifica se il gioco è installato (StateFlags & 4 == 4)
+    if !manifest.is_fully_installed() {
+        return Ok(None);
     }
+    let (Some(name), Some(install_dir)) = (manifest.name, manifest.install_dir) else {
+        return Ok(None);
+    };
+    let app_id = manifest.appid;
     
-    if let (Some(app_id), Some(name), Some(install_dir)) = (app_id, name, install_dir) {
-        // Verifica se il gioco è installato (StateFlags & 4 == 4)
-        let is_installed = state_flags.map_or(false, |flags| flags & 4 == 4);
-        
-        if is_installed {
-            let library_path = manifest_path.parent()
-                .and_then(|p| p.parent())
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_default();
-            
-            let install_path = format!("{}/steamapps/common/{}", library_path, install_dir);
-            
-            // Trova l'eseguibile principale
-            let executable_path = find_largest_exe(&install_path).await
-                .unwrap_or_else(|| format!("{}/game.exe", install_path));
-            
-            let game_result = GameScanResult {
-                title: name.clone(),
-                path: install_path.clone(),
-                executable_path: Some(executable_path),
-                app_id: Some(app_id.to_string()),
-                source: "Steam".to_string(),
-                is_installed: true,
-                id: format!("steam_{}", app_id),
-                platform: "Steam".to_string(),
-                header_image: None,
-                is_vr: is_vr_game(&name),
-                engine: detect_game_engine_smart(&name, Some(&install_path)),
-                supported_languages: None,
-                genres: None,
-                last_played: None,
-            };
-            
-            return Ok(Some(game_result));
+    let library_path = manifest_path.parent()
+        .and_then(|p| p.parent())
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    
+    let install_path = format!("{}/steamapps/common/{}", library_path, install_dir);
+    
+    // Trova l'eseguibile principale
+    let executable_path = find_largest_exe(&install_path).await
+        .unwrap_or_else(|| format!("{}/game.exe", install_path));
+    
+    let game_result = GameScanResult {
+        title: name.clone(),
+        path: install_path.clone(),
+        executable_path: Some(executable_path),
+        app_id: Some(app_id.to_string()),
+        source: "Steam".to_string(),
+        is_installed: true,
+        id: format!("steam_{}", app_id),
+        platform: "Steam".to_string(),
+        header_image: None,
+        is_vr: is_vr_game(&name),
+        engine: detect_game_engine_smart(&name, Some(&install_path)),
+        supported_languages: None,
+        genres: None,
+        last_played: None,
+    };
+    
+    Ok(Some(game_result))
+}
+
+/// Parti di nome che identificano installer, redistribuibili e tool di supporto
+const NON_GAME_EXE_HINTS: &[&str] = &[
+    "unins", "setup", "install", "redist", "vcredist", "vc_redist", "dxsetup", "directx",
+    "dotnet", "crashhandler", "crashreport", "ue4prereq", "ueprereq", "easyanticheat",
+    "battleye", "start_protected_game",
+];
+
+fn is_game_exe_candidate(path: &Path) -> bool {
+    let is_exe = path.extension()
+        .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("exe"));
+    let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+    is_exe && !NON_GAME_EXE_HINTS.iter().any(|hint| name.contains(hint))
+}
+
+/// Eseguibili candidati direttamente dentro `dir`
+fn exe_candidates(dir: &Path) -> Vec<std::path::PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && is_game_exe_candidate(path))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// Unreal: `<Progetto>/Binaries/Win64/<Progetto>-Win64-Shipping.exe` (fino a 3 livelli).
+/// `Engine/` contiene solo tool del motore (CrashReportClient, prerequisiti) e viene saltata.
+fn find_unreal_shipping_exe(dir: &Path, depth: u32) -> Option<std::path::PathBuf> {
+    let entries: Vec<_> = std::fs::read_dir(dir).ok()?.flatten().map(|e| e.path()).collect();
+    
+    let shipping = entries.iter().find(|path| {
+        path.is_file() && is_game_exe_candidate(path) && path.file_name()
+            .is_some_and(|n| n.to_string_lossy().to_lowercase().ends_with("-shipping.exe"))
+    });
+    if let Some(path) = shipping {
+        return Some(path.clone());
+    }
+    
+    if depth == 0 {
+        return None;
+    }
+    entries.iter()
+        .filter(|path| path.is_dir())
+        .filter(|path| !matches!(
+            path.file_name().map(|n| n.to_string_lossy().to_lowercase()).as_deref(),
+            Some("engine" | "content" | "saved" | "_commonredist" | "redist")
+        ))
+        .find_map(|path| find_unreal_shipping_exe(path, depth - 1))
+}
+
+/// Unity: l'exe accanto alla cartella `<Nome>_Data`, altrimenti quello col nome della cartella del gioco
+fn find_unity_exe(install_path: &Path, candidates: &[std::path::PathBuf]) -> Option<std::path::PathBuf> {
+    let stem = |path: &std::path::PathBuf| path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    
+    if let Some(path) = candidates.iter().find(|path| install_path.join(format!("{}_Data", stem(path))).is_dir()) {
+        return Some(path.clone());
+    }
+    
+    let normalize = |name: &str| name.to_lowercase().chars().filter(|c| c.is_alphanumeric()).collect::<String>();
+    let folder = install_path.file_name().map(|n| normalize(&n.to_string_lossy())).unwrap_or_default();
+    candidates.iter().find(|path| normalize(&stem(path)) == folder).cloned()
+}
+
+/// Eseguibile più grande (a parità di dimensione il più recente)
+fn largest_recent_exe(candidates: &[std::path::PathBuf]) -> Option<std::path::PathBuf> {
+    candidates.iter()
+        .filter_map(|path| {
+            let metadata = path.metadata().ok()?;
+            Some((metadata.len(), metadata.modified().ok(), path))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)))
+        .map(|(_, _, path)| path.clone())
+}
+
+/// Trova l'eseguibile principale di un gioco installato.
+/// Unreal: `*-Win64-Shipping.exe`; Unity: exe abbinato alla cartella `_Data`; altrimenti
+/// l'exe più grande/recente nella cartella principale (o in `bin`/`x64`). Senza engine
+/// noto vengono provate tutte le strategie.
+pub fn find_game_executable(install_path: &Path, engine: Option<&str>) -> Option<std::path::PathBuf> {
+    if !install_path.is_dir() {
+        return None;
+    }
+    
+    let engine = engine.map(|e| e.to_lowercase()).unwrap_or_default();
+    let known_engine = !engine.is_empty() && engine != "unknown";
+    let candidates = exe_candidates(install_path);
+    
+    if !known_engine || engine.contains("unreal") {
+        if let Some(path) = find_unreal_shipping_exe(install_path, 3) {
+            return Some(path);
+        }
+    }
+    if !known_engine || engine.contains("unity") {
+        if let Some(path) = find_unity_exe(install_path, &candidates) {
+            return Some(path);
         }
     }
     
-    Ok(None)
+    largest_recent_exe(&candidates).or_else(|| {
+        ["bin", "Bin", "x64", "bin64", "Binaries"].iter()
+            .map(|sub| install_path.join(sub))
+            .filter(|dir| dir.is_dir())
+            .find_map(|dir| largest_recent_exe(&exe_candidates(&dir)))
+    })
+}
+
+/// Comando Tauri per risolvere l'eseguibile principale di un gioco
+#[tauri::command]
+pub async fn resolve_game_executable(install_path: String, engine: Option<String>) -> Result<Option<String>, String> {
+    log::info!("🔍 Ricerca eseguibile principale in: {}", install_path);
+    
+    let executable = tauri::async_runtime::spawn_blocking(move || {
+        find_game_executable(Path::new(&install_path), engine.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    
+    match &executable {
+        Some(path) => log::info!("✅ Eseguibile trovato: {}", path.display()),
+        None => log::warn!("⚠️ Nessun eseguibile principale trovato"),
+    }
+    Ok(executable.map(|path| path.to_string_lossy().to_string()))
 }
 
 // Funzione per trovare l'eseguibile più grande in una directory
@@ -1748,3 +1841,78 @@ async fn find_largest_exe(dir: &str) -> Option<String> {
         Err(_) => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn touch(root: &Path, relative: &str, size: usize) -> std::path::PathBuf {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, vec![0u8; size]).unwrap();
+        path
+    }
+
+    #[test]
+    fn unreal_shipping_exe_skips_engine_tools() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        // `Engine/` contiene exe "-Shipping" del motore che non sono il gioco
+        touch(root, "Engine/Binaries/Win64/CrashReportClient-Win64-Shipping.exe", 10);
+        touch(root, "Engine/Extras/Tool-Win64-Shipping.exe", 10);
+        let game = touch(root, "MyGame/Binaries/Win64/MyGame-Win64-Shipping.exe", 10);
+        touch(root, "MyGame.exe", 100);
+
+        assert_eq!(find_game_executable(root, Some("Unreal Engine")), Some(game.clone()));
+        assert_eq!(find_game_executable(root, None), Some(game));
+    }
+
+    #[test]
+    fn unreal_shipping_exe_ignores_non_game_executables() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        touch(root, "Game/Binaries/Win64/CrashReportClient-Win64-Shipping.exe", 10);
+        touch(root, "Game/Binaries/Win64/EasyAntiCheat-Win64-Shipping.exe", 10);
+        let launcher = touch(root, "Game.exe", 50);
+
+        // Nessun vero shipping exe: si ricade sull'exe principale della cartella
+        assert_eq!(find_game_executable(root, Some("unreal")), Some(launcher));
+    }
+
+    #[test]
+    fn unity_exe_matches_data_folder() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let game = touch(root, "Hollow Knight.exe", 10);
+        touch(root, "Hollow Knight_Data/globalgamemanagers", 1);
+        touch(root, "UnityCrashHandler64.exe", 500);
+        touch(root, "unins000.exe", 1000);
+
+        assert_eq!(find_game_executable(root, Some("Unity")), Some(game));
+    }
+
+    #[test]
+    fn largest_exe_fallback_and_subfolders() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        touch(root, "small.exe", 10);
+        let big = touch(root, "big.exe", 100);
+        touch(root, "vcredist_x64.exe", 1000);
+        assert_eq!(find_game_executable(root, Some("Custom")), Some(big));
+
+        // Nessun exe nella radice: cerca in `bin`
+        let nested = TempDir::new().unwrap();
+        let game = touch(nested.path(), "bin/game.exe", 10);
+        touch(nested.path(), "readme.txt", 10);
+        assert_eq!(find_game_executable(nested.path(), Some("Custom")), Some(game));
+    }
+
+    #[test]
+    fn missing_or_empty_folder_has_no_executable() {
+        let dir = TempDir::new().unwrap();
+        assert!(find_game_executable(dir.path(), None).is_none());
+        assert!(find_game_executable(&dir.path().join("missing"), None).is_none());
+    }
+}
@@ -4,6 +4,8 @@ use std::path::PathBuf;
 use uuid::Uuid;
 use chrono::Utc;
 
+use super::translation_memory::calculate_similarity;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlossaryEntry {
     pub id: String,
@@ -12,6 +14,9 @@ pub struct GlossaryEntry {
     pub case_sensitive: bool,
     pub context: Option<String>,
     pub notes: Option<String>,
+    /// Traduzioni alternative (es. da import con strategia "keep_both")
+    #[serde(default)]
+    pub variants: Vec<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -101,10 +106,19 @@ pub async fn create_glossary(
 pub async fn get_glossary(game_id: String) -> Result<Option<GameGlossary>, String> {
     log::info!("📖 Caricamento glossario per gioco: {}", game_id);
     
-    let path = get_glossary_path(&game_id)?;
+    let glossary = load_glossary_sync(&game_id)?;
+    match &glossary {
+        Some(g) => log::info!("✅ Glossario caricato: {} voci", g.entries.len()),
+        None => log::info!("ℹ️ Nessun glossario trovato per: {}", game_id),
+    }
+    Ok(glossary)
+}
+
+/// Carica il glossario di un gioco da disco (usato anche dalla traduzione offline)
+pub(crate) fn load_glossary_sync(game_id: &str) -> Result<Option<GameGlossary>, String> {
+    let path = get_glossary_path(game_id)?;
     
     if !path.exists() {
-        log::info!("ℹ️ Nessun glossario trovato per: {}", game_id);
         return Ok(None);
     }
     
@@ -114,7 +128,6 @@ pub async fn get_glossary(game_id: String) -> Result<Option<GameGlossary>, Strin
     let glossary: GameGlossary = serde_json::from_str(&content)
         .map_err(|e| format!("Errore parsing glossario: {}", e))?;
     
-    log::info!("✅ Glossario caricato: {} voci", glossary.entries.len());
     Ok(Some(glossary))
 }
 
@@ -165,6 +178,7 @@ pub async fn add_glossary_entry(
         case_sensitive,
         context,
         notes,
+        variants: Vec::new(),
         created_at: now.clone(),
         updated_at: now.clone(),
     };
@@ -286,20 +300,146 @@ pub async fn export_glossary(game_id: String) -> Result<String, String> {
     Ok(json)
 }
 
+/// Strategia di merge per l'import di un glossario
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GlossaryMergeStrategy {
+    /// Mantiene la traduzione esistente
+    KeepExisting,
+    /// Sovrascrive con la traduzione importata
+    Overwrite,
+    /// Mantiene l'esistente e aggiunge l'importata come variante
+    KeepBoth,
+}
+
+/// Termine importato in conflitto con uno esistente
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryConflict {
+    pub original: String,
+    pub existing_translation: String,
+    pub imported_translation: String,
+    /// Azione applicata secondo la strategia
+    pub resolution: GlossaryMergeStrategy,
+}
+
+/// Esito dell'import di un glossario
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryImportResult {
+    pub glossary: GameGlossary,
+    pub added: usize,
+    pub unchanged: usize,
+    pub conflicts: Vec<GlossaryConflict>,
+}
+
 #[tauri::command]
-pub async fn import_glossary(json_content: String) -> Result<GameGlossary, String> {
+pub async fn import_glossary(
+    json_content: String,
+    strategy: Option<GlossaryMergeStrategy>,
+) -> Result<GlossaryImportResult, String> {
     log::info!("📥 Importazione glossario...");
     
     let export: GlossaryExport = serde_json::from_str(&json_content)
         .map_err(|e| format!("Errore parsing JSON: {}", e))?;
     
-    let mut glossary = export.glossary;
-    glossary.updated_at = Utc::now().to_rfc3339();
+    let strategy = strategy.unwrap_or(GlossaryMergeStrategy::KeepExisting);
+    let imported = export.glossary;
+    
+    let mut result = match get_glossary(imported.game_id.clone()).await? {
+        Some(existing) => merge_glossary(existing, imported, strategy),
+        None => {
+            let added = imported.entries.len();
+            GlossaryImportResult {
+                glossary: imported,
+                added,
+                unchanged: 0,
+                conflicts: Vec::new(),
+            }
+        }
+    };
+    result.glossary.updated_at = Utc::now().to_rfc3339();
     
-    save_glossary_internal(&glossary)?;
+    save_glossary_internal(&result.glossary)?;
     
-    log::info!("✅ Glossario importato: {} voci", glossary.entries.len());
-    Ok(glossary)
+    log::info!(
+        "✅ Glossario importato: {} voci ({} nuove, {} conflitti)",
+        result.glossary.entries.len(),
+        result.added,
+        result.conflicts.len()
+    );
+    Ok(result)
+}
+
+/// Unisce le voci importate nel glossario esistente, segnalando i termini
+/// già presenti con una traduzione diversa
+fn merge_glossary(
+    mut existing: GameGlossary,
+    imported: GameGlossary,
+    strategy: GlossaryMergeStrategy,
+) -> GlossaryImportResult {
+    let now = Utc::now().to_rfc3339();
+    let mut added = 0;
+    let mut unchanged = 0;
+    let mut conflicts = Vec::new();
+    
+    for incoming in imported.entries {
+        let key = incoming.original.to_lowercase();
+        let current = existing.entries.iter_mut().find(|e| {
+            if e.case_sensitive || incoming.case_sensitive {
+                e.original == incoming.original
+            } else {
+                e.original.to_lowercase() == key
+            }
+        });
+        
+        let entry = match current {
+            Some(entry) => entry,
+            None => {
+                existing.entries.push(incoming);
+                added += 1;
+                continue;
+            }
+        };
+        
+        if entry.translation == incoming.translation
+            || entry.variants.contains(&incoming.translation)
+        {
+            unchanged += 1;
+            continue;
+        }
+        
+        conflicts.push(GlossaryConflict {
+            original: entry.original.clone(),
+            existing_translation: entry.translation.clone(),
+            imported_translation: incoming.translation.clone(),
+            resolution: strategy,
+        });
+        
+        match strategy {
+            GlossaryMergeStrategy::KeepExisting => {}
+            GlossaryMergeStrategy::Overwrite => {
+                entry.translation = incoming.translation;
+                entry.updated_at = now.clone();
+            }
+            GlossaryMergeStrategy::KeepBoth => {
+                entry.variants.push(incoming.translation);
+                entry.updated_at = now.clone();
+            }
+        }
+    }
+    
+    // Unisce anche i termini da non tradurre
+    for term in imported.metadata.do_not_translate {
+        if !existing.metadata.do_not_translate.contains(&term) {
+            existing.metadata.do_not_translate.push(term);
+        }
+    }
+    
+    GlossaryImportResult {
+        glossary: existing,
+        added,
+        unchanged,
+        conflicts,
+    }
 }
 
 #[tauri::command]
@@ -330,6 +470,114 @@ pub async fn search_glossary(
     Ok(replacements)
 }
 
+/// Risultato di una ricerca nel glossario con punteggio di somiglianza
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryMatch {
+    pub entry: GlossaryEntry,
+    /// Somiglianza 0.0 - 1.0 (1.0 = match esatto)
+    pub score: f64,
+    /// Porzione del testo che ha prodotto il match
+    pub matched_text: String,
+}
+
+/// Cerca nel glossario con punteggio per ogni voce.
+/// `mode` = "exact" (default, sottostringa) oppure "fuzzy" (Levenshtein su finestre di parole).
+#[tauri::command]
+pub async fn search_glossary_scored(
+    game_id: String,
+    text: String,
+    mode: Option<String>,
+    min_score: Option<f64>,
+    max_results: Option<usize>,
+) -> Result<Vec<GlossaryMatch>, String> {
+    let glossary = match get_glossary(game_id).await? {
+        Some(g) => g,
+        None => return Ok(Vec::new()),
+    };
+    
+    let fuzzy = matches!(mode.as_deref(), Some("fuzzy"));
+    let min_score = min_score.unwrap_or(0.75).clamp(0.0, 1.0);
+    let max_results = max_results.unwrap_or(20);
+    
+    let text_lower = text.to_lowercase();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    
+    let mut matches: Vec<GlossaryMatch> = glossary.entries.iter()
+        .filter_map(|entry| {
+            // Match esatto: sempre punteggio pieno
+            let exact = if entry.case_sensitive {
+                text.contains(&entry.original)
+            } else {
+                text_lower.contains(&entry.original.to_lowercase())
+            };
+            if exact {
+                return Some(GlossaryMatch {
+                    entry: entry.clone(),
+                    score: 1.0,
+                    matched_text: entry.original.clone(),
+                });
+            }
+            if !fuzzy {
+                return None;
+            }
+            
+            let (score, matched_text) = best_fuzzy_window(&words, entry);
+            if score >= min_score {
+                Some(GlossaryMatch {
+                    entry: entry.clone(),
+                    score,
+                    matched_text,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+    
+    matches.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    matches.truncate(max_results);
+    
+    Ok(matches)
+}
+
+/// Confronta il termine con tutte le finestre di parole del testo di lunghezza
+/// simile e ritorna il miglior punteggio con la finestra corrispondente
+fn best_fuzzy_window(words: &[&str], entry: &GlossaryEntry) -> (f64, String) {
+    let term = if entry.case_sensitive {
+        entry.original.clone()
+    } else {
+        entry.original.to_lowercase()
+    };
+    let term_words = term.split_whitespace().count().max(1);
+    
+    let mut best = (0.0, String::new());
+    
+    // Considera anche finestre di una parola in più/meno per coprire articoli e flessioni
+    let min_len = term_words.saturating_sub(1).max(1);
+    let max_len = term_words + 1;
+    
+    for len in min_len..=max_len {
+        if len > words.len() {
+            break;
+        }
+        for window in words.windows(len) {
+            let candidate = window.join(" ");
+            let candidate = candidate.trim_matches(|c: char| c.is_ascii_punctuation());
+            let compare = if entry.case_sensitive {
+                candidate.to_string()
+            } else {
+                candidate.to_lowercase()
+            };
+            let score = calculate_similarity(&term, &compare);
+            if score > best.0 {
+                best = (score, candidate.to_string());
+            }
+        }
+    }
+    
+    best
+}
+
 fn save_glossary_internal(glossary: &GameGlossary) -> Result<(), String> {
     let path = get_glossary_path(&glossary.game_id)?;
     
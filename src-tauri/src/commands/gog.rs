@@ -4,7 +4,6 @@ use std::time::Duration;
 use std::path::Path;
 use std::fs;
 use reqwest::Client;
-use once_cell::sync::Lazy;
 use crate::commands::library::InstalledGame;
 use std::path::PathBuf;
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
@@ -13,14 +12,10 @@ use rand::{RngCore, rngs::OsRng};
 use base64::{Engine as _, engine::general_purpose};
 use chrono;
 
-// Client HTTP globale per GOG
-static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
-    Client::builder()
-        .timeout(Duration::from_secs(30))
-        .user_agent("GameStringer/1.0")
-        .build()
-        .expect("Failed to create HTTP client")
-});
+// Client HTTP per GOG (timeout dalle preferenze di rete)
+fn http_client() -> Client {
+    crate::http::client_for("gog")
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GogGame {
@@ -123,7 +118,7 @@ pub async fn get_gog_game_details(game_id: String) -> Result<GogGame, String> {
     // GOG API endpoint pubblico
     let url = format!("https://api.gog.com/products/{}?expand=description", game_id);
     
-    match HTTP_CLIENT.get(&url).send().await {
+    match http_client().get(&url).send().await {
         Ok(response) => {
             if response.status().is_success() {
                 match response.json::<serde_json::Value>().await {
@@ -146,7 +141,7 @@ pub async fn search_gog_game(query: String) -> Result<Vec<GogGame>, String> {
     let url = format!("https://api.gog.com/products?search={}&limit=10", 
                      urlencoding::encode(&query));
     
-    match HTTP_CLIENT.get(&url).send().await {
+    match http_client().get(&url).send().await {
         Ok(response) => {
             if response.status().is_success() {
                 match response.json::<serde_json::Value>().await {
@@ -237,7 +232,7 @@ pub async fn test_gog_connection() -> Result<String, String> {
     // Prima prova l'API principale
     let url = "https://api.gog.com/products?limit=1";
     
-    match HTTP_CLIENT.get(url).send().await {
+    match http_client().get(url).send().await {
         Ok(response) => {
             if response.status().is_success() {
                 return Ok(format!("Connessione GOG API riuscita (Status: {} OK)", response.status().as_u16()));
@@ -255,7 +250,7 @@ pub async fn test_gog_connection() -> Result<String, String> {
     
     // Fallback: verifica se il sito GOG è raggiungibile
     let fallback_url = "https://www.gog.com";
-    match HTTP_CLIENT.head(fallback_url).send().await {
+    match http_client().head(fallback_url).send().await {
         Ok(response) => {
             if response.status().is_success() || response.status().as_u16() == 301 || response.status().as_u16() == 302 {
                 Ok("Connessione GOG riuscita (API temporaneamente non disponibile, ma servizio raggiungibile)".to_string())
@@ -601,7 +596,7 @@ pub async fn test_gog_auth(email: &str, password: &str) -> Result<GogUser, Strin
 
 // Funzioni per gestione credenziali GOG
 
-fn get_gog_credentials_path() -> Result<PathBuf, String> {
+pub(crate) fn get_gog_credentials_path() -> Result<PathBuf, String> {
     let mut path = std::env::current_dir()
         .map_err(|e| format!("Errore getting current dir: {}", e))?;
     path.push(".cache");
@@ -616,6 +611,11 @@ fn get_gog_credentials_path() -> Result<PathBuf, String> {
     Ok(path)
 }
 
+/// Credenziali GOG salvate da GameStringer (usato dalla diagnostica launcher)
+pub(crate) fn has_saved_credentials() -> bool {
+    get_gog_credentials_path().map(|path| path.exists()).unwrap_or(false)
+}
+
 fn get_machine_key() -> Result<[u8; 32], String> {
     // Genera una chiave basata su caratteristiche della macchina
     let username = std::env::var("USERNAME").unwrap_or_else(|_| "default".to_string());
@@ -0,0 +1,620 @@
+//! 🕐 HowLongToBeat
+//!
+//! Ricerca tempi di completamento tramite l'endpoint JSON di ricerca di HLTB, senza
+//! parser HTML/XML. L'endpoint e la chiave (che HLTB cambia periodicamente nel bundle JS)
+//! sono configurabili; se la chiave non è impostata viene ricavata dal bundle.
+//! Dopo un errore le ricerche (e la rilevazione della chiave) vengono sospese con
+//! backoff crescente, così l'arricchimento della libreria non ritenta per ogni gioco.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use regex::Regex;
+use reqwest::{header, Client, RequestBuilder, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::models::HowLongToBeatData;
+use super::cache_registry::{json_size, TrackedCache};
+
+/// Configurazione dell'endpoint HLTB (sovrascrivibile da frontend o variabili d'ambiente)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HltbConfig {
+    /// Pagina principale, usata per Referer/Origin e per trovare il bundle JS
+    pub base_url: String,
+    /// Endpoint di ricerca senza chiave (es. `https://howlongtobeat.com/api/search`)
+    pub search_url: String,
+    /// Chiave da aggiungere all'endpoint (`{search_url}/{api_key}`); None = ricavala dal bundle
+    pub api_key: Option<String>,
+    /// Se true, rileva di nuovo la chiave quando l'endpoint risponde 403/404
+    pub auto_discover_key: bool,
+    pub user_agent: String,
+    pub timeout_secs: u64,
+}
+
+impl Default for HltbConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://howlongtobeat.com".to_string(),
+            search_url: "https://howlongtobeat.com/api/search".to_string(),
+            api_key: None,
+            auto_discover_key: true,
+            user_agent: "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0 Safari/537.36".to_string(),
+            timeout_secs: 15,
+        }
+    }
+}
+
+impl HltbConfig {
+    /// Configurazione di default con override da `GAMESTRINGER_HLTB_SEARCH_URL` e `GAMESTRINGER_HLTB_API_KEY`
+    fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Ok(url) = std::env::var("GAMESTRINGER_HLTB_SEARCH_URL") {
+            if !url.trim().is_empty() {
+                config.search_url = url.trim().trim_end_matches('/').to_string();
+            }
+        }
+        if let Ok(key) = std::env::var("GAMESTRINGER_HLTB_API_KEY") {
+            if !key.trim().is_empty() {
+                config.api_key = Some(key.trim().to_string());
+            }
+        }
+        config
+    }
+}
+
+/// Risultato di una ricerca HLTB (ore arrotondate)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HltbSearchResult {
+    pub found: bool,
+    pub game_id: Option<u64>,
+    pub game_name: Option<String>,
+    pub main: Option<u32>,
+    pub main_extra: Option<u32>,
+    pub completionist: Option<u32>,
+    pub url: String,
+}
+
+impl HltbSearchResult {
+    fn not_found(title: &str) -> Self {
+        Self {
+            found: false,
+            game_id: None,
+            game_name: None,
+            main: None,
+            main_extra: None,
+            completionist: None,
+            url: format!("https://howlongtobeat.com/?q={}", urlencoding::encode(title)),
+        }
+    }
+
+    /// Converte nel formato usato da `SteamGame::how_long_to_beat`
+    pub fn to_game_data(&self) -> Option<HowLongToBeatData> {
+        if !self.found {
+            return None;
+        }
+        Some(HowLongToBeatData {
+            main: self.main.unwrap_or(0),
+            main_extra: self.main_extra.unwrap_or(0),
+            completionist: self.completionist.unwrap_or(0),
+        })
+    }
+}
+
+/// Statistiche della cache HLTB
+#[derive(Debug, Clone, Serialize)]
+pub struct HltbStatistics {
+    pub cached_entries: u64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    pub api_errors: u64,
+    pub key_discovered: bool,
+    /// Secondi rimanenti prima che le ricerche riprendano dopo un errore
+    pub backoff_remaining_secs: u64,
+}
+
+static HLTB_CONFIG: Lazy<RwLock<HltbConfig>> = Lazy::new(|| RwLock::new(HltbConfig::from_env()));
+
+/// Chiave ricavata dal bundle JS (usata solo se `api_key` non è configurata)
+static DISCOVERED_KEY: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+
+/// Cache risultati per titolo normalizzato (24 ore)
+pub(crate) static HLTB_CACHE: Lazy<TrackedCache<String, HltbSearchResult>> = Lazy::new(|| {
+    TrackedCache::new(
+        "hltb",
+        "Tempi di completamento HowLongToBeat per titolo",
+        5000,
+        Duration::from_secs(24 * 3600),
+        json_size,
+    )
+});
+
+static API_ERRORS: AtomicU64 = AtomicU64::new(0);
+
+/// Sospensione delle ricerche dopo errori di rete/endpoint (HLTB giù o pagina cambiata)
+static SEARCH_BACKOFF: Lazy<Mutex<FailureBackoff>> = Lazy::new(|| {
+    Mutex::new(FailureBackoff::new(Duration::from_secs(60), Duration::from_secs(30 * 60)))
+});
+
+/// Sospensione della rilevazione chiave dopo un bundle irraggiungibile o senza chiave
+static DISCOVERY_BACKOFF: Lazy<Mutex<FailureBackoff>> = Lazy::new(|| {
+    Mutex::new(FailureBackoff::new(Duration::from_secs(10 * 60), Duration::from_secs(6 * 3600)))
+});
+
+/// Backoff dopo errori consecutivi: parte da `base` e raddoppia fino a `max`
+#[derive(Debug)]
+struct FailureBackoff {
+    base: Duration,
+    max: Duration,
+    failures: u32,
+    until: Option<Instant>,
+}
+
+impl FailureBackoff {
+    fn new(base: Duration, max: Duration) -> Self {
+        Self { base, max, failures: 0, until: None }
+    }
+
+    /// Tempo rimanente di sospensione, se attiva
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.until.filter(|until| *until > now).map(|until| until - now)
+    }
+
+    fn record_failure(&mut self, now: Instant) -> Duration {
+        let delay = self.base
+            .saturating_mul(1u32 << self.failures.min(16))
+            .min(self.max);
+        self.failures = self.failures.saturating_add(1);
+        self.until = Some(now + delay);
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.failures = 0;
+        self.until = None;
+    }
+}
+
+static SCRIPT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"src="([^"]*/_app-[^"]*\.js)""#).unwrap()
+});
+
+/// `"/api/search/".concat("abc").concat("def")` oppure `"/api/search/abcdef"`
+static KEY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#""/api/\w+/"((?:\.concat\("[0-9A-Za-z]+"\))+)|"/api/\w+/([0-9A-Za-z]{6,})""#).unwrap()
+});
+
+static CONCAT_PART_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\.concat\("([0-9A-Za-z]+)"\)"#).unwrap());
+
+/// Normalizza un titolo per cache e confronto: minuscolo, senza simboli (™, ®, :, ...) e spazi multipli
+pub fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Cerca i tempi di completamento di un gioco (con cache)
+pub async fn lookup_hltb(title: &str) -> Result<HltbSearchResult, String> {
+    let key = normalize_title(title);
+    if key.is_empty() {
+        return Ok(HltbSearchResult::not_found(title));
+    }
+
+    if let Some(cached) = HLTB_CACHE.get(&key).await {
+        return Ok(cached);
+    }
+
+    if let Some(remaining) = SEARCH_BACKOFF.lock().remaining(Instant::now()) {
+        return Err(format!("HLTB sospeso dopo errori recenti, nuovo tentativo tra {}s", remaining.as_secs()));
+    }
+
+    let result = match search_remote(title, &key).await {
+        Ok(result) => {
+            SEARCH_BACKOFF.lock().reset();
+            result
+        }
+        Err(e) => {
+            API_ERRORS.fetch_add(1, Ordering::Relaxed);
+            let delay = SEARCH_BACKOFF.lock().record_failure(Instant::now());
+            warn!("⚠️ Ricerca HLTB fallita, ricerche sospese per {}s: {}", delay.as_secs(), e);
+            return Err(e);
+        }
+    };
+
+    // Anche i "non trovato" vanno in cache: la ricerca è andata a buon fine
+    HLTB_CACHE.insert(key, result.clone()).await;
+    Ok(result)
+}
+
+/// Dati HLTB per l'arricchimento di `SteamGame` (errori solo loggati)
+pub async fn lookup_hltb_data(title: &str) -> Option<HowLongToBeatData> {
+    match lookup_hltb(title).await {
+        Ok(result) => result.to_game_data(),
+        Err(e) => {
+            debug!("🕐 HLTB non disponibile per '{}': {}", title, e);
+            None
+        }
+    }
+}
+
+/// HLTB rifiuta lo user agent di default: ogni richiesta usa quello configurato e il suo timeout
+fn with_hltb_headers(request: RequestBuilder, config: &HltbConfig) -> RequestBuilder {
+    request
+        .header(header::USER_AGENT, &config.user_agent)
+        .timeout(Duration::from_secs(config.timeout_secs.max(1)))
+}
+
+/// Esegue la ricerca su HLTB, rilevando di nuovo la chiave una volta se l'endpoint è cambiato
+async fn search_remote(title: &str, normalized: &str) -> Result<HltbSearchResult, String> {
+    let config = HLTB_CONFIG.read().clone();
+    let client = crate::http::client_for("hltb");
+
+    let mut api_key = match config.api_key.clone() {
+        Some(key) => Some(key),
+        None => current_or_discovered_key(&client, &config).await,
+    };
+
+    for attempt in 0..2 {
+        let endpoint = match &api_key {
+            Some(key) => format!("{}/{}", config.search_url.trim_end_matches('/'), key),
+            None => config.search_url.clone(),
+        };
+
+        let body = build_search_body(normalized);
+        let response = crate::http::send_with_retry(
+            || async {
+                with_hltb_headers(client.post(&endpoint), &config)
+                    .header("Origin", &config.base_url)
+                    .header("Referer", format!("{}/", config.base_url.trim_end_matches('/')))
+                    .json(&body)
+            },
+            "HLTB search",
+            &crate::http::RetryPolicy::default(),
+        )
+        .await
+        .map_err(|e| format!("Errore richiesta HLTB: {}", e))?;
+
+        let status = response.status();
+        if (status == StatusCode::NOT_FOUND || status == StatusCode::FORBIDDEN)
+            && attempt == 0
+            && config.auto_discover_key
+            && config.api_key.is_none()
+        {
+            warn!("⚠️ Endpoint HLTB rifiutato ({}), rilevo di nuovo la chiave", status);
+            *DISCOVERED_KEY.write() = None;
+            api_key = discover_api_key(&client, &config).await;
+            continue;
+        }
+
+        if !status.is_success() {
+            return Err(format!("HLTB ha risposto con status {}", status));
+        }
+
+        let data: Value = response.json()
+            .await
+            .map_err(|e| format!("Risposta HLTB non valida: {}", e))?;
+
+        return Ok(parse_search_response(title, &data));
+    }
+
+    Err("Endpoint HLTB non raggiungibile: configura search_url/api_key".to_string())
+}
+
+/// Corpo JSON della ricerca (stesso formato del sito)
+fn build_search_body(normalized: &str) -> Value {
+    let terms: Vec<&str> = normalized.split_whitespace().collect();
+    serde_json::json!({
+        "searchType": "games",
+        "searchTerms": terms,
+        "searchPage": 1,
+        "size": 20,
+        "searchOptions": {
+            "games": {
+                "userId": 0,
+                "platform": "",
+                "sortCategory": "popular",
+                "rangeCategory": "main",
+                "rangeTime": { "min": null, "max": null },
+                "gameplay": { "perspective": "", "flow": "", "genre": "", "difficulty": "" },
+                "rangeYear": { "min": "", "max": "" },
+                "modifier": ""
+            },
+            "users": { "sortCategory": "postcount" },
+            "lists": { "sortCategory": "follows" },
+            "filter": "",
+            "sort": 0,
+            "randomizer": 0
+        },
+        "useCache": true
+    })
+}
+
+/// Sceglie il risultato migliore: titolo identico, altrimenti maggiore sovrapposizione di parole
+pub fn parse_search_response(title: &str, data: &Value) -> HltbSearchResult {
+    let Some(entries) = data.get("data").and_then(|d| d.as_array()) else {
+        return HltbSearchResult::not_found(title);
+    };
+
+    let wanted = normalize_title(title);
+    let best = entries.iter()
+        .filter_map(|entry| {
+            let name = entry.get("game_name")?.as_str()?;
+            Some((title_similarity(&wanted, &normalize_title(name)), entry))
+        })
+        .filter(|(score, _)| *score >= 0.5)
+        .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let Some((_, entry)) = best else {
+        return HltbSearchResult::not_found(title);
+    };
+
+    let game_id = entry.get("game_id").and_then(|v| v.as_u64());
+    HltbSearchResult {
+        found: true,
+        game_id,
+        game_name: entry.get("game_name").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        main: seconds_to_hours(entry.get("comp_main")),
+        main_extra: seconds_to_hours(entry.get("comp_plus")),
+        completionist: seconds_to_hours(entry.get("comp_100")),
+        url: match game_id {
+            Some(id) => format!("https://howlongtobeat.com/game/{}", id),
+            None => HltbSearchResult::not_found(title).url,
+        },
+    }
+}
+
+/// I tempi HLTB sono in secondi; 0 significa "nessun dato"
+fn seconds_to_hours(value: Option<&Value>) -> Option<u32> {
+    let seconds = value.and_then(|v| v.as_f64())?;
+    if seconds <= 0.0 {
+        return None;
+    }
+    Some(((seconds / 3600.0).round() as u32).max(1))
+}
+
+/// Sovrapposizione di parole (Jaccard) tra due titoli normalizzati, 1.0 se identici
+fn title_similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let words_a: std::collections::HashSet<&str> = a.split_whitespace().collect();
+    let words_b: std::collections::HashSet<&str> = b.split_whitespace().collect();
+    let union = words_a.union(&words_b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    // Leggermente sotto 1.0 per preferire sempre il match esatto
+    words_a.intersection(&words_b).count() as f64 / union as f64 * 0.99
+}
+
+async fn current_or_discovered_key(client: &Client, config: &HltbConfig) -> Option<String> {
+    let cached = DISCOVERED_KEY.read().clone();
+    if cached.is_some() {
+        return cached;
+    }
+    if !config.auto_discover_key {
+        return None;
+    }
+    discover_api_key(client, config).await
+}
+
+/// Ricava la chiave dell'endpoint di ricerca dal bundle `_app-*.js` della home page.
+/// Un fallimento sospende nuovi tentativi (`DISCOVERY_BACKOFF`).
+async fn discover_api_key(client: &Client, config: &HltbConfig) -> Option<String> {
+    if let Some(remaining) = DISCOVERY_BACKOFF.lock().remaining(Instant::now()) {
+        debug!("🕐 Rilevazione chiave HLTB sospesa per altri {}s", remaining.as_secs());
+        return None;
+    }
+
+    match fetch_api_key(client, config).await {
+        Ok(key) => {
+            info!("🕐 Chiave HLTB rilevata dal bundle");
+            DISCOVERY_BACKOFF.lock().reset();
+            *DISCOVERED_KEY.write() = Some(key.clone());
+            Some(key)
+        }
+        Err(e) => {
+            let delay = DISCOVERY_BACKOFF.lock().record_failure(Instant::now());
+            warn!("⚠️ Rilevazione chiave HLTB fallita, riprovo tra {}s: {}", delay.as_secs(), e);
+            None
+        }
+    }
+}
+
+async fn fetch_api_key(client: &Client, config: &HltbConfig) -> Result<String, String> {
+    let base = config.base_url.trim_end_matches('/');
+    let html = fetch_text(client, config, base, "HLTB home").await?;
+
+    let script_path = SCRIPT_RE.captures(&html)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string())
+        .ok_or("bundle _app-*.js non trovato nella home page")?;
+    let script_url = if script_path.starts_with("http") {
+        script_path
+    } else {
+        format!("{}/{}", base, script_path.trim_start_matches('/'))
+    };
+
+    let script = fetch_text(client, config, &script_url, "HLTB bundle").await?;
+    extract_api_key(&script).ok_or_else(|| "chiave non trovata nel bundle".to_string())
+}
+
+async fn fetch_text(client: &Client, config: &HltbConfig, url: &str, endpoint: &str) -> Result<String, String> {
+    let response = crate::http::send_with_retry(
+        || async { with_hltb_headers(client.get(url), config) },
+        endpoint,
+        &crate::http::RetryPolicy::default(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("{}: HTTP {}", endpoint, response.status()));
+    }
+    response.text().await.map_err(|e| format!("{}: {}", endpoint, e))
+}
+
+/// Estrae la chiave dal sorgente del bundle JS
+pub fn extract_api_key(script: &str) -> Option<String> {
+    let captures = KEY_RE.captures(script)?;
+    if let Some(concat) = captures.get(1) {
+        let key: String = CONCAT_PART_RE.captures_iter(concat.as_str())
+            .filter_map(|c| c.get(1))
+            .map(|m| m.as_str())
+            .collect();
+        return (!key.is_empty()).then_some(key);
+    }
+    captures.get(2).map(|m| m.as_str().to_string())
+}
+
+/// 🕐 Cerca i tempi di completamento di un gioco su HowLongToBeat
+#[tauri::command]
+pub async fn search_game_hltb(title: String) -> Result<HltbSearchResult, String> {
+    info!("🕐 Ricerca HLTB per: {}", title);
+    lookup_hltb(&title).await
+}
+
+/// 🕐 Cerca più giochi (sequenziale, per non superare i limiti di HLTB)
+#[tauri::command]
+pub async fn search_games_batch_hltb(titles: Vec<String>) -> Result<Vec<HltbSearchResult>, String> {
+    let mut results = Vec::with_capacity(titles.len());
+    for title in &titles {
+        let result = lookup_hltb(title).await
+            .unwrap_or_else(|_| HltbSearchResult::not_found(title));
+        results.push(result);
+    }
+    info!("🕐 Batch HLTB: {}/{} trovati", results.iter().filter(|r| r.found).count(), results.len());
+    Ok(results)
+}
+
+/// 🕐 Statistiche cache HLTB
+#[tauri::command]
+pub async fn get_hltb_statistics() -> Result<HltbStatistics, String> {
+    HLTB_CACHE.run_pending_tasks().await;
+    Ok(HltbStatistics {
+        cached_entries: HLTB_CACHE.entry_count(),
+        cache_hits: HLTB_CACHE.hits(),
+        cache_misses: HLTB_CACHE.misses(),
+        api_errors: API_ERRORS.load(Ordering::Relaxed),
+        key_discovered: DISCOVERED_KEY.read().is_some(),
+        backoff_remaining_secs: SEARCH_BACKOFF.lock()
+            .remaining(Instant::now())
+            .map_or(0, |d| d.as_secs()),
+    })
+}
+
+/// 🕐 Svuota la cache HLTB
+#[tauri::command]
+pub async fn cleanup_hltb_cache() -> Result<(), String> {
+    HLTB_CACHE.invalidate_all();
+    info!("🧹 Cache HLTB svuotata");
+    Ok(())
+}
+
+/// 🕐 Configurazione endpoint HLTB corrente
+#[tauri::command]
+pub fn get_hltb_config() -> Result<HltbConfig, String> {
+    Ok(HLTB_CONFIG.read().clone())
+}
+
+/// 🕐 Sovrascrive endpoint/chiave HLTB (es. dopo un cambio del bundle del sito)
+#[tauri::command]
+pub async fn update_hltb_config(config: HltbConfig) -> Result<HltbConfig, String> {
+    if !config.search_url.starts_with("http") || !config.base_url.starts_with("http") {
+        return Err("search_url e base_url devono essere URL http(s)".to_string());
+    }
+
+    let mut config = config;
+    config.search_url = config.search_url.trim_end_matches('/').to_string();
+    config.api_key = config.api_key.map(|k| k.trim().to_string()).filter(|k| !k.is_empty());
+
+    *HLTB_CONFIG.write() = config.clone();
+    *DISCOVERED_KEY.write() = None;
+    SEARCH_BACKOFF.lock().reset();
+    DISCOVERY_BACKOFF.lock().reset();
+    HLTB_CACHE.invalidate_all();
+    info!("🕐 Configurazione HLTB aggiornata: {}", config.search_url);
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEARCH_RESPONSE: &str = r#"{
+        "color": "blue",
+        "title": "",
+        "category": "games",
+        "count": 3,
+        "pageCurrent": 1,
+        "data": [
+            { "game_id": 10270, "game_name": "Hollow Knight: Godmaster", "comp_main": 0, "comp_plus": 0, "comp_100": 0 },
+            { "game_id": 26286, "game_name": "Hollow Knight", "comp_main": 97920, "comp_plus": 146520, "comp_100": 226800 },
+            { "game_id": 68151, "game_name": "Hollow Knight: Silksong", "comp_main": 1800, "comp_plus": 0, "comp_100": 0 }
+        ]
+    }"#;
+
+    const APP_BUNDLE: &str = r#"let r=await fetch("/api/search/".concat("4b4cbe57").concat("0602c88660f7"),{method:"POST",headers:t})"#;
+
+    #[test]
+    fn search_response_prefers_exact_title() {
+        let data: Value = serde_json::from_str(SEARCH_RESPONSE).unwrap();
+        let result = parse_search_response("Hollow Knight™", &data);
+        assert!(result.found);
+        assert_eq!(result.game_id, Some(26286));
+        assert_eq!((result.main, result.main_extra, result.completionist), (Some(27), Some(41), Some(63)));
+        assert_eq!(result.url, "https://howlongtobeat.com/game/26286");
+    }
+
+    #[test]
+    fn search_response_without_match_is_not_found() {
+        let data: Value = serde_json::from_str(SEARCH_RESPONSE).unwrap();
+        assert!(!parse_search_response("Celeste", &data).found);
+        assert!(!parse_search_response("Celeste", &serde_json::json!({ "data": [] })).found);
+        assert!(!parse_search_response("Celeste", &serde_json::json!({ "error": "bad" })).found);
+
+        // Tempi a zero: trovato ma senza dati
+        let godmaster = parse_search_response("Hollow Knight: Godmaster", &data);
+        assert_eq!(godmaster.game_id, Some(10270));
+        assert_eq!(godmaster.main, None);
+    }
+
+    #[test]
+    fn api_key_is_extracted_from_bundle() {
+        assert_eq!(extract_api_key(APP_BUNDLE).as_deref(), Some("4b4cbe570602c88660f7"));
+        assert_eq!(
+            extract_api_key(r#"fetch("/api/find/a1b2c3d4e5f6",{method:"POST"})"#).as_deref(),
+            Some("a1b2c3d4e5f6")
+        );
+        assert_eq!(extract_api_key(r#"fetch("/api/search",{method:"POST"})"#), None);
+    }
+
+    #[test]
+    fn app_script_is_found_in_home_page() {
+        let html = r#"<script src="/_next/static/chunks/pages/_app-5e3a4cf4b1f7c2a8.js" defer=""></script>"#;
+        let path = SCRIPT_RE.captures(html).and_then(|c| c.get(1)).map(|m| m.as_str());
+        assert_eq!(path, Some("/_next/static/chunks/pages/_app-5e3a4cf4b1f7c2a8.js"));
+    }
+
+    #[test]
+    fn backoff_grows_and_resets() {
+        let now = Instant::now();
+        let mut backoff = FailureBackoff::new(Duration::from_secs(60), Duration::from_secs(300));
+        assert!(backoff.remaining(now).is_none());
+        assert_eq!(backoff.record_failure(now), Duration::from_secs(60));
+        assert_eq!(backoff.record_failure(now), Duration::from_secs(120));
+        assert_eq!(backoff.record_failure(now), Duration::from_secs(240));
+        assert_eq!(backoff.record_failure(now), Duration::from_secs(300));
+        assert!(backoff.remaining(now).is_some());
+        assert!(backoff.remaining(now + Duration::from_secs(301)).is_none());
+        backoff.reset();
+        assert!(backoff.remaining(now).is_none());
+    }
+}
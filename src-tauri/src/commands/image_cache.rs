@@ -69,10 +69,7 @@ pub async fn cache_image(url: String) -> Result<String, String> {
     }
     
     // Scarica l'immagine
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Errore client HTTP: {}", e))?;
+    let client = crate::http::client_for("image_cache");
     
     let response = client.get(&url)
         .send()
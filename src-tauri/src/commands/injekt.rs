@@ -1,5 +1,15 @@
-use crate::injekt::InjectionConfig;
-use crate::multi_process_injekt::{MultiProcessInjekt, MultiProcessConfig, InjectionStrategy};
+use crate::anti_cheat::AntiCheatManager;
+use crate::injekt::{
+    InjectionConfig, ProcessArchitecture, get_process_architecture, debug_privilege_status,
+    ensure_debug_privilege, is_process_elevated,
+    readable_module_count, injection_stats_report, record_target_started, record_target_stopped,
+    scan_process_memory_for_bytes, MemoryScanOptions, TextEncoding, restore_process_hooks,
+    cleanup_all_injections,
+};
+use crate::multi_process_injekt::{
+    MultiProcessInjekt, MultiProcessConfig, InjectionStrategy, INJECTION_ALLOWLIST,
+    DENIED_PROCESSES, DENIED_PROCESS_PATTERNS,
+};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use once_cell::sync::Lazy;
@@ -8,11 +18,28 @@ use once_cell::sync::Lazy;
 static MULTI_PROCESS_INSTANCES: Lazy<Arc<Mutex<HashMap<String, MultiProcessInjekt>>>> = 
     Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
+/// Gate anti-cheat comune ai comandi di injection: restituisce i sistemi rilevati
+fn anti_cheat_gate(process_id: u32, force: bool) -> Result<Vec<String>, String> {
+    let manager = AntiCheatManager::new();
+    match manager.check_injection_gate(process_id, force) {
+        Ok(detection) => Ok(detection.detected_systems),
+        Err(e) => {
+            log::error!("🛡️ {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
 #[tauri::command]
-pub async fn start_injection(process_id: u32, process_name: String, config: serde_json::Value) -> Result<serde_json::Value, String> {
+pub async fn start_injection(process_id: u32, process_name: String, config: serde_json::Value, force: Option<bool>) -> Result<serde_json::Value, String> {
     log::info!("🚀 Avvio iniezione per processo: {} (PID: {})", process_name, process_id);
     log::info!("⚙️ Configurazione: {}", config);
     
+    // Nessuna injection in processi protetti da anti-cheat, salvo override esplicito
+    let force = force.unwrap_or(false);
+    let anti_cheat = anti_cheat_gate(process_id, force)?;
+    debug_privilege_gate()?;
+    
     // TODO: Implementare sistema di iniezione reale
     // Per ora simuliamo l'avvio dell'iniezione
     
@@ -23,9 +50,13 @@ pub async fn start_injection(process_id: u32, process_name: String, config: serd
         "start_time": chrono::Utc::now().to_rfc3339(),
         "status": "active",
         "translated_count": 0,
-        "session_id": format!("session_{}", process_id)
+        "session_id": format!("session_{}", process_id),
+        "anti_cheat": anti_cheat,
+        "anti_cheat_override": force && !anti_cheat.is_empty()
     });
     
+    record_target_started(process_id, &process_name);
+    
     log::info!("✅ Sessione di iniezione avviata per PID: {}", process_id);
     Ok(session)
 }
@@ -34,63 +65,347 @@ pub async fn start_injection(process_id: u32, process_name: String, config: serd
 pub async fn stop_injection(process_id: u32) -> Result<serde_json::Value, String> {
     log::info!("🛑 Arresto iniezione per processo PID: {}", process_id);
     
-    // TODO: Implementare arresto iniezione reale
+    // Ripristina i byte originali degli hook ancora installati nel processo
+    let cleanup = restore_process_hooks(process_id, None);
+    record_target_stopped(process_id);
     let result = serde_json::json!({
         "process_id": process_id,
         "stopped_at": chrono::Utc::now().to_rfc3339(),
-        "status": "stopped",
-        "final_translated_count": 0
+        "status": if cleanup.failed > 0 { "stopped_with_errors" } else { "stopped" },
+        "final_translated_count": 0,
+        "hooks_restored": cleanup.restored,
+        "hooks_failed": cleanup.failed,
+        "errors": cleanup.errors
     });
     
     log::info!("✅ Iniezione arrestata per PID: {}", process_id);
     Ok(result)
 }
 
+/// Pulizia forzata per processi rimasti "bloccati": ferma gli injector multi-processo che
+/// tracciano il PID (senza attendere quelli bloccati) e ripristina direttamente la memoria
+#[tauri::command]
+pub async fn force_cleanup_injection(process_id: u32) -> Result<serde_json::Value, String> {
+    log::warn!("🧨 Pulizia forzata injection per PID: {}", process_id);
+    
+    let mut stopped_instances = Vec::new();
+    if let Ok(mut instances) = MULTI_PROCESS_INSTANCES.try_lock() {
+        for (game_name, multi_injekt) in instances.iter_mut() {
+            if multi_injekt.tracked_pids().contains(&process_id) && multi_injekt.stop_process_injection(process_id) {
+                stopped_instances.push(game_name.clone());
+            }
+        }
+    } else {
+        log::warn!("⚠️ Istanze multi-processo occupate, ripristino diretto della memoria");
+    }
+    
+    // Ripristino diretto dal registro hook: copre anche injector bloccati o già rilasciati
+    let cleanup = restore_process_hooks(process_id, None);
+    record_target_stopped(process_id);
+    
+    log::info!("✅ Pulizia forzata PID {}: {} hook ripristinati, {} falliti",
+        process_id, cleanup.restored, cleanup.failed);
+    Ok(serde_json::json!({
+        "process_id": process_id,
+        "stopped_instances": stopped_instances,
+        "cleanup": cleanup,
+        "clean": cleanup.failed == 0
+    }))
+}
+
+/// Arresta tutte le injection e ripristina la memoria dei processi (chiusura dell'app)
+pub fn shutdown_all_injections() {
+    log::info!("🔴 Chiusura app: arresto di tutte le injection...");
+    
+    if let Ok(mut instances) = MULTI_PROCESS_INSTANCES.lock() {
+        for (game_name, mut multi_injekt) in instances.drain() {
+            if let Err(e) = multi_injekt.stop() {
+                log::error!("❌ Errore arresto injection multi-processo per {}: {}", game_name, e);
+            }
+        }
+    }
+    
+    let reports = cleanup_all_injections();
+    let failed: usize = reports.iter().map(|r| r.failed).sum();
+    if failed > 0 {
+        log::error!("❌ {} hook non ripristinati alla chiusura", failed);
+    } else {
+        log::info!("✅ Memoria ripristinata per {} processi", reports.len());
+    }
+}
+
 #[tauri::command]
 pub async fn get_injection_stats(process_id: Option<u32>) -> Result<serde_json::Value, String> {
-    if let Some(pid) = process_id {
-        log::info!("📊 Recupero statistiche iniezione per PID: {}", pid);
+    match process_id {
+        Some(pid) => log::info!("📊 Recupero statistiche iniezione per PID: {}", pid),
+        None => log::info!("📊 Recupero statistiche globali iniezione"),
+    }
+    
+    // Dettaglio per target (pid, exe, stringhe, byte scritti, errori recenti) + totali
+    let pids = process_id.map(|pid| vec![pid]);
+    let report = injection_stats_report(pids.as_deref());
+    serde_json::to_value(report).map_err(|e| e.to_string())
+}
+
+/// Nome eseguibile normalizzato per il confronto ("Game.exe" == "game")
+fn normalized_exe_name(name: &str) -> String {
+    let lower = name.trim().to_lowercase();
+    lower.strip_suffix(".exe").map(|s| s.to_string()).unwrap_or(lower)
+}
+
+/// Avvia il gioco tramite il launcher universale, attende il processo reale e poi inietta.
+/// I processi di bootstrap (es. exe che si rilancia tramite Steam e termina) vengono
+/// scartati: si inietta solo in un processo con finestra visibile, moduli caricati
+/// e ancora vivo dopo un breve periodo di stabilità.
+#[tauri::command]
+pub async fn launch_and_inject(
+    request: crate::commands::launcher::LaunchRequest,
+    target_exe: Option<String>,
+    config: serde_json::Value,
+    timeout_secs: Option<u64>,
+    force: Option<bool>,
+) -> Result<serde_json::Value, String> {
+    const POLL_INTERVAL_MS: u64 = 500;
+    const STABLE_MS: u64 = 2000;
+    
+    let target = target_exe
+        .or_else(|| {
+            request.executable_path.as_deref()
+                .and_then(|p| std::path::Path::new(p).file_name())
+                .map(|n| n.to_string_lossy().to_string())
+        })
+        .ok_or("Specifica l'eseguibile del gioco da attendere (target_exe)")?;
+    let target_key = normalized_exe_name(&target);
+    let timeout = std::time::Duration::from_secs(timeout_secs.unwrap_or(120));
+    
+    // Istanze già in esecuzione prima del lancio: non sono il processo avviato ora
+    let preexisting: std::collections::HashSet<u32> = crate::process_utils::get_running_processes()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|p| normalized_exe_name(&p.name) == target_key)
+        .map(|p| p.pid)
+        .collect();
+    
+    log::info!("🚀 Avvio e injection: {} (attendo {})", request.game_name, target);
+    let launch = crate::commands::launcher::launch_game_universal(request).await?;
+    if !launch.success {
+        return Err(format!("Avvio fallito: {}", launch.message));
+    }
+    
+    let started = std::time::Instant::now();
+    let mut bootstrap_pids: Vec<u32> = Vec::new();
+    let mut candidate: Option<(u32, String, std::time::Instant)> = None;
+    
+    let (pid, process_name) = loop {
+        if started.elapsed() > timeout {
+            return Err(format!(
+                "Timeout: processo {} non pronto entro {}s (bootstrap terminati: {:?})",
+                target, timeout.as_secs(), bootstrap_pids
+            ));
+        }
         
-        // TODO: Implementare recupero statistiche reali
-        let stats = serde_json::json!({
-            "process_id": pid,
-            "status": "active",
-            "translated_count": 0,
-            "cache_hits": 0,
-            "cache_misses": 0,
-            "translation_rate": 0.0,
-            "uptime_seconds": 0,
-            "memory_usage_mb": 0.0,
-            "last_activity": chrono::Utc::now().to_rfc3339()
-        });
+        let processes = crate::process_utils::get_running_processes().map_err(|e| e.to_string())?;
+        let matching: Vec<_> = processes.into_iter()
+            .filter(|p| normalized_exe_name(&p.name) == target_key && !preexisting.contains(&p.pid))
+            .collect();
         
-        Ok(stats)
-    } else {
-        log::info!("📊 Recupero statistiche globali iniezione");
+        // Il candidato è terminato: era un processo di bootstrap (es. rilancio via Steam)
+        if let Some((cand_pid, _, _)) = &candidate {
+            if !matching.iter().any(|p| p.pid == *cand_pid) {
+                log::info!("🔁 PID {} terminato durante l'avvio, attendo il processo reale", cand_pid);
+                bootstrap_pids.push(*cand_pid);
+                candidate = None;
+            }
+        }
         
-        let global_stats = serde_json::json!({
-            "active_sessions": 0,
-            "total_translated": 0,
-            "total_cache_hits": 0,
-            "total_cache_misses": 0,
-            "average_translation_rate": 0.0,
-            "total_uptime_seconds": 0,
-            "processes": []
-        });
+        // Pronto = finestra visibile e moduli leggibili
+        let ready = matching.iter()
+            .filter(|p| !bootstrap_pids.contains(&p.pid))
+            .find(|p| p.window_title.is_some() && readable_module_count(p.pid).map(|n| n > 0).unwrap_or(false));
         
-        Ok(global_stats)
+        match (&candidate, ready) {
+            (Some((cand_pid, name, since)), Some(p)) if p.pid == *cand_pid => {
+                if since.elapsed().as_millis() as u64 >= STABLE_MS {
+                    break (*cand_pid, name.clone());
+                }
+            }
+            (_, Some(p)) => {
+                log::debug!("🎯 Candidato PID {} ({}) pronto, verifico stabilità", p.pid, p.name);
+                candidate = Some((p.pid, p.name.clone(), std::time::Instant::now()));
+            }
+            (_, None) => {}
+        }
+        
+        tokio::time::sleep(std::time::Duration::from_millis(POLL_INTERVAL_MS)).await;
+    };
+    
+    let waited_ms = started.elapsed().as_millis() as u64;
+    log::info!("✅ Processo {} pronto (PID: {}) dopo {}ms", process_name, pid, waited_ms);
+    
+    let injection = start_injection(pid, process_name.clone(), config, force).await?;
+    
+    Ok(serde_json::json!({
+        "process_id": pid,
+        "process_name": process_name,
+        "launch": launch,
+        "waited_ms": waited_ms,
+        "bootstrap_pids": bootstrap_pids,
+        "injection": injection
+    }))
+}
+
+/// Esito di un singolo controllo del dry-run
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ReadinessCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Report "questa injection funzionerà?" prodotto senza scrivere memoria
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct InjectionReadinessReport {
+    pub process_id: u32,
+    pub dry_run: bool,
+    pub ready: bool,
+    pub checks: Vec<ReadinessCheck>,
+    pub timestamp: String,
+}
+
+impl InjectionReadinessReport {
+    fn check(&mut self, name: &str, result: Result<String, String>) -> bool {
+        let (passed, detail) = match result {
+            Ok(detail) => (true, detail),
+            Err(detail) => (false, detail),
+        };
+        self.checks.push(ReadinessCheck { name: name.to_string(), passed, detail });
+        passed
     }
 }
 
+/// Gate SeDebugPrivilege comune ai comandi di injection: lo abilita se possibile,
+/// altrimenti errore chiaro invece di un'injection che fallisce in silenzio
+fn debug_privilege_gate() -> Result<(), String> {
+    ensure_debug_privilege().inspect_err(|e| log::error!("🔒 {}", e))
+}
+
+/// True se SeDebugPrivilege è abilitato nel token di GameStringer
 #[tauri::command]
-pub async fn test_injection() -> Result<serde_json::Value, String> {
+pub async fn check_debug_privilege() -> bool {
+    matches!(debug_privilege_status(), Ok((_, true)))
+}
+
+/// Abilita SeDebugPrivilege (richiede GameStringer avviato come amministratore)
+#[tauri::command]
+pub async fn enable_debug_privilege() -> Result<(), String> {
+    crate::injekt::enable_debug_privilege()
+}
+
+/// Elevazione del processo e stato di SeDebugPrivilege
+#[tauri::command]
+pub async fn get_privilege_status() -> Result<serde_json::Value, String> {
+    let (present, enabled) = debug_privilege_status()?;
+    let elevated = is_process_elevated()?;
+    Ok(serde_json::json!({
+        "elevated": elevated,
+        "debug_privilege_present": present,
+        "debug_privilege_enabled": enabled,
+        "hint": (!present).then_some(crate::injekt::DEBUG_PRIVILEGE_HINT)
+    }))
+}
+
+/// Verifica che un processo sia iniettabile senza mai chiamare WriteProcessMemory
+fn injection_dry_run(process_id: u32) -> InjectionReadinessReport {
+    let mut report = InjectionReadinessReport {
+        process_id,
+        dry_run: true,
+        ready: false,
+        checks: Vec::new(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+    
+    // 1. Il processo esiste
+    let exists = report.check("process_exists", if crate::process_utils::is_process_running(process_id) {
+        Ok(format!("Processo {} in esecuzione", process_id))
+    } else {
+        Err(format!("Processo {} non trovato", process_id))
+    });
+    
+    // 2. Architettura compatibile con il payload
+    let payload = ProcessArchitecture::payload();
+    report.check("architecture", if !exists {
+        Err("Saltato: processo non trovato".to_string())
+    } else {
+        match get_process_architecture(process_id) {
+            Ok(arch) if arch == payload => Ok(format!("Processo {} = payload {}", arch, payload)),
+            Ok(arch) => Err(format!("ArchitectureMismatch: processo {} ma payload {}", arch, payload)),
+            Err(e) => Err(e),
+        }
+    });
+    
+    // 3. GameStringer elevato e SeDebugPrivilege disponibile nel token
+    report.check("elevation", match is_process_elevated() {
+        Ok(true) => Ok("GameStringer in esecuzione come amministratore".to_string()),
+        Ok(false) => Err("GameStringer non è in esecuzione come amministratore".to_string()),
+        Err(e) => Err(e),
+    });
+    report.check("debug_privilege", match debug_privilege_status() {
+        Ok((true, true)) => Ok("SeDebugPrivilege abilitato".to_string()),
+        Ok((true, false)) => Ok("SeDebugPrivilege presente (verrà abilitato all'avvio)".to_string()),
+        Ok((false, _)) => Err(crate::injekt::DEBUG_PRIVILEGE_HINT.to_string()),
+        Err(e) => Err(e),
+    });
+    
+    // 4. Lista moduli leggibile
+    report.check("module_list", if !exists {
+        Err("Saltato: processo non trovato".to_string())
+    } else {
+        readable_module_count(process_id).map(|count| format!("{} moduli leggibili", count))
+    });
+    
+    // 5. Nessun anti-cheat bloccante
+    report.check("anti_cheat", if !exists {
+        Err("Saltato: processo non trovato".to_string())
+    } else {
+        let manager = AntiCheatManager::new();
+        match manager.detect_anti_cheat(process_id) {
+            Ok(detection) if manager.is_injection_safe(&detection) => {
+                if detection.detected_systems.is_empty() {
+                    Ok("Nessun anti-cheat rilevato".to_string())
+                } else {
+                    Ok(format!("Anti-cheat a basso rischio: {}", detection.detected_systems.join(", ")))
+                }
+            }
+            Ok(detection) => Err(format!("Anti-cheat bloccante: {}", detection.detected_systems.join(", "))),
+            Err(e) => Err(format!("Rilevamento anti-cheat fallito: {}", e)),
+        }
+    });
+    
+    report.ready = report.checks.iter().all(|c| c.passed);
+    report
+}
+
+#[tauri::command]
+pub async fn test_injection(process_id: Option<u32>, dry_run: Option<bool>) -> Result<serde_json::Value, String> {
     log::info!("🧪 Test sistema di iniezione");
     
+    // Dry-run su un processo specifico: solo letture, nessuna scrittura in memoria
+    if let Some(pid) = process_id {
+        if !dry_run.unwrap_or(true) {
+            return Err("Il test su un processo è disponibile solo in modalità dry-run".to_string());
+        }
+        let report = injection_dry_run(pid);
+        log::info!("✅ Dry-run iniezione PID {}: {}", pid, if report.ready { "pronto" } else { "non pronto" });
+        return serde_json::to_value(report).map_err(|e| e.to_string());
+    }
+    
+    let admin_privileges = is_process_elevated().unwrap_or(false);
+    
     // TODO: Implementare test reale del sistema di iniezione
     let test_result = serde_json::json!({
         "injection_system_available": true,
-        "admin_privileges": false, // TODO: Verificare privilegi reali
-        "supported_architectures": ["x64", "x86"],
+        "admin_privileges": admin_privileges,
+        "supported_architectures": [ProcessArchitecture::payload().to_string()],
         "test_passed": true,
         "test_timestamp": chrono::Utc::now().to_rfc3339(),
         "notes": "Sistema di iniezione non ancora implementato - test simulato"
@@ -132,16 +447,30 @@ pub async fn get_processes() -> Result<serde_json::Value, String> {
 pub async fn get_process_info(process_id: u32) -> Result<serde_json::Value, String> {
     log::info!("🔍 Recupero informazioni processo PID: {}", process_id);
     
+    // Architettura reale via IsWow64Process: un mismatch con il payload rende l'injection impossibile
+    let payload_arch = ProcessArchitecture::payload();
+    let (architecture, architecture_error) = match get_process_architecture(process_id) {
+        Ok(arch) => (Some(arch), None),
+        Err(e) => {
+            log::warn!("⚠️ Architettura non rilevabile per PID {}: {}", process_id, e);
+            (None, Some(e))
+        }
+    };
+    let architecture_mismatch = architecture.map_or(false, |arch| arch != payload_arch);
+    
     // TODO: Implementare recupero informazioni processo reale
     let process_info = serde_json::json!({
         "pid": process_id,
         "name": "unknown.exe",
         "window_title": "Unknown Process",
-        "architecture": "x64",
+        "architecture": architecture.map(|a| a.to_string()).unwrap_or_else(|| "unknown".to_string()),
+        "payload_architecture": payload_arch.to_string(),
+        "architecture_mismatch": architecture_mismatch,
+        "architecture_error": architecture_error,
         "memory_usage_mb": 0.0,
         "cpu_usage_percent": 0.0,
         "is_game": false,
-        "injection_compatible": true,
+        "injection_compatible": architecture.is_some() && !architecture_mismatch,
         "modules": [],
         "threads": 0,
         "created_at": chrono::Utc::now().to_rfc3339()
@@ -151,6 +480,52 @@ pub async fn get_process_info(process_id: u32) -> Result<serde_json::Value, Stri
     Ok(process_info)
 }
 
+/// Finestre di gioco candidate per l'injection: l'utente sceglie la finestra, non il PID
+#[tauri::command]
+pub async fn list_injectable_windows() -> Result<Vec<serde_json::Value>, String> {
+    log::info!("🪟 Recupero finestre candidate per l'injection");
+    
+    let payload_arch = ProcessArchitecture::payload();
+    let windows = tauri::async_runtime::spawn_blocking(crate::process_utils::list_top_level_windows)
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    let result: Vec<serde_json::Value> = windows.into_iter()
+        .map(|window| {
+            let denied = crate::multi_process_injekt::denylist_match(&window.exe);
+            let architecture = get_process_architecture(window.pid).ok();
+            let architecture_mismatch = architecture.is_some_and(|arch| arch != payload_arch);
+            let mut value = serde_json::to_value(&window).unwrap_or_default();
+            value["architecture"] = serde_json::json!(architecture.map(|a| a.to_string()));
+            value["denied_reason"] = serde_json::json!(denied);
+            value["injection_compatible"] = serde_json::json!(denied.is_none() && architecture.is_some() && !architecture_mismatch);
+            value
+        })
+        .collect();
+    
+    log::info!("✅ Trovate {} finestre", result.len());
+    Ok(result)
+}
+
+/// Risolve la finestra scelta nel PID del processo proprietario, con le stesse
+/// informazioni di `get_process_info`, per usare il normale percorso di injection
+#[tauri::command]
+pub async fn get_window_process_info(hwnd: isize) -> Result<serde_json::Value, String> {
+    let pid = crate::process_utils::window_process_id(hwnd)
+        .ok_or_else(|| "La finestra selezionata non esiste più: aggiorna l'elenco".to_string())?;
+    
+    let mut info = get_process_info(pid).await?;
+    if let Some(window) = crate::process_utils::list_top_level_windows().into_iter().find(|w| w.hwnd == hwnd) {
+        info["name"] = serde_json::json!(window.exe);
+        info["window_title"] = serde_json::json!(window.title);
+        info["exe_path"] = serde_json::json!(window.exe_path);
+    }
+    info["hwnd"] = serde_json::json!(hwnd);
+    
+    log::info!("🪟 Finestra {:#x} → PID {}", hwnd, pid);
+    Ok(info)
+}
+
 #[tauri::command]
 pub async fn inject_translation(process_id: u32, original_text: String, translated_text: String, _position: Option<serde_json::Value>) -> Result<(), String> {
     log::info!("💉 Iniezione traduzione in PID {}: '{}' -> '{}'", 
@@ -183,18 +558,73 @@ pub async fn scan_process_memory(process_id: u32, pattern: String) -> Result<ser
     Ok(scan_result)
 }
 
+/// Cerca un testo (UTF-8 o UTF-16LE) nella memoria leggibile del processo e
+/// restituisce gli indirizzi trovati, utile per individuare dove agganciare gli hook
+#[tauri::command]
+pub async fn scan_process_for_text(
+    pid: u32,
+    needle: String,
+    encoding: String,
+    options: Option<MemoryScanOptions>
+) -> Result<serde_json::Value, String> {
+    let encoding = TextEncoding::parse(&encoding)?;
+    let options = options.unwrap_or_default();
+    let pattern = encoding.encode(&needle);
+    log::info!("🔎 Ricerca testo in PID {} ({:?}, {} byte)", pid, encoding, pattern.len());
+    
+    let start = std::time::Instant::now();
+    let scan = tokio::task::spawn_blocking(move || scan_process_memory_for_bytes(pid, &pattern, &options))
+        .await
+        .map_err(|e| format!("Errore scansione: {}", e))??;
+    
+    log::info!("✅ Trovate {} occorrenze in {} regioni ({} MB)",
+        scan.addresses.len(), scan.scanned_regions, scan.bytes_scanned / (1024 * 1024));
+    
+    Ok(serde_json::json!({
+        "process_id": pid,
+        "needle": needle,
+        "encoding": encoding,
+        "addresses": scan.addresses.iter().map(|a| format!("0x{:X}", a)).collect::<Vec<_>>(),
+        "match_count": scan.addresses.len(),
+        "scanned_regions": scan.scanned_regions,
+        "skipped_regions": scan.skipped_regions,
+        "bytes_scanned": scan.bytes_scanned,
+        "truncated": scan.truncated,
+        "scan_duration_ms": start.elapsed().as_millis() as u64
+    }))
+}
+
 // === COMANDI MULTI-PROCESSO ===
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn start_multi_process_injection(
     game_name: String,
     primary_process: String,
     secondary_processes: Vec<String>,
     injection_strategy: String,
-    base_config: serde_json::Value
+    base_config: serde_json::Value,
+    app_id: Option<String>,
+    force: Option<bool>,
+    app: tauri::AppHandle,
 ) -> Result<serde_json::Value, String> {
     log::info!("🚀 Avvio injection multi-processo per: {}", game_name);
     
+    debug_privilege_gate()?;
+    
+    // Gate anti-cheat sui processi primari già in esecuzione
+    let force = force.unwrap_or(false);
+    let primary_lower = primary_process.to_lowercase();
+    let running = crate::process_utils::get_running_processes().map_err(|e| e.to_string())?;
+    let mut anti_cheat: Vec<String> = Vec::new();
+    for process in running.iter().filter(|p| p.name.to_lowercase().contains(&primary_lower)) {
+        for system in anti_cheat_gate(process.pid, force)? {
+            if !anti_cheat.contains(&system) {
+                anti_cheat.push(system);
+            }
+        }
+    }
+    
     // Converti la strategia da stringa a enum
     let strategy = match injection_strategy.as_str() {
         "primary_only" => InjectionStrategy::PrimaryOnly,
@@ -211,6 +641,11 @@ pub async fn start_multi_process_injection(
         injection_strategy: strategy,
         sync_translations: true,
         max_processes: 10,
+        app_id,
+        auto_reinject_on_restart: base_config.get("auto_reinject_on_restart")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true),
+        ipc_timeout_secs: base_config.get("ipc_timeout_secs").and_then(|v| v.as_u64()),
     };
     
     // Crea configurazione base injection
@@ -235,11 +670,13 @@ pub async fn start_multi_process_injection(
         cache_enabled: base_config.get("cache_enabled")
             .and_then(|v| v.as_bool())
             .unwrap_or(true),
+        force_anti_cheat_override: force,
     };
     
     // Crea e avvia sistema multi-processo
     match MultiProcessInjekt::new(multi_config, injection_config) {
         Ok(mut multi_injekt) => {
+            multi_injekt.set_app_handle(app);
             match multi_injekt.start() {
                 Ok(()) => {
                     // Salva l'istanza nel singleton
@@ -251,7 +688,9 @@ pub async fn start_multi_process_injection(
                         "game_name": game_name,
                         "status": "started",
                         "start_time": chrono::Utc::now().to_rfc3339(),
-                        "message": "Sistema multi-processo avviato con successo"
+                        "message": "Sistema multi-processo avviato con successo",
+                        "anti_cheat": anti_cheat,
+                        "anti_cheat_override": force && !anti_cheat.is_empty()
                     });
                     
                     log::info!("✅ Sistema multi-processo avviato per: {}", game_name);
@@ -312,7 +751,12 @@ pub async fn get_multi_process_stats(game_name: String) -> Result<serde_json::Va
     
     if let Ok(instances) = MULTI_PROCESS_INSTANCES.lock() {
         if let Some(multi_injekt) = instances.get(&game_name) {
-            let stats = multi_injekt.get_stats();
+            let mut stats = multi_injekt.get_stats();
+            let report = injection_stats_report(Some(&multi_injekt.tracked_pids()));
+            if let Some(obj) = stats.as_object_mut() {
+                obj.insert("targets".to_string(), serde_json::to_value(&report.targets).unwrap_or_default());
+                obj.insert("totals".to_string(), serde_json::to_value(&report.totals).unwrap_or_default());
+            }
             log::info!("✅ Statistiche recuperate per: {}", game_name);
             Ok(stats)
         } else {
@@ -331,17 +775,12 @@ pub async fn get_multi_process_active_processes(game_name: String) -> Result<ser
     
     if let Ok(instances) = MULTI_PROCESS_INSTANCES.lock() {
         if let Some(multi_injekt) = instances.get(&game_name) {
-            let processes = multi_injekt.get_active_processes();
+            // Include liveness: processo vivo, ultimo scambio IPC, target bloccati
+            let processes = multi_injekt.get_target_liveness();
             let result = serde_json::json!({
                 "game_name": game_name,
-                "processes": processes.into_iter().map(|(pid, name, is_primary, injection_active)| {
-                    serde_json::json!({
-                        "pid": pid,
-                        "name": name,
-                        "is_primary": is_primary,
-                        "injection_active": injection_active
-                    })
-                }).collect::<Vec<_>>()
+                "ipc_timeout_secs": multi_injekt.ipc_timeout_secs(),
+                "processes": processes
             });
             
             log::info!("✅ Processi attivi recuperati per: {}", game_name);
@@ -409,3 +848,52 @@ pub async fn list_multi_process_games() -> Result<serde_json::Value, String> {
         Err("Errore accesso istanze multi-processo".to_string())
     }
 }
+
+// === ALLOWLIST / DENYLIST TARGET ===
+
+#[tauri::command]
+pub async fn get_injection_allowlist() -> Result<serde_json::Value, String> {
+    let allowlist = INJECTION_ALLOWLIST.lock()
+        .map_err(|_| "Errore accesso allowlist injection".to_string())?;
+    
+    Ok(serde_json::json!({
+        "entries": allowlist.entries,
+        "enforced": !allowlist.entries.is_empty(),
+        "denied_processes": DENIED_PROCESSES,
+        "denied_patterns": DENIED_PROCESS_PATTERNS
+    }))
+}
+
+#[tauri::command]
+pub async fn add_injection_allowlist_entry(exe_name: Option<String>, app_id: Option<String>) -> Result<serde_json::Value, String> {
+    let mut allowlist = INJECTION_ALLOWLIST.lock()
+        .map_err(|_| "Errore accesso allowlist injection".to_string())?;
+    
+    let added = allowlist.add(exe_name, app_id)?;
+    if added {
+        allowlist.save()?;
+        log::info!("✅ Voce aggiunta all'allowlist injection ({} totali)", allowlist.entries.len());
+    }
+    
+    Ok(serde_json::json!({
+        "added": added,
+        "entries": allowlist.entries
+    }))
+}
+
+#[tauri::command]
+pub async fn remove_injection_allowlist_entry(exe_name: Option<String>, app_id: Option<String>) -> Result<serde_json::Value, String> {
+    let mut allowlist = INJECTION_ALLOWLIST.lock()
+        .map_err(|_| "Errore accesso allowlist injection".to_string())?;
+    
+    let removed = allowlist.remove(exe_name.as_deref(), app_id.as_deref());
+    if removed > 0 {
+        allowlist.save()?;
+        log::info!("🗑️ Rimosse {} voci dall'allowlist injection", removed);
+    }
+    
+    Ok(serde_json::json!({
+        "removed": removed,
+        "entries": allowlist.entries
+    }))
+}
@@ -5,6 +5,7 @@ use std::fs;
 use winreg::enums::*;
 use winreg::RegKey;
 use crate::commands::library::InstalledGame;
+use crate::commands::store_detection::{ConnectionCheck, StoreConnectionState};
 use std::path::PathBuf;
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
 use aes_gcm::aead::Aead;
@@ -23,6 +24,23 @@ pub struct ItchioGame {
     pub last_modified: Option<u64>,
     pub author: Option<String>,
     pub version: Option<String>,
+    /// ID del gioco su itch.io (da butler.db)
+    pub itch_game_id: Option<i64>,
+    /// ID dell'upload installato (da butler.db)
+    pub upload_id: Option<i64>,
+}
+
+/// Installazione ("cave") letta dal database `butler.db` dell'app itch
+#[derive(Debug, Clone)]
+struct ItchioCave {
+    game_id: i64,
+    upload_id: Option<i64>,
+    title: String,
+    install_path: PathBuf,
+    executable: Option<String>,
+    installed_size: Option<u64>,
+    cover_url: Option<String>,
+    last_touched_at: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -37,21 +55,21 @@ struct ItchioCredentials {
 pub async fn get_itchio_installed_games() -> Result<Vec<InstalledGame>, String> {
     let mut games = Vec::new();
     
-    // 1. Scansiona giochi itch.io dal registro
+    // 1. Scansiona database itch.io app (se disponibile, ha la precedenza nei duplicati)
+    if let Ok(db_games) = scan_itchio_database().await {
+        games.extend(db_games);
+    }
+    
+    // 2. Scansiona giochi itch.io dal registro
     if let Ok(itchio_games) = scan_itchio_registry().await {
         games.extend(itchio_games);
     }
     
-    // 2. Scansiona cartelle di installazione itch.io
+    // 3. Scansiona cartelle di installazione itch.io
     if let Ok(folder_games) = scan_itchio_folders().await {
         games.extend(folder_games);
     }
     
-    // 3. Scansiona database itch.io app (se disponibile)
-    if let Ok(db_games) = scan_itchio_database().await {
-        games.extend(db_games);
-    }
-    
     // Rimuovi duplicati basandosi sul nome del gioco
     let mut unique_games = Vec::new();
     let mut seen_names = std::collections::HashSet::new();
@@ -144,34 +162,131 @@ async fn scan_itchio_folders() -> Result<Vec<InstalledGame>, String> {
 
 /// Scansiona database itch.io app (se disponibile)
 async fn scan_itchio_database() -> Result<Vec<InstalledGame>, String> {
-    let games = Vec::new();
-    
-    // itch.io app memorizza i dati in SQLite, ma per semplicità
-    // cerchiamo i file di configurazione JSON
-    let username = std::env::var("USERNAME").unwrap_or_else(|_| "User".to_string());
-    
-    let config_paths = vec![
-        format!(r"C:\Users\{}\AppData\Roaming\itch\db\butler.db", username),
-        format!(r"C:\Users\{}\AppData\Roaming\itch\preferences.json", username),
-        format!(r"C:\Users\{}\AppData\Local\itch\preferences.json", username),
-    ];
-    
-    for config_path in config_paths {
-        let path = Path::new(&config_path);
-        if path.exists() {
-            // Per ora, saltiamo il parsing del database SQLite
-            // In futuro si potrebbe implementare il parsing del database
-            // per ottenere informazioni più dettagliate sui giochi
-            break;
+    let caves = read_itchio_caves().await?;
+    
+    Ok(caves.into_iter().map(|cave| InstalledGame {
+        id: format!("itchio_{}", cave.game_id),
+        name: cave.title,
+        path: cave.install_path.to_string_lossy().to_string(),
+        executable: cave.executable,
+        size_bytes: cave.installed_size,
+        last_modified: cave.last_touched_at,
+        platform: "itch.io".to_string(),
+    }).collect())
+}
+
+/// Cartella dati dell'app itch (%APPDATA%\itch su Windows, ~/.config/itch su Linux)
+fn itchio_app_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("itch"))
+}
+
+/// Legge le installazioni dal database butler dell'app itch
+async fn read_itchio_caves() -> Result<Vec<ItchioCave>, String> {
+    let db_path = itchio_app_dir()
+        .map(|dir| dir.join("db").join("butler.db"))
+        .filter(|path| path.exists())
+        .ok_or_else(|| "App itch non installata (butler.db non trovato)".to_string())?;
+    
+    tokio::task::spawn_blocking(move || read_itchio_caves_sync(&db_path))
+        .await
+        .map_err(|e| format!("Errore lettura database itch: {}", e))?
+}
+
+fn read_itchio_caves_sync(db_path: &Path) -> Result<Vec<ItchioCave>, String> {
+    let conn = rusqlite::Connection::open_with_flags(
+        db_path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY
+    ).map_err(|e| format!("Errore apertura database itch: {}", e))?;
+    
+    // caves = installazioni, install_locations = cartelle base configurate nell'app
+    let query = "SELECT c.game_id, c.upload_id, c.install_folder_name, c.installed_size, c.verdict, \
+                        c.last_touched_at, g.title, g.cover_url, l.path \
+                 FROM caves c \
+                 LEFT JOIN games g ON g.id = c.game_id \
+                 LEFT JOIN install_locations l ON l.id = c.install_location_id";
+    
+    let mut stmt = conn.prepare(query)
+        .map_err(|e| format!("Schema butler.db non supportato: {}", e))?;
+    
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<i64>>(1).ok().flatten(),
+            row.get::<_, Option<String>>(2).ok().flatten(),
+            row.get::<_, Option<i64>>(3).ok().flatten(),
+            row.get::<_, Option<String>>(4).ok().flatten(),
+            row.get::<_, Option<String>>(5).ok().flatten(),
+            row.get::<_, Option<String>>(6).ok().flatten(),
+            row.get::<_, Option<String>>(7).ok().flatten(),
+            row.get::<_, Option<String>>(8).ok().flatten(),
+        ))
+    }).map_err(|e| format!("Errore query database itch: {}", e))?;
+    
+    let mut caves = Vec::new();
+    for (game_id, upload_id, folder_name, installed_size, verdict, last_touched_at, title, cover_url, location) in rows.flatten() {
+        let (Some(folder_name), Some(location)) = (folder_name, location) else { continue };
+        let install_path = Path::new(&location).join(&folder_name);
+        if !install_path.exists() {
+            continue;
         }
+        
+        caves.push(ItchioCave {
+            game_id,
+            upload_id,
+            title: title.unwrap_or_else(|| clean_itchio_game_name(&folder_name)),
+            executable: verdict.as_deref().and_then(|v| executable_from_verdict(&install_path, v)),
+            install_path,
+            installed_size: installed_size.and_then(|size| u64::try_from(size).ok()),
+            cover_url,
+            last_touched_at: last_touched_at
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(&t).ok())
+                .and_then(|t| u64::try_from(t.timestamp()).ok()),
+        });
     }
     
-    Ok(games)
+    Ok(caves)
 }
 
-/// Test della connessione itch.io API
+/// Eseguibile principale dal "verdict" di butler (JSON con i candidati al lancio)
+fn executable_from_verdict(install_path: &Path, verdict: &str) -> Option<String> {
+    let verdict: serde_json::Value = serde_json::from_str(verdict).ok()?;
+    let candidates = verdict.get("candidates")?.as_array()?;
+    
+    let candidate = candidates.iter()
+        .find(|c| c.get("flavor").and_then(|f| f.as_str()) == Some("windows"))
+        .or_else(|| candidates.first())?;
+    let relative = candidate.get("path")?.as_str()?;
+    
+    let base = verdict.get("basePath").and_then(|b| b.as_str())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| install_path.to_path_buf());
+    Some(base.join(relative).to_string_lossy().to_string())
+}
+
+/// Giochi itch installati (da butler.db) nel formato `GameInfo` della libreria
 #[tauri::command]
-pub async fn test_itchio_connection(api_key: Option<String>) -> Result<String, String> {
+pub async fn get_itchio_installed_games_info() -> Result<Vec<crate::models::GameInfo>, String> {
+    println!("[ITCH.IO] Scansione butler.db");
+    
+    let caves = read_itchio_caves().await?;
+    println!("[ITCH.IO] ✅ {} giochi installati trovati", caves.len());
+    
+    Ok(caves.into_iter().map(|cave| crate::models::GameInfo {
+        id: format!("itchio_{}", cave.game_id),
+        title: cave.title,
+        platform: "itch.io".to_string(),
+        install_path: Some(cave.install_path.to_string_lossy().to_string()),
+        executable_path: cave.executable,
+        header_image: cave.cover_url.clone(),
+        image_url: cave.cover_url,
+        is_installed: true,
+        last_played: cave.last_touched_at,
+        ..Default::default()
+    }).collect())
+}
+
+/// Test della connessione itch.io API, con esito strutturato per la dashboard degli store
+pub(crate) async fn check_itchio_connection(api_key: Option<String>) -> Result<ConnectionCheck, String> {
     println!("[ITCH.IO] Test connessione API");
     
     // Prima prova con API key passata, poi con credenziali salvate
@@ -201,17 +316,17 @@ pub async fn test_itchio_connection(api_key: Option<String>) -> Result<String, S
             Ok(user) => {
                 match get_itchio_owned_games(&key).await {
                     Ok(games) => {
-                        Ok(format!("✅ Connesso come '{}' - {} giochi trovati", 
+                        Ok(ConnectionCheck::new(StoreConnectionState::Connected, format!("✅ Connesso come '{}' - {} giochi trovati", 
                                   user.display_name.unwrap_or_else(|| user.username), 
-                                  games.len()))
+                                  games.len())))
                     }
                     Err(e) => {
                         if e.contains("403") || e.contains("Forbidden") {
-                            Ok(format!("✅ Connesso come '{}' - Autenticazione riuscita (limitazioni API per lista giochi)", 
-                                      user.display_name.unwrap_or_else(|| user.username)))
+                            Ok(ConnectionCheck::new(StoreConnectionState::Connected, format!("✅ Connesso come '{}' - Autenticazione riuscita (limitazioni API per lista giochi)", 
+                                      user.display_name.unwrap_or_else(|| user.username))))
                         } else {
-                            Ok(format!("✅ Connesso come '{}' - Autenticazione riuscita", 
-                                      user.display_name.unwrap_or_else(|| user.username)))
+                            Ok(ConnectionCheck::new(StoreConnectionState::Connected, format!("✅ Connesso come '{}' - Autenticazione riuscita", 
+                                      user.display_name.unwrap_or_else(|| user.username))))
                         }
                     }
                 }
@@ -222,15 +337,40 @@ pub async fn test_itchio_connection(api_key: Option<String>) -> Result<String, S
         // Fallback a scansione locale
         println!("[ITCH.IO] Nessuna API key - fallback a scansione locale");
         let games = get_itchio_installed_games().await?;
-        Ok(format!("Scansione locale completata: {} giochi trovati", games.len()))
+        Ok(ConnectionCheck::new(StoreConnectionState::LocalOnly, format!("Scansione locale completata: {} giochi trovati", games.len())).with_local_games(games.len()))
     }
 }
 
+/// Test della connessione itch.io API
+#[tauri::command]
+pub async fn test_itchio_connection(api_key: Option<String>) -> Result<String, String> {
+    check_itchio_connection(api_key).await.map(|check| check.message)
+}
+
 /// Recupera informazioni su un gioco itch.io specifico
 #[tauri::command]
 pub async fn get_itchio_game_info(game_id: String) -> Result<ItchioGame, String> {
     println!("[ITCH.IO] Recupero informazioni per: {}", game_id);
     
+    // Prima il database dell'app itch (ha upload id e titolo ufficiale)
+    if let Ok(caves) = read_itchio_caves().await {
+        if let Some(cave) = caves.into_iter().find(|c| format!("itchio_{}", c.game_id) == game_id) {
+            return Ok(ItchioGame {
+                id: game_id,
+                title: cave.title,
+                install_path: Some(cave.install_path.to_string_lossy().to_string()),
+                executable: cave.executable,
+                platform: "itch.io".to_string(),
+                size_bytes: cave.installed_size,
+                last_modified: cave.last_touched_at,
+                author: None,
+                version: None,
+                itch_game_id: Some(cave.game_id),
+                upload_id: cave.upload_id,
+            });
+        }
+    }
+    
     let games = get_itchio_installed_games().await?;
     
     for game in games {
@@ -245,6 +385,8 @@ pub async fn get_itchio_game_info(game_id: String) -> Result<ItchioGame, String>
                 last_modified: game.last_modified,
                 author: None, // Potrebbe essere estratto dal nome della cartella
                 version: None, // Potrebbe essere estratto dai metadati
+                itch_game_id: None,
+                upload_id: None,
             });
         }
     }
@@ -607,7 +749,7 @@ pub async fn connect_itchio(api_key: String) -> Result<String, String> {
 
 // Funzioni per gestione credenziali itch.io
 
-fn get_itchio_credentials_path() -> Result<PathBuf, String> {
+pub(crate) fn get_itchio_credentials_path() -> Result<PathBuf, String> {
     let mut path = std::env::current_dir()
         .map_err(|e| format!("Errore getting current dir: {}", e))?;
     path.push(".cache");
@@ -1,6 +1,7 @@
 // use tauri::api::shell; // Rimosso - non più disponibile in Tauri v2
 use std::process::Command;
 use std::path::Path;
+use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 use log::{info, warn, error, debug};
 
@@ -22,6 +23,84 @@ pub struct LaunchRequest {
     pub launch_options: Option<String>,
 }
 
+/// Comando di avvio risolto ma non eseguito (anteprima per script e diagnostica)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LaunchSpec {
+    pub store: String,
+    /// Stesso valore di `LaunchResult::method` che produrrebbe l'avvio reale
+    pub method: String,
+    /// URI di protocollo (steam://, com.epicgames.launcher://, goggalaxy://) se usato
+    pub uri: Option<String>,
+    pub program: String,
+    pub args: Vec<String>,
+    /// None = directory corrente di GameStringer (ereditata dal processo figlio)
+    pub working_dir: Option<String>,
+    /// Variabili d'ambiente aggiunte al processo (vuoto = ambiente ereditato)
+    pub env: HashMap<String, String>,
+    /// Riga di comando pronta da copiare
+    pub command_line: String,
+    /// Metodi alternativi tentati se il primo fallisce
+    pub fallbacks: Vec<LaunchSpec>,
+    pub warnings: Vec<String>,
+}
+
+impl LaunchSpec {
+    fn new(store: &str, method: &str, uri: Option<String>, program: String, args: Vec<String>) -> Self {
+        let command_line = std::iter::once(program.as_str())
+            .chain(args.iter().map(|a| a.as_str()))
+            .map(quote_arg)
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            store: store.to_string(),
+            method: method.to_string(),
+            uri,
+            program,
+            args,
+            working_dir: None,
+            env: HashMap::new(),
+            command_line,
+            fallbacks: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+    
+    /// Avvio via URI di protocollo, come fanno `launch_with_*_protocol`
+    fn protocol(store: &str, method: &str, uri: String) -> Self {
+        #[cfg(target_os = "windows")]
+        let (program, args) = ("cmd".to_string(), vec!["/C".to_string(), "start".to_string(), uri.clone()]);
+        #[cfg(not(target_os = "windows"))]
+        let (program, args) = ("xdg-open".to_string(), vec![uri.clone()]);
+        #[allow(unused_mut)]
+        let mut spec = Self::new(store, method, Some(uri.clone()), program, args);
+        #[cfg(target_os = "windows")]
+        if uri.contains('&') {
+            spec.warnings.push("cmd /C start interpreta '&' come separatore: i parametri dopo '&' possono andare persi".to_string());
+        }
+        spec
+    }
+}
+
+fn quote_arg(arg: &str) -> String {
+    if arg.is_empty() || arg.contains(char::is_whitespace) || arg.contains('&') {
+        format!("\"{}\"", arg)
+    } else {
+        arg.to_string()
+    }
+}
+
+fn steam_protocol_url(app_id: &str) -> String {
+    format!("steam://rungameid/{}", app_id)
+}
+
+fn epic_protocol_url(app_name: &str) -> String {
+    format!("com.epicgames.launcher://apps/{}?action=launch&silent=true", app_name)
+}
+
+fn gog_protocol_url(game_id: &str) -> String {
+    format!("goggalaxy://openGameView/{}", game_id)
+}
+
 // ================================================================================================
 // STEAM LAUNCH SYSTEM
 // ================================================================================================
@@ -56,7 +135,7 @@ pub async fn launch_steam_game(app_id: String) -> Result<LaunchResult, String> {
     }
 
     // Metodo 2: Fallback - Protocollo Steam
-    let steam_url = format!("steam://rungameid/{}", app_id);
+    let steam_url = steam_protocol_url(&app_id);
     match launch_with_steam_protocol(&steam_url, &app_id).await {
         Ok(result) => {
             info!("✅ Gioco Steam {} avviato con successo via protocollo", app_id);
@@ -212,7 +291,7 @@ pub async fn launch_epic_game(app_name: String) -> Result<LaunchResult, String>
     }
 
     // Metodo 1: Protocollo Epic Games
-    let epic_url = format!("com.epicgames.launcher://apps/{}?action=launch&silent=true", app_name);
+    let epic_url = epic_protocol_url(&app_name);
     match launch_with_epic_protocol(&epic_url, &app_name).await {
         Ok(result) => {
             info!("✅ Gioco Epic Games {} avviato con successo", app_name);
@@ -371,7 +450,7 @@ pub async fn launch_gog_game(game_id: String) -> Result<LaunchResult, String> {
     }
 
     // Metodo 1: Protocollo GOG Galaxy
-    let gog_url = format!("goggalaxy://openGameView/{}", game_id);
+    let gog_url = gog_protocol_url(&game_id);
     match launch_with_gog_protocol(&gog_url, &game_id).await {
         Ok(result) => {
             info!("✅ Gioco GOG {} avviato con successo", game_id);
@@ -601,6 +680,95 @@ pub async fn launch_game_universal(request: LaunchRequest) -> Result<LaunchResul
     }
 }
 
+/// Restituisce il comando che `launch_game_universal` eseguirebbe, senza avviare nulla
+#[tauri::command]
+pub async fn get_launch_command(request: LaunchRequest) -> Result<LaunchSpec, String> {
+    debug!("🔍 Anteprima comando di avvio: {} (Store: {})", request.game_name, request.store);
+    
+    match request.store.to_lowercase().as_str() {
+        "steam" => {
+            if !is_valid_steam_app_id(&request.game_id) {
+                return Err(format!("App ID Steam non valido: {}", request.game_id));
+            }
+            let protocol = LaunchSpec::protocol("Steam", "steam_protocol", steam_protocol_url(&request.game_id));
+            match find_steam_executable() {
+                Ok(steam_path) => {
+                    let mut spec = LaunchSpec::new(
+                        "Steam",
+                        "steam_direct",
+                        None,
+                        steam_path,
+                        vec!["-applaunch".to_string(), request.game_id.clone()],
+                    );
+                    spec.fallbacks.push(protocol);
+                    Ok(spec)
+                }
+                Err(e) => {
+                    let mut spec = protocol;
+                    spec.warnings.push(format!("{}: verrà usato il protocollo steam://", e));
+                    Ok(spec)
+                }
+            }
+        }
+        "epic games" | "epic" => {
+            if request.game_id.trim().is_empty() {
+                return Err("App Name Epic Games non può essere vuoto".to_string());
+            }
+            let mut spec = LaunchSpec::protocol("Epic Games", "epic_protocol", epic_protocol_url(&request.game_id));
+            match find_epic_launcher_executable() {
+                Ok(epic_path) => spec.fallbacks.push(LaunchSpec::new(
+                    "Epic Games",
+                    "epic_direct",
+                    None,
+                    epic_path,
+                    vec!["-openapp".to_string(), request.game_id.clone()],
+                )),
+                Err(e) => spec.warnings.push(format!("Nessun fallback diretto: {}", e)),
+            }
+            Ok(spec)
+        }
+        "gog" => {
+            if request.game_id.trim().is_empty() {
+                return Err("Game ID GOG non può essere vuoto".to_string());
+            }
+            let mut spec = LaunchSpec::protocol("GOG", "gog_protocol", gog_protocol_url(&request.game_id));
+            match find_gog_galaxy_executable() {
+                Ok(gog_path) => spec.fallbacks.push(LaunchSpec::new(
+                    "GOG",
+                    "gog_direct",
+                    None,
+                    gog_path,
+                    vec![format!("/gameId={}", request.game_id)],
+                )),
+                Err(e) => spec.warnings.push(format!("Nessun fallback diretto: {}", e)),
+            }
+            Ok(spec)
+        }
+        _ => {
+            let exe_path = request.executable_path.ok_or_else(|| {
+                format!("Store non supportato e nessun percorso eseguibile fornito: {}", request.store)
+            })?;
+            let args: Vec<String> = request.launch_options
+                .as_deref()
+                .map(|options| options.split_whitespace().map(|a| a.to_string()).collect())
+                .unwrap_or_default();
+            let exists = Path::new(&exe_path).exists();
+            let mut spec = LaunchSpec::new("Direct", "direct_execution", None, exe_path.clone(), args);
+            if !exists {
+                spec.warnings.push(format!("Eseguibile non trovato: {}", exe_path));
+            }
+            // launch_game_direct non imposta current_dir: il gioco eredita quella di GameStringer
+            if let Some(exe_dir) = Path::new(&exe_path).parent() {
+                spec.warnings.push(format!(
+                    "La directory di lavoro non viene impostata; alcuni giochi richiedono {}",
+                    exe_dir.display()
+                ));
+            }
+            Ok(spec)
+        }
+    }
+}
+
 // ================================================================================================
 // UTILITY FUNCTIONS
 // ================================================================================================
@@ -638,6 +806,225 @@ pub async fn get_installed_launchers() -> Result<Vec<String>, String> {
     Ok(launchers)
 }
 
+/// Singolo controllo della diagnostica launcher
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LauncherCheck {
+    pub name: String,
+    pub passed: bool,
+    /// false = informativo, non impedisce l'avvio dei giochi
+    pub required: bool,
+    pub detail: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LauncherDiagnosis {
+    pub store: String,
+    pub installed: bool,
+    pub executable_path: Option<String>,
+    pub protocol: String,
+    pub protocol_handler: Option<String>,
+    pub checks: Vec<LauncherCheck>,
+    /// true se tutti i controlli obbligatori sono superati
+    pub healthy: bool,
+    pub suggestion: Option<String>,
+}
+
+/// Legge il comando registrato per un protocollo URI (HKCR\<protocollo>\shell\open\command)
+fn registered_protocol_handler(protocol: &str) -> Option<String> {
+    #[cfg(target_os = "windows")]
+    {
+        use winreg::enums::*;
+        use winreg::RegKey;
+        
+        let hkcr = RegKey::predef(HKEY_CLASSES_ROOT);
+        let key = hkcr.open_subkey(format!("{}\\shell\\open\\command", protocol)).ok()?;
+        key.get_value::<String, _>("").ok().filter(|cmd| !cmd.trim().is_empty())
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = protocol;
+        None
+    }
+}
+
+/// Estrae il percorso dell'eseguibile da una riga di comando registrata ("C:\x.exe" %1)
+fn handler_executable(command: &str) -> String {
+    let trimmed = command.trim();
+    if let Some(rest) = trimmed.strip_prefix('"') {
+        rest.split('"').next().unwrap_or("").to_string()
+    } else {
+        trimmed.split_whitespace().next().unwrap_or("").to_string()
+    }
+}
+
+/// Verifica che il file sia un eseguibile PE leggibile (header "MZ")
+fn check_executable_runnable(path: &str) -> Result<(), String> {
+    use std::io::Read;
+    let mut file = std::fs::File::open(path).map_err(|e| format!("impossibile aprire: {}", e))?;
+    let mut header = [0u8; 2];
+    file.read_exact(&mut header).map_err(|e| format!("file illeggibile: {}", e))?;
+    if &header != b"MZ" {
+        return Err("non è un eseguibile Windows valido".to_string());
+    }
+    Ok(())
+}
+
+fn diagnose_launcher(
+    store: &str,
+    protocol: &str,
+    executable: Result<String, String>,
+    has_credentials: bool,
+    reinstall_hint: &str,
+) -> LauncherDiagnosis {
+    let mut checks = Vec::new();
+    let mut suggestion: Option<String> = None;
+    let mut suggest = |text: String| {
+        if suggestion.is_none() {
+            suggestion = Some(text);
+        }
+    };
+    
+    let (installed, executable_path) = match executable {
+        Ok(path) => {
+            checks.push(LauncherCheck { name: "installed".to_string(), passed: true, required: true, detail: path.clone() });
+            (true, Some(path))
+        }
+        Err(e) => {
+            checks.push(LauncherCheck { name: "installed".to_string(), passed: false, required: true, detail: e });
+            suggest(format!("{} non è installato o non è nel percorso standard: {}", store, reinstall_hint));
+            (false, None)
+        }
+    };
+    
+    if let Some(path) = &executable_path {
+        match check_executable_runnable(path) {
+            Ok(()) => checks.push(LauncherCheck {
+                name: "executable_runnable".to_string(), passed: true, required: true,
+                detail: "Eseguibile PE valido".to_string(),
+            }),
+            Err(e) => {
+                checks.push(LauncherCheck { name: "executable_runnable".to_string(), passed: false, required: true, detail: e.clone() });
+                suggest(format!("L'eseguibile di {} è danneggiato o bloccato ({}): {}", store, e, reinstall_hint));
+            }
+        }
+    }
+    
+    let protocol_handler = registered_protocol_handler(protocol);
+    match &protocol_handler {
+        Some(command) => {
+            let handler_exe = handler_executable(command);
+            let exists = Path::new(&handler_exe).exists();
+            checks.push(LauncherCheck {
+                name: "protocol_handler".to_string(),
+                passed: exists,
+                required: true,
+                detail: if exists { command.clone() } else { format!("Il gestore punta a un file inesistente: {}", handler_exe) },
+            });
+            if !exists {
+                suggest(format!("Il gestore URI {}:// di {} punta a un percorso non valido: {}", protocol, store, reinstall_hint));
+            }
+        }
+        None => {
+            checks.push(LauncherCheck {
+                name: "protocol_handler".to_string(), passed: false, required: true,
+                detail: format!("Protocollo {}:// non registrato", protocol),
+            });
+            suggest(format!("Il gestore URI {}:// di {} non è registrato: {}", protocol, store, reinstall_hint));
+        }
+    }
+    
+    checks.push(LauncherCheck {
+        name: "credentials".to_string(),
+        passed: has_credentials,
+        required: false,
+        detail: if has_credentials {
+            "Credenziali salvate in GameStringer".to_string()
+        } else {
+            "Nessuna credenziale salvata: la libreria online non sarà disponibile".to_string()
+        },
+    });
+    
+    let healthy = checks.iter().all(|c| c.passed || !c.required);
+    if healthy && !has_credentials {
+        suggest(format!("Avvio OK. Collega l'account {} nelle impostazioni per importare la libreria completa", store));
+    }
+    
+    LauncherDiagnosis {
+        store: store.to_string(),
+        installed,
+        executable_path,
+        protocol: format!("{}://", protocol),
+        protocol_handler,
+        checks,
+        healthy,
+        suggestion,
+    }
+}
+
+/// Credenziali salvate per uno store: nel profilo attivo (dove le porta la migrazione) o,
+/// se non ancora migrate, nel file legacy
+async fn store_has_credentials(
+    manager: &crate::profiles::ProfileManager,
+    store: crate::profiles::StoreType,
+    has_legacy_file: bool,
+) -> bool {
+    has_legacy_file || matches!(manager.load_credential_for_active_profile(store).await, Ok(Some(_)))
+}
+
+/// Diagnostica di tutti i launcher: installazione, eseguibile, gestore URI e credenziali
+#[tauri::command]
+pub async fn diagnose_launchers(
+    profile_state: tauri::State<'_, crate::commands::profiles::ProfileManagerState>,
+) -> Result<Vec<LauncherDiagnosis>, String> {
+    use crate::profiles::StoreType;
+
+    info!("🩺 Diagnostica launcher in corso");
+    
+    let (steam_credentials, epic_credentials, gog_credentials) = {
+        let manager = profile_state.manager.lock().await;
+        (
+            store_has_credentials(&manager, StoreType::Steam, crate::commands::steam::has_saved_credentials()).await,
+            store_has_credentials(&manager, StoreType::Epic, crate::commands::epic::has_saved_credentials()).await,
+            store_has_credentials(&manager, StoreType::Gog, crate::commands::gog::has_saved_credentials()).await,
+        )
+    };
+    
+    let diagnoses = vec![
+        diagnose_launcher(
+            "Steam",
+            "steam",
+            find_steam_executable(),
+            steam_credentials,
+            "reinstalla Steam da store.steampowered.com",
+        ),
+        diagnose_launcher(
+            "Epic Games",
+            "com.epicgames.launcher",
+            find_epic_launcher_executable(),
+            epic_credentials,
+            "reinstalla Epic Games Launcher",
+        ),
+        diagnose_launcher(
+            "GOG",
+            "goggalaxy",
+            find_gog_galaxy_executable(),
+            gog_credentials,
+            "reinstalla GOG Galaxy",
+        ),
+    ];
+    
+    for diagnosis in &diagnoses {
+        if diagnosis.healthy {
+            info!("✅ {}: OK", diagnosis.store);
+        } else {
+            warn!("⚠️ {}: {}", diagnosis.store, diagnosis.suggestion.as_deref().unwrap_or("problemi rilevati"));
+        }
+    }
+    
+    Ok(diagnoses)
+}
+
 /// Testa la funzionalità di avvio per un launcher specifico
 #[tauri::command]
 pub async fn test_launcher_functionality(launcher: String) -> Result<LaunchResult, String> {
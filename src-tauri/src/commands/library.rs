@@ -1,9 +1,19 @@
 use serde::{Serialize, Deserialize};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tauri::{AppHandle, Emitter};
 use winreg::enums::*;
 use winreg::RegKey;
 
+use super::file_manager::decode_text;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct InstalledGame {
     pub id: String,
@@ -117,28 +127,10 @@ async fn get_steam_installed_games() -> Result<Vec<InstalledGame>, String> {
 }
 
 async fn parse_acf_file(file_path: &Path) -> Result<InstalledGame, String> {
-    let content = fs::read_to_string(file_path)
-        .map_err(|e| format!("Failed to read ACF file: {}", e))?;
-    
-    // Simple ACF parsing - extract appid, name, and installdir
-    let mut app_id = String::new();
-    let mut name = String::new();
-    let mut install_dir = String::new();
-    
-    for line in content.lines() {
-        let line = line.trim();
-        if line.starts_with("\"appid\"") {
-            app_id = extract_quoted_value(line);
-        } else if line.starts_with("\"name\"") {
-            name = extract_quoted_value(line);
-        } else if line.starts_with("\"installdir\"") {
-            install_dir = extract_quoted_value(line);
-        }
-    }
-    
-    if app_id.is_empty() || name.is_empty() {
-        return Err("Invalid ACF file".to_string());
-    }
+    let manifest = crate::vdf::AppManifest::parse_file(file_path)?;
+    let app_id = manifest.appid;
+    let name = manifest.name.filter(|n| !n.is_empty()).ok_or("Invalid ACF file")?;
+    let install_dir = manifest.install_dir.unwrap_or_default();
     
     // Construct full path
     let steam_path = find_steam_path_from_registry()
@@ -356,18 +348,6 @@ async fn find_main_executable(game_path: &Path) -> Option<String> {
     None
 }
 
-fn extract_quoted_value(line: &str) -> String {
-    // Extract value from "key" "value" format
-    let parts: Vec<&str> = line.split('\t').collect();
-    if parts.len() >= 2 {
-        let value_part = parts[1].trim();
-        if value_part.starts_with('"') && value_part.ends_with('"') {
-            return value_part[1..value_part.len()-1].to_string();
-        }
-    }
-    String::new()
-}
-
 #[tauri::command]
 pub async fn get_game_path(game_id: String) -> Result<String, String> {
     println!("[RUST] get_game_path called for game_id: {}", game_id);
@@ -380,10 +360,13 @@ pub async fn get_game_path(game_id: String) -> Result<String, String> {
 #[tauri::command]
 pub async fn read_game_file(file_path: String) -> Result<String, String> {
     println!("[RUST] read_game_file called for file_path: {}", file_path);
-    
-    // This would implement the logic from /api/library/read-file
-    // For now, return a placeholder
-    Err("Not yet implemented".to_string())
+
+    tauri::async_runtime::spawn_blocking(move || {
+        read_text_range(Path::new(&file_path), DEFAULT_MAX_READ_BYTES, None, None)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map(|chunk| chunk.content)
 }
 
 #[tauri::command]
@@ -427,64 +410,622 @@ pub async fn list_directory_files(path: String) -> Result<Vec<String>, String> {
     Ok(files)
 }
 
+// --- Scansione incrementale (eventi a batch) ---
+
+/// Evento con un batch di file trovati
+pub const DIRECTORY_SCAN_BATCH_EVENT: &str = "directory-scan-batch";
+/// Evento finale (completata, annullata o fallita)
+pub const DIRECTORY_SCAN_COMPLETE_EVENT: &str = "directory-scan-complete";
+
+/// Token di annullamento per le scansioni attive (scan_id -> flag)
+static ACTIVE_SCANS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Intervallo massimo tra due batch anche se il batch non è pieno
+const SCAN_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DirectoryScanBatch {
+    pub scan_id: String,
+    pub files: Vec<ScannedFile>,
+    pub total_so_far: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DirectoryScanComplete {
+    pub scan_id: String,
+    pub total: usize,
+    pub cancelled: bool,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Avvia una scansione in background che emette `directory-scan-batch` man mano che trova file
+/// e `directory-scan-complete` alla fine. Restituisce subito lo `scanId` da usare per annullarla.
+/// Il client può passare il proprio `scanId` per registrare i listener prima che arrivi il primo
+/// batch; altrimenti ne viene generato uno. Senza `extensions` vengono riportati tutti i file.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn start_directory_scan(
+    app: AppHandle,
+    path: String,
+    extensions: Option<Vec<String>>,
+    max_depth: Option<u32>,
+    batch_size: Option<usize>,
+    scan_id: Option<String>,
+) -> Result<String, String> {
+    let root = PathBuf::from(&path);
+    if !root.is_dir() {
+        return Err(format!("Directory not found: {}", path));
+    }
+
+    let scan_id = scan_id
+        .filter(|id| !id.trim().is_empty())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut scans = ACTIVE_SCANS.lock();
+        if scans.contains_key(&scan_id) {
+            return Err(format!("Scan already running: {}", scan_id));
+        }
+        scans.insert(scan_id.clone(), cancel.clone());
+    }
+
+    let extensions: Vec<String> = extensions.unwrap_or_default().iter().map(|e| e.to_lowercase()).collect();
+    let max_depth = max_depth.unwrap_or(u32::MAX);
+    let batch_size = batch_size.unwrap_or(200).clamp(1, 5000);
+
+    println!("[RUST] start_directory_scan {} for path: {}", scan_id, path);
+
+    let id = scan_id.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let started = Instant::now();
+        let result = stream_directory(&app, &id, &root, &extensions, max_depth, batch_size, &cancel);
+        ACTIVE_SCANS.lock().remove(&id);
+
+        let (total, error) = match result {
+            Ok(total) => (total, None),
+            Err((total, e)) => (total, Some(e)),
+        };
+        let complete = DirectoryScanComplete {
+            scan_id: id,
+            total,
+            cancelled: cancel.load(Ordering::Relaxed),
+            duration_ms: started.elapsed().as_millis() as u64,
+            error,
+        };
+        println!("[RUST] Directory scan {} finished: {} files (cancelled: {})", complete.scan_id, complete.total, complete.cancelled);
+        if let Err(e) = app.emit(DIRECTORY_SCAN_COMPLETE_EVENT, &complete) {
+            println!("[RUST] ⚠️ Errore emissione evento fine scansione: {}", e);
+        }
+    });
+
+    Ok(scan_id)
+}
+
+/// Annulla una scansione attiva; false se lo scan_id non esiste (o è già terminato)
+#[tauri::command(rename_all = "camelCase")]
+pub async fn cancel_directory_scan(scan_id: String) -> Result<bool, String> {
+    match ACTIVE_SCANS.lock().get(&scan_id) {
+        Some(cancel) => {
+            cancel.store(true, Ordering::Relaxed);
+            println!("[RUST] Directory scan {} cancellation requested", scan_id);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Visita iterativa (niente ricorsione su alberi molto profondi) con emissione a batch.
+/// In caso di errore di emissione restituisce anche il totale raggiunto.
+fn stream_directory(
+    app: &AppHandle,
+    scan_id: &str,
+    root: &Path,
+    extensions: &[String],
+    max_depth: u32,
+    batch_size: usize,
+    cancel: &AtomicBool,
+) -> Result<usize, (usize, String)> {
+    let mut stack = vec![(root.to_path_buf(), 0u32)];
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut total = 0usize;
+    let mut last_flush = Instant::now();
+
+    let flush = |batch: &mut Vec<ScannedFile>, total: usize| -> Result<(), (usize, String)> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let event = DirectoryScanBatch {
+            scan_id: scan_id.to_string(),
+            files: std::mem::take(batch),
+            total_so_far: total,
+        };
+        app.emit(DIRECTORY_SCAN_BATCH_EVENT, &event).map_err(|e| (total, e.to_string()))
+    };
+
+    while let Some((dir, depth)) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+
+        for entry in entries.flatten() {
+            if cancel.load(Ordering::Relaxed) {
+                flush(&mut batch, total)?;
+                return Ok(total);
+            }
+
+            let Ok(file_type) = entry.file_type() else { continue };
+            let path = entry.path();
+
+            if file_type.is_dir() {
+                if depth < max_depth {
+                    stack.push((path, depth + 1));
+                }
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+
+            let extension = path.extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            if !extensions.is_empty() && !extensions.contains(&extension) {
+                continue;
+            }
+
+            batch.push(ScannedFile {
+                path: path.to_string_lossy().to_string(),
+                name: entry.file_name().to_string_lossy().to_string(),
+                size: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                extension,
+            });
+            total += 1;
+
+            if batch.len() >= batch_size || last_flush.elapsed() >= SCAN_FLUSH_INTERVAL {
+                flush(&mut batch, total)?;
+                last_flush = Instant::now();
+            }
+        }
+    }
+
+    flush(&mut batch, total)?;
+    Ok(total)
+}
+
+/// Ruolo di un file di localizzazione rispetto all'engine del gioco
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalizationRole {
+    /// Testo libero (.txt/.json/.csv/.xml) dentro `<game>_Data`
+    UnityTextAsset,
+    /// File in `<game>_Data/StreamingAssets`
+    UnityStreamingAsset,
+    /// `resources.assets` (TextAsset serializzati)
+    UnityResourcesAssets,
+    /// Tabella compilata `.locres` in `Content/Localization`
+    UnrealLocres,
+    /// Sorgente gettext `.po` in `Content/Localization`
+    UnrealPo,
+    /// Traduzione compilata `.translation`
+    GodotTranslation,
+    /// Sorgente gettext `.po`
+    GodotPo,
+    /// Engine non supportato: file testuale con nome "da localizzazione"
+    Generic,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LocalizationFile {
+    pub path: String,
+    pub name: String,
+    pub size: u64,
+    pub extension: String,
+    pub engine_role: LocalizationRole,
+    /// Codice lingua ricavato dal nome file o dalla cartella (es. "it", "pt-br")
+    pub language: Option<String>,
+}
+
+/// Scansione dei file di localizzazione.
+/// Senza `engine_hint` filtra solo per estensione; con `engine_hint` la ricerca è guidata
+/// dall'engine ("auto" = rilevamento, oppure "unity"/"unreal"/"godot"):
+/// Unity -> `<game>_Data`, Unreal -> `Content/Localization`, Godot -> `.translation`/`.po`,
+/// per gli altri engine ricerca generica filtrata per nome.
+/// In modalità engine le `extensions`, se presenti, restringono ulteriormente il risultato.
 #[tauri::command(rename_all = "camelCase")]
 pub async fn scan_localization_files(
-    path: String, 
+    path: String,
     extensions: Vec<String>,
-    max_depth: u32
-) -> Result<Vec<ScannedFile>, String> {
-    println!("[RUST] scan_localization_files called for path: {} with extensions: {:?}", path, extensions);
-    
+    max_depth: u32,
+    engine_hint: Option<String>,
+) -> Result<Vec<LocalizationFile>, String> {
+    println!("[RUST] scan_localization_files called for path: {} with extensions: {:?} (engine: {:?})", path, extensions, engine_hint);
+
     let dir_path = Path::new(&path);
-    if !dir_path.exists() {
+    if !dir_path.is_dir() {
         return Err(format!("Directory not found: {}", path));
     }
-    
-    let mut files = Vec::new();
-    scan_directory_recursive(dir_path, &extensions, max_depth, 0, &mut files);
-    
+
+    let extensions: Vec<String> = extensions.iter().map(|e| e.trim_start_matches('.').to_lowercase()).collect();
+    let files = match engine_hint.as_deref() {
+        None => {
+            let mut files = Vec::new();
+            walk_files(dir_path, max_depth, 0, &mut |file| {
+                if extensions.contains(&file_extension(file)) {
+                    files.push(localization_file(file, LocalizationRole::Generic));
+                }
+            });
+            files
+        }
+        Some(hint) => {
+            let engine = engine_from_hint(dir_path, hint);
+            let mut files = collect_engine_localization_files(dir_path, &engine, max_depth);
+            if !extensions.is_empty() {
+                files.retain(|f| extensions.contains(&f.extension));
+            }
+            println!("[RUST] Engine per la scansione: {}", engine.as_str());
+            files
+        }
+    };
+
     println!("[RUST] Found {} localization files", files.len());
     Ok(files)
 }
 
-fn scan_directory_recursive(
-    dir: &Path, 
-    extensions: &[String], 
-    max_depth: u32, 
-    current_depth: u32,
-    files: &mut Vec<ScannedFile>
-) {
+fn engine_from_hint(game_path: &Path, hint: &str) -> crate::engine_detector::GameEngine {
+    use crate::engine_detector::{detect_engine_from_files, GameEngine};
+
+    match hint.trim().to_lowercase().as_str() {
+        "unity" => GameEngine::Unity,
+        "unreal" | "unreal engine" => GameEngine::Unreal,
+        "godot" => GameEngine::Godot,
+        "auto" | "" => detect_engine_from_files(game_path).engine,
+        _ => GameEngine::Unknown,
+    }
+}
+
+fn collect_engine_localization_files(
+    game_path: &Path,
+    engine: &crate::engine_detector::GameEngine,
+    max_depth: u32,
+) -> Vec<LocalizationFile> {
+    use crate::engine_detector::GameEngine;
+
+    let mut files = Vec::new();
+    match engine {
+        GameEngine::Unity => {
+            if let Ok(entries) = fs::read_dir(game_path) {
+                for entry in entries.flatten() {
+                    let data_dir = entry.path();
+                    if data_dir.is_dir() && entry.file_name().to_string_lossy().ends_with("_Data") {
+                        walk_files(&data_dir, max_depth, 0, &mut |file| {
+                            if let Some(role) = unity_role(&data_dir, file) {
+                                files.push(localization_file(file, role));
+                            }
+                        });
+                    }
+                }
+            }
+        }
+        GameEngine::Unreal => {
+            walk_files(game_path, max_depth, 0, &mut |file| {
+                if !is_under_unreal_localization(file) {
+                    return;
+                }
+                let role = match file_extension(file).as_str() {
+                    "locres" => LocalizationRole::UnrealLocres,
+                    "po" => LocalizationRole::UnrealPo,
+                    _ => return,
+                };
+                files.push(localization_file(file, role));
+            });
+        }
+        GameEngine::Godot => {
+            walk_files(game_path, max_depth, 0, &mut |file| {
+                let role = match file_extension(file).as_str() {
+                    "translation" => LocalizationRole::GodotTranslation,
+                    "po" => LocalizationRole::GodotPo,
+                    _ => return,
+                };
+                files.push(localization_file(file, role));
+            });
+        }
+        _ => {
+            walk_files(game_path, max_depth, 0, &mut |file| {
+                if is_generic_localization_file(file) {
+                    files.push(localization_file(file, LocalizationRole::Generic));
+                }
+            });
+        }
+    }
+
+    files
+}
+
+/// Lingua trovata tra i file installati del gioco
+#[derive(Serialize, Debug, Clone)]
+pub struct InstalledLanguage {
+    /// Codice lingua normalizzato (es. "it", "pt-br", "zh-hans")
+    pub code: String,
+    /// Esiste un formato caricato dal gioco (`.locres`, `.mo`, bundle, file in StreamingAssets...).
+    /// Con soli sorgenti (`.po` fuori da Godot, `.archive`) la lingua è su disco ma non selezionabile
+    pub selectable: bool,
+    /// Alcuni file che attestano la lingua
+    pub evidence: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct InstalledLanguagesResult {
+    pub engine: String,
+    /// Lingue presenti su disco
+    pub present: Vec<String>,
+    /// Sottoinsieme selezionabile in gioco
+    pub selectable: Vec<String>,
+    pub languages: Vec<InstalledLanguage>,
+}
+
+/// Estensioni che contengono sempre testi localizzati, ovunque si trovino
+const LANGUAGE_FILE_EXTENSIONS: &[&str] = &["locres", "po", "mo", "lang", "translation"];
+/// Sorgenti di traduzione: non vengono caricati dal gioco
+const LANGUAGE_SOURCE_EXTENSIONS: &[&str] = &["po", "pot", "archive", "manifest", "xlf", "xliff"];
+/// Cartelle che raggruppano i file per lingua
+const LANGUAGE_DIR_NAMES: &[&str] = &["localization", "localisation", "locale", "locales", "lang", "languages", "i18n", "l10n"];
+const MAX_LANGUAGE_EVIDENCE: usize = 5;
+
+fn is_under_language_dir(file: &Path) -> bool {
+    file.parent().is_some_and(|dir| {
+        dir.components().any(|c| {
+            let name = c.as_os_str().to_string_lossy().to_lowercase();
+            LANGUAGE_DIR_NAMES.contains(&name.as_str())
+        })
+    })
+}
+
+fn is_under_unity_streaming_assets(file: &Path) -> bool {
+    let components: Vec<String> = file.components()
+        .map(|c| c.as_os_str().to_string_lossy().to_lowercase())
+        .collect();
+    components.windows(2).any(|pair| pair[0].ends_with("_data") && pair[1] == "streamingassets")
+}
+
+/// Lingua attestata da un file: Unreal usa il nome della cartella della cultura
+/// (`Content/Localization/Game/it/Game.locres`), gli altri nome file o cartella
+fn installed_file_language(file: &Path, unreal: bool, unity: bool) -> Option<String> {
+    let extension = file_extension(file);
+
+    // I template gettext non appartengono a nessuna lingua
+    if extension == "pot" {
+        return None;
+    }
+    if extension == "po" || extension == "mo" {
+        return guess_language_code(file).or_else(|| po_header_language(file));
+    }
+    if unreal && is_under_unreal_localization(file) {
+        return file.parent()
+            .and_then(|p| p.file_name())
+            .and_then(|name| language_from_name(&name.to_string_lossy()));
+    }
+    if LANGUAGE_FILE_EXTENSIONS.contains(&extension.as_str()) || LANGUAGE_SOURCE_EXTENSIONS.contains(&extension.as_str()) {
+        return guess_language_code(file);
+    }
+    if unity && is_under_unity_streaming_assets(file) {
+        let lower = file.to_string_lossy().to_lowercase();
+        if ["local", "lang", "i18n", "l10n", "translation", "string-table"].iter().any(|hint| lower.contains(hint)) {
+            return guess_language_code(file);
+        }
+        return None;
+    }
+    if is_under_language_dir(file) {
+        return guess_language_code(file);
+    }
+    None
+}
+
+/// Lingua dichiarata nell'header di un catalogo gettext (`"Language: pt_BR\n"`).
+/// Legge solo l'inizio del file: l'header è la prima voce.
+fn po_header_language(file: &Path) -> Option<String> {
+    const HEADER_BYTES: u64 = 8 * 1024;
+
+    let mut header = Vec::new();
+    fs::File::open(file).ok()?.take(HEADER_BYTES).read_to_end(&mut header).ok()?;
+    let header = String::from_utf8_lossy(&header);
+
+    header.lines().find_map(|line| {
+        let value = line.trim().trim_start_matches('"').strip_prefix("Language:")?;
+        let value = value.split(['\\', '"']).next()?.trim();
+        language_from_name(value)
+    })
+}
+
+/// Rileva le lingue effettivamente presenti nei file del gioco (cartelle `Content/Localization`
+/// di Unreal, tabelle di localizzazione Unity, file `.lang`/`.po`/`.mo`...), indipendentemente
+/// dalle lingue dichiarate dallo store
+#[tauri::command(rename_all = "camelCase")]
+pub async fn detect_installed_languages(
+    install_path: String,
+    engine: Option<String>,
+) -> Result<InstalledLanguagesResult, String> {
+    println!("[RUST] detect_installed_languages called for path: {}", install_path);
+
+    let game_path = PathBuf::from(&install_path);
+    if !game_path.is_dir() {
+        return Err(format!("Directory not found: {}", install_path));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let engine = engine
+            .filter(|e| !e.is_empty() && !e.eq_ignore_ascii_case("unknown"))
+            .unwrap_or_else(|| crate::engine_detector::detect_engine_from_files(&game_path).engine_name);
+        Ok(scan_installed_languages(&game_path, engine))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn scan_installed_languages(game_path: &Path, engine: String) -> InstalledLanguagesResult {
+    let engine_lower = engine.to_lowercase();
+    let known = engine_lower != "unknown";
+    let unreal = !known || engine_lower.contains("unreal");
+    let unity = !known || engine_lower.contains("unity");
+    // Godot carica i `.po` direttamente; altrove sono sorgenti da compilare in `.mo`
+    let loads_po = engine_lower.contains("godot");
+
+    let mut found: HashMap<String, InstalledLanguage> = HashMap::new();
+    walk_files(game_path, 8, 0, &mut |file| {
+        let Some(code) = installed_file_language(file, unreal, unity) else {
+            return;
+        };
+        let extension = file_extension(file);
+        let selectable = (loads_po && extension == "po") || !LANGUAGE_SOURCE_EXTENSIONS.contains(&extension.as_str());
+        let language = found.entry(code.clone()).or_insert_with(|| InstalledLanguage {
+            code,
+            selectable: false,
+            evidence: Vec::new(),
+        });
+        language.selectable |= selectable;
+        if language.evidence.len() < MAX_LANGUAGE_EVIDENCE {
+            let relative = file.strip_prefix(game_path).unwrap_or(file);
+            language.evidence.push(relative.to_string_lossy().to_string());
+        }
+    });
+
+    let mut languages: Vec<InstalledLanguage> = found.into_values().collect();
+    languages.sort_by(|a, b| a.code.cmp(&b.code));
+
+    let present: Vec<String> = languages.iter().map(|l| l.code.clone()).collect();
+    let selectable: Vec<String> = languages.iter().filter(|l| l.selectable).map(|l| l.code.clone()).collect();
+    println!("[RUST] Lingue su disco ({}): {:?}, selezionabili: {:?}", engine, present, selectable);
+
+    InstalledLanguagesResult { engine, present, selectable, languages }
+}
+
+/// Visita ricorsivamente i file saltando le cartelle di backup di GameStringer
+fn walk_files(dir: &Path, max_depth: u32, current_depth: u32, visit: &mut dyn FnMut(&Path)) {
     if current_depth > max_depth {
         return;
     }
-    
+
     if let Ok(entries) = fs::read_dir(dir) {
         for entry in entries.flatten() {
             let path = entry.path();
-            
             if path.is_dir() {
-                scan_directory_recursive(&path, extensions, max_depth, current_depth + 1, files);
-            } else if path.is_file() {
-                if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                    let ext_lower = ext.to_lowercase();
-                    if extensions.iter().any(|e| e.to_lowercase() == ext_lower) {
-                        if let Ok(metadata) = fs::metadata(&path) {
-                            files.push(ScannedFile {
-                                path: path.to_string_lossy().to_string(),
-                                name: path.file_name()
-                                    .map(|n| n.to_string_lossy().to_string())
-                                    .unwrap_or_default(),
-                                size: metadata.len(),
-                                extension: ext_lower,
-                            });
-                        }
-                    }
+                if !entry.file_name().to_string_lossy().eq_ignore_ascii_case("gamestringer_backups") {
+                    walk_files(&path, max_depth, current_depth + 1, visit);
                 }
+            } else if path.is_file() {
+                visit(&path);
             }
         }
     }
 }
 
+fn file_extension(path: &Path) -> String {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn localization_file(path: &Path, role: LocalizationRole) -> LocalizationFile {
+    LocalizationFile {
+        path: path.to_string_lossy().to_string(),
+        name: path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        size: fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        extension: file_extension(path),
+        engine_role: role,
+        language: guess_language_code(path),
+    }
+}
+
+fn unity_role(data_dir: &Path, file: &Path) -> Option<LocalizationRole> {
+    let name = file.file_name()?.to_string_lossy().to_lowercase();
+    if name == "resources.assets" {
+        return Some(LocalizationRole::UnityResourcesAssets);
+    }
+
+    let in_streaming_assets = file.strip_prefix(data_dir).ok()?
+        .components()
+        .any(|c| c.as_os_str().eq_ignore_ascii_case("StreamingAssets"));
+
+    match file_extension(file).as_str() {
+        "txt" | "json" | "csv" | "xml" if in_streaming_assets => Some(LocalizationRole::UnityStreamingAsset),
+        "txt" => Some(LocalizationRole::UnityTextAsset),
+        _ => None,
+    }
+}
+
+/// Vero se il file si trova sotto una cartella `Content/Localization`
+fn is_under_unreal_localization(file: &Path) -> bool {
+    let components: Vec<String> = file.components()
+        .map(|c| c.as_os_str().to_string_lossy().to_lowercase())
+        .collect();
+    components.windows(2).any(|pair| pair[0] == "content" && pair[1] == "localization")
+}
+
+fn is_generic_localization_file(file: &Path) -> bool {
+    const EXTENSIONS: &[&str] = &["json", "csv", "xml", "txt", "po", "lang", "loc", "strings", "ini"];
+    const HINTS: &[&str] = &["local", "lang", "i18n", "l10n", "translation", "string", "dialog", "text"];
+
+    if !EXTENSIONS.contains(&file_extension(file).as_str()) {
+        return false;
+    }
+    let lower = file.to_string_lossy().to_lowercase();
+    HINTS.iter().any(|hint| lower.contains(hint)) || guess_language_code(file).is_some()
+}
+
+/// Ricava un codice lingua dal nome del file (es. `strings_it.txt`, `pt-BR.po`, `english.json`)
+/// o, in mancanza, dalla cartella che lo contiene (es. `Localization/Game/fr/Game.locres`,
+/// `locale/de/LC_MESSAGES/game.mo` per il layout gettext)
+fn guess_language_code(path: &Path) -> Option<String> {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string());
+    let mut parent_dir = path.parent();
+    if parent_dir.and_then(|p| p.file_name()).is_some_and(|n| n.eq_ignore_ascii_case("LC_MESSAGES")) {
+        parent_dir = parent_dir.and_then(|p| p.parent());
+    }
+    let parent = parent_dir
+        .and_then(|p| p.file_name())
+        .map(|s| s.to_string_lossy().to_string());
+
+    [stem, parent].into_iter().flatten().find_map(|name| language_from_name(&name))
+}
+
+fn language_from_name(name: &str) -> Option<String> {
+    // "id" e "no" sono esclusi: troppo frequenti come parole nei nomi file
+    const CODES: &[&str] = &[
+        "en", "it", "fr", "de", "es", "pt", "ru", "ja", "zh", "ko", "pl", "nl", "sv", "fi",
+        "da", "cs", "hu", "tr", "ar", "th", "vi", "uk", "ro", "el", "he", "bg",
+    ];
+    const NAMES: &[(&str, &str)] = &[
+        ("english", "en"), ("italian", "it"), ("italiano", "it"), ("french", "fr"),
+        ("german", "de"), ("spanish", "es"), ("portuguese", "pt"), ("brazilian", "pt-br"),
+        ("russian", "ru"), ("japanese", "ja"), ("chinese", "zh"), ("schinese", "zh-hans"),
+        ("tchinese", "zh-hant"), ("korean", "ko"), ("polish", "pl"), ("dutch", "nl"),
+        ("swedish", "sv"), ("finnish", "fi"), ("danish", "da"), ("czech", "cs"),
+        ("hungarian", "hu"), ("turkish", "tr"), ("arabic", "ar"), ("thai", "th"),
+        ("vietnamese", "vi"), ("ukrainian", "uk"), ("romanian", "ro"), ("greek", "el"),
+        ("norwegian", "no"), ("indonesian", "id"),
+    ];
+
+    let tokens: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_ascii_lowercase())
+        .collect();
+
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some((_, code)) = NAMES.iter().find(|(n, _)| n == token) {
+            return Some(code.to_string());
+        }
+        if CODES.contains(&token.as_str()) {
+            // Regione o script opzionale subito dopo (pt_BR, zh-Hans)
+            return match tokens.get(i + 1) {
+                Some(next) if (next.len() == 2 && next.chars().all(|c| c.is_ascii_alphabetic()) && !CODES.contains(&next.as_str()))
+                    || next == "hans" || next == "hant" => Some(format!("{}-{}", token, next)),
+                _ => Some(token.clone()),
+            };
+        }
+    }
+    None
+}
+
 #[tauri::command(rename_all = "camelCase")]
 pub async fn read_text_file(path: String, max_bytes: Option<u64>) -> Result<String, String> {
     println!("[RUST] read_text_file called for path: {}", path);
@@ -499,18 +1040,16 @@ pub async fn read_text_file(path: String, max_bytes: Option<u64>) -> Result<Stri
     match fs::metadata(&file_path) {
         Ok(metadata) => {
             if metadata.len() > max {
-                // Read only first max_bytes
-                match fs::read(&file_path) {
-                    Ok(bytes) => {
-                        let truncated = &bytes[..std::cmp::min(bytes.len(), max as usize)];
-                        Ok(String::from_utf8_lossy(truncated).to_string())
-                    }
+                // Legge solo i primi max_bytes dal disco (niente file interi in memoria)
+                let mut bytes = Vec::with_capacity(max as usize);
+                match fs::File::open(&file_path).and_then(|f| f.take(max).read_to_end(&mut bytes)) {
+                    Ok(_) => decode_text(&bytes, None).map(|d| d.content),
                     Err(e) => Err(format!("Failed to read file: {}", e))
                 }
             } else {
-                // Usa read + from_utf8_lossy per gestire file non-UTF8 (binari, encoding diversi)
+                // Rileva l'encoding (BOM, UTF-8/UTF-16) per evitare mojibake
                 match fs::read(&file_path) {
-                    Ok(bytes) => Ok(String::from_utf8_lossy(&bytes).to_string()),
+                    Ok(bytes) => decode_text(&bytes, None).map(|d| d.content),
                     Err(e) => Err(format!("Failed to read file: {}", e))
                 }
             }
@@ -518,3 +1057,349 @@ pub async fn read_text_file(path: String, max_bytes: Option<u64>) -> Result<Stri
         Err(e) => Err(format!("Failed to get file metadata: {}", e))
     }
 }
+
+// --- Lettura limitata / a pagine ---
+
+/// Limite di default per la lettura di un file intero
+const DEFAULT_MAX_READ_BYTES: u64 = 50 * 1024 * 1024;
+/// Oltre questa dimensione il conteggio totale delle righe non viene calcolato
+const LINE_COUNT_MAX_FILE_SIZE: u64 = 512 * 1024 * 1024;
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Porzione di un file di testo (intero o per intervallo di righe)
+#[derive(Serialize, Debug, Clone)]
+pub struct TextFileChunk {
+    pub content: String,
+    pub encoding: String,
+    pub file_size: u64,
+    /// Prima riga restituita (0-based)
+    pub line_offset: usize,
+    /// Righe restituite
+    pub line_count: usize,
+    /// Righe totali del file, se calcolabili senza rileggere tutto (None per file enormi o UTF-16)
+    pub total_lines: Option<usize>,
+    pub has_more: bool,
+}
+
+/// Legge un file di testo con limite di dimensione.
+/// Senza `lineOffset`/`lineCount` restituisce tutto il file ed è un errore se supera `maxBytes`
+/// (default 50MB); con un intervallo legge a righe in streaming e il limite vale per il contenuto restituito.
+#[tauri::command(rename_all = "camelCase")]
+pub async fn read_text_file_range(
+    path: String,
+    max_bytes: Option<u64>,
+    line_offset: Option<usize>,
+    line_count: Option<usize>,
+) -> Result<TextFileChunk, String> {
+    println!("[RUST] read_text_file_range called for path: {} (offset {:?}, count {:?})", path, line_offset, line_count);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        read_text_range(Path::new(&path), max_bytes.unwrap_or(DEFAULT_MAX_READ_BYTES), line_offset, line_count)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Raccoglie le righe dell'intervallo richiesto rispettando il limite di byte
+struct LineCollector {
+    offset: usize,
+    wanted: usize,
+    max_bytes: u64,
+    current_line: usize,
+    content: String,
+    returned: usize,
+}
+
+impl LineCollector {
+    fn is_done(&self) -> bool {
+        self.returned >= self.wanted
+    }
+
+    /// La riga corrente precede l'intervallo: il suo contenuto non serve
+    fn is_skipping(&self) -> bool {
+        self.current_line < self.offset
+    }
+
+    fn push(&mut self, line: &str) -> Result<(), String> {
+        if self.current_line >= self.offset && !self.is_done() {
+            if (self.content.len() + line.len()) as u64 > self.max_bytes {
+                return Err(format!(
+                    "Le righe richieste superano il limite di {} byte: ridurre lineCount", self.max_bytes
+                ));
+            }
+            self.content.push_str(line);
+            self.returned += 1;
+        }
+        self.current_line += 1;
+        Ok(())
+    }
+}
+
+fn read_text_range(
+    path: &Path,
+    max_bytes: u64,
+    line_offset: Option<usize>,
+    line_count: Option<usize>,
+) -> Result<TextFileChunk, String> {
+    use std::io::{BufRead, BufReader};
+
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let file_size = file.metadata().map_err(|e| format!("Failed to get file metadata: {}", e))?.len();
+
+    let ranged = line_offset.is_some() || line_count.is_some();
+    if !ranged && file_size > max_bytes {
+        return Err(format!(
+            "File troppo grande ({} byte, limite {}): usare lineOffset/lineCount per leggerlo a pagine",
+            file_size, max_bytes
+        ));
+    }
+
+    let mut reader = BufReader::with_capacity(READ_CHUNK_SIZE, file);
+    let (detected, bom_len) = {
+        let sample = reader.fill_buf().map_err(|e| format!("Failed to read file: {}", e))?;
+        super::file_manager::detect_encoding(sample)
+    };
+    reader.consume(bom_len);
+    // Codepage legacy non riconoscibile: decodifica UTF-8 con sostituzioni
+    let encoding = detected.unwrap_or(encoding_rs::UTF_8);
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+
+    let mut collector = LineCollector {
+        offset: line_offset.unwrap_or(0),
+        wanted: line_count.unwrap_or(usize::MAX),
+        max_bytes: if ranged { max_bytes } else { u64::MAX },
+        current_line: 0,
+        content: String::new(),
+        returned: 0,
+    };
+
+    let mut buf = vec![0u8; READ_CHUNK_SIZE];
+    let mut pending = String::new();
+    let mut reached_eof = false;
+    // Una riga senza newline non può crescere oltre il limite: quelle da restituire sono un
+    // errore, quelle da saltare vengono scartate fino al newline (`discarding`)
+    let line_limit = collector.max_bytes;
+    let mut discarding = false;
+
+    while !collector.is_done() {
+        let read = reader.read(&mut buf).map_err(|e| format!("Failed to read file: {}", e))?;
+        let last = read == 0;
+
+        pending.reserve(decoder.max_utf8_buffer_length(read).unwrap_or(read * 3 + 16));
+        let _ = decoder.decode_to_string(&buf[..read], &mut pending, last);
+
+        let mut start = 0;
+        if discarding {
+            match pending.find('\n') {
+                Some(pos) => {
+                    collector.push("")?;
+                    start = pos + 1;
+                    discarding = false;
+                }
+                None => pending.clear(),
+            }
+        }
+        while let Some(pos) = pending[start..].find('\n') {
+            if collector.is_done() {
+                break;
+            }
+            let end = start + pos + 1;
+            collector.push(&pending[start..end])?;
+            start = end;
+        }
+        pending.drain(..start);
+
+        if !collector.is_done() && pending.len() as u64 > line_limit {
+            if !collector.is_skipping() {
+                return Err(format!(
+                    "La riga {} supera il limite di {} byte: aumentare maxBytes",
+                    collector.current_line + 1, line_limit
+                ));
+            }
+            pending.clear();
+            discarding = true;
+        }
+
+        if last {
+            // Ultima riga senza newline finale
+            if discarding {
+                collector.push("")?;
+            } else if !pending.is_empty() && !collector.is_done() {
+                collector.push(&pending)?;
+                pending.clear();
+            }
+            reached_eof = true;
+            break;
+        }
+    }
+
+    let has_remaining = !pending.is_empty()
+        || (!reached_eof && !reader.fill_buf().map(|b| b.is_empty()).unwrap_or(true));
+
+    let total_lines = if !has_remaining {
+        Some(collector.current_line)
+    } else if file_size <= LINE_COUNT_MAX_FILE_SIZE && encoding != encoding_rs::UTF_16LE && encoding != encoding_rs::UTF_16BE {
+        // Encoding compatibili ASCII: basta contare i byte '\n' rimanenti
+        let mut newlines = pending.matches('\n').count();
+        let mut last_byte = pending.as_bytes().last().copied();
+        loop {
+            let read = reader.read(&mut buf).map_err(|e| format!("Failed to read file: {}", e))?;
+            if read == 0 {
+                break;
+            }
+            newlines += buf[..read].iter().filter(|&&b| b == b'\n').count();
+            last_byte = Some(buf[read - 1]);
+        }
+        let trailing = usize::from(last_byte.is_some_and(|b| b != b'\n'));
+        Some(collector.current_line + newlines + trailing)
+    } else {
+        None
+    };
+
+    Ok(TextFileChunk {
+        encoding: detected.map_or(super::file_manager::UNKNOWN_ENCODING, |enc| enc.name()).to_string(),
+        file_size,
+        line_offset: collector.offset,
+        line_count: collector.returned,
+        total_lines,
+        has_more: has_remaining,
+        content: collector.content,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(dir: &tempfile::TempDir, name: &str, content: &str) -> std::path::PathBuf {
+        let path = dir.path().join(name);
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn engine_hint_restricts_unity_scan_to_data_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("Game_Data/StreamingAssets")).unwrap();
+        fs::write(dir.path().join("Game_Data/StreamingAssets/strings_it.json"), "{}").unwrap();
+        fs::write(dir.path().join("readme.txt"), "readme").unwrap();
+
+        let unity = collect_engine_localization_files(dir.path(), &engine_from_hint(dir.path(), "Unity"), 10);
+        assert_eq!(unity.len(), 1);
+        assert_eq!(unity[0].engine_role, LocalizationRole::UnityStreamingAsset);
+        assert_eq!(unity[0].language.as_deref(), Some("it"));
+
+        assert_eq!(engine_from_hint(dir.path(), "rpg maker"), crate::engine_detector::GameEngine::Unknown);
+    }
+
+    fn touch(dir: &tempfile::TempDir, relative: &str, content: &str) {
+        let path = dir.path().join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn installed_languages_separate_unreal_sources_from_locres() {
+        let dir = tempfile::TempDir::new().unwrap();
+        touch(&dir, "Game/Content/Localization/Game/it/Game.locres", "locres");
+        touch(&dir, "Game/Content/Localization/Game/fr/Game.archive", "archive");
+        touch(&dir, "Game/Content/Localization/Game/Game.manifest", "manifest");
+
+        let result = scan_installed_languages(dir.path(), "Unreal Engine".to_string());
+        assert_eq!(result.present, vec!["fr", "it"]);
+        assert_eq!(result.selectable, vec!["it"]);
+    }
+
+    #[test]
+    fn installed_languages_read_gettext_layout_and_skip_templates() {
+        let dir = tempfile::TempDir::new().unwrap();
+        touch(&dir, "locale/de/LC_MESSAGES/game.po", "msgid \"\"");
+        touch(&dir, "locale/de/LC_MESSAGES/game.mo", "mo");
+        touch(&dir, "locale/es/LC_MESSAGES/game.po", "msgid \"\"");
+        touch(&dir, "locale/game.pot", "msgid \"\"");
+
+        let result = scan_installed_languages(dir.path(), "Unknown".to_string());
+        assert_eq!(result.present, vec!["de", "es"]);
+        // Lo spagnolo ha solo il sorgente .po, non compilato
+        assert_eq!(result.selectable, vec!["de"]);
+        let german = result.languages.iter().find(|l| l.code == "de").unwrap();
+        assert_eq!(german.evidence.len(), 2);
+    }
+
+    #[test]
+    fn installed_languages_use_po_header_and_godot_loads_po() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let header = "msgid \"\"\nmsgstr \"\"\n\"Project-Id-Version: game\\n\"\n\"Language: pt_BR\\n\"\n";
+        touch(&dir, "translations/strings.po", header);
+
+        let generic = scan_installed_languages(dir.path(), "Unknown".to_string());
+        assert_eq!(generic.present, vec!["pt-br"]);
+        assert!(generic.selectable.is_empty());
+
+        let godot = scan_installed_languages(dir.path(), "Godot".to_string());
+        assert_eq!(godot.selectable, vec!["pt-br"]);
+    }
+
+    #[test]
+    fn read_text_range_pages_through_lines() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let content: String = (0..10).map(|i| format!("line {}\n", i)).collect();
+        let path = write_file(&dir, "lines.txt", &content);
+
+        let first = read_text_range(&path, 1024, Some(0), Some(4)).unwrap();
+        assert_eq!(first.content, "line 0\nline 1\nline 2\nline 3\n");
+        assert_eq!((first.line_offset, first.line_count), (0, 4));
+        assert!(first.has_more);
+        assert_eq!(first.total_lines, Some(10));
+
+        let last = read_text_range(&path, 1024, Some(8), Some(4)).unwrap();
+        assert_eq!(last.content, "line 8\nline 9\n");
+        assert_eq!(last.line_count, 2);
+        assert!(!last.has_more);
+        assert_eq!(last.total_lines, Some(10));
+
+        let whole = read_text_range(&path, 1024, None, None).unwrap();
+        assert_eq!(whole.content, content);
+        assert!(read_text_range(&path, 10, None, None).is_err());
+    }
+
+    #[test]
+    fn read_text_range_keeps_last_line_without_newline() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = write_file(&dir, "no_newline.txt", "alpha\nbeta\ngamma");
+
+        let chunk = read_text_range(&path, 1024, Some(1), Some(5)).unwrap();
+        assert_eq!(chunk.content, "beta\ngamma");
+        assert_eq!(chunk.line_count, 2);
+        assert_eq!(chunk.total_lines, Some(3));
+        assert!(!chunk.has_more);
+
+        let path = write_file(&dir, "single.txt", "only line");
+        let single = read_text_range(&path, 1024, Some(0), Some(1)).unwrap();
+        assert_eq!(single.content, "only line");
+        assert_eq!(single.total_lines, Some(1));
+    }
+
+    #[test]
+    fn read_text_range_bounds_long_lines() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let long = "x".repeat(3 * READ_CHUNK_SIZE);
+        let path = write_file(&dir, "long.txt", &format!("{}\nshort\n", long));
+
+        // La riga lunga precede l'intervallo: viene saltata senza essere accumulata
+        let chunk = read_text_range(&path, 1024, Some(1), Some(1)).unwrap();
+        assert_eq!(chunk.content, "short\n");
+        assert_eq!(chunk.total_lines, Some(2));
+
+        // Richiesta: errore invece di caricarla tutta
+        assert!(read_text_range(&path, 1024, Some(0), Some(1)).is_err());
+
+        // Riga lunga senza newline a fine file: saltata se precede l'intervallo, altrimenti errore
+        let path = write_file(&dir, "tail.txt", &format!("head\n{}", long));
+        let chunk = read_text_range(&path, 1024, Some(2), Some(5)).unwrap();
+        assert_eq!(chunk.line_count, 0);
+        assert_eq!(chunk.total_lines, Some(2));
+        let err = read_text_range(&path, 1024, Some(0), Some(5)).unwrap_err();
+        assert!(err.contains("riga 2"));
+    }
+}
@@ -2,7 +2,10 @@
 use crate::commands::profiles::ProfileManagerState;
 use crate::commands::profile_settings::ProfileSettingsManagerState;
 use crate::profiles::manager::{LegacyMigrationResult, LegacyCredentialInfo, LegacySettingsInfo, LegacySettingsMigrationResult};
+use crate::commands::{battlenet, epic, gog, itchio, origin, rockstar, steam, ubisoft};
+use crate::profiles::credential_manager::{PlainCredential, StoreType};
 use serde::Serialize;
+use std::path::PathBuf;
 use tauri::{command, State};
 
 /// Risposta generica per operazioni migrazione
@@ -464,4 +467,200 @@ impl MigrationWizardResult {
             completed_at: chrono::Utc::now(),
         }
     }
-}
\ No newline at end of file
+}
+/// Esito migrazione di un singolo store
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StoreMigrationStatus {
+    /// Credenziali importate nel profilo, file originale rimosso
+    Migrated,
+    /// Nessun file legacy per questo store
+    NotFound,
+    /// Lettura, decrittografia o import falliti: file originale lasciato intatto
+    Failed,
+}
+
+/// Report migrazione per store
+#[derive(Debug, Clone, Serialize)]
+pub struct StoreCredentialMigration {
+    pub store: String,
+    pub status: StoreMigrationStatus,
+    pub source_path: Option<String>,
+    pub backup_path: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Report complessivo della migrazione credenziali legacy di tutti gli store
+#[derive(Debug, Clone, Serialize)]
+pub struct LegacyStoreMigrationReport {
+    pub stores: Vec<StoreCredentialMigration>,
+    pub total_migrated: u32,
+    pub total_failed: u32,
+    pub migrated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Store con file credenziali standalone (formato pre-profili)
+const LEGACY_CREDENTIAL_STORES: [StoreType; 8] = [
+    StoreType::Steam,
+    StoreType::Epic,
+    StoreType::Gog,
+    StoreType::Origin,
+    StoreType::Ubisoft,
+    StoreType::Battlenet,
+    StoreType::Itchio,
+    StoreType::Rockstar,
+];
+
+/// Percorso del file credenziali legacy di uno store
+fn legacy_store_credentials_path(store: &StoreType) -> Result<PathBuf, String> {
+    match store {
+        StoreType::Steam => steam::get_steam_credentials_path(),
+        StoreType::Epic => epic::get_epic_credentials_path(),
+        StoreType::Gog => gog::get_gog_credentials_path(),
+        StoreType::Origin => origin::get_origin_credentials_path(),
+        StoreType::Ubisoft => ubisoft::get_ubisoft_credentials_path(),
+        StoreType::Battlenet => battlenet::get_battlenet_credentials_path(),
+        StoreType::Itchio => itchio::get_itchio_credentials_path(),
+        StoreType::Rockstar => rockstar::get_rockstar_credentials_path(),
+    }
+}
+
+fn json_field(value: &serde_json::Value, key: &str) -> String {
+    value[key].as_str().unwrap_or_default().to_string()
+}
+
+/// Decripta il file legacy con lo schema del rispettivo store
+async fn decrypt_legacy_store_credential(store: &StoreType) -> Result<PlainCredential, String> {
+    let credential = match store {
+        StoreType::Steam => {
+            let (api_key, steam_id) = steam::load_credentials_securely()?;
+            PlainCredential::new(StoreType::Steam, steam_id.clone(), api_key.expose().to_string())
+                .with_data("steam_id".to_string(), steam_id)
+        }
+        StoreType::Epic => {
+            let (username, password) = epic::get_decrypted_epic_credentials().await?;
+            PlainCredential::new(StoreType::Epic, username, password.expose().to_string())
+        }
+        StoreType::Itchio => {
+            let data = itchio::load_itchio_credentials().await?;
+            PlainCredential::new(StoreType::Itchio, json_field(&data, "username"), json_field(&data, "api_key"))
+        }
+        _ => {
+            let data = match store {
+                StoreType::Gog => gog::load_gog_credentials().await?,
+                StoreType::Origin => origin::load_origin_credentials().await?,
+                StoreType::Ubisoft => ubisoft::load_ubisoft_credentials().await?,
+                StoreType::Battlenet => battlenet::load_battlenet_credentials().await?,
+                _ => rockstar::load_rockstar_credentials().await?,
+            };
+            let mut credential = PlainCredential::new(store.clone(), json_field(&data, "email"), json_field(&data, "password"));
+            let username = json_field(&data, "username");
+            if !username.is_empty() {
+                credential = credential.with_data("username".to_string(), username);
+            }
+            credential
+        }
+    };
+
+    if credential.password.is_empty() {
+        return Err("Credenziale legacy vuota".to_string());
+    }
+
+    Ok(credential)
+}
+
+/// Copia il file legacy in `backups/legacy_credentials` accanto all'originale
+async fn backup_legacy_store_file(store: &StoreType, path: &std::path::Path) -> Result<PathBuf, String> {
+    let backup_dir = path.parent()
+        .ok_or_else(|| "Percorso credenziali senza directory padre".to_string())?
+        .join("backups")
+        .join("legacy_credentials");
+    tokio::fs::create_dir_all(&backup_dir).await
+        .map_err(|e| format!("Errore creazione directory backup: {}", e))?;
+
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+    let backup_path = backup_dir.join(format!("{}_credentials_backup_{}.json", store.as_str(), timestamp));
+    tokio::fs::copy(path, &backup_path).await
+        .map_err(|e| format!("Errore copia file backup: {}", e))?;
+
+    Ok(backup_path)
+}
+
+/// Migra le credenziali legacy di tutti gli store nel profilo attivo.
+/// Per ogni store: decritta con lo schema legacy, importa nel profilo, crea un backup
+/// e solo allora elimina il file originale.
+#[command]
+pub async fn migrate_all_legacy_store_credentials(
+    profile_state: State<'_, ProfileManagerState>,
+) -> Result<MigrationResponse<LegacyStoreMigrationReport>, String> {
+    let mut manager = profile_state.manager.lock().await;
+
+    if !manager.is_profile_active() {
+        return Ok(MigrationResponse::error("Nessun profilo attivo per la migrazione".to_string()));
+    }
+
+    let mut report = LegacyStoreMigrationReport {
+        stores: Vec::new(),
+        total_migrated: 0,
+        total_failed: 0,
+        migrated_at: chrono::Utc::now(),
+    };
+
+    for store in &LEGACY_CREDENTIAL_STORES {
+        let mut entry = StoreCredentialMigration {
+            store: store.as_str().to_string(),
+            status: StoreMigrationStatus::NotFound,
+            source_path: None,
+            backup_path: None,
+            error: None,
+        };
+
+        let path = match legacy_store_credentials_path(store) {
+            Ok(path) if path.exists() => path,
+            Ok(_) => {
+                report.stores.push(entry);
+                continue;
+            }
+            Err(e) => {
+                entry.status = StoreMigrationStatus::Failed;
+                entry.error = Some(e);
+                report.total_failed += 1;
+                report.stores.push(entry);
+                continue;
+            }
+        };
+        entry.source_path = Some(path.to_string_lossy().to_string());
+
+        let outcome = async {
+            let credential = decrypt_legacy_store_credential(store).await?;
+            manager.save_credential_for_active_profile(credential).await
+                .map_err(|e| format!("Errore import nel profilo: {}", e))?;
+            let backup_path = backup_legacy_store_file(store, &path).await?;
+            tokio::fs::remove_file(&path).await
+                .map_err(|e| format!("Credenziali importate ma file originale non eliminato: {}", e))?;
+            Ok::<PathBuf, String>(backup_path)
+        }.await;
+
+        match outcome {
+            Ok(backup_path) => {
+                println!("[MIGRATION] ✅ Credenziali {} migrate nel profilo", store.as_str());
+                entry.status = StoreMigrationStatus::Migrated;
+                entry.backup_path = Some(backup_path.to_string_lossy().to_string());
+                report.total_migrated += 1;
+            }
+            Err(e) => {
+                println!("[MIGRATION] ❌ Migrazione credenziali {} fallita: {}", store.as_str(), e);
+                entry.status = StoreMigrationStatus::Failed;
+                entry.error = Some(e);
+                report.total_failed += 1;
+            }
+        }
+
+        report.stores.push(entry);
+    }
+
+    println!("[MIGRATION] 🔄 Migrazione credenziali store: {} migrate, {} fallite",
+             report.total_migrated, report.total_failed);
+
+    Ok(MigrationResponse::success(report))
+}
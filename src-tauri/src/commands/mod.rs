@@ -18,19 +18,24 @@ pub mod mod_profiles;
 pub mod load_order;
 pub mod games;
 pub mod utilities;
+pub mod cache_registry;
+pub mod preferences;
 pub mod patches;
 pub mod injekt;
 pub mod anti_cheat;
 pub mod dlc_manager;
-// pub mod hltb_manager; // Rimosso per eliminare dipendenza xml5ever
+pub mod hltb_manager; // Solo JSON (serde_json), niente xml5ever
+pub mod steamgriddb;
 pub mod launcher;
 pub mod profile_credentials;
 pub mod profiles;
 pub mod profile_settings;
+pub mod profile_backups;
 pub mod migration;
 pub mod validation;
 pub mod glossary;
 pub mod translation_memory;
+pub mod translation;
 pub mod qa_check;
 pub mod export_formats;
 pub mod smart_glossary;
@@ -49,9 +54,10 @@ pub mod image_cache;
 pub mod backup;
 pub mod global_hotkeys;
 pub mod store_detection;
+pub mod diagnostics;
 pub mod unity_bundle;
 pub mod universal_injector;
 pub mod screen_capture;
 pub mod batch_processor;
 pub mod community_hub;
-// pub mod notifications; // TEMPORANEAMENTE DISABILITATO
+pub mod notifications;
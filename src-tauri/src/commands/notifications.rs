@@ -0,0 +1,113 @@
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+use crate::notifications::{
+    errors::NotificationResult,
+    manager::NotificationManager,
+    models::{CreateNotificationRequest, Notification, NotificationFilter},
+    profile_integration::ProfileNotificationIntegration,
+};
+
+/// Stato condiviso per il NotificationManager
+pub struct NotificationManagerState {
+    pub manager: Arc<Mutex<NotificationManager>>,
+    pub profile_integration: Arc<Mutex<ProfileNotificationIntegration>>,
+}
+
+impl NotificationManagerState {
+    pub fn new(manager: NotificationManager) -> Self {
+        let manager = Arc::new(Mutex::new(manager));
+        let profile_integration = Arc::new(Mutex::new(ProfileNotificationIntegration::new(Arc::clone(&manager))));
+        Self { manager, profile_integration }
+    }
+
+    /// Notifica un errore di autenticazione; gli errori vengono solo loggati
+    pub async fn notify_authentication_error(&self, profile_id: &str, error_message: &str) {
+        let integration = self.profile_integration.lock().await;
+        if let Err(e) = integration.create_authentication_error_notification(profile_id, error_message).await {
+            eprintln!("[NOTIFICATIONS] ⚠️ Impossibile creare notifica di autenticazione: {}", e);
+        }
+    }
+
+    /// Notifica il blocco temporaneo del profilo; gli errori vengono solo loggati
+    pub async fn notify_profile_locked(&self, profile_id: &str, remaining_seconds: u64) {
+        let integration = self.profile_integration.lock().await;
+        if let Err(e) = integration.create_profile_locked_notification(profile_id, remaining_seconds).await {
+            eprintln!("[NOTIFICATIONS] ⚠️ Impossibile creare notifica di blocco profilo: {}", e);
+        }
+    }
+
+    /// Notifica l'esito di un backup; gli errori vengono solo loggati
+    pub async fn notify_backup(&self, profile_id: &str, backup_path: &str, success: bool) {
+        let integration = self.profile_integration.lock().await;
+        if let Err(e) = integration.create_backup_notification(profile_id, backup_path, success).await {
+            eprintln!("[NOTIFICATIONS] ⚠️ Impossibile creare notifica di backup: {}", e);
+        }
+    }
+}
+
+/// Risposta generica per comandi notifiche (stessa forma attesa da `hooks/use-notifications.ts`)
+#[derive(Debug, Serialize)]
+pub struct NotificationResponse<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    pub error: Option<String>,
+}
+
+impl<T> From<NotificationResult<T>> for NotificationResponse<T> {
+    fn from(result: NotificationResult<T>) -> Self {
+        match result {
+            Ok(data) => Self { success: true, data: Some(data), error: None },
+            Err(e) => Self { success: false, data: None, error: Some(e.to_string()) },
+        }
+    }
+}
+
+/// Crea una nuova notifica
+#[tauri::command(rename_all = "snake_case")]
+pub async fn create_notification(
+    request: CreateNotificationRequest,
+    state: State<'_, NotificationManagerState>,
+) -> Result<NotificationResponse<Notification>, String> {
+    Ok(state.manager.lock().await.create_notification(request).await.into())
+}
+
+/// Ottiene le notifiche per un profilo
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_notifications(
+    profile_id: String,
+    filter: Option<NotificationFilter>,
+    state: State<'_, NotificationManagerState>,
+) -> Result<NotificationResponse<Vec<Notification>>, String> {
+    Ok(state.manager.lock().await.get_notifications(&profile_id, filter.unwrap_or_default()).await.into())
+}
+
+/// Marca una notifica come letta
+#[tauri::command(rename_all = "snake_case")]
+pub async fn mark_notification_as_read(
+    notification_id: String,
+    profile_id: String,
+    state: State<'_, NotificationManagerState>,
+) -> Result<NotificationResponse<()>, String> {
+    Ok(state.manager.lock().await.mark_as_read(&notification_id, &profile_id).await.into())
+}
+
+/// Elimina una notifica
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_notification(
+    notification_id: String,
+    profile_id: String,
+    state: State<'_, NotificationManagerState>,
+) -> Result<NotificationResponse<()>, String> {
+    Ok(state.manager.lock().await.delete_notification(&notification_id, &profile_id).await.into())
+}
+
+/// Ottiene il conteggio delle notifiche non lette
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_unread_notifications_count(
+    profile_id: String,
+    state: State<'_, NotificationManagerState>,
+) -> Result<NotificationResponse<u32>, String> {
+    Ok(state.manager.lock().await.get_unread_count(&profile_id).await.into())
+}
@@ -5,6 +5,7 @@ use std::fs;
 use winreg::enums::*;
 use winreg::RegKey;
 use crate::commands::library::InstalledGame;
+use crate::commands::store_detection::{ConnectionCheck, StoreConnectionState};
 use std::path::PathBuf;
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
 use aes_gcm::aead::Aead;
@@ -198,9 +199,8 @@ async fn scan_origin_folders() -> Result<Vec<InstalledGame>, String> {
     Ok(games)
 }
 
-/// Test della connessione Origin/EA App
-#[tauri::command]
-pub async fn test_origin_connection() -> Result<String, String> {
+/// Test della connessione Origin/EA App, con esito strutturato per la dashboard degli store
+pub(crate) async fn check_origin_connection() -> Result<ConnectionCheck, String> {
     println!("[ORIGIN] Test connessione");
     
     // Prima prova a caricare credenziali salvate
@@ -216,31 +216,37 @@ pub async fn test_origin_connection() -> Result<String, String> {
                 match test_origin_auth(email, password).await {
                     Ok(user) => {
                         let games = get_origin_installed_games().await?;
-                        Ok(format!("✅ Connesso come '{}' - {} giochi locali trovati", 
-                                  user.username, games.len()))
+                        Ok(ConnectionCheck::new(StoreConnectionState::Connected, format!("✅ Connesso come '{}' - {} giochi locali trovati", 
+                                  user.username, games.len())).with_local_games(games.len()))
                     }
                     Err(e) => {
                         println!("[ORIGIN] Errore autenticazione salvata: {}", e);
                         // Fallback a scansione locale
                         let games = get_origin_installed_games().await?;
-                        Ok(format!("❌ Account disconnesso (credenziali non valide) - {} giochi locali trovati", games.len()))
+                        Ok(ConnectionCheck::new(StoreConnectionState::InvalidCredentials, format!("❌ Account disconnesso (credenziali non valide) - {} giochi locali trovati", games.len())).with_local_games(games.len()))
                     }
                 }
             } else {
                 // Fallback a scansione locale
                 let games = get_origin_installed_games().await?;
-                Ok(format!("Scansione Origin/EA completata: {} giochi trovati", games.len()))
+                Ok(ConnectionCheck::new(StoreConnectionState::LocalOnly, format!("Scansione Origin/EA completata: {} giochi trovati", games.len())).with_local_games(games.len()))
             }
         }
         Err(_) => {
             // Nessuna credenziale salvata - scansione locale
             println!("[ORIGIN] Nessuna credenziale salvata");
             let games = get_origin_installed_games().await?;
-            Ok(format!("Scansione Origin/EA completata: {} giochi trovati", games.len()))
+            Ok(ConnectionCheck::new(StoreConnectionState::LocalOnly, format!("Scansione Origin/EA completata: {} giochi trovati", games.len())).with_local_games(games.len()))
         }
     }
 }
 
+/// Test della connessione Origin/EA App
+#[tauri::command]
+pub async fn test_origin_connection() -> Result<String, String> {
+    check_origin_connection().await.map(|check| check.message)
+}
+
 /// Recupera informazioni su un gioco Origin/EA App specifico
 #[tauri::command]
 pub async fn get_origin_game_info(game_id: String) -> Result<OriginGame, String> {
@@ -537,7 +543,7 @@ async fn find_main_executable(game_path: &Path) -> Option<String> {
 
 // Funzioni per gestione credenziali Origin/EA
 
-fn get_origin_credentials_path() -> Result<PathBuf, String> {
+pub(crate) fn get_origin_credentials_path() -> Result<PathBuf, String> {
     let mut path = std::env::current_dir()
         .map_err(|e| format!("Errore getting current dir: {}", e))?;
     path.push(".cache");
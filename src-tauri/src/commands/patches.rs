@@ -1,24 +1,32 @@
 use serde_json;
 use std::fs;
-use std::path::Path;
-use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::collections::{BTreeSet, HashMap};
+use serde::{Deserialize, Serialize};
+use once_cell::sync::Lazy;
+
+use super::glossary::search_glossary_scored;
+use super::translation_memory::tm_fuzzy_lookup;
+use crate::string_id::{lookup_text, string_id};
+use super::qa_check::{extract_placeholders, malformed_placeholders, PlaceholderFormats, PlaceholderStyle};
 
 #[tauri::command]
 pub async fn get_patches(patch_id: Option<String>) -> Result<serde_json::Value, String> {
     log::info!("📦 Recupero patch{}", 
         if let Some(ref id) = patch_id { format!(" con ID: {}", id) } else { "".to_string() });
     
-    // TODO: Implementare patch manager con database/file system
-    if let Some(_id) = patch_id {
-        // Recupera patch specifica
-        log::warn!("⚠️ Recupero patch specifica non ancora implementato");
-        Err("Patch non trovata".to_string())
-    } else {
-        // Recupera tutte le patch
-        let patches = serde_json::json!([]);
-        log::info!("✅ Recuperate {} patch", 0);
-        Ok(patches)
+    if let Some(id) = patch_id {
+        return load_saved_patch(&id);
     }
+    
+    // Patch salvate dal backend, le più recenti prima
+    let mut patches = load_saved_patches();
+    patches.sort_by(|a, b| {
+        let updated = |p: &serde_json::Value| p.get("updatedAt").and_then(|v| v.as_str()).unwrap_or("").to_string();
+        updated(b).cmp(&updated(a))
+    });
+    log::info!("✅ Recuperate {} patch", patches.len());
+    Ok(serde_json::Value::Array(patches))
 }
 
 #[tauri::command]
@@ -76,6 +84,675 @@ pub async fn export_patch(patch_id: String, format: String) -> Result<serde_json
     Ok(export_result)
 }
 
+/// Avanzamento della traduzione di un gioco
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationProgress {
+    pub game_id: String,
+    pub total: usize,
+    /// Voci con testo tradotto non vuoto
+    pub translated: usize,
+    /// Voci segnate come revisionate/verificate
+    pub reviewed: usize,
+    pub percent_translated: f64,
+    pub percent_reviewed: f64,
+    /// Fonte principale dei conteggi: "project", "patch", "translation_memory" o "none"
+    pub source: String,
+    pub patch_ids: Vec<String>,
+}
+
+/// Avanzamento complessivo su tutti i giochi
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationProgressSummary {
+    pub games: Vec<TranslationProgress>,
+    pub total: usize,
+    pub translated: usize,
+    pub reviewed: usize,
+    pub percent_translated: f64,
+    pub percent_reviewed: f64,
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (part as f64 * 1000.0 / total as f64).round() / 10.0
+}
+
+/// Patch salvate dal backend (file JSON nella directory patch)
+fn load_saved_patches() -> Vec<serde_json::Value> {
+    let Ok(dir) = get_patches_dir() else { return Vec::new() };
+    let Ok(entries) = fs::read_dir(&dir) else { return Vec::new() };
+    entries.flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|e| fs::read_to_string(e.path()).ok())
+        .filter_map(|content| serde_json::from_str(&content).ok())
+        .collect()
+}
+
+/// Unità TM associate a un gioco (tutte le coppie di lingue)
+fn load_game_tm_units() -> Vec<super::translation_memory::TranslationUnit> {
+    let memories = super::translation_memory::list_translation_memories().unwrap_or_default();
+    memories.into_iter()
+        .filter_map(|info| super::translation_memory::load_translation_memory(info.source_language, info.target_language).ok().flatten())
+        .flat_map(|memory| memory.units)
+        .filter(|unit| unit.game_id.is_some())
+        .collect()
+}
+
+/// Progetto di traduzione aperto nel frontend (`gamestringer_partial_translations`):
+/// `total` sono le stringhe estratte dai file, `items` quelle già lavorate
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectProgressInput {
+    pub game_id: Option<String>,
+    pub total: usize,
+    pub items: Vec<ProjectProgressItem>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectProgressItem {
+    pub source_text: String,
+    pub translated_text: Option<String>,
+    /// Modifica manuale nell'editor: conta come revisione
+    pub is_manual_edit: bool,
+}
+
+fn mark_progress_entry<'a>(entries: &mut HashMap<&'a str, (bool, bool)>, original: &'a str, translated: bool, reviewed: bool) {
+    let original = original.trim();
+    if !original.is_empty() {
+        let state = entries.entry(original).or_insert((false, false));
+        state.0 |= translated;
+        state.1 |= reviewed;
+    }
+}
+
+/// Conteggi di un gioco unendo progetto, patch salvate e TM: le voci con lo stesso testo
+/// originale contano una volta, tradotta/revisionata se lo è in almeno una fonte
+fn game_progress(
+    game_id: &str,
+    project: Option<&ProjectProgressInput>,
+    patches: &[serde_json::Value],
+    units: &[super::translation_memory::TranslationUnit],
+) -> TranslationProgress {
+    // originale -> (tradotta, revisionata)
+    let mut entries: HashMap<&str, (bool, bool)> = HashMap::new();
+    let mut sources = Vec::new();
+
+    let project = project.filter(|p| p.game_id.as_deref() == Some(game_id));
+    let mut project_total = 0;
+    if let Some(project) = project {
+        sources.push("project");
+        project_total = project.total;
+        for item in &project.items {
+            let translated = item.translated_text.as_deref().is_some_and(|t| !t.trim().is_empty());
+            mark_progress_entry(&mut entries, &item.source_text, translated, item.is_manual_edit);
+        }
+    }
+
+    let game_patches: Vec<&serde_json::Value> = patches.iter()
+        .filter(|p| p.get("gameId").and_then(|v| v.as_str()) == Some(game_id))
+        .collect();
+    if !game_patches.is_empty() {
+        sources.push("patch");
+    }
+    for patch in &game_patches {
+        for entry in patch.get("translations").and_then(|t| t.as_array()).into_iter().flatten() {
+            let Some(original) = entry.get("originalText").and_then(|v| v.as_str()) else { continue };
+            let translated = entry.get("translatedText").and_then(|v| v.as_str()).is_some_and(|t| !t.trim().is_empty());
+            let reviewed = entry.get("reviewed").and_then(|v| v.as_bool()).unwrap_or(false);
+            mark_progress_entry(&mut entries, original, translated, reviewed);
+        }
+    }
+
+    let game_units: Vec<_> = units.iter().filter(|u| u.game_id.as_deref() == Some(game_id)).collect();
+    if !game_units.is_empty() {
+        sources.push("translation_memory");
+    }
+    for unit in game_units {
+        mark_progress_entry(&mut entries, &unit.source_text, !unit.target_text.trim().is_empty(), unit.verified);
+    }
+
+    // Le stringhe del progetto non ancora lavorate non compaiono tra le voci
+    let total = entries.len().max(project_total);
+    let translated = entries.values().filter(|(t, _)| *t).count();
+    let reviewed = entries.values().filter(|(_, r)| *r).count();
+    TranslationProgress {
+        game_id: game_id.to_string(),
+        total,
+        translated,
+        reviewed,
+        percent_translated: percent(translated, total),
+        percent_reviewed: percent(reviewed, total),
+        source: sources.first().copied().unwrap_or("none").to_string(),
+        patch_ids: game_patches.iter()
+            .filter_map(|p| p.get("id").and_then(|v| v.as_str()).map(str::to_string))
+            .collect(),
+    }
+}
+
+/// Avanzamento della traduzione di un gioco: progetto aperto nel frontend (se passato),
+/// patch salvate e Translation Memory
+#[tauri::command]
+pub async fn get_translation_progress(
+    game_id: String,
+    project: Option<ProjectProgressInput>,
+) -> Result<TranslationProgress, String> {
+    let progress = game_progress(&game_id, project.as_ref(), &load_saved_patches(), &load_game_tm_units());
+    log::info!("📊 Avanzamento {}: {}/{} tradotte ({}%, fonte: {})",
+        game_id, progress.translated, progress.total, progress.percent_translated, progress.source);
+    Ok(progress)
+}
+
+/// Avanzamento di tutti i giochi con progetto, patch o voci TM, più il totale aggregato
+#[tauri::command]
+pub async fn get_all_translation_progress(
+    projects: Option<Vec<ProjectProgressInput>>,
+) -> Result<TranslationProgressSummary, String> {
+    let projects = projects.unwrap_or_default();
+    let patches = load_saved_patches();
+    let units = load_game_tm_units();
+    Ok(progress_summary(&projects, &patches, &units))
+}
+
+fn progress_summary(
+    projects: &[ProjectProgressInput],
+    patches: &[serde_json::Value],
+    units: &[super::translation_memory::TranslationUnit],
+) -> TranslationProgressSummary {
+    let game_ids: BTreeSet<String> = projects.iter()
+        .filter_map(|p| p.game_id.clone())
+        .chain(patches.iter().filter_map(|p| p.get("gameId").and_then(|v| v.as_str()).map(str::to_string)))
+        .chain(units.iter().filter_map(|u| u.game_id.clone()))
+        .collect();
+
+    let games: Vec<TranslationProgress> = game_ids.iter()
+        .map(|id| {
+            let project = projects.iter().find(|p| p.game_id.as_deref() == Some(id.as_str()));
+            game_progress(id, project, patches, units)
+        })
+        .collect();
+
+    let total = games.iter().map(|g| g.total).sum();
+    let translated = games.iter().map(|g| g.translated).sum();
+    let reviewed = games.iter().map(|g| g.reviewed).sum();
+    TranslationProgressSummary {
+        games,
+        total,
+        translated,
+        reviewed,
+        percent_translated: percent(translated, total),
+        percent_reviewed: percent(reviewed, total),
+    }
+}
+
+/// Termine del glossario non rispettato in una voce della patch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintFinding {
+    /// Id della voce nella patch (o testo originale se manca)
+    pub key: String,
+    pub source: String,
+    pub target: String,
+    /// Termine trovato nel testo originale
+    pub term: String,
+    /// Traduzione imposta dal glossario
+    pub expected: String,
+    /// "missing_term" o "do_not_translate"
+    pub rule: String,
+    /// Traduzione corretta proposta, se ricavabile automaticamente
+    pub suggested_fix: Option<String>,
+    pub message: String,
+}
+
+/// Carica una patch salvata dal backend per id
+fn load_saved_patch(patch_id: &str) -> Result<serde_json::Value, String> {
+    if patch_id.is_empty() || patch_id.contains(['/', '\\']) || patch_id.contains("..") {
+        return Err(format!("ID patch non valido: {}", patch_id));
+    }
+    let path = get_patches_dir()?.join(format!("{}.json", patch_id));
+    let content = fs::read_to_string(&path).map_err(|_| format!("Patch non trovata: {}", patch_id))?;
+    serde_json::from_str(&content).map_err(|e| format!("Errore parsing patch {}: {}", patch_id, e))
+}
+
+/// Chiave, originale e traduzione di una voce di patch (None se non tradotta)
+fn entry_texts(key: String, entry: &serde_json::Value) -> Option<(String, &str, &str)> {
+    let source = entry.get("originalText").or_else(|| entry.get("original")).and_then(|v| v.as_str())?;
+    let target = entry.get("translatedText").or_else(|| entry.get("translated")).and_then(|v| v.as_str())?;
+    if source.is_empty() || target.trim().is_empty() {
+        return None;
+    }
+    let key = entry.get("id").and_then(|v| v.as_str()).map(str::to_string).unwrap_or(key);
+    Some((key, source, target))
+}
+
+/// Regex per un termine: confini di parola solo dove il termine inizia/finisce con un carattere di parola
+fn term_regex(term: &str, case_insensitive: bool, whole_word: bool) -> Option<regex::Regex> {
+    let term = term.trim();
+    if term.is_empty() {
+        return None;
+    }
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let (start, end) = if whole_word {
+        (if is_word(term.chars().next()) { r"\b" } else { "" }, if is_word(term.chars().last()) { r"\b" } else { "" })
+    } else {
+        ("", "")
+    };
+    regex::RegexBuilder::new(&format!("{}{}{}", start, regex::escape(term), end))
+        .case_insensitive(case_insensitive)
+        .build()
+        .ok()
+}
+
+/// Confronta le voci della patch con i termini del glossario (vedi `lint_patch_against_glossary`)
+fn lint_patch_entries(
+    patch: &serde_json::Value,
+    glossary: &super::glossary::GameGlossary,
+    case_insensitive: Option<bool>,
+    whole_word: bool,
+) -> Vec<LintFinding> {
+    // (termine, traduzioni accettate, regex sorgente, regex per ciascuna traduzione accettata, regola)
+    let mut rules = Vec::new();
+    for entry in &glossary.entries {
+        let insensitive = case_insensitive.unwrap_or(false) || !entry.case_sensitive;
+        let Some(source_re) = term_regex(&entry.original, insensitive, whole_word) else { continue };
+        let accepted: Vec<String> = std::iter::once(entry.translation.clone())
+            .chain(entry.variants.iter().cloned())
+            .filter(|t| !t.trim().is_empty())
+            .collect();
+        let accepted_re: Vec<regex::Regex> = accepted.iter()
+            .filter_map(|t| term_regex(t, insensitive, whole_word))
+            .collect();
+        if accepted_re.is_empty() {
+            continue;
+        }
+        rules.push((entry.original.clone(), accepted, source_re, accepted_re, "missing_term"));
+    }
+    for term in &glossary.metadata.do_not_translate {
+        let insensitive = case_insensitive.unwrap_or(false);
+        if let Some(term_re) = term_regex(term, insensitive, whole_word) {
+            rules.push((term.clone(), vec![term.clone()], term_re.clone(), vec![term_re], "do_not_translate"));
+        }
+    }
+
+    let mut findings = Vec::new();
+    for (key, entry) in patch_entries(&patch) {
+        let Some((key, source, target)) = entry_texts(key, &entry) else { continue };
+
+        for (term, accepted, source_re, accepted_re, rule) in &rules {
+            if !source_re.is_match(source) || accepted_re.iter().any(|re| re.is_match(target)) {
+                continue;
+            }
+            let expected = accepted[0].clone();
+            // Termine lasciato in originale nella traduzione: sostituzione diretta
+            let suggested_fix = (*rule == "missing_term" && source_re.is_match(target))
+                .then(|| source_re.replace_all(target, regex::NoExpand(&expected)).to_string());
+            let message = if *rule == "do_not_translate" {
+                format!("«{}» non va tradotto ma manca nella traduzione", term)
+            } else {
+                format!("«{}» va tradotto come «{}»", term, expected)
+            };
+            findings.push(LintFinding {
+                key: key.clone(),
+                source: source.to_string(),
+                target: target.to_string(),
+                term: term.clone(),
+                expected,
+                rule: rule.to_string(),
+                suggested_fix,
+                message,
+            });
+        }
+    }
+    findings.sort_by(|a, b| a.key.cmp(&b.key).then_with(|| a.term.cmp(&b.term)));
+    findings
+}
+
+
+/// Controlla che ogni voce tradotta usi i termini imposti dal glossario del gioco.
+/// `case_insensitive` forza il confronto senza maiuscole anche per i termini case-sensitive;
+/// `whole_word` (default true) evita match dentro altre parole ("Ice" in "Price").
+#[tauri::command]
+pub async fn lint_patch_against_glossary(
+    patch_id: String,
+    glossary_id: String,
+    case_insensitive: Option<bool>,
+    whole_word: Option<bool>,
+) -> Result<Vec<LintFinding>, String> {
+    let patch = load_saved_patch(&patch_id)?;
+    // Glossari salvati per gioco: accetta sia il game_id sia l'id del glossario
+    let glossary = match super::glossary::load_glossary_sync(&glossary_id)? {
+        Some(glossary) => glossary,
+        None => super::glossary::list_glossaries().await?
+            .into_iter()
+            .find(|g| g.id == glossary_id)
+            .ok_or_else(|| format!("Glossario non trovato: {}", glossary_id))?,
+    };
+    let findings = lint_patch_entries(&patch, &glossary, case_insensitive, whole_word.unwrap_or(true));
+
+    log::info!("🔎 Lint patch {} con glossario {}: {} problemi", patch_id, glossary_id, findings.len());
+    Ok(findings)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaceholderIssueKind {
+    /// Presente nell'originale, assente (o meno volte) nella traduzione
+    Missing,
+    /// Presente nella traduzione ma non nell'originale
+    Extra,
+    /// Placeholder rovinato nella traduzione ("{ 0 }", "{0", "%1$")
+    Malformed,
+}
+
+/// Placeholder non coerente tra originale e traduzione
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaceholderIssue {
+    pub key: String,
+    pub kind: PlaceholderIssueKind,
+    pub style: PlaceholderStyle,
+    pub placeholder: String,
+    pub source: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// Conteggio dei placeholder per confronto indipendente dall'ordine
+fn placeholder_counts(placeholders: Vec<(PlaceholderStyle, String)>) -> HashMap<String, (PlaceholderStyle, usize)> {
+    let mut counts = HashMap::new();
+    for (style, ph) in placeholders {
+        counts.entry(ph).or_insert((style, 0)).1 += 1;
+    }
+    counts
+}
+
+/// Verifica che ogni traduzione della patch contenga gli stessi placeholder dell'originale.
+/// Un placeholder perso o alterato fa crashare il gioco al momento della formattazione.
+#[tauri::command]
+pub async fn validate_patch_placeholders(
+    patch_id: String,
+    formats: Option<PlaceholderFormats>,
+) -> Result<Vec<PlaceholderIssue>, String> {
+    let patch = load_saved_patch(&patch_id)?;
+    let formats = formats.unwrap_or_default();
+
+    let mut issues = Vec::new();
+    for (key, entry) in patch_entries(&patch) {
+        let Some((key, source, target)) = entry_texts(key, &entry) else { continue };
+        let mut push = |kind: PlaceholderIssueKind, style: PlaceholderStyle, placeholder: String, message: String| {
+            issues.push(PlaceholderIssue {
+                key: key.clone(),
+                kind,
+                style,
+                placeholder,
+                source: source.to_string(),
+                target: target.to_string(),
+                message,
+            });
+        };
+
+        let source_counts = placeholder_counts(extract_placeholders(source, &formats));
+        let target_counts = placeholder_counts(extract_placeholders(target, &formats));
+        for (ph, (style, count)) in &source_counts {
+            let found = target_counts.get(ph).map(|(_, c)| *c).unwrap_or(0);
+            if found < *count {
+                push(PlaceholderIssueKind::Missing, *style, ph.clone(),
+                    format!("Placeholder mancante: {} ({} su {})", ph, found, count));
+            }
+        }
+        for (ph, (style, count)) in &target_counts {
+            let expected = source_counts.get(ph).map(|(_, c)| *c).unwrap_or(0);
+            if *count > expected {
+                push(PlaceholderIssueKind::Extra, *style, ph.clone(),
+                    format!("Placeholder non presente nell'originale: {}", ph));
+            }
+        }
+
+        // Frammenti già presenti nell'originale sono testo letterale del gioco, non errori
+        let source_malformed: Vec<String> = malformed_placeholders(source, &formats).into_iter().map(|(_, f)| f).collect();
+        for (style, fragment) in malformed_placeholders(target, &formats) {
+            if !source_malformed.contains(&fragment) {
+                push(PlaceholderIssueKind::Malformed, style, fragment.clone(),
+                    format!("Placeholder malformato: {}", fragment));
+            }
+        }
+    }
+    issues.sort_by(|a, b| a.key.cmp(&b.key).then_with(|| a.placeholder.cmp(&b.placeholder)));
+
+    log::info!("🧩 Verifica placeholder patch {}: {} problemi", patch_id, issues.len());
+    Ok(issues)
+}
+
+/// Traduzione probabilmente troppo lunga per l'interfaccia del gioco
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LengthOverflow {
+    pub key: String,
+    pub source: String,
+    pub target: String,
+    /// Larghezza in colonne (CJK e fullwidth contano 2 se richiesto)
+    pub source_width: usize,
+    pub target_width: usize,
+    pub ratio: f64,
+    pub extra_chars: usize,
+}
+
+/// Caratteri che occupano due colonne nei font di gioco (CJK, Hangul, kana, fullwidth)
+fn is_wide_char(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x115F | 0x2E80..=0x303E | 0x3041..=0x33FF | 0x3400..=0x4DBF |
+        0x4E00..=0x9FFF | 0xA000..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF |
+        0xFE30..=0xFE4F | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 | 0x20000..=0x3FFFD)
+}
+
+/// Tag di formattazione (<color=...>, [b]) che non occupano spazio a schermo
+static MARKUP: Lazy<regex::Regex> = Lazy::new(|| regex::Regex::new(r"<[^>]+>|\[/?[a-zA-Z][^\]]*\]").unwrap());
+
+/// Larghezza visibile del testo, senza tag di formattazione
+fn display_width(text: &str, cjk_width: bool) -> usize {
+    MARKUP.replace_all(text, "")
+        .chars()
+        .map(|c| if cjk_width && is_wide_char(c) { 2 } else { 1 })
+        .sum()
+}
+
+/// Voci della patch la cui traduzione supera l'originale oltre le soglie, le più critiche prima.
+/// Le stringhe molto corte sono escluse dal rapporto ("OK" → "Conferma" è normale).
+fn length_overflows(
+    patch: &serde_json::Value,
+    ratio_threshold: f64,
+    max_extra_chars: Option<usize>,
+    cjk_width: bool,
+) -> Vec<LengthOverflow> {
+    const MIN_SOURCE_WIDTH_FOR_RATIO: usize = 6;
+
+    let mut overflows = Vec::new();
+    for (key, entry) in patch_entries(patch) {
+        let Some((key, source, target)) = entry_texts(key, &entry) else { continue };
+        let source_width = display_width(source, cjk_width);
+        let target_width = display_width(target, cjk_width);
+        if source_width == 0 || target_width <= source_width {
+            continue;
+        }
+        let ratio = target_width as f64 / source_width as f64;
+        let extra_chars = target_width - source_width;
+        let over_ratio = source_width >= MIN_SOURCE_WIDTH_FOR_RATIO && ratio > ratio_threshold;
+        let over_extra = max_extra_chars.is_some_and(|max| extra_chars > max);
+        if over_ratio || over_extra {
+            overflows.push(LengthOverflow {
+                key,
+                source: source.to_string(),
+                target: target.to_string(),
+                source_width,
+                target_width,
+                ratio,
+                extra_chars,
+            });
+        }
+    }
+    overflows.sort_by(|a, b| b.ratio.total_cmp(&a.ratio).then_with(|| a.key.cmp(&b.key)));
+    overflows
+}
+
+/// Segnala le traduzioni che superano l'originale di più di `ratio_threshold` (default 1.3)
+/// o di più di `max_extra_chars` caratteri: testo quasi certamente tagliato in UI a larghezza fissa.
+#[tauri::command]
+pub async fn find_length_overflows(
+    patch_id: String,
+    ratio_threshold: Option<f64>,
+    max_extra_chars: Option<usize>,
+    cjk_width: Option<bool>,
+) -> Result<Vec<LengthOverflow>, String> {
+    let patch = load_saved_patch(&patch_id)?;
+    let ratio_threshold = ratio_threshold.unwrap_or(1.3).max(1.0);
+    let overflows = length_overflows(&patch, ratio_threshold, max_extra_chars, cjk_width.unwrap_or(true));
+
+    log::info!("📏 Patch {}: {} traduzioni potenzialmente troppo lunghe", patch_id, overflows.len());
+    Ok(overflows)
+}
+
+/// Voce di traduzione proveniente da una sorgente automatica (es. OCR)
+#[derive(Debug, Clone)]
+pub struct SourcedTranslation {
+    pub original: String,
+    pub translated: Option<String>,
+    pub confidence: f32,
+}
+
+/// Esito dell'aggiornamento di una patch da sorgente automatica
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchUpsertResult {
+    pub patch_id: String,
+    pub path: String,
+    pub created: bool,
+    pub added: usize,
+    pub updated: usize,
+    pub total: usize,
+}
+
+/// Directory delle patch salvate dal backend
+fn get_patches_dir() -> Result<PathBuf, String> {
+    let app_data = dirs::data_local_dir()
+        .ok_or("Impossibile trovare la directory dei dati locali")?;
+    let dir = app_data.join("GameStringer").join("patches");
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Impossibile creare directory patch: {}", e))?;
+    }
+    Ok(dir)
+}
+
+/// Crea o aggiorna la patch `<source>_<game_id>` con le voci fornite.
+/// Le voci già revisionate non vengono toccate; le altre vengono aggiornate solo
+/// se la nuova traduzione ha confidenza pari o superiore.
+pub fn upsert_sourced_translations(
+    game_id: &str,
+    source: &str,
+    source_lang: &str,
+    target_lang: &str,
+    entries: &[SourcedTranslation],
+) -> Result<PatchUpsertResult, String> {
+    upsert_sourced_translations_in(&get_patches_dir()?, game_id, source, source_lang, target_lang, entries)
+}
+
+fn upsert_sourced_translations_in(
+    patches_dir: &Path,
+    game_id: &str,
+    source: &str,
+    source_lang: &str,
+    target_lang: &str,
+    entries: &[SourcedTranslation],
+) -> Result<PatchUpsertResult, String> {
+    let safe_game_id: String = game_id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if safe_game_id.is_empty() {
+        return Err("game_id non valido".to_string());
+    }
+    
+    let patch_id = format!("{}_{}", source, safe_game_id);
+    let path = patches_dir.join(format!("{}.json", patch_id));
+    let now = chrono::Utc::now().to_rfc3339();
+    
+    let existing: Option<serde_json::Value> = fs::read_to_string(&path).ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    let created = existing.is_none();
+    let mut patch = existing.unwrap_or_else(|| serde_json::json!({
+        "id": patch_id,
+        "gameId": game_id,
+        "name": format!("{} ({})", game_id, source.to_uppercase()),
+        "description": format!("Testi raccolti automaticamente da {}", source),
+        "version": "1.0.0",
+        "sourceLanguage": source_lang,
+        "targetLanguage": target_lang,
+        "patchType": "REPLACEMENT",
+        "files": [],
+        "translations": [],
+        "createdAt": now,
+    }));
+    
+    let mut translations: Vec<serde_json::Value> = patch.get("translations")
+        .and_then(|t| t.as_array())
+        .cloned()
+        .unwrap_or_default();
+    // Le voci si abbinano per id canonico: lo stesso testo da un'altra pipeline finisce sulla stessa voce
+    // (anche con virgolette o puntini diversi, se la normalizzazione tipografica è attiva)
+    let mut index: HashMap<String, usize> = translations.iter().enumerate()
+        .filter_map(|(i, t)| t.get("originalText").and_then(|v| v.as_str()).map(|o| (string_id(&lookup_text(o)), i)))
+        .collect();
+    
+    let (mut added, mut updated) = (0, 0);
+    for entry in entries {
+        let translated = entry.translated.clone().unwrap_or_default();
+        let match_id = string_id(&lookup_text(&entry.original));
+        match index.get(&match_id) {
+            Some(&i) => {
+                let current = &mut translations[i];
+                let reviewed = current.get("reviewed").and_then(|v| v.as_bool()).unwrap_or(false);
+                let current_confidence = current.get("confidence").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                if reviewed || translated.is_empty() || (entry.confidence as f64) < current_confidence {
+                    continue;
+                }
+                if current.get("translatedText").and_then(|v| v.as_str()) != Some(translated.as_str()) {
+                    current["translatedText"] = serde_json::json!(translated);
+                    current["confidence"] = serde_json::json!(entry.confidence);
+                    updated += 1;
+                }
+            }
+            None => {
+                index.insert(match_id, translations.len());
+                translations.push(serde_json::json!({
+                    "id": string_id(&entry.original),
+                    "originalText": entry.original,
+                    "translatedText": translated,
+                    "context": source,
+                    "source": source,
+                    "confidence": entry.confidence,
+                    "reviewed": false,
+                }));
+                added += 1;
+            }
+        }
+    }
+    
+    let total = translations.len();
+    patch["translations"] = serde_json::Value::Array(translations);
+    patch["updatedAt"] = serde_json::json!(now);
+    
+    let content = serde_json::to_string_pretty(&patch).map_err(|e| e.to_string())?;
+    super::file_manager::write_atomic(&path, content.as_bytes())
+        .map_err(|e| format!("Errore salvataggio patch: {}", e))?;
+    
+    log::info!("💾 Patch {} {}: {} nuove voci, {} aggiornate ({} totali)",
+        patch_id, if created { "creata" } else { "aggiornata" }, added, updated, total);
+    Ok(PatchUpsertResult {
+        patch_id,
+        path: path.to_string_lossy().to_string(),
+        created,
+        added,
+        updated,
+        total,
+    })
+}
+
 #[tauri::command]
 #[allow(dead_code)] // Comando traduzione testo - essenziale per sistema injection/traduzione
 pub async fn translate_text(text: String, provider: String, _api_key: String, target_lang: String) -> Result<serde_json::Value, String> {
@@ -116,31 +793,123 @@ pub async fn translate_text(text: String, provider: String, _api_key: String, ta
     Ok(translation_result)
 }
 
+/// Fonte di un suggerimento di traduzione
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionSource {
+    TranslationMemory,
+    Glossary,
+    Dictionary,
+}
+
+/// Suggerimento di traduzione con punteggio già pesato
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub text: String,
+    pub source: SuggestionSource,
+    /// Confidenza 0.0 - 1.0 dopo l'applicazione del peso della fonte
+    pub confidence: f64,
+    /// Etichetta leggibile (es. "95% match")
+    pub label: String,
+    /// Testo sorgente che ha prodotto il suggerimento
+    pub matched_source: Option<String>,
+}
+
+/// Peso di ogni fonte nel ranking dei suggerimenti
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuggestionWeights {
+    pub translation_memory: f64,
+    pub glossary: f64,
+    pub dictionary: f64,
+}
+
+impl Default for SuggestionWeights {
+    fn default() -> Self {
+        Self {
+            translation_memory: 1.0,
+            glossary: 0.9,
+            dictionary: 0.6,
+        }
+    }
+}
+
+/// Aggrega suggerimenti da Translation Memory, glossario del gioco e dizionario
+/// integrato, ordinati per confidenza e senza traduzioni duplicate
 #[tauri::command]
-pub async fn get_translation_suggestions(text: String, context: Option<String>) -> Result<serde_json::Value, String> {
-    log::info!("💡 Recupero suggerimenti traduzione per: '{}'", 
-        if text.len() > 50 { format!("{}...", &text[..50]) } else { text.clone() });
+pub async fn get_translation_suggestions(
+    text: String,
+    context: Option<String>,
+    source_lang: Option<String>,
+    target_lang: Option<String>,
+    game_id: Option<String>,
+    min_score: Option<u8>,
+    weights: Option<SuggestionWeights>,
+    max_results: Option<usize>,
+) -> Result<Vec<Suggestion>, String> {
+    log::info!("💡 Recupero suggerimenti traduzione per: '{}'{}", 
+        if text.len() > 50 { format!("{}...", text.chars().take(50).collect::<String>()) } else { text.clone() },
+        context.as_ref().map(|c| format!(" (contesto: {})", c)).unwrap_or_default());
     
-    // TODO: Implementare sistema suggerimenti basato su database/AI
-    let suggestions = serde_json::json!({
-        "original_text": text,
-        "context": context,
-        "suggestions": [
-            {
-                "text": format!("Suggerimento 1 per: {}", text),
-                "confidence": 0.9,
-                "source": "database"
-            },
-            {
-                "text": format!("Suggerimento 2 per: {}", text),
-                "confidence": 0.8,
-                "source": "ai"
+    let source = source_lang.unwrap_or_else(|| "en".to_string());
+    let target = target_lang.unwrap_or_else(|| "it".to_string());
+    let weights = weights.unwrap_or_default();
+    let mut suggestions = Vec::new();
+    let lookup = lookup_text(&text).into_owned();
+    
+    // 1. Translation Memory (exact + fuzzy)
+    let matches = tm_fuzzy_lookup(lookup.clone(), source.clone(), target.clone(), min_score, Some(10))?;
+    for m in matches {
+        suggestions.push(Suggestion {
+            text: m.target_text,
+            source: SuggestionSource::TranslationMemory,
+            confidence: m.score as f64 / 100.0 * weights.translation_memory,
+            label: format!("{}% match", m.score),
+            matched_source: Some(m.source_text),
+        });
+    }
+    
+    // 2. Glossario del gioco
+    if let Some(game_id) = game_id {
+        let hits = search_glossary_scored(game_id, lookup.clone(), Some("fuzzy".to_string()), None, Some(10)).await?;
+        for hit in hits {
+            let label = if hit.score >= 1.0 {
+                "Glossario".to_string()
+            } else {
+                format!("Glossario ({}%)", (hit.score * 100.0).round())
+            };
+            for translation in std::iter::once(&hit.entry.translation).chain(&hit.entry.variants) {
+                suggestions.push(Suggestion {
+                    text: translation.clone(),
+                    source: SuggestionSource::Glossary,
+                    confidence: hit.score * weights.glossary,
+                    label: label.clone(),
+                    matched_source: Some(hit.entry.original.clone()),
+                });
             }
-        ],
-        "generated_at": chrono::Utc::now().to_rfc3339()
-    });
+        }
+    }
+    
+    // 3. Dizionario integrato (solo da inglese)
+    if source.eq_ignore_ascii_case("en") {
+        if let Some(translated) = crate::ocr_translator::simple_translate(lookup.trim(), &target) {
+            suggestions.push(Suggestion {
+                text: translated,
+                source: SuggestionSource::Dictionary,
+                confidence: weights.dictionary,
+                label: "Dizionario".to_string(),
+                matched_source: Some(text.trim().to_string()),
+            });
+        }
+    }
+    
+    // Ordina e rimuove duplicati mantenendo la confidenza più alta
+    suggestions.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+    let mut seen = std::collections::HashSet::new();
+    suggestions.retain(|s| seen.insert(s.text.trim().to_lowercase()));
+    suggestions.truncate(max_results.unwrap_or(10));
     
-    log::warn!("⚠️ Suggerimenti traduzione non ancora implementati");
+    log::info!("✅ {} suggerimenti aggregati", suggestions.len());
     Ok(suggestions)
 }
 
@@ -151,6 +920,8 @@ pub async fn export_translations(
     translations: Vec<serde_json::Value>,
     source_lang: Option<String>,
     target_lang: Option<String>,
+    include_regex: Option<bool>,
+    substitutions: Option<HashMap<String, String>>,
 ) -> Result<serde_json::Value, String> {
     log::info!("📋 Export {} traduzioni in formato: {} -> {}", translations.len(), format, file_path);
     
@@ -256,8 +1027,57 @@ pub async fn export_translations(
             fs::write(&file_path, po_content)
                 .map_err(|e| format!("Errore scrittura file: {}", e))?;
         }
+        "xunity" => {
+            // Formato XUnity.AutoTranslator (_AutoGeneratedTranslations.txt): originale=traduzione
+            let include_regex = include_regex.unwrap_or(false);
+            let mut content = format!(
+                "// Translation file exported by GameStringer\n// Source: {}\n// Target: {}\n\n",
+                source, target
+            );
+            let mut regex_lines = Vec::new();
+            
+            for t in &translations {
+                if let (Some(orig), Some(trans)) = (
+                    t.get("original").and_then(|v| v.as_str()),
+                    t.get("translated").and_then(|v| v.as_str())
+                ) {
+                    let is_regex = t.get("regex").and_then(|v| v.as_bool()).unwrap_or(false);
+                    if is_regex {
+                        if include_regex {
+                            regex_lines.push(format!("r:\"{}\"={}", escape_xunity(orig), escape_xunity(trans)));
+                        }
+                    } else {
+                        content.push_str(&format!("{}={}\n", escape_xunity(orig), escape_xunity(trans)));
+                    }
+                }
+            }
+            
+            if !regex_lines.is_empty() {
+                content.push_str("\n// Regex\n");
+                for line in regex_lines {
+                    content.push_str(&line);
+                    content.push('\n');
+                }
+            }
+            
+            fs::write(&file_path, content)
+                .map_err(|e| format!("Errore scrittura file: {}", e))?;
+            
+            // Le sostituzioni vanno in _Substitutions.txt accanto al file di traduzione
+            if let Some(subs) = substitutions.as_ref().filter(|s| !s.is_empty()) {
+                let subs_path = Path::new(&file_path).with_file_name("_Substitutions.txt");
+                let mut subs_sorted: Vec<_> = subs.iter().collect();
+                subs_sorted.sort();
+                let mut subs_content = String::from("// Substitutions exported by GameStringer\n");
+                for (from, to) in subs_sorted {
+                    subs_content.push_str(&format!("{}={}\n", escape_xunity(from), escape_xunity(to)));
+                }
+                fs::write(&subs_path, subs_content)
+                    .map_err(|e| format!("Errore scrittura sostituzioni: {}", e))?;
+            }
+        }
         _ => {
-            return Err(format!("Formato non supportato: {}. Usa: json, json_full, csv, tsv, po", format));
+            return Err(format!("Formato non supportato: {}. Usa: json, json_full, csv, tsv, po, xunity", format));
         }
     }
     
@@ -275,7 +1095,7 @@ pub async fn export_translations(
 }
 
 /// Escape campo CSV
-fn escape_csv_field(field: &str) -> String {
+pub(crate) fn escape_csv_field(field: &str) -> String {
     if field.contains(',') || field.contains('"') || field.contains('\n') {
         format!("\"{}\"", field.replace('"', "\"\""))
     } else {
@@ -283,6 +1103,21 @@ fn escape_csv_field(field: &str) -> String {
     }
 }
 
+/// Escape chiave/valore XUnity.AutoTranslator
+pub(crate) fn escape_xunity(s: &str) -> String {
+    let escaped = s.replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    // Una riga che inizia con "//" verrebbe letta come commento
+    if escaped.starts_with("//") {
+        format!("\\/{}", &escaped[1..])
+    } else {
+        escaped
+    }
+}
+
 /// Escape stringa PO
 fn escape_po_string(s: &str) -> String {
     s.replace('\\', "\\\\")
@@ -452,8 +1287,262 @@ pub async fn import_translations(
     }))
 }
 
+/// Dimensione massima di un singolo file importato da cartella
+const MAX_DIRECTORY_IMPORT_FILE_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Stessa stringa originale tradotta diversamente in due file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportConflict {
+    pub original: String,
+    /// Traduzione mantenuta (primo file in ordine alfabetico)
+    pub kept: String,
+    pub kept_file: String,
+    pub discarded: String,
+    pub discarded_file: String,
+}
+
+/// File della cartella non importato
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportFileError {
+    pub file: String,
+    pub error: String,
+}
+
+/// Formato di un file di traduzioni della community
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CommunityFormat {
+    /// XUnity.AutoTranslator: `originale=traduzione`, `\=` e `\n` escapati, commenti `//`
+    AutoTranslator,
+    /// `chiave=valore` semplice, commenti `#` o `;`
+    KeyValue,
+    /// `originale,traduzione` con intestazione opzionale
+    Csv,
+}
+
+impl CommunityFormat {
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "autotranslator" | "xunity" | "txt" => Some(Self::AutoTranslator),
+            "keyvalue" | "key=value" | "kv" | "ini" | "properties" => Some(Self::KeyValue),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    /// Formato dedotto dall'estensione (modalità "auto")
+    pub(crate) fn from_extension(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "txt" => Some(Self::AutoTranslator),
+            "ini" | "properties" | "cfg" | "lang" => Some(Self::KeyValue),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Rimuove gli escape di XUnity (\= \n \r \t \\)
+pub(crate) fn unescape_autotranslator(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Posizione del primo `=` non escapato
+pub(crate) fn find_unescaped_equals(line: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '=' if !escaped => return Some(i),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// Euristica per i file senza estensione nota: BOM Unicode oppure nessun byte NUL
+/// nei primi 8 KB (gli asset binari ne contengono quasi sempre)
+fn looks_like_text(bytes: &[u8]) -> bool {
+    const BOMS: &[&[u8]] = &[b"\xEF\xBB\xBF", b"\xFF\xFE", b"\xFE\xFF"];
+    if BOMS.iter().any(|bom| bytes.starts_with(bom)) {
+        return true;
+    }
+    !bytes.iter().take(8192).any(|&b| b == 0)
+}
+
+/// Coppie (originale, traduzione) di un file; le righe non valide vengono saltate
+fn parse_community_file(content: &str, format: CommunityFormat) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    for (line_num, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let pair = match format {
+            CommunityFormat::AutoTranslator => {
+                // Le righe "r:" / "sr:" sono regex di XUnity, non testi
+                if trimmed.starts_with("//") || trimmed.starts_with("r:") || trimmed.starts_with("sr:") {
+                    continue;
+                }
+                find_unescaped_equals(line).map(|idx| (unescape_autotranslator(&line[..idx]), unescape_autotranslator(&line[idx + 1..])))
+            }
+            CommunityFormat::KeyValue => {
+                if trimmed.starts_with('#') || trimmed.starts_with(';') || trimmed.starts_with('[') {
+                    continue;
+                }
+                trimmed.split_once('=').map(|(k, v)| (k.trim().to_string(), v.trim().replace("\\n", "\n")))
+            }
+            CommunityFormat::Csv => {
+                let parts = parse_csv_line(line);
+                let is_header = line_num == 0 && parts.first().is_some_and(|h| {
+                    matches!(h.trim().to_lowercase().as_str(), "source" | "original" | "key" | "id" | "text")
+                });
+                if is_header || parts.len() < 2 {
+                    continue;
+                }
+                Some((parts[0].trim().to_string(), parts[1].trim().to_string()))
+            }
+        };
+        if let Some((orig, trans)) = pair {
+            if !orig.trim().is_empty() && !trans.trim().is_empty() {
+                pairs.push((orig, trans));
+            }
+        }
+    }
+    pairs
+}
+
+/// Importa ricorsivamente una cartella di traduzioni della community in un'unica patch.
+/// `format`: "auto" (default, dall'estensione), "autotranslator", "keyvalue" o "csv".
+/// L'encoding è rilevato per file; in caso di conflitto vince il primo file in ordine alfabetico.
+#[tauri::command]
+pub async fn import_translations_from_directory(
+    dir: String,
+    format: Option<String>,
+) -> Result<serde_json::Value, String> {
+    log::info!("📥 Import traduzioni dalla cartella: {}", dir);
+
+    let root = PathBuf::from(&dir);
+    if !root.is_dir() {
+        return Err(format!("Cartella non trovata: {}", dir));
+    }
+    let forced = match format.as_deref().map(str::trim).filter(|f| !f.is_empty() && !f.eq_ignore_ascii_case("auto")) {
+        Some(name) => Some(CommunityFormat::parse(name)
+            .ok_or_else(|| format!("Formato non supportato: {}. Usa: auto, autotranslator, keyvalue, csv", name))?),
+        None => None,
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        // original -> (traduzione, file)
+        let mut merged: HashMap<String, (String, String)> = HashMap::new();
+        let mut order: Vec<String> = Vec::new();
+        let mut conflicts = Vec::new();
+        let mut errors = Vec::new();
+        let mut files = Vec::new();
+        let mut duplicates = 0usize;
+
+        let walker = walkdir::WalkDir::new(&root).sort_by_file_name().into_iter().filter_map(|e| e.ok());
+        for entry in walker.filter(|e| e.file_type().is_file()) {
+            let path = entry.path();
+            let known_format = CommunityFormat::from_extension(path);
+            let Some(file_format) = forced.or(known_format) else { continue };
+            let relative = path.strip_prefix(&root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+
+            if entry.metadata().is_ok_and(|m| m.len() > MAX_DIRECTORY_IMPORT_FILE_BYTES) {
+                // Con formato forzato i file estranei (es. asset binari) vengono solo saltati
+                if known_format.is_some() {
+                    errors.push(ImportFileError { file: relative, error: "File troppo grande".to_string() });
+                }
+                continue;
+            }
+            let bytes = match fs::read(path) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    errors.push(ImportFileError { file: relative, error: e.to_string() });
+                    continue;
+                }
+            };
+            // Formato forzato su un'estensione non riconosciuta: solo se il contenuto sembra testo
+            if known_format.is_none() && !looks_like_text(&bytes) {
+                log::debug!("⏭️ {}: file binario ignorato", relative);
+                continue;
+            }
+            let decoded = match super::file_manager::decode_text(&bytes, None) {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    errors.push(ImportFileError { file: relative, error: e });
+                    continue;
+                }
+            };
+            if decoded.had_errors {
+                log::warn!("⚠️ {}: caratteri non decodificabili come {}", relative, decoded.encoding);
+            }
+            let pairs = parse_community_file(&decoded.content, file_format);
+            files.push(serde_json::json!({ "file": relative, "encoding": decoded.encoding, "entries": pairs.len() }));
+
+            for (orig, trans) in pairs {
+                match merged.get(&orig) {
+                    Some((kept, _)) if *kept == trans => duplicates += 1,
+                    Some((kept, kept_file)) => conflicts.push(ImportConflict {
+                        original: orig.clone(),
+                        kept: kept.clone(),
+                        kept_file: kept_file.clone(),
+                        discarded: trans,
+                        discarded_file: relative.clone(),
+                    }),
+                    None => {
+                        order.push(orig.clone());
+                        merged.insert(orig, (trans, relative.clone()));
+                    }
+                }
+            }
+        }
+
+        let translations: Vec<serde_json::Value> = order.into_iter()
+            .filter_map(|orig| merged.remove(&orig).map(|(trans, file)| serde_json::json!({
+                "id": string_id(&orig),
+                "original": orig,
+                "translated": trans,
+                "source_file": file
+            })))
+            .collect();
+
+        log::info!("✅ Importate {} traduzioni da {} file ({} conflitti, {} duplicati, {} errori)",
+            translations.len(), files.len(), conflicts.len(), duplicates, errors.len());
+
+        Ok(serde_json::json!({
+            "dir": dir,
+            "format": format.unwrap_or_else(|| "auto".to_string()),
+            "imported_at": chrono::Utc::now().to_rfc3339(),
+            "files": files,
+            "imported_count": translations.len(),
+            "duplicate_count": duplicates,
+            "conflicts": conflicts,
+            "file_errors": errors,
+            "translations": translations,
+            "status": "imported"
+        }))
+    })
+    .await
+    .map_err(|e| format!("Errore import cartella: {}", e))?
+}
+
 /// Parse CSV line handling quoted fields
-fn parse_csv_line(line: &str) -> Vec<String> {
+pub(crate) fn parse_csv_line(line: &str) -> Vec<String> {
     let mut result = Vec::new();
     let mut current = String::new();
     let mut in_quotes = false;
@@ -492,3 +1581,554 @@ fn unescape_po_string(s: &str) -> String {
         .replace("\\\"", "\"")
         .replace("\\\\", "\\")
 }
+
+/// Tipo di modifica di una chiave rispetto alla versione base
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Lato che ha introdotto la modifica
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchChangeSide {
+    Mine,
+    Theirs,
+    /// Stessa modifica su entrambi i lati
+    Both,
+    /// Modifiche diverse sui due lati
+    Conflict,
+}
+
+/// Differenza su una singola chiave di traduzione
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchKeyChange {
+    pub key: String,
+    pub kind: PatchChangeKind,
+    pub side: PatchChangeSide,
+    pub base: Option<String>,
+    pub mine: Option<String>,
+    pub theirs: Option<String>,
+}
+
+/// Diff a tre vie tra due versioni di una patch
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PatchDiff {
+    pub added: Vec<PatchKeyChange>,
+    pub removed: Vec<PatchKeyChange>,
+    pub changed: Vec<PatchKeyChange>,
+    pub conflicts: Vec<PatchKeyChange>,
+}
+
+/// Strategia di risoluzione dei conflitti
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchMergeStrategy {
+    PreferMine,
+    PreferTheirs,
+    /// Mantiene il valore base e lascia i conflitti all'utente
+    Manual,
+}
+
+/// Risultato di un merge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatchMergeResult {
+    pub patch: serde_json::Value,
+    /// Conflitti rilevati (risolti automaticamente salvo strategia manual)
+    pub conflicts: Vec<PatchKeyChange>,
+    pub unresolved: usize,
+}
+
+/// Chiave di una voce di traduzione: id esplicito o testo originale
+fn translation_key(entry: &serde_json::Value) -> Option<String> {
+    ["key", "id", "original"].iter()
+        .find_map(|k| entry.get(*k).and_then(|v| v.as_str()))
+        .map(|s| s.to_string())
+}
+
+/// Estrae le voci di traduzione di una patch, indicizzate per chiave
+fn patch_entries(patch: &serde_json::Value) -> HashMap<String, serde_json::Value> {
+    let translations = patch.get("translations").unwrap_or(patch);
+    let mut entries = HashMap::new();
+    
+    if let Some(arr) = translations.as_array() {
+        for entry in arr {
+            if let Some(key) = translation_key(entry) {
+                entries.insert(key, entry.clone());
+            }
+        }
+    } else if let Some(obj) = translations.as_object() {
+        // Mappa semplice { "original": "translated" }
+        for (orig, trans) in obj {
+            if let Some(t) = trans.as_str() {
+                entries.insert(orig.clone(), serde_json::json!({
+                    "original": orig,
+                    "translated": t
+                }));
+            }
+        }
+    }
+    
+    entries
+}
+
+fn translated_value(entry: Option<&serde_json::Value>) -> Option<String> {
+    entry.and_then(|e| e.get("translated"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Calcola il diff a tre vie tra le voci indicizzate
+fn three_way_diff(
+    base: &HashMap<String, serde_json::Value>,
+    mine: &HashMap<String, serde_json::Value>,
+    theirs: &HashMap<String, serde_json::Value>,
+) -> PatchDiff {
+    let keys: BTreeSet<&String> = base.keys().chain(mine.keys()).chain(theirs.keys()).collect();
+    let mut diff = PatchDiff::default();
+    
+    for key in keys {
+        let b = translated_value(base.get(key));
+        let m = translated_value(mine.get(key));
+        let t = translated_value(theirs.get(key));
+        
+        let side = if m == t {
+            if m == b { continue; }
+            PatchChangeSide::Both
+        } else if m == b {
+            PatchChangeSide::Theirs
+        } else if t == b {
+            PatchChangeSide::Mine
+        } else {
+            PatchChangeSide::Conflict
+        };
+        
+        let changed_value = match side {
+            PatchChangeSide::Theirs => &t,
+            _ => &m,
+        };
+        let kind = match (&b, changed_value) {
+            (None, _) => PatchChangeKind::Added,
+            (Some(_), None) => PatchChangeKind::Removed,
+            (Some(_), Some(_)) => PatchChangeKind::Changed,
+        };
+        
+        let change = PatchKeyChange {
+            key: key.clone(),
+            kind,
+            side,
+            base: b,
+            mine: m,
+            theirs: t,
+        };
+        
+        if side == PatchChangeSide::Conflict {
+            diff.conflicts.push(change);
+        } else {
+            match kind {
+                PatchChangeKind::Added => diff.added.push(change),
+                PatchChangeKind::Removed => diff.removed.push(change),
+                PatchChangeKind::Changed => diff.changed.push(change),
+            }
+        }
+    }
+    
+    diff
+}
+
+#[tauri::command]
+pub async fn diff_patches(
+    base: serde_json::Value,
+    mine: serde_json::Value,
+    theirs: serde_json::Value,
+) -> Result<PatchDiff, String> {
+    log::info!("🔀 Diff patch a tre vie");
+    
+    let diff = three_way_diff(&patch_entries(&base), &patch_entries(&mine), &patch_entries(&theirs));
+    
+    log::info!("✅ Diff: {} aggiunte, {} rimosse, {} modificate, {} conflitti",
+        diff.added.len(), diff.removed.len(), diff.changed.len(), diff.conflicts.len());
+    Ok(diff)
+}
+
+#[tauri::command]
+pub async fn merge_patches(
+    base: serde_json::Value,
+    mine: serde_json::Value,
+    theirs: serde_json::Value,
+    strategy: PatchMergeStrategy,
+) -> Result<PatchMergeResult, String> {
+    log::info!("🔀 Merge patch con strategia {:?}", strategy);
+    
+    let base_entries = patch_entries(&base);
+    let mine_entries = patch_entries(&mine);
+    let theirs_entries = patch_entries(&theirs);
+    let diff = three_way_diff(&base_entries, &mine_entries, &theirs_entries);
+    
+    // Parte dalla mia versione e applica le modifiche dell'altro lato
+    let mut merged = mine_entries.clone();
+    let non_conflicting = diff.added.iter().chain(&diff.removed).chain(&diff.changed);
+    for change in non_conflicting {
+        if change.side != PatchChangeSide::Theirs {
+            continue;
+        }
+        match theirs_entries.get(&change.key) {
+            Some(entry) => { merged.insert(change.key.clone(), entry.clone()); }
+            None => { merged.remove(&change.key); }
+        }
+    }
+    
+    for conflict in &diff.conflicts {
+        let winner = match strategy {
+            PatchMergeStrategy::PreferMine => mine_entries.get(&conflict.key),
+            PatchMergeStrategy::PreferTheirs => theirs_entries.get(&conflict.key),
+            PatchMergeStrategy::Manual => base_entries.get(&conflict.key),
+        };
+        match winner {
+            Some(entry) => { merged.insert(conflict.key.clone(), entry.clone()); }
+            None => { merged.remove(&conflict.key); }
+        }
+    }
+    
+    let mut translations: Vec<(String, serde_json::Value)> = merged.into_iter().collect();
+    translations.sort_by(|a, b| a.0.cmp(&b.0));
+    let translations: Vec<serde_json::Value> = translations.into_iter().map(|(_, v)| v).collect();
+    
+    let mut patch = if mine.is_object() { mine.clone() } else { serde_json::json!({}) };
+    patch["translations"] = serde_json::Value::Array(translations);
+    patch["updated_at"] = serde_json::json!(chrono::Utc::now().to_rfc3339());
+    
+    let unresolved = if strategy == PatchMergeStrategy::Manual { diff.conflicts.len() } else { 0 };
+    
+    log::info!("✅ Merge completato: {} conflitti ({} da risolvere)", diff.conflicts.len(), unresolved);
+    Ok(PatchMergeResult {
+        patch,
+        conflicts: diff.conflicts,
+        unresolved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sourced(original: &str, translated: &str, confidence: f32) -> SourcedTranslation {
+        SourcedTranslation { original: original.to_string(), translated: Some(translated.to_string()), confidence }
+    }
+
+    fn translation<'a>(patch: &'a serde_json::Value, original: &str) -> &'a serde_json::Value {
+        patch["translations"].as_array().unwrap().iter()
+            .find(|t| t["originalText"] == original)
+            .unwrap()
+    }
+
+    #[test]
+    fn upsert_adds_and_updates_entries() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let first = upsert_sourced_translations_in(dir.path(), "steam:42", "ocr", "en", "it",
+            &[sourced("Open the door", "Apri la porta", 0.6), sourced("Exit", "Esci", 0.9)]).unwrap();
+        assert!(first.created);
+        assert_eq!((first.added, first.updated, first.total), (2, 0, 2));
+        assert_eq!(first.patch_id, "ocr_steam_42");
+
+        // Confidenza più alta: aggiorna; più bassa: ignorata
+        let second = upsert_sourced_translations_in(dir.path(), "steam:42", "ocr", "en", "it",
+            &[sourced("Open the door", "Apri quella porta", 0.8), sourced("Exit", "Uscita", 0.5),
+              sourced("Load game", "Carica partita", 0.7)]).unwrap();
+        assert!(!second.created);
+        assert_eq!((second.added, second.updated, second.total), (1, 1, 3));
+
+        let patch: serde_json::Value = serde_json::from_str(&fs::read_to_string(&second.path).unwrap()).unwrap();
+        assert_eq!(translation(&patch, "Open the door")["translatedText"], "Apri quella porta");
+        assert_eq!(translation(&patch, "Exit")["translatedText"], "Esci");
+        assert_eq!(patch["gameId"], "steam:42");
+    }
+
+    #[test]
+    fn upsert_keeps_reviewed_entries() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let result = upsert_sourced_translations_in(dir.path(), "game", "ocr", "en", "it",
+            &[sourced("Save", "Salva", 0.5)]).unwrap();
+
+        // Revisione manuale della voce
+        let mut patch: serde_json::Value = serde_json::from_str(&fs::read_to_string(&result.path).unwrap()).unwrap();
+        patch["translations"][0]["translatedText"] = serde_json::json!("Salva partita");
+        patch["translations"][0]["reviewed"] = serde_json::json!(true);
+        fs::write(&result.path, serde_json::to_string(&patch).unwrap()).unwrap();
+
+        let result = upsert_sourced_translations_in(dir.path(), "game", "ocr", "en", "it",
+            &[sourced("Save", "Salvataggio", 1.0)]).unwrap();
+        assert_eq!((result.added, result.updated), (0, 0));
+        let patch: serde_json::Value = serde_json::from_str(&fs::read_to_string(&result.path).unwrap()).unwrap();
+        assert_eq!(translation(&patch, "Save")["translatedText"], "Salva partita");
+    }
+
+    fn glossary(entries: &[(&str, &str, bool, &[&str])], do_not_translate: &[&str]) -> super::super::glossary::GameGlossary {
+        use super::super::glossary::{GameGlossary, GlossaryEntry, GlossaryMetadata};
+        GameGlossary {
+            id: "gl".to_string(),
+            game_id: "game".to_string(),
+            game_name: "Game".to_string(),
+            source_language: "en".to_string(),
+            target_language: "it".to_string(),
+            entries: entries.iter().enumerate().map(|(i, (original, translation, case_sensitive, variants))| GlossaryEntry {
+                id: i.to_string(),
+                original: original.to_string(),
+                translation: translation.to_string(),
+                case_sensitive: *case_sensitive,
+                context: None,
+                notes: None,
+                variants: variants.iter().map(|v| v.to_string()).collect(),
+                created_at: String::new(),
+                updated_at: String::new(),
+            }).collect(),
+            metadata: GlossaryMetadata {
+                genre: None,
+                tone: None,
+                setting: None,
+                do_not_translate: do_not_translate.iter().map(|t| t.to_string()).collect(),
+                notes: None,
+            },
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn term_regex_respects_word_boundaries_and_case() {
+        let whole = term_regex("Ice", false, true).unwrap();
+        assert!(whole.is_match("Ice storm"));
+        assert!(!whole.is_match("Price list"));
+        assert!(!whole.is_match("ice storm"));
+        assert!(term_regex("Ice", true, true).unwrap().is_match("ice storm"));
+        assert!(term_regex("Ice", false, false).unwrap().is_match("Price list"));
+
+        // Confine solo sul lato "di parola" del termine
+        let cpp = term_regex("C++", false, true).unwrap();
+        assert!(cpp.is_match("scritto in C++."));
+        assert!(!cpp.is_match("ABC++"));
+        assert!(term_regex("  ", false, true).is_none());
+    }
+
+    #[test]
+    fn lint_reports_missing_and_untranslatable_terms() {
+        let patch = serde_json::json!({ "translations": [
+            { "id": "k1", "originalText": "Cast Fireball now", "translatedText": "Lancia Fireball ora" },
+            { "id": "k2", "originalText": "Fireball!", "translatedText": "Palla di fuoco!" },
+            { "id": "k3", "originalText": "a fireball hits", "translatedText": "una sfera colpisce" },
+            { "id": "k4", "originalText": "Welcome to Novigrad", "translatedText": "Benvenuto a Nuova Vigrad" },
+            { "id": "k5", "originalText": "Drink the Potion", "translatedText": "Bevi il Filtro" },
+            { "id": "k6", "originalText": "Fireball", "translatedText": "" }
+        ]});
+        let glossary = glossary(
+            &[("Fireball", "Palla di fuoco", true, &[]), ("Potion", "Pozione", false, &["Filtro"])],
+            &["Novigrad"],
+        );
+
+        let findings = lint_patch_entries(&patch, &glossary, None, true);
+        let summary: Vec<(&str, &str)> = findings.iter().map(|f| (f.key.as_str(), f.rule.as_str())).collect();
+        assert_eq!(summary, vec![("k1", "missing_term"), ("k4", "do_not_translate")]);
+        assert_eq!(findings[0].expected, "Palla di fuoco");
+        assert_eq!(findings[0].suggested_fix.as_deref(), Some("Lancia Palla di fuoco ora"));
+        assert_eq!(findings[1].suggested_fix, None);
+    }
+
+    #[test]
+    fn lint_case_insensitive_override_matches_case_sensitive_entries() {
+        let patch = serde_json::json!({ "translations": [
+            { "id": "k3", "originalText": "a fireball hits", "translatedText": "una sfera colpisce" },
+            { "id": "k7", "originalText": "a fireball hits", "translatedText": "una palla di fuoco colpisce" }
+        ]});
+        let glossary = glossary(&[("Fireball", "Palla di fuoco", true, &[])], &[]);
+
+        assert!(lint_patch_entries(&patch, &glossary, None, true).is_empty());
+
+        let findings = lint_patch_entries(&patch, &glossary, Some(true), true);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].key, "k3");
+        assert_eq!(findings[0].suggested_fix, None);
+    }
+
+    #[test]
+    fn display_width_ignores_markup_and_counts_wide_chars() {
+        assert_eq!(display_width("<color=red>Ciao</color> [b]mondo[/b]", true), 10);
+        assert_eq!(display_width("日本語", true), 6);
+        assert_eq!(display_width("日本語", false), 3);
+        assert_eq!(display_width("ＡＢ", true), 4);
+        assert_eq!(display_width("Ok 한글", true), 7);
+    }
+
+    #[test]
+    fn length_overflows_apply_ratio_threshold_to_long_sources_only() {
+        let patch = serde_json::json!({ "translations": [
+            { "id": "short", "originalText": "OK", "translatedText": "Conferma" },
+            { "id": "within", "originalText": "Music volume", "translatedText": "Volume musica" },
+            { "id": "over", "originalText": "Save game", "translatedText": "Salva la partita" },
+            { "id": "shorter", "originalText": "Options menu", "translatedText": "Opzioni" }
+        ]});
+
+        let overflows = length_overflows(&patch, 1.3, None, true);
+        let keys: Vec<&str> = overflows.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, vec!["over"]);
+        assert_eq!((overflows[0].source_width, overflows[0].target_width, overflows[0].extra_chars), (9, 16, 7));
+
+        // Soglia più alta: "Salva la partita" (1.78) resta sopra, nulla sotto
+        assert_eq!(length_overflows(&patch, 1.8, None, true).len(), 0);
+        assert_eq!(length_overflows(&patch, 1.7, None, true).len(), 1);
+    }
+
+    #[test]
+    fn length_overflows_max_extra_chars_also_covers_short_sources() {
+        let patch = serde_json::json!({ "translations": [
+            { "id": "short", "originalText": "OK", "translatedText": "Conferma" },
+            { "id": "within", "originalText": "Music volume", "translatedText": "Volume musica" }
+        ]});
+
+        let overflows = length_overflows(&patch, 10.0, Some(5), true);
+        let keys: Vec<&str> = overflows.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, vec!["short"]);
+        assert!(length_overflows(&patch, 10.0, Some(6), true).is_empty());
+    }
+
+    #[test]
+    fn length_overflows_count_cjk_as_double_width() {
+        let patch = serde_json::json!({ "translations": [
+            { "id": "ja", "originalText": "Start new game", "translatedText": "新しいゲームを開始します" }
+        ]});
+
+        // 12 caratteri CJK: 24 colonne su 14 ne superano 1.3, 12 no
+        let wide = length_overflows(&patch, 1.3, None, true);
+        assert_eq!(wide.len(), 1);
+        assert_eq!(wide[0].target_width, 24);
+        assert!(length_overflows(&patch, 1.3, None, false).is_empty());
+    }
+
+    #[test]
+    fn parse_autotranslator_handles_escapes_and_comments() {
+        let content = "// commento\nr:\"^Level (\\d+)$\"=Livello $1\nHello\\=World=Ciao\\=Mondo\nLine\\nBreak=Riga\\nA capo\nNoTranslation=\n\nno separator\n";
+        let pairs = parse_community_file(content, CommunityFormat::AutoTranslator);
+        assert_eq!(pairs, vec![
+            ("Hello=World".to_string(), "Ciao=Mondo".to_string()),
+            ("Line\nBreak".to_string(), "Riga\nA capo".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn parse_key_value_skips_comments_and_sections() {
+        let content = "[General]\n# commento\n; altro commento\n menu.start = Inizia \nmenu.help=Riga 1\\nRiga 2\nvuoto=\n";
+        let pairs = parse_community_file(content, CommunityFormat::KeyValue);
+        assert_eq!(pairs, vec![
+            ("menu.start".to_string(), "Inizia".to_string()),
+            ("menu.help".to_string(), "Riga 1\nRiga 2".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn parse_csv_skips_header_and_keeps_quoted_commas() {
+        let content = "source,target\n\"Hello, world\",\"Ciao, mondo\"\n\"Say \"\"hi\"\"\",Di' ciao\nsolo una colonna\n";
+        let pairs = parse_community_file(content, CommunityFormat::Csv);
+        assert_eq!(pairs, vec![
+            ("Hello, world".to_string(), "Ciao, mondo".to_string()),
+            ("Say \"hi\"".to_string(), "Di' ciao".to_string()),
+        ]);
+
+        // Senza intestazione la prima riga è una voce
+        let pairs = parse_community_file("Exit,Esci\n", CommunityFormat::Csv);
+        assert_eq!(pairs, vec![("Exit".to_string(), "Esci".to_string())]);
+    }
+
+    #[test]
+    fn text_sniffing_rejects_binary_assets() {
+        assert!(looks_like_text(b"Hello=Ciao\r\n"));
+        assert!(looks_like_text(b"\xFF\xFEH\0i\0"));
+        assert!(looks_like_text(b""));
+        assert!(!looks_like_text(b"UnityFS\0\0\0\x06"));
+        assert!(!looks_like_text(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"));
+    }
+
+    fn tm_unit(game_id: &str, source: &str, target: &str, verified: bool) -> super::super::translation_memory::TranslationUnit {
+        super::super::translation_memory::TranslationUnit {
+            id: format!("{}:{}", game_id, source),
+            source_text: source.to_string(),
+            target_text: target.to_string(),
+            source_language: "en".to_string(),
+            target_language: "it".to_string(),
+            context: None,
+            game_id: Some(game_id.to_string()),
+            provider: "test".to_string(),
+            confidence: 1.0,
+            verified,
+            usage_count: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+            metadata: None,
+            string_id: None,
+        }
+    }
+
+    fn project(game_id: &str, total: usize, items: &[(&str, &str, bool)]) -> ProjectProgressInput {
+        serde_json::from_value(serde_json::json!({
+            "gameId": game_id,
+            "total": total,
+            "items": items.iter().map(|(source, target, manual)| serde_json::json!({
+                "sourceText": source, "translatedText": target, "isManualEdit": manual, "fromMemory": false
+            })).collect::<Vec<_>>(),
+            "gameName": "Hell Clock"
+        })).unwrap()
+    }
+
+    #[test]
+    fn progress_counts_untouched_project_strings() {
+        let project = project("hell-clock", 10, &[("Start", "Inizia", true), ("Exit", "Esci", false), ("Load", "", false)]);
+        let progress = game_progress("hell-clock", Some(&project), &[], &[]);
+        assert_eq!((progress.total, progress.translated, progress.reviewed), (10, 2, 1));
+        assert_eq!(progress.percent_translated, 20.0);
+        assert_eq!(progress.source, "project");
+
+        // Progetto di un altro gioco: ignorato
+        let other = game_progress("other", Some(&project), &[], &[]);
+        assert_eq!((other.total, other.source.as_str()), (0, "none"));
+        assert_eq!(other.percent_translated, 0.0);
+    }
+
+    #[test]
+    fn progress_merges_project_patches_and_tm() {
+        let project = project("game", 0, &[("Start", "Inizia", false)]);
+        let patches = vec![serde_json::json!({
+            "id": "ocr_game",
+            "gameId": "game",
+            "translations": [
+                { "originalText": "Options", "translatedText": "Opzioni", "reviewed": true },
+                { "originalText": "Credits", "translatedText": "" }
+            ]
+        })];
+        let units = vec![
+            tm_unit("game", "Start", "Avvia", true),
+            tm_unit("game", "Credits", "Riconoscimenti", false),
+            tm_unit("other", "Quit", "Esci", true),
+        ];
+
+        let progress = game_progress("game", Some(&project), &patches, &units);
+        assert_eq!((progress.total, progress.translated, progress.reviewed), (3, 3, 2));
+        assert_eq!(progress.patch_ids, vec!["ocr_game"]);
+
+        let tm_only = game_progress("game", None, &[], &units);
+        assert_eq!((tm_only.total, tm_only.translated, tm_only.reviewed), (2, 2, 1));
+        assert_eq!(tm_only.source, "translation_memory");
+    }
+
+    #[test]
+    fn progress_summary_aggregates_all_games() {
+        let projects = vec![project("a", 4, &[("One", "Uno", false)])];
+        let units = vec![tm_unit("b", "Two", "Due", true)];
+
+        let summary = progress_summary(&projects, &[], &units);
+        let ids: Vec<&str> = summary.games.iter().map(|g| g.game_id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!((summary.total, summary.translated, summary.reviewed), (5, 2, 1));
+        assert_eq!(summary.percent_translated, 40.0);
+    }
+}
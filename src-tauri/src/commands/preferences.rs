@@ -0,0 +1,656 @@
+// ⚙️ Preferenze utente tipizzate
+// Le preferenze globali sono salvate in `GameStringer/preferences.json`; i campi che
+// esistono anche in `ProfileSettings` (lingua, tema, auto refresh, notifiche) vengono
+// sincronizzati con il profilo attivo. Ogni campo ha un default serde, e in lettura un
+// campo malformato viene scartato singolarmente invece di azzerare tutto il file.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::State;
+use crate::commands::profile_settings::ProfileSettingsManagerState;
+use crate::commands::profiles::ProfileManagerState;
+use crate::profiles::models::{LibrarySort, LibraryView, ProfileSettings, Theme};
+
+const PREFERENCES_VERSION: u32 = 1;
+
+/// Tema dell'interfaccia (stringhe minuscole come nel formato legacy)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PreferenceTheme {
+    Light,
+    #[default]
+    Dark,
+    Auto,
+}
+
+impl From<&Theme> for PreferenceTheme {
+    fn from(theme: &Theme) -> Self {
+        match theme {
+            Theme::Light => Self::Light,
+            Theme::Dark => Self::Dark,
+            Theme::Auto => Self::Auto,
+        }
+    }
+}
+
+impl From<PreferenceTheme> for Theme {
+    fn from(theme: PreferenceTheme) -> Self {
+        match theme {
+            PreferenceTheme::Light => Theme::Light,
+            PreferenceTheme::Dark => Theme::Dark,
+            PreferenceTheme::Auto => Theme::Auto,
+        }
+    }
+}
+
+/// Coppia di lingue di default per le traduzioni
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct LanguagePair {
+    pub source_language: String,
+    pub target_language: String,
+}
+
+impl Default for LanguagePair {
+    fn default() -> Self {
+        Self {
+            source_language: "en".to_string(),
+            target_language: "it".to_string(),
+        }
+    }
+}
+
+/// Valori di default proposti all'avvio dell'OCR translator
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct OcrDefaults {
+    pub capture_interval_ms: u64,
+    pub min_confidence: f32,
+}
+
+impl Default for OcrDefaults {
+    fn default() -> Self {
+        Self {
+            capture_interval_ms: 500,
+            min_confidence: 0.5,
+        }
+    }
+}
+
+/// Impostazioni cache
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CachePreferences {
+    pub enabled: bool,
+    pub duration_hours: u32,
+    pub max_entries: u32,
+    pub max_disk_mb: u32,
+}
+
+impl Default for CachePreferences {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            duration_hours: 24,
+            max_entries: 10_000,
+            max_disk_mb: 500,
+        }
+    }
+}
+
+/// Timeout di rete in secondi (`read_timeout_secs` copre l'intera risposta)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NetworkPreferences {
+    pub connect_timeout_secs: u64,
+    pub read_timeout_secs: u64,
+    /// Override per store ("steam") o per endpoint ("steam_owned_games")
+    pub endpoint_overrides: BTreeMap<String, EndpointTimeouts>,
+}
+
+/// Override parziale: i valori assenti vengono presi dallo store o dai default globali
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct EndpointTimeouts {
+    pub connect_timeout_secs: Option<u64>,
+    pub read_timeout_secs: Option<u64>,
+}
+
+impl Default for NetworkPreferences {
+    fn default() -> Self {
+        let cover_head = EndpointTimeouts {
+            connect_timeout_secs: Some(5),
+            read_timeout_secs: Some(10),
+        };
+        let steamgriddb = EndpointTimeouts {
+            connect_timeout_secs: None,
+            read_timeout_secs: Some(15),
+        };
+
+        Self {
+            connect_timeout_secs: 10,
+            read_timeout_secs: 30,
+            endpoint_overrides: BTreeMap::from([
+                ("steam_cover_head".to_string(), cover_head),
+                ("steamgriddb".to_string(), steamgriddb),
+            ]),
+        }
+    }
+}
+
+/// Impostazioni specifiche del profilo attivo (assenti se nessun profilo)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProfilePreferences {
+    pub auto_login: bool,
+    pub session_timeout: u32,
+    pub library_view: LibraryView,
+    pub library_sort: LibrarySort,
+}
+
+/// Preferenze utente
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Preferences {
+    pub version: u32,
+    /// Lingua dell'interfaccia
+    pub language: String,
+    pub theme: PreferenceTheme,
+    pub translation: LanguagePair,
+    /// Uniforma virgolette, trattini e `…` prima dei lookup in TM, glossario e dizionari
+    pub normalize_typography: bool,
+    pub ocr: OcrDefaults,
+    /// Aggiornamento automatico della libreria (ex `auto_scan`)
+    #[serde(alias = "auto_scan")]
+    pub auto_refresh: bool,
+    pub cache: CachePreferences,
+    pub network: NetworkPreferences,
+    pub howlongtobeat_enabled: bool,
+    pub notifications_enabled: bool,
+    pub auto_update_check: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_settings: Option<ProfilePreferences>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            version: PREFERENCES_VERSION,
+            language: "it".to_string(),
+            theme: PreferenceTheme::default(),
+            translation: LanguagePair::default(),
+            normalize_typography: true,
+            ocr: OcrDefaults::default(),
+            auto_refresh: true,
+            cache: CachePreferences::default(),
+            network: NetworkPreferences::default(),
+            howlongtobeat_enabled: true,
+            notifications_enabled: true,
+            auto_update_check: true,
+            profile_settings: None,
+        }
+    }
+}
+
+fn validate_timeout(field: &str, secs: u64) -> Result<(), String> {
+    if (1..=600).contains(&secs) {
+        Ok(())
+    } else {
+        Err(format!("{} deve essere tra 1 e 600 secondi", field))
+    }
+}
+
+fn validate_language_code(field: &str, code: &str) -> Result<(), String> {
+    let valid = (2..=10).contains(&code.len())
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("{}: codice lingua non valido '{}'", field, code))
+    }
+}
+
+/// Converte i campi del formato legacy (`profile_settings_to_legacy_format`) nei nomi correnti:
+/// `auto_scan` -> `auto_refresh`, `cache_enabled`/`cache_duration_hours` -> `cache.*`,
+/// `library_view`/`library_sort` minuscoli o snake_case (`last_played`) -> varianti serde.
+/// Se sono presenti sia il nome legacy sia quello corrente vince quello corrente.
+fn migrate_legacy_fields(fields: &Map<String, Value>) -> Map<String, Value> {
+    let mut fields = fields.clone();
+
+    // Nome legacy: inserirlo accanto al default darebbe "duplicate field"
+    if let Some(auto_scan) = fields.remove("auto_scan") {
+        fields.entry("auto_refresh").or_insert(auto_scan);
+    }
+
+    for (legacy, current) in [("cache_enabled", "enabled"), ("cache_duration_hours", "duration_hours")] {
+        let Some(legacy_value) = fields.remove(legacy) else { continue };
+        let cache = fields.entry("cache").or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(cache) = cache {
+            cache.entry(current).or_insert(legacy_value);
+        }
+    }
+
+    if let Some(Value::Object(profile)) = fields.get_mut("profile_settings") {
+        for key in ["library_view", "library_sort"] {
+            if let Some(Value::String(variant)) = profile.get_mut(key) {
+                if !variant.chars().any(|c| c.is_ascii_uppercase()) {
+                    *variant = legacy_variant_name(variant);
+                }
+            }
+        }
+    }
+
+    fields
+}
+
+/// Merge patch JSON (RFC 7396): gli oggetti vengono fusi ricorsivamente, `null` rimuove
+/// la chiave (che torna al default serde), ogni altro valore sostituisce quello corrente.
+fn merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch_fields) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+    let Value::Object(target_fields) = target else { return };
+
+    for (key, value) in patch_fields {
+        if value.is_null() {
+            target_fields.remove(key);
+        } else {
+            merge_patch(target_fields.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// `last_played` -> `LastPlayed`, `grid` -> `Grid`
+fn legacy_variant_name(name: &str) -> String {
+    name.split(['_', '-'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+impl Preferences {
+    /// Verifica che i valori siano nei range accettati
+    pub fn validate(&self) -> Result<(), String> {
+        validate_language_code("language", &self.language)?;
+        validate_language_code("translation.source_language", &self.translation.source_language)?;
+        validate_language_code("translation.target_language", &self.translation.target_language)?;
+
+        if !(100..=60_000).contains(&self.ocr.capture_interval_ms) {
+            return Err("ocr.capture_interval_ms deve essere tra 100 e 60000".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.ocr.min_confidence) {
+            return Err("ocr.min_confidence deve essere tra 0.0 e 1.0".to_string());
+        }
+        if !(1..=24 * 30).contains(&self.cache.duration_hours) {
+            return Err("cache.duration_hours deve essere tra 1 e 720".to_string());
+        }
+        if !(100..=1_000_000).contains(&self.cache.max_entries) {
+            return Err("cache.max_entries deve essere tra 100 e 1000000".to_string());
+        }
+        if !(10..=100_000).contains(&self.cache.max_disk_mb) {
+            return Err("cache.max_disk_mb deve essere tra 10 e 100000".to_string());
+        }
+
+        validate_timeout("network.connect_timeout_secs", self.network.connect_timeout_secs)?;
+        validate_timeout("network.read_timeout_secs", self.network.read_timeout_secs)?;
+        for (endpoint, timeouts) in &self.network.endpoint_overrides {
+            if let Some(secs) = timeouts.connect_timeout_secs {
+                validate_timeout(&format!("network.endpoint_overrides.{}.connect_timeout_secs", endpoint), secs)?;
+            }
+            if let Some(secs) = timeouts.read_timeout_secs {
+                validate_timeout(&format!("network.endpoint_overrides.{}.read_timeout_secs", endpoint), secs)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Carica le preferenze campo per campo: i campi malformati o fuori range tornano
+    /// al default, gli altri vengono mantenuti. Restituisce anche i campi scartati.
+    pub fn from_value_lenient(value: &Value) -> (Self, Vec<String>) {
+        let mut accepted = match serde_json::to_value(Self::default()) {
+            Ok(Value::Object(map)) => map,
+            _ => Map::new(),
+        };
+        let mut rejected = Vec::new();
+
+        if let Some(fields) = value.as_object() {
+            for (key, field) in migrate_legacy_fields(fields) {
+                let mut candidate = accepted.clone();
+                candidate.insert(key.clone(), field);
+
+                let valid = serde_json::from_value::<Self>(Value::Object(candidate.clone()))
+                    .is_ok_and(|prefs| prefs.validate().is_ok());
+                if valid {
+                    accepted = candidate;
+                } else {
+                    rejected.push(key);
+                }
+            }
+        }
+
+        let preferences = serde_json::from_value(Value::Object(accepted)).unwrap_or_default();
+        (preferences, rejected)
+    }
+
+    /// Applica un aggiornamento parziale (merge patch JSON) e valida il risultato
+    pub fn with_changes(&self, changes: &Value) -> Result<Self, String> {
+        let Some(fields) = changes.as_object() else {
+            return Err("Aggiornamento preferenze non valido: atteso un oggetto JSON".to_string());
+        };
+
+        let mut merged = serde_json::to_value(self)
+            .map_err(|e| format!("Errore serializzazione: {}", e))?;
+        merge_patch(&mut merged, &Value::Object(migrate_legacy_fields(fields)));
+
+        let mut preferences: Self = serde_json::from_value(merged)
+            .map_err(|e| format!("Preferenze non valide: {}", e))?;
+        preferences.validate()?;
+        preferences.version = PREFERENCES_VERSION;
+        Ok(preferences)
+    }
+
+    /// Sovrascrive i campi condivisi con le impostazioni del profilo
+    fn merge_profile_settings(&mut self, settings: &ProfileSettings) {
+        self.language = settings.language.clone();
+        self.theme = PreferenceTheme::from(&settings.theme);
+        self.auto_refresh = settings.game_library.auto_refresh;
+        self.notifications_enabled = settings.notifications.desktop_enabled;
+        self.profile_settings = Some(ProfilePreferences {
+            auto_login: settings.auto_login,
+            session_timeout: settings.security.session_timeout,
+            library_view: settings.game_library.default_view.clone(),
+            library_sort: settings.game_library.default_sort.clone(),
+        });
+    }
+
+    /// Applica i campi condivisi alle impostazioni del profilo
+    fn apply_to_profile_settings(&self, settings: &mut ProfileSettings) {
+        settings.language = self.language.clone();
+        settings.theme = self.theme.into();
+        settings.game_library.auto_refresh = self.auto_refresh;
+        settings.notifications.desktop_enabled = self.notifications_enabled;
+        settings.notifications.sound_enabled = self.notifications_enabled;
+
+        if let Some(profile) = &self.profile_settings {
+            settings.auto_login = profile.auto_login;
+            settings.security.session_timeout = profile.session_timeout;
+            settings.game_library.default_view = profile.library_view.clone();
+            settings.game_library.default_sort = profile.library_sort.clone();
+        }
+    }
+}
+
+/// Percorso del file preferenze globali
+fn preferences_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("GameStringer")
+        .join("preferences.json")
+}
+
+/// Legge le preferenze da disco (default se il file manca o non è JSON valido)
+fn load_preferences_file() -> Preferences {
+    let path = preferences_path();
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Preferences::default();
+    };
+
+    match serde_json::from_str::<Value>(&content) {
+        Ok(value) => {
+            let (preferences, rejected) = Preferences::from_value_lenient(&value);
+            if !rejected.is_empty() {
+                log::warn!("⚠️ Campi preferenze non validi ripristinati al default: {}", rejected.join(", "));
+            }
+            preferences
+        }
+        Err(e) => {
+            log::warn!("⚠️ File preferenze non valido ({:?}): {}", path, e);
+            Preferences::default()
+        }
+    }
+}
+
+/// Timeout di rete correnti (usati da `crate::http` per costruire i client)
+pub fn load_network_preferences() -> NetworkPreferences {
+    load_preferences_file().network
+}
+
+/// Preferenza di normalizzazione tipografica (usata da `crate::string_id` all'avvio)
+pub fn load_typography_preference() -> bool {
+    load_preferences_file().normalize_typography
+}
+
+fn save_preferences_file(preferences: &Preferences) -> Result<(), String> {
+    let path = preferences_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Errore creazione directory: {}", e))?;
+    }
+
+    // Le impostazioni del profilo vivono nei settings del profilo, non nel file globale
+    let mut stored = preferences.clone();
+    stored.profile_settings = None;
+
+    let json = serde_json::to_string_pretty(&stored)
+        .map_err(|e| format!("Errore serializzazione: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Errore scrittura: {}", e))
+}
+
+/// Salva le preferenze su disco e le sincronizza con il profilo attivo (se presente)
+async fn persist_preferences(
+    preferences: &Preferences,
+    profile_manager_state: &ProfileManagerState,
+    settings_state: &ProfileSettingsManagerState,
+) -> Result<(), String> {
+    save_preferences_file(preferences)?;
+    crate::http::apply_network_preferences(&preferences.network);
+    crate::string_id::set_typography_normalization(preferences.normalize_typography);
+
+    let profile_manager = profile_manager_state.manager.lock().await;
+    let Some(profile) = profile_manager.current_profile() else {
+        log::info!("📝 Preferenze salvate (nessun profilo attivo)");
+        return Ok(());
+    };
+
+    let settings_manager = settings_state.manager.lock().await;
+    let mut profile_settings = settings_manager.load_profile_settings(&profile.id).await
+        .unwrap_or_default();
+    preferences.apply_to_profile_settings(&mut profile_settings);
+
+    settings_manager.save_profile_settings(&profile.id, &profile_settings).await
+        .map_err(|e| format!("Errore salvataggio settings: {}", e))?;
+
+    log::info!("✅ Preferenze salvate nel profilo: {}", profile.name);
+    Ok(())
+}
+
+/// Preferenze correnti: file globale più le impostazioni del profilo attivo
+async fn current_preferences(
+    profile_manager_state: &ProfileManagerState,
+    settings_state: &ProfileSettingsManagerState,
+) -> Preferences {
+    let mut preferences = load_preferences_file();
+
+    let profile_manager = profile_manager_state.manager.lock().await;
+    if let Some(profile) = profile_manager.current_profile() {
+        let settings_manager = settings_state.manager.lock().await;
+        match settings_manager.load_profile_settings(&profile.id).await {
+            Ok(profile_settings) => {
+                preferences.merge_profile_settings(&profile_settings);
+                log::info!("✅ Preferenze caricate dal profilo: {}", profile.name);
+            }
+            Err(e) => log::warn!("⚠️ Errore caricamento settings profilo: {}", e),
+        }
+    }
+
+    preferences
+}
+
+#[tauri::command]
+pub async fn get_preferences(
+    profile_manager_state: State<'_, ProfileManagerState>,
+    settings_state: State<'_, ProfileSettingsManagerState>,
+) -> Result<Preferences, String> {
+    log::info!("⚙️ Recupero preferenze utente");
+    Ok(current_preferences(&profile_manager_state, &settings_state).await)
+}
+
+/// Aggiornamento parziale: `changes` contiene solo i campi da modificare (anche annidati,
+/// es. `{"cache": {"max_entries": 5000}}`); `null` riporta un campo al default.
+#[tauri::command]
+pub async fn update_preferences(
+    changes: Value,
+    profile_manager_state: State<'_, ProfileManagerState>,
+    settings_state: State<'_, ProfileSettingsManagerState>,
+) -> Result<Preferences, String> {
+    log::info!("💾 Aggiornamento preferenze utente");
+
+    let current = current_preferences(&profile_manager_state, &settings_state).await;
+    let preferences = current.with_changes(&changes)?;
+
+    persist_preferences(&preferences, &profile_manager_state, &settings_state).await?;
+    Ok(preferences)
+}
+
+#[tauri::command]
+pub async fn reset_preferences(
+    profile_manager_state: State<'_, ProfileManagerState>,
+    settings_state: State<'_, ProfileSettingsManagerState>,
+) -> Result<Preferences, String> {
+    log::info!("🔄 Ripristino preferenze ai valori di default");
+
+    let preferences = Preferences::default();
+    persist_preferences(&preferences, &profile_manager_state, &settings_state).await?;
+    Ok(preferences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn lenient_load_migrates_legacy_format() {
+        let legacy = json!({
+            "language": "en",
+            "theme": "light",
+            "auto_scan": false,
+            "cache_enabled": false,
+            "cache_duration_hours": 48,
+            "steam_api_key": "",
+            "profile_settings": {
+                "auto_login": true,
+                "session_timeout": 15,
+                "library_view": "list",
+                "library_sort": "last_played",
+                "show_hidden_games": false
+            }
+        });
+
+        let (preferences, rejected) = Preferences::from_value_lenient(&legacy);
+        assert!(rejected.is_empty(), "scartati: {:?}", rejected);
+        assert_eq!(preferences.language, "en");
+        assert_eq!(preferences.theme, PreferenceTheme::Light);
+        assert!(!preferences.auto_refresh);
+        assert!(!preferences.cache.enabled);
+        assert_eq!(preferences.cache.duration_hours, 48);
+        assert_eq!(preferences.cache.max_entries, CachePreferences::default().max_entries);
+
+        let profile = preferences.profile_settings.expect("profile_settings migrati");
+        assert!(profile.auto_login);
+        assert_eq!(profile.session_timeout, 15);
+        assert_eq!(profile.library_view, LibraryView::List);
+        assert_eq!(profile.library_sort, LibrarySort::LastPlayed);
+    }
+
+    #[test]
+    fn lenient_load_prefers_current_names_over_legacy() {
+        let mixed = json!({
+            "auto_scan": false,
+            "auto_refresh": true,
+            "cache_duration_hours": 48,
+            "cache": { "duration_hours": 12 }
+        });
+
+        let (preferences, rejected) = Preferences::from_value_lenient(&mixed);
+        assert!(rejected.is_empty());
+        assert!(preferences.auto_refresh);
+        assert_eq!(preferences.cache.duration_hours, 12);
+    }
+
+    #[test]
+    fn lenient_load_keeps_valid_fields_of_partially_malformed_file() {
+        let malformed = json!({
+            "language": "fr",
+            "theme": "purple",
+            "cache_duration_hours": 0,
+            "ocr": { "capture_interval_ms": 1000, "min_confidence": 3.0 },
+            "network": "fast",
+            "notifications_enabled": false
+        });
+
+        let (preferences, mut rejected) = Preferences::from_value_lenient(&malformed);
+        rejected.sort();
+        assert_eq!(rejected, vec!["cache", "network", "ocr", "theme"]);
+        assert_eq!(preferences.language, "fr");
+        assert!(!preferences.notifications_enabled);
+        assert_eq!(preferences.theme, PreferenceTheme::default());
+        assert_eq!(preferences.cache, CachePreferences::default());
+        assert_eq!(preferences.ocr, OcrDefaults::default());
+        assert_eq!(preferences.network, NetworkPreferences::default());
+    }
+
+    #[test]
+    fn partial_update_keeps_untouched_fields() {
+        let mut current = Preferences {
+            language: "en".to_string(),
+            ..Preferences::default()
+        };
+        current.cache.max_entries = 2_000;
+
+        let updated = current.with_changes(&json!({
+            "theme": "light",
+            "cache": { "max_disk_mb": 50 },
+            "network": { "endpoint_overrides": { "steam": { "read_timeout_secs": 20 } } }
+        })).unwrap();
+
+        assert_eq!(updated.theme, PreferenceTheme::Light);
+        assert_eq!(updated.language, "en");
+        assert_eq!(updated.cache.max_disk_mb, 50);
+        assert_eq!(updated.cache.max_entries, 2_000);
+        assert!(updated.network.endpoint_overrides.contains_key("steamgriddb"));
+        assert_eq!(updated.network.endpoint_overrides["steam"].read_timeout_secs, Some(20));
+    }
+
+    #[test]
+    fn partial_update_null_resets_and_invalid_values_are_rejected() {
+        let mut current = Preferences::default();
+        current.ocr.capture_interval_ms = 2_000;
+        current.auto_refresh = false;
+
+        let updated = current.with_changes(&json!({ "ocr": null, "auto_scan": true })).unwrap();
+        assert_eq!(updated.ocr, OcrDefaults::default());
+        assert!(updated.auto_refresh);
+
+        assert!(current.with_changes(&json!({ "cache": { "duration_hours": 0 } })).is_err());
+        assert!(current.with_changes(&json!({ "theme": "purple" })).is_err());
+        assert!(current.with_changes(&json!("dark")).is_err());
+    }
+
+    #[test]
+    fn legacy_variant_names_are_converted() {
+        assert_eq!(legacy_variant_name("recently_added"), "RecentlyAdded");
+        assert_eq!(legacy_variant_name("grid"), "Grid");
+        assert_eq!(legacy_variant_name("alphabetical"), "Alphabetical");
+    }
+}
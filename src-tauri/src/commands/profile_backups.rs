@@ -0,0 +1,159 @@
+use crate::profiles::manager::ProfileManager;
+use crate::profiles::models::ProfileSettings;
+use crate::profiles::settings_manager::ProfileSettingsManager;
+use crate::profiles::storage::{BackupSchedule, ScheduledBackupInfo};
+use super::notifications::NotificationManagerState;
+use super::profile_settings::ProfileSettingsManagerState;
+use super::profiles::{ProfileManagerState, ProfileResponse};
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tauri::{command, AppHandle, Emitter, Manager, State};
+
+/// Evento emesso dopo ogni backup pianificato (riuscito o fallito)
+pub const PROFILE_BACKUP_EVENT: &str = "profile-backup-completed";
+
+/// Ogni quanto lo scheduler controlla se è ora di un backup
+const BACKUP_SCHEDULER_INTERVAL_SECS: u64 = 60;
+
+/// Payload dell'evento di backup pianificato
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileBackupEvent {
+    pub profile_id: String,
+    pub success: bool,
+    pub backup: Option<ScheduledBackupInfo>,
+    pub error: Option<String>,
+}
+
+/// Impostazioni del profilo serializzate per il backup (None se mai salvate o illeggibili)
+async fn settings_snapshot(settings: &Arc<Mutex<ProfileSettingsManager>>, profile_id: &str) -> Option<String> {
+    let settings = settings.lock().await.load_profile_settings(profile_id).await.ok()?;
+    serde_json::to_string_pretty(&settings).ok()
+}
+
+/// Esegue un backup pianificato del profilo attivo se l'intervallo è trascorso
+async fn run_scheduled_backup(app: &AppHandle, manager: &Arc<Mutex<ProfileManager>>, settings: &Arc<Mutex<ProfileSettingsManager>>) {
+    let (profile_id, schedule, last_backup) = {
+        let manager = manager.lock().await;
+        let Some(profile_id) = manager.current_profile_id().map(str::to_string) else { return };
+        let Ok(schedule) = manager.get_backup_schedule().await else { return };
+        if !schedule.enabled {
+            return;
+        }
+        let last_backup = manager.list_scheduled_backups(Some(&profile_id)).await
+            .ok()
+            .and_then(|backups| backups.first().map(|b| b.created_at));
+        (profile_id, schedule, last_backup)
+    };
+
+    let interval = chrono::Duration::hours(schedule.interval_hours as i64);
+    if last_backup.is_some_and(|last| chrono::Utc::now().signed_duration_since(last) < interval) {
+        return;
+    }
+
+    let settings_json = settings_snapshot(settings, &profile_id).await;
+    let result = manager.lock().await.create_scheduled_backup(&profile_id, settings_json.as_deref()).await;
+
+    let notifications = app.state::<NotificationManagerState>();
+    let event = match result {
+        Ok(info) => {
+            println!("[PROFILES] ✅ Backup pianificato creato: {}", info.id);
+            notifications.notify_backup(&profile_id, &info.id, true).await;
+            ProfileBackupEvent { profile_id, success: true, backup: Some(info), error: None }
+        }
+        Err(e) => {
+            println!("[PROFILES] ⚠️ Backup pianificato fallito: {}", e);
+            notifications.notify_backup(&profile_id, "", false).await;
+            ProfileBackupEvent { profile_id, success: false, backup: None, error: Some(e.to_string()) }
+        }
+    };
+    if let Err(e) = app.emit(PROFILE_BACKUP_EVENT, &event) {
+        println!("[PROFILES] ⚠️ Errore emissione evento backup: {}", e);
+    }
+}
+
+/// Avvia lo scheduler dei backup automatici del profilo attivo
+pub fn start_profile_backup_scheduler(app: AppHandle) {
+    let manager = app.state::<ProfileManagerState>().manager.clone();
+    let settings = app.state::<ProfileSettingsManagerState>().manager.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(BACKUP_SCHEDULER_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            run_scheduled_backup(&app, &manager, &settings).await;
+        }
+    });
+}
+
+/// Comando: Ottieni la pianificazione dei backup automatici
+#[command]
+pub async fn get_profile_backup_schedule(
+    profile_state: State<'_, ProfileManagerState>,
+) -> Result<ProfileResponse<BackupSchedule>, String> {
+    let manager = profile_state.manager.lock().await;
+
+    match manager.get_backup_schedule().await {
+        Ok(schedule) => Ok(ProfileResponse::success(schedule)),
+        Err(err) => Ok(ProfileResponse::error(err.to_string())),
+    }
+}
+
+/// Comando: Aggiorna la pianificazione dei backup automatici
+#[command]
+pub async fn set_profile_backup_schedule(
+    profile_state: State<'_, ProfileManagerState>,
+    schedule: BackupSchedule,
+) -> Result<ProfileResponse<BackupSchedule>, String> {
+    let manager = profile_state.manager.lock().await;
+
+    match manager.set_backup_schedule(schedule).await {
+        Ok(schedule) => Ok(ProfileResponse::success(schedule)),
+        Err(err) => Ok(ProfileResponse::error(err.to_string())),
+    }
+}
+
+/// Comando: Elenca i backup pianificati (del profilo attivo se non indicato)
+#[command]
+pub async fn list_profile_backups(
+    profile_state: State<'_, ProfileManagerState>,
+    profile_id: Option<String>,
+) -> Result<ProfileResponse<Vec<ScheduledBackupInfo>>, String> {
+    let manager = profile_state.manager.lock().await;
+
+    match manager.list_scheduled_backups(profile_id.as_deref()).await {
+        Ok(backups) => Ok(ProfileResponse::success(backups)),
+        Err(err) => Ok(ProfileResponse::error(err.to_string())),
+    }
+}
+
+/// Comando: Ripristina un backup pianificato (profilo cifrato, credenziali e impostazioni).
+/// `password` è quella del profilo al momento del backup.
+#[command]
+pub async fn restore_profile_backup(
+    profile_state: State<'_, ProfileManagerState>,
+    settings_state: State<'_, ProfileSettingsManagerState>,
+    backup_id: String,
+    password: String,
+) -> Result<ProfileResponse<ScheduledBackupInfo>, String> {
+    let mut manager = profile_state.manager.lock().await;
+
+    let (info, settings_json) = match manager.restore_scheduled_backup(&backup_id, &password).await {
+        Ok(restored) => restored,
+        Err(err) => return Ok(ProfileResponse::error(err.to_string())),
+    };
+    drop(manager);
+
+    if let Some(json) = settings_json {
+        match serde_json::from_str::<ProfileSettings>(&json) {
+            Ok(settings) => {
+                if let Err(e) = settings_state.manager.lock().await.save_profile_settings(&info.profile_id, &settings).await {
+                    return Ok(ProfileResponse::error(format!("Profilo ripristinato, impostazioni non ripristinate: {}", e)));
+                }
+            }
+            Err(e) => println!("[PROFILES] ⚠️ Impostazioni del backup non leggibili: {}", e),
+        }
+    }
+
+    Ok(ProfileResponse::success(info))
+}
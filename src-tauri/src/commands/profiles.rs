@@ -1,10 +1,13 @@
-use crate::profiles::manager::ProfileManager;
+use crate::profiles::manager::{ProfileManager, SessionStatus};
 use crate::profiles::models::{CreateProfileRequest, ProfileInfo, ProfileSettings, UserProfile, ProfilesSystemStats, ProfilesHealthCheck, ProfilesSystemConfig, ProfileUsageStats};
 use crate::profiles::errors::ProfileError;
+use crate::profiles::rate_limiter::LockoutStatus;
+use crate::profiles::cleanup::OrphanCleanupReport;
+use super::notifications::NotificationManagerState;
 use serde::Serialize;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tauri::{command, State};
+use tauri::{command, AppHandle, Emitter, State};
 // PathBuf rimosso - non utilizzato
 
 /// Stato globale per il ProfileManager
@@ -46,6 +49,23 @@ fn profile_error_to_string(err: ProfileError) -> String {
     format!("{}", err)
 }
 
+/// Crea una notifica di sicurezza se l'accesso è fallito per credenziali errate o blocco
+async fn notify_auth_failure(
+    notification_state: &NotificationManagerState,
+    profile_id: &str,
+    err: &ProfileError,
+) {
+    match err {
+        ProfileError::TooManyAttempts(remaining_seconds) => {
+            notification_state.notify_profile_locked(profile_id, *remaining_seconds).await;
+        }
+        ProfileError::InvalidCredentials | ProfileError::InvalidPassword => {
+            notification_state.notify_authentication_error(profile_id, &err.to_string()).await;
+        }
+        _ => {}
+    }
+}
+
 /// Comando: Lista profili disponibili
 #[command]
 pub async fn list_profiles(
@@ -77,14 +97,18 @@ pub async fn create_profile(
 #[command]
 pub async fn authenticate_profile(
     profile_state: State<'_, ProfileManagerState>,
-    name: String,
+    notification_state: State<'_, NotificationManagerState>,
+    profile_id: String,
     password: String,
 ) -> Result<ProfileResponse<UserProfile>, String> {
     let mut manager = profile_state.manager.lock().await;
     
-    match manager.authenticate_profile(&name, &password).await {
+    match manager.authenticate_profile(&profile_id, &password).await {
         Ok(profile) => Ok(ProfileResponse::success(profile)),
-        Err(err) => Ok(ProfileResponse::error(profile_error_to_string(err))),
+        Err(err) => {
+            notify_auth_failure(&notification_state, &profile_id, &err).await;
+            Ok(ProfileResponse::error(profile_error_to_string(err)))
+        }
     }
 }
 
@@ -92,14 +116,18 @@ pub async fn authenticate_profile(
 #[command]
 pub async fn switch_profile(
     profile_state: State<'_, ProfileManagerState>,
-    name: String,
+    notification_state: State<'_, NotificationManagerState>,
+    profile_id: String,
     password: String,
 ) -> Result<ProfileResponse<UserProfile>, String> {
     let mut manager = profile_state.manager.lock().await;
     
-    match manager.switch_profile(&name, &password).await {
+    match manager.switch_profile(&profile_id, &password).await {
         Ok(profile) => Ok(ProfileResponse::success(profile)),
-        Err(err) => Ok(ProfileResponse::error(profile_error_to_string(err))),
+        Err(err) => {
+            notify_auth_failure(&notification_state, &profile_id, &err).await;
+            Ok(ProfileResponse::error(profile_error_to_string(err)))
+        }
     }
 }
 
@@ -164,13 +192,12 @@ pub async fn export_profile(
     profile_id: String,
     password: String,
     export_path: String,
-    export_password: Option<String>,
-) -> Result<ProfileResponse<String>, String> {
+    passphrase: Option<String>,
+) -> Result<ProfileResponse<crate::profiles::manager::ExportSummary>, String> {
     let manager = profile_state.manager.lock().await;
-    let export_pwd = export_password.as_deref();
     
-    match manager.export_profile_to_file(&profile_id, &password, &export_path, export_pwd).await {
-        Ok(_) => Ok(ProfileResponse::success(export_path)),
+    match manager.export_profile_to_file(&profile_id, &password, &export_path, passphrase.as_deref()).await {
+        Ok(summary) => Ok(ProfileResponse::success(summary)),
         Err(err) => Ok(ProfileResponse::error(profile_error_to_string(err))),
     }
 }
@@ -196,11 +223,12 @@ pub async fn import_profile(
 pub async fn validate_export_file(
     profile_state: State<'_, ProfileManagerState>,
     file_path: String,
-) -> Result<ProfileResponse<crate::profiles::manager::ExportMetadata>, String> {
+    passphrase: Option<String>,
+) -> Result<ProfileResponse<crate::profiles::manager::ExportValidation>, String> {
     let manager = profile_state.manager.lock().await;
     
-    match manager.validate_export_file(&file_path).await {
-        Ok(metadata) => Ok(ProfileResponse::success(metadata)),
+    match manager.validate_export_file(&file_path, passphrase.as_deref()).await {
+        Ok(validation) => Ok(ProfileResponse::success(validation)),
         Err(err) => Ok(ProfileResponse::error(profile_error_to_string(err))),
     }
 }
@@ -209,14 +237,24 @@ pub async fn validate_export_file(
 #[command]
 pub async fn create_profile_backup(
     profile_state: State<'_, ProfileManagerState>,
+    notification_state: State<'_, NotificationManagerState>,
     profile_id: String,
     password: String,
 ) -> Result<ProfileResponse<String>, String> {
     let manager = profile_state.manager.lock().await;
     
     match manager.create_profile_backup(&profile_id, &password).await {
-        Ok(backup_path) => Ok(ProfileResponse::success(backup_path)),
-        Err(err) => Ok(ProfileResponse::error(profile_error_to_string(err))),
+        Ok(backup_path) => {
+            notification_state.notify_backup(&profile_id, &backup_path, true).await;
+            Ok(ProfileResponse::success(backup_path))
+        }
+        Err(err) => {
+            // Profilo inesistente: nessun destinatario per la notifica
+            if !matches!(err, ProfileError::ProfileNotFound(_)) {
+                notification_state.notify_backup(&profile_id, "", false).await;
+            }
+            Ok(ProfileResponse::error(profile_error_to_string(err)))
+        }
     }
 }
 
@@ -268,6 +306,94 @@ pub async fn get_session_time_remaining(
     Ok(ProfileResponse::success(remaining))
 }
 
+/// Evento emesso quando la sessione sta per scadere
+pub const SESSION_EXPIRING_EVENT: &str = "profile-session-expiring";
+/// Evento emesso quando la sessione è scaduta e il profilo è stato disconnesso
+pub const SESSION_EXPIRED_EVENT: &str = "profile-session-expired";
+
+/// Intervallo di controllo del monitor sessione
+const SESSION_MONITOR_INTERVAL_SECS: u64 = 15;
+
+/// Comando: Pulizia dati di profili eliminati (settings, file profilo, avatar).
+/// Dry-run di default: con `confirm = true` sposta i file in quarantena.
+#[command]
+pub async fn cleanup_orphaned_profile_data(
+    profile_state: State<'_, ProfileManagerState>,
+    settings_state: State<'_, crate::commands::profile_settings::ProfileSettingsManagerState>,
+    confirm: Option<bool>,
+) -> Result<ProfileResponse<OrphanCleanupReport>, String> {
+    let manager = profile_state.manager.lock().await;
+    let settings_manager = settings_state.manager.lock().await;
+    let dry_run = !confirm.unwrap_or(false);
+
+    match crate::profiles::cleanup::cleanup_orphaned_profile_data(&manager, &settings_manager, dry_run).await {
+        Ok(report) => Ok(ProfileResponse::success(report)),
+        Err(err) => Ok(ProfileResponse::error(profile_error_to_string(err))),
+    }
+}
+
+/// Comando: Ottieni stato sessione (idle timeout e durata massima dal profilo)
+#[command]
+pub async fn get_session_status(
+    profile_state: State<'_, ProfileManagerState>,
+) -> Result<ProfileResponse<SessionStatus>, String> {
+    let manager = profile_state.manager.lock().await;
+    Ok(ProfileResponse::success(manager.get_session_status()))
+}
+
+/// Comando: Registra attività utente e fa scorrere la scadenza per inattività
+#[command]
+pub async fn touch_session(
+    profile_state: State<'_, ProfileManagerState>,
+) -> Result<ProfileResponse<SessionStatus>, String> {
+    let mut manager = profile_state.manager.lock().await;
+
+    match manager.touch_session() {
+        Ok(status) => Ok(ProfileResponse::success(status)),
+        Err(err) => Ok(ProfileResponse::error(profile_error_to_string(err))),
+    }
+}
+
+/// Avvia il monitor che notifica il frontend prima della scadenza sessione
+/// e disconnette il profilo quando la sessione scade
+pub fn start_session_monitor(app: AppHandle, manager: Arc<Mutex<ProfileManager>>) {
+    tauri::async_runtime::spawn(async move {
+        let mut warned_for: Option<chrono::DateTime<chrono::Utc>> = None;
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(SESSION_MONITOR_INTERVAL_SECS));
+
+        loop {
+            interval.tick().await;
+
+            let mut manager = manager.lock().await;
+            match manager.expire_session_if_needed() {
+                Ok(Some(status)) => {
+                    warned_for = None;
+                    if let Err(e) = app.emit(SESSION_EXPIRED_EVENT, &status) {
+                        println!("[PROFILES] ⚠️ Errore emissione evento scadenza sessione: {}", e);
+                    }
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    println!("[PROFILES] ⚠️ Errore controllo scadenza sessione: {}", e);
+                    continue;
+                }
+            }
+
+            let status = manager.get_session_status();
+            drop(manager);
+
+            // Un solo avviso per ogni scadenza: touch_session sposta expires_at e riarma l'avviso
+            if status.near_expiry && warned_for != status.expires_at {
+                warned_for = status.expires_at;
+                if let Err(e) = app.emit(SESSION_EXPIRING_EVENT, &status) {
+                    println!("[PROFILES] ⚠️ Errore emissione evento sessione in scadenza: {}", e);
+                }
+            }
+        }
+    });
+}
+
 /// Comando: Verifica se profilo può essere autenticato
 #[command]
 pub async fn can_authenticate(
@@ -310,6 +436,34 @@ pub async fn get_failed_attempts(
     }
 }
 
+/// Comando: Ottieni stato di blocco (tempo di attesa rimanente)
+#[command]
+pub async fn get_lockout_status(
+    profile_state: State<'_, ProfileManagerState>,
+    name: String,
+) -> Result<ProfileResponse<LockoutStatus>, String> {
+    let manager = profile_state.manager.lock().await;
+
+    match manager.get_lockout_status(&name).await {
+        Ok(status) => Ok(ProfileResponse::success(status)),
+        Err(err) => Ok(ProfileResponse::error(profile_error_to_string(err))),
+    }
+}
+
+/// Comando: Azzera tentativi falliti (non rimuove un blocco attivo)
+#[command]
+pub async fn reset_failed_attempts(
+    profile_state: State<'_, ProfileManagerState>,
+    name: String,
+) -> Result<ProfileResponse<bool>, String> {
+    let mut manager = profile_state.manager.lock().await;
+
+    match manager.reset_failed_attempts(&name).await {
+        Ok(_) => Ok(ProfileResponse::success(true)),
+        Err(err) => Ok(ProfileResponse::error(profile_error_to_string(err))),
+    }
+}
+
 /// API pubblica per accesso informazioni profilo dettagliate
 #[allow(dead_code)]
 #[command(rename_all = "camelCase")]
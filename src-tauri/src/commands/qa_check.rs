@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use regex::Regex;
+use once_cell::sync::Lazy;
 
 /// 🔍 QA Check System - Verifica qualità traduzioni
 /// 
@@ -281,52 +282,174 @@ fn check_tags(source: &str, target: &str, id: &str) -> Vec<QAIssue> {
     issues
 }
 
-/// Verifica placeholder
+/// Verifica placeholder (stessa estrazione della validazione delle patch)
 fn check_placeholders(source: &str, target: &str, id: &str) -> Vec<QAIssue> {
     let mut issues = Vec::new();
+    let formats = PlaceholderFormats::default();
+    
+    // Conta placeholder nel source, nell'ordine in cui compaiono
+    let mut source_counts: Vec<(String, usize)> = Vec::new();
+    for (_, ph) in extract_placeholders(source, &formats) {
+        match source_counts.iter_mut().find(|(p, _)| *p == ph) {
+            Some((_, count)) => *count += 1,
+            None => source_counts.push((ph, 1)),
+        }
+    }
+    let target_placeholders = extract_placeholders(target, &formats);
     
-    // Pattern per vari tipi di placeholder
-    let patterns = [
-        r"\{[0-9]+\}",           // {0}, {1}, etc.
-        r"\{\w+\}",              // {name}, {value}, etc.
-        r"%[sdifoxX]",           // %s, %d, etc. (printf style)
-        r"%\d+\$[sdifoxX]",      // %1$s, %2$d, etc.
-        r"\$\{[^}]+\}",          // ${variable}
-        r"\[\[[^\]]+\]\]",       // [[placeholder]]
-        r"@@[^@]+@@",            // @@placeholder@@
-    ];
+    // Verifica placeholder mancanti
+    for (ph, count) in &source_counts {
+        let target_count = target_placeholders.iter().filter(|(_, t)| t == ph).count();
+        if target_count < *count {
+            issues.push(QAIssue {
+                id: format!("{}_ph_missing", id),
+                issue_type: QAIssueType::MissingPlaceholder,
+                severity: QASeverity::Error,
+                message: format!("Placeholder mancante: {}", ph),
+                source_text: source.to_string(),
+                target_text: target.to_string(),
+                position: None,
+                suggestion: Some(format!("Aggiungi {} alla traduzione", ph)),
+            });
+        }
+    }
     
-    for pattern in &patterns {
-        let regex = Regex::new(pattern).unwrap();
-        
-        let source_matches: Vec<&str> = regex.find_iter(source).map(|m| m.as_str()).collect();
-        let target_matches: Vec<&str> = regex.find_iter(target).map(|m| m.as_str()).collect();
-        
-        // Conta placeholder nel source
-        let mut source_counts: HashMap<&str, i32> = HashMap::new();
-        for ph in &source_matches {
-            *source_counts.entry(*ph).or_insert(0) += 1;
+    issues
+}
+
+/// Stile di placeholder riconosciuto nelle stringhe di gioco
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaceholderStyle {
+    /// printf: %s, %d, %5.2f, %1$s
+    CStyle,
+    /// .NET String.Format: {0}, {1:N2}, {0,-10}
+    Dotnet,
+    /// Interpolazione con nome: {playerName}, {count:D2}
+    Named,
+    /// Template: ${variable}, [[placeholder]], @@placeholder@@
+    Template,
+}
+
+/// Formati di placeholder da verificare (tutti attivi di default)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaceholderFormats {
+    #[serde(default = "default_true")]
+    pub c_style: bool,
+    #[serde(default = "default_true")]
+    pub dotnet: bool,
+    #[serde(default = "default_true")]
+    pub named: bool,
+    #[serde(default = "default_true")]
+    pub template: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for PlaceholderFormats {
+    fn default() -> Self {
+        Self { c_style: true, dotnet: true, named: true, template: true }
+    }
+}
+
+impl PlaceholderFormats {
+    fn allows(&self, style: PlaceholderStyle) -> bool {
+        match style {
+            PlaceholderStyle::CStyle => self.c_style,
+            PlaceholderStyle::Dotnet => self.dotnet,
+            PlaceholderStyle::Named => self.named,
+            PlaceholderStyle::Template => self.template,
         }
-        
-        // Verifica placeholder mancanti
-        for (ph, count) in &source_counts {
-            let target_count = target_matches.iter().filter(|&&t| t == *ph).count() as i32;
-            if target_count < *count {
-                issues.push(QAIssue {
-                    id: format!("{}_ph_missing", id),
-                    issue_type: QAIssueType::MissingPlaceholder,
-                    severity: QASeverity::Error,
-                    message: format!("Placeholder mancante: {}", ph),
-                    source_text: source.to_string(),
-                    target_text: target.to_string(),
-                    position: None,
-                    suggestion: Some(format!("Aggiungi {} alla traduzione", ph)),
-                });
+    }
+}
+
+/// %% è un letterale; flag, larghezza, precisione e modificatori di lunghezza fanno parte del placeholder.
+/// Il flag spazio è escluso: "50% di sconto" non è un "% d"
+static C_PLACEHOLDER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"%%|%(?:\d+\$)?[-+0#]*(?:\d+|\*)?(?:\.(?:\d+|\*))?(?:hh|h|ll|l|L|z|j|t)?[diouxXeEfFgGaAcspn@]").unwrap()
+});
+/// {{ e }} sono graffe letterali; {indice[,allineamento][:formato]} o {nome[:formato]}
+static BRACE_PLACEHOLDER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{|\}\}|\{(?:(\d+)(?:,\s*-?\d+)?|([A-Za-z_][A-Za-z0-9_.]*))(?::[^{}]*)?\}").unwrap()
+});
+/// ${variabile}, [[placeholder]], @@placeholder@@ (hanno la precedenza sulle graffe)
+static TEMPLATE_PLACEHOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"\$\{[^}]+\}|\[\[[^\]]+\]\]|@@[^@]+@@").unwrap());
+/// Resti di placeholder rovinati dalla traduzione: "%1$", "{ 0 }", "{0", "{player name}"
+static MALFORMED_C: Lazy<Regex> = Lazy::new(|| Regex::new(r"%\d+\$(?:[^diouxXeEfFgGaAcspn@]|$)").unwrap());
+static MALFORMED_BRACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{[^{}\n]{0,40}\}?|\}").unwrap());
+
+/// "50%off", "30%dmg": un % subito dopo una cifra e seguito da altre lettere è una
+/// percentuale, non un placeholder
+fn is_percentage(text: &str, start: usize, end: usize) -> bool {
+    text[..start].ends_with(|c: char| c.is_ascii_digit())
+        && text[end..].starts_with(|c: char| c.is_alphabetic())
+}
+
+/// Placeholder estratti da una stringa, nell'ordine in cui compaiono.
+/// Unica fonte per il QA check e per la validazione delle patch
+pub(crate) fn extract_placeholders(text: &str, formats: &PlaceholderFormats) -> Vec<(PlaceholderStyle, String)> {
+    let mut found = Vec::new();
+    let templates: Vec<(usize, usize)> = TEMPLATE_PLACEHOLDER.find_iter(text).map(|m| (m.start(), m.end())).collect();
+    let inside_template = |pos: usize| templates.iter().any(|&(start, end)| pos >= start && pos < end);
+    if formats.template {
+        found.extend(TEMPLATE_PLACEHOLDER.find_iter(text)
+            .map(|m| (m.start(), PlaceholderStyle::Template, m.as_str().to_string())));
+    }
+    if formats.c_style {
+        found.extend(C_PLACEHOLDER.find_iter(text)
+            .filter(|m| m.as_str() != "%%" && !is_percentage(text, m.start(), m.end()))
+            .map(|m| (m.start(), PlaceholderStyle::CStyle, m.as_str().to_string())));
+    }
+    for caps in BRACE_PLACEHOLDER.captures_iter(text) {
+        let style = if caps.get(1).is_some() {
+            PlaceholderStyle::Dotnet
+        } else if caps.get(2).is_some() {
+            PlaceholderStyle::Named
+        } else {
+            continue;
+        };
+        let m = caps.get(0).unwrap();
+        if formats.allows(style) && !inside_template(m.start()) {
+            found.push((m.start(), style, m.as_str().to_string()));
+        }
+    }
+    found.sort_by_key(|(start, _, _)| *start);
+    found.into_iter().map(|(_, style, ph)| (style, ph)).collect()
+}
+
+/// Frammenti che sembrano placeholder ma non sono validi (es. spazi dentro le graffe, graffa non chiusa)
+pub(crate) fn malformed_placeholders(text: &str, formats: &PlaceholderFormats) -> Vec<(PlaceholderStyle, String)> {
+    let mut found = Vec::new();
+    if formats.c_style {
+        found.extend(MALFORMED_C.find_iter(text).map(|m| (PlaceholderStyle::CStyle, m.as_str().to_string())));
+    }
+    if formats.dotnet || formats.named {
+        // I template e le graffe valide (ed escapate) vengono tolti prima: resta solo ciò che non è un placeholder
+        let stripped = TEMPLATE_PLACEHOLDER.replace_all(text, " ");
+        let stripped = BRACE_PLACEHOLDER.replace_all(&stripped, " ");
+        for m in MALFORMED_BRACE.find_iter(&stripped) {
+            let fragment = m.as_str();
+            let fragment = if fragment.ends_with('}') {
+                fragment.to_string()
+            } else {
+                // Graffa non chiusa: basta la prima parola per identificarla
+                format!("{{{}", fragment[1..].split_whitespace().next().unwrap_or_default())
+            };
+            let inner = fragment.trim_matches(|c| c == '{' || c == '}').trim();
+            let style = if !inner.is_empty() && inner.chars().all(|c| c.is_ascii_digit()) {
+                PlaceholderStyle::Dotnet
+            } else {
+                PlaceholderStyle::Named
+            };
+            if formats.allows(style) {
+                found.push((style, fragment));
             }
         }
     }
-    
-    issues
+    found
 }
 
 /// Verifica lunghezza
@@ -551,3 +674,60 @@ pub fn qa_auto_fix(target: String, issue_types: Vec<String>) -> Result<String, S
     
     Ok(fixed)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(text: &str) -> Vec<String> {
+        extract_placeholders(text, &PlaceholderFormats::default()).into_iter().map(|(_, ph)| ph).collect()
+    }
+
+    #[test]
+    fn extracts_printf_placeholders() {
+        assert_eq!(extract("%s has %d coins (%5.2f%%)"), vec!["%s", "%d", "%5.2f"]);
+        assert_eq!(extract("%1$s gives %2$d to %1$s"), vec!["%1$s", "%2$d", "%1$s"]);
+        assert_eq!(extract("Progress: 100%%"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn percentages_are_not_printf_placeholders() {
+        assert!(extract("50%off today").is_empty());
+        assert!(extract("+30%dmg, 50% di sconto").is_empty());
+        // Dopo una cifra ma a fine parola resta un placeholder
+        assert_eq!(extract("Level 5%s"), vec!["%s"]);
+        assert_eq!(extract("%off"), vec!["%o"]);
+    }
+
+    #[test]
+    fn extracts_brace_and_template_placeholders() {
+        let found = extract_placeholders("{0} {1:N2} {{literal}} {playerName} ${gold} [[item]] @@npc@@", &PlaceholderFormats::default());
+        assert_eq!(found, vec![
+            (PlaceholderStyle::Dotnet, "{0}".to_string()),
+            (PlaceholderStyle::Dotnet, "{1:N2}".to_string()),
+            (PlaceholderStyle::Named, "{playerName}".to_string()),
+            (PlaceholderStyle::Template, "${gold}".to_string()),
+            (PlaceholderStyle::Template, "[[item]]".to_string()),
+            (PlaceholderStyle::Template, "@@npc@@".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn detects_malformed_placeholders() {
+        let formats = PlaceholderFormats::default();
+        let found: Vec<String> = malformed_placeholders("{ 0 } and {player name} and %1$ and {0", &formats)
+            .into_iter().map(|(_, f)| f).collect();
+        assert_eq!(found, vec!["%1$ ", "{ 0 }", "{player name}", "{0"]);
+        assert!(malformed_placeholders("${two words} {0} {{x}}", &formats).is_empty());
+    }
+
+    #[test]
+    fn check_placeholders_reports_each_missing_placeholder_once() {
+        let issues = check_placeholders("{0} found %d items", "{0} ha trovato oggetti", "t1");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "Placeholder mancante: %d");
+
+        assert!(check_placeholders("50%off", "sconto del 50%", "t2").is_empty());
+        assert!(check_placeholders("${name} {0}", "{0} ${name}", "t3").is_empty());
+    }
+}
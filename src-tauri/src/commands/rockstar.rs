@@ -5,6 +5,7 @@ use std::fs;
 use winreg::enums::*;
 use winreg::RegKey;
 use crate::commands::library::InstalledGame;
+use crate::commands::store_detection::{ConnectionCheck, StoreConnectionState};
 use std::path::PathBuf;
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
 use aes_gcm::aead::Aead;
@@ -157,9 +158,8 @@ async fn scan_rockstar_folders() -> Result<Vec<InstalledGame>, String> {
     Ok(games)
 }
 
-/// Test della connessione Rockstar Games
-#[tauri::command]
-pub async fn test_rockstar_connection() -> Result<String, String> {
+/// Test della connessione Rockstar Games, con esito strutturato per la dashboard degli store
+pub(crate) async fn check_rockstar_connection() -> Result<ConnectionCheck, String> {
     println!("[ROCKSTAR] Test connessione");
     
     // Prima prova a caricare credenziali salvate
@@ -175,31 +175,37 @@ pub async fn test_rockstar_connection() -> Result<String, String> {
                 match test_rockstar_auth(email, password).await {
                     Ok(user) => {
                         let games = get_rockstar_installed_games().await?;
-                        Ok(format!("✅ Connesso come '{}' - {} giochi locali trovati", 
-                                  user.username, games.len()))
+                        Ok(ConnectionCheck::new(StoreConnectionState::Connected, format!("✅ Connesso come '{}' - {} giochi locali trovati", 
+                                  user.username, games.len())).with_local_games(games.len()))
                     }
                     Err(e) => {
                         println!("[ROCKSTAR] Errore autenticazione salvata: {}", e);
                         // Fallback a scansione locale
                         let games = get_rockstar_installed_games().await?;
-                        Ok(format!("❌ Account disconnesso (credenziali non valide) - {} giochi locali trovati", games.len()))
+                        Ok(ConnectionCheck::new(StoreConnectionState::InvalidCredentials, format!("❌ Account disconnesso (credenziali non valide) - {} giochi locali trovati", games.len())).with_local_games(games.len()))
                     }
                 }
             } else {
                 // Fallback a scansione locale
                 let games = get_rockstar_installed_games().await?;
-                Ok(format!("Scansione Rockstar Games completata: {} giochi trovati", games.len()))
+                Ok(ConnectionCheck::new(StoreConnectionState::LocalOnly, format!("Scansione Rockstar Games completata: {} giochi trovati", games.len())).with_local_games(games.len()))
             }
         }
         Err(_) => {
             // Nessuna credenziale salvata - scansione locale
             println!("[ROCKSTAR] Nessuna credenziale salvata");
             let games = get_rockstar_installed_games().await?;
-            Ok(format!("Scansione Rockstar Games completata: {} giochi trovati", games.len()))
+            Ok(ConnectionCheck::new(StoreConnectionState::LocalOnly, format!("Scansione Rockstar Games completata: {} giochi trovati", games.len())).with_local_games(games.len()))
         }
     }
 }
 
+/// Test della connessione Rockstar Games
+#[tauri::command]
+pub async fn test_rockstar_connection() -> Result<String, String> {
+    check_rockstar_connection().await.map(|check| check.message)
+}
+
 /// Recupera informazioni su un gioco Rockstar Games specifico
 #[tauri::command]
 pub async fn get_rockstar_game_info(game_id: String) -> Result<RockstarGame, String> {
@@ -401,7 +407,7 @@ async fn find_main_executable(game_path: &Path) -> Option<String> {
 
 // Funzioni per gestione credenziali Rockstar Games
 
-fn get_rockstar_credentials_path() -> Result<PathBuf, String> {
+pub(crate) fn get_rockstar_credentials_path() -> Result<PathBuf, String> {
     let mut path = std::env::current_dir()
         .map_err(|e| format!("Errore getting current dir: {}", e))?;
     path.push(".cache");
@@ -18,18 +18,18 @@ use winreg::HKEY;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 use std::time::Duration;
-use moka::future::Cache;
+use super::cache_registry::{json_size, TrackedCache};
+use super::file_manager::write_atomic;
 use once_cell::sync::Lazy;
-use reqwest::Client;
 use serde::{Serialize, Deserialize};
 use std::fs;
 use std::path::Path;
-use keyvalues_serde as kv;
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
 use aes_gcm::aead::Aead;
 use base64::{Engine as _, engine::general_purpose};
 use rand::{RngCore, rngs::OsRng};
 use log::{debug, info, warn, error};
+use crate::profiles::secure_memory::{SecureBytes, SecureString};
 
 // ============================================================================
 // SEZIONE 1: STRUTTURE DATI
@@ -37,54 +37,6 @@ use log::{debug, info, warn, error};
 
 pub struct SessionState;
 
-// Struct per deserializzare loginusers.vdf
-#[derive(Debug, Deserialize)]
-struct LoginUsersVdf {
-    users: HashMap<String, UserData>,
-}
-
-#[derive(Debug, Deserialize)]
-struct UserData {
-    #[serde(rename = "AccountName")]
-    account_name: Option<String>,
-}
-
-// Struct per deserializzare libraryfolders.vdf
-#[derive(Debug, Deserialize)]
-struct LibraryFoldersVdf {
-    libraryfolders: HashMap<String, LibraryFolderData>,
-}
-
-#[derive(Debug, Deserialize)]
-struct LibraryFolderData {
-    path: Option<String>,
-    label: Option<String>,
-    mounted: Option<String>,
-    tool: Option<String>,
-}
-
-// Struct per deserializzare file ACF
-#[derive(Debug, Deserialize)]
-struct AcfFile {
-    #[serde(rename = "AppState")]
-    app_state: AcfAppState,
-}
-
-#[derive(Debug, Deserialize)]
-struct AcfAppState {
-    appid: Option<String>,
-    name: Option<String>,
-    #[serde(rename = "StateFlags")]
-    #[allow(dead_code)] // Campo necessario per parsing completo ACF - contiene flag di stato del gioco
-    state_flags: Option<String>,
-    #[serde(rename = "LastUpdated")]
-    last_updated: Option<String>,
-    #[serde(rename = "SizeOnDisk")]
-    size_on_disk: Option<String>,
-    #[serde(rename = "installdir")]
-    install_dir: Option<String>,
-}
-
 // SECURITY: Input validation functions
 /// Validates Steam App ID format and range
 /// Steam App IDs are positive integers up to 10 digits
@@ -122,24 +74,34 @@ fn validate_steam_id64(steam_id: &str) -> bool {
     }
 }
 
-/// Sanitizes string input to prevent injection attacks
+/// Sanitizes string input to prevent injection attacks.
+/// Unicode-aware: NFC normalization, letters from any script (accents, CJK) are kept;
+/// control, zero-width and bidi-override characters are rejected instead of silently stripped.
 fn sanitize_string_input(input: &str, max_length: usize) -> Result<String, String> {
-    if input.is_empty() || input.len() > max_length {
+    if input.is_empty() || input.chars().count() > max_length {
         return Err("Invalid input length".to_string());
     }
-    
-    // Remove potentially dangerous characters
-    let sanitized = input
+
+    let sanitized = crate::profiles::validation::sanitize_unicode(input, false);
+    if !sanitized.is_clean() {
+        return Err(format!("Input contains invisible or control characters: {}", sanitized.removed_summary()));
+    }
+
+    // Reject potentially dangerous characters (markup, quotes, escapes)
+    let disallowed: Vec<char> = sanitized
+        .value
         .chars()
-        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || "-_".contains(*c))
-        .collect::<String>()
-        .trim()
-        .to_string();
-    
+        .filter(|c| "<>\"`\\{}$;|".contains(*c))
+        .collect();
+    if !disallowed.is_empty() {
+        return Err(format!("Input contains disallowed characters: {}", disallowed.iter().collect::<String>()));
+    }
+
+    let sanitized = sanitized.value.trim().to_string();
     if sanitized.is_empty() {
         return Err("Input contains only invalid characters".to_string());
     }
-    
+
     Ok(sanitized)
 }
 
@@ -147,113 +109,11 @@ fn sanitize_string_input(input: &str, max_length: usize) -> Result<String, Strin
 // SEZIONE 2: RATE LIMITER E API UTILS
 // ============================================================================
 
-/// SECURITY: Rate limiting implementation for Steam API calls
+/// SECURITY: Rate limiting for Steam API calls
 /// Prevents API abuse and maintains compliance with Steam API terms
-struct SteamApiRateLimiter {
-    requests: std::sync::Mutex<std::collections::HashMap<String, Vec<u64>>>,
-    max_requests_per_minute: usize,
-    max_requests_per_second: usize,
-    rate_limit_window: u64,
-    burst_window: u64,
-}
-
-impl SteamApiRateLimiter {
-    fn new() -> Self {
-        Self {
-            requests: std::sync::Mutex::new(std::collections::HashMap::new()),
-            max_requests_per_minute: 100, // Steam API limit
-            max_requests_per_second: 10,  // Conservative limit
-            rate_limit_window: 60 * 1000, // 1 minute in milliseconds
-            burst_window: 1000,           // 1 second in milliseconds
-        }
-    }
-    
-    fn get_current_time_ms() -> u64 {
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64
-    }
-    
-    fn is_request_allowed(&self, endpoint: &str) -> bool {
-        let now = Self::get_current_time_ms();
-        let mut requests = self.requests.lock().unwrap();
-        
-        // Get or create request history for this endpoint
-        let request_history = requests.entry(endpoint.to_string()).or_insert(Vec::new());
-        
-        // Clean old requests outside the window
-        request_history.retain(|&timestamp| now - timestamp < self.rate_limit_window);
-        
-        // Check burst limit (requests per second)
-        let recent_requests: Vec<&u64> = request_history
-            .iter()
-            .filter(|&&timestamp| now - timestamp < self.burst_window)
-            .collect();
-        
-        if recent_requests.len() >= self.max_requests_per_second {
-            warn!("[RateLimit] Burst limit exceeded for {}: {}/{} per second", 
-                  endpoint, recent_requests.len(), self.max_requests_per_second);
-            return false;
-        }
-        
-        // Check minute limit
-        if request_history.len() >= self.max_requests_per_minute {
-            warn!("[RateLimit] Minute limit exceeded for {}: {}/{} per minute", 
-                  endpoint, request_history.len(), self.max_requests_per_minute);
-            return false;
-        }
-        
-        // Request is allowed, record it
-        request_history.push(now);
-        true
-    }
-    
-    fn get_delay_until_next_request(&self, endpoint: &str) -> u64 {
-        let now = Self::get_current_time_ms();
-        let requests = self.requests.lock().unwrap();
-        
-        if let Some(request_history) = requests.get(endpoint) {
-            // Check if we need to wait for burst limit
-            let recent_requests: Vec<&u64> = request_history
-                .iter()
-                .filter(|&&timestamp| now - timestamp < self.burst_window)
-                .collect();
-            
-            if recent_requests.len() >= self.max_requests_per_second {
-                if let Some(&&oldest_recent) = recent_requests.iter().min() {
-                    return self.burst_window - (now - oldest_recent);
-                }
-            }
-            
-            // Check if we need to wait for minute limit
-            let valid_requests: Vec<&u64> = request_history
-                .iter()
-                .filter(|&&timestamp| now - timestamp < self.rate_limit_window)
-                .collect();
-            
-            if valid_requests.len() >= self.max_requests_per_minute {
-                if let Some(&&oldest_valid) = valid_requests.iter().min() {
-                    return self.rate_limit_window - (now - oldest_valid);
-                }
-            }
-        }
-        
-        0
-    }
-    
-    async fn wait_for_next_request(&self, endpoint: &str) {
-        let delay = self.get_delay_until_next_request(endpoint);
-        if delay > 0 {
-            info!("[RateLimit] Waiting {}ms before next request to {}", delay, endpoint);
-            tokio::time::sleep(Duration::from_millis(delay)).await;
-        }
-    }
-}
-
-// Global rate limiter instance
-static RATE_LIMITER: once_cell::sync::Lazy<SteamApiRateLimiter> = 
-    once_cell::sync::Lazy::new(|| SteamApiRateLimiter::new());
+/// (100 requests per minute, conservative 10 per second burst)
+static RATE_LIMITER: Lazy<crate::http::RateLimiter> =
+    Lazy::new(|| crate::http::RateLimiter::new("Steam", 10, 100));
 
 /// Helper function for rate-limited Steam API calls with exponential backoff
 /// Handles 403/429 errors with automatic retry
@@ -341,12 +201,16 @@ async fn make_quick_request(
 // ============================================================================
 
 #[tauri::command]
-pub async fn debug_steam_api_extended(api_key: String, steam_id: String) -> Result<String, String> {
+pub async fn debug_steam_api_extended(
+    api_key: String,
+    steam_id: String,
+    profile_state: tauri::State<'_, crate::commands::profiles::ProfileManagerState>
+) -> Result<String, String> {
     debug!("[RUST] DEBUG: Test esteso API Steam...");
     
     // Se le credenziali sono vuote, carica quelle salvate
     let (actual_key, actual_id) = if api_key.is_empty() || steam_id.is_empty() {
-        match get_decrypted_api_key().await {
+        match load_steam_api_credentials(&profile_state).await {
             Ok((key, id)) => (key, id),
             Err(e) => return Err(format!("Impossibile caricare credenziali: {}", e))
         }
@@ -361,20 +225,17 @@ pub async fn debug_steam_api_extended(api_key: String, steam_id: String) -> Resu
             return Err("Invalid API key format".to_string());
         }
         
-        (api_key, steam_id)
+        (SecureString::from(api_key), steam_id)
     };
     
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Errore creazione client: {}", e))?;
+    let client = crate::http::client_for("steam");
     
     let mut results = Vec::new();
     
     // Test 1: GetOwnedGames con parametri minimali
     let url1 = format!(
         "https://api.steampowered.com/IPlayerService/GetOwnedGames/v0001/?key={}&steamid={}&format=json",
-        actual_key, actual_id
+        actual_key.expose(), actual_id
     );
     
     // SECURITY FIX: Apply rate limiting to API calls
@@ -402,7 +263,7 @@ pub async fn debug_steam_api_extended(api_key: String, steam_id: String) -> Resu
     // Test 2: GetOwnedGames senza include_played_free_games
     let url2 = format!(
         "https://api.steampowered.com/IPlayerService/GetOwnedGames/v0001/?key={}&steamid={}&format=json&include_appinfo=true",
-        actual_key, actual_id
+        actual_key.expose(), actual_id
     );
     
     // SECURITY FIX: Apply rate limiting to API calls
@@ -430,7 +291,7 @@ pub async fn debug_steam_api_extended(api_key: String, steam_id: String) -> Resu
     // Test 3: GetOwnedGames con parametri community-suggested
     let url3 = format!(
         "https://api.steampowered.com/IPlayerService/GetOwnedGames/v0001/?key={}&steamid={}&format=json&include_appinfo=1&include_played_free_games=1&skip_unvetted_apps=false",
-        actual_key, actual_id
+        actual_key.expose(), actual_id
     );
     
     // SECURITY FIX: Removed URL logging to prevent API key exposure
@@ -450,7 +311,7 @@ pub async fn debug_steam_api_extended(api_key: String, steam_id: String) -> Resu
     // Test 4: GetPlayerSummaries per verificare ancora il profilo
     let url4 = format!(
         "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v0002/?key={}&steamids={}",
-        actual_key, actual_id
+        actual_key.expose(), actual_id
     );
     
     // SECURITY FIX: Removed URL logging to prevent API key exposure
@@ -483,7 +344,7 @@ pub async fn debug_steam_api_extended(api_key: String, steam_id: String) -> Resu
     
     let url5 = format!(
         "https://api.steampowered.com/IPlayerService/GetOwnedGames/v0001/?key={}&steamid={}&format=json&include_appinfo=1&include_played_free_games=1",
-        actual_key, actual_id
+        actual_key.expose(), actual_id
     );
     
     match client.get(&url5).send().await {
@@ -503,12 +364,16 @@ pub async fn debug_steam_api_extended(api_key: String, steam_id: String) -> Resu
 
 // 🔍 DEBUG: Comando per testare specificamente l'API Steam
 #[tauri::command]
-pub async fn debug_steam_api(api_key: String, steam_id: String) -> Result<String, String> {
+pub async fn debug_steam_api(
+    api_key: String,
+    steam_id: String,
+    profile_state: tauri::State<'_, crate::commands::profiles::ProfileManagerState>
+) -> Result<String, String> {
     debug!("[RUST] DEBUG: Testando API Steam...");
     
     // Se le credenziali sono vuote, carica quelle salvate
     let (actual_key, actual_id) = if api_key.is_empty() || steam_id.is_empty() {
-        match get_decrypted_api_key().await {
+        match load_steam_api_credentials(&profile_state).await {
             Ok((key, id)) => (key, id),
             Err(e) => return Err(format!("Impossibile caricare credenziali: {}", e))
         }
@@ -523,12 +388,12 @@ pub async fn debug_steam_api(api_key: String, steam_id: String) -> Result<String
             return Err("Invalid API key format".to_string());
         }
         
-        (api_key, steam_id)
+        (SecureString::from(api_key), steam_id)
     };
     
     let url = format!(
         "https://api.steampowered.com/IPlayerService/GetOwnedGames/v0001/?key={}&steamid={}&format=json&include_appinfo=true&include_played_free_games=true&skip_unvetted_apps=false",
-        actual_key, actual_id
+        actual_key.expose(), actual_id
     );
     
     // SECURITY FIX: Removed URL logging to prevent API key exposure
@@ -547,10 +412,7 @@ pub async fn debug_steam_api(api_key: String, steam_id: String) -> Result<String
         debug!("[RUST] ⚠️ WARNING: Steam ID doesn't start with 7656119 (64-bit SteamID format)");
     }
     
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Errore creazione client: {}", e))?;
+    let client = crate::http::client_for("steam");
     
     // SECURITY FIX: Apply rate limiting to API calls
     let endpoint = "api.steampowered.com/IPlayerService/GetOwnedGames";
@@ -604,6 +466,29 @@ pub async fn debug_steam_api(api_key: String, steam_id: String) -> Result<String
     }
 }
 
+/// Lock per file JSON modificati con read-modify-write (chiave: percorso canonico).
+/// Due aggiunte concorrenti dalla UI altrimenti si sovrascrivono a vicenda.
+static JSON_FILE_LOCKS: Lazy<std::sync::Mutex<HashMap<std::path::PathBuf, std::sync::Arc<tokio::sync::Mutex<()>>>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+/// Chiave del lock: directory canonica + nome file. Il file può non esistere ancora
+/// (prima aggiunta), ma la chiave deve restare la stessa dopo la sua creazione
+fn json_file_lock_key(path: &Path) -> std::path::PathBuf {
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => {
+            let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+            fs::canonicalize(parent).map(|dir| dir.join(name)).unwrap_or_else(|_| path.to_path_buf())
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+fn json_file_lock(path: &Path) -> std::sync::Arc<tokio::sync::Mutex<()>> {
+    let key = json_file_lock_key(path);
+    let mut locks = JSON_FILE_LOCKS.lock().unwrap_or_else(|e| e.into_inner());
+    locks.entry(key).or_default().clone()
+}
+
 // 🔄 SISTEMA: Auto-aggiornamento libreria Steam
 #[tauri::command]
 pub async fn add_game_to_library(appid: u32, name: String) -> Result<String, String> {
@@ -617,13 +502,19 @@ pub async fn add_game_to_library(appid: u32, name: String) -> Result<String, Str
     
     debug!("[RUST] Aggiungendo gioco alla libreria: {} ({})", sanitized_name, appid);
     
-    let file_path = "../steam_owned_games.json";
+    let file_path = owned_games_json_path();
+    let file_path = file_path.as_path();
     
-    // Leggi il file esistente
+    // Lettura, controllo duplicati e scrittura sotto lo stesso lock
+    let lock = json_file_lock(file_path);
+    let _guard = lock.lock().await;
+    
+    // Leggi il file esistente (assente = libreria vuota)
     let mut games: Vec<serde_json::Value> = match std::fs::read_to_string(file_path) {
         Ok(content) => {
             serde_json::from_str(&content).map_err(|e| format!("Errore parsing JSON: {}", e))?
         }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
         Err(e) => {
             return Err(format!("Errore lettura file: {}", e));
         }
@@ -660,42 +551,369 @@ pub async fn add_game_to_library(appid: u32, name: String) -> Result<String, Str
     let updated_content = serde_json::to_string_pretty(&games)
         .map_err(|e| format!("Errore serializzazione JSON: {}", e))?;
     
-    std::fs::write(file_path, updated_content)
-        .map_err(|e| format!("Errore scrittura file: {}", e))?;
+    write_atomic(file_path, updated_content.as_bytes())?;
     
     debug!("[RUST] ✅ Gioco '{}' aggiunto alla libreria. Totale giochi: {}", sanitized_name, games.len());
     
     Ok(format!("Gioco '{}' aggiunto con successo! Totale giochi: {}", sanitized_name, games.len()))
 }
 
+/// Esito della validazione del JSON dei giochi posseduti
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnedGamesFileReport {
+    pub path: String,
+    pub exists: bool,
+    pub valid: bool,
+    pub entries: usize,
+    pub error: Option<String>,
+    /// Posizione dell'errore di parsing (1-based)
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+/// Esito della riparazione del JSON dei giochi posseduti
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnedGamesRepairResult {
+    pub path: String,
+    /// Copia del file originale (None se non è stato necessario riscriverlo)
+    pub backup_path: Option<String>,
+    pub recovered: usize,
+    pub dropped: usize,
+}
+
+/// Voce valida del file: oggetto con `appid` numerico
+fn is_valid_owned_game(value: &serde_json::Value) -> bool {
+    value.get("appid").and_then(|v| v.as_u64()).is_some()
+}
+
+/// Inizio dell'array dei giochi: il file stesso oppure `{"response": {"games": [...]}}`
+/// salvato direttamente dall'API
+fn owned_games_array_start(content: &str) -> Option<usize> {
+    let trimmed = content.trim_start_matches('\u{feff}').trim_start();
+    match trimmed.chars().next()? {
+        '[' => Some(content.len() - trimmed.len()),
+        '{' => {
+            let key = content.find("\"games\"")? + "\"games\"".len();
+            let rest = content[key..].trim_start().strip_prefix(':')?.trim_start();
+            rest.starts_with('[').then(|| content.len() - rest.len())
+        }
+        _ => None,
+    }
+}
+
+/// Recupera gli oggetti ben formati di primo livello da un array JSON danneggiato
+/// (troncato, virgole mancanti, oggetti rotti). Restituisce (voci recuperate, scartate).
+fn salvage_owned_games(content: &str) -> (Vec<serde_json::Value>, usize) {
+    let mut recovered = Vec::new();
+    let mut seen = HashSet::new();
+    let mut dropped = 0;
+
+    let Some(start) = owned_games_array_start(content) else {
+        return (recovered, dropped);
+    };
+    let content = &content[start..];
+
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut object_start = None;
+
+    for (i, c) in content.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => {
+                // Oggetto diretto dell'array principale (depth 1)
+                if c == '{' && depth == 1 {
+                    object_start = Some(i);
+                }
+                depth += 1;
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                if c == '}' && depth == 1 {
+                    if let Some(start) = object_start.take() {
+                        match serde_json::from_str::<serde_json::Value>(&content[start..=i]) {
+                            Ok(value) if is_valid_owned_game(&value) => {
+                                if seen.insert(value["appid"].as_u64()) {
+                                    recovered.push(value);
+                                } else {
+                                    dropped += 1;
+                                }
+                            }
+                            _ => dropped += 1,
+                        }
+                    }
+                }
+                // Fine dell'array principale: il resto (chiusura del wrapper) non interessa
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Oggetto troncato a fine file
+    if object_start.is_some() {
+        dropped += 1;
+    }
+    (recovered, dropped)
+}
+
+/// Verifica che il JSON dei giochi posseduti sia leggibile, indicando dove si rompe
+#[tauri::command]
+pub async fn validate_owned_games_file() -> Result<OwnedGamesFileReport, String> {
+    let path = owned_games_json_path();
+    let mut report = OwnedGamesFileReport {
+        path: path.to_string_lossy().to_string(),
+        exists: path.exists(),
+        valid: false,
+        entries: 0,
+        error: None,
+        line: None,
+        column: None,
+    };
+    if !report.exists {
+        report.error = Some("File non trovato".to_string());
+        return Ok(report);
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("Errore lettura file: {}", e))?;
+    match serde_json::from_str::<Vec<serde_json::Value>>(&content) {
+        Ok(games) => {
+            let invalid = games.iter().filter(|g| !is_valid_owned_game(g)).count();
+            report.entries = games.len() - invalid;
+            report.valid = invalid == 0;
+            if invalid > 0 {
+                report.error = Some(format!("{} voci senza appid valido", invalid));
+            }
+        }
+        Err(e) => {
+            report.error = Some(e.to_string());
+            report.line = Some(e.line());
+            report.column = Some(e.column());
+        }
+    }
+
+    info!("[RUST] 🩺 Validazione {}: valido={} voci={}", report.path, report.valid, report.entries);
+    Ok(report)
+}
+
+/// Ripara il JSON dei giochi posseduti salvando le voci ben formate (con backup dell'originale)
+#[tauri::command]
+pub async fn repair_owned_games_file() -> Result<OwnedGamesRepairResult, String> {
+    let path = owned_games_json_path();
+    let lock = json_file_lock(&path);
+    let _guard = lock.lock().await;
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("Errore lettura file: {}", e))?;
+    let mut result = OwnedGamesRepairResult {
+        path: path.to_string_lossy().to_string(),
+        backup_path: None,
+        recovered: 0,
+        dropped: 0,
+    };
+
+    // File già valido: niente da riscrivere
+    if let Ok(games) = serde_json::from_str::<Vec<serde_json::Value>>(&content) {
+        if games.iter().all(is_valid_owned_game) {
+            result.recovered = games.len();
+            return Ok(result);
+        }
+    }
+
+    let (games, dropped) = salvage_owned_games(&content);
+    if games.is_empty() {
+        warn!("[RUST] ⚠️ {}: nessuna voce recuperabile ({} scartate), file lasciato invariato", result.path, dropped);
+        return Err(format!("Nessuna voce recuperabile ({} scartate): file lasciato invariato", dropped));
+    }
+    let backup = path.with_file_name(format!("{}.{}.bak", OWNED_GAMES_FILE, chrono::Utc::now().format("%Y%m%d_%H%M%S")));
+    fs::copy(&path, &backup).map_err(|e| format!("Errore backup file originale: {}", e))?;
+
+    let repaired = serde_json::to_string_pretty(&games).map_err(|e| format!("Errore serializzazione JSON: {}", e))?;
+    write_atomic(&path, repaired.as_bytes())?;
+
+    result.backup_path = Some(backup.to_string_lossy().to_string());
+    result.recovered = games.len();
+    result.dropped = dropped;
+    warn!("[RUST] 🔧 {} riparato: {} voci recuperate, {} scartate (backup: {})",
+        result.path, result.recovered, result.dropped, backup.display());
+    Ok(result)
+}
+
+/// Esito della sincronizzazione API → file locale
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnedGamesSyncResult {
+    pub path: String,
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    /// Voci totali nel file dopo il merge (le voci assenti dall'API non vengono mai rimosse)
+    pub total: usize,
+}
+
+/// Campi aggiornati dall'API sulle voci già presenti nel file
+const OWNED_GAMES_SYNC_FIELDS: &[&str] = &[
+    "name",
+    "img_icon_url",
+    "playtime_forever",
+    "playtime_windows_forever",
+    "playtime_mac_forever",
+    "playtime_linux_forever",
+    "playtime_deck_forever",
+    "playtime_disconnected",
+    "rtime_last_played",
+];
+
+/// Unisce i giochi dell'API alle voci locali: aggiunge i nuovi, aggiorna playtime e ultimo
+/// avvio degli esistenti, non elimina nulla. Restituisce (aggiunti, aggiornati, invariati).
+fn merge_owned_games(local: &mut Vec<Value>, remote: &[Value]) -> (usize, usize, usize) {
+    let mut positions: HashMap<u64, usize> = local.iter()
+        .enumerate()
+        .filter_map(|(i, g)| g["appid"].as_u64().map(|id| (id, i)))
+        .collect();
+    let (mut added, mut updated, mut unchanged) = (0, 0, 0);
+
+    for game in remote {
+        let Some(appid) = game["appid"].as_u64() else { continue };
+        match positions.get(&appid) {
+            Some(&i) => {
+                let entry = &mut local[i];
+                let mut changed = false;
+                for field in OWNED_GAMES_SYNC_FIELDS {
+                    if let Some(value) = game.get(*field) {
+                        // Un nome vuoto dall'API non sovrascrive quello locale
+                        if *field == "name" && !value.as_str().is_some_and(|n| !n.trim().is_empty()) {
+                            continue;
+                        }
+                        if entry.get(*field) != Some(value) {
+                            entry[*field] = value.clone();
+                            changed = true;
+                        }
+                    }
+                }
+                if changed { updated += 1 } else { unchanged += 1 }
+            }
+            None => {
+                positions.insert(appid, local.len());
+                local.push(game.clone());
+                added += 1;
+            }
+        }
+    }
+
+    (added, updated, unchanged)
+}
+
+/// Elenco giochi di GetOwnedGames: Steam omette `games` quando non ce ne sono,
+/// quindi la chiave assente vale come elenco vuoto
+fn owned_games_from_response(json: &Value) -> Result<Vec<Value>, String> {
+    let response = json.get("response")
+        .filter(|r| r.is_object())
+        .ok_or("Risposta GetOwnedGames senza oggetto 'response'")?;
+    match response.get("games") {
+        None => Ok(Vec::new()),
+        Some(games) => games.as_array()
+            .cloned()
+            .ok_or_else(|| "Risposta GetOwnedGames con 'games' non valido".to_string()),
+    }
+}
+
+/// Scarica i giochi posseduti dall'API Steam e li unisce al file locale usato offline
+#[tauri::command]
+pub async fn sync_owned_games_to_local_file(
+    profile_state: tauri::State<'_, crate::commands::profiles::ProfileManagerState>
+) -> Result<OwnedGamesSyncResult, String> {
+    // Credenziali: profilo attivo, poi configurazione Steam salvata
+    let (api_key, steam_id) = load_steam_api_credentials(&profile_state).await?;
+    if api_key.is_empty() || steam_id.is_empty() {
+        return Err("Credenziali Steam non configurate".to_string());
+    }
+
+    let url = format!(
+        "https://api.steampowered.com/IPlayerService/GetOwnedGames/v0001/?key={}&steamid={}&format=json&include_appinfo=true&include_played_free_games=true&skip_unvetted_apps=false",
+        api_key.expose(), steam_id
+    );
+    let client = crate::http::client_for("steam_owned_games");
+    let response = crate::http::get_with_retry(&client, &url, "Steam GetOwnedGames").await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("GetOwnedGames: HTTP {}", response.status()));
+    }
+    let json: Value = response.json().await
+        .map_err(|e| format!("Errore parsing GetOwnedGames: {}", e.without_url()))?;
+    let remote = owned_games_from_response(&json)?;
+    if remote.is_empty() {
+        warn!("[RUST] ⚠️ GetOwnedGames senza giochi (libreria vuota o profilo privato?)");
+    }
+
+    let path = owned_games_json_path();
+    let lock = json_file_lock(&path);
+    let _guard = lock.lock().await;
+
+    let mut local: Vec<Value> = match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("File locale non valido ({}): usa repair_owned_games_file", e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(format!("Errore lettura file: {}", e)),
+    };
+
+    let (added, updated, unchanged) = merge_owned_games(&mut local, &remote);
+    if added > 0 || updated > 0 {
+        let content = serde_json::to_string_pretty(&local)
+            .map_err(|e| format!("Errore serializzazione JSON: {}", e))?;
+        write_atomic(&path, content.as_bytes())?;
+    }
+
+    info!("[RUST] 🔄 Sync giochi posseduti: {} aggiunti, {} aggiornati, {} invariati ({} totali)",
+        added, updated, unchanged, local.len());
+    Ok(OwnedGamesSyncResult {
+        path: path.to_string_lossy().to_string(),
+        added,
+        updated,
+        unchanged,
+        total: local.len(),
+    })
+}
+
 // 🔍 DEBUG: Comando per testare il profilo Steam
 #[tauri::command]
-pub async fn debug_steam_profile(api_key: String, steam_id: String) -> Result<String, String> {
+pub async fn debug_steam_profile(
+    api_key: String,
+    steam_id: String,
+    profile_state: tauri::State<'_, crate::commands::profiles::ProfileManagerState>
+) -> Result<String, String> {
     debug!("[RUST] DEBUG: Testando profilo Steam...");
     
     // Se le credenziali sono vuote, carica quelle salvate
     let (actual_key, actual_id) = if api_key.is_empty() || steam_id.is_empty() {
-        match get_decrypted_api_key().await {
+        match load_steam_api_credentials(&profile_state).await {
             Ok((key, id)) => (key, id),
             Err(e) => return Err(format!("Impossibile caricare credenziali: {}", e))
         }
     } else {
-        (api_key, steam_id)
+        (SecureString::from(api_key), steam_id)
     };
     
     // Test profilo pubblico/privato
     let profile_url = format!(
         "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v0002/?key={}&steamids={}",
-        actual_key, actual_id
+        actual_key.expose(), actual_id
     );
     
     // SECURITY FIX: Removed URL logging to prevent API key exposure
     info!("[RUST] Steam API debug - GetPlayerSummaries call initiated");
     
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Errore creazione client: {}", e))?;
+    let client = crate::http::client_for("steam");
     
     match client.get(&profile_url).send().await {
         Ok(response) => {
@@ -844,11 +1062,49 @@ fn detect_vr_game(name: &str, appid: u32) -> bool {
 }
 
 // 🎮 Rilevamento engine di sviluppo (DATABASE MASSIVO)
-fn detect_game_engine(name: &str, _appid: u32) -> String {
-    // Usa il rilevamento centralizzato
-    crate::engine_detector::detect_engine_by_name(name).unwrap_or_else(|| "Unknown".to_string())
+/// Engine di ogni gioco `(appid, nome)`: file su disco per i giochi installati, titolo
+/// come ripiego. Le librerie Steam vengono lette una volta per scansione e le sonde
+/// sul disco girano su un thread bloccante, fuori dal runtime async.
+async fn detect_game_engines(games: Vec<(u32, String)>) -> HashMap<u32, String> {
+    tokio::task::spawn_blocking(move || {
+        let install_dirs = steam_install_dirs();
+        games.into_iter()
+            .map(|(appid, name)| {
+                let install_dir = install_dirs.get(&appid).map(std::path::PathBuf::as_path);
+                (appid, crate::engine_detector::detect_engine_smart(&name, install_dir))
+            })
+            .collect()
+    })
+    .await
+    .unwrap_or_else(|e| {
+        warn!("[RUST] ⚠️ Rilevamento engine interrotto: {}", e);
+        HashMap::new()
+    })
+}
+
+/// Mappa appid → cartella di installazione di tutti i giochi nelle librerie Steam locali
+fn steam_install_dirs() -> HashMap<u32, std::path::PathBuf> {
+    let Some(steam_dir) = STEAM_DIR.as_ref() else {
+        return HashMap::new();
+    };
+    let Ok(libraries) = steam_dir.libraries() else {
+        return HashMap::new();
+    };
+    libraries
+        .flatten()
+        .flat_map(|library| {
+            library.apps()
+                .flatten()
+                .map(|app| (app.app_id, library.resolve_app_dir(&app)))
+                .collect::<Vec<_>>()
+        })
+        .collect()
 }
 
+// Installazione Steam locale, localizzata una sola volta per il rilevamento engine
+static STEAM_DIR: once_cell::sync::Lazy<Option<steamlocate::SteamDir>> =
+    once_cell::sync::Lazy::new(|| steamlocate::SteamDir::locate().ok());
+
 // 💾 Rilevamento installazione Steam
 fn detect_steam_installation(appid: u32) -> bool {
     // Percorsi comuni di installazione Steam
@@ -1409,6 +1665,11 @@ fn encrypt_api_key(api_key: &str) -> Result<(String, String), String> {
 }
 
 pub fn decrypt_api_key(encrypted_b64: &str, nonce_b64: &str) -> Result<String, String> {
+    decrypt_api_key_secure(encrypted_b64, nonce_b64).map(|key| key.expose().to_string())
+}
+
+/// Decripta l'API key mantenendo plaintext e payload in buffer azzerati al Drop
+pub fn decrypt_api_key_secure(encrypted_b64: &str, nonce_b64: &str) -> Result<SecureString, String> {
     // SECURITY FIX: Validate input parameters
     if encrypted_b64.is_empty() || nonce_b64.is_empty() {
         return Err("Encrypted data and nonce cannot be empty".to_string());
@@ -1440,11 +1701,11 @@ pub fn decrypt_api_key(encrypted_b64: &str, nonce_b64: &str) -> Result<String, S
     let nonce = Nonce::from_slice(&nonce_bytes);
     
     // SECURITY FIX: Decrypt with authenticated encryption verification
-    let plaintext = cipher.decrypt(nonce, ciphertext.as_ref())
-        .map_err(|e| format!("Decryption failed - data may be corrupted or tampered: {}", e))?;
+    let plaintext = SecureBytes::new(cipher.decrypt(nonce, ciphertext.as_ref())
+        .map_err(|e| format!("Decryption failed - data may be corrupted or tampered: {}", e))?);
     
-    // SECURITY FIX: Convert to string with validation
-    let payload = String::from_utf8(plaintext)
+    // SECURITY FIX: Convert to string with validation (senza copiare il plaintext)
+    let payload = std::str::from_utf8(plaintext.as_slice())
         .map_err(|e| format!("Invalid UTF-8 in decrypted data: {}", e))?;
     
     // SECURITY FIX: Parse payload to extract API key and timestamp
@@ -1484,11 +1745,11 @@ pub fn decrypt_api_key(encrypted_b64: &str, nonce_b64: &str) -> Result<String, S
     // SECURITY FIX: Log successful decryption (without sensitive data)
     info!("[Security] API key decrypted successfully, created at timestamp {}", timestamp);
     
-    Ok(api_key.to_string())
+    Ok(SecureString::from_str_copy(api_key))
 }
 
 // Percorso file credenziali
-fn get_steam_credentials_path() -> Result<std::path::PathBuf, String> {
+pub(crate) fn get_steam_credentials_path() -> Result<std::path::PathBuf, String> {
     // SECURITY FIX: Validate APPDATA environment variable
     let app_data = std::env::var("APPDATA")
         .map_err(|_| "APPDATA environment variable not found".to_string())?;
@@ -1539,6 +1800,11 @@ fn get_steam_credentials_path() -> Result<std::path::PathBuf, String> {
     Ok(credentials_file)
 }
 
+/// Credenziali Steam salvate da GameStringer (usato dalla diagnostica launcher)
+pub(crate) fn has_saved_credentials() -> bool {
+    get_steam_credentials_path().map(|path| path.exists()).unwrap_or(false)
+}
+
 /// SECURITY FIX: Credential integrity verification
 fn verify_credential_integrity(api_key: &str, steam_id: &str) -> Result<(), String> {
     // Validate API key format
@@ -1626,7 +1892,7 @@ fn save_credentials_securely(api_key: &str, steam_id: &str) -> Result<(), String
 }
 
 /// SECURITY FIX: Secure credential loading with integrity verification
-fn load_credentials_securely() -> Result<(String, String), String> {
+pub(crate) fn load_credentials_securely() -> Result<(SecureString, String), String> {
     let credentials_path = get_steam_credentials_path()?;
     
     // Check if file exists
@@ -1664,10 +1930,10 @@ fn load_credentials_securely() -> Result<(String, String), String> {
     }
     
     // Decrypt API key
-    let api_key = decrypt_api_key(encrypted_key, nonce)?;
+    let api_key = decrypt_api_key_secure(encrypted_key, nonce)?;
     
     // Verify integrity of loaded credentials
-    verify_credential_integrity(&api_key, steam_id)?;
+    verify_credential_integrity(api_key.expose(), steam_id)?;
     
     info!("[Security] Credentials loaded and verified successfully");
     Ok((api_key, steam_id.to_string()))
@@ -1687,26 +1953,66 @@ fn get_steam_status_path() -> Result<std::path::PathBuf, String> {
     Ok(app_dir.join("steam_status.json"))
 }
 
+/// Variabile d'ambiente per usare un file giochi posseduti diverso da quello nella directory dati
+const OWNED_GAMES_PATH_ENV: &str = "GAMESTRINGER_OWNED_GAMES_PATH";
+const OWNED_GAMES_FILE: &str = "steam_owned_games.json";
+
+static OWNED_GAMES_MIGRATION: std::sync::Once = std::sync::Once::new();
+
+/// Percorso assoluto del JSON dei giochi posseduti (accanto a steam_status.json).
+/// Al primo uso migra un eventuale file legacy relativo alla directory di lavoro ("../").
+pub fn owned_games_json_path() -> std::path::PathBuf {
+    if let Some(custom) = std::env::var_os(OWNED_GAMES_PATH_ENV).filter(|p| !p.is_empty()) {
+        return std::path::PathBuf::from(custom);
+    }
+
+    let path = get_steam_status_path()
+        .map(|p| p.with_file_name(OWNED_GAMES_FILE))
+        .unwrap_or_else(|_| std::path::PathBuf::from("..").join(OWNED_GAMES_FILE));
+
+    OWNED_GAMES_MIGRATION.call_once(|| {
+        if path.exists() {
+            return;
+        }
+        let legacy = [std::path::PathBuf::from("..").join(OWNED_GAMES_FILE), std::path::PathBuf::from(OWNED_GAMES_FILE)];
+        if let Some(source) = legacy.iter().find(|p| p.is_file()) {
+            match fs::copy(source, &path) {
+                Ok(_) => info!("[RUST] 📦 {} migrato da {} a {}", OWNED_GAMES_FILE, source.display(), path.display()),
+                Err(e) => warn!("[RUST] ⚠️ Migrazione {} fallita: {}", OWNED_GAMES_FILE, e),
+            }
+        }
+    });
+
+    path
+}
+
 use winreg::enums::*;
 use winreg::RegKey;
 
-// Global HTTP client and cache
-static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
-    Client::builder()
-        .timeout(Duration::from_secs(30))
-        .build()
-        .expect("Failed to create HTTP client")
-});
 
 // ============================================================================
 // SEZIONE 6: STEAM CONFIG & CONNECTION
 // ============================================================================
 
-static GAME_CACHE: Lazy<Cache<u32, SteamGame>> = Lazy::new(|| {
-    Cache::builder()
-        .max_capacity(10000)
-        .time_to_live(Duration::from_secs(3600)) // 1 hour
-        .build()
+pub(crate) static GAME_CACHE: Lazy<TrackedCache<u32, SteamGame>> = Lazy::new(|| {
+    TrackedCache::new(
+        "steam_games",
+        "Giochi Steam arricchiti (appdetails + HLTB)",
+        10000,
+        Duration::from_secs(3600), // 1 hour
+        json_size,
+    )
+});
+
+/// Dettagli gioco (lingue supportate) per AppID
+pub(crate) static DETAILS_CACHE: Lazy<TrackedCache<String, Value>> = Lazy::new(|| {
+    TrackedCache::new(
+        "steam_details",
+        "Dettagli Steam Store per AppID (lingue supportate)",
+        1000,
+        Duration::from_secs(3600), // 1 ora
+        json_size,
+    )
 });
 
 #[tauri::command]
@@ -1721,17 +2027,10 @@ pub async fn auto_detect_steam_config() -> Result<SteamConfig, String> {
         if login_users_path.exists() {
             match fs::read_to_string(login_users_path) {
                 Ok(content) => {
-                    match kv::from_str::<LoginUsersVdf>(&content) {
-                        Ok(vdf) => {
-                            for (steam_id, user_data) in vdf.users.iter() {
-                                if let Some(account_name) = &user_data.account_name {
-                                    if !account_name.is_empty() {
-                                        logged_in_users.push(steam_id.clone());
-                                    }
-                                }
-                            }
-                        },
-                        Err(e) => debug!("Failed to parse VDF: {}", e),
+                    for user in crate::vdf::parse_login_users(&content) {
+                        if user.account_name.is_some() {
+                            logged_in_users.push(user.steam_id);
+                        }
                     }
                 },
                 Err(e) => debug!("Failed to read loginusers.vdf: {}", e),
@@ -1767,6 +2066,8 @@ fn find_steam_path_in_hive(hive: HKEY, subkey: &str) -> Option<String> {
 #[derive(serde::Serialize)]
 pub struct TestConnectionResult {
     pub connected: bool,
+    /// false se il profilo attivo non ha credenziali Steam: `games_count` conta i giochi installati
+    pub has_credentials: bool,
     pub games_count: Option<u32>,
     pub error: Option<String>,
 }
@@ -1791,6 +2092,7 @@ pub async fn test_steam_connection(
             if let Err(e) = verify_credential_integrity(&credential.password, &steam_id) {
                 return Ok(TestConnectionResult {
                     connected: false,
+                    has_credentials: true,
                     games_count: None,
                     error: Some(format!("Credenziali Steam non valide: {}", e)),
                 });
@@ -1817,6 +2119,7 @@ pub async fn test_steam_connection(
                             
                             Ok(TestConnectionResult {
                                 connected: true,
+                                has_credentials: true,
                                 games_count: Some(games_count),
                                 error: None,
                             })
@@ -1825,6 +2128,7 @@ pub async fn test_steam_connection(
                             let installed_games = get_installed_steam_app_ids().await;
                             Ok(TestConnectionResult {
                                 connected: true,
+                                has_credentials: true,
                                 games_count: Some(installed_games.len() as u32),
                                 error: None,
                             })
@@ -1832,6 +2136,7 @@ pub async fn test_steam_connection(
                     } else {
                         Ok(TestConnectionResult {
                             connected: false,
+                            has_credentials: true,
                             games_count: None,
                             error: Some(format!("API Steam error: {}", response.status())),
                         })
@@ -1843,6 +2148,7 @@ pub async fn test_steam_connection(
                     debug!("[RUST] ⚠️ API timeout, usando {} giochi installati", installed_games.len());
                     Ok(TestConnectionResult {
                         connected: true,
+                        has_credentials: true,
                         games_count: Some(installed_games.len() as u32),
                         error: Some(format!("API timeout: {}", e)),
                     })
@@ -1850,16 +2156,19 @@ pub async fn test_steam_connection(
             }
         },
         Ok(None) => {
-            // Nessuna credenziale salvata
+            // Nessuna credenziale salvata: resta disponibile la libreria locale
+            let installed_games = get_installed_steam_app_ids().await;
             Ok(TestConnectionResult {
                 connected: false,
-                games_count: None,
+                has_credentials: false,
+                games_count: Some(installed_games.len() as u32),
                 error: Some("Nessuna credenziale Steam salvata".to_string()),
             })
         },
         Err(e) => {
             Ok(TestConnectionResult {
                 connected: false,
+                has_credentials: false,
                 games_count: None,
                 error: Some(format!("Errore test connessione Steam: {}", e)),
             })
@@ -1927,11 +2236,13 @@ pub async fn force_refresh_steam_games(
 }
 
 #[tauri::command]
-pub async fn debug_steam_api_raw() -> Result<String, String> {
+pub async fn debug_steam_api_raw(
+    profile_state: tauri::State<'_, crate::commands::profiles::ProfileManagerState>
+) -> Result<String, String> {
     debug!("[RUST] 🔍 DEBUG: Testing raw Steam API access...");
     
     // Carica credenziali
-    let (api_key, steam_id) = match get_decrypted_api_key().await {
+    let (api_key, steam_id) = match load_steam_api_credentials(&profile_state).await {
         Ok((key, id)) => {
             debug!("[RUST] ✅ Credentials loaded for debug");
             (key, id)
@@ -1944,15 +2255,12 @@ pub async fn debug_steam_api_raw() -> Result<String, String> {
     // URL Steam API
     let url = format!(
         "https://api.steampowered.com/IPlayerService/GetOwnedGames/v0001/?key={}&steamid={}&format=json&include_appinfo=true&include_played_free_games=true&skip_unvetted_apps=false",
-        api_key, steam_id
+        api_key.expose(), steam_id
     );
     
     debug!("[RUST] 🌐 Calling Steam API directly...");
     
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Client creation error: {}", e))?;
+    let client = crate::http::client_for("steam_owned_games");
     
     match client.get(&url).send().await {
         Ok(response) => {
@@ -2015,29 +2323,21 @@ pub async fn get_steam_games(
         GAME_CACHE.invalidate_all();
     }
     
-    // 🔒 Se non vengono passate credenziali, prova a caricarle dal profilo attivo
+    // 🔒 Se non vengono passate credenziali, prova a caricarle dal profilo attivo (o dal file legacy)
     let (actual_api_key, actual_steam_id) = if api_key.is_empty() || steam_id.is_empty() {
         debug!("[RUST] 🔒 Caricamento credenziali dal profilo attivo...");
-        let manager = profile_state.manager.lock().await;
-        match manager.load_credential_for_active_profile(crate::profiles::StoreType::Steam).await {
-            Ok(Some(credential)) => {
-                let steam_id = credential.additional_data.get("steam_id")
-                    .unwrap_or(&credential.username)
-                    .clone();
-                debug!("[RUST] ✅ Credenziali caricate dal profilo attivo");
-                (credential.password, steam_id)
-            }
-            Ok(None) => {
-                debug!("[RUST] ⚠️ Nessuna credenziale Steam nel profilo attivo, uso parametri forniti");
-                (api_key, steam_id)
+        match load_steam_api_credentials(&profile_state).await {
+            Ok(credentials) => {
+                debug!("[RUST] ✅ Credenziali Steam caricate");
+                credentials
             }
             Err(e) => {
-                debug!("[RUST] ⚠️ Errore caricamento credenziali dal profilo: {}", e);
-                (api_key, steam_id)
+                debug!("[RUST] ⚠️ Nessuna credenziale Steam salvata ({}), uso parametri forniti", e);
+                (SecureString::from(api_key), steam_id)
             }
         }
     } else {
-        (api_key, steam_id)
+        (SecureString::from(api_key), steam_id)
     };
     
     // Se abbiamo API key e Steam ID, usa l'API reale
@@ -2047,17 +2347,14 @@ pub async fn get_steam_games(
         
         let url = format!(
             "https://api.steampowered.com/IPlayerService/GetOwnedGames/v0001/?key={}&steamid={}&format=json&include_appinfo=true&include_played_free_games=true&skip_unvetted_apps=false",
-            actual_api_key, actual_steam_id
+            actual_api_key.expose(), actual_steam_id
         );
         
         // 🔧 FIX: Crea client con timeout configurato
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(|e| format!("Errore creazione client HTTP: {}", e))?;
+        let client = crate::http::client_for("steam_owned_games");
         
         debug!("[RUST] 📡 Chiamata Steam API con timeout 30s...");
-        match client.get(&url).send().await {
+        match crate::http::get_with_retry(&client, &url, "Steam GetOwnedGames").await {
             Ok(response) => {
                 let status = response.status();
                 debug!("[RUST] Steam API response status: {}", status);
@@ -2078,6 +2375,10 @@ pub async fn get_steam_games(
                             } else if let Some(games_array) = json["response"]["games"].as_array() {
                                 debug!("[RUST] ✅ Retrieved {} games from Steam API", games_array.len());
                             
+                            let engines = detect_game_engines(games_array.iter()
+                                .filter_map(|game| Some((game["appid"].as_u64()? as u32, game["name"].as_str()?.to_string())))
+                                .collect()).await;
+                            
                             let steam_games: Vec<SteamGame> = games_array.iter()
                                 .filter_map(|game| {
                                     if let (Some(appid), Some(name)) = (
@@ -2090,7 +2391,7 @@ pub async fn get_steam_games(
                                         // 🎮 Rilevamento intelligente delle caratteristiche
                                         let is_vr = detect_vr_game(&game_name, appid_u32);
                                         let is_installed = detect_steam_installation(appid_u32);
-                                        let engine = detect_game_engine(&game_name, appid_u32);
+                                        let engine = engines.get(&appid_u32).cloned().unwrap_or_else(|| "Unknown".to_string());
                                         let supported_languages = detect_supported_languages(&game_name, appid_u32);
                                         
                                         Some(SteamGame {
@@ -2151,10 +2452,10 @@ pub async fn get_steam_games(
     }
     
     // Fallback: Leggi dal file locale
-    debug!("[RUST] Falling back to local file: ../steam_owned_games.json");
+    let file_path = owned_games_json_path();
+    debug!("[RUST] Falling back to local file: {}", file_path.display());
     
-    let file_path = "../steam_owned_games.json";
-    let file_content = match std::fs::read_to_string(file_path) {
+    let file_content = match std::fs::read_to_string(&file_path) {
         Ok(content) => {
             debug!("[RUST] ✅ File loaded successfully, size: {} bytes", content.len());
             content
@@ -2182,6 +2483,9 @@ pub async fn get_steam_games(
     let mut all_games = Vec::new();
     if let Some(games) = games_data.as_array() {
         debug!("[RUST] Processing {} games from file...", games.len());
+        let engines = detect_game_engines(games.iter()
+            .filter_map(|game| Some((game["appid"].as_u64()? as u32, game["name"].as_str().unwrap_or("Unknown").to_string())))
+            .collect()).await;
         for game in games.iter() { // Processa tutti i giochi
             if let Some(appid) = game["appid"].as_u64() {
                 let game_name = game["name"].as_str().unwrap_or("Unknown").to_string();
@@ -2190,7 +2494,7 @@ pub async fn get_steam_games(
                 // 🎮 Rilevamento intelligente delle caratteristiche
                 let is_vr = detect_vr_game(&game_name, appid_u32);
                 let is_installed = detect_steam_installation(appid_u32);
-                let engine = detect_game_engine(&game_name, appid_u32);
+                let engine = engines.get(&appid_u32).cloned().unwrap_or_else(|| "Unknown".to_string());
                 let supported_languages = detect_supported_languages(&game_name, appid_u32);
                 let detected_genres = detect_game_genres(&game_name, appid_u32);
                 
@@ -2282,7 +2586,7 @@ async fn enrich_game_details(app_id: u32) -> Result<SteamGame, String> {
     
     let details_url = format!("https://store.steampowered.com/api/appdetails?appids={}&l=it", app_id);
     
-    let response = HTTP_CLIENT.get(&details_url)
+    let response = crate::http::client_for("steam_appdetails").get(&details_url)
         .send()
         .await
         .map_err(|e| format!("Failed to fetch game details: {}", e))?;
@@ -2386,6 +2690,9 @@ async fn enrich_game_details(app_id: u32) -> Result<SteamGame, String> {
         };
     }
     
+    // Tempi di completamento (HowLongToBeat)
+    enriched_game.how_long_to_beat = super::hltb_manager::lookup_hltb_data(&enriched_game.name).await;
+    
     // Cache the result
     GAME_CACHE.insert(app_id, enriched_game.clone()).await;
     
@@ -2405,14 +2712,6 @@ pub async fn get_game_details(appid: String) -> Result<serde_json::Value, String
     log::info!("🔍 Recupero dettagli per AppID: {}", appid);
     
     // Verifica cache
-    static DETAILS_CACHE: once_cell::sync::Lazy<moka::future::Cache<String, serde_json::Value>> = 
-        once_cell::sync::Lazy::new(|| {
-            moka::future::Cache::builder()
-                .max_capacity(1000)
-                .time_to_live(std::time::Duration::from_secs(3600)) // 1 ora
-                .build()
-        });
-    
     if let Some(cached) = DETAILS_CACHE.get(&appid).await {
         log::info!("📋 Dettagli per {} trovati nella cache", appid);
         return Ok(cached);
@@ -2420,11 +2719,12 @@ pub async fn get_game_details(appid: String) -> Result<serde_json::Value, String
     
     // Chiamata API Steam Store
     let url = format!("https://store.steampowered.com/api/appdetails?appids={}", appid);
-    let client = reqwest::Client::new();
+    let client = crate::http::client_for("steam_appdetails");
     
-    match client.get(&url).send().await {
+    match crate::http::get_with_retry(&client, &url, "Steam appdetails").await {
         Ok(response) => {
-            if response.status() == 403 || response.status() == 429 {
+            // 403 = blocco temporaneo dello Store, non viene ritentato
+            if response.status() == 403 {
                 log::warn!("⚠️ Rate limit raggiunto per {} (Status: {})", appid, response.status());
                 return Err("Rate limited by Steam".to_string());
             }
@@ -2467,6 +2767,10 @@ pub async fn get_game_details(appid: String) -> Result<serde_json::Value, String
                 Err(e) => Err(format!("Errore parsing JSON: {}", e))
             }
         }
+        Err(e) if e.status == Some(reqwest::StatusCode::TOO_MANY_REQUESTS) => {
+            log::warn!("⚠️ Rate limit raggiunto per {} ({})", appid, e);
+            Err("Rate limited by Steam".to_string())
+        }
         Err(e) => {
             log::error!("❌ Errore durante il recupero dei dettagli per {}: {}", appid, e);
             Err(format!("Errore di rete: {}", e))
@@ -2506,45 +2810,127 @@ fn parse_supported_languages(languages_string: &str) -> Vec<String> {
     unique_languages
 }
 
-#[tauri::command]
-pub async fn get_steam_cover(appid: String) -> Result<String, String> {
-    log::info!("🖼️ Recupero copertina per Steam App ID: {}", appid);
-    
-    // URL copertina Steam usando CDN Cloudflare (come RAI PAL)
-    // Formato capsule_231x87 per consistenza con RAI PAL
-    let cover_url = format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{}/capsule_231x87.jpg", appid);
-    
-    // Fallback a header.jpg se capsule non disponibile
-    let fallback_url = format!("https://cdn.akamai.steamstatic.com/steam/apps/{}/header.jpg", appid);
-    
-    // Verifica che l'immagine esista facendo una HEAD request
-    match HTTP_CLIENT.head(&cover_url).send().await {
-        Ok(response) => {
-            if response.status().is_success() {
-                log::info!("✅ Copertina Cloudflare trovata per App ID {}: {}", appid, cover_url);
-                Ok(cover_url)
-            } else {
-                log::info!("🔄 Provo fallback Akamai per App ID {}", appid);
-                // Prova con fallback URL
-                match HTTP_CLIENT.head(&fallback_url).send().await {
-                    Ok(fallback_response) if fallback_response.status().is_success() => {
-                        log::info!("✅ Copertina Akamai trovata per App ID {}: {}", appid, fallback_url);
-                        Ok(fallback_url)
-                    }
-                    _ => {
-                        log::warn!("⚠️ Nessuna copertina trovata per App ID {}", appid);
-                        // Fallback a copertina placeholder
-                        Ok(format!("https://via.placeholder.com/231x87/1a1a2e/16213e?text=Steam+{}", appid))
-                    }
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("❌ Errore verifica copertina per App ID {}: {}", appid, e);
-            // Fallback a copertina placeholder
-            Ok(format!("https://via.placeholder.com/460x215/1a1a2e/16213e?text=Steam+Game+{}", appid))
-        }
-    }
+/// Esito della verifica HEAD di una copertina (`url` None = nessuna immagine sul CDN)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CoverProbe {
+    url: Option<String>,
+    checked_at: i64,
+}
+
+/// Le copertine trovate restano valide 30 giorni, quelle mancanti 7 (Steam può aggiungerle)
+const COVER_FOUND_TTL_SECS: i64 = 30 * 24 * 3600;
+const COVER_MISSING_TTL_SECS: i64 = 7 * 24 * 3600;
+
+// Esiti delle verifiche per appid, persistiti in steam_covers.json tra una sessione e l'altra
+static COVER_PROBES: Lazy<std::sync::Mutex<HashMap<String, CoverProbe>>> =
+    Lazy::new(|| std::sync::Mutex::new(load_cover_probes()));
+static COVER_PROBES_DIRTY: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+fn cover_probes_path() -> Result<std::path::PathBuf, String> {
+    Ok(get_steam_status_path()?.with_file_name("steam_covers.json"))
+}
+
+fn load_cover_probes() -> HashMap<String, CoverProbe> {
+    cover_probes_path().ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Scrive la cache su disco solo se ci sono nuove verifiche
+fn save_cover_probes() {
+    if !COVER_PROBES_DIRTY.swap(false, std::sync::atomic::Ordering::Relaxed) {
+        return;
+    }
+    
+    let result = cover_probes_path().and_then(|path| {
+        let json = {
+            let probes = COVER_PROBES.lock().unwrap();
+            serde_json::to_string(&*probes).map_err(|e| e.to_string())?
+        };
+        fs::write(path, json).map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        log::warn!("⚠️ Cache copertine Steam non salvata: {}", e);
+    }
+}
+
+/// Esito ancora valido per l'appid, se presente
+fn cached_cover_probe(appid: &str) -> Option<CoverProbe> {
+    let probes = COVER_PROBES.lock().unwrap();
+    let probe = probes.get(appid)?;
+    let ttl = if probe.url.is_some() { COVER_FOUND_TTL_SECS } else { COVER_MISSING_TTL_SECS };
+    (chrono::Utc::now().timestamp() - probe.checked_at < ttl).then(|| probe.clone())
+}
+
+fn placeholder_cover(appid: &str) -> String {
+    format!("https://via.placeholder.com/231x87/1a1a2e/16213e?text=Steam+{}", appid)
+}
+
+/// HEAD su Cloudflare (capsule) e poi Akamai (header). Err solo per errori di rete,
+/// che non vengono memorizzati
+async fn probe_steam_cover(appid: &str) -> Result<Option<String>, String> {
+    // URL copertina Steam usando CDN Cloudflare (come RAI PAL), fallback a header.jpg su Akamai
+    let candidates = [
+        format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{}/capsule_231x87.jpg", appid),
+        format!("https://cdn.akamai.steamstatic.com/steam/apps/{}/header.jpg", appid),
+    ];
+    let client = crate::http::client_for("steam_cover_head");
+    let mut network_error = None;
+    
+    for url in candidates {
+        match client.head(&url).send().await {
+            Ok(response) if response.status().is_success() => return Ok(Some(url)),
+            Ok(_) => {}
+            Err(e) => network_error = Some(e.without_url().to_string()),
+        }
+    }
+    
+    match network_error {
+        Some(e) => Err(e),
+        None => Ok(None),
+    }
+}
+
+/// Copertina dalla cache su disco oppure verificata via HEAD (e memorizzata)
+async fn resolve_steam_cover(appid: &str, force_refresh: bool) -> Result<Option<String>, String> {
+    if !force_refresh {
+        if let Some(probe) = cached_cover_probe(appid) {
+            return Ok(probe.url);
+        }
+    }
+    
+    let url = probe_steam_cover(appid).await?;
+    COVER_PROBES.lock().unwrap().insert(appid.to_string(), CoverProbe {
+        url: url.clone(),
+        checked_at: chrono::Utc::now().timestamp(),
+    });
+    COVER_PROBES_DIRTY.store(true, std::sync::atomic::Ordering::Relaxed);
+    Ok(url)
+}
+
+#[tauri::command]
+pub async fn get_steam_cover(appid: String, force_refresh: Option<bool>) -> Result<String, String> {
+    log::info!("🖼️ Recupero copertina per Steam App ID: {}", appid);
+    
+    let result = resolve_steam_cover(&appid, force_refresh.unwrap_or(false)).await;
+    save_cover_probes();
+    
+    match result {
+        Ok(Some(cover_url)) => {
+            log::info!("✅ Copertina trovata per App ID {}: {}", appid, cover_url);
+            Ok(cover_url)
+        }
+        Ok(None) => {
+            log::warn!("⚠️ Nessuna copertina trovata per App ID {}", appid);
+            Ok(placeholder_cover(&appid))
+        }
+        Err(e) => {
+            log::error!("❌ Errore verifica copertina per App ID {}: {}", appid, e);
+            // Fallback a copertina placeholder
+            Ok(format!("https://via.placeholder.com/460x215/1a1a2e/16213e?text=Steam+Game+{}", appid))
+        }
+    }
 }
 
 // ============================================================================
@@ -2613,7 +2999,12 @@ async fn get_decrypted_api_key_from_profile(
 // 🔒 Funzione helper interna per get_steam_games
 /// FUTURE USE: Internal helper for Steam games retrieval with caching
 #[allow(dead_code)]
-async fn get_steam_games_internal(api_key: String, steam_id: String, force_refresh: Option<bool>) -> Result<Vec<SteamGame>, String> {
+async fn get_steam_games_internal(
+    api_key: String,
+    steam_id: String,
+    force_refresh: Option<bool>,
+    profile_state: &crate::commands::profiles::ProfileManagerState,
+) -> Result<Vec<SteamGame>, String> {
     let force = force_refresh.unwrap_or(false);
     debug!("[RUST] get_steam_games_internal called with force: {}", force);
     
@@ -2623,21 +3014,21 @@ async fn get_steam_games_internal(api_key: String, steam_id: String, force_refre
         GAME_CACHE.invalidate_all();
     }
     
-    // 🔒 Se non vengono passate credenziali, prova a caricarle dai file criptati (legacy)
+    // 🔒 Se non vengono passate credenziali, prova a caricarle dal profilo attivo (o dal file legacy)
     let (actual_api_key, actual_steam_id) = if api_key.is_empty() || steam_id.is_empty() {
-        debug!("[RUST] 🔒 Caricamento credenziali legacy...");
-        match get_decrypted_api_key().await {
+        debug!("[RUST] 🔒 Caricamento credenziali salvate...");
+        match load_steam_api_credentials(profile_state).await {
             Ok((key, id)) => {
-                debug!("[RUST] ✅ Credenziali legacy caricate");
+                debug!("[RUST] ✅ Credenziali Steam caricate");
                 (key, id)
             }
             Err(e) => {
-                debug!("[RUST] ⚠️ Impossibile caricare credenziali legacy: {}", e);
-                (api_key, steam_id)
+                debug!("[RUST] ⚠️ Impossibile caricare credenziali Steam: {}", e);
+                (SecureString::from(api_key), steam_id)
             }
         }
     } else {
-        (api_key, steam_id)
+        (SecureString::from(api_key), steam_id)
     };
     
     // Continua con la logica esistente...
@@ -2650,17 +3041,14 @@ async fn get_steam_games_internal(api_key: String, steam_id: String, force_refre
         
         let url = format!(
             "https://api.steampowered.com/IPlayerService/GetOwnedGames/v0001/?key={}&steamid={}&format=json&include_appinfo=true&include_played_free_games=true&skip_unvetted_apps=false",
-            actual_api_key, actual_steam_id
+            actual_api_key.expose(), actual_steam_id
         );
         
         // 🔧 FIX: Crea client con timeout configurato
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .map_err(|e| format!("Errore creazione client HTTP: {}", e))?;
+        let client = crate::http::client_for("steam_owned_games");
         
         debug!("[RUST] 📡 Chiamata Steam API con timeout 30s...");
-        match client.get(&url).send().await {
+        match crate::http::get_with_retry(&client, &url, "Steam GetOwnedGames").await {
             Ok(response) => {
                 let status = response.status();
                 debug!("[RUST] Steam API response status: {}", status);
@@ -2681,6 +3069,10 @@ async fn get_steam_games_internal(api_key: String, steam_id: String, force_refre
                             } else if let Some(games_array) = json["response"]["games"].as_array() {
                                 debug!("[RUST] ✅ Retrieved {} games from Steam API", games_array.len());
                             
+                            let engines = detect_game_engines(games_array.iter()
+                                .filter_map(|game| Some((game["appid"].as_u64()? as u32, game["name"].as_str()?.to_string())))
+                                .collect()).await;
+                            
                             let steam_games: Vec<SteamGame> = games_array.iter()
                                 .filter_map(|game| {
                                     if let (Some(appid), Some(name)) = (
@@ -2693,7 +3085,7 @@ async fn get_steam_games_internal(api_key: String, steam_id: String, force_refre
                                         // 🎮 Rilevamento intelligente delle caratteristiche
                                         let is_vr = detect_vr_game(&game_name, appid_u32);
                                         let is_installed = detect_steam_installation(appid_u32);
-                                        let engine = detect_game_engine(&game_name, appid_u32);
+                                        let engine = engines.get(&appid_u32).cloned().unwrap_or_else(|| "Unknown".to_string());
                                         let supported_languages = detect_supported_languages(&game_name, appid_u32);
                                         
                                         Some(SteamGame {
@@ -2754,10 +3146,10 @@ async fn get_steam_games_internal(api_key: String, steam_id: String, force_refre
     }
     
     // Fallback: Leggi dal file locale
-    debug!("[RUST] Falling back to local file: ../steam_owned_games.json");
+    let file_path = owned_games_json_path();
+    debug!("[RUST] Falling back to local file: {}", file_path.display());
     
-    let file_path = "../steam_owned_games.json";
-    let file_content = match std::fs::read_to_string(file_path) {
+    let file_content = match std::fs::read_to_string(&file_path) {
         Ok(content) => {
             debug!("[RUST] ✅ File loaded successfully, size: {} bytes", content.len());
             content
@@ -2785,6 +3177,9 @@ async fn get_steam_games_internal(api_key: String, steam_id: String, force_refre
     let mut all_games = Vec::new();
     if let Some(games) = games_data.as_array() {
         debug!("[RUST] Processing {} games from file...", games.len());
+        let engines = detect_game_engines(games.iter()
+            .filter_map(|game| Some((game["appid"].as_u64()? as u32, game["name"].as_str().unwrap_or("Unknown").to_string())))
+            .collect()).await;
         for game in games.iter() { // Processa tutti i giochi
             if let Some(appid) = game["appid"].as_u64() {
                 let game_name = game["name"].as_str().unwrap_or("Unknown").to_string();
@@ -2793,7 +3188,7 @@ async fn get_steam_games_internal(api_key: String, steam_id: String, force_refre
                 // 🎮 Rilevamento intelligente delle caratteristiche
                 let is_vr = detect_vr_game(&game_name, appid_u32);
                 let is_installed = detect_steam_installation(appid_u32);
-                let engine = detect_game_engine(&game_name, appid_u32);
+                let engine = engines.get(&appid_u32).cloned().unwrap_or_else(|| "Unknown".to_string());
                 let supported_languages = detect_supported_languages(&game_name, appid_u32);
                 
                 all_games.push(SteamGame {
@@ -2830,11 +3225,38 @@ async fn get_steam_games_internal(api_key: String, steam_id: String, force_refre
 }
 
 // 🔒 Funzione helper per compatibilità con il sistema legacy
-async fn get_decrypted_api_key() -> Result<(String, String), String> {
+async fn get_decrypted_api_key() -> Result<(SecureString, String), String> {
     // SECURITY FIX: Use secure credential loading with integrity verification
     load_credentials_securely()
 }
 
+/// Credenziali Steam (API key, Steam ID) per le chiamate API: profilo attivo, dove le porta
+/// `migrate_all_legacy_store_credentials`, poi il file legacy se non è ancora stato migrato
+pub(crate) async fn load_steam_api_credentials(
+    profile_state: &crate::commands::profiles::ProfileManagerState,
+) -> Result<(SecureString, String), String> {
+    let profile_credential = {
+        let manager = profile_state.manager.lock().await;
+        match manager.load_credential_for_active_profile(crate::profiles::StoreType::Steam).await {
+            Ok(credential) => credential,
+            Err(e) => {
+                debug!("[RUST] ⚠️ Errore caricamento credenziali dal profilo: {}", e);
+                None
+            }
+        }
+    };
+
+    match profile_credential {
+        Some(credential) => {
+            let steam_id = credential.additional_data.get("steam_id")
+                .unwrap_or(&credential.username)
+                .clone();
+            Ok((SecureString::from(credential.password), steam_id))
+        }
+        None => get_decrypted_api_key().await,
+    }
+}
+
 // Comando per caricare le credenziali Steam - AGGIORNATO PER PROFILI
 #[tauri::command]
 pub async fn load_steam_credentials(
@@ -3044,40 +3466,37 @@ pub async fn auto_connect_steam(
 }
 
 #[tauri::command]
-pub async fn get_steam_covers_batch(appids: Vec<String>) -> Result<HashMap<String, String>, String> {
+pub async fn get_steam_covers_batch(appids: Vec<String>, force_refresh: Option<bool>) -> Result<HashMap<String, String>, String> {
     log::info!("🖼️ Recupero copertine batch per {} giochi", appids.len());
     
+    let force = force_refresh.unwrap_or(false);
     let mut covers = HashMap::new();
+    let mut to_probe = Vec::new();
+    
+    // Prima la cache su disco: le copertine già verificate non richiedono HEAD
+    for appid in appids {
+        match cached_cover_probe(&appid).filter(|_| !force) {
+            Some(probe) => {
+                let cover_url = probe.url.unwrap_or_else(|| placeholder_cover(&appid));
+                covers.insert(appid, cover_url);
+            }
+            None => to_probe.push(appid),
+        }
+    }
+    log::info!("📋 {} copertine dalla cache, {} da verificare", covers.len(), to_probe.len());
     
     // Processa in batch di 10 per evitare troppi request simultanei
-    for chunk in appids.chunks(10) {
+    for chunk in to_probe.chunks(10) {
         let mut tasks = Vec::new();
         
         for appid in chunk {
             let appid_clone = appid.clone();
             let task = tokio::spawn(async move {
-                // URL copertina Steam usando CDN Cloudflare (come RAI PAL)
-                let cover_url = format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{}/capsule_231x87.jpg", appid_clone);
-                let fallback_url = format!("https://cdn.akamai.steamstatic.com/steam/apps/{}/header.jpg", appid_clone);
-                
-                // Prova prima con Cloudflare
-                match HTTP_CLIENT.head(&cover_url).send().await {
-                    Ok(response) if response.status().is_success() => {
-                        (appid_clone.clone(), cover_url)
-                    }
-                    _ => {
-                        // Fallback a Akamai
-                        match HTTP_CLIENT.head(&fallback_url).send().await {
-                            Ok(fallback_response) if fallback_response.status().is_success() => {
-                                (appid_clone.clone(), fallback_url)
-                            }
-                            _ => {
-                                // Fallback a placeholder
-                                (appid_clone.clone(), format!("https://via.placeholder.com/231x87/1a1a2e/16213e?text=Steam+{}", appid_clone))
-                            }
-                        }
-                    }
-                }
+                let cover_url = resolve_steam_cover(&appid_clone, true).await
+                    .ok()
+                    .flatten()
+                    .unwrap_or_else(|| placeholder_cover(&appid_clone));
+                (appid_clone, cover_url)
             });
             tasks.push(task);
         }
@@ -3093,6 +3512,7 @@ pub async fn get_steam_covers_batch(appids: Vec<String>) -> Result<HashMap<Strin
         tokio::time::sleep(Duration::from_millis(100)).await;
     }
     
+    save_cover_probes();
     log::info!("✅ Recuperate {} copertine", covers.len());
     Ok(covers)
 }
@@ -3338,35 +3758,10 @@ async fn read_owned_games_from_localconfig(steam_path: &str, user_id: &str) -> R
     let content = fs::read_to_string(&localconfig_path)
         .map_err(|e| format!("Errore lettura localconfig.vdf: {}", e))?;
     
-    let mut app_ids = Vec::new();
-    let mut in_apps_section = false;
-    
-    // Parse VDF per trovare la sezione "apps"
-    for line in content.lines() {
-        let line = line.trim();
-        
-        if line.contains("\"apps\"") {
-            in_apps_section = true;
-            continue;
-        }
-        
-        if in_apps_section && line.starts_with('"') && !line.contains("apps") {
-            // Il formato è: "123456"
-            if let Some(start) = line.find('"') {
-                if let Some(end) = line[start + 1..].find('"') {
-                    let app_id_str = &line[start + 1..start + 1 + end];
-                    if let Ok(app_id) = app_id_str.parse::<u32>() {
-                        app_ids.push(app_id);
-                    }
-                }
-            }
-        }
-        
-        // Fine della sezione apps
-        if in_apps_section && line == "}" {
-            break;
-        }
-    }
+    let app_ids = crate::vdf::parse_localconfig_apps(&content)
+        .into_iter()
+        .map(|app| app.appid)
+        .collect();
     
     Ok(app_ids)
 }
@@ -3386,45 +3781,15 @@ async fn read_library_folders(steam_path: &str) -> Result<Vec<String>, String> {
     let mut folders = vec![steam_path.to_string()]; // Directory principale sempre inclusa
     log::info!("📁 Directory Steam principale: {}", steam_path);
     
-    // Parse migliorato del VDF - gestisce anche strutture annidate
-    let mut _current_section = String::new();
-    let mut _brace_count = 0;
-    
-    for line in content.lines() {
-        let line = line.trim();
-        
-        if line.contains('{') {
-            _brace_count += 1;
-        }
-        if line.contains('}') {
-            _brace_count -= 1;
-        }
-        
-        // Cerca "path" in qualsiasi sezione
-        if line.starts_with("\"path\"") && line.contains("\"") {
-            if let Some(start) = line.find("\"path\"") {
-                let after_key = &line[start + 6..]; // Salta "path"
-                if let Some(value_start) = after_key.find('"') {
-                    if let Some(value_end) = after_key[value_start + 1..].find('"') {
-                        let path = &after_key[value_start + 1..value_start + 1 + value_end];
-                        
-                        // Normalizza il path (sostituisce \\ con /)
-                        let normalized_path = path.replace("\\\\", "\\");
-                        
-                        if Path::new(&normalized_path).exists() {
-                            log::info!("💾 Trovata libreria Steam: {}", normalized_path);
-                            folders.push(normalized_path);
-                        } else {
-                            log::warn!("⚠️ Path libreria non esiste: {}", normalized_path);
-                        }
-                    }
-                }
-            }
+    for library in crate::vdf::parse_library_folders(&content) {
+        if library.path.eq_ignore_ascii_case(steam_path) || folders.contains(&library.path) {
+            continue;
         }
-        
-        // Metodo alternativo: cerca anche pattern numerici come "1", "2", "3" che rappresentano librerie
-        if line.starts_with('"') && line.len() < 10 && line.chars().nth(1).map_or(false, |c| c.is_numeric()) {
-            _current_section = line.trim_matches('"').to_string();
+        if Path::new(&library.path).exists() {
+            log::info!("💾 Trovata libreria Steam: {}", library.path);
+            folders.push(library.path);
+        } else {
+            log::warn!("⚠️ Path libreria non esiste: {}", library.path);
         }
     }
     
@@ -3438,33 +3803,11 @@ async fn read_library_folders(steam_path: &str) -> Result<Vec<String>, String> {
 
 // Parse di un file .acf per creare GameInfo
 async fn parse_acf_to_gameinfo(file_path: &Path, library_path: &str) -> Result<GameInfo, String> {
-    let content = fs::read_to_string(file_path)
-        .map_err(|e| format!("Errore lettura ACF: {}", e))?;
+    let manifest = crate::vdf::AppManifest::parse_file(file_path)?;
     
-    let mut app_id = String::new();
-    let mut name = String::new();
-    let mut install_dir = String::new();
-    let mut _size_on_disk = String::new();
-    let mut last_updated = String::new();
-    
-    for line in content.lines() {
-        let line = line.trim();
-        if line.starts_with("\"appid\"") {
-            app_id = extract_quoted_value_vdf(line).unwrap_or_default();
-        } else if line.starts_with("\"name\"") {
-            name = extract_quoted_value_vdf(line).unwrap_or_default();
-        } else if line.starts_with("\"installdir\"") {
-            install_dir = extract_quoted_value_vdf(line).unwrap_or_default();
-        } else if line.starts_with("\"SizeOnDisk\"") {
-            _size_on_disk = extract_quoted_value_vdf(line).unwrap_or_default();
-        } else if line.starts_with("\"LastUpdated\"") {
-            last_updated = extract_quoted_value_vdf(line).unwrap_or_default();
-        }
-    }
-    
-    if app_id.is_empty() || name.is_empty() {
-        return Err("ACF invalido".to_string());
-    }
+    let app_id = manifest.appid.to_string();
+    let name = manifest.name.clone().ok_or("ACF invalido: nome mancante")?;
+    let install_dir = manifest.install_dir.clone().unwrap_or_default();
     
     // Costruisci il percorso completo del gioco
     let game_path = Path::new(library_path)
@@ -3478,6 +3821,20 @@ async fn parse_acf_to_gameinfo(file_path: &Path, library_path: &str) -> Result<G
     // Rileva engine (usa la funzione da games.rs)
     let engine = crate::commands::games::detect_game_engine(&name);
     
+    // Eseguibile principale per avvio diretto e injection: la ricerca scende nelle
+    // sottocartelle, quindi gira su un thread bloccante e non sul runtime async
+    let executable_path = {
+        let game_path = game_path.clone();
+        let engine = engine.clone();
+        tokio::task::spawn_blocking(move || {
+            crate::commands::games::find_game_executable(&game_path, engine.as_deref())
+        })
+        .await
+        .ok()
+        .flatten()
+        .map(|path| path.to_string_lossy().to_string())
+    };
+    
     // Genera URL immagini Steam
     let header_image = format!("https://cdn.akamai.steamstatic.com/steam/apps/{}/header.jpg", app_id);
     
@@ -3486,7 +3843,7 @@ async fn parse_acf_to_gameinfo(file_path: &Path, library_path: &str) -> Result<G
         title: name.clone(),
         platform: "Steam".to_string(),
         install_path: Some(game_path.to_string_lossy().to_string()),
-        executable_path: None, // TODO: potremmo cercare l'exe principale
+        executable_path,
         icon: Some(format!("https://cdn.akamai.steamstatic.com/steam/apps/{}/icon.jpg", app_id)),
         image_url: Some(header_image.clone()),
         header_image: Some(header_image),
@@ -3494,11 +3851,11 @@ async fn parse_acf_to_gameinfo(file_path: &Path, library_path: &str) -> Result<G
         steam_app_id: app_id.parse::<u32>().ok(),
         is_vr,
         engine,
-        last_played: last_updated.parse::<u64>().ok(),
+        last_played: manifest.last_updated,
         is_shared: false,
         supported_languages: {
             // Usa il sistema di detection lingue migliorato invece del fallback generico
-            let detected_langs = detect_supported_languages(&name, app_id.parse::<u32>().unwrap_or(0));
+            let detected_langs = detect_supported_languages(&name, manifest.appid);
             Some(detected_langs.split(',').map(|s| s.trim().to_lowercase()).collect())
         },
         genres: Some(vec!["Game".to_string()]), // Default, potremmo migliorare
@@ -3506,19 +3863,6 @@ async fn parse_acf_to_gameinfo(file_path: &Path, library_path: &str) -> Result<G
     })
 }
 
-// Helper per estrarre valori quotati da VDF
-fn extract_quoted_value_vdf(line: &str) -> Option<String> {
-    // Formato: "key"		"value"
-    let parts: Vec<&str> = line.split('\t').collect();
-    if parts.len() >= 2 {
-        let value_part = parts[1].trim();
-        if value_part.starts_with('"') && value_part.ends_with('"') {
-            return Some(value_part[1..value_part.len()-1].to_string());
-        }
-    }
-    None
-}
-
 // ================================================================================================
 // FUNZIONI PER LETTURA AVANZATA DI STEAM LOCALI
 // ================================================================================================
@@ -3552,24 +3896,16 @@ pub async fn test_single_acf() -> Result<String, String> {
                             debug_info.push_str(&format!("First 200 chars: {}\n", 
                                 content.chars().take(200).collect::<String>()));
                             
-                            // Prova parsing VDF
-                            match kv::from_str::<serde_json::Value>(&content) {
-                                Ok(vdf) => {
-                                    debug_info.push_str("VDF parsing: SUCCESS\n");
-                                    if let Some(app_state) = vdf.get("AppState") {
-                                        debug_info.push_str("AppState found\n");
-                                        if let Some(table) = app_state.as_object() {
-                                            for (key, value) in table.iter().take(5) {
-                                                debug_info.push_str(&format!("  {}: {:?}\n", key, value));
-                                            }
-                                        }
-                                    } else {
-                                        debug_info.push_str("No AppState found\n");
+                            // Albero VDF grezzo
+                            let vdf = crate::vdf::parse(&content);
+                            match vdf.get_obj("AppState") {
+                                Some(app_state) => {
+                                    debug_info.push_str("AppState found\n");
+                                    for (key, value) in app_state.iter().take(5) {
+                                        debug_info.push_str(&format!("  {}: {:?}\n", key, value));
                                     }
-                                },
-                                Err(e) => {
-                                    debug_info.push_str(&format!("VDF parsing FAILED: {}\n", e));
                                 }
+                                None => debug_info.push_str("No AppState found\n"),
                             }
                             
                             // Prova il nostro parser personalizzato
@@ -3653,23 +3989,338 @@ pub async fn debug_steam_paths() -> Result<String, String> {
     Ok(debug_info)
 }
 
-/// Ottiene tutti i giochi Steam dalla libreria locale
-#[tauri::command]
-pub async fn get_all_local_steam_games() -> Result<Vec<LocalGameInfo>, String> {
-    debug!("[RUST] get_all_local_steam_games called");
+/// Nome di un appid dallo Store (`name` None = lookup fallito, si riprova dopo il TTL)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AppNameEntry {
+    name: Option<String>,
+    checked_at: i64,
+}
+
+/// I nomi trovati restano validi 90 giorni, i lookup falliti si ritentano dopo 1 giorno
+const APP_NAME_FOUND_TTL_SECS: i64 = 90 * 24 * 3600;
+const APP_NAME_MISSING_TTL_SECS: i64 = 24 * 3600;
+/// Lookup Store massimi per scansione: i restanti vengono risolti alle scansioni successive
+const MAX_APP_NAME_LOOKUPS: usize = 40;
+/// Pausa tra i lookup Store in background (~40 al minuto)
+const APP_NAME_LOOKUP_INTERVAL: Duration = Duration::from_millis(1500);
+/// Backoff dopo un 429/403 dello Store: raddoppia a ogni risposta consecutiva
+const APP_NAME_BACKOFF_INITIAL_SECS: u64 = 60;
+const APP_NAME_MAX_BACKOFFS: u32 = 3;
+/// Nomi risolti per evento `local-steam-game-names`
+const APP_NAME_EVENT_BATCH: usize = 10;
+
+/// Evento con i nomi dei giochi risolti in background dopo una scansione locale
+pub const LOCAL_STEAM_GAME_NAMES_EVENT: &str = "local-steam-game-names";
+
+// Un solo lookup dei nomi in background alla volta
+static APP_NAME_LOOKUP_RUNNING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+// Nomi per appid, persistiti in steam_app_names.json
+static APP_NAMES: Lazy<std::sync::Mutex<HashMap<u32, AppNameEntry>>> =
+    Lazy::new(|| std::sync::Mutex::new(load_app_names()));
+
+fn app_names_path() -> Result<std::path::PathBuf, String> {
+    Ok(get_steam_status_path()?.with_file_name("steam_app_names.json"))
+}
+
+fn load_app_names() -> HashMap<u32, AppNameEntry> {
+    app_names_path().ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_app_names() {
+    let result = app_names_path().and_then(|path| {
+        let json = {
+            let names = APP_NAMES.lock().unwrap();
+            serde_json::to_string(&*names).map_err(|e| e.to_string())?
+        };
+        fs::write(path, json).map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        warn!("[RUST] ⚠️ Cache nomi giochi Steam non salvata: {}", e);
+    }
+}
+
+/// Voce della cache ancora valida per l'appid
+fn cached_app_name(appid: u32) -> Option<AppNameEntry> {
+    let names = APP_NAMES.lock().unwrap();
+    let entry = names.get(&appid)?;
+    let ttl = if entry.name.is_some() { APP_NAME_FOUND_TTL_SECS } else { APP_NAME_MISSING_TTL_SECS };
+    (chrono::Utc::now().timestamp() - entry.checked_at < ttl).then(|| entry.clone())
+}
+
+/// Errore di un lookup del nome: il rate limit interrompe la coda con backoff
+#[derive(Debug)]
+enum AppNameLookupError {
+    RateLimited,
+    Failed(String),
+}
+
+/// Nome dallo Store con appdetails (solo dati base). Gli errori non vengono memorizzati
+async fn fetch_app_name(client: &reqwest::Client, appid: u32) -> Result<Option<String>, AppNameLookupError> {
+    RATE_LIMITER.wait_for_next_request("appdetails_names").await;
+    if !RATE_LIMITER.is_request_allowed("appdetails_names") {
+        return Err(AppNameLookupError::RateLimited);
+    }
+    
+    let url = format!("https://store.steampowered.com/api/appdetails?appids={}&filters=basic", appid);
+    let response = crate::http::get_with_retry(client, &url, "steam_appdetails").await
+        .map_err(|e| match e.status {
+            Some(reqwest::StatusCode::TOO_MANY_REQUESTS) => AppNameLookupError::RateLimited,
+            _ => AppNameLookupError::Failed(e.to_string()),
+        })?;
+    let status = response.status();
+    if status == reqwest::StatusCode::FORBIDDEN {
+        // Lo Store risponde 403 anche quando limita le richieste
+        return Err(AppNameLookupError::RateLimited);
+    }
+    if !status.is_success() {
+        return Err(AppNameLookupError::Failed(format!("HTTP {}", status)));
+    }
     
-    let steam_path = match find_steam_path_from_registry().await {
-        Some(path) => {
-            debug!("[RUST] Steam path found: {}", path);
-            path
-        },
-        None => return Err("Steam non trovato nel sistema".to_string()),
+    let json: Value = response.json().await
+        .map_err(|e| AppNameLookupError::Failed(format!("Errore parsing appdetails: {}", e.without_url())))?;
+    let entry = &json[appid.to_string()];
+    if entry["success"].as_bool() != Some(true) {
+        return Ok(None);
+    }
+    Ok(entry["data"]["name"].as_str()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string))
+}
+
+/// Sostituisce i nomi segnaposto dei giochi non installati con quelli reali, solo da fonti
+/// offline (appinfo.vdf e cache). Restituisce gli appid da cercare sullo Store, al massimo
+/// `MAX_APP_NAME_LOOKUPS`: il lookup avviene in background con `spawn_app_name_lookups`
+fn enrich_local_game_names(
+    steam_path: &str,
+    games: &mut [LocalGameInfo],
+    placeholders: &HashSet<u32>,
+    reporter: Option<&LocalScanReporter<'_>>,
+) -> Vec<u32> {
+    if placeholders.is_empty() {
+        return Vec::new();
+    }
+    
+    let app_infos = crate::appinfo::load_app_infos(Path::new(steam_path));
+    let mut pending = Vec::new();
+    let mut resolved = 0;
+    
+    for (index, game) in games.iter_mut().filter(|g| placeholders.contains(&g.appid)).enumerate() {
+        if let Some(reporter) = reporter {
+            if reporter.is_cancelled() {
+                break;
+            }
+            if index % 25 == 0 {
+                reporter.emit(LocalScanPhase::ResolvingNames, index, placeholders.len(), None);
+            }
+        }
+        
+        if let Some(name) = app_infos.get(&game.appid).and_then(|app| app.name.clone()) {
+            game.name = name;
+            resolved += 1;
+            continue;
+        }
+        
+        match cached_app_name(game.appid) {
+            Some(AppNameEntry { name: Some(name), .. }) => {
+                game.name = name;
+                resolved += 1;
+            }
+            Some(_) => {}
+            None if pending.len() < MAX_APP_NAME_LOOKUPS => pending.push(game.appid),
+            None => {}
+        }
+    }
+    
+    info!("[RUST] 🏷️ Nomi risolti offline per {}/{} giochi non installati ({} da cercare sullo Store)",
+          resolved, placeholders.len(), pending.len());
+    pending
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ResolvedGameName {
+    pub appid: u32,
+    pub name: String,
+}
+
+/// Nomi risolti in background; `done` segna l'ultimo evento del lookup
+#[derive(Serialize, Debug, Clone)]
+pub struct LocalSteamGameNames {
+    pub scan_id: Option<String>,
+    pub names: Vec<ResolvedGameName>,
+    pub done: bool,
+}
+
+/// Avvia in background il lookup Store degli appid, se non ce n'è già uno in corso.
+/// Con `app` emette `local-steam-game-names` man mano che i nomi vengono risolti;
+/// senza, aggiorna solo la cache usata dalle scansioni successive
+fn spawn_app_name_lookups(appids: Vec<u32>, app: Option<tauri::AppHandle>, scan_id: Option<String>) {
+    use std::sync::atomic::Ordering;
+    
+    if appids.is_empty() {
+        return;
+    }
+    if APP_NAME_LOOKUP_RUNNING.swap(true, Ordering::SeqCst) {
+        debug!("[RUST] 🏷️ Lookup nomi già in corso, {} appid rimandati", appids.len());
+        return;
+    }
+    
+    tauri::async_runtime::spawn(async move {
+        lookup_app_names(&appids, app.as_ref(), scan_id).await;
+        APP_NAME_LOOKUP_RUNNING.store(false, Ordering::SeqCst);
+    });
+}
+
+async fn lookup_app_names(appids: &[u32], app: Option<&tauri::AppHandle>, scan_id: Option<String>) {
+    use tauri::Emitter;
+    
+    let emit = |names: Vec<ResolvedGameName>, done: bool| {
+        let Some(app) = app else { return };
+        let payload = LocalSteamGameNames { scan_id: scan_id.clone(), names, done };
+        if let Err(e) = app.emit(LOCAL_STEAM_GAME_NAMES_EVENT, &payload) {
+            debug!("[RUST] ⚠️ Errore emissione nomi giochi: {}", e);
+        }
     };
     
+    let client = crate::http::client_for("steam_appdetails");
+    let mut batch = Vec::new();
+    let mut backoffs = 0;
+    let mut lookups = 0;
+    let mut index = 0;
+    
+    while index < appids.len() {
+        let appid = appids[index];
+        match fetch_app_name(&client, appid).await {
+            Ok(name) => {
+                lookups += 1;
+                backoffs = 0;
+                index += 1;
+                APP_NAMES.lock().unwrap().insert(appid, AppNameEntry {
+                    name: name.clone(),
+                    checked_at: chrono::Utc::now().timestamp(),
+                });
+                if let Some(name) = name {
+                    batch.push(ResolvedGameName { appid, name });
+                }
+            }
+            Err(AppNameLookupError::RateLimited) => {
+                if backoffs >= APP_NAME_MAX_BACKOFFS {
+                    warn!("[RUST] ⚠️ Store in rate limit, {} nomi rimandati alla prossima scansione",
+                          appids.len() - index);
+                    break;
+                }
+                let delay = APP_NAME_BACKOFF_INITIAL_SECS << backoffs;
+                backoffs += 1;
+                warn!("[RUST] ⏳ Rate limit appdetails, nuovo tentativo tra {}s", delay);
+                tokio::time::sleep(Duration::from_secs(delay)).await;
+                continue;
+            }
+            Err(AppNameLookupError::Failed(e)) => {
+                debug!("[RUST] ⚠️ Nome non disponibile per {}: {}", appid, e);
+                index += 1;
+            }
+        }
+        
+        if batch.len() >= APP_NAME_EVENT_BATCH {
+            save_app_names();
+            emit(std::mem::take(&mut batch), false);
+        }
+        if index < appids.len() {
+            tokio::time::sleep(APP_NAME_LOOKUP_INTERVAL).await;
+        }
+    }
+    
+    if lookups > 0 {
+        save_app_names();
+    }
+    emit(batch, true);
+    info!("[RUST] 🏷️ Lookup nomi in background completato ({} lookup Store)", lookups);
+}
+
+/// Evento di avanzamento della scansione locale Steam
+pub const LOCAL_STEAM_SCAN_PROGRESS_EVENT: &str = "local-steam-scan-progress";
+
+/// Token di annullamento per le scansioni locali attive (scan_id -> flag)
+static LOCAL_STEAM_SCANS: Lazy<std::sync::Mutex<HashMap<String, std::sync::Arc<std::sync::atomic::AtomicBool>>>> =
+    Lazy::new(|| std::sync::Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalScanPhase {
+    ScanningInstalled,
+    ResolvingOwned,
+    FetchingShared,
+    ResolvingNames,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LocalSteamScanProgress {
+    pub scan_id: String,
+    pub phase: LocalScanPhase,
+    /// Elementi elaborati nella fase corrente (librerie o giochi)
+    pub current: usize,
+    pub total: usize,
+    pub games_found: usize,
+    /// Libreria in scansione (solo fase scanning_installed)
+    pub library: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct LocalSteamScanResult {
+    pub scan_id: String,
+    pub games: Vec<LocalGameInfo>,
+    /// true se annullata: `games` contiene i risultati parziali
+    pub cancelled: bool,
+}
+
+/// Emette l'avanzamento di una scansione e ne espone l'annullamento
+struct LocalScanReporter<'a> {
+    app: &'a tauri::AppHandle,
+    scan_id: &'a str,
+    cancel: &'a std::sync::atomic::AtomicBool,
+    games_found: std::sync::atomic::AtomicUsize,
+}
+
+impl LocalScanReporter<'_> {
+    fn is_cancelled(&self) -> bool {
+        self.cancel.load(std::sync::atomic::Ordering::Relaxed)
+    }
+    
+    fn set_games_found(&self, count: usize) {
+        self.games_found.store(count, std::sync::atomic::Ordering::Relaxed);
+    }
+    
+    fn emit(&self, phase: LocalScanPhase, current: usize, total: usize, library: Option<&str>) {
+        use tauri::Emitter;
+        
+        let progress = LocalSteamScanProgress {
+            scan_id: self.scan_id.to_string(),
+            phase,
+            current,
+            total,
+            games_found: self.games_found.load(std::sync::atomic::Ordering::Relaxed),
+            library: library.map(str::to_string),
+        };
+        if let Err(e) = self.app.emit(LOCAL_STEAM_SCAN_PROGRESS_EVENT, &progress) {
+            debug!("[RUST] ⚠️ Errore emissione avanzamento scansione: {}", e);
+        }
+    }
+}
+
+/// Raccoglie installati, posseduti e condivisi. Con `reporter` emette l'avanzamento e si
+/// interrompe all'annullamento restituendo quanto trovato (secondo valore = annullata)
+async fn collect_local_steam_games(steam_path: &str, reporter: Option<&LocalScanReporter<'_>>) -> (Vec<LocalGameInfo>, bool) {
+    let cancelled = || reporter.is_some_and(|r| r.is_cancelled());
     let mut all_games: Vec<LocalGameInfo> = Vec::new();
     
     // 1. Trova tutte le librerie Steam
-    let library_folders = match parse_library_folders(&steam_path) {
+    let library_folders = match parse_library_folders(steam_path) {
         Ok(folders) => {
             debug!("[RUST] Found {} library folders", folders.len());
             folders
@@ -3678,7 +4329,7 @@ pub async fn get_all_local_steam_games() -> Result<Vec<LocalGameInfo>, String> {
             debug!("[RUST] Errore parsing library folders: {}", e);
             // Fallback: usa solo la cartella Steam principale
             vec![SteamLibraryFolder {
-                path: steam_path.clone(),
+                path: steam_path.to_string(),
                 label: "Main".to_string(),
                 mounted: true,
                 tool: "0".to_string(),
@@ -3686,38 +4337,48 @@ pub async fn get_all_local_steam_games() -> Result<Vec<LocalGameInfo>, String> {
         }
     };
     
-    // 2. Scansiona giochi installati
-    let installed_games = match find_installed_games(&library_folders) {
-        Ok(games) => {
-            debug!("[RUST] Found {} installed games", games.len());
-            games
-        },
-        Err(e) => {
-            debug!("[RUST] Errore find_installed_games: {}", e);
-            // Fallback: scansione diretta cartella steamapps
-            let mut fallback_games = Vec::new();
-            let steamapps_path = Path::new(&steam_path).join("steamapps");
-            if steamapps_path.exists() {
-                if let Ok(entries) = fs::read_dir(&steamapps_path) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                            if filename.starts_with("appmanifest_") && filename.ends_with(".acf") {
-                                if let Ok(game_info) = parse_acf_file(&path) {
-                                    fallback_games.push(game_info);
-                                }
-                            }
+    // 2. Scansiona giochi installati, una libreria alla volta
+    for (index, folder) in library_folders.iter().enumerate() {
+        if cancelled() {
+            return (all_games, true);
+        }
+        if let Some(reporter) = reporter {
+            reporter.emit(LocalScanPhase::ScanningInstalled, index, library_folders.len(), Some(&folder.path));
+        }
+        
+        match find_installed_games(std::slice::from_ref(folder)) {
+            Ok(games) => all_games.extend(games),
+            Err(e) => debug!("[RUST] Errore find_installed_games in {}: {}", folder.path, e),
+        }
+        if let Some(reporter) = reporter {
+            reporter.set_games_found(all_games.len());
+        }
+    }
+    debug!("[RUST] Found {} installed games", all_games.len());
+    
+    if all_games.is_empty() {
+        // Fallback: scansione diretta cartella steamapps
+        let steamapps_path = Path::new(steam_path).join("steamapps");
+        if let Ok(entries) = fs::read_dir(&steamapps_path) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
+                    if filename.starts_with("appmanifest_") && filename.ends_with(".acf") {
+                        if let Ok(game_info) = parse_acf_file(&path) {
+                            all_games.push(game_info);
                         }
                     }
                 }
             }
-            debug!("[RUST] Fallback found {} games", fallback_games.len());
-            fallback_games
         }
-    };
+        debug!("[RUST] Fallback found {} games", all_games.len());
+    }
     
     // 3. Trova giochi posseduti (non installati)
-    let owned_game_ids = match parse_owned_games(&steam_path) {
+    if cancelled() {
+        return (all_games, true);
+    }
+    let owned_game_ids = match parse_owned_games(steam_path) {
         Ok(games) => {
             debug!("[RUST] Found {} owned games from config", games.len());
             games
@@ -3728,27 +4389,21 @@ pub async fn get_all_local_steam_games() -> Result<Vec<LocalGameInfo>, String> {
         }
     };
     
-    // 4. Trova giochi condivisi
-    let shared_games = match parse_shared_config(&steam_path) {
-        Ok(games) => {
-            debug!("[RUST] Found {} shared game sources", games.len());
-            games
-        },
-        Err(e) => {
-            debug!("[RUST] Errore parse_shared_config: {}", e);
-            HashMap::new() // Fallback vuoto per ora
-        }
-    };
-    
-    // 5. Unisci tutti i dati
-    all_games.extend(installed_games.clone());
+    // Appid con nome segnaposto, da arricchire con il nome reale
+    let mut placeholder_names = HashSet::new();
     
     // Aggiungi giochi posseduti (evita duplicati con quelli installati)
-    for appid in owned_game_ids {
+    for (index, appid) in owned_game_ids.iter().copied().enumerate() {
+        if let Some(reporter) = reporter {
+            if index % 100 == 0 {
+                reporter.emit(LocalScanPhase::ResolvingOwned, index, owned_game_ids.len(), None);
+            }
+        }
         if !all_games.iter().any(|g| g.appid == appid) {
+            placeholder_names.insert(appid);
             all_games.push(LocalGameInfo {
                 appid,
-                name: format!("Game {}", appid), // Placeholder - andrebbe enrichito con nome reale
+                name: format!("Game {}", appid),
                 status: GameStatus::Owned,
                 install_dir: None,
                 last_updated: None,
@@ -3757,14 +4412,37 @@ pub async fn get_all_local_steam_games() -> Result<Vec<LocalGameInfo>, String> {
             });
         }
     }
+    if let Some(reporter) = reporter {
+        reporter.set_games_found(all_games.len());
+        reporter.emit(LocalScanPhase::ResolvingOwned, owned_game_ids.len(), owned_game_ids.len(), None);
+    }
+    
+    // 4. Trova giochi condivisi
+    if cancelled() {
+        return (all_games, true);
+    }
+    let shared_games = match parse_shared_config(steam_path) {
+        Ok(games) => {
+            debug!("[RUST] Found {} shared game sources", games.len());
+            games
+        },
+        Err(e) => {
+            debug!("[RUST] Errore parse_shared_config: {}", e);
+            HashMap::new() // Fallback vuoto per ora
+        }
+    };
     
-    // Aggiungi giochi condivisi (evita duplicati)
-    for (lender_id, shared_app_ids) in shared_games {
+    let lenders = shared_games.len();
+    for (index, (lender_id, shared_app_ids)) in shared_games.into_iter().enumerate() {
+        if let Some(reporter) = reporter {
+            reporter.emit(LocalScanPhase::FetchingShared, index, lenders, None);
+        }
         for appid in shared_app_ids {
             if !all_games.iter().any(|g| g.appid == appid) {
+                placeholder_names.insert(appid);
                 all_games.push(LocalGameInfo {
                     appid,
-                    name: format!("Shared Game {}", appid), // Placeholder
+                    name: format!("Shared Game {}", appid),
                     status: GameStatus::Shared { from_steam_id: lender_id.clone() },
                     install_dir: None,
                     last_updated: None,
@@ -3774,9 +4452,88 @@ pub async fn get_all_local_steam_games() -> Result<Vec<LocalGameInfo>, String> {
             }
         }
     }
+    if let Some(reporter) = reporter {
+        reporter.set_games_found(all_games.len());
+        reporter.emit(LocalScanPhase::FetchingShared, lenders, lenders, None);
+    }
+    
+    // 5. Nomi reali per i giochi non installati: offline subito, dallo Store in background
+    let pending_names = enrich_local_game_names(steam_path, &mut all_games, &placeholder_names, reporter);
+    if !cancelled() {
+        spawn_app_name_lookups(
+            pending_names,
+            reporter.map(|r| r.app.clone()),
+            reporter.map(|r| r.scan_id.to_string()),
+        );
+    }
     
     debug!("[RUST] Total games found: {}", all_games.len());
-    Ok(all_games)
+    (all_games, cancelled())
+}
+
+/// Ottiene tutti i giochi Steam dalla libreria locale
+#[tauri::command]
+pub async fn get_all_local_steam_games() -> Result<Vec<LocalGameInfo>, String> {
+    debug!("[RUST] get_all_local_steam_games called");
+    
+    let steam_path = match find_steam_path_from_registry().await {
+        Some(path) => {
+            debug!("[RUST] Steam path found: {}", path);
+            path
+        },
+        None => return Err("Steam non trovato nel sistema".to_string()),
+    };
+    
+    let (games, _) = collect_local_steam_games(&steam_path, None).await;
+    Ok(games)
+}
+
+/// Come `get_all_local_steam_games`, ma emette `local-steam-scan-progress` per ogni fase e
+/// può essere annullata con `cancel_local_steam_scan(scan_id)`: in quel caso restituisce
+/// i giochi trovati fino a quel momento. I nomi cercati sullo Store arrivano dopo, con
+/// `local-steam-game-names` (stesso scan_id)
+#[tauri::command]
+pub async fn get_all_local_steam_games_with_progress(
+    app: tauri::AppHandle,
+    scan_id: Option<String>,
+) -> Result<LocalSteamScanResult, String> {
+    let scan_id = scan_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    debug!("[RUST] get_all_local_steam_games_with_progress called ({})", scan_id);
+    
+    let steam_path = find_steam_path_from_registry().await
+        .ok_or("Steam non trovato nel sistema")?;
+    
+    let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    LOCAL_STEAM_SCANS.lock().unwrap().insert(scan_id.clone(), cancel.clone());
+    
+    let reporter = LocalScanReporter {
+        app: &app,
+        scan_id: &scan_id,
+        cancel: &cancel,
+        games_found: std::sync::atomic::AtomicUsize::new(0),
+    };
+    let (games, cancelled) = collect_local_steam_games(&steam_path, Some(&reporter)).await;
+    LOCAL_STEAM_SCANS.lock().unwrap().remove(&scan_id);
+    
+    reporter.set_games_found(games.len());
+    let phase = if cancelled { LocalScanPhase::Cancelled } else { LocalScanPhase::Completed };
+    reporter.emit(phase, games.len(), games.len(), None);
+    info!("[RUST] 📊 Scansione locale {} terminata: {} giochi (annullata: {})", scan_id, games.len(), cancelled);
+    
+    Ok(LocalSteamScanResult { scan_id, games, cancelled })
+}
+
+/// Annulla una scansione locale attiva; false se lo scan_id non esiste (o è già terminata)
+#[tauri::command]
+pub async fn cancel_local_steam_scan(scan_id: String) -> Result<bool, String> {
+    match LOCAL_STEAM_SCANS.lock().unwrap().get(&scan_id) {
+        Some(cancel) => {
+            cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+            info!("[RUST] ⏹️ Annullamento scansione locale {} richiesto", scan_id);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
 }
 
 /// Parsa libraryfolders.vdf per trovare tutte le librerie Steam
@@ -3806,20 +4563,18 @@ fn parse_library_folders(steam_path: &str) -> Result<Vec<SteamLibraryFolder>, St
 
 /// Parsa il contenuto del file libraryfolders.vdf
 fn parse_library_folders_content(content: &str) -> Result<Vec<SteamLibraryFolder>, String> {
-    let vdf = kv::from_str::<LibraryFoldersVdf>(content)
-        .map_err(|e| format!("Errore parsing libraryfolders.vdf: {}", e))?;
-    
-    let mut folders = Vec::new();
-    
-    for (_key, folder_data) in vdf.libraryfolders.iter() {
-        if let Some(path) = &folder_data.path {
-            folders.push(SteamLibraryFolder {
-                path: path.clone(),
-                label: folder_data.label.as_deref().unwrap_or("").to_string(),
-                mounted: folder_data.mounted.as_deref().unwrap_or("1") == "1",
-                tool: folder_data.tool.as_deref().unwrap_or("0").to_string(),
-            });
-        }
+    let folders: Vec<SteamLibraryFolder> = crate::vdf::parse_library_folders(content)
+        .into_iter()
+        .map(|folder| SteamLibraryFolder {
+            path: folder.path,
+            label: folder.label.unwrap_or_default(),
+            mounted: folder.mounted,
+            tool: folder.tool.unwrap_or_else(|| "0".to_string()),
+        })
+        .collect();
+    
+    if folders.is_empty() {
+        return Err("Nessuna libreria trovata in libraryfolders.vdf".to_string());
     }
     
     Ok(folders)
@@ -3850,148 +4605,50 @@ fn find_installed_games(library_folders: &[SteamLibraryFolder]) -> Result<Vec<Lo
     Ok(games)
 }
 
-/// Parser ACF personalizzato (non usa steamy-vdf)
-/// Ora rileva anche giochi condivisi tramite Family Sharing
-fn parse_acf_file_custom(content: &str) -> Result<LocalGameInfo, String> {
-    let mut appid = None;
-    let mut name = None;
-    let mut install_dir = None;
-    let mut last_updated = None;
-    let mut size_on_disk = None;
-    let mut buildid = None;
-    let mut state_flags = None;
-    let mut last_owner = None;
-    
-    // Parse manuale del formato VDF
-    for line in content.lines() {
-        let line = line.trim();
-        
-        if line.contains("\"appid\"") {
-            if let Some(value) = extract_quoted_value(line) {
-                appid = value.parse::<u32>().ok();
-            }
-        } else if line.contains("\"name\"") {
-            name = extract_quoted_value(line);
-        } else if line.contains("\"installdir\"") {
-            install_dir = extract_quoted_value(line);
-        } else if line.contains("\"LastUpdated\"") {
-            if let Some(value) = extract_quoted_value(line) {
-                last_updated = value.parse::<u64>().ok();
-            }
-        } else if line.contains("\"SizeOnDisk\"") {
-            if let Some(value) = extract_quoted_value(line) {
-                size_on_disk = value.parse::<u64>().ok();
-            }
-        } else if line.contains("\"buildid\"") {
-            if let Some(value) = extract_quoted_value(line) {
-                buildid = value.parse::<u32>().ok();
-            }
-        } else if line.contains("\"StateFlags\"") {
-            if let Some(value) = extract_quoted_value(line) {
-                state_flags = value.parse::<u32>().ok();
-            }
-        } else if line.contains("\"LastOwner\"") {
-            last_owner = extract_quoted_value(line);
-        }
-    }
-    
-    let appid_value = appid.ok_or("AppID non trovato")?;
-    let name = name.unwrap_or_else(|| format!("Game {}", appid_value));
-    
-    let install_path = install_dir.as_ref()
-        .map(|dir| format!("C:\\Program Files (x86)\\Steam\\steamapps\\common\\{}", dir))
-        .unwrap_or_else(|| "Unknown".to_string());
+/// Converte un manifest ACF in `LocalGameInfo`.
+/// Rileva anche giochi condivisi tramite Family Sharing
+fn local_game_from_manifest(manifest: crate::vdf::AppManifest, steamapps_dir: Option<&Path>) -> LocalGameInfo {
+    let name = manifest.name.unwrap_or_else(|| format!("Game {}", manifest.appid));
+    
+    let install_path = match (&manifest.install_dir, steamapps_dir) {
+        (Some(dir), Some(steamapps)) => steamapps.join("common").join(dir).to_string_lossy().to_string(),
+        (Some(dir), None) => format!("C:\\Program Files (x86)\\Steam\\steamapps\\common\\{}", dir),
+        (None, _) => "Unknown".to_string(),
+    };
     
     // Rileva giochi condivisi tramite Family Sharing
     // StateFlags: 4 = fully installed, 1026 = shared content
     // LastOwner diverso da 0 indica che il gioco potrebbe essere condiviso
-    let is_shared = state_flags.map(|f| f & 1024 != 0).unwrap_or(false) 
-                    || last_owner.as_ref().map(|o| !o.is_empty() && o != "0").unwrap_or(false);
+    let is_shared = manifest.state_flags.is_some_and(|f| f & 1024 != 0)
+                    || manifest.last_owner.as_ref().is_some_and(|o| o != "0");
     
     let status = if is_shared {
-        GameStatus::Shared { from_steam_id: last_owner.unwrap_or_default() }
+        GameStatus::Shared { from_steam_id: manifest.last_owner.unwrap_or_default() }
     } else {
         GameStatus::Installed { path: install_path }
     };
     
-    Ok(LocalGameInfo {
-        appid: appid_value,
+    LocalGameInfo {
+        appid: manifest.appid,
         name,
         status,
-        install_dir,
-        last_updated,
-        size_on_disk,
-        buildid,
-    })
+        install_dir: manifest.install_dir,
+        last_updated: manifest.last_updated,
+        size_on_disk: manifest.size_on_disk,
+        buildid: manifest.buildid,
+    }
 }
 
-/// Estrae valore tra virgolette da una linea VDF
-fn extract_quoted_value(line: &str) -> Option<String> {
-    // Trova l'ultima coppia di virgolette (il valore)
-    let parts: Vec<&str> = line.split('"').collect();
-    if parts.len() >= 4 {
-        Some(parts[parts.len() - 2].to_string())
-    } else {
-        None
-    }
+/// Parser ACF dal contenuto (percorso di installazione sulla libreria di default)
+fn parse_acf_file_custom(content: &str) -> Result<LocalGameInfo, String> {
+    let manifest = crate::vdf::AppManifest::parse(content)?;
+    Ok(local_game_from_manifest(manifest, None))
 }
 
 /// Parsa un file .acf per estrarre informazioni sul gioco
 fn parse_acf_file(acf_path: &Path) -> Result<LocalGameInfo, String> {
-    let content = fs::read_to_string(acf_path)
-        .map_err(|e| format!("Errore lettura file ACF: {}", e))?;
-    
-    // Prima prova il nostro parser personalizzato
-    if let Ok(game) = parse_acf_file_custom(&content) {
-        return Ok(game);
-    }
-    
-    // Fallback: prova keyvalues-serde
-    let vdf = kv::from_str::<AcfFile>(&content)
-        .map_err(|e| format!("Errore parsing file ACF: {}", e))?;
-    
-    let appid = vdf.app_state.appid
-        .as_ref()
-        .and_then(|id| id.parse::<u32>().ok())
-        .ok_or("AppID non valido")?;
-    
-    let name = vdf.app_state.name
-        .as_deref()
-        .unwrap_or("Unknown Game")
-        .to_string();
-        
-    let install_dir = vdf.app_state.install_dir.clone();
-    
-    let last_updated = vdf.app_state.last_updated
-        .as_ref()
-        .and_then(|lu| lu.parse::<u64>().ok());
-    
-    let size_on_disk = vdf.app_state.size_on_disk
-        .as_ref()
-        .and_then(|sod| sod.parse::<u64>().ok());
-    
-    let buildid = None; // buildid non è nella struct, possiamo aggiungerlo se necessario
-        
-    let install_path = install_dir.as_ref().map(|dir| {
-        acf_path.parent()
-            .unwrap_or_else(|| Path::new(""))
-            .join("common")
-            .join(dir)
-            .to_string_lossy()
-            .to_string()
-    });
-    
-    Ok(LocalGameInfo {
-        appid,
-        name,
-        status: GameStatus::Installed { 
-            path: install_path.unwrap_or_else(|| "Unknown".to_string()) 
-        },
-        install_dir,
-        last_updated,
-        size_on_disk,
-        buildid,
-    })
+    let manifest = crate::vdf::AppManifest::parse_file(acf_path)?;
+    Ok(local_game_from_manifest(manifest, acf_path.parent()))
 }
 
 /// Parsa sharedconfig.vdf per trovare giochi condivisi
@@ -4006,8 +4663,7 @@ fn parse_shared_config(steam_path: &str) -> Result<HashMap<String, Vec<u32>>, St
     let content = fs::read_to_string(shared_config_path)
         .map_err(|e| format!("Errore lettura sharedconfig.vdf: {}", e))?;
     
-    let vdf = kv::from_str::<serde_json::Value>(&content)
-        .map_err(|e| format!("Errore parsing sharedconfig.vdf: {}", e))?;
+    let vdf = crate::vdf::parse(&content);
     
     let mut shared_games = HashMap::new();
     
@@ -4016,21 +4672,16 @@ fn parse_shared_config(steam_path: &str) -> Result<HashMap<String, Vec<u32>>, St
     // sotto chiavi come "SharedContent" o dentro le configurazioni degli utenti
     
     // Implementazione semplificata: cerca pattern comuni
-    if let Some(root) = vdf.as_object() {
-        for (key, value) in root.iter() {
-            if let Some(section) = value.as_object() {
-                // Cerca sezioni che potrebbero contenere giochi condivisi
-                if key.contains("SharedContent") || key.len() == 17 { // Possibile Steam ID
-                    if let Some(apps) = section.get("apps").and_then(|a| a.as_object()) {
-                        let mut app_ids = Vec::new();
-                        for (app_id, _) in apps.iter() {
-                            if let Ok(appid) = app_id.parse::<u32>() {
-                                app_ids.push(appid);
-                            }
-                        }
-                        if !app_ids.is_empty() {
-                            shared_games.insert(key.clone(), app_ids);
-                        }
+    for (key, value) in vdf.iter() {
+        if let Some(section) = value.as_obj() {
+            // Cerca sezioni che potrebbero contenere giochi condivisi
+            if key.contains("SharedContent") || key.len() == 17 { // Possibile Steam ID
+                if let Some(apps) = section.get_obj("apps") {
+                    let app_ids: Vec<u32> = apps.iter()
+                        .filter_map(|(app_id, _)| app_id.parse::<u32>().ok())
+                        .collect();
+                    if !app_ids.is_empty() {
+                        shared_games.insert(key.to_string(), app_ids);
                     }
                 }
             }
@@ -4089,11 +4740,14 @@ fn parse_owned_games(steam_path: &str) -> Result<Vec<u32>, String> {
 }
 
 /// Parsa localconfig.vdf per trovare giochi
-fn parse_localconfig_for_games(_localconfig_path: &Path) -> Result<Vec<u32>, String> {
-    // TODO: Implementare parser VDF alternativo dopo rimozione steamy_vdf
-    debug!("[RUST] parse_localconfig_for_games temporaneamente disabilitata");
-    Ok(Vec::new())
-
+fn parse_localconfig_for_games(localconfig_path: &Path) -> Result<Vec<u32>, String> {
+    let content = fs::read_to_string(localconfig_path)
+        .map_err(|e| format!("Errore lettura localconfig.vdf: {}", e))?;
+    
+    Ok(crate::vdf::parse_localconfig_apps(&content)
+        .into_iter()
+        .map(|app| app.appid)
+        .collect())
 }
 
 /// Parsa shortcuts.vdf per trovare giochi non-Steam
@@ -4149,6 +4803,8 @@ pub async fn parse_shared_config_vdf(file_content: String) -> Result<FamilyShari
                         name: format!("Game {}", appid), // Nome placeholder, verrà arricchito dopo
                         owner_steam_id: authorized_users.first().cloned().unwrap_or_default(),
                         owner_account_name: String::new(),
+                        owner_persona_name: None,
+                        owner_avatar_url: None,
                         is_shared: true,
                     });
                 }
@@ -4176,6 +4832,8 @@ pub async fn parse_shared_config_vdf(file_content: String) -> Result<FamilyShari
                                     name: format!("Game {}", appid),
                                     owner_steam_id: authorized_users.first().cloned().unwrap_or_default(),
                                     owner_account_name: String::new(),
+                                    owner_persona_name: None,
+                                    owner_avatar_url: None,
                                     is_shared: true,
                                 });
                             }
@@ -4196,9 +4854,143 @@ pub async fn parse_shared_config_vdf(file_content: String) -> Result<FamilyShari
     })
 }
 
+/// Profilo pubblico di chi presta giochi, da GetPlayerSummaries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FamilyOwnerProfile {
+    persona_name: Option<String>,
+    avatar_url: Option<String>,
+    resolved_at: i64,
+}
+
+/// I profili restano validi 7 giorni (nome e avatar possono cambiare)
+const FAMILY_OWNER_TTL_SECS: i64 = 7 * 24 * 3600;
+
+// Profili dei proprietari per Steam ID, persistiti in steam_family_owners.json
+static FAMILY_OWNERS: Lazy<std::sync::Mutex<HashMap<String, FamilyOwnerProfile>>> =
+    Lazy::new(|| std::sync::Mutex::new(load_family_owners()));
+
+fn family_owners_path() -> Result<std::path::PathBuf, String> {
+    Ok(get_steam_status_path()?.with_file_name("steam_family_owners.json"))
+}
+
+fn load_family_owners() -> HashMap<String, FamilyOwnerProfile> {
+    family_owners_path().ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_family_owners() {
+    let result = family_owners_path().and_then(|path| {
+        let json = {
+            let owners = FAMILY_OWNERS.lock().unwrap();
+            serde_json::to_string(&*owners).map_err(|e| e.to_string())?
+        };
+        fs::write(path, json).map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        warn!("[RUST] ⚠️ Cache proprietari Family Sharing non salvata: {}", e);
+    }
+}
+
+/// Scarica i profili mancanti con GetPlayerSummaries (max 100 Steam ID per chiamata)
+async fn fetch_owner_profiles(api_key: &SecureString, steam_ids: &[String]) -> Result<HashMap<String, FamilyOwnerProfile>, String> {
+    let client = crate::http::client_for("steam");
+    let mut profiles = HashMap::new();
+    
+    for chunk in steam_ids.chunks(100) {
+        let url = format!(
+            "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v0002/?key={}&steamids={}",
+            api_key.expose(), chunk.join(",")
+        );
+        let response = crate::http::get_with_retry(&client, &url, "steam_player_summaries").await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("GetPlayerSummaries: HTTP {}", response.status()));
+        }
+        let json: Value = response.json().await
+            .map_err(|e| format!("Errore parsing GetPlayerSummaries: {}", e.without_url()))?;
+        
+        let now = chrono::Utc::now().timestamp();
+        for player in json["response"]["players"].as_array().into_iter().flatten() {
+            if let Some(steam_id) = player["steamid"].as_str() {
+                profiles.insert(steam_id.to_string(), FamilyOwnerProfile {
+                    persona_name: player["personaname"].as_str().map(str::to_string),
+                    avatar_url: player["avatarmedium"].as_str().map(str::to_string),
+                    resolved_at: now,
+                });
+            }
+        }
+    }
+    
+    Ok(profiles)
+}
+
+/// Risolve nome e avatar di chi presta ogni gioco: loginusers.vdf (utenti che hanno
+/// effettuato l'accesso su questo PC), poi cache e GetPlayerSummaries se ci sono credenziali
+async fn enrich_shared_game_owners(
+    steam_path: &str,
+    shared_games: &mut [crate::models::SharedGame],
+    profile_state: &crate::commands::profiles::ProfileManagerState,
+) {
+    let login_users: HashMap<String, crate::vdf::LoginUser> =
+        fs::read_to_string(Path::new(steam_path).join("config").join("loginusers.vdf"))
+            .map(|content| crate::vdf::parse_login_users(&content))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|user| (user.steam_id.clone(), user))
+            .collect();
+    
+    let owner_ids: HashSet<String> = shared_games.iter()
+        .map(|g| g.owner_steam_id.clone())
+        .filter(|id| validate_steam_id64(id))
+        .collect();
+    
+    let now = chrono::Utc::now().timestamp();
+    let missing: Vec<String> = {
+        let owners = FAMILY_OWNERS.lock().unwrap();
+        owner_ids.iter()
+            .filter(|id| !owners.get(*id).is_some_and(|p| now - p.resolved_at < FAMILY_OWNER_TTL_SECS))
+            .cloned()
+            .collect()
+    };
+    
+    if !missing.is_empty() {
+        match load_steam_api_credentials(profile_state).await {
+            Ok((api_key, _)) => match fetch_owner_profiles(&api_key, &missing).await {
+                Ok(profiles) => {
+                    debug!("[RUST] 👥 Risolti {} proprietari Family Sharing via API", profiles.len());
+                    FAMILY_OWNERS.lock().unwrap().extend(profiles);
+                    save_family_owners();
+                }
+                Err(e) => warn!("[RUST] ⚠️ Profili proprietari non disponibili: {}", e),
+            },
+            Err(_) => debug!("[RUST] Nessuna credenziale Steam: proprietari risolti solo da loginusers.vdf"),
+        }
+    }
+    
+    let owners = FAMILY_OWNERS.lock().unwrap();
+    for game in shared_games.iter_mut() {
+        let profile = owners.get(&game.owner_steam_id);
+        let login_user = login_users.get(&game.owner_steam_id);
+        
+        game.owner_persona_name = profile.and_then(|p| p.persona_name.clone())
+            .or_else(|| login_user.and_then(|u| u.persona_name.clone()));
+        game.owner_avatar_url = profile.and_then(|p| p.avatar_url.clone());
+        game.owner_account_name = game.owner_persona_name.clone()
+            .or_else(|| login_user.and_then(|u| u.account_name.clone()))
+            .unwrap_or_else(|| match game.owner_steam_id.len() {
+                0 => "Utente sconosciuto".to_string(),
+                len => format!("Steam User {}", &game.owner_steam_id[len.saturating_sub(4)..]),
+            });
+    }
+}
+
 /// Comando per ottenere giochi condivisi automaticamente
 #[tauri::command]
-pub async fn get_family_sharing_games() -> Result<FamilySharingConfig, String> {
+pub async fn get_family_sharing_games(
+    profile_state: tauri::State<'_, crate::commands::profiles::ProfileManagerState>
+) -> Result<FamilySharingConfig, String> {
     debug!("[RUST] get_family_sharing_games called");
     
     // Trova il path di Steam
@@ -4255,6 +5047,8 @@ pub async fn get_family_sharing_games() -> Result<FamilySharingConfig, String> {
     all_authorized_users.sort();
     all_authorized_users.dedup();
     
+    enrich_shared_game_owners(&steam_path, &mut all_shared_games, &profile_state).await;
+    
     let total_shared_games = all_shared_games.len() as u32;
     
     info!("[RUST] ✅ Family Sharing: {} giochi condivisi da {} utenti", 
@@ -4278,10 +5072,10 @@ pub async fn get_steam_games_with_family_sharing(
     debug!("[RUST] get_steam_games_with_family_sharing called");
     
     // Prima ottieni i giochi posseduti
-    let mut owned_games = get_steam_games(api_key, steam_id, force_refresh, profile_state).await?;
+    let mut owned_games = get_steam_games(api_key, steam_id, force_refresh, profile_state.clone()).await?;
     
     // Poi ottieni i giochi condivisi
-    match get_family_sharing_games().await {
+    match get_family_sharing_games(profile_state).await {
         Ok(family_config) => {
             info!("[RUST] ✅ Aggiungendo {} giochi Family Sharing", family_config.total_shared_games);
             
@@ -4330,4 +5124,110 @@ pub async fn get_steam_games_with_family_sharing(
     Ok(owned_games)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_file_lock_key_is_stable_across_creation() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("steam_library.json");
+        let before = json_file_lock_key(&path);
+        fs::write(&path, "{}").unwrap();
+        assert_eq!(json_file_lock_key(&path), before);
+        // Stesso file raggiunto da un percorso non normalizzato
+        let indirect = dir.path().join(".").join("steam_library.json");
+        assert_eq!(json_file_lock_key(&indirect), before);
+        assert!(std::sync::Arc::ptr_eq(&json_file_lock(&path), &json_file_lock(&indirect)));
+    }
+
+    fn appids(games: &[Value]) -> Vec<u64> {
+        games.iter().filter_map(|g| g["appid"].as_u64()).collect()
+    }
+
+    #[test]
+    fn salvage_recovers_complete_entries_of_truncated_array() {
+        let content = r#"[
+            {"appid": 10, "name": "Counter-Strike", "content_descriptorids": [2, 5]},
+            {"appid": 20, "name": "Team \"Fortress\" {Classic}"}
+            {"appid": 30, "name": "Broken"
+            {"appid": 40, "name": "Half"#;
+        let (games, dropped) = salvage_owned_games(content);
+        assert_eq!(appids(&games), vec![10, 20]);
+        assert_eq!(games[1]["name"], "Team \"Fortress\" {Classic}");
+        assert_eq!(dropped, 1);
+    }
+
+    #[test]
+    fn salvage_skips_duplicates_and_entries_without_appid() {
+        let content = r#"[{"appid": 10}, {"appid": 10}, {"name": "no id"}, {"appid": "x"}]"#;
+        let (games, dropped) = salvage_owned_games(content);
+        assert_eq!(appids(&games), vec![10]);
+        assert_eq!(dropped, 3);
+    }
+
+    #[test]
+    fn salvage_unwraps_api_response_object() {
+        let content = r#"{"response": {"game_count": 2, "games": [{"appid": 570, "name": "Dota 2"}, {"appid": 730, "name": "CS2"}]}}"#;
+        let (games, dropped) = salvage_owned_games(content);
+        assert_eq!(appids(&games), vec![570, 730]);
+        assert_eq!(dropped, 0);
+
+        let truncated = "\u{feff}{\"response\": {\"games\": [{\"appid\": 570}, {\"appid\": 7";
+        let (games, dropped) = salvage_owned_games(truncated);
+        assert_eq!(appids(&games), vec![570]);
+        assert_eq!(dropped, 1);
+    }
+
+    #[test]
+    fn salvage_recovers_nothing_from_garbage() {
+        for content in ["", "not json at all", "{\"response\": {}}", "\0\0\0{{{"] {
+            let (games, _) = salvage_owned_games(content);
+            assert!(games.is_empty(), "recuperate voci da {:?}", content);
+        }
+    }
 
+    #[test]
+    fn owned_games_response_without_games_is_empty() {
+        let games = owned_games_from_response(&serde_json::json!({"response": {}})).unwrap();
+        assert!(games.is_empty());
+
+        let games = owned_games_from_response(&serde_json::json!({"response": {"games": [{"appid": 1}]}})).unwrap();
+        assert_eq!(appids(&games), vec![1]);
+
+        assert!(owned_games_from_response(&serde_json::json!({})).is_err());
+        assert!(owned_games_from_response(&serde_json::json!({"response": {"games": "x"}})).is_err());
+    }
+
+    #[test]
+    fn merge_adds_updates_and_keeps_local_entries() {
+        let mut local = vec![
+            serde_json::json!({"appid": 10, "name": "Local name", "playtime_forever": 5, "custom": true}),
+            serde_json::json!({"appid": 20, "name": "Unchanged", "playtime_forever": 1}),
+            serde_json::json!({"appid": 99, "name": "Solo locale"}),
+        ];
+        let remote = vec![
+            serde_json::json!({"appid": 10, "name": "", "playtime_forever": 42}),
+            serde_json::json!({"appid": 20, "name": "Unchanged", "playtime_forever": 1}),
+            serde_json::json!({"appid": 30, "name": "New"}),
+            serde_json::json!({"appid": 30, "name": "New again"}),
+            serde_json::json!({"name": "no appid"}),
+        ];
+
+        let (added, updated, unchanged) = merge_owned_games(&mut local, &remote);
+        assert_eq!((added, updated, unchanged), (1, 2, 1));
+        assert_eq!(appids(&local), vec![10, 20, 99, 30]);
+        // Nome vuoto dall'API ignorato, campi locali extra conservati
+        assert_eq!(local[0]["name"], "Local name");
+        assert_eq!(local[0]["playtime_forever"], 42);
+        assert_eq!(local[0]["custom"], true);
+        assert_eq!(local[3]["name"], "New again");
+    }
+
+    #[test]
+    fn merge_with_empty_remote_changes_nothing() {
+        let mut local = vec![serde_json::json!({"appid": 10, "name": "A"})];
+        assert_eq!(merge_owned_games(&mut local, &[]), (0, 0, 0));
+        assert_eq!(appids(&local), vec![10]);
+    }
+}
@@ -287,63 +287,30 @@ struct AppInfoData {
 
 /// 📖 Legge i nomi e tipi dei giochi da appinfo.vdf (cache Steam con TUTTI i nomi)
 fn load_game_info_from_appinfo(steam_path: &std::path::Path) -> HashMap<u32, AppInfoData> {
-    use new_vdf_parser::appinfo_vdf_parser::open_appinfo_vdf;
-    use std::path::PathBuf;
-    
-    let mut app_info: HashMap<u32, AppInfoData> = HashMap::new();
-    let appinfo_path: PathBuf = steam_path.join("appcache").join("appinfo.vdf");
-    
-    if !appinfo_path.exists() {
-        warn!("⚠️ appinfo.vdf non trovato: {}", appinfo_path.display());
-        return app_info;
-    }
-    
     info!("📖 Parsing appinfo.vdf per nomi e tipi giochi...");
     
-    // open_appinfo_vdf prende &PathBuf e Option<bool> per filter
-    let apps = open_appinfo_vdf(&appinfo_path, None);
+    let apps = crate::appinfo::load_app_infos(steam_path);
+    if apps.is_empty() {
+        warn!("⚠️ appinfo.vdf non trovato o illeggibile: {}", crate::appinfo::appinfo_path(steam_path).display());
+    }
     
-    for (appid_str, app_data) in apps.iter() {
-        if let Ok(appid) = appid_str.parse::<u32>() {
-            let common = app_data.get("common");
-            
-            // Cerca il nome
-            let name = common
-                .and_then(|c| c.get("name"))
-                .and_then(|n| n.as_str())
-                .unwrap_or("")
-                .to_string();
-            
-            if name.is_empty() {
-                continue;
-            }
-            
-            // Controlla se è un DLC
-            let app_type = common
-                .and_then(|c| c.get("type"))
-                .and_then(|t| t.as_str())
-                .unwrap_or("game");
+    let app_info: HashMap<u32, AppInfoData> = apps.values()
+        .filter_map(|app| {
+            let name = app.name.clone()?;
             
-            let is_dlc = app_type.eq_ignore_ascii_case("dlc") || 
-                         app_type.eq_ignore_ascii_case("music") ||
-                         app_type.eq_ignore_ascii_case("tool") ||
-                         app_type.eq_ignore_ascii_case("demo") ||
-                         app_type.eq_ignore_ascii_case("advertising") ||
-                         app_type.eq_ignore_ascii_case("mod");
-            
-            // Cerca il parent appid per i DLC
-            let parent_appid = common
-                .and_then(|c| c.get("parent"))
-                .and_then(|p| p.as_str())
-                .and_then(|s| s.parse::<u32>().ok());
+            // Controlla se è un DLC (o comunque non un gioco)
+            let is_dlc = matches!(
+                app.app_type.as_deref(),
+                Some("dlc" | "music" | "tool" | "demo" | "advertising" | "mod")
+            );
             
-            app_info.insert(appid, AppInfoData {
+            Some((app.appid, AppInfoData {
                 name,
                 is_dlc,
-                _parent_appid: parent_appid,
-            });
-        }
-    }
+                _parent_appid: app.parent_appid,
+            }))
+        })
+        .collect();
     
     let dlc_count = app_info.values().filter(|a| a.is_dlc).count();
     let game_count = app_info.len() - dlc_count;
@@ -402,29 +369,20 @@ pub async fn fetch_game_names_batch(appids: Vec<u32>) -> HashMap<u32, String> {
 /// 👤 Ottiene lo Steam ID dell'utente corrente da loginusers.vdf
 fn get_current_steam_id(steam_path: &std::path::Path) -> Option<String> {
     use std::fs;
-    use regex::Regex;
     
     let loginusers_path = steam_path.join("config").join("loginusers.vdf");
     if !loginusers_path.exists() {
         return None;
     }
     
-    if let Ok(content) = fs::read_to_string(&loginusers_path) {
-        // Cerca l'utente con MostRecent = 1 (utente attivo)
-        // Formato: "steamid" { ... "MostRecent" "1" ... }
-        let steamid_regex = Regex::new(r#""(\d{17})"\s*\{[^}]*"MostRecent"\s*"1""#).ok()?;
-        if let Some(cap) = steamid_regex.captures(&content) {
-            return cap.get(1).map(|m| m.as_str().to_string());
-        }
-        
-        // Fallback: prendi il primo Steam ID trovato
-        let fallback_regex = Regex::new(r#""(\d{17})""#).ok()?;
-        if let Some(cap) = fallback_regex.captures(&content) {
-            return cap.get(1).map(|m| m.as_str().to_string());
-        }
-    }
+    let content = fs::read_to_string(&loginusers_path).ok()?;
+    let users = crate::vdf::parse_login_users(&content);
     
-    None
+    // Preferisci l'utente con MostRecent = 1 (utente attivo), altrimenti il primo trovato
+    users.iter()
+        .find(|u| u.most_recent)
+        .or_else(|| users.first())
+        .map(|u| u.steam_id.clone())
 }
 
 /// 🚀 SCAN COMPLETO - Trova TUTTI i giochi (installati + owned + family sharing)
@@ -519,9 +477,9 @@ pub async fn scan_all_steam_games_fast() -> Result<Vec<GameInfo>, String> {
                         
                         // Rileva il motore di gioco
                         let engine = {
-                            let detected = crate::engine_detector::detect_engine(&full_install_path);
-                            if detected != crate::engine_detector::GameEngine::Unknown {
-                                Some(detected.as_str().to_string())
+                            let detected = crate::engine_detector::detect_engine_from_files(&full_install_path);
+                            if detected.engine != crate::engine_detector::GameEngine::Unknown {
+                                Some(detected.engine_name)
                             } else {
                                 None
                             }
@@ -536,22 +494,17 @@ pub async fn scan_all_steam_games_fast() -> Result<Vec<GameInfo>, String> {
                                 .map(|t| t.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs());
                             
                             // 🔍 Leggi LastOwner per rilevare Family Sharing
-                            let shared = if let Ok(content) = fs::read_to_string(&manifest_path) {
-                                // Cerca "LastOwner" nel file ACF
-                                let last_owner_regex = Regex::new(r#""LastOwner"\s*"(\d+)""#).unwrap();
-                                if let Some(cap) = last_owner_regex.captures(&content) {
-                                    if let Some(owner_match) = cap.get(1) {
-                                        let owner_id = owner_match.as_str();
-                                        // Se LastOwner è diverso dal nostro Steam ID, è Family Sharing
-                                        if let Some(ref my_id) = current_steam_id {
-                                            !owner_id.is_empty() && owner_id != "0" && owner_id != my_id
-                                        } else {
-                                            // Se non conosciamo il nostro ID, assumiamo shared se LastOwner è presente
-                                            !owner_id.is_empty() && owner_id != "0"
-                                        }
-                                    } else { false }
-                                } else { false }
-                            } else { false };
+                            let shared = match crate::vdf::AppManifest::parse_file(&manifest_path) {
+                                Ok(manifest) => match manifest.last_owner.as_deref() {
+                                    // Se LastOwner è diverso dal nostro Steam ID, è Family Sharing
+                                    // Se non conosciamo il nostro ID, assumiamo shared se LastOwner è presente
+                                    Some(owner_id) if !owner_id.is_empty() && owner_id != "0" => {
+                                        current_steam_id.as_deref() != Some(owner_id)
+                                    }
+                                    _ => false,
+                                },
+                                Err(_) => false,
+                            };
                             
                             (added, shared)
                         } else {
@@ -598,53 +551,39 @@ pub async fn scan_all_steam_games_fast() -> Result<Vec<GameInfo>, String> {
                 let config_path = entry.path().join("config").join("localconfig.vdf");
                 if config_path.exists() {
                     if let Ok(content) = fs::read_to_string(&config_path) {
-                        // Cerca tutti gli appid e i loro LastPlayed
-                        // Pattern: "appid" { ... "LastPlayed" "timestamp" ... }
-                        let appid_regex = Regex::new(r#""(\d{4,7})"\s*\{([^}]*)\}"#).unwrap();
-                        let lastplayed_regex = Regex::new(r#""LastPlayed"\s*"(\d+)""#).unwrap();
-                        
-                        for cap in appid_regex.captures_iter(&content) {
-                            if let (Some(appid_match), Some(block)) = (cap.get(1), cap.get(2)) {
-                                if let Ok(appid) = appid_match.as_str().parse::<u32>() {
-                                    // Estrai LastPlayed dal blocco
-                                    if let Some(lp_cap) = lastplayed_regex.captures(block.as_str()) {
-                                        if let Some(lp_match) = lp_cap.get(1) {
-                                            if let Ok(timestamp) = lp_match.as_str().parse::<u64>() {
-                                                if timestamp > 0 {
-                                                    last_played_map.insert(appid, timestamp);
-                                                }
-                                            }
-                                        }
-                                    }
-                                    
-                                    // 🚫 SALTA DLC
-                                    if is_dlc(appid) {
-                                        continue;
-                                    }
-                                    if !all_games.contains_key(&appid) && appid > 100 {
-                                        let name = get_name(appid);
-                                        let last_played = last_played_map.get(&appid).copied();
-                                        all_games.insert(appid, GameInfo {
-                                            id: format!("steam_{}", appid),
-                                            title: name,
-                                            platform: "Steam".to_string(),
-                                            install_path: None,
-                                            executable_path: None,
-                                            icon: None,
-                                            image_url: Some(format!("https://cdn.akamai.steamstatic.com/steam/apps/{}/header.jpg", appid)),
-                                            header_image: Some(format!("https://cdn.akamai.steamstatic.com/steam/apps/{}/header.jpg", appid)),
-                                            is_installed: false,
-                                            steam_app_id: Some(appid),
-                                            is_vr: false,
-                                            engine: None,
-                                            last_played,
-                                            is_shared: false,
-                                            supported_languages: None,
-                                            genres: None,
-                                            added_date: None, // Non installato
-                                            });
-                                    }
-                                }
+                        // Sezione UserLocalConfigStore/Software/Valve/Steam/apps con LastPlayed
+                        for app in crate::vdf::parse_localconfig_apps(&content) {
+                            let appid = app.appid;
+                            if let Some(timestamp) = app.last_played {
+                                last_played_map.insert(appid, timestamp);
+                            }
+                            
+                            // 🚫 SALTA DLC
+                            if is_dlc(appid) {
+                                continue;
+                            }
+                            if !all_games.contains_key(&appid) && appid > 100 {
+                                let name = get_name(appid);
+                                let last_played = last_played_map.get(&appid).copied();
+                                all_games.insert(appid, GameInfo {
+                                    id: format!("steam_{}", appid),
+                                    title: name,
+                                    platform: "Steam".to_string(),
+                                    install_path: None,
+                                    executable_path: None,
+                                    icon: None,
+                                    image_url: Some(format!("https://cdn.akamai.steamstatic.com/steam/apps/{}/header.jpg", appid)),
+                                    header_image: Some(format!("https://cdn.akamai.steamstatic.com/steam/apps/{}/header.jpg", appid)),
+                                    is_installed: false,
+                                    steam_app_id: Some(appid),
+                                    is_vr: false,
+                                    engine: None,
+                                    last_played,
+                                    is_shared: false,
+                                    supported_languages: None,
+                                    genres: None,
+                                    added_date: None, // Non installato
+                                    });
                             }
                         }
                     }
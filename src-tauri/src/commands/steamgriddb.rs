@@ -0,0 +1,425 @@
+//! 🎨 SteamGridDB
+//!
+//! Artwork per grid (capsule verticali), hero, logo e icon tramite API v2 di SteamGridDB.
+//! Risultati in cache per appid+tipo (moka in memoria + JSON su disco); se un tipo manca
+//! si ricade su tipi affini e infine sulle immagini della CDN di Steam.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use log::{debug, info, warn};
+use super::cache_registry::{json_size, TrackedCache};
+use once_cell::sync::Lazy;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const STEAMGRIDDB_API: &str = "https://www.steamgriddb.com/api/v2";
+
+/// Durata della cache (memoria e disco)
+const CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Tipo di artwork SteamGridDB
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtworkType {
+    /// Capsule verticale (600x900)
+    Grid,
+    Hero,
+    Logo,
+    Icon,
+}
+
+impl ArtworkType {
+    pub const ALL: [ArtworkType; 4] = [ArtworkType::Grid, ArtworkType::Hero, ArtworkType::Logo, ArtworkType::Icon];
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "grid" | "grids" | "capsule" | "cover" => Some(ArtworkType::Grid),
+            "hero" | "heroes" => Some(ArtworkType::Hero),
+            "logo" | "logos" => Some(ArtworkType::Logo),
+            "icon" | "icons" => Some(ArtworkType::Icon),
+            _ => None,
+        }
+    }
+
+    fn endpoint(self, app_id: u32) -> String {
+        match self {
+            // Solo capsule verticali: la vista libreria non usa le grid orizzontali
+            ArtworkType::Grid => format!("{}/grids/steam/{}?dimensions=600x900,342x482,660x930", STEAMGRIDDB_API, app_id),
+            ArtworkType::Hero => format!("{}/heroes/steam/{}", STEAMGRIDDB_API, app_id),
+            ArtworkType::Logo => format!("{}/logos/steam/{}", STEAMGRIDDB_API, app_id),
+            ArtworkType::Icon => format!("{}/icons/steam/{}", STEAMGRIDDB_API, app_id),
+        }
+    }
+
+    /// Ordine di fallback quando il tipo richiesto non è disponibile
+    fn fallbacks(self) -> &'static [ArtworkType] {
+        match self {
+            ArtworkType::Grid => &[ArtworkType::Hero],
+            ArtworkType::Hero => &[ArtworkType::Grid],
+            ArtworkType::Logo => &[ArtworkType::Icon],
+            ArtworkType::Icon => &[ArtworkType::Logo],
+        }
+    }
+
+    /// Immagine equivalente sulla CDN di Steam (ultimo fallback)
+    fn steam_cdn_url(self, app_id: u32) -> Option<String> {
+        let base = format!("https://cdn.cloudflare.steamstatic.com/steam/apps/{}", app_id);
+        match self {
+            ArtworkType::Grid => Some(format!("{}/library_600x900.jpg", base)),
+            ArtworkType::Hero => Some(format!("{}/library_hero.jpg", base)),
+            ArtworkType::Logo => Some(format!("{}/logo.png", base)),
+            ArtworkType::Icon => None,
+        }
+    }
+}
+
+/// Origine dell'immagine
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtworkSource {
+    Steamgriddb,
+    SteamCdn,
+}
+
+/// Singola immagine restituita al frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtworkImage {
+    pub url: String,
+    pub thumb: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub source: ArtworkSource,
+    /// Tipo da cui proviene l'immagine (diverso da quello richiesto se è un fallback)
+    pub artwork_type: ArtworkType,
+}
+
+/// Artwork di un gioco, un campo per tipo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SteamGridDbArtwork {
+    pub app_id: u32,
+    pub found: bool,
+    pub grid: Option<ArtworkImage>,
+    pub hero: Option<ArtworkImage>,
+    pub logo: Option<ArtworkImage>,
+    pub icon: Option<ArtworkImage>,
+}
+
+impl SteamGridDbArtwork {
+    fn slot_mut(&mut self, artwork_type: ArtworkType) -> &mut Option<ArtworkImage> {
+        match artwork_type {
+            ArtworkType::Grid => &mut self.grid,
+            ArtworkType::Hero => &mut self.hero,
+            ArtworkType::Logo => &mut self.logo,
+            ArtworkType::Icon => &mut self.icon,
+        }
+    }
+}
+
+/// Voce della cache su disco: risultati grezzi di SteamGridDB per tipo
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DiskCacheEntry {
+    fetched_at: i64,
+    images: HashMap<ArtworkType, Option<ArtworkImage>>,
+}
+
+/// Cache in memoria: (appid, tipo) -> immagine SteamGridDB (None = tipo non disponibile)
+pub(crate) static ARTWORK_CACHE: Lazy<TrackedCache<(u32, ArtworkType), Option<ArtworkImage>>> = Lazy::new(|| {
+    TrackedCache::new(
+        "steamgriddb",
+        "Artwork SteamGridDB per (AppID, tipo)",
+        20_000,
+        CACHE_TTL,
+        json_size,
+    )
+});
+
+// Client HTTP per SteamGridDB (timeout dalle preferenze di rete)
+fn http_client() -> Client {
+    crate::http::client_for("steamgriddb")
+}
+
+/// Directory della cache su disco
+fn get_cache_dir() -> Result<PathBuf, String> {
+    let cache_dir = if cfg!(debug_assertions) {
+        PathBuf::from("../gamestringer_data/steamgriddb_cache")
+    } else {
+        dirs::cache_dir()
+            .ok_or("Impossibile trovare directory cache")?
+            .join("GameStringer")
+            .join("steamgriddb")
+    };
+
+    if !cache_dir.exists() {
+        fs::create_dir_all(&cache_dir)
+            .map_err(|e| format!("Errore creazione directory cache: {}", e))?;
+    }
+
+    Ok(cache_dir)
+}
+
+fn disk_cache_path(app_id: u32) -> Result<PathBuf, String> {
+    Ok(get_cache_dir()?.join(format!("{}.json", app_id)))
+}
+
+fn read_disk_cache(app_id: u32) -> DiskCacheEntry {
+    let entry = disk_cache_path(app_id).ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<DiskCacheEntry>(&content).ok());
+
+    match entry {
+        Some(entry) if chrono::Utc::now().timestamp() - entry.fetched_at < CACHE_TTL.as_secs() as i64 => entry,
+        _ => DiskCacheEntry::default(),
+    }
+}
+
+fn write_disk_cache(app_id: u32, artwork_type: ArtworkType, image: &Option<ArtworkImage>) {
+    let mut entry = read_disk_cache(app_id);
+    if entry.images.is_empty() {
+        entry.fetched_at = chrono::Utc::now().timestamp();
+    }
+    entry.images.insert(artwork_type, image.clone());
+
+    let result = disk_cache_path(app_id).and_then(|path| {
+        let content = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+        fs::write(path, content).map_err(|e| e.to_string())
+    });
+    if let Err(e) = result {
+        debug!("⚠️ Cache SteamGridDB su disco non scritta per {}: {}", app_id, e);
+    }
+}
+
+/// API key: parametro del comando (il frontend la tiene nelle sue impostazioni),
+/// poi variabile d'ambiente
+fn resolve_api_key(explicit: Option<String>) -> Option<String> {
+    explicit
+        .filter(|key| !key.trim().is_empty())
+        .or_else(|| std::env::var("STEAMGRIDDB_API_KEY").ok())
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
+
+/// Immagine SteamGridDB per un tipo (memoria -> disco -> API)
+async fn fetch_type(app_id: u32, artwork_type: ArtworkType, api_key: &str) -> Result<Option<ArtworkImage>, String> {
+    if let Some(cached) = ARTWORK_CACHE.get(&(app_id, artwork_type)).await {
+        return Ok(cached);
+    }
+
+    if let Some(cached) = read_disk_cache(app_id).images.get(&artwork_type).cloned() {
+        ARTWORK_CACHE.insert((app_id, artwork_type), cached.clone()).await;
+        return Ok(cached);
+    }
+
+    let response = http_client().get(artwork_type.endpoint(app_id))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .send()
+        .await
+        .map_err(|e| format!("Errore richiesta SteamGridDB: {}", e))?;
+
+    let image = match response.status() {
+        StatusCode::NOT_FOUND => None,
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            return Err("API key SteamGridDB non valida".to_string());
+        }
+        status if !status.is_success() => {
+            return Err(format!("SteamGridDB ha risposto con status {}", status));
+        }
+        _ => {
+            let json: Value = response.json()
+                .await
+                .map_err(|e| format!("Risposta SteamGridDB non valida: {}", e))?;
+            parse_first_image(&json, artwork_type)
+        }
+    };
+
+    ARTWORK_CACHE.insert((app_id, artwork_type), image.clone()).await;
+    write_disk_cache(app_id, artwork_type, &image);
+    Ok(image)
+}
+
+/// Primo risultato della lista `data` (SteamGridDB ordina per punteggio)
+fn parse_first_image(json: &Value, artwork_type: ArtworkType) -> Option<ArtworkImage> {
+    let first = json["data"].as_array()?.first()?;
+    Some(ArtworkImage {
+        url: first["url"].as_str()?.to_string(),
+        thumb: first["thumb"].as_str().map(|s| s.to_string()),
+        width: first["width"].as_u64().map(|w| w as u32),
+        height: first["height"].as_u64().map(|h| h as u32),
+        source: ArtworkSource::Steamgriddb,
+        artwork_type,
+    })
+}
+
+/// Risolve gli artwork richiesti applicando i fallback tra tipi e verso la CDN di Steam
+pub async fn resolve_artwork(app_id: u32, types: &[ArtworkType], api_key: Option<String>) -> SteamGridDbArtwork {
+    let api_key = resolve_api_key(api_key);
+    if api_key.is_none() {
+        debug!("🎨 Nessuna API key SteamGridDB: uso solo la CDN di Steam per {}", app_id);
+    }
+
+    let mut artwork = SteamGridDbArtwork { app_id, found: false, grid: None, hero: None, logo: None, icon: None };
+    let mut fetched: HashMap<ArtworkType, Option<ArtworkImage>> = HashMap::new();
+
+    for &requested in types {
+        let mut image = None;
+
+        if let Some(key) = api_key.as_deref() {
+            for &candidate in std::iter::once(&requested).chain(requested.fallbacks()) {
+                if !fetched.contains_key(&candidate) {
+                    let result = match fetch_type(app_id, candidate, key).await {
+                        Ok(result) => result,
+                        Err(e) => {
+                            warn!("⚠️ SteamGridDB {:?} per {}: {}", candidate, app_id, e);
+                            None
+                        }
+                    };
+                    fetched.insert(candidate, result);
+                }
+                if let Some(Some(found)) = fetched.get(&candidate) {
+                    image = Some(found.clone());
+                    break;
+                }
+            }
+        }
+
+        let image = image.or_else(|| requested.steam_cdn_url(app_id).map(|url| ArtworkImage {
+            url,
+            thumb: None,
+            width: None,
+            height: None,
+            source: ArtworkSource::SteamCdn,
+            artwork_type: requested,
+        }));
+
+        artwork.found |= image.as_ref().is_some_and(|i| i.source == ArtworkSource::Steamgriddb);
+        *artwork.slot_mut(requested) = image;
+    }
+
+    artwork
+}
+
+/// Interpreta "grid", "grid,hero" oppure "all"
+fn parse_artwork_types(value: &str) -> Result<Vec<ArtworkType>, String> {
+    if value.trim().is_empty() || value.trim().eq_ignore_ascii_case("all") {
+        return Ok(ArtworkType::ALL.to_vec());
+    }
+
+    let mut types = Vec::new();
+    for part in value.split(',') {
+        let artwork_type = ArtworkType::parse(part)
+            .ok_or_else(|| format!("Tipo artwork non valido: {}", part.trim()))?;
+        if !types.contains(&artwork_type) {
+            types.push(artwork_type);
+        }
+    }
+    Ok(types)
+}
+
+/// 🎨 Artwork SteamGridDB per un gioco (`artwork_type`: grid, hero, logo, icon, lista o "all")
+#[tauri::command]
+pub async fn get_steamgriddb_artwork(app_id: String, artwork_type: String, api_key: Option<String>) -> Result<SteamGridDbArtwork, String> {
+    let app_id: u32 = app_id.trim().parse()
+        .map_err(|_| format!("AppID non valido: {}", app_id))?;
+    let types = parse_artwork_types(&artwork_type)?;
+
+    info!("🎨 Ricerca artwork SteamGridDB per AppID: {} (tipi: {:?})", app_id, types);
+    Ok(resolve_artwork(app_id, &types, api_key).await)
+}
+
+/// 🎨 Artwork per più giochi (es. capsule verticali della libreria)
+#[tauri::command]
+pub async fn get_steamgriddb_artwork_batch(app_ids: Vec<u32>, artwork_type: Option<String>, api_key: Option<String>) -> Result<Vec<SteamGridDbArtwork>, String> {
+    let types = parse_artwork_types(artwork_type.as_deref().unwrap_or("grid"))?;
+    let api_key = resolve_api_key(api_key);
+
+    let mut results = Vec::with_capacity(app_ids.len());
+    for app_id in app_ids {
+        results.push(resolve_artwork(app_id, &types, api_key.clone()).await);
+    }
+
+    info!("🎨 Batch SteamGridDB: {}/{} giochi con artwork", results.iter().filter(|a| a.found).count(), results.len());
+    Ok(results)
+}
+
+/// 🧹 Svuota la cache SteamGridDB (memoria e disco)
+#[tauri::command]
+pub async fn clear_steamgriddb_cache() -> Result<u32, String> {
+    ARTWORK_CACHE.invalidate_all();
+
+    let mut removed = 0;
+    if let Ok(entries) = fs::read_dir(get_cache_dir()?) {
+        for entry in entries.flatten() {
+            if entry.path().extension().is_some_and(|ext| ext == "json") && fs::remove_file(entry.path()).is_ok() {
+                removed += 1;
+            }
+        }
+    }
+
+    info!("🧹 Cache SteamGridDB svuotata ({} file)", removed);
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_first_image_takes_the_top_result() {
+        let json = json!({
+            "success": true,
+            "data": [
+                { "url": "https://cdn2.steamgriddb.com/grid/a.png", "thumb": "https://cdn2.steamgriddb.com/thumb/a.png", "width": 600, "height": 900 },
+                { "url": "https://cdn2.steamgriddb.com/grid/b.png", "width": 342, "height": 482 }
+            ]
+        });
+
+        let image = parse_first_image(&json, ArtworkType::Grid).unwrap();
+        assert_eq!(image.url, "https://cdn2.steamgriddb.com/grid/a.png");
+        assert_eq!(image.thumb.as_deref(), Some("https://cdn2.steamgriddb.com/thumb/a.png"));
+        assert_eq!((image.width, image.height), (Some(600), Some(900)));
+        assert_eq!(image.source, ArtworkSource::Steamgriddb);
+        assert_eq!(image.artwork_type, ArtworkType::Grid);
+    }
+
+    #[test]
+    fn parse_first_image_handles_missing_fields() {
+        // Campi opzionali assenti
+        let image = parse_first_image(&json!({ "data": [{ "url": "https://x/logo.png" }] }), ArtworkType::Logo).unwrap();
+        assert!(image.thumb.is_none() && image.width.is_none() && image.height.is_none());
+
+        // Nessun risultato, risposta senza `data` o primo risultato senza URL
+        assert!(parse_first_image(&json!({ "success": true, "data": [] }), ArtworkType::Hero).is_none());
+        assert!(parse_first_image(&json!({ "success": false, "errors": ["Game not found"] }), ArtworkType::Hero).is_none());
+        assert!(parse_first_image(&json!({ "data": [{ "thumb": "https://x/t.png" }] }), ArtworkType::Icon).is_none());
+    }
+
+    #[test]
+    fn parse_artwork_types_accepts_lists_aliases_and_all() {
+        assert_eq!(parse_artwork_types("").unwrap(), ArtworkType::ALL.to_vec());
+        assert_eq!(parse_artwork_types(" ALL ").unwrap(), ArtworkType::ALL.to_vec());
+        assert_eq!(parse_artwork_types("grid").unwrap(), vec![ArtworkType::Grid]);
+        assert_eq!(
+            parse_artwork_types("heroes, Capsule,logo,grid").unwrap(),
+            vec![ArtworkType::Hero, ArtworkType::Grid, ArtworkType::Logo]
+        );
+    }
+
+    #[test]
+    fn parse_artwork_types_rejects_unknown_types() {
+        let err = parse_artwork_types("grid,banner").unwrap_err();
+        assert!(err.contains("banner"));
+        assert!(parse_artwork_types("grid,").is_err());
+    }
+
+    #[test]
+    fn explicit_api_key_is_trimmed_and_blank_is_ignored() {
+        assert_eq!(resolve_api_key(Some("  abc123 ".to_string())).as_deref(), Some("abc123"));
+        if std::env::var("STEAMGRIDDB_API_KEY").is_err() {
+            assert!(resolve_api_key(Some("   ".to_string())).is_none());
+            assert!(resolve_api_key(None).is_none());
+        }
+    }
+}
@@ -2,7 +2,12 @@
 //! Rileva quali piattaforme di gioco sono installate sul sistema
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
 use std::path::Path;
+use std::time::Duration;
+use crate::commands::library::InstalledGame;
+use crate::commands::profiles::ProfileManagerState;
 use winreg::enums::*;
 use winreg::RegKey;
 
@@ -15,6 +20,61 @@ pub struct StoreStatus {
     pub games_count: Option<u32>,
 }
 
+/// Esito di un test di connessione
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StoreConnectionState {
+    /// Account autenticato o servizio raggiungibile
+    Connected,
+    /// Nessuna credenziale salvata: disponibile solo la scansione locale
+    LocalOnly,
+    /// Credenziali salvate ma rifiutate dallo store
+    InvalidCredentials,
+    /// Test fallito (rete, timeout, store non installato)
+    Error,
+}
+
+impl StoreConnectionState {
+    /// Gli store senza login restano utilizzabili con la sola scansione locale
+    pub fn is_connected(self) -> bool {
+        matches!(self, Self::Connected | Self::LocalOnly)
+    }
+}
+
+/// Esito strutturato dei test `check_*_connection`; `message` è il testo restituito dai comandi `test_*_connection`
+#[derive(Debug, Clone)]
+pub struct ConnectionCheck {
+    pub state: StoreConnectionState,
+    pub message: String,
+    /// Giochi trovati dalla scansione locale, se il test l'ha già eseguita
+    pub local_games: Option<u32>,
+}
+
+impl ConnectionCheck {
+    pub fn new(state: StoreConnectionState, message: String) -> Self {
+        Self { state, message, local_games: None }
+    }
+
+    /// Registra il conteggio della scansione locale, così la dashboard non la ripete
+    pub fn with_local_games(mut self, count: usize) -> Self {
+        self.local_games = Some(count as u32);
+        self
+    }
+}
+
+/// Stato di connessione di uno store (stessa forma di `SteamConnectionStatus`, generalizzata)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreConnectionStatus {
+    pub connected: bool,
+    pub state: StoreConnectionState,
+    pub games_count: u32,
+    pub last_checked: String,
+    pub error: Option<String>,
+}
+
+/// Timeout per singolo test, così uno store lento non blocca l'intera dashboard
+const CONNECTION_TEST_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoresConfig {
     pub stores: Vec<StoreStatus>,
@@ -350,6 +410,136 @@ pub async fn get_installed_stores() -> Result<Vec<StoreStatus>, String> {
     Ok(stores)
 }
 
+async fn with_timeout<T>(future: impl Future<Output = Result<T, String>>) -> Result<T, String> {
+    tokio::time::timeout(CONNECTION_TEST_TIMEOUT, future)
+        .await
+        .unwrap_or_else(|_| Err(format!("Timeout dopo {}s", CONNECTION_TEST_TIMEOUT.as_secs())))
+}
+
+fn connection_status(state: StoreConnectionState, games_count: u32, error: Option<String>) -> StoreConnectionStatus {
+    StoreConnectionStatus {
+        connected: state.is_connected(),
+        state,
+        games_count,
+        last_checked: chrono::Utc::now().to_rfc3339(),
+        error,
+    }
+}
+
+/// Stato per gli store con test `check_*_connection`: il conteggio arriva dalla scansione locale,
+/// riusando quella del test quando l'ha già eseguita
+async fn checked_status<I>(
+    check: impl Future<Output = Result<ConnectionCheck, String>>,
+    installed: impl FnOnce() -> I,
+) -> StoreConnectionStatus
+where
+    I: Future<Output = Result<Vec<InstalledGame>, String>>,
+{
+    let check = with_timeout(check).await;
+    let scanned = check.as_ref().ok().and_then(|check| check.local_games);
+    let games_count = match scanned {
+        Some(count) => count,
+        None => with_timeout(installed()).await.map(|games| games.len() as u32).unwrap_or(0),
+    };
+
+    match check {
+        Ok(check) if check.state.is_connected() => connection_status(check.state, games_count, None),
+        Ok(check) => connection_status(check.state, games_count, Some(check.message)),
+        Err(e) => connection_status(StoreConnectionState::Error, games_count, Some(e)),
+    }
+}
+
+/// Per gli store senza login il test riesce o fallisce: un `Ok` equivale a connesso
+async fn reachable(test: impl Future<Output = Result<String, String>>) -> Result<ConnectionCheck, String> {
+    test.await.map(|message| ConnectionCheck::new(StoreConnectionState::Connected, message))
+}
+
+async fn steam_connection_status(profile_state: tauri::State<'_, ProfileManagerState>) -> StoreConnectionStatus {
+    match with_timeout(crate::commands::steam::test_steam_connection(profile_state)).await {
+        Ok(result) if result.connected => connection_status(StoreConnectionState::Connected, result.games_count.unwrap_or(0), result.error),
+        // Senza credenziali la libreria locale resta utilizzabile
+        Ok(result) if !result.has_credentials && result.games_count.unwrap_or(0) > 0 => {
+            connection_status(StoreConnectionState::LocalOnly, result.games_count.unwrap_or(0), None)
+        }
+        Ok(result) => connection_status(StoreConnectionState::Error, result.games_count.unwrap_or(0), result.error),
+        Err(e) => connection_status(StoreConnectionState::Error, 0, Some(e)),
+    }
+}
+
+async fn epic_connection_status() -> StoreConnectionStatus {
+    match with_timeout(crate::commands::epic::test_epic_connection()).await {
+        Ok(result) => {
+            let state = if result["connected"].as_bool().unwrap_or(false) {
+                StoreConnectionState::Connected
+            } else {
+                StoreConnectionState::Error
+            };
+            connection_status(
+                state,
+                result["games_count"].as_u64().unwrap_or(0) as u32,
+                result["error"].as_str().map(str::to_string),
+            )
+        }
+        Err(e) => connection_status(StoreConnectionState::Error, 0, Some(e)),
+    }
+}
+
+/// Esegue il test di uno store su un thread bloccante dedicato: le scansioni locali leggono
+/// registro e disco in modo sincrono e, sullo stesso task, serializzerebbero tutti gli store
+async fn on_blocking_thread<F>(test: impl FnOnce() -> F + Send + 'static) -> StoreConnectionStatus
+where
+    F: Future<Output = StoreConnectionStatus>,
+{
+    tokio::task::spawn_blocking(move || tauri::async_runtime::block_on(test()))
+        .await
+        .unwrap_or_else(|e| connection_status(StoreConnectionState::Error, 0, Some(format!("Test interrotto: {}", e))))
+}
+
+/// Comando Tauri: Esegue in parallelo i test di connessione di tutti gli store
+#[tauri::command]
+pub async fn get_all_connection_statuses(
+    app: tauri::AppHandle,
+) -> Result<HashMap<String, StoreConnectionStatus>, String> {
+    use crate::commands::{amazon, battlenet, gog, itchio, origin, rockstar, ubisoft};
+    use tauri::Manager;
+
+    println!("[STORES] 🔌 Test connessione di tutti gli store");
+
+    let (steam, epic, gog, origin, ubisoft, battlenet, itchio, rockstar, amazon) = tokio::join!(
+        on_blocking_thread(move || async move {
+            steam_connection_status(app.state::<ProfileManagerState>()).await
+        }),
+        on_blocking_thread(epic_connection_status),
+        on_blocking_thread(|| checked_status(reachable(gog::test_gog_connection()), gog::get_gog_installed_games)),
+        on_blocking_thread(|| checked_status(origin::check_origin_connection(), origin::get_origin_installed_games)),
+        on_blocking_thread(|| checked_status(ubisoft::check_ubisoft_connection(), ubisoft::get_ubisoft_installed_games)),
+        on_blocking_thread(|| checked_status(battlenet::check_battlenet_connection(), battlenet::get_battlenet_installed_games)),
+        on_blocking_thread(|| checked_status(itchio::check_itchio_connection(None), itchio::get_itchio_installed_games)),
+        on_blocking_thread(|| checked_status(rockstar::check_rockstar_connection(), rockstar::get_rockstar_installed_games)),
+        on_blocking_thread(|| checked_status(amazon::check_amazon_connection(), amazon::get_amazon_installed_games)),
+    );
+
+    let statuses: HashMap<String, StoreConnectionStatus> = [
+        ("steam", steam),
+        ("epic", epic),
+        ("gog", gog),
+        ("origin", origin),
+        ("ubisoft", ubisoft),
+        ("battlenet", battlenet),
+        ("itchio", itchio),
+        ("rockstar", rockstar),
+        ("amazon", amazon),
+    ]
+    .into_iter()
+    .map(|(id, status)| (id.to_string(), status))
+    .collect();
+
+    let connected = statuses.values().filter(|s| s.connected).count();
+    println!("[STORES] ✅ {}/{} store connessi", connected, statuses.len());
+
+    Ok(statuses)
+}
+
 /// Comando Tauri: Salva la configurazione degli store
 #[tauri::command]
 pub async fn save_stores_config(config: StoresConfig) -> Result<(), String> {
@@ -0,0 +1,606 @@
+//! 🌐 Traduzione offline
+//!
+//! Unico punto di ingresso per OCR e frontend: nessuna rete, usa solo
+//! Translation Memory, glossario del gioco, dizionario del bridge e dizionario integrato.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use log::{debug, info};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tauri::State;
+
+use super::file_manager::{decode_text, encode_text, write_atomic, UNKNOWN_ENCODING};
+use super::glossary::{load_glossary_sync, GameGlossary};
+use super::patches::{escape_csv_field, escape_xunity, find_unescaped_equals, parse_csv_line, unescape_autotranslator, CommunityFormat};
+use super::translation_bridge::TranslationBridgeState;
+use super::translation_memory::{fuzzy_lookup_units, load_translation_memory, TranslationUnit};
+use crate::string_id::{lookup_text, typography_normalization_enabled};
+use crate::translation_bridge::dictionary_engine::DictionaryEngine;
+
+/// Durata della cache TM in memoria (le TM vengono ricaricate da disco dopo questo intervallo)
+const TM_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Punteggio minimo (0-100) per accettare un match fuzzy dalla TM
+const MIN_FUZZY_SCORE: u8 = 75;
+
+/// Commento inserito da `translate_file` sopra le righe rimaste non tradotte
+const UNTRANSLATED_MARKER: &str = "GameStringer: non tradotto";
+
+/// Righe tradotte da `translate_file` prima di rilasciare il lock del bridge: un file grande
+/// non deve bloccare per tutta la durata le traduzioni in tempo reale dei giochi collegati
+const FILE_TRANSLATION_BATCH: usize = 200;
+
+/// Origine della traduzione offline
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OfflineTranslationSource {
+    TranslationMemory,
+    Glossary,
+    BridgeDictionary,
+    BuiltinDictionary,
+    None,
+}
+
+/// Risultato di una traduzione offline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineTranslation {
+    pub original: String,
+    /// Traduzione trovata (None se nessuna fonte ha un match)
+    pub translated: Option<String>,
+    /// Confidenza 0.0 - 1.0
+    pub confidence: f64,
+    pub source: OfflineTranslationSource,
+    /// "exact" oppure "fuzzy"
+    pub match_type: Option<String>,
+}
+
+impl OfflineTranslation {
+    fn not_found(original: &str) -> Self {
+        Self {
+            original: original.to_string(),
+            translated: None,
+            confidence: 0.0,
+            source: OfflineTranslationSource::None,
+            match_type: None,
+        }
+    }
+
+    fn found(original: &str, translated: String, confidence: f64, source: OfflineTranslationSource, match_type: &str) -> Self {
+        Self {
+            original: original.to_string(),
+            translated: Some(translated),
+            confidence,
+            source,
+            match_type: Some(match_type.to_string()),
+        }
+    }
+}
+
+/// TM caricata in memoria per una coppia di lingue
+struct CachedMemory {
+    loaded_at: Instant,
+    /// Stato della normalizzazione tipografica con cui sono state calcolate le chiavi
+    typography: bool,
+    /// Sorgente minuscola -> (traduzione, confidenza)
+    exact: HashMap<String, (String, f64)>,
+    units: Vec<TranslationUnit>,
+}
+
+static TM_CACHE: Lazy<Mutex<HashMap<String, CachedMemory>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Cerca la migliore traduzione offline, in ordine: TM esatta, glossario, dizionario bridge,
+/// dizionario integrato, TM fuzzy. `glossary` va caricato una volta per chiamata con
+/// `load_offline_glossary`; `bridge_dictionary` è disponibile solo dai comandi Tauri.
+pub fn translate_offline_text(
+    text: &str,
+    source_lang: &str,
+    target_lang: &str,
+    glossary: Option<&GameGlossary>,
+    bridge_dictionary: Option<&DictionaryEngine>,
+) -> OfflineTranslation {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return OfflineTranslation::not_found(text);
+    }
+
+    // Chiave di lookup con tipografia uniformata; `text` resta l'originale mostrato all'utente
+    let lookup = lookup_text(trimmed);
+    let trimmed = lookup.as_ref();
+
+    let source_lang = source_lang.to_lowercase();
+    let target_lang = target_lang.to_lowercase();
+    let key = format!("{}_{}", source_lang, target_lang);
+    let text_lower = trimmed.to_lowercase();
+
+    let mut cache = TM_CACHE.lock();
+    let memory = cached_memory(&mut cache, &key, &source_lang, &target_lang);
+
+    // 1. Translation Memory - match esatto
+    if let Some((translated, confidence)) = memory.and_then(|m| m.exact.get(&text_lower)) {
+        return OfflineTranslation::found(text, translated.clone(), *confidence, OfflineTranslationSource::TranslationMemory, "exact");
+    }
+
+    // 2. Glossario del gioco - voce che copre l'intero testo
+    if let Some(translated) = glossary.and_then(|g| glossary_exact_match(g, trimmed, &text_lower)) {
+        return OfflineTranslation::found(text, translated, 0.9, OfflineTranslationSource::Glossary, "exact");
+    }
+
+    // 3. Dizionario caricato nel Translation Bridge
+    if let Some(translated) = bridge_dictionary.and_then(|d| d.get_translation_for(&source_lang, &target_lang, trimmed)) {
+        return OfflineTranslation::found(text, translated, 0.9, OfflineTranslationSource::BridgeDictionary, "exact");
+    }
+
+    // 4. Dizionario integrato (solo EN -> IT)
+    if source_lang == "en" {
+        if let Some(translated) = crate::ocr_translator::simple_translate(trimmed, &target_lang) {
+            return OfflineTranslation::found(text, translated, 0.7, OfflineTranslationSource::BuiltinDictionary, "exact");
+        }
+    }
+
+    // 5. Translation Memory - match fuzzy
+    if let Some(best) = memory.and_then(|m| fuzzy_lookup_units(&m.units, trimmed, MIN_FUZZY_SCORE, 1).into_iter().next()) {
+        let confidence = best.score as f64 / 100.0 * 0.85;
+        return OfflineTranslation::found(text, best.target_text, confidence, OfflineTranslationSource::TranslationMemory, "fuzzy");
+    }
+
+    OfflineTranslation::not_found(text)
+}
+
+/// Traduce più testi riusando la stessa TM in cache e lo stesso glossario
+pub fn translate_offline_texts(
+    texts: &[String],
+    source_lang: &str,
+    target_lang: &str,
+    glossary: Option<&GameGlossary>,
+    bridge_dictionary: Option<&DictionaryEngine>,
+) -> Vec<OfflineTranslation> {
+    texts.iter()
+        .map(|text| translate_offline_text(text, source_lang, target_lang, glossary, bridge_dictionary))
+        .collect()
+}
+
+/// Ritorna la TM in cache per la coppia di lingue, ricaricandola se scaduta
+fn cached_memory<'a>(
+    cache: &'a mut HashMap<String, CachedMemory>,
+    key: &str,
+    source_lang: &str,
+    target_lang: &str,
+) -> Option<&'a CachedMemory> {
+    let typography = typography_normalization_enabled();
+    let fresh = matches!(cache.get(key), Some(m) if m.loaded_at.elapsed() <= TM_CACHE_TTL && m.typography == typography);
+    if !fresh {
+        let units = match load_translation_memory(source_lang.to_string(), target_lang.to_string()) {
+            Ok(Some(memory)) => memory.units,
+            Ok(None) => Vec::new(),
+            Err(e) => {
+                debug!("⚠️ TM non disponibile per traduzione offline: {}", e);
+                Vec::new()
+            }
+        };
+
+        let exact = units.iter()
+            .map(|unit| {
+                let confidence = if unit.verified { 1.0 } else { unit.confidence.clamp(0.0, 0.95) };
+                (lookup_text(&unit.source_text).to_lowercase(), (unit.target_text.clone(), confidence))
+            })
+            .collect();
+
+        cache.insert(key.to_string(), CachedMemory { loaded_at: Instant::now(), typography, exact, units });
+    }
+
+    cache.get(key).filter(|m| !m.units.is_empty())
+}
+
+/// Glossario del gioco, se esiste ed è per la coppia di lingue richiesta
+pub fn load_offline_glossary(game_id: Option<&str>, source_lang: &str, target_lang: &str) -> Option<GameGlossary> {
+    let glossary = load_glossary_sync(game_id?).ok().flatten()?;
+    (glossary.source_language.eq_ignore_ascii_case(source_lang)
+        && glossary.target_language.eq_ignore_ascii_case(target_lang))
+        .then_some(glossary)
+}
+
+/// Voce di glossario che corrisponde all'intero testo (`text_lower` è `text` in minuscolo)
+fn glossary_exact_match(glossary: &GameGlossary, text: &str, text_lower: &str) -> Option<String> {
+    glossary.entries.iter()
+        .find(|entry| {
+            let original = lookup_text(&entry.original);
+            if entry.case_sensitive {
+                original == text
+            } else {
+                original.to_lowercase() == text_lower
+            }
+        })
+        .map(|entry| entry.translation.clone())
+}
+
+/// 🌐 Traduce un testo senza rete (TM, glossario, dizionari)
+#[tauri::command]
+pub fn translate_offline(
+    text: String,
+    source: String,
+    target: String,
+    game_id: Option<String>,
+    bridge_state: State<'_, TranslationBridgeState>,
+) -> Result<OfflineTranslation, String> {
+    let bridge = bridge_state.bridge.lock();
+    let dictionary = bridge.dictionary().read();
+
+    let glossary = load_offline_glossary(game_id.as_deref(), &source, &target);
+    let result = translate_offline_text(&text, &source, &target, glossary.as_ref(), Some(&dictionary));
+    debug!("🌐 Traduzione offline ({:?}, {:.2}): {}", result.source, result.confidence,
+        text.chars().take(50).collect::<String>());
+    Ok(result)
+}
+
+/// 🌐 Traduce un batch di testi senza rete
+#[tauri::command]
+pub fn translate_offline_batch(
+    texts: Vec<String>,
+    source: String,
+    target: String,
+    game_id: Option<String>,
+    bridge_state: State<'_, TranslationBridgeState>,
+) -> Result<Vec<OfflineTranslation>, String> {
+    let bridge = bridge_state.bridge.lock();
+    let dictionary = bridge.dictionary().read();
+
+    let glossary = load_offline_glossary(game_id.as_deref(), &source, &target);
+    let results = translate_offline_texts(&texts, &source, &target, glossary.as_ref(), Some(&dictionary));
+    let translated = results.iter().filter(|r| r.translated.is_some()).count();
+    info!("🌐 Batch offline: {}/{} testi tradotti ({} → {})", translated, results.len(), source, target);
+    Ok(results)
+}
+
+/// Esito di una riga di `translate_file`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileLineStatus {
+    Translated,
+    Fuzzy,
+    Untranslated,
+}
+
+/// Dettaglio di una riga traducibile del file
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileLineResult {
+    /// Numero di riga nel file di input (da 1)
+    pub line: usize,
+    /// Chiave (key=value, CSV) oppure testo originale (AutoTranslator)
+    pub key: String,
+    pub status: FileLineStatus,
+    pub source: OfflineTranslationSource,
+    pub confidence: f64,
+}
+
+/// Risultato di `translate_file`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTranslationReport {
+    pub input_path: String,
+    pub output_path: String,
+    pub format: String,
+    pub encoding: String,
+    pub has_bom: bool,
+    pub translated: usize,
+    pub fuzzy: usize,
+    pub untranslated: usize,
+    pub lines: Vec<FileLineResult>,
+}
+
+/// Sintassi dei commenti usata per marcare le righe non tradotte (il CSV non ne ha)
+fn comment_prefix(format: CommunityFormat, path: &Path) -> Option<&'static str> {
+    match format {
+        CommunityFormat::AutoTranslator => Some("//"),
+        CommunityFormat::KeyValue if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("ini")) => Some(";"),
+        CommunityFormat::KeyValue => Some("#"),
+        CommunityFormat::Csv => None,
+    }
+}
+
+/// Colonne (sorgente, destinazione) del CSV, dedotte dall'intestazione se presente
+fn csv_columns(header: &[String], target_lang: &str) -> Option<(usize, usize)> {
+    let names: Vec<String> = header.iter().map(|h| h.trim().to_lowercase()).collect();
+    let source = names.iter().position(|n| matches!(n.as_str(), "source" | "original" | "text"))?;
+    let target = names.iter()
+        .position(|n| matches!(n.as_str(), "target" | "translation" | "translated") || *n == target_lang.to_lowercase())
+        .unwrap_or(source + 1);
+    Some((source, target))
+}
+
+/// Riscrive il contenuto di un file traducendo ogni valore con `translate`.
+/// Commenti, righe vuote, chiavi e fine riga restano invariati; le righe senza
+/// traduzione non vengono toccate e, se il formato ha commenti, sono precedute
+/// da `UNTRANSLATED_MARKER` (i marker di passate precedenti vengono rigenerati).
+fn translate_file_content(
+    content: &str,
+    format: CommunityFormat,
+    comment: Option<&str>,
+    target_lang: &str,
+    mut translate: impl FnMut(&str) -> OfflineTranslation,
+) -> (String, Vec<FileLineResult>) {
+    let eol = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let marker = comment.map(|c| format!("{} {}", c, UNTRANSLATED_MARKER));
+    let mut output = String::with_capacity(content.len() + content.len() / 4);
+    let mut results = Vec::new();
+    let mut csv_cols = (0, 1);
+
+    for (index, raw) in content.split_inclusive('\n').enumerate() {
+        let line = raw.trim_end_matches(['\r', '\n']);
+        let ending = &raw[line.len()..];
+        let trimmed = line.trim();
+
+        if marker.as_deref().is_some_and(|m| trimmed == m) {
+            continue;
+        }
+
+        // (chiave, testo sorgente, riga ricostruita con la traduzione)
+        let entry: Option<(String, String, Box<dyn Fn(&str) -> String + '_>)> = match format {
+            _ if trimmed.is_empty() => None,
+            CommunityFormat::AutoTranslator => {
+                if trimmed.starts_with("//") || trimmed.starts_with("r:") || trimmed.starts_with("sr:") {
+                    None
+                } else {
+                    find_unescaped_equals(line).map(|idx| {
+                        let source = unescape_autotranslator(&line[..idx]);
+                        let rebuild: Box<dyn Fn(&str) -> String + '_> =
+                            Box::new(move |t: &str| format!("{}={}", &line[..idx], escape_xunity(t)));
+                        (source.clone(), source, rebuild)
+                    })
+                }
+            }
+            CommunityFormat::KeyValue => {
+                if trimmed.starts_with('#') || trimmed.starts_with(';') || trimmed.starts_with('[') {
+                    None
+                } else {
+                    line.find('=').map(|idx| {
+                        let (head, value) = line.split_at(idx + 1);
+                        let start = head.len() + (value.len() - value.trim_start().len());
+                        let end = head.len() + value.trim_end().len();
+                        let rebuild: Box<dyn Fn(&str) -> String + '_> = Box::new(move |t: &str| {
+                            format!("{}{}{}", &line[..start], t.replace('\n', "\\n"), &line[end.max(start)..])
+                        });
+                        (head[..idx].trim().to_string(), value.trim().replace("\\n", "\n"), rebuild)
+                    })
+                }
+            }
+            CommunityFormat::Csv => {
+                let fields = parse_csv_line(line);
+                if index == 0 {
+                    if let Some(cols) = csv_columns(&fields, target_lang) {
+                        csv_cols = cols;
+                    }
+                }
+                let is_header = index == 0 && fields.first().is_some_and(|h| {
+                    matches!(h.trim().to_lowercase().as_str(), "source" | "original" | "key" | "id" | "text")
+                });
+                let (source_col, target_col) = csv_cols;
+                match fields.get(source_col) {
+                    Some(source) if !is_header => {
+                        let key = fields[0].trim().to_string();
+                        let source = source.trim().to_string();
+                        let rebuild: Box<dyn Fn(&str) -> String + '_> = Box::new(move |t: &str| {
+                            let mut fields = fields.clone();
+                            if fields.len() <= target_col {
+                                fields.resize(target_col + 1, String::new());
+                            }
+                            fields[target_col] = t.to_string();
+                            fields.iter().map(|f| escape_csv_field(f)).collect::<Vec<_>>().join(",")
+                        });
+                        Some((key, source, rebuild))
+                    }
+                    _ => None,
+                }
+            }
+        };
+
+        let Some((key, source, rebuild)) = entry.filter(|(_, source, _)| !source.trim().is_empty()) else {
+            output.push_str(raw);
+            continue;
+        };
+
+        let result = translate(&source);
+        let status = match (&result.translated, result.match_type.as_deref()) {
+            (None, _) => FileLineStatus::Untranslated,
+            (Some(_), Some("fuzzy")) => FileLineStatus::Fuzzy,
+            (Some(_), _) => FileLineStatus::Translated,
+        };
+
+        match &result.translated {
+            Some(translated) => {
+                output.push_str(&rebuild(translated));
+                output.push_str(ending);
+            }
+            None => {
+                if let Some(marker) = &marker {
+                    output.push_str(marker);
+                    output.push_str(eol);
+                }
+                output.push_str(raw);
+            }
+        }
+
+        results.push(FileLineResult {
+            line: index + 1,
+            key,
+            status,
+            source: result.source,
+            confidence: result.confidence,
+        });
+    }
+
+    (output, results)
+}
+
+/// 📄 Traduce un intero file (AutoTranslator, key=value o CSV) con TM, glossario e dizionari.
+/// `format`: "auto" (default, dall'estensione), "autotranslator", "keyvalue" o "csv".
+/// Encoding, BOM e fine riga del file originale vengono mantenuti; `output_path` può
+/// coincidere con `input_path` per tradurre sul posto (la scrittura è atomica).
+/// I codepage legacy (Shift_JIS, Windows-1252...) non vengono indovinati: vanno
+/// indicati con `encoding`. Se il file contiene byte non validi per l'encoding si
+/// ferma, salvo `allow_lossy`: riscriverlo sostituirebbe quei caratteri con U+FFFD.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn translate_file(
+    input_path: String,
+    output_path: String,
+    source: String,
+    target: String,
+    format: Option<String>,
+    game_id: Option<String>,
+    encoding: Option<String>,
+    allow_lossy: Option<bool>,
+    bridge_state: State<'_, TranslationBridgeState>,
+) -> Result<FileTranslationReport, String> {
+    info!("📄 Traduzione file: {} ({} → {})", input_path, source, target);
+
+    let input = PathBuf::from(&input_path);
+    if !input.is_file() {
+        return Err(format!("File non trovato: {}", input_path));
+    }
+    let format = match format.as_deref().map(str::trim).filter(|f| !f.is_empty() && !f.eq_ignore_ascii_case("auto")) {
+        Some(name) => CommunityFormat::parse(name)
+            .ok_or_else(|| format!("Formato non supportato: {}. Usa: auto, autotranslator, keyvalue, csv", name))?,
+        None => CommunityFormat::from_extension(&input)
+            .ok_or_else(|| format!("Impossibile dedurre il formato di {}: specificalo esplicitamente", input_path))?,
+    };
+
+    let bytes = std::fs::read(&input).map_err(|e| format!("Errore lettura {}: {}", input_path, e))?;
+    let decoded = decode_text(&bytes, encoding.as_deref())?;
+    if decoded.encoding == UNKNOWN_ENCODING {
+        return Err(format!(
+            "Impossibile determinare l'encoding di {}: specificalo con encoding (es. shift_jis, windows-1252)",
+            input_path
+        ));
+    }
+    if decoded.had_errors && !allow_lossy.unwrap_or(false) {
+        return Err(format!(
+            "{} contiene caratteri non validi per {}: il file verrebbe danneggiato. Controlla l'encoding o conferma con allow_lossy",
+            input_path, decoded.encoding
+        ));
+    }
+
+    let glossary = load_offline_glossary(game_id.as_deref(), &source, &target);
+    let (content, lines) = {
+        let mut bridge = None;
+        let mut batch_lines = 0;
+        translate_file_content(&decoded.content, format, comment_prefix(format, &input), &target, |text| {
+            if batch_lines == FILE_TRANSLATION_BATCH {
+                // Rilascio equo: chi è in attesa del bridge passa prima del batch successivo
+                if let Some(guard) = bridge.take() {
+                    parking_lot::MutexGuard::unlock_fair(guard);
+                }
+                batch_lines = 0;
+            }
+            batch_lines += 1;
+            let guard = bridge.get_or_insert_with(|| bridge_state.bridge.lock());
+            let dictionary = guard.dictionary().read();
+            translate_offline_text(text, &source, &target, glossary.as_ref(), Some(&dictionary))
+        })
+    };
+
+    let output = PathBuf::from(&output_path);
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Errore creazione cartella {}: {}", parent.display(), e))?;
+    }
+    let encoded = encode_text(&content, &decoded.encoding, decoded.has_bom)?;
+    write_atomic(&output, &encoded).map_err(|e| format!("Errore scrittura {}: {}", output_path, e))?;
+
+    let count = |status: FileLineStatus| lines.iter().filter(|l| l.status == status).count();
+    let report = FileTranslationReport {
+        input_path,
+        output_path,
+        format: format!("{:?}", format).to_lowercase(),
+        encoding: decoded.encoding,
+        has_bom: decoded.has_bom,
+        translated: count(FileLineStatus::Translated),
+        fuzzy: count(FileLineStatus::Fuzzy),
+        untranslated: count(FileLineStatus::Untranslated),
+        lines,
+    };
+    info!("📄 File tradotto: {} esatte, {} fuzzy, {} non tradotte → {}",
+        report.translated, report.fuzzy, report.untranslated, report.output_path);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate_with<'a>(table: &'a [(&'a str, &'a str)]) -> impl FnMut(&str) -> OfflineTranslation + 'a {
+        move |text: &str| {
+            let mut result = OfflineTranslation::not_found(text);
+            if let Some((_, translated)) = table.iter().find(|(source, _)| *source == text) {
+                result.translated = Some(translated.to_string());
+                result.confidence = 1.0;
+                result.source = OfflineTranslationSource::TranslationMemory;
+                result.match_type = Some("exact".to_string());
+            }
+            result
+        }
+    }
+
+    fn marker(comment: &str) -> String {
+        format!("{} {}", comment, UNTRANSLATED_MARKER)
+    }
+
+    #[test]
+    fn autotranslator_keeps_escapes() {
+        let content = "// Generato\nHello\\nWorld=\nA\\=B=\nMissing=\n";
+        let table = [("Hello\nWorld", "Ciao\nMondo"), ("A=B", "X=Y")];
+        let (output, lines) = translate_file_content(content, CommunityFormat::AutoTranslator, Some("//"), "it", translate_with(&table));
+        assert_eq!(output, format!("// Generato\nHello\\nWorld=Ciao\\nMondo\nA\\=B=X\\=Y\n{}\nMissing=\n", marker("//")));
+        let statuses: Vec<FileLineStatus> = lines.iter().map(|l| l.status).collect();
+        assert_eq!(statuses, vec![FileLineStatus::Translated, FileLineStatus::Translated, FileLineStatus::Untranslated]);
+        assert_eq!(lines[2].line, 4);
+    }
+
+    #[test]
+    fn key_value_translates_values_with_newlines() {
+        let content = "[menu]\ntitle = Line one\\nLine two\n# commento\nother=Bye\n";
+        let table = [("Line one\nLine two", "Riga uno\nRiga due")];
+        let (output, lines) = translate_file_content(content, CommunityFormat::KeyValue, Some("#"), "it", translate_with(&table));
+        assert_eq!(output, format!("[menu]\ntitle = Riga uno\\nRiga due\n# commento\n{}\nother=Bye\n", marker("#")));
+        assert_eq!(lines[0].key, "title");
+        assert_eq!(lines[1].key, "other");
+    }
+
+    #[test]
+    fn csv_detects_header_and_columns() {
+        let content = "key,source,it\nk1,Hello,\nk2,\"Hi, there\",\nk3,Missing,\n";
+        let table = [("Hello", "Ciao"), ("Hi, there", "Salve, a te")];
+        let (output, lines) = translate_file_content(content, CommunityFormat::Csv, None, "it", translate_with(&table));
+        assert_eq!(output, "key,source,it\nk1,Hello,Ciao\nk2,\"Hi, there\",\"Salve, a te\"\nk3,Missing,\n");
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].key, "k1");
+        assert_eq!(lines[2].status, FileLineStatus::Untranslated);
+
+        // Senza intestazione: sorgente nella prima colonna, traduzione nella seconda
+        let (output, _) = translate_file_content("Hello,\n", CommunityFormat::Csv, None, "it", translate_with(&table));
+        assert_eq!(output, "Hello,Ciao\n");
+    }
+
+    #[test]
+    fn markers_are_regenerated_not_duplicated() {
+        let content = "a=Hello\nb=Bye\n";
+        let (first, _) = translate_file_content(content, CommunityFormat::KeyValue, Some("#"), "it", translate_with(&[]));
+        let (second, _) = translate_file_content(&first, CommunityFormat::KeyValue, Some("#"), "it", translate_with(&[]));
+        assert_eq!(second, first);
+        assert_eq!(first.matches(UNTRANSLATED_MARKER).count(), 2);
+
+        // Una volta tradotta, la riga perde il marker
+        let (third, _) = translate_file_content(&second, CommunityFormat::KeyValue, Some("#"), "it", translate_with(&[("Hello", "Ciao")]));
+        assert_eq!(third, format!("a=Ciao\n{}\nb=Bye\n", marker("#")));
+    }
+
+    #[test]
+    fn crlf_line_endings_are_preserved() {
+        let content = "a=Hello\r\nb=Bye\r\nc=Hello";
+        let (output, _) = translate_file_content(content, CommunityFormat::KeyValue, Some(";"), "it", translate_with(&[("Hello", "Ciao")]));
+        assert_eq!(output, format!("a=Ciao\r\n{}\r\nb=Bye\r\nc=Ciao", marker(";")));
+    }
+}
@@ -8,7 +8,8 @@ use serde::{Deserialize, Serialize};
 use tauri::State;
 
 use crate::translation_bridge::TranslationBridge;
-use crate::translation_bridge::shared_memory_ipc::BridgeStats;
+use crate::translation_bridge::protocol::PROTOCOL_VERSION;
+use crate::translation_bridge::shared_memory_ipc::{BridgeStats, DictionaryReload, PluginHandshake};
 use crate::translation_bridge::dictionary_engine::DictionaryStats;
 
 /// Stato globale del Translation Bridge
@@ -79,13 +80,37 @@ pub async fn translation_bridge_stop(
     Ok(BridgeResponse::ok("Translation Bridge fermato".to_string()))
 }
 
-/// Verifica se il bridge è in esecuzione
+/// Stato del bridge e compatibilità del plugin connesso
+#[derive(Debug, Serialize)]
+pub struct BridgeStatus {
+    pub running: bool,
+    /// Versione del protocollo del backend
+    pub protocol_version: u8,
+    /// Ultimo handshake del plugin (None se il plugin non si è ancora presentato)
+    pub plugin: Option<PluginHandshake>,
+    /// false se il plugin ha una versione del protocollo incompatibile
+    pub compatible: bool,
+    /// Spiegazione leggibile dello stato (versione non compatibile, plugin in attesa, ...)
+    pub message: Option<String>,
+}
+
+/// Verifica se il bridge è in esecuzione e se il plugin è compatibile
 #[tauri::command]
 pub async fn translation_bridge_status(
     state: State<'_, TranslationBridgeState>,
-) -> Result<BridgeResponse<bool>, String> {
+) -> Result<BridgeResponse<BridgeStatus>, String> {
     let bridge = state.bridge.lock();
-    Ok(BridgeResponse::ok(bridge.is_running()))
+    let running = bridge.is_running();
+    let plugin = bridge.plugin_handshake();
+    
+    let compatible = !plugin.as_ref().is_some_and(|h| !h.response.accepted);
+    let message = match &plugin {
+        Some(h) if !h.response.accepted => h.response.error.clone(),
+        None if running => Some("In attesa dell'handshake del plugin".to_string()),
+        _ => None,
+    };
+    
+    Ok(BridgeResponse::ok(BridgeStatus { running, protocol_version: PROTOCOL_VERSION, plugin, compatible, message }))
 }
 
 /// Ottieni statistiche del bridge
@@ -152,6 +177,20 @@ pub async fn translation_bridge_load_json(
     }
 }
 
+/// Ricarica un dizionario JSON sostituendolo in blocco (sicuro anche a gioco avviato)
+#[tauri::command]
+pub async fn translation_bridge_reload_from_file(
+    state: State<'_, TranslationBridgeState>,
+    path: String,
+) -> Result<BridgeResponse<DictionaryReload>, String> {
+    let bridge = state.bridge.lock();
+    
+    match bridge.reload_dictionary_from_file(&path) {
+        Ok(reload) => Ok(BridgeResponse::ok(reload)),
+        Err(e) => Ok(BridgeResponse::err(e)),
+    }
+}
+
 /// Imposta le lingue attive
 #[tauri::command]
 pub async fn translation_bridge_set_languages(
@@ -178,6 +217,26 @@ pub async fn translation_bridge_add_translation(
     Ok(BridgeResponse::ok(format!("Aggiunta traduzione: {}", original)))
 }
 
+/// Esito dell'aggiunta in blocco
+#[derive(Debug, Serialize)]
+pub struct BulkAddResult {
+    pub added: usize,
+    /// Testi già presenti nel dizionario (o ripetuti nel lotto), lasciati invariati
+    pub duplicates_skipped: usize,
+}
+
+/// Aggiungi più traduzioni con una sola chiamata (popolamento iniziale del dizionario)
+#[tauri::command]
+pub async fn translation_bridge_add_translations(
+    state: State<'_, TranslationBridgeState>,
+    pairs: Vec<(String, String)>,
+) -> Result<BridgeResponse<BulkAddResult>, String> {
+    let bridge = state.bridge.lock();
+    let mut dict = bridge.dictionary().write();
+    let (added, duplicates_skipped) = dict.add_translations(pairs);
+    Ok(BridgeResponse::ok(BulkAddResult { added, duplicates_skipped }))
+}
+
 /// Cerca una traduzione
 #[tauri::command]
 pub async fn translation_bridge_get_translation(
@@ -1,7 +1,13 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use crate::string_id::{lookup_text, normalize_source_text, string_id};
+use super::file_manager::write_atomic;
 
 /// 🧠 Translation Memory System - Backend Rust
 /// 
@@ -32,6 +38,9 @@ pub struct TranslationUnit {
     #[serde(alias = "updated_at")]
     pub updated_at: String,
     pub metadata: Option<TranslationUnitMetadata>,
+    /// Id canonico del testo sorgente (condiviso con patch e bridge)
+    #[serde(default, alias = "string_id")]
+    pub string_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -75,6 +84,10 @@ pub struct TranslationMemory {
     pub created_at: String,
     #[serde(alias = "updated_at")]
     pub updated_at: String,
+    /// Istante del caricamento da cui deriva questa copia (non salvato su disco): gli upsert
+    /// del journal non più recenti erano già inclusi, se mancano sono stati eliminati
+    #[serde(default, alias = "loaded_at", skip_serializing_if = "Option::is_none")]
+    pub loaded_at: Option<String>,
 }
 
 /// Ottiene il percorso della directory dati
@@ -98,6 +111,210 @@ fn get_tm_filename(source_lang: &str, target_lang: &str) -> String {
     format!("tm_{}_{}.json", source_lang.to_lowercase(), target_lang.to_lowercase())
 }
 
+/// Indice in memoria delle sorgenti (minuscole) per ogni TM, usato dagli upsert incrementali
+static TM_SOURCE_INDEX: Lazy<Mutex<HashMap<String, HashSet<String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Riga del journal degli upsert incrementali (una per riga, JSON)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct TmJournalEntry {
+    source_text: String,
+    target_text: String,
+    timestamp: String,
+}
+
+/// Percorso del journal append-only associato a un file TM
+fn get_journal_path(tm_file: &PathBuf) -> PathBuf {
+    tm_file.with_extension("journal.jsonl")
+}
+
+/// Ricava la coppia di lingue da un id TM ("tm_en_it", "tm_zh_hans_en").
+/// I codici lingua possono contenere `_` e tutte le divisioni puntano allo stesso file:
+/// vale la coppia registrata nel file TM, altrimenti l'unica in cui entrambe le parti
+/// sono codici plausibili.
+fn parse_tm_id(tm_id: &str, data_dir: &Path) -> Result<(String, String), String> {
+    let candidates = tm_id_candidates(tm_id);
+    if let Some(stored) = candidates.first().and_then(|(s, t)| stored_tm_languages(&data_dir.join(get_tm_filename(s, t)))) {
+        if let Some(pair) = candidates.iter().find(|pair| **pair == stored) {
+            return Ok(pair.clone());
+        }
+    }
+    
+    let plausible: Vec<&(String, String)> = candidates.iter()
+        .filter(|(s, t)| is_language_code(s) && is_language_code(t))
+        .collect();
+    match plausible.as_slice() {
+        [pair] => Ok((*pair).clone()),
+        [] => Err(format!("ID TM non valido: {}", tm_id)),
+        _ => Err(format!("ID TM ambiguo: {} (crea prima la TM con load/save)", tm_id)),
+    }
+}
+
+/// Coppia di lingue scritta in un file TM esistente
+fn stored_tm_languages(file_path: &Path) -> Option<(String, String)> {
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(file_path).ok()?).ok()?;
+    let field = |camel: &str, snake: &str| json.get(camel).or_else(|| json.get(snake))?.as_str().map(|v| v.to_lowercase());
+    Some((field("sourceLanguage", "source_language")?, field("targetLanguage", "target_language")?))
+}
+
+/// Tutte le divisioni "tm_<sorgente>_<target>" di un id TM
+fn tm_id_candidates(tm_id: &str) -> Vec<(String, String)> {
+    let lower = tm_id.to_lowercase();
+    let Some(rest) = lower.strip_prefix("tm_") else {
+        return Vec::new();
+    };
+    rest.match_indices('_')
+        .map(|(i, _)| (rest[..i].to_string(), rest[i + 1..].to_string()))
+        .filter(|(s, t)| !s.is_empty() && !t.is_empty())
+        .collect()
+}
+
+/// Codice lingua plausibile: "en", "fil", "zh_hans", "pt_br", "es_419"
+fn is_language_code(code: &str) -> bool {
+    let mut parts = code.split('_');
+    let base = parts.next().unwrap_or_default();
+    let base_ok = (2..=3).contains(&base.len()) && base.chars().all(|c| c.is_ascii_lowercase());
+    let subtags: Vec<&str> = parts.collect();
+    base_ok && subtags.len() <= 1 && subtags.iter().all(|t| (2..=4).contains(&t.len()) && t.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Crea una TM vuota per una coppia di lingue
+fn new_translation_memory(source_lang: &str, target_lang: &str, now: &str) -> TranslationMemory {
+    TranslationMemory {
+        id: format!("tm_{}_{}", source_lang, target_lang),
+        name: format!("{} → {}", source_lang.to_uppercase(), target_lang.to_uppercase()),
+        source_language: source_lang.to_string(),
+        target_language: target_lang.to_string(),
+        units: Vec::new(),
+        stats: TMStats {
+            total_units: 0,
+            verified_units: 0,
+            total_usage_count: 0,
+            average_confidence: 0.0,
+            by_provider: HashMap::new(),
+            by_context: HashMap::new(),
+        },
+        created_at: now.to_string(),
+        updated_at: now.to_string(),
+        loaded_at: None,
+    }
+}
+
+/// Chiave di deduplica delle unità: testo normalizzato, senza distinzione di maiuscole
+fn tm_source_key(text: &str) -> String {
+    normalize_source_text(text).to_lowercase()
+}
+
+/// Inserisce o aggiorna un'unità nella TM. Ritorna true se è stata inserita.
+fn upsert_unit_in_memory(memory: &mut TranslationMemory, source_text: &str, target_text: &str, now: &str) -> bool {
+    let source_key = tm_source_key(source_text);
+    if let Some(existing) = memory.units.iter_mut()
+        .find(|u| tm_source_key(&u.source_text) == source_key)
+    {
+        existing.target_text = target_text.to_string();
+        existing.updated_at = now.to_string();
+        existing.usage_count += 1;
+        false
+    } else {
+        memory.units.push(TranslationUnit {
+            id: format!("tu_{}", uuid::Uuid::new_v4()),
+            string_id: Some(string_id(source_text)),
+            source_text: source_text.to_string(),
+            target_text: target_text.to_string(),
+            source_language: memory.source_language.clone(),
+            target_language: memory.target_language.clone(),
+            context: None,
+            game_id: None,
+            provider: "manual".to_string(),
+            confidence: 1.0,
+            verified: false,
+            usage_count: 1,
+            created_at: now.to_string(),
+            updated_at: now.to_string(),
+            metadata: None,
+        });
+        true
+    }
+}
+
+/// Applica alla TM le righe del journal non ancora consolidate
+fn replay_journal(memory: &mut TranslationMemory, journal_path: &PathBuf) -> usize {
+    let content = match fs::read_to_string(journal_path) {
+        Ok(c) => c,
+        Err(_) => return 0,
+    };
+    
+    let mut applied = 0;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        // Una riga troncata (crash durante l'append) viene ignorata
+        match serde_json::from_str::<TmJournalEntry>(line) {
+            Ok(entry) => {
+                upsert_unit_in_memory(memory, &entry.source_text, &entry.target_text, &entry.timestamp);
+                applied += 1;
+            }
+            Err(e) => warn!("⚠️ Riga journal TM non valida ignorata: {}", e),
+        }
+    }
+    
+    if applied > 0 {
+        memory.stats.total_units = memory.units.len() as u32;
+        memory.stats.verified_units = memory.units.iter().filter(|u| u.verified).count() as u32;
+        memory.stats.total_usage_count = memory.units.iter().map(|u| u.usage_count).sum();
+    }
+    applied
+}
+
+/// Porta nella TM da salvare gli upsert del journal che non contiene ancora.
+/// La TM arriva dal frontend, caricata magari prima di altri upsert: un'unità già presente
+/// con testo diverso viene aggiornata solo se l'upsert è più recente della sua ultima modifica,
+/// un'unità assente solo se l'upsert è successivo al caricamento (altrimenti è stata eliminata).
+fn merge_pending_journal(memory: &mut TranslationMemory, journal_path: &Path) -> usize {
+    let Ok(content) = fs::read_to_string(journal_path) else {
+        return 0;
+    };
+    let parse_time = |value: &str| chrono::DateTime::parse_from_rfc3339(value).ok();
+    let loaded_at = memory.loaded_at.as_deref().and_then(parse_time);
+    
+    let mut merged = 0;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let Ok(entry) = serde_json::from_str::<TmJournalEntry>(line) else {
+            continue;
+        };
+        let source_key = tm_source_key(&entry.source_text);
+        let pending = match memory.units.iter().find(|u| tm_source_key(&u.source_text) == source_key) {
+            // Senza istante di caricamento (TM creata dal frontend) l'upsert è sempre nuovo
+            None => match loaded_at {
+                Some(loaded) => parse_time(&entry.timestamp).is_some_and(|time| time > loaded),
+                None => true,
+            },
+            Some(unit) if unit.target_text == entry.target_text => false,
+            Some(unit) => match (parse_time(&entry.timestamp), parse_time(&unit.updated_at)) {
+                (Some(entry_time), Some(unit_time)) => entry_time > unit_time,
+                _ => false,
+            },
+        };
+        if pending {
+            upsert_unit_in_memory(memory, &entry.source_text, &entry.target_text, &entry.timestamp);
+            merged += 1;
+        }
+    }
+    
+    if merged > 0 {
+        memory.stats.total_units = memory.units.len() as u32;
+        memory.stats.verified_units = memory.units.iter().filter(|u| u.verified).count() as u32;
+        memory.stats.total_usage_count = memory.units.iter().map(|u| u.usage_count).sum();
+    }
+    merged
+}
+
+/// Completa l'id canonico delle unità create prima della sua introduzione (o dal frontend)
+fn fill_string_ids(memory: &mut TranslationMemory) {
+    for unit in memory.units.iter_mut().filter(|u| u.string_id.is_none()) {
+        unit.string_id = Some(string_id(&unit.source_text));
+    }
+}
+
 /// 📥 Carica una Translation Memory
 #[tauri::command]
 pub fn load_translation_memory(
@@ -109,17 +326,31 @@ pub fn load_translation_memory(
     let data_dir = get_data_dir()?;
     let filename = get_tm_filename(&source_lang, &target_lang);
     let file_path = data_dir.join(&filename);
+    let journal_path = get_journal_path(&file_path);
+    // Prima di leggere: un upsert concorrente al caricamento risulta successivo e non va perso
+    let loaded_at = chrono::Utc::now().to_rfc3339();
     
-    if !file_path.exists() {
+    let mut memory: TranslationMemory = if file_path.exists() {
+        let content = fs::read_to_string(&file_path)
+            .map_err(|e| format!("Errore lettura TM: {}", e))?;
+        
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Errore parsing TM: {}", e))?
+    } else if journal_path.exists() {
+        // TM creata solo tramite upsert incrementali
+        let now = chrono::Utc::now().to_rfc3339();
+        new_translation_memory(&source_lang.to_lowercase(), &target_lang.to_lowercase(), &now)
+    } else {
         info!("📭 TM non trovata: {}", filename);
         return Ok(None);
-    }
-    
-    let content = fs::read_to_string(&file_path)
-        .map_err(|e| format!("Errore lettura TM: {}", e))?;
+    };
     
-    let memory: TranslationMemory = serde_json::from_str(&content)
-        .map_err(|e| format!("Errore parsing TM: {}", e))?;
+    let replayed = replay_journal(&mut memory, &journal_path);
+    if replayed > 0 {
+        info!("📜 Applicati {} upsert dal journal", replayed);
+    }
+    fill_string_ids(&mut memory);
+    memory.loaded_at = Some(loaded_at);
     
     info!("✅ TM caricata: {} unità", memory.units.len());
     Ok(Some(memory))
@@ -127,19 +358,39 @@ pub fn load_translation_memory(
 
 /// 💾 Salva una Translation Memory
 #[tauri::command]
-pub fn save_translation_memory(memory: TranslationMemory) -> Result<(), String> {
+pub fn save_translation_memory(mut memory: TranslationMemory) -> Result<(), String> {
     info!("💾 Salvataggio TM: {} ({} unità)", memory.name, memory.units.len());
     
     let data_dir = get_data_dir()?;
     let filename = get_tm_filename(&memory.source_language, &memory.target_language);
     let file_path = data_dir.join(&filename);
+    let journal_path = get_journal_path(&file_path);
+    
+    // Lock dell'indice: nessun upsert può finire nel journal tra il merge e la sua rimozione
+    let mut index = TM_SOURCE_INDEX.lock();
+    
+    // Il salvataggio completo consolida il journal degli upsert, senza perdere quelli successivi al caricamento
+    let merged = merge_pending_journal(&mut memory, &journal_path);
+    if merged > 0 {
+        info!("📜 Consolidati {} upsert dal journal", merged);
+    }
+    fill_string_ids(&mut memory);
+    memory.loaded_at = None;
     
     let content = serde_json::to_string_pretty(&memory)
         .map_err(|e| format!("Errore serializzazione TM: {}", e))?;
     
-    fs::write(&file_path, content)
+    // Scrittura atomica: il journal viene rimosso solo con il file completo già al suo posto
+    write_atomic(&file_path, content.as_bytes())
         .map_err(|e| format!("Errore scrittura TM: {}", e))?;
     
+    if journal_path.exists() {
+        fs::remove_file(&journal_path)
+            .map_err(|e| format!("Errore rimozione journal TM: {}", e))?;
+    }
+    index.remove(&format!("tm_{}_{}", memory.source_language, memory.target_language).to_lowercase());
+    index.remove(&memory.id.to_lowercase());
+    
     info!("✅ TM salvata: {}", file_path.display());
     Ok(())
 }
@@ -197,6 +448,13 @@ pub fn delete_translation_memory(source_lang: String, target_lang: String) -> Re
     let filename = get_tm_filename(&source_lang, &target_lang);
     let file_path = data_dir.join(&filename);
     
+    let journal_path = get_journal_path(&file_path);
+    if journal_path.exists() {
+        fs::remove_file(&journal_path)
+            .map_err(|e| format!("Errore eliminazione journal TM: {}", e))?;
+    }
+    TM_SOURCE_INDEX.lock().remove(&format!("tm_{}_{}", source_lang, target_lang).to_lowercase());
+    
     if file_path.exists() {
         fs::remove_file(&file_path)
             .map_err(|e| format!("Errore eliminazione TM: {}", e))?;
@@ -208,40 +466,110 @@ pub fn delete_translation_memory(source_lang: String, target_lang: String) -> Re
     Ok(())
 }
 
+/// ⚙️ Opzioni di esportazione TMX
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TmxExportOptions {
+    /// Versione TMX ("1.4" o "1.4b", default "1.4")
+    pub tmx_version: Option<String>,
+    /// Nome dello strumento nell'header (default "GameStringer")
+    pub tool_name: Option<String>,
+    /// Versione dello strumento nell'header (default versione app)
+    pub tool_version: Option<String>,
+    /// Emette creationdate/changedate per ogni unità
+    pub include_dates: bool,
+    /// Emette <note> dal contesto dell'unità
+    pub include_notes: bool,
+    /// Emette <prop type="x-game-id"> per le unità con un gioco associato
+    pub include_game_id_props: bool,
+}
+
+/// Versioni TMX supportate in esportazione
+const SUPPORTED_TMX_VERSIONS: &[&str] = &["1.4", "1.4b"];
+
+/// Converte una data RFC 3339 nel formato TMX (YYYYMMDDThhmmssZ)
+fn to_tmx_date(rfc3339: &str) -> Option<String> {
+    chrono::DateTime::parse_from_rfc3339(rfc3339)
+        .ok()
+        .map(|d| d.with_timezone(&chrono::Utc).format("%Y%m%dT%H%M%SZ").to_string())
+}
+
+/// Converte una data TMX (YYYYMMDDThhmmssZ) in RFC 3339
+fn from_tmx_date(tmx: &str) -> Option<String> {
+    chrono::NaiveDateTime::parse_from_str(tmx, "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|d| d.and_utc().to_rfc3339())
+}
+
 /// 📤 Esporta una Translation Memory in formato TMX (standard industria)
 #[tauri::command]
 pub fn export_translation_memory_tmx(
     source_lang: String,
     target_lang: String,
-    output_path: String
+    output_path: String,
+    options: Option<TmxExportOptions>
 ) -> Result<String, String> {
     info!("📤 Esportazione TM in TMX: {} → {}", source_lang, target_lang);
     
+    let options = options.unwrap_or_default();
+    let version = options.tmx_version.clone().unwrap_or_else(|| "1.4".to_string());
+    if !SUPPORTED_TMX_VERSIONS.contains(&version.as_str()) {
+        return Err(format!("Versione TMX non supportata: {} (supportate: {})",
+            version, SUPPORTED_TMX_VERSIONS.join(", ")));
+    }
+    let tool_name = options.tool_name.clone().unwrap_or_else(|| "GameStringer".to_string());
+    let tool_version = options.tool_version.clone()
+        .unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string());
+    
     let memory = load_translation_memory(source_lang.clone(), target_lang.clone())?
         .ok_or("TM non trovata")?;
     
     // Genera TMX (Translation Memory eXchange format)
-    let mut tmx = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>
+    let mut tmx = format!(r#"<?xml version="1.0" encoding="UTF-8"?>
 <!DOCTYPE tmx SYSTEM "tmx14.dtd">
-<tmx version="1.4">
+<tmx version="{}">
   <header
-    creationtool="GameStringer"
-    creationtoolversion="1.0"
+    creationtool="{}"
+    creationtoolversion="{}"
+    creationdate="{}"
     datatype="plaintext"
     segtype="sentence"
     adminlang="en"
-    srclang=""#);
-    
-    tmx.push_str(&source_lang);
-    tmx.push_str(r#""
+    srclang="{}"
     o-tmf="GameStringer TM">
   </header>
   <body>
-"#);
+"#,
+        escape_xml(&version),
+        escape_xml(&tool_name),
+        escape_xml(&tool_version),
+        chrono::Utc::now().format("%Y%m%dT%H%M%SZ"),
+        escape_xml(&source_lang)
+    );
     
     for unit in &memory.units {
-        tmx.push_str(&format!(r#"    <tu tuid="{}">
-      <tuv xml:lang="{}">
+        let mut attrs = format!(r#"tuid="{}""#, escape_xml(&unit.id));
+        if options.include_dates {
+            if let Some(created) = to_tmx_date(&unit.created_at) {
+                attrs.push_str(&format!(r#" creationdate="{}""#, created));
+            }
+            if let Some(changed) = to_tmx_date(&unit.updated_at) {
+                attrs.push_str(&format!(r#" changedate="{}""#, changed));
+            }
+        }
+        
+        tmx.push_str(&format!("    <tu {}>\n", attrs));
+        if options.include_notes {
+            if let Some(ref context) = unit.context {
+                tmx.push_str(&format!("      <note>{}</note>\n", escape_xml(context)));
+            }
+        }
+        if options.include_game_id_props {
+            if let Some(ref game_id) = unit.game_id {
+                tmx.push_str(&format!("      <prop type=\"x-game-id\">{}</prop>\n", escape_xml(game_id)));
+            }
+        }
+        tmx.push_str(&format!(r#"      <tuv xml:lang="{}">
         <seg>{}</seg>
       </tuv>
       <tuv xml:lang="{}">
@@ -249,10 +577,9 @@ pub fn export_translation_memory_tmx(
       </tuv>
     </tu>
 "#,
-            unit.id,
-            source_lang,
+            escape_xml(&source_lang),
             escape_xml(&unit.source_text),
-            target_lang,
+            escape_xml(&target_lang),
             escape_xml(&unit.target_text)
         ));
     }
@@ -291,38 +618,70 @@ pub fn import_translation_memory_tmx(
     let mut units = Vec::new();
     let now = chrono::Utc::now().to_rfc3339();
     
-    // Regex per estrarre translation units
-    let tu_regex = regex::Regex::new(r#"<tu[^>]*tuid="([^"]*)"[^>]*>[\s\S]*?<tuv[^>]*xml:lang="([^"]*)"[^>]*>\s*<seg>([\s\S]*?)</seg>[\s\S]*?<tuv[^>]*xml:lang="([^"]*)"[^>]*>\s*<seg>([\s\S]*?)</seg>[\s\S]*?</tu>"#)
+    // Regex per estrarre translation units e i relativi campi
+    let tu_regex = regex::Regex::new(r#"<tu\b([^>]*)>([\s\S]*?)</tu>"#)
+        .map_err(|e| format!("Errore regex: {}", e))?;
+    let attr_regex = regex::Regex::new(r#"([\w:-]+)="([^"]*)""#)
+        .map_err(|e| format!("Errore regex: {}", e))?;
+    let tuv_regex = regex::Regex::new(r#"<tuv\b[^>]*(?:xml:)?lang="([^"]*)"[^>]*>\s*<seg>([\s\S]*?)</seg>"#)
+        .map_err(|e| format!("Errore regex: {}", e))?;
+    let note_regex = regex::Regex::new(r#"<note>([\s\S]*?)</note>"#)
+        .map_err(|e| format!("Errore regex: {}", e))?;
+    let game_prop_regex = regex::Regex::new(r#"<prop\s+type="x-game-id">([\s\S]*?)</prop>"#)
         .map_err(|e| format!("Errore regex: {}", e))?;
     
     for cap in tu_regex.captures_iter(&content) {
-        let id = cap.get(1).map_or("", |m| m.as_str());
-        let lang1 = cap.get(2).map_or("", |m| m.as_str());
-        let text1 = cap.get(3).map_or("", |m| m.as_str());
-        let _lang2 = cap.get(4).map_or("", |m| m.as_str());
-        let text2 = cap.get(5).map_or("", |m| m.as_str());
+        let attrs = cap.get(1).map_or("", |m| m.as_str());
+        let body = cap.get(2).map_or("", |m| m.as_str());
+        
+        let attr = |name: &str| attr_regex.captures_iter(attrs)
+            .find(|a| &a[1] == name)
+            .map(|a| a[2].to_string());
+        
+        let tuvs: Vec<(String, String)> = tuv_regex.captures_iter(body)
+            .map(|t| (t[1].to_string(), t[2].to_string()))
+            .collect();
+        if tuvs.len() < 2 {
+            continue;
+        }
         
         // Determina source e target
-        let (source_text, target_text) = if lang1.to_lowercase() == source_lang.to_lowercase() {
-            (unescape_xml(text1), unescape_xml(text2))
-        } else {
-            (unescape_xml(text2), unescape_xml(text1))
+        let (source_text, target_text) = match tuvs.iter().position(|(l, _)| l.to_lowercase() == source_lang.to_lowercase()) {
+            Some(i) => {
+                let target = tuvs.iter().enumerate()
+                    .find(|(j, _)| *j != i)
+                    .map(|(_, (_, t))| t.as_str())
+                    .unwrap_or("");
+                (unescape_xml(&tuvs[i].1), unescape_xml(target))
+            }
+            None => (unescape_xml(&tuvs[1].1), unescape_xml(&tuvs[0].1)),
         };
         
+        let id = attr("tuid").unwrap_or_default();
+        let created_at = attr("creationdate")
+            .and_then(|d| from_tmx_date(&d))
+            .unwrap_or_else(|| now.clone());
+        let updated_at = attr("changedate")
+            .and_then(|d| from_tmx_date(&d))
+            .unwrap_or_else(|| created_at.clone());
+        let context = note_regex.captures(body).map(|n| unescape_xml(&n[1]));
+        let game_id = game_prop_regex.captures(body).map(|p| unescape_xml(&p[1]));
+        
         units.push(TranslationUnit {
-            id: if id.is_empty() { format!("imported_{}", units.len()) } else { id.to_string() },
+            id: if id.is_empty() { format!("imported_{}", units.len()) } else { unescape_xml(&id) },
+            string_id: Some(string_id(&source_text)),
             source_text,
             target_text,
             source_language: source_lang.clone(),
             target_language: target_lang.clone(),
-            context: None,
-            game_id: None,
+            context,
+            game_id,
             provider: "tmx_import".to_string(),
             confidence: 0.9,
             verified: true, // TMX sono tipicamente verificate
             usage_count: 0,
-            created_at: now.clone(),
-            updated_at: now.clone(),
+            created_at,
+            updated_at,
             metadata: None,
         });
     }
@@ -347,15 +706,16 @@ pub fn import_translation_memory_tmx(
             },
             created_at: now.clone(),
             updated_at: now.clone(),
+            loaded_at: None,
         });
     
     // Aggiungi unità importate (evita duplicati)
     let existing_sources: std::collections::HashSet<_> = memory.units.iter()
-        .map(|u| u.source_text.to_lowercase())
+        .map(|u| tm_source_key(&u.source_text))
         .collect();
     
     for unit in units {
-        if !existing_sources.contains(&unit.source_text.to_lowercase()) {
+        if !existing_sources.contains(&tm_source_key(&unit.source_text)) {
             memory.units.push(unit);
         }
     }
@@ -371,6 +731,195 @@ pub fn import_translation_memory_tmx(
     Ok(count)
 }
 
+/// ⚠️ Problema rilevato su una singola translation unit
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TmxUnitIssue {
+    /// Indice (0-based) della <tu> nel file
+    pub index: usize,
+    pub tuid: Option<String>,
+    pub message: String,
+}
+
+/// 🩺 Report di validazione di un file TMX
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TmxValidationReport {
+    /// true se il file non presenta errori strutturali né unità malformate
+    pub valid: bool,
+    pub total_units: u32,
+    pub valid_units: u32,
+    /// srclang dichiarato nell'header
+    pub header_source_language: Option<String>,
+    pub detected_source_language: Option<String>,
+    pub detected_target_language: Option<String>,
+    pub missing_source: Vec<TmxUnitIssue>,
+    pub missing_target: Vec<TmxUnitIssue>,
+    pub language_issues: Vec<TmxUnitIssue>,
+    /// Errori strutturali (header/body mancanti, ecc.)
+    pub errors: Vec<String>,
+}
+
+/// Confronta due codici lingua sul subtag primario ("en-US" ~ "en")
+fn lang_matches(a: &str, b: &str) -> bool {
+    let primary = |s: &str| s.split(['-', '_']).next().unwrap_or("").to_lowercase();
+    primary(a) == primary(b)
+}
+
+/// Verifica che il codice lingua abbia una forma BCP 47 plausibile
+fn is_valid_lang_code(code: &str) -> bool {
+    let mut parts = code.split(['-', '_']);
+    let primary_ok = parts.next()
+        .map_or(false, |p| (2..=3).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphabetic()));
+    primary_ok && parts.all(|p| (1..=8).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// 🩺 Valida un file TMX senza modificare alcuna TM
+#[tauri::command]
+pub fn validate_tmx(
+    path: String,
+    source_lang: Option<String>,
+    target_lang: Option<String>
+) -> Result<TmxValidationReport, String> {
+    info!("🩺 Validazione TMX: {}", path);
+    
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Errore lettura TMX: {}", e))?;
+    
+    let mut errors = Vec::new();
+    if !content.contains("<tmx") {
+        errors.push("Elemento <tmx> mancante".to_string());
+    }
+    if !content.contains("<body") {
+        errors.push("Elemento <body> mancante".to_string());
+    }
+    
+    let header_regex = regex::Regex::new(r#"<header\b[^>]*\bsrclang="([^"]*)""#)
+        .map_err(|e| format!("Errore regex: {}", e))?;
+    let header_source_language = header_regex.captures(&content)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string());
+    if header_source_language.is_none() {
+        errors.push("Header senza attributo srclang".to_string());
+    }
+    
+    let tu_regex = regex::Regex::new(r#"<tu\b([^>]*)>([\s\S]*?)</tu>"#)
+        .map_err(|e| format!("Errore regex: {}", e))?;
+    let tuid_regex = regex::Regex::new(r#"\btuid="([^"]*)""#)
+        .map_err(|e| format!("Errore regex: {}", e))?;
+    let tuv_regex = regex::Regex::new(r#"<tuv\b[^>]*(?:xml:)?lang="([^"]*)"[^>]*>([\s\S]*?)</tuv>"#)
+        .map_err(|e| format!("Errore regex: {}", e))?;
+    let seg_regex = regex::Regex::new(r#"<seg>([\s\S]*?)</seg>"#)
+        .map_err(|e| format!("Errore regex: {}", e))?;
+    
+    // Lingue attese: argomenti espliciti, altrimenti l'header
+    let expected_source = source_lang.or_else(|| header_source_language.clone());
+    let expected_target = target_lang;
+    
+    // Prima passata: raccoglie le <tuv> di ogni unità
+    let mut parsed_units = Vec::new();
+    let mut lang_counts: std::collections::HashMap<String, u32> = std::collections::HashMap::new();
+    for tu in tu_regex.captures_iter(&content) {
+        let attrs = tu.get(1).map_or("", |m| m.as_str());
+        let body = tu.get(2).map_or("", |m| m.as_str());
+        let tuid = tuid_regex.captures(attrs)
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().to_string());
+        
+        let tuvs: Vec<(String, bool)> = tuv_regex.captures_iter(body)
+            .map(|tuv| {
+                let lang = tuv.get(1).map_or("", |m| m.as_str()).to_string();
+                let inner = tuv.get(2).map_or("", |m| m.as_str());
+                let has_text = seg_regex.captures(inner)
+                    .and_then(|c| c.get(1))
+                    .map_or(false, |m| !m.as_str().trim().is_empty());
+                (lang, has_text)
+            })
+            .collect();
+        
+        for (lang, _) in &tuvs {
+            *lang_counts.entry(lang.to_lowercase()).or_insert(0) += 1;
+        }
+        parsed_units.push((tuid, tuvs));
+    }
+    
+    // Rileva le lingue: source dall'header (o la più frequente), target la più frequente diversa
+    let mut langs_by_freq: Vec<(String, u32)> = lang_counts.into_iter().collect();
+    langs_by_freq.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let detected_source_language = expected_source.clone()
+        .or_else(|| langs_by_freq.first().map(|(l, _)| l.clone()));
+    let detected_target_language = langs_by_freq.iter()
+        .map(|(l, _)| l)
+        .find(|l| detected_source_language.as_deref().map_or(true, |src| !lang_matches(l, src)))
+        .cloned();
+    
+    let source = detected_source_language.clone().unwrap_or_default();
+    let target = expected_target.clone()
+        .or_else(|| detected_target_language.clone())
+        .unwrap_or_default();
+    
+    let mut missing_source = Vec::new();
+    let mut missing_target = Vec::new();
+    let mut language_issues = Vec::new();
+    let mut valid_units = 0u32;
+    
+    for (index, (tuid, tuvs)) in parsed_units.iter().enumerate() {
+        let issue = |message: String| TmxUnitIssue { index, tuid: tuid.clone(), message };
+        let mut unit_ok = true;
+        
+        for (lang, _) in tuvs {
+            if !is_valid_lang_code(lang) {
+                language_issues.push(issue(format!("Codice lingua non valido: '{}'", lang)));
+                unit_ok = false;
+            } else if !source.is_empty() && !target.is_empty()
+                && !lang_matches(lang, &source) && !lang_matches(lang, &target)
+            {
+                language_issues.push(issue(format!(
+                    "Lingua '{}' non corrisponde alla coppia attesa {} → {}", lang, source, target
+                )));
+                unit_ok = false;
+            }
+        }
+        
+        let has_source = tuvs.iter().any(|(l, has_text)| *has_text && lang_matches(l, &source));
+        let has_target = tuvs.iter().any(|(l, has_text)| *has_text && !target.is_empty() && lang_matches(l, &target));
+        
+        if !has_source {
+            missing_source.push(issue(format!("<tuv> sorgente ({}) mancante o vuota", source)));
+            unit_ok = false;
+        }
+        if !has_target {
+            missing_target.push(issue(format!("<tuv> destinazione ({}) mancante o vuota", target)));
+            unit_ok = false;
+        }
+        
+        if unit_ok {
+            valid_units += 1;
+        }
+    }
+    
+    let total_units = parsed_units.len() as u32;
+    if total_units == 0 {
+        errors.push("Nessuna translation unit <tu> trovata".to_string());
+    }
+    
+    let report = TmxValidationReport {
+        valid: errors.is_empty() && valid_units == total_units,
+        total_units,
+        valid_units,
+        header_source_language,
+        detected_source_language,
+        detected_target_language,
+        missing_source,
+        missing_target,
+        language_issues,
+        errors,
+    };
+    
+    info!("✅ TMX validato: {}/{} unità valide", report.valid_units, report.total_units);
+    Ok(report)
+}
+
 /// Unescape caratteri XML
 fn unescape_xml(s: &str) -> String {
     s.replace("&amp;", "&")
@@ -458,8 +1007,131 @@ pub fn search_translation_memory(
     Ok(matches)
 }
 
+/// 🎯 Match fuzzy con percentuale stile CAT tool
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TmFuzzyMatch {
+    pub unit_id: String,
+    pub source_text: String,
+    pub target_text: String,
+    /// Somiglianza 0-100 (100 = match esatto)
+    pub score: u8,
+}
+
+/// 🎯 Lookup fuzzy nella TM con punteggio percentuale basato sui token
+#[tauri::command]
+pub fn tm_fuzzy_lookup(
+    source_text: String,
+    source_lang: String,
+    target_lang: String,
+    min_score: Option<u8>,
+    max_results: Option<usize>
+) -> Result<Vec<TmFuzzyMatch>, String> {
+    let memory = match load_translation_memory(source_lang, target_lang)? {
+        Some(m) => m,
+        None => return Ok(Vec::new()),
+    };
+    
+    let matches = fuzzy_lookup_units(
+        &memory.units,
+        &source_text,
+        min_score.unwrap_or(70),
+        max_results.unwrap_or(10),
+    );
+    
+    info!("🎯 Fuzzy lookup: {} match", matches.len());
+    Ok(matches)
+}
+
+/// Calcola i match fuzzy su un insieme di unità, ordinati per punteggio
+pub(crate) fn fuzzy_lookup_units(
+    units: &[TranslationUnit],
+    source_text: &str,
+    min_score: u8,
+    max_results: usize
+) -> Vec<TmFuzzyMatch> {
+    let query_tokens = tokenize(source_text);
+    
+    let mut matches: Vec<TmFuzzyMatch> = units.iter()
+        .filter_map(|unit| {
+            let score = token_similarity_score(&query_tokens, &tokenize(&unit.source_text));
+            if score >= min_score {
+                Some(TmFuzzyMatch {
+                    unit_id: unit.id.clone(),
+                    source_text: unit.source_text.clone(),
+                    target_text: unit.target_text.clone(),
+                    score,
+                })
+            } else {
+                None
+            }
+        })
+        .collect();
+    
+    matches.sort_by(|a, b| b.score.cmp(&a.score));
+    matches.truncate(max_results);
+    matches
+}
+
+/// Suddivide il testo in token (parole e punteggiatura) in minuscolo,
+/// dopo la normalizzazione tipografica di `lookup_text` (query e unità TM allo stesso modo)
+fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    
+    for c in lookup_text(text).chars() {
+        if c.is_alphanumeric() || c == '\'' {
+            current.extend(c.to_lowercase());
+        } else {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            if !c.is_whitespace() {
+                tokens.push(c.to_string());
+            }
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    
+    tokens
+}
+
+/// Punteggio 0-100 dalla distanza di edit calcolata sui token
+fn token_similarity_score(a: &[String], b: &[String]) -> u8 {
+    if a.is_empty() && b.is_empty() {
+        return 100;
+    }
+    let max_len = a.len().max(b.len());
+    let distance = token_edit_distance(a, b);
+    ((1.0 - distance as f64 / max_len as f64) * 100.0).round() as u8
+}
+
+/// Distanza di Levenshtein su sequenze di token
+fn token_edit_distance(a: &[String], b: &[String]) -> usize {
+    if a.is_empty() { return b.len(); }
+    if b.is_empty() { return a.len(); }
+    
+    let mut prev_row: Vec<usize> = (0..=b.len()).collect();
+    let mut curr_row = vec![0; b.len() + 1];
+    
+    for i in 1..=a.len() {
+        curr_row[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr_row[j] = (prev_row[j] + 1)
+                .min(curr_row[j - 1] + 1)
+                .min(prev_row[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev_row, &mut curr_row);
+    }
+    
+    prev_row[b.len()]
+}
+
 /// Calcola similarità tra due stringhe (algoritmo Levenshtein normalizzato)
-fn calculate_similarity(s1: &str, s2: &str) -> f64 {
+pub(crate) fn calculate_similarity(s1: &str, s2: &str) -> f64 {
     if s1 == s2 {
         return 1.0;
     }
@@ -567,12 +1239,13 @@ pub fn add_translation_to_memory(
             },
             created_at: now.clone(),
             updated_at: now.clone(),
+            loaded_at: None,
         });
     
     // Cerca se esiste già
-    let source_lower = source_text.to_lowercase();
+    let source_key = tm_source_key(&source_text);
     if let Some(existing) = memory.units.iter_mut()
-        .find(|u| u.source_text.to_lowercase() == source_lower) 
+        .find(|u| tm_source_key(&u.source_text) == source_key) 
     {
         // Aggiorna esistente
         existing.target_text = target_text;
@@ -583,6 +1256,7 @@ pub fn add_translation_to_memory(
         // Aggiungi nuova
         let unit = TranslationUnit {
             id: format!("tu_{}", uuid::Uuid::new_v4()),
+            string_id: Some(string_id(&source_text)),
             source_text,
             target_text,
             source_language: source_lang,
@@ -642,10 +1316,11 @@ pub fn add_translations_batch(
             },
             created_at: now.clone(),
             updated_at: now.clone(),
+            loaded_at: None,
         });
     
     let existing_sources: std::collections::HashSet<_> = memory.units.iter()
-        .map(|u| u.source_text.to_lowercase())
+        .map(|u| tm_source_key(&u.source_text))
         .collect();
     
     let mut added = 0u32;
@@ -655,9 +1330,10 @@ pub fn add_translations_batch(
             continue;
         }
         
-        if !existing_sources.contains(&source.to_lowercase()) {
+        if !existing_sources.contains(&tm_source_key(&source)) {
             memory.units.push(TranslationUnit {
                 id: format!("tu_{}", uuid::Uuid::new_v4()),
+                string_id: Some(string_id(&source)),
                 source_text: source,
                 target_text: target,
                 source_language: source_lang.clone(),
@@ -685,3 +1361,153 @@ pub fn add_translations_batch(
     info!("✅ Aggiunte {} nuove traduzioni", added);
     Ok(added)
 }
+
+/// Esito di un upsert incrementale
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TmUpsertResult {
+    Inserted,
+    Updated,
+}
+
+/// ⚡ Inserisce o aggiorna una singola unità senza riscrivere l'intera TM.
+///
+/// L'operazione viene accodata a un journal append-only che viene applicato al
+/// caricamento e consolidato dal prossimo `save_translation_memory`.
+#[tauri::command]
+pub fn tm_upsert_unit(
+    tm_id: String,
+    source: String,
+    target: String
+) -> Result<TmUpsertResult, String> {
+    if source.trim().is_empty() {
+        return Err("Testo sorgente vuoto".to_string());
+    }
+    
+    let data_dir = get_data_dir()?;
+    let (source_lang, target_lang) = parse_tm_id(&tm_id, &data_dir)?;
+    let index_key = format!("tm_{}_{}", source_lang, target_lang);
+    let file_path = data_dir.join(get_tm_filename(&source_lang, &target_lang));
+    let journal_path = get_journal_path(&file_path);
+    
+    let mut index = TM_SOURCE_INDEX.lock();
+    if !index.contains_key(&index_key) {
+        let sources = load_translation_memory(source_lang.clone(), target_lang.clone())?
+            .map(|m| m.units.iter().map(|u| tm_source_key(&u.source_text)).collect())
+            .unwrap_or_default();
+        index.insert(index_key.clone(), sources);
+    }
+    
+    let entry = TmJournalEntry {
+        source_text: source.clone(),
+        target_text: target,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+    let line = serde_json::to_string(&entry)
+        .map_err(|e| format!("Errore serializzazione journal: {}", e))?;
+    
+    let mut journal = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&journal_path)
+        .map_err(|e| format!("Errore apertura journal TM: {}", e))?;
+    writeln!(journal, "{}", line)
+        .map_err(|e| format!("Errore scrittura journal TM: {}", e))?;
+    
+    let inserted = index.get_mut(&index_key)
+        .map(|sources| sources.insert(tm_source_key(&source)))
+        .unwrap_or(true);
+    
+    Ok(if inserted { TmUpsertResult::Inserted } else { TmUpsertResult::Updated })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tm_id_supports_underscored_language_codes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(parse_tm_id("tm_en_it", dir.path()).unwrap(), ("en".to_string(), "it".to_string()));
+        assert_eq!(parse_tm_id("tm_zh_hans_en", dir.path()).unwrap(), ("zh_hans".to_string(), "en".to_string()));
+        assert_eq!(parse_tm_id("tm_pt_br_en_us", dir.path()).unwrap(), ("pt_br".to_string(), "en_us".to_string()));
+        assert!(parse_tm_id("tm_en", dir.path()).is_err());
+        assert!(parse_tm_id("en_it", dir.path()).is_err());
+
+        // "pt" + "br_en" o "pt_br" + "en": decide il file esistente
+        assert!(parse_tm_id("tm_pt_br_en", dir.path()).is_err());
+        let stored = new_translation_memory("pt_br", "en", "2024-01-01T00:00:00+00:00");
+        fs::write(dir.path().join(get_tm_filename("pt_br", "en")), serde_json::to_string(&stored).unwrap()).unwrap();
+        assert_eq!(parse_tm_id("tm_pt_br_en", dir.path()).unwrap(), ("pt_br".to_string(), "en".to_string()));
+    }
+
+    #[test]
+    fn save_keeps_upserts_journaled_after_load() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let journal_path = dir.path().join("tm_en_it.journal.jsonl");
+
+        // TM caricata dal frontend, con un'unità modificata a mano dopo il caricamento
+        let mut memory = new_translation_memory("en", "it", "2024-01-01T00:00:00+00:00");
+        upsert_unit_in_memory(&mut memory, "Hello", "Ciao", "2024-01-01T00:00:00+00:00");
+        upsert_unit_in_memory(&mut memory, "Door", "Porta (a mano)", "2024-01-03T00:00:00+00:00");
+
+        let lines = [
+            // Già incluso al caricamento
+            TmJournalEntry { source_text: "Hello".into(), target_text: "Ciao".into(), timestamp: "2024-01-01T00:00:00+00:00".into() },
+            // Upsert arrivati dopo il caricamento
+            TmJournalEntry { source_text: "World".into(), target_text: "Mondo".into(), timestamp: "2024-01-02T00:00:00+00:00".into() },
+            TmJournalEntry { source_text: "Hello".into(), target_text: "Salve".into(), timestamp: "2024-01-02T00:00:00+00:00".into() },
+            // Più vecchio della modifica a mano
+            TmJournalEntry { source_text: "Door".into(), target_text: "Porta".into(), timestamp: "2024-01-02T00:00:00+00:00".into() },
+        ];
+        let content: Vec<String> = lines.iter().map(|l| serde_json::to_string(l).unwrap()).collect();
+        fs::write(&journal_path, content.join("\n") + "\n{troncata").unwrap();
+
+        assert_eq!(merge_pending_journal(&mut memory, &journal_path), 2);
+        let target = |source: &str| memory.units.iter().find(|u| u.source_text == source).map(|u| u.target_text.clone());
+        assert_eq!(target("World").as_deref(), Some("Mondo"));
+        assert_eq!(target("Hello").as_deref(), Some("Salve"));
+        assert_eq!(target("Door").as_deref(), Some("Porta (a mano)"));
+        assert_eq!(memory.stats.total_units, 3);
+    }
+
+    #[test]
+    fn save_does_not_resurrect_units_deleted_after_load() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let journal_path = dir.path().join("tm_en_it.journal.jsonl");
+
+        // Caricata il 2 gennaio con "Hello" e "Door" dal journal; poi "Door" eliminata a mano
+        let mut memory = new_translation_memory("en", "it", "2024-01-01T00:00:00+00:00");
+        upsert_unit_in_memory(&mut memory, "Hello", "Ciao", "2024-01-01T00:00:00+00:00");
+        memory.loaded_at = Some("2024-01-02T00:00:00+00:00".to_string());
+
+        let lines = [
+            TmJournalEntry { source_text: "Hello".into(), target_text: "Ciao".into(), timestamp: "2024-01-01T00:00:00+00:00".into() },
+            TmJournalEntry { source_text: "Door".into(), target_text: "Porta".into(), timestamp: "2024-01-01T12:00:00+00:00".into() },
+            // Arrivato dopo il caricamento: il frontend non l'ha mai visto
+            TmJournalEntry { source_text: "World".into(), target_text: "Mondo".into(), timestamp: "2024-01-03T00:00:00+00:00".into() },
+        ];
+        let content: Vec<String> = lines.iter().map(|l| serde_json::to_string(l).unwrap()).collect();
+        fs::write(&journal_path, content.join("\n")).unwrap();
+
+        assert_eq!(merge_pending_journal(&mut memory, &journal_path), 1);
+        assert!(memory.units.iter().any(|u| u.source_text == "World"));
+        assert!(!memory.units.iter().any(|u| u.source_text == "Door"));
+
+        // L'istante di caricamento non finisce nel file salvato
+        memory.loaded_at = None;
+        let saved = serde_json::to_value(&memory).unwrap();
+        assert!(saved.get("loadedAt").is_none());
+    }
+
+    #[test]
+    fn fuzzy_lookup_normalizes_typography() {
+        crate::string_id::set_typography_normalization(true);
+        let mut memory = new_translation_memory("en", "it", "2024-01-01T00:00:00+00:00");
+        upsert_unit_in_memory(&mut memory, "Don\u{2019}t go\u{2026} now", "Non andare... ora", "2024-01-01T00:00:00+00:00");
+
+        let matches = fuzzy_lookup_units(&memory.units, "Don't go... now", 70, 10);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].score, 100);
+    }
+}
@@ -12,6 +12,7 @@ use crate::ue_translator::{
     get_translator_dll_path,
     injector::{inject_translator_dll, find_game_process},
     translation_cache::{TranslationCache, CacheStats},
+    locres::{parse_locres_file, write_locres_with_version, LocresEntry, LocresFile, LocresVersion},
 };
 
 /// Stato globale del translator
@@ -246,3 +247,26 @@ pub struct CompatibilityResult {
     pub anticheat_type: Option<String>,
     pub message: String,
 }
+
+/// Legge le stringhe traducibili da un file `.locres`
+#[command]
+pub fn parse_ue_locres(path: String) -> Result<LocresFile, String> {
+    let file = parse_locres_file(Path::new(&path))?;
+    log::info!("📖 {} voci lette da {} ({:?})", file.entries.len(), path, file.version);
+    Ok(file)
+}
+
+/// Riscrive un file `.locres` con le stringhe tradotte.
+/// Se il file esiste già ne viene salvata una copia `.locres.bak` (solo la prima volta).
+#[command]
+pub fn write_ue_locres(path: String, entries: Vec<LocresEntry>, version: Option<LocresVersion>) -> Result<(), String> {
+    let target = Path::new(&path);
+    let backup = target.with_extension("locres.bak");
+    if target.exists() && !backup.exists() {
+        std::fs::copy(target, &backup).map_err(|e| format!("Impossibile creare backup: {}", e))?;
+    }
+
+    write_locres_with_version(&entries, target, version.unwrap_or(LocresVersion::OptimizedCrc32))?;
+    log::info!("💾 {} voci scritte in {}", entries.len(), path);
+    Ok(())
+}
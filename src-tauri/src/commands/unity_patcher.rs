@@ -35,11 +35,34 @@ const XUNITY_URL: &str = "https://github.com/bbepis/XUnity.AutoTranslator/releas
 // XUnity per BepInEx IL2CPP (versione speciale per IL2CPP)
 const XUNITY_IL2CPP_URL: &str = "https://github.com/bbepis/XUnity.AutoTranslator/releases/download/v5.5.0/XUnity.AutoTranslator-BepInEx-IL2CPP-5.5.0.zip";
 
+// Versione di XUnity.AutoTranslator distribuita dagli URL sopra
+const XUNITY_VERSION: &str = "5.5.0";
+
+/// Azione eseguita da un'operazione di patch
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchAction {
+    /// Patch scaricata e installata
+    Installed,
+    /// Installazione già presente e aggiornata, nulla da fare
+    Skipped,
+    /// Installazione già presente, aggiornata solo la configurazione
+    Reconfigured,
+    /// Patch rimossa
+    Removed,
+    /// Nessuna modifica
+    Unchanged,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
 pub struct PatchStatus {
     success: bool,
     message: String,
     steps_completed: Vec<String>,
+    action: PatchAction,
+    /// Modifiche alla cartella del gioco (installazione/disinstallazione con backup)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    manifest: Option<UnityPatchManifest>,
 }
 
 /// Rileva se un eseguibile PE è 32-bit o 64-bit
@@ -617,6 +640,252 @@ fn detect_godot_embedded_pck(exe_path: &Path) -> Option<String> {
     None
 }
 
+// ============================================================================
+// GODOT: RISORSE DI TRADUZIONE
+// ============================================================================
+
+/// Pacchetto .pck trovato (separato o incorporato nell'eseguibile)
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct GodotPackInfo {
+    pub path: String,
+    pub embedded: bool,
+    /// 1 = Godot 3.x, 2 = Godot 4.0-4.3, 3 = Godot 4.4+
+    pub format_version: u32,
+    pub godot_version: String,
+    pub file_count: usize,
+    /// Directory cifrata: impossibile elencare i file senza la chiave
+    pub encrypted: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct GodotTranslationInfo {
+    pub game_path: String,
+    pub godot_version: Option<String>,
+    pub packs: Vec<GodotPackInfo>,
+    /// Risorse .translation/.po/.tres trovate (percorsi res:// o su disco)
+    pub translation_resources: Vec<String>,
+    /// File .csv sorgente importati come traduzioni
+    pub csv_sources: Vec<String>,
+    pub uses_csv_translation: bool,
+    pub uses_po_translation: bool,
+    pub locales: Vec<String>,
+    pub recommendation: String,
+}
+
+/// Legge header e directory di un pck che inizia a `start` nel file.
+/// Supporta i formati 1 (Godot 3), 2 e 3 (Godot 4).
+fn read_pck_directory(file: &mut File, start: u64, path: &Path, embedded: bool) -> Result<(GodotPackInfo, Vec<String>), String> {
+    use std::io::{Seek, SeekFrom};
+    
+    fn read_u32(file: &mut File) -> Result<u32, String> {
+        let mut buf = [0u8; 4];
+        file.read_exact(&mut buf).map_err(|e| e.to_string())?;
+        Ok(u32::from_le_bytes(buf))
+    }
+    fn read_u64(file: &mut File) -> Result<u64, String> {
+        let mut buf = [0u8; 8];
+        file.read_exact(&mut buf).map_err(|e| e.to_string())?;
+        Ok(u64::from_le_bytes(buf))
+    }
+    
+    file.seek(SeekFrom::Start(start)).map_err(|e| e.to_string())?;
+    let mut magic = [0u8; 4];
+    file.read_exact(&mut magic).map_err(|e| e.to_string())?;
+    if &magic != b"GDPC" {
+        return Err("Magic GDPC non trovato".to_string());
+    }
+    
+    let format_version = read_u32(file)?;
+    let major = read_u32(file)?;
+    let minor = read_u32(file)?;
+    let patch = read_u32(file)?;
+    
+    let mut encrypted = false;
+    match format_version {
+        1 => {}
+        2 => {
+            let flags = read_u32(file)?;
+            encrypted = flags & 1 != 0;
+            let _file_base = read_u64(file)?;
+        }
+        3 => {
+            let flags = read_u32(file)?;
+            encrypted = flags & 1 != 0;
+            let _file_base = read_u64(file)?;
+            let dir_offset = read_u64(file)?;
+            file.seek(SeekFrom::Start(start + dir_offset)).map_err(|e| e.to_string())?;
+        }
+        other => return Err(format!("Formato pck {} non supportato", other)),
+    }
+    if format_version < 3 {
+        // 16 u32 riservati
+        file.seek(SeekFrom::Current(16 * 4)).map_err(|e| e.to_string())?;
+    }
+    
+    let file_count = read_u32(file)? as usize;
+    let mut files = Vec::new();
+    if !encrypted {
+        for _ in 0..file_count.min(200_000) {
+            let path_len = read_u32(file)? as usize;
+            if path_len > 4096 {
+                return Err("Directory pck corrotta".to_string());
+            }
+            let mut raw = vec![0u8; path_len];
+            file.read_exact(&mut raw).map_err(|e| e.to_string())?;
+            let name = String::from_utf8_lossy(&raw).trim_end_matches('\0').to_string();
+            // offset + size + md5, più flags dal formato 2
+            let skip = 8 + 8 + 16 + if format_version >= 2 { 4 } else { 0 };
+            file.seek(SeekFrom::Current(skip)).map_err(|e| e.to_string())?;
+            files.push(name);
+        }
+    }
+    
+    let info = GodotPackInfo {
+        path: path.to_string_lossy().to_string(),
+        embedded,
+        format_version,
+        godot_version: format!("{}.{}.{}", major, minor, patch),
+        file_count,
+        encrypted,
+    };
+    Ok((info, files))
+}
+
+/// Apre un pck separato o quello incorporato in un eseguibile
+fn open_godot_pack(path: &Path) -> Option<(GodotPackInfo, Vec<String>)> {
+    use std::io::{Seek, SeekFrom};
+    
+    let mut file = File::open(path).ok()?;
+    let is_pck = path.extension().map(|e| e.eq_ignore_ascii_case("pck")).unwrap_or(false);
+    if is_pck {
+        return read_pck_directory(&mut file, 0, path, false).ok();
+    }
+    
+    // Eseguibile: [.. exe ..][pck][dimensione pck: u64]["GDPC"]
+    let len = file.seek(SeekFrom::End(0)).ok()?;
+    if len < 12 {
+        return None;
+    }
+    file.seek(SeekFrom::End(-12)).ok()?;
+    let mut tail = [0u8; 12];
+    file.read_exact(&mut tail).ok()?;
+    if &tail[8..12] != b"GDPC" {
+        return None;
+    }
+    let pck_size = u64::from_le_bytes(tail[0..8].try_into().ok()?);
+    let start = len.checked_sub(12)?.checked_sub(pck_size)?;
+    read_pck_directory(&mut file, start, path, true).ok()
+}
+
+/// Estrae il codice lingua da nomi come "strings.it.translation" o "it.po"
+fn godot_locale_from_name(name: &str) -> Option<String> {
+    let file = name.rsplit('/').next()?;
+    let stem = file.rsplit_once('.')?.0;
+    let locale = stem.rsplit('.').next()?;
+    let valid = (2..=7).contains(&locale.len())
+        && locale.chars().all(|c| c.is_ascii_alphabetic() || c == '_' || c == '-');
+    if valid { Some(locale.to_string()) } else { None }
+}
+
+/// Individua le risorse di traduzione di un gioco Godot (sola lettura)
+#[command]
+pub async fn prepare_godot_translation(game_path: String) -> Result<GodotTranslationInfo, String> {
+    let game_dir = Path::new(&game_path);
+    if !game_dir.is_dir() {
+        return Err("Cartella del gioco non trovata".to_string());
+    }
+    
+    let mut packs = Vec::new();
+    let mut all_files: Vec<String> = Vec::new();
+    
+    // Pck separati ed eseguibili con pck incorporato
+    for entry in fs::read_dir(game_dir).map_err(|e| e.to_string())?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+        if ["pck", "exe", "x86_64", "x86_32", "arm64"].contains(&ext.as_str()) {
+            if let Some((info, files)) = open_godot_pack(&path) {
+                all_files.extend(files);
+                packs.push(info);
+            }
+        }
+    }
+    
+    // Progetto non impacchettato: file direttamente su disco
+    let project_godot = game_dir.join("project.godot");
+    if project_godot.exists() {
+        for entry in walkdir::WalkDir::new(game_dir).max_depth(6).into_iter().filter_map(|e| e.ok()) {
+            if let Ok(rel) = entry.path().strip_prefix(game_dir) {
+                all_files.push(format!("res://{}", rel.to_string_lossy().replace('\\', "/")));
+            }
+        }
+    }
+    
+    if packs.is_empty() && !project_godot.exists() {
+        return Err("Nessun pck Godot o project.godot trovato: non sembra un gioco Godot".to_string());
+    }
+    
+    let mut translation_resources = Vec::new();
+    let mut csv_sources = Vec::new();
+    let mut locales: Vec<String> = Vec::new();
+    let mut uses_po_translation = false;
+    
+    for file in &all_files {
+        let lower = file.to_lowercase();
+        if lower.ends_with(".translation")
+            || lower.ends_with(".po")
+            || ((lower.ends_with(".tres") || lower.ends_with(".res")) && lower.contains("translation")) {
+            translation_resources.push(file.clone());
+            if lower.ends_with(".po") {
+                uses_po_translation = true;
+            }
+            if let Some(locale) = godot_locale_from_name(file) {
+                if !locales.contains(&locale) {
+                    locales.push(locale);
+                }
+            }
+        } else if lower.ends_with(".csv.import") {
+            // Il CSV è stato importato come traduzione dall'editor Godot
+            csv_sources.push(file.trim_end_matches(".import").to_string());
+        }
+    }
+    
+    // In Godot il CSV importato produce file .<locale>.translation
+    let uses_csv_translation = !csv_sources.is_empty()
+        || translation_resources.iter().any(|f| f.to_lowercase().ends_with(".translation"));
+    locales.sort();
+    
+    let godot_version = packs.first().map(|p| p.godot_version.clone())
+        .or_else(|| detect_godot_version(game_dir));
+    
+    let recommendation = if packs.iter().any(|p| p.encrypted) {
+        "Pck cifrato: serve la chiave di cifratura per estrarre le traduzioni. Usa OCR Translator come alternativa.".to_string()
+    } else if uses_csv_translation {
+        format!(
+            "Il gioco usa il sistema CSV di Godot ({} lingue). Estrai il pck con GDRE Tools, aggiungi una colonna per la nuova lingua al CSV e reimporta.",
+            locales.len()
+        )
+    } else if uses_po_translation {
+        "Il gioco usa file gettext .po: estrai il pck con GDRE Tools e traduci il file .po della lingua sorgente.".to_string()
+    } else {
+        "Nessuna risorsa di traduzione Godot trovata: i testi sono probabilmente nelle scene (.tscn/.scn). Estrai il pck con GDRE Tools.".to_string()
+    };
+    
+    Ok(GodotTranslationInfo {
+        game_path,
+        godot_version,
+        packs,
+        translation_resources,
+        csv_sources,
+        uses_csv_translation,
+        uses_po_translation,
+        locales,
+        recommendation,
+    })
+}
+
 // ============================================================================
 // RILEVAMENTO RPG MAKER CON VERSIONE SPECIFICA
 // ============================================================================
@@ -1958,11 +2227,340 @@ EnableBatching=true
         success: true,
         message: "Patch IPA installata! Avvia il gioco per completare il setup.".to_string(),
         steps_completed: steps,
+        action: PatchAction::Installed,
+        manifest: None,
+    })
+}
+
+/// Backend di scripting di un gioco Unity
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnityRuntime {
+    Mono,
+    #[serde(rename = "IL2CPP")]
+    Il2Cpp,
+}
+
+/// Backend Unity rilevato e variante BepInEx compatibile
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct UnityBackend {
+    pub runtime: UnityRuntime,
+    pub is_64bit: bool,
+    pub data_folder: String,
+    pub unity_version: Option<String>,
+    /// Variante BepInEx da installare, es. "BepInEx 5 x64"
+    pub required_bepinex: String,
+    /// Variante BepInEx già presente nella cartella del gioco
+    pub installed_bepinex: Option<String>,
+    /// true se il BepInEx presente è compatibile (runtime e architettura) e può essere riutilizzato
+    pub installed_bepinex_compatible: bool,
+    /// Perché il BepInEx presente non è compatibile, se non lo è
+    pub installed_bepinex_mismatch: Option<String>,
+}
+
+/// BepInEx già presente nella cartella del gioco
+struct InstalledBepInEx {
+    runtime: UnityRuntime,
+    /// Architettura del proxy doorstop (winhttp.dll); None se assente o illeggibile
+    is_64bit: Option<bool>,
+    name: String,
+}
+
+/// Variante di BepInEx già presente: runtime dal core, architettura dal proxy winhttp.dll
+fn detect_installed_bepinex(game_dir: &Path) -> Option<InstalledBepInEx> {
+    let core = game_dir.join("BepInEx").join("core");
+    let (runtime, variant) = if core.join("BepInEx.Unity.IL2CPP.dll").exists() || core.join("BepInEx.IL2CPP.dll").exists() {
+        (UnityRuntime::Il2Cpp, "BepInEx 6 IL2CPP")
+    } else if core.join("BepInEx.Unity.Mono.dll").exists() {
+        (UnityRuntime::Mono, "BepInEx 6 Mono")
+    } else if core.join("BepInEx.dll").exists() {
+        (UnityRuntime::Mono, "BepInEx 5")
+    } else {
+        return None;
+    };
+    
+    let is_64bit = detect_exe_architecture(&game_dir.join("winhttp.dll")).ok();
+    let name = match is_64bit {
+        Some(true) => format!("{} x64", variant),
+        Some(false) => format!("{} x86", variant),
+        None => variant.to_string(),
+    };
+    Some(InstalledBepInEx { runtime, is_64bit, name })
+}
+
+/// Motivo per cui il BepInEx presente non è riutilizzabile; None se è compatibile
+fn installed_bepinex_mismatch(installed: &InstalledBepInEx, runtime: UnityRuntime, is_64bit: bool) -> Option<String> {
+    if installed.runtime != runtime {
+        let runtime_str = |rt: UnityRuntime| if rt == UnityRuntime::Il2Cpp { "IL2CPP" } else { "Mono" };
+        return Some(format!(
+            "runtime {} ma il gioco è {}",
+            runtime_str(installed.runtime),
+            runtime_str(runtime)
+        ));
+    }
+    let arch = |x64: bool| if x64 { "x64" } else { "x86" };
+    match installed.is_64bit {
+        Some(loader_64bit) if loader_64bit != is_64bit => Some(format!(
+            "loader winhttp.dll {} ma il gioco è {}",
+            arch(loader_64bit),
+            arch(is_64bit)
+        )),
+        Some(_) => None,
+        None => Some("loader winhttp.dll mancante o non leggibile: architettura non verificabile".to_string()),
+    }
+}
+
+/// Trova la cartella <gioco>_Data, preferendo quella che corrisponde all'eseguibile
+fn find_unity_data_folder(game_dir: &Path, exe_path: &Path) -> Result<std::path::PathBuf, String> {
+    if let Some(stem) = exe_path.file_stem() {
+        let expected = game_dir.join(format!("{}_Data", stem.to_string_lossy()));
+        if expected.is_dir() {
+            return Ok(expected);
+        }
+    }
+    
+    let candidates: Vec<std::path::PathBuf> = fs::read_dir(game_dir)
+        .map_err(|e| format!("Impossibile leggere la cartella del gioco: {}", e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir() && p.file_name().map_or(false, |n| n.to_string_lossy().ends_with("_Data")))
+        .collect();
+    
+    match candidates.len() {
+        0 => Err("Cartella <gioco>_Data non trovata: impossibile determinare il backend Unity".to_string()),
+        1 => Ok(candidates[0].clone()),
+        _ => Err(format!(
+            "Rilevamento ambiguo: trovate più cartelle _Data ({}) e nessuna corrisponde all'eseguibile",
+            candidates.iter()
+                .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Rileva backend (Mono/IL2CPP) e architettura di un gioco Unity.
+/// Restituisce un errore esplicito se i segnali sono contraddittori.
+fn detect_unity_backend(game_dir: &Path, exe_path: &Path) -> Result<UnityBackend, String> {
+    let data_folder = find_unity_data_folder(game_dir, exe_path)?;
+    let game_assembly = game_dir.join("GameAssembly.dll");
+    
+    let il2cpp_signals = game_assembly.exists() || data_folder.join("il2cpp_data").is_dir();
+    let managed = data_folder.join("Managed");
+    let mono_signals = managed.join("Assembly-CSharp.dll").exists() || managed.join("UnityEngine.dll").exists();
+    
+    let runtime = match (il2cpp_signals, mono_signals) {
+        (true, false) => UnityRuntime::Il2Cpp,
+        (false, true) => UnityRuntime::Mono,
+        (true, true) => return Err(format!(
+            "Backend ambiguo: presenti sia GameAssembly.dll/il2cpp_data (IL2CPP) sia assembly Mono in {}/Managed",
+            data_folder.file_name().unwrap_or_default().to_string_lossy()
+        )),
+        (false, false) => return Err(format!(
+            "Backend non rilevabile: né GameAssembly.dll (IL2CPP) né assembly Mono in {}/Managed trovati",
+            data_folder.file_name().unwrap_or_default().to_string_lossy()
+        )),
+    };
+    
+    let is_64bit = detect_exe_architecture(exe_path)
+        .map_err(|e| format!("Architettura dell'eseguibile non rilevabile: {}", e))?;
+    
+    // Il runtime nativo deve avere la stessa architettura dell'eseguibile
+    let native_runtime = if runtime == UnityRuntime::Il2Cpp { game_assembly } else { game_dir.join("UnityPlayer.dll") };
+    if native_runtime.exists() {
+        if let Ok(native_64bit) = detect_exe_architecture(&native_runtime) {
+            if native_64bit != is_64bit {
+                return Err(format!(
+                    "Architettura incoerente: eseguibile {} ma {} {}",
+                    if is_64bit { "x64" } else { "x86" },
+                    native_runtime.file_name().unwrap_or_default().to_string_lossy(),
+                    if native_64bit { "x64" } else { "x86" }
+                ));
+            }
+        }
+    }
+    
+    let arch = if is_64bit { "x64" } else { "x86" };
+    let required_bepinex = match runtime {
+        UnityRuntime::Il2Cpp => format!("BepInEx 6 IL2CPP {}", arch),
+        UnityRuntime::Mono => format!("BepInEx 5 {}", arch),
+    };
+    
+    let installed = detect_installed_bepinex(game_dir);
+    let installed_bepinex_mismatch = installed.as_ref()
+        .and_then(|i| installed_bepinex_mismatch(i, runtime, is_64bit));
+    let installed_bepinex_compatible = installed.is_some() && installed_bepinex_mismatch.is_none();
+    
+    Ok(UnityBackend {
+        runtime,
+        is_64bit,
+        data_folder: data_folder.to_string_lossy().to_string(),
+        unity_version: detect_unity_version(game_dir),
+        required_bepinex,
+        installed_bepinex: installed.map(|i| i.name),
+        installed_bepinex_compatible,
+        installed_bepinex_mismatch,
     })
 }
 
+/// Rileva backend Unity e variante BepInEx richiesta senza installare nulla
+#[command]
+pub async fn detect_unity_backend_info(game_path: String, game_exe_name: String) -> Result<UnityBackend, String> {
+    let game_dir = Path::new(&game_path);
+    let exe_path = game_dir.join(&game_exe_name);
+    if !exe_path.exists() {
+        return Err(format!("Eseguibile del gioco '{}' non trovato nella cartella specificata", game_exe_name));
+    }
+    detect_unity_backend(game_dir, &exe_path)
+}
+
+/// Stato di un'installazione BepInEx/IPA + XUnity.AutoTranslator esistente
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct InstallStatus {
+    /// Loader rilevato: "BepInEx 5", "BepInEx 6" o "IPA"
+    pub loader: Option<String>,
+    pub loader_version: Option<String>,
+    pub autotranslator_installed: bool,
+    /// Versione letta dalla DLL del plugin
+    pub autotranslator_version: Option<String>,
+    /// Versione distribuita da GameStringer
+    pub latest_version: String,
+    pub up_to_date: bool,
+    pub config_path: Option<String>,
+    /// Valore di Language= nella configurazione
+    pub configured_language: Option<String>,
+    pub expected_language: Option<String>,
+    /// None se la lingua attesa non è stata indicata o la config non esiste
+    pub language_matches: Option<bool>,
+}
+
+/// Legge la versione file (VS_FIXEDFILEINFO) da un PE, es. "5.5.0.0"
+fn read_pe_file_version(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    // Signature di VS_FIXEDFILEINFO: 0xFEEF04BD
+    let signature = [0xBD, 0x04, 0xEF, 0xFE];
+    let pos = bytes.windows(4).position(|w| w == signature)?;
+    let read_u32 = |offset: usize| -> Option<u32> {
+        bytes.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    let ms = read_u32(pos + 8)?;
+    let ls = read_u32(pos + 12)?;
+    Some(format!("{}.{}.{}.{}", ms >> 16, ms & 0xFFFF, ls >> 16, ls & 0xFFFF))
+}
+
+/// Confronta due versioni numeriche puntate (a >= b)
+fn version_at_least(version: &str, minimum: &str) -> bool {
+    let parse = |v: &str| -> Vec<u32> {
+        v.split('.').map(|p| p.trim().parse().unwrap_or(0)).collect()
+    };
+    let (mut a, mut b) = (parse(version), parse(minimum));
+    let len = a.len().max(b.len());
+    a.resize(len, 0);
+    b.resize(len, 0);
+    a >= b
+}
+
+/// Legge il valore Language= dalla sezione [General] di un file di config XUnity
+fn read_configured_language(config_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(config_path).ok()?;
+    let mut in_general = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_general = line.eq_ignore_ascii_case("[General]");
+        } else if in_general {
+            if let Some(value) = line.strip_prefix("Language=") {
+                return Some(value.trim().to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Aggiorna il valore Language= nella sezione [General]
+fn set_configured_language(config_path: &Path, lang: &str) -> Result<(), String> {
+    let content = fs::read_to_string(config_path).map_err(|e| e.to_string())?;
+    let mut in_general = false;
+    let mut replaced = false;
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_general = trimmed.eq_ignore_ascii_case("[General]");
+        } else if in_general && !replaced && trimmed.starts_with("Language=") {
+            lines.push(format!("Language={}", lang));
+            replaced = true;
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+    if !replaced {
+        return Err("Chiave Language= non trovata nella sezione [General]".to_string());
+    }
+    fs::write(config_path, lines.join("\n") + "\n").map_err(|e| e.to_string())
+}
+
+/// Ispeziona la cartella del gioco alla ricerca di BepInEx/IPA e XUnity.AutoTranslator
+fn inspect_autotranslator_install(game_dir: &Path, expected_language: Option<&str>) -> InstallStatus {
+    let bepinex_core = game_dir.join("BepInEx").join("core");
+    let (loader, loader_dll) = if bepinex_core.join("BepInEx.Core.dll").exists() {
+        (Some("BepInEx 6"), Some(bepinex_core.join("BepInEx.Core.dll")))
+    } else if bepinex_core.join("BepInEx.dll").exists() {
+        (Some("BepInEx 5"), Some(bepinex_core.join("BepInEx.dll")))
+    } else if game_dir.join("Plugins").exists() && game_dir.join("IPA").exists() {
+        (Some("IPA"), None)
+    } else {
+        (None, None)
+    };
+    
+    // Possibili posizioni della DLL del plugin e della configurazione
+    let plugin_candidates = [
+        game_dir.join("BepInEx").join("plugins").join("XUnity.AutoTranslator").join("XUnity.AutoTranslator.Plugin.Core.dll"),
+        game_dir.join("Plugins").join("XUnity.AutoTranslator").join("XUnity.AutoTranslator.Plugin.Core.dll"),
+        game_dir.join("Plugins").join("XUnity.AutoTranslator.Plugin.Core.dll"),
+    ];
+    let config_candidates = [
+        game_dir.join("BepInEx").join("config").join("AutoTranslatorConfig.ini"),
+        game_dir.join("Plugins").join("XUnity.AutoTranslator").join("Config.ini"),
+    ];
+    
+    let plugin_dll = plugin_candidates.iter().find(|p| p.exists());
+    let autotranslator_version = plugin_dll.and_then(|p| read_pe_file_version(p));
+    let config_path = config_candidates.iter().find(|p| p.exists());
+    let configured_language = config_path.and_then(|p| read_configured_language(p));
+    
+    let language_matches = match (expected_language, &configured_language) {
+        (Some(expected), Some(configured)) => Some(expected.eq_ignore_ascii_case(configured)),
+        _ => None,
+    };
+    let up_to_date = loader.is_some()
+        && autotranslator_version.as_deref().map_or(false, |v| version_at_least(v, XUNITY_VERSION));
+    
+    InstallStatus {
+        loader: loader.map(|l| l.to_string()),
+        loader_version: loader_dll.and_then(|p| read_pe_file_version(&p)),
+        autotranslator_installed: plugin_dll.is_some(),
+        autotranslator_version,
+        latest_version: XUNITY_VERSION.to_string(),
+        up_to_date,
+        config_path: config_path.map(|p| p.to_string_lossy().to_string()),
+        configured_language,
+        expected_language: expected_language.map(|l| l.to_string()),
+        language_matches,
+    }
+}
+
+/// Verifica se BepInEx/XUnity.AutoTranslator è già installato e con quale versione
 #[command]
-pub async fn install_unity_autotranslator(game_path: String, game_exe_name: String, target_lang: Option<String>, translation_mode: Option<String>) -> Result<PatchStatus, String> {
+pub async fn check_unity_autotranslator(game_path: String, target_lang: Option<String>) -> Result<InstallStatus, String> {
+    let game_dir = Path::new(&game_path);
+    if !game_dir.exists() {
+        return Err("Cartella del gioco non trovata".to_string());
+    }
+    Ok(inspect_autotranslator_install(game_dir, target_lang.as_deref()))
+}
+
+#[command]
+pub async fn install_unity_autotranslator(game_path: String, game_exe_name: String, target_lang: Option<String>, translation_mode: Option<String>, force_reinstall: Option<bool>) -> Result<PatchStatus, String> {
     let lang = target_lang.unwrap_or_else(|| "it".to_string());
     let mode = translation_mode.unwrap_or_else(|| "capture".to_string());
     let mut steps = Vec::new();
@@ -2001,23 +2599,117 @@ pub async fn install_unity_autotranslator(game_path: String, game_exe_name: Stri
         return Err("Questo non sembra essere un gioco Unity. BepInEx richiede UnityPlayer.dll o una cartella *_Data.".to_string());
     }
     
-    // Rileva se è IL2CPP (GameAssembly.dll presente)
-    let is_il2cpp = game_dir.join("GameAssembly.dll").exists();
+    // Evita di reinstallare sopra un'installazione funzionante e aggiornata
+    let existing = inspect_autotranslator_install(game_dir, Some(lang.as_str()));
+    if existing.up_to_date && !force_reinstall.unwrap_or(false) {
+        steps.push(format!(
+            "✓ {} + XUnity.AutoTranslator {} già installati",
+            existing.loader.as_deref().unwrap_or("Loader"),
+            existing.autotranslator_version.as_deref().unwrap_or("?")
+        ));
+        
+        if existing.language_matches == Some(false) {
+            if let Some(ref config) = existing.config_path {
+                set_configured_language(Path::new(config), &lang)?;
+                steps.push(format!(
+                    "✓ Lingua aggiornata: {} → {}",
+                    existing.configured_language.as_deref().unwrap_or("?"), lang
+                ));
+                return Ok(PatchStatus {
+                    success: true,
+                    message: "Patch già aggiornata: configurazione lingua aggiornata.".to_string(),
+                    steps_completed: steps,
+                    action: PatchAction::Reconfigured,
+                    manifest: None,
+                });
+            }
+        }
+        
+        return Ok(PatchStatus {
+            success: true,
+            message: "Patch già installata e aggiornata, nessuna modifica necessaria.".to_string(),
+            steps_completed: steps,
+            action: PatchAction::Skipped,
+            manifest: None,
+        });
+    }
+    if existing.autotranslator_installed {
+        steps.push(format!(
+            "⚠ Reinstallazione sopra XUnity.AutoTranslator {}",
+            existing.autotranslator_version.as_deref().unwrap_or("versione sconosciuta")
+        ));
+    }
+    
+    // Rileva backend (Mono/IL2CPP) e architettura: meglio fermarsi che installare il BepInEx sbagliato
+    let backend = detect_unity_backend(game_dir, &exe_path)?;
+    let is_il2cpp = backend.runtime == UnityRuntime::Il2Cpp;
     let runtime_str = if is_il2cpp { "IL2CPP" } else { "Mono" };
     steps.push(format!("✓ Gioco Unity ({}) rilevato", runtime_str));
 
-    // Rileva architettura dell'eseguibile (x64 o x86)
-    let is_64bit = detect_exe_architecture(&exe_path).unwrap_or(true); // Default x64 se fallisce
+    let is_64bit = backend.is_64bit;
     let arch_str = if is_64bit { "x64 (64-bit)" } else { "x86 (32-bit)" };
     steps.push(format!("✓ Architettura rilevata: {}", arch_str));
 
-    // Rileva versione Unity per scegliere BepInEx appropriato
-    let unity_version = detect_unity_version(game_dir);
+    // Un BepInEx incompatibile già presente va rimosso prima, non sovrascritto
+    if let Some(ref installed) = backend.installed_bepinex {
+        if !backend.installed_bepinex_compatible {
+            return Err(format!(
+                "{} già installato non è compatibile con questo gioco Unity {} ({}; richiesto {}). Rimuovi la patch esistente prima di reinstallare.",
+                installed,
+                runtime_str,
+                backend.installed_bepinex_mismatch.as_deref().unwrap_or("variante diversa"),
+                backend.required_bepinex
+            ));
+        }
+    }
+    let install_loader = !backend.installed_bepinex_compatible || force_reinstall.unwrap_or(false);
+
+    // Copia di sicurezza di ciò che l'installazione sovrascrive: annullabile con uninstall_unity_autotranslator
+    let (mut manifest, mut attempt) = create_patch_backup(game_dir)?;
+    let reused = attempt.backup_dir != manifest.backup_dir;
+    if reused {
+        steps.push("✓ Backup della prima installazione conservato".to_string());
+    } else if !manifest.backed_up.is_empty() {
+        steps.push(format!("✓ Backup creato: {}", manifest.backed_up.join(", ")));
+    }
+
+    let result = install_patch_files(game_dir, &exe_path, &backend, &lang, &mode, install_loader, steps).await;
+
+    match result {
+        Ok(mut status) => {
+            manifest.added = added_entries(game_dir, &manifest.entries_before);
+            save_patch_manifest(&manifest)?;
+            if reused {
+                let _ = fs::remove_dir_all(&attempt.backup_dir);
+            }
+            status.manifest = Some(manifest);
+            Ok(status)
+        }
+        Err(e) => {
+            // Installazione a metà: si annulla solo questo tentativo
+            attempt.added = added_entries(game_dir, &attempt.entries_before);
+            let rollback = match restore_patch_backup(game_dir, &attempt) {
+                Ok(_) => "cartella del gioco ripristinata".to_string(),
+                Err(re) => format!("ripristino fallito: {} (backup in {})", re, attempt.backup_dir),
+            };
+            Err(format!("{} ({})", e, rollback))
+        }
+    }
+}
+
+/// Scarica e installa loader e XUnity.AutoTranslator (variante scelta dal backend)
+async fn install_patch_files(game_dir: &Path, exe_path: &Path, backend: &UnityBackend, lang: &str, mode: &str, install_loader: bool, mut steps: Vec<String>) -> Result<PatchStatus, String> {
+    let is_il2cpp = backend.runtime == UnityRuntime::Il2Cpp;
+    let is_64bit = backend.is_64bit;
+    let arch_str = if is_64bit { "x64 (64-bit)" } else { "x86 (32-bit)" };
+
+    // Versione Unity per scegliere BepInEx appropriato
+    let unity_version = backend.unity_version.clone();
     
     // Se è IL2CPP, usa BepInEx 6 IL2CPP
     if is_il2cpp {
         steps.push("⚡ Usando BepInEx 6 per IL2CPP...".to_string());
-        return install_il2cpp_patch(game_dir, &lang, &mode, is_64bit, steps).await;
+        return install_il2cpp_patch(game_dir, lang, mode, is_64bit, install_loader, steps).await;
     }
     
     // Unity 5.6+ può usare BepInEx Legacy
@@ -2036,7 +2728,7 @@ pub async fn install_unity_autotranslator(game_path: String, game_exe_name: Stri
     
     // Se usa IPA, flusso diverso
     if use_ipa {
-        return install_with_ipa(game_dir, &exe_path, &lang, steps).await;
+        return install_with_ipa(game_dir, exe_path, lang, steps).await;
     }
     
     // Mono: usa BepInEx 5.x
@@ -2054,11 +2746,15 @@ pub async fn install_unity_autotranslator(game_path: String, game_exe_name: Stri
     }
     steps.push(format!("✓ Usando BepInEx {}", bepinex_version));
 
-    // 1. Scarica e Installa BepInEx
-    steps.push(format!("Download BepInEx {} {}...", bepinex_version, arch_str));
-    match download_and_extract(bepinex_url, game_dir).await {
-        Ok(_) => steps.push("✓ BepInEx installato".to_string()),
-        Err(e) => return Err(format!("Errore installazione BepInEx: {}", e)),
+    // 1. Scarica e Installa BepInEx (se non già presente e compatibile)
+    if install_loader {
+        steps.push(format!("Download BepInEx {} {}...", bepinex_version, arch_str));
+        match download_and_extract(bepinex_url, game_dir).await {
+            Ok(_) => steps.push("✓ BepInEx installato".to_string()),
+            Err(e) => return Err(format!("Errore installazione BepInEx: {}", e)),
+        }
+    } else {
+        steps.push(format!("✓ {} già presente e compatibile", backend.installed_bepinex.as_deref().unwrap_or("BepInEx")));
     }
 
     // 2. Scarica e Installa XUnity.AutoTranslator
@@ -2077,7 +2773,7 @@ pub async fn install_unity_autotranslator(game_path: String, game_exe_name: Stri
     let auto_translator_config = config_dir.join("AutoTranslatorConfig.ini");
     
     // Configura endpoint in base alla modalità scelta
-    let (endpoint, mode_desc) = match mode.as_str() {
+    let (endpoint, mode_desc) = match mode {
         "google" => ("GoogleTranslateV2", "Google Translate (automatico)"),
         "deepl" => ("DeepLTranslate", "DeepL (richiede API key)"),
         _ => ("", "Solo cattura (traduci manualmente)"), // capture = nessun endpoint
@@ -2130,11 +2826,13 @@ EnableTextAssetRedirector=true
         success: true,
         message: "Patch Unity installata con successo! Avvia il gioco per completare il setup.".to_string(),
         steps_completed: steps,
+        action: PatchAction::Installed,
+        manifest: None,
     })
 }
 
 /// Installa BepInEx 6 IL2CPP + XUnity per giochi Unity IL2CPP
-async fn install_il2cpp_patch(game_dir: &Path, lang: &str, mode: &str, is_64bit: bool, mut steps: Vec<String>) -> Result<PatchStatus, String> {
+async fn install_il2cpp_patch(game_dir: &Path, lang: &str, mode: &str, is_64bit: bool, install_loader: bool, mut steps: Vec<String>) -> Result<PatchStatus, String> {
     // Verifica versione Unity - Unity 6 (6000.x) non è supportato
     let unity_version = detect_unity_version(game_dir);
     if let Some(ref ver) = unity_version {
@@ -2152,10 +2850,14 @@ async fn install_il2cpp_patch(game_dir: &Path, lang: &str, mode: &str, is_64bit:
     let bepinex_url = if is_64bit { BEPINEX6_IL2CPP_X64_URL } else { BEPINEX6_IL2CPP_X86_URL };
     let arch_str = if is_64bit { "x64" } else { "x86" };
     
-    steps.push(format!("Download BepInEx 6 IL2CPP {}...", arch_str));
-    match download_and_extract(bepinex_url, game_dir).await {
-        Ok(_) => steps.push("✓ BepInEx 6 IL2CPP installato".to_string()),
-        Err(e) => return Err(format!("Errore installazione BepInEx 6 IL2CPP: {}", e)),
+    if install_loader {
+        steps.push(format!("Download BepInEx 6 IL2CPP {}...", arch_str));
+        match download_and_extract(bepinex_url, game_dir).await {
+            Ok(_) => steps.push("✓ BepInEx 6 IL2CPP installato".to_string()),
+            Err(e) => return Err(format!("Errore installazione BepInEx 6 IL2CPP: {}", e)),
+        }
+    } else {
+        steps.push(format!("✓ BepInEx 6 IL2CPP {} già presente e compatibile", arch_str));
     }
 
     // 2. Scarica XUnity IL2CPP
@@ -2227,6 +2929,8 @@ EnableTextAssetRedirector=true
         success: true,
         message: "Patch Unity IL2CPP installata! Il primo avvio richiederà alcuni minuti.".to_string(),
         steps_completed: steps,
+        action: PatchAction::Installed,
+        manifest: None,
     })
 }
 
@@ -2343,6 +3047,237 @@ pub async fn save_xunity_translation(game_path: String, original: String, new_tr
     Ok(())
 }
 
+/// Elementi della cartella del gioco che l'installazione può sovrascrivere
+const PATCH_BACKUP_ITEMS: &[&str] = &["BepInEx", "winhttp.dll", "doorstop_config.ini", ".doorstop_version", "Plugins"];
+
+/// Cartelle con il lavoro dell'utente (traduzioni, configurazione): ripristino e rollback non le eliminano mai
+const PATCH_PROTECTED_PATHS: &[&str] = &["BepInEx/Translation", "BepInEx/config"];
+
+/// Sottocartella del backup con l'istantanea del singolo tentativo di reinstallazione
+const PATCH_ATTEMPT_DIR: &str = "attempt";
+
+/// Modifiche di un'installazione della patch Unity, salvate accanto al backup
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct UnityPatchManifest {
+    pub game_path: String,
+    pub created_at: String,
+    pub backup_dir: String,
+    /// Elementi già presenti, copiati nel backup prima di sovrascriverli
+    pub backed_up: Vec<String>,
+    /// Elementi creati dall'installazione (rimossi alla disinstallazione)
+    pub added: Vec<String>,
+    /// Contenuto della cartella del gioco prima della prima installazione
+    #[serde(default)]
+    pub entries_before: Vec<String>,
+}
+
+/// Cartella di backup di un gioco: una per percorso, fuori dalla cartella del gioco
+fn patch_backup_dir(game_dir: &Path) -> Result<std::path::PathBuf, String> {
+    use sha2::{Digest, Sha256};
+    let canonical = game_dir.canonicalize().unwrap_or_else(|_| game_dir.to_path_buf());
+    let digest = Sha256::digest(canonical.to_string_lossy().to_lowercase().as_bytes());
+    let hash: String = digest[..6].iter().map(|b| format!("{:02x}", b)).collect();
+    let name: String = game_dir.file_name().unwrap_or_default().to_string_lossy().chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    Ok(dirs::data_dir()
+        .ok_or("Impossibile trovare directory dati")?
+        .join("GameStringer")
+        .join("unity_patch_backups")
+        .join(format!("{}_{}", name, hash)))
+}
+
+/// Nomi degli elementi al primo livello della cartella del gioco
+fn top_level_entries(game_dir: &Path) -> Vec<String> {
+    let mut entries: Vec<String> = fs::read_dir(game_dir)
+        .map(|rd| rd.filter_map(|e| e.ok()).map(|e| e.file_name().to_string_lossy().to_string()).collect())
+        .unwrap_or_default();
+    entries.sort();
+    entries
+}
+
+/// Elementi comparsi rispetto a `before`
+fn added_entries(game_dir: &Path, before: &[String]) -> Vec<String> {
+    top_level_entries(game_dir).into_iter().filter(|e| !before.contains(e)).collect()
+}
+
+fn load_patch_manifest(game_dir: &Path) -> Option<UnityPatchManifest> {
+    load_patch_manifest_at(&patch_backup_dir(game_dir).ok()?)
+}
+
+fn load_patch_manifest_at(backup_dir: &Path) -> Option<UnityPatchManifest> {
+    serde_json::from_str(&fs::read_to_string(backup_dir.join("manifest.json")).ok()?).ok()
+}
+
+fn save_patch_manifest(manifest: &UnityPatchManifest) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    fs::write(Path::new(&manifest.backup_dir).join("manifest.json"), content)
+        .map_err(|e| format!("Errore salvataggio manifest backup: {}", e))
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() { fs::remove_dir_all(path) } else { fs::remove_file(path) }
+}
+
+/// Percorsi protetti dentro `name`, relativi a `name` (es. "BepInEx" -> ["Translation", "config"])
+fn nested_protected<'a>(protected: &[&'a str], name: &str) -> Vec<&'a str> {
+    protected.iter()
+        .filter_map(|p| p.strip_prefix(name)?.strip_prefix('/'))
+        .collect()
+}
+
+/// Rimuove un elemento della cartella del gioco lasciando intatti i percorsi protetti al suo interno
+fn remove_preserving(path: &Path, protected: &[&str]) -> std::io::Result<()> {
+    if protected.is_empty() || !path.is_dir() {
+        return remove_path(path);
+    }
+    for entry in fs::read_dir(path)?.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if protected.contains(&name.as_str()) {
+            continue;
+        }
+        remove_preserving(&entry.path(), &nested_protected(protected, &name))?;
+    }
+    Ok(())
+}
+
+/// Rimette un elemento salvato senza sovrascrivere i percorsi protetti già presenti
+fn restore_preserving(saved: &Path, target: &Path, protected: &[&str]) -> std::io::Result<()> {
+    if !saved.is_dir() {
+        return fs::copy(saved, target).map(|_| ());
+    }
+    if protected.is_empty() {
+        return super::utilities::copy_dir_recursive(saved, target);
+    }
+    fs::create_dir_all(target)?;
+    for entry in fs::read_dir(saved)?.filter_map(|e| e.ok()) {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let destination = target.join(&name);
+        if protected.contains(&name.as_str()) && destination.exists() {
+            continue;
+        }
+        restore_preserving(&entry.path(), &destination, &nested_protected(protected, &name))?;
+    }
+    Ok(())
+}
+
+/// Copia in backup gli elementi che l'installazione sovrascrive.
+/// Ritorna il manifest dell'installazione e quello da usare per annullare questo tentativo:
+/// alla prima installazione coincidono; se esiste già il backup di una precedente installazione
+/// (lo stato originale del gioco) lo conserva e fa un'istantanea a parte dello stato attuale,
+/// così un aggiornamento fallito torna alla patch funzionante e non al gioco senza patch.
+fn create_patch_backup(game_dir: &Path) -> Result<(UnityPatchManifest, UnityPatchManifest), String> {
+    create_patch_backup_at(game_dir, &patch_backup_dir(game_dir)?)
+}
+
+fn create_patch_backup_at(game_dir: &Path, backup_dir: &Path) -> Result<(UnityPatchManifest, UnityPatchManifest), String> {
+    if let Some(existing) = load_patch_manifest_at(backup_dir) {
+        let attempt_dir = backup_dir.join(PATCH_ATTEMPT_DIR);
+        if attempt_dir.exists() {
+            fs::remove_dir_all(&attempt_dir).map_err(|e| format!("Errore pulizia backup precedente: {}", e))?;
+        }
+        let attempt = snapshot_patch_items(game_dir, &attempt_dir)?;
+        return Ok((existing, attempt));
+    }
+
+    let manifest = snapshot_patch_items(game_dir, backup_dir)?;
+    // Salvato subito: se l'app si chiude a metà installazione il backup resta recuperabile
+    save_patch_manifest(&manifest)?;
+    Ok((manifest.clone(), manifest))
+}
+
+/// Copia in `backup_dir/files` gli elementi della patch presenti ora nella cartella del gioco
+fn snapshot_patch_items(game_dir: &Path, backup_dir: &Path) -> Result<UnityPatchManifest, String> {
+    let files_dir = backup_dir.join("files");
+    fs::create_dir_all(&files_dir).map_err(|e| format!("Errore creazione cartella backup: {}", e))?;
+
+    let mut backed_up = Vec::new();
+    for item in PATCH_BACKUP_ITEMS {
+        let source = game_dir.join(item);
+        if !source.exists() {
+            continue;
+        }
+        let target = files_dir.join(item);
+        let copied = if source.is_dir() {
+            super::utilities::copy_dir_recursive(&source, &target)
+        } else {
+            fs::copy(&source, &target).map(|_| ())
+        };
+        copied.map_err(|e| format!("Errore backup di {}: {}", item, e))?;
+        backed_up.push(item.to_string());
+    }
+
+    Ok(UnityPatchManifest {
+        game_path: game_dir.to_string_lossy().to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        backup_dir: backup_dir.to_string_lossy().to_string(),
+        backed_up,
+        added: Vec::new(),
+        entries_before: top_level_entries(game_dir),
+    })
+}
+
+/// Rimuove ciò che l'installazione ha aggiunto, rimette gli elementi salvati ed elimina il backup.
+/// Traduzioni e configurazione dell'utente (`PATCH_PROTECTED_PATHS`) restano dove sono.
+fn restore_patch_backup(game_dir: &Path, manifest: &UnityPatchManifest) -> Result<Vec<String>, String> {
+    let mut steps = Vec::new();
+    let files_dir = Path::new(&manifest.backup_dir).join("files");
+
+    for item in manifest.added.iter().filter(|i| !i.contains(['/', '\\']) && !i.is_empty()) {
+        let path = game_dir.join(item);
+        if path.exists() {
+            remove_preserving(&path, &nested_protected(PATCH_PROTECTED_PATHS, item))
+                .map_err(|e| format!("Errore rimozione {}: {}", item, e))?;
+            steps.push(format!("✓ Rimosso: {}", item));
+        }
+    }
+
+    for item in &manifest.backed_up {
+        let saved = files_dir.join(item);
+        let target = game_dir.join(item);
+        let protected = nested_protected(PATCH_PROTECTED_PATHS, item);
+        if target.exists() {
+            remove_preserving(&target, &protected).map_err(|e| format!("Errore rimozione {}: {}", item, e))?;
+        }
+        restore_preserving(&saved, &target, &protected)
+            .map_err(|e| format!("Errore ripristino {}: {}", item, e))?;
+        steps.push(format!("✓ Ripristinato: {}", item));
+    }
+
+    if let Err(e) = fs::remove_dir_all(&manifest.backup_dir) {
+        steps.push(format!("⚠ Backup non eliminato ({}): {}", manifest.backup_dir, e));
+    }
+    Ok(steps)
+}
+
+/// Disinstalla la patch: ripristina il backup fatto prima dell'installazione
+/// o, se manca (patch installata a mano o da versioni precedenti), rimuove i file noti
+#[command]
+pub async fn uninstall_unity_autotranslator(game_path: String) -> Result<PatchStatus, String> {
+    let game_dir = Path::new(&game_path);
+    if !game_dir.exists() {
+        return Err("Cartella del gioco non trovata".to_string());
+    }
+
+    let Some(manifest) = load_patch_manifest(game_dir) else {
+        let mut status = remove_unity_patch(game_path).await?;
+        status.steps_completed.insert(0, "⚠ Nessun backup trovato: rimozione dei file noti della patch".to_string());
+        return Ok(status);
+    };
+
+    let steps = restore_patch_backup(game_dir, &manifest)?;
+    Ok(PatchStatus {
+        success: true,
+        message: format!(
+            "Patch disinstallata: {} elementi rimossi, {} ripristinati dal backup.",
+            manifest.added.len(), manifest.backed_up.len()
+        ),
+        steps_completed: steps,
+        action: PatchAction::Removed,
+        manifest: Some(manifest),
+    })
+}
+
 /// Rimuove BepInEx e XUnity da un gioco Unity
 #[command]
 pub async fn remove_unity_patch(game_path: String) -> Result<PatchStatus, String> {
@@ -2395,6 +3330,8 @@ pub async fn remove_unity_patch(game_path: String) -> Result<PatchStatus, String
             success: true,
             message: "Nessuna patch trovata da rimuovere.".to_string(),
             steps_completed: steps,
+            action: PatchAction::Unchanged,
+            manifest: None,
         });
     }
     
@@ -2402,6 +3339,8 @@ pub async fn remove_unity_patch(game_path: String) -> Result<PatchStatus, String
         success: true,
         message: format!("Patch rimossa con successo! {} elementi eliminati.", removed_count),
         steps_completed: steps,
+        action: PatchAction::Removed,
+        manifest: None,
     })
 }
 
@@ -2580,3 +3519,118 @@ pub async fn get_translation_recommendation(game_path: String, _game_name: Strin
     
     Ok(recommendation)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn failed_reinstall_rolls_back_only_the_attempt() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let game_dir = temp.path().join("game");
+        let backup_dir = temp.path().join("backup");
+        write(&game_dir.join("Game.exe"), "exe");
+
+        // Prima installazione riuscita
+        let (mut manifest, attempt) = create_patch_backup_at(&game_dir, &backup_dir).unwrap();
+        assert_eq!(attempt.backup_dir, manifest.backup_dir);
+        write(&game_dir.join("BepInEx/plugins/XUnity.dll"), "v1");
+        write(&game_dir.join("winhttp.dll"), "loader");
+        manifest.added = added_entries(&game_dir, &manifest.entries_before);
+        save_patch_manifest(&manifest).unwrap();
+
+        // Il gioco genera traduzioni e configurazione
+        write(&game_dir.join("BepInEx/Translation/it/Text.txt"), "Hello=Ciao");
+        write(&game_dir.join("BepInEx/config/AutoTranslatorConfig.ini"), "Language=it");
+
+        // Reinstallazione che fallisce a metà
+        let (manifest, mut attempt) = create_patch_backup_at(&game_dir, &backup_dir).unwrap();
+        assert_ne!(attempt.backup_dir, manifest.backup_dir);
+        write(&game_dir.join("BepInEx/plugins/XUnity.dll"), "v2-partial");
+        write(&game_dir.join("doorstop_config.ini"), "partial");
+        attempt.added = added_entries(&game_dir, &attempt.entries_before);
+        restore_patch_backup(&game_dir, &attempt).unwrap();
+
+        // Torna la patch funzionante, non il gioco senza patch
+        assert_eq!(fs::read_to_string(game_dir.join("BepInEx/plugins/XUnity.dll")).unwrap(), "v1");
+        assert!(game_dir.join("winhttp.dll").exists());
+        assert!(!game_dir.join("doorstop_config.ini").exists());
+        assert_eq!(fs::read_to_string(game_dir.join("BepInEx/Translation/it/Text.txt")).unwrap(), "Hello=Ciao");
+        assert!(game_dir.join("BepInEx/config/AutoTranslatorConfig.ini").exists());
+
+        // Il backup della prima installazione resta per la disinstallazione
+        assert!(load_patch_manifest_at(&backup_dir).is_some());
+        assert!(!backup_dir.join(PATCH_ATTEMPT_DIR).exists());
+    }
+
+    #[test]
+    fn uninstall_keeps_user_translations() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let game_dir = temp.path().join("game");
+        let backup_dir = temp.path().join("backup");
+        write(&game_dir.join("Game.exe"), "exe");
+
+        let (mut manifest, _) = create_patch_backup_at(&game_dir, &backup_dir).unwrap();
+        write(&game_dir.join("BepInEx/core/BepInEx.dll"), "core");
+        write(&game_dir.join("BepInEx/Translation/it/Text.txt"), "Hello=Ciao");
+        manifest.added = added_entries(&game_dir, &manifest.entries_before);
+
+        restore_patch_backup(&game_dir, &manifest).unwrap();
+        assert!(!game_dir.join("BepInEx/core").exists());
+        assert!(game_dir.join("BepInEx/Translation/it/Text.txt").exists());
+        assert!(game_dir.join("Game.exe").exists());
+    }
+
+    /// Scrive un PE minimo con il solo campo Machine impostato
+    fn write_pe(path: &Path, machine: u16) {
+        let mut pe = vec![0u8; 512];
+        pe[0] = b'M';
+        pe[1] = b'Z';
+        pe[0x3C] = 0x80;
+        pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+        pe[0x84..0x86].copy_from_slice(&machine.to_le_bytes());
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, pe).unwrap();
+    }
+
+    #[test]
+    fn installed_bepinex_reports_loader_architecture() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let game_dir = temp.path();
+        assert!(detect_installed_bepinex(game_dir).is_none());
+
+        write(&game_dir.join("BepInEx/core/BepInEx.dll"), "core");
+        write_pe(&game_dir.join("winhttp.dll"), 0x14c);
+        let installed = detect_installed_bepinex(game_dir).unwrap();
+        assert_eq!(installed.runtime, UnityRuntime::Mono);
+        assert_eq!(installed.is_64bit, Some(false));
+        assert_eq!(installed.name, "BepInEx 5 x86");
+
+        write_pe(&game_dir.join("winhttp.dll"), 0x8664);
+        write(&game_dir.join("BepInEx/core/BepInEx.Unity.IL2CPP.dll"), "core");
+        let installed = detect_installed_bepinex(game_dir).unwrap();
+        assert_eq!(installed.runtime, UnityRuntime::Il2Cpp);
+        assert_eq!(installed.name, "BepInEx 6 IL2CPP x64");
+    }
+
+    #[test]
+    fn installed_bepinex_mismatch_checks_runtime_and_architecture() {
+        let installed = |runtime, is_64bit| InstalledBepInEx { runtime, is_64bit, name: String::new() };
+
+        assert!(installed_bepinex_mismatch(&installed(UnityRuntime::Mono, Some(true)), UnityRuntime::Mono, true).is_none());
+
+        let wrong_runtime = installed_bepinex_mismatch(&installed(UnityRuntime::Mono, Some(true)), UnityRuntime::Il2Cpp, true);
+        assert!(wrong_runtime.unwrap().contains("IL2CPP"));
+
+        // Stesso runtime ma loader x86 su gioco x64: il proxy non verrebbe caricato
+        let wrong_arch = installed_bepinex_mismatch(&installed(UnityRuntime::Il2Cpp, Some(false)), UnityRuntime::Il2Cpp, true);
+        assert!(wrong_arch.unwrap().contains("x86"));
+
+        assert!(installed_bepinex_mismatch(&installed(UnityRuntime::Mono, None), UnityRuntime::Mono, false).is_some());
+    }
+}
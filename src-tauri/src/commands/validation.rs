@@ -1,6 +1,7 @@
 // Comandi Tauri per validazione input profili
 use crate::commands::profiles::ProfileManagerState;
-use crate::profiles::validation::{ProfileNameValidationResult, PasswordValidationResult, ValidationConfig};
+use crate::profiles::validation::{ProfileNameValidationResult, PasswordValidationResult, SanitizedInput, ValidationConfig};
+use crate::profiles::password_strength::DEFAULT_PASSPHRASE_WORDS;
 use serde::Serialize;
 use tauri::{command, State};
 
@@ -68,15 +69,15 @@ pub async fn validate_unique_profile_name(
     }
 }
 
-/// Sanitizza input generico
+/// Sanitizza input generico (Unicode NFC), riportando i caratteri rimossi
 #[command]
 pub async fn sanitize_input(
     input: String,
     profile_state: State<'_, ProfileManagerState>,
-) -> Result<ValidationResponse<String>, String> {
+) -> Result<ValidationResponse<SanitizedInput>, String> {
     let manager = profile_state.manager.lock().await;
     
-    let sanitized = manager.sanitize_input(&input);
+    let sanitized = manager.sanitize_input_detailed(&input);
     Ok(ValidationResponse::success(sanitized))
 }
 
@@ -99,8 +100,10 @@ pub async fn update_validation_config(
 ) -> Result<ValidationResponse<bool>, String> {
     let mut manager = profile_state.manager.lock().await;
     
-    manager.update_validation_config(config);
-    Ok(ValidationResponse::success(true))
+    match manager.update_validation_config(config).await {
+        Ok(_) => Ok(ValidationResponse::success(true)),
+        Err(err) => Ok(ValidationResponse::error(err.to_string())),
+    }
 }
 
 /// Valida input completo per creazione profilo
@@ -141,47 +144,24 @@ pub struct ProfileCreationValidationResult {
     pub is_valid: bool,
 }
 
-/// Genera suggerimenti per password sicura
+/// Genera passphrase sicure che rispettano la policy di validazione configurata
 #[command]
 pub async fn generate_password_suggestions(
-    current_password: String,
+    count: Option<usize>,
+    word_count: Option<usize>,
     profile_state: State<'_, ProfileManagerState>,
 ) -> Result<ValidationResponse<Vec<String>>, String> {
     let manager = profile_state.manager.lock().await;
-    
-    let validation = manager.validate_password(&current_password);
-    let mut suggestions = validation.suggestions;
-    
-    // Aggiungi suggerimenti specifici basati sulla password corrente
-    if current_password.len() < 12 {
-        suggestions.push("Considera di usare almeno 12 caratteri per maggiore sicurezza".to_string());
-    }
-    
-    if !current_password.chars().any(|c| c.is_uppercase()) {
-        suggestions.push("Aggiungi lettere maiuscole (A-Z)".to_string());
-    }
-    
-    if !current_password.chars().any(|c| c.is_lowercase()) {
-        suggestions.push("Aggiungi lettere minuscole (a-z)".to_string());
-    }
-    
-    if !current_password.chars().any(|c| c.is_numeric()) {
-        suggestions.push("Aggiungi numeri (0-9)".to_string());
-    }
-    
-    if !current_password.chars().any(|c| "!@#$%^&*()_+-=[]{}|;:,.<>?".contains(c)) {
-        suggestions.push("Aggiungi caratteri speciali (!@#$%^&*)".to_string());
-    }
-    
-    // Suggerimenti generali
-    if suggestions.is_empty() {
-        suggestions.push("La tua password è già molto sicura!".to_string());
-    } else {
-        suggestions.push("Evita informazioni personali come nome, data di nascita o parole comuni".to_string());
-        suggestions.push("Considera l'uso di una passphrase con parole casuali separate da simboli".to_string());
+
+    let count = count.unwrap_or(3).clamp(1, 10);
+    let word_count = word_count.unwrap_or(DEFAULT_PASSPHRASE_WORDS).clamp(3, 8);
+    let passphrases = manager.generate_passphrases(count, word_count);
+
+    if passphrases.is_empty() {
+        return Ok(ValidationResponse::error("Impossibile generare passphrase conformi alla policy".to_string()));
     }
-    
-    Ok(ValidationResponse::success(suggestions))
+
+    Ok(ValidationResponse::success(passphrases))
 }
 
 /// Controlla forza password in tempo reale
@@ -193,6 +173,7 @@ pub async fn check_password_strength_realtime(
     let manager = profile_state.manager.lock().await;
     
     let validation = manager.validate_password(&password);
+    let estimate = manager.estimate_password_strength(&password);
     
     let is_acceptable = validation.strength_level.is_acceptable();
     
@@ -205,6 +186,10 @@ pub async fn check_password_strength_realtime(
         } else {
             validation.errors
         },
+        entropy_bits: estimate.entropy_bits,
+        crack_time_seconds: estimate.crack_time_seconds,
+        crack_time_display: estimate.crack_time_display,
+        hints: estimate.hints,
     };
     
     Ok(ValidationResponse::success(info))
@@ -217,4 +202,11 @@ pub struct PasswordStrengthInfo {
     pub level: crate::profiles::validation::PasswordStrength,
     pub is_acceptable: bool,
     pub feedback: Vec<String>,
+    /// Entropia stimata in bit
+    pub entropy_bits: f64,
+    /// Tempo di crack offline stimato (secondi)
+    pub crack_time_seconds: f64,
+    pub crack_time_display: String,
+    /// Suggerimenti specifici (lunghezza, pattern comuni, parole del dizionario)
+    pub hints: Vec<String>,
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 use std::path::Path;
+use serde::Serialize;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize)]
 #[allow(dead_code)]
 pub enum GameEngine {
     Unity,
@@ -88,6 +89,195 @@ impl GameEngine {
     }
 }
 
+/// Affidabilità di un rilevamento engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DetectionConfidence {
+    /// Solo euristica sul titolo
+    Low,
+    /// Struttura delle cartelle compatibile ma nessun file firma
+    Medium,
+    /// File firma dell'engine trovato su disco
+    High,
+}
+
+/// Risultato del rilevamento con l'artefatto che lo giustifica
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EngineDetection {
+    pub engine: GameEngine,
+    /// Nome leggibile (può indicare engine senza variante dedicata, es. "RAGE Engine")
+    pub engine_name: String,
+    pub confidence: DetectionConfidence,
+    /// File o cartella che ha determinato il rilevamento (relativo al gioco)
+    pub artifact: Option<String>,
+    /// Backend di scripting, se noto (es. "IL2CPP" o "Mono" per Unity)
+    pub scripting_backend: Option<String>,
+}
+
+impl EngineDetection {
+    fn new(engine: GameEngine, confidence: DetectionConfidence, artifact: impl Into<String>) -> Self {
+        Self { engine_name: engine.as_str().to_string(), engine, confidence, artifact: Some(artifact.into()), scripting_backend: None }
+    }
+
+    fn with_backend(mut self, backend: &str) -> Self {
+        self.scripting_backend = Some(backend.to_string());
+        self
+    }
+
+    pub fn unknown() -> Self {
+        Self {
+            engine: GameEngine::Unknown,
+            engine_name: GameEngine::Unknown.as_str().to_string(),
+            confidence: DetectionConfidence::Low,
+            artifact: None,
+            scripting_backend: None,
+        }
+    }
+}
+
+/// Nomi delle voci (file e cartelle) nella radice del gioco
+fn root_entries(path: &Path) -> Vec<(String, bool)> {
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten()
+            .map(|e| (e.file_name().to_string_lossy().to_string(), e.path().is_dir()))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// Rileva l'engine ispezionando i file firma su disco.
+/// Se nessun file firma è presente ripiega sulle euristiche strutturali di `detect_engine`.
+pub fn detect_engine_from_files(game_path: &Path) -> EngineDetection {
+    if !game_path.is_dir() {
+        return EngineDetection::unknown();
+    }
+    let entries = root_entries(game_path);
+    let data_dir = entries.iter()
+        .find(|(name, is_dir)| *is_dir && name.ends_with("_Data"))
+        .map(|(name, _)| name.clone());
+    
+    // Unity IL2CPP: GameAssembly.dll accanto a UnityPlayer.dll o a una cartella _Data
+    if game_path.join("GameAssembly.dll").exists()
+        && (game_path.join("UnityPlayer.dll").exists() || data_dir.is_some())
+    {
+        return EngineDetection::new(GameEngine::Unity, DetectionConfidence::High, "GameAssembly.dll")
+            .with_backend("IL2CPP");
+    }
+    
+    // Unity Mono
+    if game_path.join("UnityPlayer.dll").exists() {
+        let backend = data_dir.as_ref()
+            .filter(|d| game_path.join(d).join("Managed").is_dir())
+            .map(|_| "Mono");
+        let detection = EngineDetection::new(GameEngine::Unity, DetectionConfidence::High, "UnityPlayer.dll");
+        return match backend {
+            Some(b) => detection.with_backend(b),
+            None => detection,
+        };
+    }
+    if let Some(ref data) = data_dir {
+        let data_path = game_path.join(data);
+        if data_path.join("globalgamemanagers").exists() || data_path.join("Managed").is_dir() {
+            let detection = EngineDetection::new(GameEngine::Unity, DetectionConfidence::High, format!("{}/", data));
+            return if data_path.join("il2cpp_data").is_dir() {
+                detection.with_backend("IL2CPP")
+            } else if data_path.join("Managed").is_dir() {
+                detection.with_backend("Mono")
+            } else {
+                detection
+            };
+        }
+    }
+    
+    // Unreal: eseguibile *-Win64-Shipping.exe (radice o <Progetto>/Binaries/Win64)
+    if let Some((name, _)) = entries.iter().find(|(name, is_dir)| !*is_dir && name.ends_with("-Win64-Shipping.exe")) {
+        return EngineDetection::new(GameEngine::Unreal, DetectionConfidence::High, name.clone());
+    }
+    for (dir, _) in entries.iter().filter(|(_, is_dir)| *is_dir) {
+        let binaries = game_path.join(dir).join("Binaries").join("Win64");
+        if let Some((exe, _)) = root_entries(&binaries).into_iter()
+            .find(|(name, is_dir)| !*is_dir && name.ends_with("-Win64-Shipping.exe"))
+        {
+            return EngineDetection::new(GameEngine::Unreal, DetectionConfidence::High, format!("{}/Binaries/Win64/{}", dir, exe));
+        }
+    }
+    if game_path.join("Engine").join("Binaries").is_dir() {
+        return EngineDetection::new(GameEngine::Unreal, DetectionConfidence::High, "Engine/Binaries/");
+    }
+    
+    // GameMaker
+    if game_path.join("data.win").exists() {
+        return EngineDetection::new(GameEngine::GameMaker, DetectionConfidence::High, "data.win");
+    }
+    
+    // Godot: .pck, project.godot o pck incorporato nell'eseguibile (export template)
+    if let Some((name, _)) = entries.iter().find(|(name, is_dir)| !*is_dir && name.to_lowercase().ends_with(".pck")) {
+        return EngineDetection::new(GameEngine::Godot, DetectionConfidence::High, name.clone());
+    }
+    if game_path.join("project.godot").exists() {
+        return EngineDetection::new(GameEngine::Godot, DetectionConfidence::High, "project.godot");
+    }
+    if let Some((name, _)) = entries.iter().find(|(name, is_dir)| {
+        !*is_dir && is_executable_name(name) && has_embedded_godot_pck(&game_path.join(name))
+    }) {
+        return EngineDetection::new(GameEngine::Godot, DetectionConfidence::High, format!("{} (pck incorporato)", name));
+    }
+    
+    // Nessun file firma: euristiche strutturali esistenti
+    match detect_engine(game_path) {
+        GameEngine::Unknown => EngineDetection::unknown(),
+        engine => EngineDetection {
+            engine_name: engine.as_str().to_string(),
+            engine,
+            confidence: DetectionConfidence::Medium,
+            artifact: None,
+            scripting_backend: None,
+        },
+    }
+}
+
+/// Rilevamento completo: file su disco se il percorso è disponibile, titolo solo come ultima risorsa
+pub fn detect_engine_with_evidence(name: &str, path: Option<&Path>) -> EngineDetection {
+    if let Some(p) = path.filter(|p| p.exists()) {
+        let detected = detect_engine_from_files(p);
+        if detected.engine != GameEngine::Unknown {
+            return detected;
+        }
+    }
+    
+    match detect_engine_by_name(name) {
+        Some(engine_name) => EngineDetection {
+            engine: engine_from_name(&engine_name),
+            engine_name,
+            confidence: DetectionConfidence::Low,
+            artifact: Some(format!("title:{}", name)),
+            scripting_backend: None,
+        },
+        None => EngineDetection::unknown(),
+    }
+}
+
+/// Converte il nome restituito da `detect_engine_by_name` nella variante corrispondente
+fn engine_from_name(name: &str) -> GameEngine {
+    let lower = name.to_lowercase();
+    ALL_ENGINES.iter()
+        .find(|e| e.as_str().to_lowercase() == lower)
+        .cloned()
+        .unwrap_or(GameEngine::Unknown)
+}
+
+const ALL_ENGINES: &[GameEngine] = &[
+    GameEngine::Unity, GameEngine::Unreal, GameEngine::Godot, GameEngine::RPGMaker,
+    GameEngine::RenPy, GameEngine::GameMaker, GameEngine::Source, GameEngine::Source2,
+    GameEngine::CryEngine, GameEngine::Frostbite, GameEngine::REDengine, GameEngine::Creation,
+    GameEngine::IdTech, GameEngine::Telltale, GameEngine::AdobeAIR, GameEngine::Construct,
+    GameEngine::Love2D, GameEngine::Phaser, GameEngine::Kirikiri, GameEngine::NScripter,
+    GameEngine::Wolf, GameEngine::Clausewitz, GameEngine::Anvil, GameEngine::Decima,
+    GameEngine::REEngine, GameEngine::FOXEngine, GameEngine::IWEngine, GameEngine::Snowdrop,
+    GameEngine::Cocos2d, GameEngine::Defold, GameEngine::Haxe, GameEngine::MonoGame,
+    GameEngine::XNA, GameEngine::LibGDX, GameEngine::LWJGL, GameEngine::Electron,
+    GameEngine::NWjs,
+];
+
 pub fn detect_engine(game_path: &Path) -> GameEngine {
     if !game_path.exists() {
         return GameEngine::Unknown;
@@ -356,9 +546,38 @@ fn is_godot(path: &Path) -> bool {
         }
     }
     
+    // Export template con pck incorporato (Godot 3 e 4)
+    if let Ok(entries) = std::fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if is_executable_name(&name) && has_embedded_godot_pck(&entry.path()) {
+                return true;
+            }
+        }
+    }
+    
     false
 }
 
+fn is_executable_name(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower.ends_with(".exe") || lower.ends_with(".x86_64") || lower.ends_with(".x86_32") || lower.ends_with(".arm64")
+}
+
+/// Un export template Godot con pck incorporato termina con [dimensione pck: u64]["GDPC"]
+pub fn has_embedded_godot_pck(exe_path: &Path) -> bool {
+    use std::io::{Read, Seek, SeekFrom};
+    
+    let mut file = match std::fs::File::open(exe_path) {
+        Ok(f) => f,
+        Err(_) => return false,
+    };
+    let mut magic = [0u8; 4];
+    file.seek(SeekFrom::End(-4)).is_ok()
+        && file.read_exact(&mut magic).is_ok()
+        && &magic == b"GDPC"
+}
+
 fn is_rpg_maker(path: &Path) -> bool {
     // RPG Maker MV/MZ
     if path.join("www").exists() && path.join("www/data/System.json").exists() {
@@ -928,18 +1147,8 @@ fn is_defold(path: &Path) -> bool {
 }
 
 pub fn detect_engine_smart(name: &str, path: Option<&Path>) -> String {
-    // 1. File system detection
-    if let Some(p) = path {
-        if p.exists() {
-             let detected = detect_engine(p);
-             if detected != GameEngine::Unknown {
-                 return detected.as_str().to_string();
-             }
-        }
-    }
-    
-    // 2. Name detection
-    detect_engine_by_name(name).unwrap_or_else(|| "Unknown".to_string())
+    // File system prima, titolo solo se il percorso non è disponibile o non rivela nulla
+    detect_engine_with_evidence(name, path).engine_name
 }
 
 pub fn detect_engine_by_name(name: &str) -> Option<String> {
@@ -0,0 +1,401 @@
+// 🌐 Helper HTTP condivisi: client con timeout configurabili, rate limiter per endpoint
+// e retry con backoff
+// I timeout vengono dalle preferenze (`network`), con override per store ("steam") o
+// per endpoint ("steam_owned_games"). Si riprova solo su 429, 5xx, timeout ed errori
+// di connessione: gli altri 4xx (credenziali non valide, risorsa inesistente) vengono
+// restituiti subito al chiamante.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use rand::Rng;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use crate::commands::preferences::{load_network_preferences, NetworkPreferences};
+
+const USER_AGENT: &str = "GameStringer/1.0";
+
+/// Timeout di rete correnti, caricati dalle preferenze al primo uso
+static NETWORK: Lazy<RwLock<NetworkPreferences>> = Lazy::new(|| RwLock::new(load_network_preferences()));
+
+/// Client già costruiti per endpoint (reqwest::Client è un Arc, il clone è economico)
+static CLIENTS: Lazy<Mutex<HashMap<String, Client>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Timeout effettivi di un endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    pub connect: Duration,
+    pub read: Duration,
+}
+
+/// Risolve i timeout: override dell'endpoint, poi dello store (prefisso prima di `_`), poi globali
+fn resolve_timeouts(network: &NetworkPreferences, endpoint: &str) -> Timeouts {
+    let store = endpoint.split('_').next().unwrap_or(endpoint);
+    let exact = network.endpoint_overrides.get(endpoint);
+    let by_store = network.endpoint_overrides.get(store);
+
+    let connect = exact.and_then(|t| t.connect_timeout_secs)
+        .or_else(|| by_store.and_then(|t| t.connect_timeout_secs))
+        .unwrap_or(network.connect_timeout_secs);
+    let read = exact.and_then(|t| t.read_timeout_secs)
+        .or_else(|| by_store.and_then(|t| t.read_timeout_secs))
+        .unwrap_or(network.read_timeout_secs);
+
+    Timeouts {
+        connect: Duration::from_secs(connect),
+        read: Duration::from_secs(read),
+    }
+}
+
+/// Timeout configurati per un endpoint
+pub fn timeouts_for(endpoint: &str) -> Timeouts {
+    resolve_timeouts(&NETWORK.read(), endpoint)
+}
+
+/// Client HTTP per un endpoint, con i timeout dalle preferenze
+pub fn client_for(endpoint: &str) -> Client {
+    let mut clients = CLIENTS.lock();
+    if let Some(client) = clients.get(endpoint) {
+        return client.clone();
+    }
+
+    let timeouts = timeouts_for(endpoint);
+    let client = Client::builder()
+        .connect_timeout(timeouts.connect)
+        .timeout(timeouts.read)
+        .user_agent(USER_AGENT)
+        .build()
+        .unwrap_or_else(|e| {
+            warn!("[HTTP] ⚠️ Errore creazione client per {}: {}", endpoint, e);
+            Client::new()
+        });
+    clients.insert(endpoint.to_string(), client.clone());
+    client
+}
+
+/// Aggiorna i timeout dopo il salvataggio delle preferenze; i client vengono ricreati al prossimo uso
+pub fn apply_network_preferences(network: &NetworkPreferences) {
+    let mut current = NETWORK.write();
+    if *current == *network {
+        return;
+    }
+    *current = network.clone();
+    CLIENTS.lock().clear();
+    info!("[HTTP] 🔧 Timeout di rete aggiornati (connect {}s, read {}s, {} override)",
+          network.connect_timeout_secs, network.read_timeout_secs, network.endpoint_overrides.len());
+}
+
+/// Finestra del limite di burst e di quello al minuto, in millisecondi
+const BURST_WINDOW_MS: u64 = 1000;
+const MINUTE_WINDOW_MS: u64 = 60 * 1000;
+
+/// Rate limiter a finestre scorrevoli, con storico separato per endpoint:
+/// al massimo `max_per_second` richieste al secondo e `max_per_minute` al minuto
+pub struct RateLimiter {
+    /// Nome usato nei log (es. "Steam", "Epic")
+    name: &'static str,
+    requests: Mutex<HashMap<String, Vec<u64>>>,
+    max_per_second: usize,
+    max_per_minute: usize,
+}
+
+impl RateLimiter {
+    pub fn new(name: &'static str, max_per_second: usize, max_per_minute: usize) -> Self {
+        Self {
+            name,
+            requests: Mutex::new(HashMap::new()),
+            max_per_second: max_per_second.max(1),
+            max_per_minute: max_per_minute.max(1),
+        }
+    }
+
+    fn now_ms() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+
+    /// Millisecondi da attendere prima che lo storico consenta una nuova richiesta (0 = subito)
+    fn delay_for(&self, history: &[u64], now: u64) -> u64 {
+        let recent: Vec<u64> = history.iter()
+            .copied()
+            .filter(|&timestamp| now.saturating_sub(timestamp) < BURST_WINDOW_MS)
+            .collect();
+        if recent.len() >= self.max_per_second {
+            let oldest = recent.iter().min().copied().unwrap_or(now);
+            return BURST_WINDOW_MS.saturating_sub(now.saturating_sub(oldest)).max(1);
+        }
+
+        let in_minute: Vec<u64> = history.iter()
+            .copied()
+            .filter(|&timestamp| now.saturating_sub(timestamp) < MINUTE_WINDOW_MS)
+            .collect();
+        if in_minute.len() >= self.max_per_minute {
+            let oldest = in_minute.iter().min().copied().unwrap_or(now);
+            return MINUTE_WINDOW_MS.saturating_sub(now.saturating_sub(oldest)).max(1);
+        }
+
+        0
+    }
+
+    /// Registra la richiesta e restituisce 0 se consentita, altrimenti i millisecondi da attendere
+    pub fn try_acquire(&self, endpoint: &str) -> u64 {
+        self.try_acquire_at(endpoint, Self::now_ms())
+    }
+
+    fn try_acquire_at(&self, endpoint: &str, now: u64) -> u64 {
+        let mut requests = self.requests.lock();
+        let history = requests.entry(endpoint.to_string()).or_default();
+        history.retain(|&timestamp| now.saturating_sub(timestamp) < MINUTE_WINDOW_MS);
+
+        let delay = self.delay_for(history, now);
+        if delay == 0 {
+            history.push(now);
+        }
+        delay
+    }
+
+    /// Come `try_acquire`, ma restituisce solo se la richiesta è consentita (e la registra)
+    pub fn is_request_allowed(&self, endpoint: &str) -> bool {
+        let delay = self.try_acquire(endpoint);
+        if delay > 0 {
+            warn!("[RateLimit] {} - limite superato per {} (attesa {}ms)", self.name, endpoint, delay);
+        }
+        delay == 0
+    }
+
+    /// Attende, senza registrare una richiesta, finché lo storico dell'endpoint non la consente
+    pub async fn wait_for_next_request(&self, endpoint: &str) {
+        let delay = {
+            let requests = self.requests.lock();
+            requests.get(endpoint)
+                .map(|history| self.delay_for(history, Self::now_ms()))
+                .unwrap_or(0)
+        };
+        if delay > 0 {
+            info!("[RateLimit] {} - attesa {}ms prima della prossima richiesta a {}", self.name, delay, endpoint);
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+    }
+
+    /// Attende finché la richiesta non è consentita e la registra
+    pub async fn acquire(&self, endpoint: &str) {
+        loop {
+            let delay = self.try_acquire(endpoint);
+            if delay == 0 {
+                return;
+            }
+            debug!("[RateLimit] {} - attesa {}ms prima della prossima richiesta a {}", self.name, delay, endpoint);
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+        }
+    }
+}
+
+/// Configurazione dei tentativi
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Tentativi totali (incluso il primo)
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self { max_attempts: max_attempts.max(1), base_delay, max_delay }
+    }
+
+    /// Backoff esponenziale con jitter: base * 2^tentativo (limitato a max), tra 50% e 100%
+    pub fn backoff_delay(&self, attempt: u32) -> Duration {
+        let base_ms = self.base_delay.as_millis() as u64;
+        let exp = base_ms
+            .saturating_mul(1u64 << attempt.min(16))
+            .min(self.max_delay.as_millis() as u64);
+        let jitter = rand::thread_rng().gen_range(0..=exp / 2);
+        Duration::from_millis(exp / 2 + jitter)
+    }
+}
+
+/// Errore finale dopo l'esaurimento dei tentativi (o errore di rete non ripetibile)
+#[derive(Debug)]
+pub struct HttpRetryError {
+    pub endpoint: String,
+    pub attempts: u32,
+    /// Ultimo status ricevuto, se il server ha risposto
+    pub status: Option<StatusCode>,
+    /// Ultimo errore di rete (senza URL, che può contenere API key)
+    source: Option<reqwest::Error>,
+}
+
+impl HttpRetryError {
+    pub fn is_timeout(&self) -> bool {
+        self.source.as_ref().is_some_and(|e| e.is_timeout())
+    }
+
+    pub fn is_connect(&self) -> bool {
+        self.source.as_ref().is_some_and(|e| e.is_connect())
+    }
+}
+
+impl fmt::Display for HttpRetryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.status, &self.source) {
+            (Some(status), _) => write!(f, "{}: HTTP {}", self.endpoint, status)?,
+            (None, Some(e)) => write!(f, "{}: {}", self.endpoint, e)?,
+            (None, None) => write!(f, "{}: errore sconosciuto", self.endpoint)?,
+        }
+        write!(f, " (dopo {} tentativi)", self.attempts)
+    }
+}
+
+impl std::error::Error for HttpRetryError {}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect()
+}
+
+/// Delay indicato dal server nell'header Retry-After (solo formato in secondi)
+fn retry_after(response: &Response) -> Option<Duration> {
+    response.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// GET con la policy di default
+pub async fn get_with_retry(client: &Client, url: &str, endpoint: &str) -> Result<Response, HttpRetryError> {
+    send_with_retry(|| async { client.get(url) }, endpoint, &RetryPolicy::default()).await
+}
+
+/// Invia la richiesta prodotta da `prepare`, ricreata a ogni tentativo (anche POST o con rate limiter).
+/// Restituisce la risposta per successi e per errori non ripetibili (es. 401/403/404):
+/// lo status va comunque controllato dal chiamante.
+pub async fn send_with_retry<F, Fut>(prepare: F, endpoint: &str, policy: &RetryPolicy) -> Result<Response, HttpRetryError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = RequestBuilder>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+
+        let (status, source, server_delay) = match prepare().await.send().await {
+            Ok(response) => {
+                let status = response.status();
+                if !is_retryable_status(status) {
+                    return Ok(response);
+                }
+                let delay = retry_after(&response);
+                (Some(status), None, delay)
+            }
+            Err(e) => {
+                let retryable = is_retryable_error(&e);
+                let e = e.without_url();
+                if !retryable {
+                    return Err(HttpRetryError { endpoint: endpoint.to_string(), attempts: attempt, status: None, source: Some(e) });
+                }
+                (None, Some(e), None)
+            }
+        };
+
+        let error = HttpRetryError { endpoint: endpoint.to_string(), attempts: attempt, status, source };
+        if attempt >= max_attempts {
+            return Err(error);
+        }
+
+        let delay = server_delay
+            .map(|d| d.min(policy.max_delay))
+            .unwrap_or_else(|| policy.backoff_delay(attempt - 1));
+        warn!("[HTTP] ⚠️ {} - nuovo tentativo tra {}ms ({}/{})",
+              error, delay.as_millis(), attempt, max_attempts);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_enforces_burst_and_minute_windows() {
+        let limiter = RateLimiter::new("test", 2, 3);
+        let start = 1_000_000;
+
+        assert_eq!(limiter.try_acquire_at("a", start), 0);
+        assert_eq!(limiter.try_acquire_at("a", start + 100), 0);
+        // Terza richiesta nello stesso secondo: attendere la scadenza della più vecchia
+        assert_eq!(limiter.try_acquire_at("a", start + 200), 800);
+        // Gli endpoint hanno storici separati
+        assert_eq!(limiter.try_acquire_at("b", start + 200), 0);
+
+        assert_eq!(limiter.try_acquire_at("a", start + 1_000), 0);
+        // Limite al minuto raggiunto (3): si libera 60s dopo la prima richiesta
+        assert_eq!(limiter.try_acquire_at("a", start + 2_000), 58_000);
+        assert_eq!(limiter.try_acquire_at("a", start + 60_000), 0);
+    }
+
+    /// Range ammesso per il delay: [exp/2, exp] con exp = min(base * 2^tentativo, max)
+    fn assert_delay_between(policy: &RetryPolicy, attempt: u32, min_ms: u64, max_ms: u64) {
+        for _ in 0..50 {
+            let delay = policy.backoff_delay(attempt).as_millis() as u64;
+            assert!((min_ms..=max_ms).contains(&delay), "tentativo {}: {}ms fuori da {}..={}", attempt, delay, min_ms, max_ms);
+        }
+    }
+
+    #[test]
+    fn backoff_delay_grows_exponentially() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_secs(10));
+        assert_delay_between(&policy, 0, 50, 100);
+        assert_delay_between(&policy, 1, 100, 200);
+        assert_delay_between(&policy, 2, 200, 400);
+        assert_delay_between(&policy, 3, 400, 800);
+    }
+
+    #[test]
+    fn backoff_delay_is_capped_at_max_delay() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_secs(2));
+        assert_delay_between(&policy, 5, 1_000, 2_000);
+        // Tentativi enormi non vanno in overflow
+        assert_delay_between(&policy, 64, 1_000, 2_000);
+        assert_delay_between(&policy, u32::MAX, 1_000, 2_000);
+
+        let zero = RetryPolicy::new(3, Duration::ZERO, Duration::from_secs(1));
+        assert_eq!(zero.backoff_delay(4), Duration::ZERO);
+    }
+
+    #[test]
+    fn only_rate_limit_and_server_errors_are_retryable() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(StatusCode::GATEWAY_TIMEOUT));
+
+        assert!(!is_retryable_status(StatusCode::OK));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable_status(StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable_status(StatusCode::FORBIDDEN));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+}
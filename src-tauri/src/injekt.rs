@@ -1,22 +1,29 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::error::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
-use winapi::shared::minwindef::{FALSE, LPVOID};
+use winapi::shared::minwindef::{DWORD, FALSE, LPVOID};
 use winapi::um::handleapi::CloseHandle;
-use winapi::um::memoryapi::{ReadProcessMemory, WriteProcessMemory, VirtualQueryEx};
-use winapi::um::processthreadsapi::OpenProcess;
+use winapi::um::memoryapi::{ReadProcessMemory, WriteProcessMemory, VirtualProtectEx, VirtualQueryEx};
+use winapi::um::processthreadsapi::{FlushInstructionCache, GetCurrentProcess, OpenProcess};
+use winapi::um::wow64apiset::IsWow64Process;
 
-use winapi::um::winnt::{HANDLE, MEM_COMMIT, PAGE_EXECUTE_READWRITE, PROCESS_ALL_ACCESS, MEMORY_BASIC_INFORMATION};
+use winapi::um::winnt::{
+    HANDLE, MEM_COMMIT, PAGE_EXECUTE_READWRITE, PROCESS_ALL_ACCESS, PROCESS_QUERY_LIMITED_INFORMATION,
+    PROCESS_VM_OPERATION, PROCESS_VM_READ, PROCESS_VM_WRITE, MEMORY_BASIC_INFORMATION,
+};
 
 use winapi::um::tlhelp32::{CreateToolhelp32Snapshot, Module32First, Module32Next, MODULEENTRY32, TH32CS_SNAPMODULE};
 use crate::anti_cheat::AntiCheatManager;
 // use crate::performance_optimizer::{PerformanceOptimizer, OptimizationConfig, PerformanceMetrics}; // Rimosso per cleanup warning
 
 use crate::process_utils::is_process_running;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
 
 /// Wrapper thread-safe per HANDLE di Windows
 #[derive(Debug)]
@@ -65,6 +72,772 @@ const MAX_HOOK_FAILURES: u32 = 10;
 #[allow(dead_code)] // Dimensione validazione memoria - essenziale per sicurezza
 const MEMORY_VALIDATION_SIZE: usize = 4096;
 
+/// Architettura di un processo o del payload di injection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessArchitecture {
+    X86,
+    X64,
+}
+
+impl std::fmt::Display for ProcessArchitecture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessArchitecture::X86 => write!(f, "x86"),
+            ProcessArchitecture::X64 => write!(f, "x64"),
+        }
+    }
+}
+
+impl ProcessArchitecture {
+    /// Architettura del payload iniettato (coincide con quella di GameStringer)
+    pub fn payload() -> Self {
+        if cfg!(target_pointer_width = "64") {
+            ProcessArchitecture::X64
+        } else {
+            ProcessArchitecture::X86
+        }
+    }
+}
+
+/// Errore restituito quando processo target e payload hanno architetture diverse
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchitectureMismatch {
+    pub process_id: u32,
+    pub process_arch: ProcessArchitecture,
+    pub payload_arch: ProcessArchitecture,
+}
+
+impl std::fmt::Display for ArchitectureMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ArchitectureMismatch: il processo {} è {} ma il payload di injection è {}",
+            self.process_id, self.process_arch, self.payload_arch
+        )
+    }
+}
+
+impl Error for ArchitectureMismatch {}
+
+/// Restituisce true se il processo gira sotto WOW64 (32-bit su Windows 64-bit)
+fn is_wow64(handle: HANDLE) -> Result<bool, String> {
+    let mut wow64 = FALSE;
+    // SAFETY: handle valido aperto con almeno PROCESS_QUERY_LIMITED_INFORMATION
+    let ok = unsafe { IsWow64Process(handle, &mut wow64) };
+    if ok == 0 {
+        return Err("IsWow64Process fallita".to_string());
+    }
+    Ok(wow64 != FALSE)
+}
+
+/// Rileva l'architettura di un processo in esecuzione tramite IsWow64Process
+pub fn get_process_architecture(pid: u32) -> Result<ProcessArchitecture, String> {
+    // Windows a 64 bit se siamo x64 oppure se il nostro processo x86 gira sotto WOW64
+    let os_is_64bit = cfg!(target_pointer_width = "64") || unsafe { is_wow64(GetCurrentProcess())? };
+    if !os_is_64bit {
+        return Ok(ProcessArchitecture::X86);
+    }
+    
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid) };
+    if handle.is_null() {
+        return Err(format!("Impossibile aprire il processo {} per leggere l'architettura", pid));
+    }
+    let handle = SafeHandle::new(handle);
+    
+    if is_wow64(handle.get())? {
+        Ok(ProcessArchitecture::X86)
+    } else {
+        Ok(ProcessArchitecture::X64)
+    }
+}
+
+/// Verifica che il payload sia compatibile con il processo target prima di scrivere memoria
+pub fn check_process_architecture(pid: u32) -> Result<ProcessArchitecture, Box<dyn Error>> {
+    let process_arch = get_process_architecture(pid)?;
+    let payload_arch = ProcessArchitecture::payload();
+    if process_arch != payload_arch {
+        return Err(Box::new(ArchitectureMismatch { process_id: pid, process_arch, payload_arch }));
+    }
+    Ok(process_arch)
+}
+
+// === STATISTICHE PER TARGET ===
+
+/// Numero massimo di errori conservati per ogni target
+pub const MAX_TARGET_ERRORS: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetError {
+    pub timestamp: DateTime<Utc>,
+    pub message: String,
+}
+
+/// Statistiche di injection per un singolo processo target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetInjectionStats {
+    pub pid: u32,
+    pub exe_name: String,
+    pub is_active: bool,
+    pub strings_injected: u64,
+    pub bytes_written: u64,
+    pub last_error: Option<String>,
+    /// Ultimi MAX_TARGET_ERRORS errori, dal più vecchio al più recente
+    pub errors: VecDeque<TargetError>,
+    pub started_at: DateTime<Utc>,
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub uptime_seconds: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InjectionTotals {
+    pub targets: usize,
+    pub active_targets: usize,
+    pub strings_injected: u64,
+    pub bytes_written: u64,
+    pub error_count: usize,
+}
+
+/// Report statistiche con dettaglio per target e totali
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionStatsReport {
+    pub targets: Vec<TargetInjectionStats>,
+    pub totals: InjectionTotals,
+    pub generated_at: DateTime<Utc>,
+}
+
+static TARGET_STATS: Lazy<Mutex<HashMap<u32, TargetInjectionStats>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Registra (o riattiva) un target di injection
+pub fn record_target_started(pid: u32, exe_name: &str) {
+    if let Ok(mut targets) = TARGET_STATS.lock() {
+        let entry = targets.entry(pid).or_insert_with(|| TargetInjectionStats {
+            pid,
+            exe_name: exe_name.to_string(),
+            is_active: false,
+            strings_injected: 0,
+            bytes_written: 0,
+            last_error: None,
+            errors: VecDeque::new(),
+            started_at: Utc::now(),
+            stopped_at: None,
+            uptime_seconds: 0,
+        });
+        if !entry.is_active {
+            entry.started_at = Utc::now();
+        }
+        entry.exe_name = exe_name.to_string();
+        entry.is_active = true;
+        entry.stopped_at = None;
+    }
+}
+
+pub fn record_target_stopped(pid: u32) {
+    if let Ok(mut targets) = TARGET_STATS.lock() {
+        if let Some(entry) = targets.get_mut(&pid) {
+            entry.is_active = false;
+            entry.stopped_at = Some(Utc::now());
+        }
+    }
+}
+
+pub fn record_strings_injected(pid: u32, count: u64) {
+    if let Ok(mut targets) = TARGET_STATS.lock() {
+        if let Some(entry) = targets.get_mut(&pid) {
+            entry.strings_injected += count;
+        }
+    }
+}
+
+pub fn record_bytes_written(pid: u32, bytes: u64) {
+    if let Ok(mut targets) = TARGET_STATS.lock() {
+        if let Some(entry) = targets.get_mut(&pid) {
+            entry.bytes_written += bytes;
+        }
+    }
+}
+
+/// Aggiunge un errore al log del target, mantenendo solo gli ultimi MAX_TARGET_ERRORS
+pub fn record_target_error(pid: u32, message: &str) {
+    if let Ok(mut targets) = TARGET_STATS.lock() {
+        if let Some(entry) = targets.get_mut(&pid) {
+            if entry.errors.len() >= MAX_TARGET_ERRORS {
+                entry.errors.pop_front();
+            }
+            entry.errors.push_back(TargetError { timestamp: Utc::now(), message: message.to_string() });
+            entry.last_error = Some(message.to_string());
+        }
+    }
+}
+
+/// Report statistiche per i PID indicati (tutti se None)
+pub fn injection_stats_report(pids: Option<&[u32]>) -> InjectionStatsReport {
+    let now = Utc::now();
+    let mut targets: Vec<TargetInjectionStats> = TARGET_STATS.lock()
+        .map(|targets| targets.values()
+            .filter(|t| pids.map_or(true, |p| p.contains(&t.pid)))
+            .cloned()
+            .collect())
+        .unwrap_or_default();
+    
+    for target in &mut targets {
+        let end = target.stopped_at.unwrap_or(now);
+        target.uptime_seconds = (end - target.started_at).num_seconds().max(0) as u64;
+    }
+    targets.sort_by_key(|t| t.pid);
+    
+    let totals = InjectionTotals {
+        targets: targets.len(),
+        active_targets: targets.iter().filter(|t| t.is_active).count(),
+        strings_injected: targets.iter().map(|t| t.strings_injected).sum(),
+        bytes_written: targets.iter().map(|t| t.bytes_written).sum(),
+        error_count: targets.iter().map(|t| t.errors.len()).sum(),
+    };
+    
+    InjectionStatsReport { targets, totals, generated_at: now }
+}
+
+// === REGISTRO HOOK INSTALLATI ===
+
+/// Regione di memoria modificata da un hook: byte originali e byte scritti al loro posto
+#[derive(Debug, Clone)]
+struct PatchedRegion {
+    address: usize,
+    original_bytes: Vec<u8>,
+    patched_bytes: Vec<u8>,
+}
+
+/// Esito del ripristino della memoria di un processo
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HookCleanupReport {
+    pub pid: u32,
+    pub process_running: bool,
+    /// Hook ripristinati con i byte originali
+    pub restored: usize,
+    /// Hook già ripristinati o sovrascritti da altri (lasciati invariati)
+    pub skipped: usize,
+    /// Hook che non è stato possibile ripristinare (restano nel registro)
+    pub failed: usize,
+    pub errors: Vec<String>,
+}
+
+/// Istanza che ha scritto un hook: più InjektTranslator possono agganciare lo stesso PID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct HookOwner {
+    pid: u32,
+    instance: u64,
+}
+
+type HookRegistry = HashMap<HookOwner, Vec<PatchedRegion>>;
+
+// Hook effettivamente scritti in memoria, per PID e istanza. Sopravvive alle istanze di
+// InjektTranslator così lo shutdown dell'app può ripristinare anche processi "orfani".
+static INSTALLED_HOOKS: Lazy<Mutex<HookRegistry>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_HOOK_INSTANCE: AtomicU64 = AtomicU64::new(1);
+
+/// Nuovo identificativo di istanza per il registro hook
+fn next_hook_instance() -> u64 {
+    NEXT_HOOK_INSTANCE.fetch_add(1, Ordering::Relaxed)
+}
+
+fn insert_patched_region(installed: &mut HookRegistry, owner: HookOwner, region: PatchedRegion) {
+    let regions = installed.entry(owner).or_default();
+    // Un hook riapplicato sullo stesso indirizzo mantiene i byte originali della prima scrittura
+    if let Some(existing) = regions.iter_mut().find(|r| r.address == region.address) {
+        existing.patched_bytes = region.patched_bytes;
+    } else {
+        regions.push(region);
+    }
+}
+
+/// Estrae dal registro le regioni di `pid`: solo quelle di `instance` se indicata, altrimenti
+/// di tutte le istanze. Le istanze più recenti vengono prima, per ripristinare in ordine inverso.
+fn take_patched_regions(installed: &mut HookRegistry, pid: u32, instance: Option<u64>) -> Vec<(HookOwner, Vec<PatchedRegion>)> {
+    let mut owners: Vec<HookOwner> = installed.keys()
+        .filter(|owner| owner.pid == pid && (instance.is_none() || instance == Some(owner.instance)))
+        .copied()
+        .collect();
+    owners.sort_unstable_by_key(|owner| std::cmp::Reverse(owner.instance));
+    owners.into_iter()
+        .filter_map(|owner| installed.remove(&owner).map(|regions| (owner, regions)))
+        .collect()
+}
+
+fn register_patched_region(owner: HookOwner, region: PatchedRegion) {
+    if let Ok(mut installed) = INSTALLED_HOOKS.lock() {
+        insert_patched_region(&mut installed, owner, region);
+    }
+}
+
+/// Rimette nel registro le regioni non ripristinate
+fn reinsert_patched_regions(owned: Vec<(HookOwner, Vec<PatchedRegion>)>) {
+    if let Ok(mut installed) = INSTALLED_HOOKS.lock() {
+        for (owner, regions) in owned {
+            installed.entry(owner).or_default().extend(regions);
+        }
+    }
+}
+
+/// PID con hook ancora installati
+pub fn tracked_hook_pids() -> Vec<u32> {
+    let mut pids: Vec<u32> = INSTALLED_HOOKS.lock()
+        .map(|installed| installed.keys().map(|owner| owner.pid).collect())
+        .unwrap_or_default();
+    pids.sort_unstable();
+    pids.dedup();
+    pids
+}
+
+/// Ripristina una singola regione. Ok(true) se riscritta, Ok(false) se lasciata invariata.
+unsafe fn restore_region(handle: HANDLE, region: &PatchedRegion) -> Result<bool, String> {
+    let len = region.original_bytes.len();
+    let mut current = vec![0u8; len];
+    let mut bytes_read = 0;
+    let read_ok = ReadProcessMemory(
+        handle,
+        region.address as LPVOID,
+        current.as_mut_ptr() as LPVOID,
+        len,
+        &mut bytes_read,
+    );
+    if read_ok == 0 || bytes_read != len {
+        return Err(format!("0x{:x}: memoria non leggibile", region.address));
+    }
+    if current == region.original_bytes {
+        return Ok(false);
+    }
+    // Byte diversi da quelli scritti da noi: modulo scaricato o patch di terzi, non toccare
+    if current != region.patched_bytes {
+        log::warn!("⚠️ 0x{:x} modificato da altri dopo l'hook: ripristino saltato", region.address);
+        return Ok(false);
+    }
+
+    let mut old_protect: DWORD = 0;
+    let protected = VirtualProtectEx(handle, region.address as LPVOID, len, PAGE_EXECUTE_READWRITE, &mut old_protect) != 0;
+    let write_ok = WriteProcessMemory(
+        handle,
+        region.address as LPVOID,
+        region.original_bytes.as_ptr() as LPVOID,
+        len,
+        std::ptr::null_mut(),
+    );
+    if protected {
+        let mut ignored: DWORD = 0;
+        VirtualProtectEx(handle, region.address as LPVOID, len, old_protect, &mut ignored);
+    }
+    if write_ok == 0 {
+        return Err(format!("0x{:x}: scrittura byte originali fallita", region.address));
+    }
+    FlushInstructionCache(handle, region.address as LPVOID, len);
+    Ok(true)
+}
+
+/// Ripristina i byte originali di tutti gli hook registrati per `pid`, di qualsiasi istanza
+/// (stop da comando, pulizia forzata, chiusura app).
+/// Usa `handle` se fornito, altrimenti apre il processo con i soli permessi necessari.
+pub fn restore_process_hooks(pid: u32, handle: Option<&SafeHandle>) -> HookCleanupReport {
+    restore_hooks(pid, None, handle)
+}
+
+/// Ripristina solo gli hook scritti dall'istanza `instance`: gli altri injector sullo stesso
+/// processo restano attivi
+fn restore_instance_hooks(pid: u32, instance: u64, handle: Option<&SafeHandle>) -> HookCleanupReport {
+    restore_hooks(pid, Some(instance), handle)
+}
+
+fn restore_hooks(pid: u32, instance: Option<u64>, handle: Option<&SafeHandle>) -> HookCleanupReport {
+    let mut report = HookCleanupReport { pid, process_running: is_process_running(pid), ..Default::default() };
+
+    let owned = INSTALLED_HOOKS.lock()
+        .map(|mut installed| take_patched_regions(&mut installed, pid, instance))
+        .unwrap_or_default();
+    if owned.is_empty() {
+        return report;
+    }
+    let total: usize = owned.iter().map(|(_, regions)| regions.len()).sum();
+    if !report.process_running {
+        // Processo terminato: la sua memoria non esiste più, niente da ripristinare
+        log::info!("🧹 PID {} terminato, {} hook rimossi dal registro", pid, total);
+        return report;
+    }
+
+    let opened = match handle {
+        Some(_) => None,
+        None => unsafe {
+            let access = PROCESS_VM_OPERATION | PROCESS_VM_READ | PROCESS_VM_WRITE | PROCESS_QUERY_LIMITED_INFORMATION;
+            let opened = OpenProcess(access, FALSE, pid);
+            if opened.is_null() {
+                report.failed = total;
+                report.errors.push(format!("Impossibile aprire il processo {}", pid));
+                reinsert_patched_regions(owned);
+                return report;
+            }
+            Some(SafeHandle::new(opened))
+        },
+    };
+    let handle = handle.or(opened.as_ref()).map_or(std::ptr::null_mut(), |h| h.get());
+
+    // Ordine inverso: hook sovrapposti tornano allo stato originale
+    let mut remaining = Vec::new();
+    for (owner, regions) in owned {
+        let mut failed = Vec::new();
+        for region in regions.into_iter().rev() {
+            match unsafe { restore_region(handle, &region) } {
+                Ok(true) => report.restored += 1,
+                Ok(false) => report.skipped += 1,
+                Err(e) => {
+                    report.failed += 1;
+                    report.errors.push(e);
+                    failed.push(region);
+                }
+            }
+        }
+        if !failed.is_empty() {
+            failed.reverse();
+            remaining.push((owner, failed));
+        }
+    }
+    if !remaining.is_empty() {
+        reinsert_patched_regions(remaining);
+        record_target_error(pid, &format!("{} hook non ripristinati", report.failed));
+    }
+
+    log::info!("♻️ PID {}: {} hook ripristinati, {} saltati, {} falliti",
+        pid, report.restored, report.skipped, report.failed);
+    report
+}
+
+/// Ripristina la memoria di tutti i processi con hook registrati (chiusura app)
+pub fn cleanup_all_injections() -> Vec<HookCleanupReport> {
+    tracked_hook_pids()
+        .into_iter()
+        .map(|pid| {
+            let report = restore_process_hooks(pid, None);
+            record_target_stopped(pid);
+            report
+        })
+        .collect()
+}
+
+// === SCANSIONE MEMORIA PER TESTO ===
+
+/// Codifica del testo cercato in memoria
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextEncoding {
+    #[serde(rename = "utf8")]
+    Utf8,
+    #[serde(rename = "utf16le")]
+    Utf16Le,
+}
+
+impl TextEncoding {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_lowercase().replace(['-', '_'], "").as_str() {
+            "utf8" => Ok(TextEncoding::Utf8),
+            "utf16" | "utf16le" => Ok(TextEncoding::Utf16Le),
+            other => Err(format!("Encoding non supportato: {} (usa utf8 o utf16le)", other)),
+        }
+    }
+
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        match self {
+            TextEncoding::Utf8 => text.as_bytes().to_vec(),
+            TextEncoding::Utf16Le => text.encode_utf16().flat_map(|u| u.to_le_bytes()).collect(),
+        }
+    }
+}
+
+/// Limiti della scansione, per non leggere gigabyte di memoria
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryScanOptions {
+    pub max_results: usize,
+    /// Regioni più grandi di questo limite vengono saltate
+    pub max_region_mb: usize,
+    /// Interrompe la scansione dopo aver letto questa quantità di memoria
+    pub max_total_mb: usize,
+    /// Includi le regioni mappate da moduli (MEM_IMAGE)
+    pub include_image: bool,
+    /// Solo regioni scrivibili (tipico per stringhe di runtime)
+    pub writable_only: bool,
+}
+
+impl Default for MemoryScanOptions {
+    fn default() -> Self {
+        Self {
+            max_results: 100,
+            max_region_mb: 64,
+            max_total_mb: 1024,
+            include_image: true,
+            writable_only: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryScanResult {
+    pub addresses: Vec<usize>,
+    pub scanned_regions: usize,
+    pub skipped_regions: usize,
+    pub bytes_scanned: u64,
+    /// true se la scansione si è fermata per max_results o max_total_mb
+    pub truncated: bool,
+}
+
+/// Cerca una sequenza di byte nelle regioni committed e leggibili di un processo
+pub fn scan_process_memory_for_bytes(pid: u32, needle: &[u8], options: &MemoryScanOptions) -> Result<MemoryScanResult, String> {
+    use winapi::um::winnt::{
+        MEM_IMAGE, PAGE_EXECUTE_READ, PAGE_EXECUTE_WRITECOPY, PAGE_GUARD, PAGE_NOACCESS,
+        PAGE_READONLY, PAGE_READWRITE, PAGE_WRITECOPY, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
+    };
+    
+    if needle.is_empty() {
+        return Err("Testo da cercare vuoto".to_string());
+    }
+    
+    const CHUNK_SIZE: usize = 1024 * 1024;
+    let max_region = options.max_region_mb.saturating_mul(1024 * 1024);
+    let max_total = (options.max_total_mb as u64).saturating_mul(1024 * 1024);
+    let readable = PAGE_READONLY | PAGE_READWRITE | PAGE_WRITECOPY | PAGE_EXECUTE_READ | PAGE_EXECUTE_READWRITE | PAGE_EXECUTE_WRITECOPY;
+    let writable = PAGE_READWRITE | PAGE_WRITECOPY | PAGE_EXECUTE_READWRITE | PAGE_EXECUTE_WRITECOPY;
+    
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, FALSE, pid) };
+    if handle.is_null() {
+        return Err(format!("Impossibile aprire il processo {} in lettura", pid));
+    }
+    let handle = SafeHandle::new(handle);
+    
+    let mut result = MemoryScanResult {
+        addresses: Vec::new(),
+        scanned_regions: 0,
+        skipped_regions: 0,
+        bytes_scanned: 0,
+        truncated: false,
+    };
+    let mut address: usize = 0;
+    let mut buffer = vec![0u8; CHUNK_SIZE + needle.len()];
+    
+    loop {
+        let mut mbi: MEMORY_BASIC_INFORMATION = unsafe { std::mem::zeroed() };
+        let queried = unsafe {
+            VirtualQueryEx(handle.get(), address as LPVOID, &mut mbi, std::mem::size_of::<MEMORY_BASIC_INFORMATION>())
+        };
+        if queried == 0 {
+            break;
+        }
+        
+        let base = mbi.BaseAddress as usize;
+        let size = mbi.RegionSize;
+        let next = base.saturating_add(size);
+        
+        let protect = mbi.Protect;
+        let is_readable = mbi.State == MEM_COMMIT
+            && protect & readable != 0
+            && protect & (PAGE_GUARD | PAGE_NOACCESS) == 0;
+        let passes_filters = (options.include_image || mbi.Type != MEM_IMAGE)
+            && (!options.writable_only || protect & writable != 0)
+            && size <= max_region;
+        
+        if !is_readable {
+            // Regioni libere/riservate non contano come saltate
+        } else if !passes_filters {
+            result.skipped_regions += 1;
+        } else {
+            result.scanned_regions += 1;
+            let mut offset = 0usize;
+            while offset < size {
+                // Sovrapposizione di needle.len()-1 byte per non perdere match a cavallo dei chunk
+                let to_read = (size - offset).min(CHUNK_SIZE + needle.len() - 1);
+                let mut bytes_read = 0usize;
+                let ok = unsafe {
+                    ReadProcessMemory(handle.get(), (base + offset) as LPVOID, buffer.as_mut_ptr() as LPVOID, to_read, &mut bytes_read)
+                };
+                if ok == 0 || bytes_read == 0 {
+                    break;
+                }
+                
+                for pos in buffer[..bytes_read].windows(needle.len())
+                    .enumerate()
+                    .filter(|(_, w)| *w == needle)
+                    .map(|(i, _)| i)
+                {
+                    result.addresses.push(base + offset + pos);
+                    if result.addresses.len() >= options.max_results {
+                        result.truncated = true;
+                        break;
+                    }
+                }
+                
+                result.bytes_scanned += bytes_read.min(CHUNK_SIZE) as u64;
+                if result.truncated || result.bytes_scanned >= max_total {
+                    result.truncated = true;
+                    break;
+                }
+                offset += CHUNK_SIZE;
+            }
+        }
+        
+        if result.truncated || next <= address {
+            break;
+        }
+        address = next;
+    }
+    
+    result.addresses.dedup();
+    Ok(result)
+}
+
+/// Stato di SeDebugPrivilege nel token del processo corrente: (presente, abilitato)
+pub fn debug_privilege_status() -> Result<(bool, bool), String> {
+    use winapi::um::processthreadsapi::OpenProcessToken;
+    use winapi::um::securitybaseapi::GetTokenInformation;
+    use winapi::um::winbase::LookupPrivilegeValueW;
+    use winapi::um::winnt::{TokenPrivileges, LUID, SE_PRIVILEGE_ENABLED, TOKEN_PRIVILEGES, TOKEN_QUERY};
+    
+    unsafe {
+        let mut token: HANDLE = std::ptr::null_mut();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+            return Err("Impossibile aprire il token del processo".to_string());
+        }
+        let token = SafeHandle::new(token);
+        
+        let name: Vec<u16> = "SeDebugPrivilege".encode_utf16().chain(std::iter::once(0)).collect();
+        let mut luid: LUID = std::mem::zeroed();
+        if LookupPrivilegeValueW(std::ptr::null(), name.as_ptr(), &mut luid) == 0 {
+            return Err("LookupPrivilegeValue fallita per SeDebugPrivilege".to_string());
+        }
+        
+        // Prima chiamata per la dimensione, seconda per i dati
+        let mut size = 0u32;
+        GetTokenInformation(token.get(), TokenPrivileges, std::ptr::null_mut(), 0, &mut size);
+        if size == 0 {
+            return Err("GetTokenInformation fallita".to_string());
+        }
+        let mut buffer = vec![0u8; size as usize];
+        if GetTokenInformation(token.get(), TokenPrivileges, buffer.as_mut_ptr() as LPVOID, size, &mut size) == 0 {
+            return Err("GetTokenInformation fallita".to_string());
+        }
+        
+        let privileges = &*(buffer.as_ptr() as *const TOKEN_PRIVILEGES);
+        let entries = std::slice::from_raw_parts(privileges.Privileges.as_ptr(), privileges.PrivilegeCount as usize);
+        for entry in entries {
+            if entry.Luid.LowPart == luid.LowPart && entry.Luid.HighPart == luid.HighPart {
+                return Ok((true, entry.Attributes & SE_PRIVILEGE_ENABLED != 0));
+            }
+        }
+        Ok((false, false))
+    }
+}
+
+/// Messaggio mostrato quando SeDebugPrivilege non è ottenibile
+pub const DEBUG_PRIVILEGE_HINT: &str =
+    "SeDebugPrivilege non disponibile: chiudi GameStringer e riavvialo come amministratore per usare l'injection";
+
+/// Abilita SeDebugPrivilege nel token del processo corrente (AdjustTokenPrivileges).
+/// Fallisce se il privilegio non è nel token, cioè se GameStringer non gira come amministratore.
+pub fn enable_debug_privilege() -> Result<(), String> {
+    use winapi::um::processthreadsapi::OpenProcessToken;
+    use winapi::um::securitybaseapi::AdjustTokenPrivileges;
+    use winapi::um::winbase::LookupPrivilegeValueW;
+    use winapi::um::winnt::{LUID, SE_PRIVILEGE_ENABLED, TOKEN_ADJUST_PRIVILEGES, TOKEN_PRIVILEGES, TOKEN_QUERY};
+    // AdjustTokenPrivileges "riesce" anche se il privilegio non è stato assegnato
+    const ERROR_NOT_ALL_ASSIGNED: i32 = 1300;
+    
+    unsafe {
+        let mut token: HANDLE = std::ptr::null_mut();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_ADJUST_PRIVILEGES | TOKEN_QUERY, &mut token) == 0 {
+            return Err("Impossibile aprire il token del processo".to_string());
+        }
+        let token = SafeHandle::new(token);
+        
+        let name: Vec<u16> = "SeDebugPrivilege".encode_utf16().chain(std::iter::once(0)).collect();
+        let mut luid: LUID = std::mem::zeroed();
+        if LookupPrivilegeValueW(std::ptr::null(), name.as_ptr(), &mut luid) == 0 {
+            return Err("LookupPrivilegeValue fallita per SeDebugPrivilege".to_string());
+        }
+        
+        let mut privileges: TOKEN_PRIVILEGES = std::mem::zeroed();
+        privileges.PrivilegeCount = 1;
+        privileges.Privileges[0].Luid = luid;
+        privileges.Privileges[0].Attributes = SE_PRIVILEGE_ENABLED;
+        
+        let ok = AdjustTokenPrivileges(token.get(), FALSE, &mut privileges, 0, std::ptr::null_mut(), std::ptr::null_mut());
+        let last_error = std::io::Error::last_os_error();
+        if ok == 0 {
+            return Err(format!("AdjustTokenPrivileges fallita: {}", last_error));
+        }
+        if last_error.raw_os_error() == Some(ERROR_NOT_ALL_ASSIGNED) {
+            return Err(DEBUG_PRIVILEGE_HINT.to_string());
+        }
+    }
+    
+    log::info!("🔓 SeDebugPrivilege abilitato");
+    Ok(())
+}
+
+/// True se il processo corrente è elevato (UAC "Esegui come amministratore")
+pub fn is_process_elevated() -> Result<bool, String> {
+    use winapi::um::processthreadsapi::OpenProcessToken;
+    use winapi::um::securitybaseapi::GetTokenInformation;
+    use winapi::um::winnt::{TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY};
+    
+    unsafe {
+        let mut token: HANDLE = std::ptr::null_mut();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+            return Err("Impossibile aprire il token del processo".to_string());
+        }
+        let token = SafeHandle::new(token);
+        
+        let mut elevation: TOKEN_ELEVATION = std::mem::zeroed();
+        let mut size = std::mem::size_of::<TOKEN_ELEVATION>() as u32;
+        if GetTokenInformation(token.get(), TokenElevation, &mut elevation as *mut _ as LPVOID, size, &mut size) == 0 {
+            return Err("GetTokenInformation(TokenElevation) fallita".to_string());
+        }
+        Ok(elevation.TokenIsElevated != 0)
+    }
+}
+
+/// Garantisce SeDebugPrivilege abilitato prima di un'injection, abilitandolo se presente
+pub fn ensure_debug_privilege() -> Result<(), String> {
+    match debug_privilege_status()? {
+        (true, true) => Ok(()),
+        (true, false) => enable_debug_privilege(),
+        (false, _) => Err(DEBUG_PRIVILEGE_HINT.to_string()),
+    }
+}
+
+/// Conta i moduli leggibili di un processo (verifica che la lista moduli sia accessibile)
+pub fn readable_module_count(pid: u32) -> Result<usize, String> {
+    use winapi::um::tlhelp32::TH32CS_SNAPMODULE32;
+    
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPMODULE | TH32CS_SNAPMODULE32, pid);
+        if snapshot == winapi::um::handleapi::INVALID_HANDLE_VALUE {
+            return Err(format!("Impossibile creare snapshot moduli per PID {}", pid));
+        }
+        let snapshot = SafeHandle::new(snapshot);
+        
+        let mut entry: MODULEENTRY32 = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<MODULEENTRY32>() as u32;
+        
+        let mut count = 0;
+        if Module32First(snapshot.get(), &mut entry) != 0 {
+            count += 1;
+            while Module32Next(snapshot.get(), &mut entry) != 0 {
+                count += 1;
+            }
+        }
+        
+        if count == 0 {
+            return Err(format!("Lista moduli vuota o non leggibile per PID {}", pid));
+        }
+        Ok(count)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InjectionConfig {
     pub target_process: String,
@@ -73,6 +846,9 @@ pub struct InjectionConfig {
     pub api_key: String,
     pub hook_mode: String,
     pub cache_enabled: bool,
+    /// Ignora il blocco anti-cheat (rischio ban, loggato)
+    #[serde(default)]
+    pub force_anti_cheat_override: bool,
 }
 
 #[derive(Debug)]
@@ -80,6 +856,8 @@ pub struct InjektTranslator {
     config: InjectionConfig,
     process_handle: Option<Arc<SafeHandle>>,
     target_pid: Option<u32>,
+    /// Identifica gli hook di questa istanza nel registro globale
+    hook_instance: u64,
     hooks: Arc<Mutex<Vec<HookPoint>>>,
     stats: Arc<Mutex<InjektStats>>,
     is_running: Arc<Mutex<bool>>,
@@ -140,6 +918,7 @@ enum ErrorType {
     AntiCheatDetected,
     InvalidAddress,
     ProcessTerminated,
+    ArchitectureMismatch,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -163,6 +942,7 @@ impl InjektTranslator {
             config,
             process_handle: None,
             target_pid: None,
+            hook_instance: next_hook_instance(),
             hooks: Arc::new(Mutex::new(Vec::new())),
             stats: Arc::new(Mutex::new(InjektStats {
                 is_active: false,
@@ -185,6 +965,14 @@ impl InjektTranslator {
     }
     
     pub fn start(&mut self) -> Result<(), Box<dyn Error>> {
+        let result = self.start_target();
+        if let (Err(e), Some(pid)) = (&result, self.target_pid) {
+            record_target_error(pid, &e.to_string());
+        }
+        result
+    }
+    
+    fn start_target(&mut self) -> Result<(), Box<dyn Error>> {
         // Trova il processo target
         let processes = crate::process_utils::find_game_processes()?;
         let target = processes
@@ -193,24 +981,33 @@ impl InjektTranslator {
             .ok_or("Processo target non trovato")?;
         
         self.target_pid = Some(target.pid);
+        record_target_started(target.pid, &target.name);
+        
+        // Verifica architettura prima di aprire il processo in scrittura
+        let arch = check_process_architecture(target.pid)?;
+        log::info!("🧬 Architettura processo target: {}", arch);
+        
+        // SeDebugPrivilege: senza, OpenProcess fallisce sui processi di altri utenti o elevati
+        let privilege = ensure_debug_privilege();
+        if let Err(e) = &privilege {
+            log::warn!("⚠️ {}", e);
+        }
         
         // Apri il processo
         unsafe {
             let handle = OpenProcess(PROCESS_ALL_ACCESS, FALSE, target.pid);
             if handle.is_null() {
-                return Err("Impossibile aprire il processo target".into());
+                return Err(match privilege {
+                    Err(e) => format!("Impossibile aprire il processo target. {}", e),
+                    Ok(()) => "Impossibile aprire il processo target".to_string(),
+                }.into());
             }
             self.process_handle = Some(Arc::new(SafeHandle::new(handle)));
         }
         
         // Verifica compatibilità anti-cheat con sistema avanzato
-        let anti_cheat_detection = self.anti_cheat_manager.detect_anti_cheat(target.pid)?;
-        
-        if !self.anti_cheat_manager.is_injection_safe(&anti_cheat_detection) {
-            let detected_systems = anti_cheat_detection.detected_systems.join(", ");
-            return Err(format!("🚨 Sistemi anti-cheat rilevati: {}. Injection non sicura (Risk: {:?})", 
-                detected_systems, anti_cheat_detection.risk_assessment).into());
-        }
+        let anti_cheat_detection = self.anti_cheat_manager
+            .check_injection_gate(target.pid, self.config.force_anti_cheat_override)?;
         
         // Applica delay se necessario per compatibilità
         if let Some(delay_ms) = self.anti_cheat_manager.get_injection_delay(&anti_cheat_detection) {
@@ -326,24 +1123,19 @@ impl InjektTranslator {
     }
     
     fn remove_hooks(&mut self) -> Result<(), Box<dyn Error>> {
-        let hooks = self.hooks.lock().unwrap();
+        let mut hooks = self.hooks.lock().unwrap();
         
-        if let Some(handle_arc) = &self.process_handle {
-            let handle = handle_arc.get();
-            for hook in hooks.iter() {
-                unsafe {
-                    // Ripristina bytes originali
-                    WriteProcessMemory(
-                        handle,
-                        hook.address as LPVOID,
-                        hook.original_bytes.as_ptr() as LPVOID,
-                        hook.original_bytes.len(),
-                        std::ptr::null_mut(),
-                    );
-                }
-            }
-        }
+        // Ripristina solo la memoria scritta da questa istanza (registro con i byte originali)
+        let report = match self.target_pid {
+            Some(pid) => restore_instance_hooks(pid, self.hook_instance, self.process_handle.as_deref()),
+            None => HookCleanupReport::default(),
+        };
+        
+        hooks.clear();
         
+        if report.failed > 0 {
+            return Err(format!("{} hook non ripristinati: {}", report.failed, report.errors.join("; ")).into());
+        }
         Ok(())
     }
     
@@ -353,7 +1145,7 @@ impl InjektTranslator {
         // per le funzioni di rendering del testo nel gioco
         let mut hook = HookPoint {
             address: 0x401000, // Indirizzo fittizio
-            original_bytes: vec![0x90; 5], // NOP placeholder
+            original_bytes: Vec::new(), // Letti da perform_hook prima della scrittura
             hook_type: HookType::TextRender,
             module_name: "game_engine.dll".to_string(),
             retry_count: 0,
@@ -382,7 +1174,7 @@ impl InjektTranslator {
         // Hook per intercettare MessageBox e dialoghi simili
         let mut hook = HookPoint {
             address: 0x402000,
-            original_bytes: vec![0x90; 5],
+            original_bytes: Vec::new(),
             hook_type: HookType::DialogBox,
             module_name: "user32.dll".to_string(),
             retry_count: 0,
@@ -409,7 +1201,7 @@ impl InjektTranslator {
         // Hook per menu di gioco
         let mut hook = HookPoint {
             address: 0x403000,
-            original_bytes: vec![0x90; 5],
+            original_bytes: Vec::new(),
             hook_type: HookType::MenuItem,
             module_name: "game_ui.dll".to_string(),
             retry_count: 0,
@@ -436,7 +1228,7 @@ impl InjektTranslator {
         // Hook per sottotitoli
         let mut hook = HookPoint {
             address: 0x404000,
-            original_bytes: vec![0x90; 5],
+            original_bytes: Vec::new(),
             hook_type: HookType::Subtitle,
             module_name: "subtitle_engine.dll".to_string(),
             retry_count: 0,
@@ -520,6 +1312,7 @@ impl InjektTranslator {
                         if failed_hooks > 0 {
                             consecutive_failures += 1;
                             stats_guard.error_count += 1;
+                            record_target_error(pid, &format!("{} hook non attivi", failed_hooks));
                             
                             if consecutive_failures >= MAX_HOOK_FAILURES {
                                 log::error!("🚨 Troppi hook falliti consecutivi: {}", consecutive_failures);
@@ -541,6 +1334,7 @@ impl InjektTranslator {
                         // Simula nuove traduzioni basate su hook attivi
                         if stats_guard.active_hooks > 0 && rand::random::<f32>() > 0.8 {
                             stats_guard.translations_applied += 1;
+                            record_strings_injected(pid, 1);
                             
                             // Simula cache hit/miss
                             if rand::random::<f32>() > 0.6 {
@@ -568,7 +1362,7 @@ impl InjektTranslator {
         for &hook_id in hook_ids {
             let hook = HookPoint {
                 address: 0x1000 + hook_id, // Indirizzo simulato
-                original_bytes: vec![0x90, 0x90, 0x90], // NOP simulato
+                original_bytes: Vec::new(), // Hook simulato: nessuna scrittura in memoria
                 hook_type: HookType::TextRender,
                 module_name: "UI_TEXT".to_string(),
                 retry_count: 0,
@@ -592,7 +1386,7 @@ impl InjektTranslator {
         for &hook_id in hook_ids {
             let hook = HookPoint {
                 address: 0x2000 + hook_id,
-                original_bytes: vec![0x90, 0x90, 0x90],
+                original_bytes: Vec::new(),
                 hook_type: HookType::DialogBox,
                 module_name: "DIALOG_BOX".to_string(),
                 retry_count: 0,
@@ -616,7 +1410,7 @@ impl InjektTranslator {
         for &hook_id in hook_ids {
             let hook = HookPoint {
                 address: 0x3000 + hook_id,
-                original_bytes: vec![0x90, 0x90, 0x90],
+                original_bytes: Vec::new(),
                 hook_type: HookType::MenuItem,
                 module_name: "MENU_ITEM".to_string(),
                 retry_count: 0,
@@ -640,7 +1434,7 @@ impl InjektTranslator {
         for &hook_id in hook_ids {
             let hook = HookPoint {
                 address: 0x4000 + hook_id,
-                original_bytes: vec![0x90, 0x90, 0x90],
+                original_bytes: Vec::new(),
                 hook_type: HookType::Subtitle,
                 module_name: "SUBTITLE".to_string(),
                 retry_count: 0,
@@ -884,6 +1678,9 @@ impl InjektTranslator {
                             stats.hook_failures += 1;
                             stats.error_count += 1;
                         }
+                        if let Some(pid) = self.target_pid {
+                            record_target_error(pid, &format!("Hook 0x{:x} fallito: {}", hook.address, e));
+                        }
                         
                         return Err(e);
                     }
@@ -896,12 +1693,15 @@ impl InjektTranslator {
     
     /// Esegue l'hook effettivo
     #[allow(dead_code)] // Implementazione hook core - essenziale per injection system
-    fn perform_hook(&self, hook: &HookPoint) -> Result<(), Box<dyn Error>> {
+    fn perform_hook(&self, hook: &mut HookPoint) -> Result<(), Box<dyn Error>> {
         if let Some(handle_arc) = &self.process_handle {
             let handle = handle_arc.get();
             unsafe {
-                // Leggi i bytes originali
-                let mut original_bytes = vec![0u8; 5];
+                // Crea il jump hook (JMP instruction)
+                let hook_bytes = vec![0xE9, 0x00, 0x00, 0x00, 0x00]; // JMP placeholder
+                
+                // Leggi i bytes originali (stessa lunghezza di quelli che verranno sovrascritti)
+                let mut original_bytes = vec![0u8; hook_bytes.len()];
                 let mut bytes_read = 0;
                 
                 let read_result = ReadProcessMemory(
@@ -915,9 +1715,7 @@ impl InjektTranslator {
                 if read_result == 0 || bytes_read != original_bytes.len() {
                     return Err("Impossibile leggere memoria processo".into());
                 }
-                
-                // Crea il jump hook (JMP instruction)
-                let hook_bytes = vec![0xE9, 0x00, 0x00, 0x00, 0x00]; // JMP placeholder
+                hook.original_bytes = original_bytes;
                 
                 // Scrivi l'hook
                 let write_result = WriteProcessMemory(
@@ -931,6 +1729,15 @@ impl InjektTranslator {
                 if write_result == 0 {
                     return Err("Impossibile scrivere hook in memoria".into());
                 }
+                if let Some(pid) = self.target_pid {
+                    record_bytes_written(pid, hook_bytes.len() as u64);
+                    // Registra i byte originali per il ripristino esatto (stop, chiusura app, force cleanup)
+                    register_patched_region(HookOwner { pid, instance: self.hook_instance }, PatchedRegion {
+                        address: hook.address,
+                        original_bytes: hook.original_bytes.clone(),
+                        patched_bytes: hook_bytes,
+                    });
+                }
                 
                 log::info!("🔗 Hook installato a 0x{:x} ({})", hook.address, hook.module_name);
                 Ok(())
@@ -985,6 +1792,10 @@ impl InjektTranslator {
         // Handle processo verrà chiuso automaticamente dal Drop di SafeHandle
         self.process_handle.take();
         
+        if let Some(pid) = self.target_pid {
+            record_target_stopped(pid);
+        }
+        
         // Reset statistiche
         if let Ok(mut stats) = self.stats.lock() {
             stats.is_active = false;
@@ -996,6 +1807,16 @@ impl InjektTranslator {
     }
 }
 
+impl Drop for InjektTranslator {
+    fn drop(&mut self) {
+        // Istanza scartata senza stop() (es. errore o panic): non lasciare hook nel gioco
+        if self.process_handle.is_some() {
+            log::warn!("⚠️ InjektTranslator rilasciato senza stop, ripristino hook...");
+            self.cleanup_resources();
+        }
+    }
+}
+
 // Funzione helper per generare numeri casuali (placeholder)
 mod rand {
     pub fn random<T>() -> T
@@ -1005,3 +1826,68 @@ mod rand {
         T::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(address: usize, original: u8, patched: u8) -> PatchedRegion {
+        PatchedRegion { address, original_bytes: vec![original; 5], patched_bytes: vec![patched; 5] }
+    }
+
+    #[test]
+    fn reapplied_hook_keeps_first_original_bytes() {
+        let mut installed = HookRegistry::new();
+        let owner = HookOwner { pid: 10, instance: 1 };
+        insert_patched_region(&mut installed, owner, region(0x1000, 0x90, 0xE9));
+        insert_patched_region(&mut installed, owner, region(0x1000, 0xE9, 0xCC));
+
+        let regions = &installed[&owner];
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].original_bytes, vec![0x90; 5]);
+        assert_eq!(regions[0].patched_bytes, vec![0xCC; 5]);
+    }
+
+    #[test]
+    fn instance_restore_takes_only_its_own_regions() {
+        let mut installed = HookRegistry::new();
+        let first = HookOwner { pid: 10, instance: 1 };
+        let second = HookOwner { pid: 10, instance: 2 };
+        let other_pid = HookOwner { pid: 20, instance: 1 };
+        insert_patched_region(&mut installed, first, region(0x1000, 0x90, 0xE9));
+        insert_patched_region(&mut installed, second, region(0x2000, 0x90, 0xE9));
+        insert_patched_region(&mut installed, other_pid, region(0x1000, 0x90, 0xE9));
+
+        let taken = take_patched_regions(&mut installed, 10, Some(1));
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].0, first);
+        assert_eq!(taken[0].1[0].address, 0x1000);
+
+        // L'altra istanza sullo stesso PID e gli altri processi restano registrati
+        assert!(installed.contains_key(&second));
+        assert!(installed.contains_key(&other_pid));
+        assert!(take_patched_regions(&mut installed, 10, Some(1)).is_empty());
+    }
+
+    #[test]
+    fn process_restore_takes_every_instance_newest_first() {
+        let mut installed = HookRegistry::new();
+        for instance in [3, 1, 2] {
+            insert_patched_region(&mut installed, HookOwner { pid: 10, instance }, region(0x1000 * instance as usize, 0x90, 0xE9));
+        }
+        insert_patched_region(&mut installed, HookOwner { pid: 20, instance: 4 }, region(0x1000, 0x90, 0xE9));
+
+        let taken = take_patched_regions(&mut installed, 10, None);
+        let instances: Vec<u64> = taken.iter().map(|(owner, _)| owner.instance).collect();
+        assert_eq!(instances, vec![3, 2, 1]);
+        assert_eq!(installed.len(), 1);
+        assert!(installed.keys().all(|owner| owner.pid == 20));
+    }
+
+    #[test]
+    fn hook_instances_are_unique() {
+        let first = next_hook_instance();
+        let second = next_hook_instance();
+        assert_ne!(first, second);
+    }
+}
@@ -1,3 +1,3 @@
-// pub mod notifications; // TEMPORANEAMENTE DISABILITATO
+pub mod notifications;
 pub mod profiles;
 pub mod unity_bundle;
\ No newline at end of file
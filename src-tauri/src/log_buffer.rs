@@ -0,0 +1,178 @@
+// 🪵 Logger dell'app con ring buffer in memoria
+// Ogni record dei macro `log::` viene scritto su stderr (console in dev) e conservato in un
+// buffer circolare limitato, così la UI può mostrare i log recenti e allegarli a una
+// segnalazione di bug senza dover avviare l'app da terminale.
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// Record conservati al massimo (i più vecchi vengono scartati)
+const MAX_ENTRIES: usize = 2000;
+
+/// Livello di default: Debug in sviluppo, Info in produzione
+const DEFAULT_LEVEL: LevelFilter = if cfg!(debug_assertions) { LevelFilter::Debug } else { LevelFilter::Info };
+
+/// Crate esterni molto verbosi: sotto Warn restano fuori dal buffer
+const NOISY_TARGETS: &[&str] = &["hyper", "reqwest", "rustls", "tao", "wry", "tracing", "h2", "mio"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// Buffer circolare limitato
+struct RingBuffer {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+}
+
+impl RingBuffer {
+    fn new(capacity: usize) -> Self {
+        Self { entries: VecDeque::with_capacity(capacity.min(256)), capacity }
+    }
+
+    fn push(&mut self, entry: LogEntry) {
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Ultimi `limit` record con livello almeno `min_level`, dal più vecchio al più recente
+    fn recent(&self, min_level: LevelFilter, limit: usize) -> Vec<LogEntry> {
+        let mut result: Vec<LogEntry> = self.entries.iter()
+            .rev()
+            .filter(|e| Level::from_str(&e.level).is_ok_and(|l| l <= min_level))
+            .take(limit)
+            .cloned()
+            .collect();
+        result.reverse();
+        result
+    }
+}
+
+static BUFFER: Lazy<Mutex<RingBuffer>> = Lazy::new(|| Mutex::new(RingBuffer::new(MAX_ENTRIES)));
+
+struct BufferLogger;
+
+static LOGGER: BufferLogger = BufferLogger;
+
+fn is_noisy(target: &str) -> bool {
+    let root = target.split("::").next().unwrap_or(target);
+    NOISY_TARGETS.contains(&root)
+}
+
+impl Log for BufferLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level() && (metadata.level() <= Level::Warn || !is_noisy(metadata.target()))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let entry = LogEntry {
+            timestamp: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, false),
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+        eprintln!("{} [{}] {}: {}", entry.timestamp, entry.level, entry.target, entry.message);
+        BUFFER.lock().push(entry);
+    }
+
+    fn flush(&self) {}
+}
+
+/// Installa il logger globale (da chiamare una volta all'avvio)
+pub fn init() {
+    match log::set_logger(&LOGGER) {
+        Ok(()) => log::set_max_level(DEFAULT_LEVEL),
+        Err(e) => eprintln!("[LOG] ⚠️ Logger già installato: {}", e),
+    }
+}
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    LevelFilter::from_str(level.trim())
+        .map_err(|_| format!("Livello di log non valido: {} (error, warn, info, debug, trace, off)", level))
+}
+
+/// Log recenti con livello almeno `level_filter` (default: tutti), al massimo `limit` (default 500)
+#[tauri::command]
+pub fn get_recent_logs(level_filter: Option<String>, limit: Option<usize>) -> Result<Vec<LogEntry>, String> {
+    let min_level = match level_filter.as_deref() {
+        Some(level) => parse_level(level)?,
+        None => LevelFilter::Trace,
+    };
+    Ok(BUFFER.lock().recent(min_level, limit.unwrap_or(500).min(MAX_ENTRIES)))
+}
+
+/// Cambia il livello di log a runtime (non persistito)
+#[tauri::command]
+pub fn set_log_level(level: String) -> Result<String, String> {
+    let filter = parse_level(&level)?;
+    log::set_max_level(filter);
+    log::info!("🪵 Livello di log impostato a {}", filter);
+    Ok(filter.to_string())
+}
+
+/// Svuota il buffer dei log
+#[tauri::command]
+pub fn clear_recent_logs() {
+    BUFFER.lock().entries.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: Level, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: String::new(),
+            level: level.to_string(),
+            target: "test".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn ring_buffer_is_bounded() {
+        let mut buffer = RingBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(entry(Level::Info, &i.to_string()));
+        }
+        let messages: Vec<String> = buffer.recent(LevelFilter::Trace, 10).into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, ["2", "3", "4"]);
+    }
+
+    #[test]
+    fn recent_filters_by_level_and_limit() {
+        let mut buffer = RingBuffer::new(10);
+        buffer.push(entry(Level::Error, "e1"));
+        buffer.push(entry(Level::Debug, "d1"));
+        buffer.push(entry(Level::Warn, "w1"));
+        buffer.push(entry(Level::Info, "i1"));
+        buffer.push(entry(Level::Error, "e2"));
+
+        let warnings: Vec<String> = buffer.recent(LevelFilter::Warn, 10).into_iter().map(|e| e.message).collect();
+        assert_eq!(warnings, ["e1", "w1", "e2"]);
+        let last_two: Vec<String> = buffer.recent(LevelFilter::Trace, 2).into_iter().map(|e| e.message).collect();
+        assert_eq!(last_two, ["i1", "e2"]);
+        assert!(buffer.recent(LevelFilter::Off, 10).is_empty());
+    }
+
+    #[test]
+    fn noisy_targets_are_detected() {
+        assert!(is_noisy("hyper::proto::h1"));
+        assert!(!is_noisy("gamestringer::commands::steam"));
+        assert!(parse_level("WARN").is_ok());
+        assert!(parse_level("loud").is_err());
+    }
+}
@@ -16,16 +16,23 @@ mod ue_translator;
 mod ocr_translator;
 
 pub mod profiles;
-// Notification system: vedi src/notifications_disabled/
+mod notifications;
 
 use profiles::storage::ProfileStorage;
 use profiles::manager::ProfileManager;
 use profiles::settings_manager::ProfileSettingsManager;
 use commands::profiles::ProfileManagerState;
 use commands::profile_settings::ProfileSettingsManagerState;
-// use tauri::Manager; // Non usato attualmente
+use commands::notifications::NotificationManagerState;
+use notifications::{manager::NotificationManager, storage::NotificationStorage};
+use tauri::Manager;
 
 mod process_utils;
+mod http;
+mod vdf;
+mod appinfo;
+mod log_buffer;
+mod string_id;
 
 #[tauri::command]
 fn close_app(app: tauri::AppHandle) {
@@ -33,6 +40,8 @@ fn close_app(app: tauri::AppHandle) {
 }
 
 fn main() {
+    log_buffer::init();
+    
     // Usa la directory dei dati dell'app per evitare che Tauri riavvii quando i file cambiano
     let app_data_dir = if cfg!(debug_assertions) {
         // In dev, usa una directory temporanea fuori da src-tauri
@@ -50,11 +59,18 @@ fn main() {
     };
 
     // Inizializza ProfileSettingsManager
-    let settings_manager = ProfileSettingsManager::new(app_data_dir).expect("Failed to initialize settings manager");
+    let settings_manager = ProfileSettingsManager::new(app_data_dir.clone()).expect("Failed to initialize settings manager");
     let settings_state = ProfileSettingsManagerState {
         manager: std::sync::Arc::new(tokio::sync::Mutex::new(settings_manager)),
     };
 
+    // Inizializza NotificationManager (un errore del database non blocca l'avvio)
+    let notification_manager = NotificationManager::new(NotificationStorage::new(app_data_dir.join("notifications.db")));
+    if let Err(e) = tauri::async_runtime::block_on(notification_manager.initialize()) {
+        eprintln!("[NOTIFICATIONS] ⚠️ Inizializzazione notifiche fallita: {}", e);
+    }
+    let notification_state = NotificationManagerState::new(notification_manager);
+
     tauri::Builder::default()
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
@@ -64,10 +80,16 @@ fn main() {
 
         .manage(profile_state)
         .manage(settings_state)
+        .manage(notification_state)
         .manage(commands::translation_bridge::TranslationBridgeState::new())
         .manage(commands::activity_history::ActivityHistoryState::default())
         .invoke_handler(tauri::generate_handler![
             close_app,
+            log_buffer::get_recent_logs,
+            log_buffer::set_log_level,
+            log_buffer::clear_recent_logs,
+            string_id::compute_string_ids,
+            commands::diagnostics::generate_diagnostics_bundle,
             commands::steam::auto_detect_steam_config,
             commands::steam::test_steam_connection,
             commands::steam::disconnect_steam,
@@ -89,8 +111,13 @@ fn main() {
             commands::steam::debug_steam_api_extended,
             commands::steam::debug_steam_profile,
             commands::steam::add_game_to_library,
+            commands::steam::validate_owned_games_file,
+            commands::steam::repair_owned_games_file,
+            commands::steam::sync_owned_games_to_local_file,
             commands::steam::get_steam_games_fast,
             commands::steam::get_all_local_steam_games,
+            commands::steam::get_all_local_steam_games_with_progress,
+            commands::steam::cancel_local_steam_scan,
             commands::steam::debug_steam_paths,
             commands::steam::test_single_acf,
             commands::steam::parse_shared_config_vdf,
@@ -133,13 +160,17 @@ fn main() {
             commands::epic_enhanced::scan_epic_games_enhanced,
             commands::epic_enhanced::get_epic_game_enhanced,
             commands::epic_enhanced::get_epic_statistics_enhanced,
-            // HowLongToBeat (game completion times) - Rimosso per eliminare dipendenza xml5ever
-            // commands::hltb_manager::search_game_hltb,
-            // commands::hltb_manager::get_hltb_statistics,
-            // commands::hltb_manager::cleanup_hltb_cache,
-            // commands::hltb_manager::search_games_batch_hltb,
+            // HowLongToBeat (game completion times) - endpoint JSON, senza xml5ever
+            commands::hltb_manager::search_game_hltb,
+            commands::hltb_manager::get_hltb_statistics,
+            commands::hltb_manager::cleanup_hltb_cache,
+            commands::hltb_manager::search_games_batch_hltb,
+            commands::hltb_manager::get_hltb_config,
+            commands::hltb_manager::update_hltb_config,
             commands::epic::get_epic_game_details,
             commands::epic::get_epic_game_cover,
+            commands::epic::lookup_epic_game,
+            commands::epic::scan_epic_installed_from_manifests,
             commands::epic::get_epic_covers_batch,
             commands::epic::test_epic_connection,
             commands::epic::start_epic_oauth_flow,
@@ -185,6 +216,7 @@ fn main() {
             commands::battlenet::clear_battlenet_credentials,
             commands::battlenet::disconnect_battlenet,
             commands::battlenet::get_battlenet_game_info,
+            commands::battlenet::get_battlenet_installed_products,
             commands::battlenet::get_battlenet_covers_batch,
             commands::itchio::test_itchio_connection,
             commands::itchio::connect_itchio,
@@ -192,6 +224,7 @@ fn main() {
             commands::itchio::load_itchio_credentials,
             commands::itchio::clear_itchio_credentials,
             commands::itchio::get_itchio_game_info,
+            commands::itchio::get_itchio_installed_games_info,
             commands::itchio::get_itchio_covers_batch,
             commands::rockstar::test_rockstar_connection,
             commands::rockstar::connect_rockstar,
@@ -206,28 +239,38 @@ fn main() {
             commands::launcher::launch_gog_game,
             commands::launcher::launch_game_direct,
             commands::launcher::launch_game_universal,
+            commands::launcher::get_launch_command,
             commands::launcher::get_installed_launchers,
             commands::launcher::test_launcher_functionality,
+            commands::launcher::diagnose_launchers,
             commands::library::get_library_games,
             commands::library::get_game_path,
             commands::library::read_game_file,
             commands::library::scan_game_files,
             commands::library::list_directory_files,
             commands::library::scan_localization_files,
+            commands::library::detect_installed_languages,
+            commands::library::start_directory_scan,
+            commands::library::cancel_directory_scan,
             commands::library::read_text_file,
+            commands::library::read_text_file_range,
             commands::games::get_games,
             commands::games::get_games_fast,
             commands::games::force_refresh_all_games,
             commands::games::detect_engine_for_game,
             commands::games::translate_text_simple,
             commands::games::find_executables_in_folder,
+            commands::games::resolve_game_executable,
             commands::games::launch_executable,
             commands::games::get_game_by_id,
             commands::games::scan_games,
             commands::utilities::get_howlongtobeat_info,
-            commands::utilities::get_steamgriddb_artwork,
-            commands::utilities::get_preferences,
-            commands::utilities::update_preferences,
+            commands::steamgriddb::get_steamgriddb_artwork,
+            commands::steamgriddb::get_steamgriddb_artwork_batch,
+            commands::steamgriddb::clear_steamgriddb_cache,
+            commands::preferences::get_preferences,
+            commands::preferences::update_preferences,
+            commands::preferences::reset_preferences,
             commands::utilities::save_app_settings,
             commands::utilities::load_app_settings,
             commands::utilities::clear_cache,
@@ -242,14 +285,30 @@ fn main() {
             commands::patches::get_translation_suggestions,
             commands::patches::export_translations,
             commands::patches::import_translations,
+            commands::patches::import_translations_from_directory,
+            commands::patches::diff_patches,
+            commands::patches::merge_patches,
+            commands::patches::get_translation_progress,
+            commands::patches::get_all_translation_progress,
+            commands::patches::lint_patch_against_glossary,
+            commands::patches::validate_patch_placeholders,
+            commands::patches::find_length_overflows,
             commands::injekt::start_injection,
             commands::injekt::stop_injection,
+            commands::injekt::force_cleanup_injection,
+            commands::injekt::check_debug_privilege,
+            commands::injekt::enable_debug_privilege,
+            commands::injekt::get_privilege_status,
+            commands::injekt::launch_and_inject,
             commands::injekt::get_injection_stats,
             commands::injekt::test_injection,
             commands::injekt::get_processes,
             commands::injekt::get_process_info,
+            commands::injekt::list_injectable_windows,
+            commands::injekt::get_window_process_info,
             commands::injekt::inject_translation,
             commands::injekt::scan_process_memory,
+            commands::injekt::scan_process_for_text,
             // Multi-Process Injection
             commands::injekt::start_multi_process_injection,
             commands::injekt::stop_multi_process_injection,
@@ -257,6 +316,9 @@ fn main() {
             commands::injekt::get_multi_process_active_processes,
             commands::injekt::force_inject_process,
             commands::injekt::list_multi_process_games,
+            commands::injekt::get_injection_allowlist,
+            commands::injekt::add_injection_allowlist_entry,
+            commands::injekt::remove_injection_allowlist_entry,
             // Anti-Cheat System
             commands::anti_cheat::detect_anti_cheat_systems,
             commands::anti_cheat::get_anti_cheat_compatibility_strategies,
@@ -371,19 +433,28 @@ fn main() {
             commands::profiles::logout,
             commands::profiles::update_settings,
             commands::profiles::delete_profile,
+            commands::profiles::cleanup_orphaned_profile_data,
             commands::profiles::export_profile,
             commands::profiles::import_profile,
             commands::profiles::validate_export_file,
             commands::profiles::create_profile_backup,
+            commands::profile_backups::get_profile_backup_schedule,
+            commands::profile_backups::set_profile_backup_schedule,
+            commands::profile_backups::list_profile_backups,
+            commands::profile_backups::restore_profile_backup,
             commands::profiles::get_auth_stats,
             commands::profiles::get_profile_avatar,
             commands::profiles::update_profile_avatar,
             commands::profiles::is_session_expired,
             commands::profiles::renew_session,
             commands::profiles::get_session_time_remaining,
+            commands::profiles::get_session_status,
+            commands::profiles::touch_session,
             commands::profiles::can_authenticate,
             commands::profiles::unlock_profile,
             commands::profiles::get_failed_attempts,
+            commands::profiles::get_lockout_status,
+            commands::profiles::reset_failed_attempts,
 
             // Profile Settings Management System
             commands::profile_settings::load_profile_settings,
@@ -411,6 +482,7 @@ fn main() {
             commands::migration::backup_legacy_settings,
             commands::migration::cleanup_legacy_settings,
             commands::migration::migration_wizard,
+            commands::migration::migrate_all_legacy_store_credentials,
 
             // Validation System
             commands::validation::validate_profile_name,
@@ -434,6 +506,7 @@ fn main() {
             commands::glossary::export_glossary,
             commands::glossary::import_glossary,
             commands::glossary::search_glossary,
+            commands::glossary::search_glossary_scored,
 
             // Translation Memory System
             commands::translation_memory::load_translation_memory,
@@ -442,13 +515,18 @@ fn main() {
             commands::translation_memory::delete_translation_memory,
             commands::translation_memory::export_translation_memory_tmx,
             commands::translation_memory::import_translation_memory_tmx,
+            commands::translation_memory::validate_tmx,
             commands::translation_memory::search_translation_memory,
+            commands::translation_memory::tm_fuzzy_lookup,
             commands::translation_memory::add_translation_to_memory,
             commands::translation_memory::add_translations_batch,
+            commands::translation_memory::tm_upsert_unit,
 
             // File Manager
             commands::file_manager::save_file_with_backup,
             commands::file_manager::read_file_content,
+            commands::file_manager::read_file_with_encoding,
+            commands::file_manager::write_file_with_encoding,
             commands::file_manager::list_file_backups,
             commands::file_manager::restore_backup,
             commands::file_manager::get_desktop_path,
@@ -459,8 +537,12 @@ fn main() {
             // Unity Patcher
             commands::unity_patcher::check_game_engine,
             commands::unity_patcher::install_unity_autotranslator,
+            commands::unity_patcher::check_unity_autotranslator,
+            commands::unity_patcher::detect_unity_backend_info,
             commands::unity_patcher::remove_unity_patch,
+            commands::unity_patcher::uninstall_unity_autotranslator,
             commands::unity_patcher::detect_localization_files,
+            commands::unity_patcher::prepare_godot_translation,
             commands::unity_patcher::apply_translation_file,
             commands::unity_patcher::get_translation_recommendation,
             commands::unity_patcher::read_xunity_translations,
@@ -475,8 +557,21 @@ fn main() {
             ocr_translator::start_ocr_translator,
             ocr_translator::stop_ocr_translator,
             ocr_translator::get_detected_texts,
+            ocr_translator::get_overlay_texts,
             ocr_translator::is_ocr_running,
+            ocr_translator::get_ocr_cache_stats,
+            ocr_translator::get_ocr_capture_status,
+            ocr_translator::get_ocr_timing_stats,
+            ocr_translator::detect_language,
+            ocr_translator::save_ocr_profile,
+            ocr_translator::load_ocr_profile,
+            ocr_translator::list_ocr_profiles,
+            ocr_translator::delete_ocr_profile,
+            ocr_translator::export_ocr_session_to_patch,
             ocr_translator::list_capture_windows,
+            ocr_translator::find_window_by_title,
+            ocr_translator::capture_full_screenshot,
+            ocr_translator::validate_capture_region,
             ocr_translator::toggle_ocr_overlay,
             ocr_translator::position_overlay_on_window,
 
@@ -495,6 +590,8 @@ fn main() {
             commands::ue_translator::get_ue_cache_stats,
             commands::ue_translator::clear_ue_cache,
             commands::ue_translator::check_ue_translator_compatibility,
+            commands::ue_translator::parse_ue_locres,
+            commands::ue_translator::write_ue_locres,
 
             // Translation Bridge (In-Game Translation System)
             commands::translation_bridge::translation_bridge_start,
@@ -504,9 +601,14 @@ fn main() {
             commands::translation_bridge::translation_bridge_dictionary_stats,
             commands::translation_bridge::translation_bridge_load_translations,
             commands::translation_bridge::translation_bridge_load_json,
+            commands::translation_bridge::translation_bridge_reload_from_file,
             commands::translation_bridge::translation_bridge_set_languages,
             commands::translation_bridge::translation_bridge_add_translation,
+            commands::translation_bridge::translation_bridge_add_translations,
             commands::translation_bridge::translation_bridge_get_translation,
+            commands::translation::translate_offline,
+            commands::translation::translate_offline_batch,
+            commands::translation::translate_file,
             commands::translation_bridge::translation_bridge_export_json,
             commands::translation_bridge::translation_bridge_clear,
 
@@ -527,6 +629,8 @@ fn main() {
             commands::activity_history::activity_count_by_type,
             commands::activity_history::activity_delete,
             commands::activity_history::activity_clear,
+            commands::activity_history::set_activity_retention,
+            commands::activity_history::get_activity_retention,
 
             // Game Dictionaries System
             commands::game_dictionaries::list_installed_dictionaries,
@@ -545,50 +649,12 @@ fn main() {
             commands::game_dictionaries::import_from_xunity,
             commands::game_dictionaries::get_dictionaries_stats,
 
-            // NOTIFICATION SYSTEM TEMPORANEAMENTE DISABILITATO PER ERRORI COMPILAZIONE
-            // commands::notifications::create_notification,
-            // commands::notifications::get_notifications,
-            // commands::notifications::get_notifications_sorted,
-            // commands::notifications::mark_notification_as_read,
-            // commands::notifications::mark_multiple_notifications_as_read,
-            // commands::notifications::mark_all_notifications_as_read,
-            // commands::notifications::delete_notification,
-            // commands::notifications::get_unread_notifications_count,
-            // commands::notifications::get_notification_counts,
-            // commands::notifications::clear_all_notifications,
-            // commands::notifications::get_notification_preferences,
-            // commands::notifications::update_notification_preferences,
-            // commands::notifications::update_partial_notification_preferences,
-            // commands::notifications::auto_save_notification_preferences,
-            // commands::notifications::sync_notification_preferences_on_profile_switch,
-            // commands::notifications::toggle_notification_type,
-            // commands::notifications::reset_notification_preferences_to_default,
-            // commands::notifications::handle_profile_event,
-            // commands::notifications::handle_profile_switch,
-            // commands::notifications::cleanup_profile_notifications,
-            // commands::notifications::generate_notification_security_report, // TODO: Implementare
-            // commands::notifications::verify_profile_notifications_integrity,
-            // commands::notifications::get_high_priority_unread_notifications,
-            // commands::notifications::cleanup_expired_notifications,
-            // commands::notifications::create_authentication_error_notification,
-            // commands::notifications::create_profile_locked_notification,
-            // commands::notifications::create_credential_operation_notification,
-            // commands::notifications::create_settings_update_notification,
-            // commands::notifications::create_backup_notification,
-            
-            // System Notification Commands - TODO: Implementare quando necessario
-            // commands::notifications::create_system_broadcast_notification,
-            // commands::notifications::create_urgent_system_notification,
-            // commands::notifications::create_maintenance_notification,
-            // commands::notifications::create_update_available_notification,
-            // commands::notifications::get_active_system_notifications,
-            // commands::notifications::get_system_notification_stats,
-            // commands::notifications::delete_system_notification_from_all_profiles,
-            // commands::notifications::update_system_notification_expiry,
-            // commands::notifications::update_system_notification_priority,
-            // commands::notifications::get_system_notification_read_status,
-            // commands::notifications::expire_old_system_notifications,
-            // commands::notifications::get_profiles_for_notification_admin,
+            // Notification System
+            commands::notifications::create_notification,
+            commands::notifications::get_notifications,
+            commands::notifications::mark_notification_as_read,
+            commands::notifications::delete_notification,
+            commands::notifications::get_unread_notifications_count,
 
             // Update System
             commands::updates::check_for_updates,
@@ -651,6 +717,7 @@ fn main() {
             commands::global_hotkeys::clear_global_hotkeys,
             // Store Detection
             commands::store_detection::get_installed_stores,
+            commands::store_detection::get_all_connection_statuses,
             commands::store_detection::save_stores_config,
             commands::store_detection::load_stores_config,
             commands::store_detection::add_custom_game_folder,
@@ -720,9 +787,18 @@ fn main() {
             commands::community_hub::community_get_stats,
             commands::community_hub::community_delete_package,
         ])
-        .setup(|_app| {
+        .setup(|app| {
+            let profile_manager = app.state::<ProfileManagerState>().manager.clone();
+            commands::profiles::start_session_monitor(app.handle().clone(), profile_manager);
+            commands::profile_backups::start_profile_backup_scheduler(app.handle().clone());
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            // Nessun gioco deve restare con hook installati dopo la chiusura
+            if let tauri::RunEvent::Exit = event {
+                commands::injekt::shutdown_all_injections();
+            }
+        });
 }
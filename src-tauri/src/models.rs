@@ -91,7 +91,12 @@ pub struct SharedGame {
     pub appid: u32,
     pub name: String,
     pub owner_steam_id: String,
+    /// Nome da mostrare per chi presta il gioco (persona name, account o fallback)
     pub owner_account_name: String,
+    #[serde(default)]
+    pub owner_persona_name: Option<String>,
+    #[serde(default)]
+    pub owner_avatar_url: Option<String>,
     pub is_shared: bool,
 }
 
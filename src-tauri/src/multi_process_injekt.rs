@@ -2,14 +2,223 @@
 // Gestisce l'injection in giochi con architetture multi-processo
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
+use std::path::PathBuf;
 use chrono::{DateTime, Utc};
-use crate::injekt::{InjektTranslator, InjectionConfig};
+use once_cell::sync::Lazy;
+use crate::anti_cheat::AntiCheatManager;
+use crate::injekt::{InjektTranslator, InjectionConfig, restore_process_hooks};
 use crate::process_utils::is_process_running;
+use tauri::{AppHandle, Emitter};
+
+/// Processi di sistema e launcher in cui non si inietta mai (confronto esatto)
+pub const DENIED_PROCESSES: &[&str] = &[
+    "system", "registry", "smss.exe", "csrss.exe", "wininit.exe", "winlogon.exe",
+    "services.exe", "lsass.exe", "svchost.exe", "dwm.exe", "explorer.exe",
+    "audiodg.exe", "fontdrvhost.exe", "conhost.exe", "msmpeng.exe",
+    "steam.exe", "steamwebhelper.exe", "epicgameslauncher.exe",
+];
+
+/// Pattern di processi anti-cheat in cui non si inietta mai (confronto per sottostringa)
+pub const DENIED_PROCESS_PATTERNS: &[&str] = &[
+    "easyanticheat", "battleye", "beservice", "bedaisy", "vgc.exe", "vgtray",
+    "faceit", "xigncode", "gameguard", "nprotect", "pnkbstr", "punkbuster",
+    "ricochet", "mhyprot", "ace-base", "ace-tray",
+];
+
+/// Restituisce la voce di denylist che blocca il processo, se presente
+pub fn denylist_match(process_name: &str) -> Option<&'static str> {
+    let name = process_name.to_lowercase();
+    DENIED_PROCESSES.iter().copied().find(|entry| name == *entry)
+        .or_else(|| DENIED_PROCESS_PATTERNS.iter().copied().find(|pattern| name.contains(pattern)))
+}
+
+/// Voce dell'allowlist: per nome eseguibile o per appid del gioco
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AllowlistEntry {
+    #[serde(default)]
+    pub exe_name: Option<String>,
+    #[serde(default)]
+    pub app_id: Option<String>,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Allowlist persistita dei target di injection multi-processo.
+/// Se vuota non filtra nulla (restano attivi denylist e controllo anti-cheat).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InjectionAllowlist {
+    pub entries: Vec<AllowlistEntry>,
+}
+
+impl InjectionAllowlist {
+    fn path() -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("GameStringer")
+            .join("injection_allowlist.json")
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&path, json).map_err(|e| e.to_string())
+    }
+
+    /// Aggiunge una voce; restituisce false se era già presente
+    pub fn add(&mut self, exe_name: Option<String>, app_id: Option<String>) -> Result<bool, String> {
+        let exe_name = exe_name.map(|n| n.trim().to_lowercase()).filter(|n| !n.is_empty());
+        let app_id = app_id.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+        if exe_name.is_none() && app_id.is_none() {
+            return Err("Specificare almeno exe_name o app_id".to_string());
+        }
+        if self.entries.iter().any(|e| e.exe_name == exe_name && e.app_id == app_id) {
+            return Ok(false);
+        }
+        self.entries.push(AllowlistEntry { exe_name, app_id, added_at: Utc::now() });
+        Ok(true)
+    }
+
+    /// Rimuove le voci corrispondenti; restituisce quante ne sono state rimosse
+    pub fn remove(&mut self, exe_name: Option<&str>, app_id: Option<&str>) -> usize {
+        let exe_name = exe_name.map(|n| n.trim().to_lowercase());
+        let before = self.entries.len();
+        self.entries.retain(|e| {
+            let exe_hit = exe_name.as_ref().map_or(false, |n| e.exe_name.as_ref() == Some(n));
+            let app_hit = app_id.map_or(false, |a| e.app_id.as_deref() == Some(a));
+            !(exe_hit || app_hit)
+        });
+        before - self.entries.len()
+    }
+
+    pub fn is_allowed(&self, process_name: &str, app_id: Option<&str>) -> bool {
+        if self.entries.is_empty() {
+            return true;
+        }
+        let name = process_name.to_lowercase();
+        self.entries.iter().any(|e| {
+            e.exe_name.as_deref().map_or(false, |n| n == name)
+                || (e.app_id.is_some() && e.app_id.as_deref() == app_id)
+        })
+    }
+}
+
+pub static INJECTION_ALLOWLIST: Lazy<Mutex<InjectionAllowlist>> =
+    Lazy::new(|| Mutex::new(InjectionAllowlist::load()));
+
+/// Verifica se un processo può ricevere l'injection: denylist, allowlist e anti-cheat
+/// (quest'ultimo aggirabile solo con override forzato).
+/// Restituisce il motivo dello scarto se il processo va saltato.
+pub fn check_injection_target(
+    pid: u32,
+    process_name: &str,
+    app_id: Option<&str>,
+    check_allowlist: bool,
+    force_anti_cheat: bool,
+    anti_cheat: &AntiCheatManager,
+) -> Result<(), String> {
+    if let Some(entry) = denylist_match(process_name) {
+        return Err(format!("processo in denylist ({})", entry));
+    }
+    if check_allowlist {
+        let allowed = INJECTION_ALLOWLIST.lock()
+            .map(|list| list.is_allowed(process_name, app_id))
+            .unwrap_or(false);
+        if !allowed {
+            return Err("processo non presente nell'allowlist".to_string());
+        }
+    }
+    anti_cheat.check_injection_gate(pid, force_anti_cheat)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Evento emesso quando un target esce dal set attivo (processo terminato o IPC bloccato)
+pub const TARGET_DROPPED_EVENT: &str = "multi-process-target-dropped";
+/// Secondi senza scambi IPC prima di considerare bloccato un target iniettato
+const DEFAULT_IPC_TIMEOUT_SECS: u64 = 30;
+/// L'injector aggiorna il suo heartbeat ogni 5s: timeout più brevi darebbero falsi positivi
+const MIN_IPC_TIMEOUT_SECS: u64 = 10;
+
+/// Tentativi falliti (injection non avviata o IPC bloccato) dopo cui il watchdog rinuncia su un PID
+const MAX_INJECTION_FAILURES: u32 = 3;
+/// Backoff tra un tentativo e l'altro: raddoppia a ogni fallimento fino al massimo
+const INJECTION_RETRY_BASE_SECS: i64 = 5;
+const INJECTION_RETRY_MAX_SECS: i64 = 120;
+
+/// Fallimenti di injection di un singolo PID, con il momento del prossimo tentativo consentito
+#[derive(Debug, Clone)]
+struct InjectionAttempts {
+    failures: u32,
+    retry_at: DateTime<Utc>,
+    /// Avvio riuscito più recente, se da allora non ci sono stati altri fallimenti
+    running_since: Option<DateTime<Utc>>,
+}
+
+impl InjectionAttempts {
+    fn record_failure(&mut self, now: DateTime<Utc>) {
+        self.failures += 1;
+        self.running_since = None;
+        let delay = (INJECTION_RETRY_BASE_SECS << (self.failures - 1).min(10)).min(INJECTION_RETRY_MAX_SECS);
+        self.retry_at = now + chrono::Duration::seconds(delay);
+    }
+
+    fn record_start(&mut self, now: DateTime<Utc>) {
+        self.running_since = Some(now);
+    }
+
+    /// Vero se l'injection è attiva da almeno `window` senza nuovi fallimenti: i tentativi
+    /// vanno azzerati. Un avvio riuscito da solo non basta, un IPC bloccato subito dopo
+    /// deve continuare a contare verso la rinuncia.
+    fn recovered(&self, now: DateTime<Utc>, window: chrono::Duration) -> bool {
+        self.running_since.is_some_and(|since| now - since >= window)
+    }
+
+    fn exhausted(&self) -> bool {
+        self.failures >= MAX_INJECTION_FAILURES
+    }
+
+    fn ready(&self, now: DateTime<Utc>) -> bool {
+        !self.exhausted() && now >= self.retry_at
+    }
+
+    /// Motivo mostrato in `skip_reason` finché il PID non viene reiniettato
+    fn describe(&self) -> String {
+        if self.exhausted() {
+            format!("injection abbandonata dopo {} tentativi falliti", self.failures)
+        } else {
+            format!(
+                "injection fallita ({}/{}), nuovo tentativo alle {}",
+                self.failures,
+                MAX_INJECTION_FAILURES,
+                self.retry_at.format("%H:%M:%S")
+            )
+        }
+    }
+}
+
+impl Default for InjectionAttempts {
+    fn default() -> Self {
+        Self { failures: 0, retry_at: Utc::now(), running_since: None }
+    }
+}
+
+fn default_auto_reinject() -> bool {
+    true
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultiProcessConfig {
@@ -19,6 +228,58 @@ pub struct MultiProcessConfig {
     pub injection_strategy: InjectionStrategy,
     pub sync_translations: bool,
     pub max_processes: u32,
+    /// AppID del gioco, usato per l'allowlist
+    #[serde(default)]
+    pub app_id: Option<String>,
+    /// Reinietta automaticamente un processo già iniettato quando viene riavviato
+    #[serde(default = "default_auto_reinject")]
+    pub auto_reinject_on_restart: bool,
+    /// Secondi senza scambi IPC dopo cui un target è considerato bloccato (default 30)
+    #[serde(default)]
+    pub ipc_timeout_secs: Option<u64>,
+}
+
+impl MultiProcessConfig {
+    fn ipc_timeout(&self) -> i64 {
+        self.ipc_timeout_secs.unwrap_or(DEFAULT_IPC_TIMEOUT_SECS).max(MIN_IPC_TIMEOUT_SECS) as i64
+    }
+}
+
+/// Motivo per cui un target è stato rimosso dal set attivo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetDropReason {
+    ProcessExited,
+    IpcStalled,
+}
+
+/// Payload di TARGET_DROPPED_EVENT
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetDroppedEvent {
+    pub game_name: String,
+    pub pid: u32,
+    pub name: String,
+    pub is_primary: bool,
+    pub was_injected: bool,
+    pub reason: TargetDropReason,
+    pub last_ipc_at: Option<DateTime<Utc>>,
+    pub dropped_at: DateTime<Utc>,
+}
+
+/// Stato di salute di un target tracciato
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetLiveness {
+    pub pid: u32,
+    pub name: String,
+    pub is_primary: bool,
+    pub injection_active: bool,
+    pub alive: bool,
+    pub last_seen: DateTime<Utc>,
+    /// Ultimo scambio IPC riuscito con l'injector
+    pub last_ipc_at: Option<DateTime<Utc>>,
+    pub ipc_age_seconds: Option<i64>,
+    pub stalled: bool,
+    pub skip_reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,13 +296,16 @@ pub enum InjectionStrategy {
 
 #[derive(Debug, Clone)]
 pub struct ProcessInfo {
-    #[allow(dead_code)] // PID processo - essenziale per identificazione processo target
     pub pid: u32,
     pub name: String,
     pub is_primary: bool,
     pub injection_active: bool,
     pub last_seen: DateTime<Utc>,
     pub injector: Option<Arc<Mutex<InjektTranslator>>>,
+    /// Motivo per cui il processo è stato escluso dall'injection
+    pub skip_reason: Option<String>,
+    /// Ultimo scambio IPC riuscito con l'injector (heartbeat)
+    pub last_ipc_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug)]
@@ -53,6 +317,8 @@ pub struct MultiProcessInjekt {
     monitor_thread: Option<thread::JoinHandle<()>>,
     translation_cache: Arc<Mutex<HashMap<String, String>>>,
     stats: Arc<Mutex<MultiProcessStats>>,
+    /// Per emettere TARGET_DROPPED_EVENT (assente nei test / uso senza UI)
+    app_handle: Option<AppHandle>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +331,7 @@ pub struct MultiProcessStats {
     pub synchronized_translations: u32,
     pub last_process_scan: Option<String>,
     pub uptime_seconds: u64,
+    pub skipped_processes: u32,
 }
 
 // SAFETY: MultiProcessInjekt contiene solo tipi thread-safe (Arc<Mutex<T>>, JoinHandle)
@@ -90,10 +357,17 @@ impl MultiProcessInjekt {
                 synchronized_translations: 0,
                 last_process_scan: None,
                 uptime_seconds: 0,
+                skipped_processes: 0,
             })),
+            app_handle: None,
         })
     }
 
+    /// Abilita l'emissione degli eventi verso il frontend
+    pub fn set_app_handle(&mut self, app: AppHandle) {
+        self.app_handle = Some(app);
+    }
+
     pub fn start(&mut self) -> Result<(), Box<dyn Error>> {
         log::info!("🚀 Avvio sistema multi-processo per: {}", self.config.game_name);
         
@@ -134,29 +408,74 @@ impl MultiProcessInjekt {
         let translation_cache = Arc::clone(&self.translation_cache);
         let config = self.config.clone();
         let base_config = self.base_injection_config.clone();
+        let app_handle = self.app_handle.clone();
         let start_time = Utc::now();
         
         let monitor_thread = thread::spawn(move || {
             log::info!("🔍 Avvio monitoraggio processi multi-processo");
+            let anti_cheat = AntiCheatManager::new();
+            // Nomi dei processi iniettati e poi terminati: se ricompaiono è un riavvio
+            let mut restarted_names: HashSet<String> = HashSet::new();
+            // Watchdog: fallimenti per PID, azzerati quando il processo termina
+            let mut attempts: HashMap<u32, InjectionAttempts> = HashMap::new();
             
             while *is_running.lock().unwrap() {
                 // Scansiona processi attivi
                 match Self::scan_game_processes(&config.game_name, &config.primary_process, &config.secondary_processes) {
                     Ok(discovered_processes) => {
                         let mut processes = active_processes.lock().unwrap();
-                        let mut current_pids: Vec<u32> = Vec::new();
+                        let current_pids: Vec<u32> = discovered_processes.iter().map(|(pid, _, _)| *pid).collect();
+                        
+                        // Liveness prima dei nuovi processi, così un riavvio viene riconosciuto come tale
+                        for event in Self::drop_dead_targets(&config, &mut processes, &current_pids) {
+                            match event.reason {
+                                TargetDropReason::ProcessExited if event.was_injected => {
+                                    restarted_names.insert(event.name.to_lowercase());
+                                }
+                                // Il processo è ancora vivo: conta come fallimento, il watchdog
+                                // lo reinietta con backoff e alla fine rinuncia
+                                TargetDropReason::IpcStalled if event.was_injected => {
+                                    Self::record_injection_failure(&mut attempts, event.pid, &event.name);
+                                }
+                                _ => {}
+                            }
+                            if let Some(app) = &app_handle {
+                                if let Err(e) = app.emit(TARGET_DROPPED_EVENT, &event) {
+                                    log::warn!("⚠️ Emissione {} fallita: {}", TARGET_DROPPED_EVENT, e);
+                                }
+                            }
+                        }
+                        // Un target rimasto attivo per un intero timeout IPC è tornato sano: si riparte da zero
+                        let recovery_window = chrono::Duration::seconds(config.ipc_timeout());
+                        attempts.retain(|pid, pid_attempts| {
+                            current_pids.contains(pid) && !pid_attempts.recovered(Utc::now(), recovery_window)
+                        });
                         
                         // Aggiorna processi esistenti e aggiungi nuovi
                         for (pid, name, is_primary) in discovered_processes {
-                            current_pids.push(pid);
-                            
                             if let Some(process_info) = processes.get_mut(&pid) {
                                 // Aggiorna processo esistente
                                 process_info.last_seen = Utc::now();
+                                
+                                // Watchdog: ritenta un'injection fallita quando il backoff è scaduto
+                                if !process_info.injection_active && attempts.get(&pid).is_some_and(|a| a.ready(Utc::now())) {
+                                    match check_injection_target(pid, &name, config.app_id.as_deref(), true, base_config.force_anti_cheat_override, &anti_cheat) {
+                                        Ok(()) => {
+                                            log::info!("🔁 Nuovo tentativo di injection per {} (PID: {})", name, pid);
+                                            Self::inject_with_watchdog(process_info, &mut attempts, &base_config, &translation_cache);
+                                        }
+                                        Err(reason) => {
+                                            log::warn!("⛔ Processo saltato: {} (PID: {}) - {}", name, pid, reason);
+                                            process_info.skip_reason = Some(reason);
+                                            attempts.remove(&pid);
+                                        }
+                                    }
+                                }
                             } else {
                                 // Nuovo processo rilevato
                                 log::info!("🆕 Nuovo processo rilevato: {} (PID: {}, Primary: {})", name, pid, is_primary);
                                 
+                                let restarted = restarted_names.remove(&name.to_lowercase());
                                 let should_inject = match &config.injection_strategy {
                                     InjectionStrategy::PrimaryOnly => is_primary,
                                     InjectionStrategy::AllProcesses => true,
@@ -181,43 +500,44 @@ impl MultiProcessInjekt {
                                     injection_active: false,
                                     last_seen: Utc::now(),
                                     injector: None,
+                                    skip_reason: None,
+                                    last_ipc_at: None,
                                 };
                                 
-                                // Avvia injection se necessario
+                                // Riavvio di un processo già iniettato: decide l'opzione auto-reinject
+                                let should_inject = if restarted {
+                                    if config.auto_reinject_on_restart {
+                                        log::info!("🔁 Riavvio rilevato: {} (PID: {}), reinjection automatica", name, pid);
+                                        true
+                                    } else {
+                                        log::info!("⏸️ Riavvio rilevato: {} (PID: {}), reinjection automatica disabilitata", name, pid);
+                                        process_info.skip_reason = Some("riavvio rilevato, reinjection automatica disabilitata".to_string());
+                                        false
+                                    }
+                                } else {
+                                    should_inject
+                                };
+                                
+                                // Denylist, allowlist e anti-cheat prima di toccare il processo
                                 if should_inject {
-                                    match Self::start_injection_for_process(&mut process_info, &base_config, &translation_cache) {
-                                        Ok(()) => {
-                                            log::info!("✅ Injection avviata per processo: {} (PID: {})", name, pid);
-                                        }
-                                        Err(e) => {
-                                            log::error!("❌ Errore avvio injection per {}: {}", name, e);
-                                        }
+                                    if let Err(reason) = check_injection_target(pid, &name, config.app_id.as_deref(), true, base_config.force_anti_cheat_override, &anti_cheat) {
+                                        log::warn!("⛔ Processo saltato: {} (PID: {}) - {}", name, pid, reason);
+                                        process_info.skip_reason = Some(reason);
                                     }
                                 }
                                 
-                                processes.insert(pid, process_info);
-                            }
-                        }
-                        
-                        // Rimuovi processi terminati
-                        let mut terminated_pids = Vec::new();
-                        for (pid, process_info) in processes.iter() {
-                            if !current_pids.contains(pid) || !is_process_running(*pid) {
-                                terminated_pids.push(*pid);
-                                log::info!("🔴 Processo terminato: {} (PID: {})", process_info.name, pid);
-                            }
-                        }
-                        
-                        for pid in terminated_pids {
-                            if let Some(mut process_info) = processes.remove(&pid) {
-                                // Ferma injection se attiva
-                                if process_info.injection_active {
-                                    if let Some(injector) = process_info.injector.take() {
-                                        if let Ok(mut injector_guard) = injector.lock() {
-                                            let _ = injector_guard.stop();
-                                        }
+                                // Avvia injection se necessario, salvo PID in backoff o abbandonati dal watchdog
+                                if should_inject && process_info.skip_reason.is_none() {
+                                    let waiting = attempts.get(&pid)
+                                        .filter(|pid_attempts| !pid_attempts.ready(Utc::now()))
+                                        .map(InjectionAttempts::describe);
+                                    match waiting {
+                                        Some(reason) => process_info.skip_reason = Some(reason),
+                                        None => Self::inject_with_watchdog(&mut process_info, &mut attempts, &base_config, &translation_cache),
                                     }
                                 }
+                                
+                                processes.insert(pid, process_info);
                             }
                         }
                         
@@ -232,6 +552,9 @@ impl MultiProcessInjekt {
                             stats_guard.secondary_processes_active = processes.values()
                                 .filter(|p| !p.is_primary && p.injection_active)
                                 .count() as u32;
+                            stats_guard.skipped_processes = processes.values()
+                                .filter(|p| p.skip_reason.is_some())
+                                .count() as u32;
                             stats_guard.last_process_scan = Some(chrono::Utc::now().to_rfc3339());
                             stats_guard.uptime_seconds = Utc::now().signed_duration_since(start_time).num_seconds() as u64;
                         }
@@ -297,10 +620,117 @@ impl MultiProcessInjekt {
         
         process_info.injector = Some(injector_arc);
         process_info.injection_active = true;
+        process_info.last_ipc_at = Some(Utc::now());
         
         Ok(())
     }
 
+    /// Avvia l'injection registrando l'esito nel watchdog: un fallimento lascia il motivo
+    /// (prossimo tentativo o rinuncia) in `skip_reason`
+    fn inject_with_watchdog(
+        process_info: &mut ProcessInfo,
+        attempts: &mut HashMap<u32, InjectionAttempts>,
+        base_config: &InjectionConfig,
+        translation_cache: &Arc<Mutex<HashMap<String, String>>>,
+    ) {
+        match Self::start_injection_for_process(process_info, base_config, translation_cache) {
+            Ok(()) => {
+                process_info.skip_reason = None;
+                if let Some(pid_attempts) = attempts.get_mut(&process_info.pid) {
+                    pid_attempts.record_start(Utc::now());
+                }
+                log::info!("✅ Injection avviata per processo: {} (PID: {})", process_info.name, process_info.pid);
+            }
+            Err(e) => {
+                log::error!("❌ Errore avvio injection per {}: {}", process_info.name, e);
+                let pid_attempts = Self::record_injection_failure(attempts, process_info.pid, &process_info.name);
+                process_info.skip_reason = Some(pid_attempts.describe());
+            }
+        }
+    }
+
+    /// Conta un fallimento per il PID e pianifica il prossimo tentativo con backoff esponenziale
+    fn record_injection_failure<'a>(
+        attempts: &'a mut HashMap<u32, InjectionAttempts>,
+        pid: u32,
+        name: &str,
+    ) -> &'a InjectionAttempts {
+        let pid_attempts = attempts.entry(pid).or_default();
+        pid_attempts.record_failure(Utc::now());
+        if pid_attempts.exhausted() {
+            log::warn!("🛑 Injection abbandonata per {} (PID: {}) dopo {} tentativi falliti", name, pid, pid_attempts.failures);
+        } else {
+            log::info!("⏳ Injection fallita per {} (PID: {}): {}", name, pid, pid_attempts.describe());
+        }
+        pid_attempts
+    }
+
+    /// Ultimo heartbeat dell'injector, senza attendere un injector occupato
+    fn injector_heartbeat(process_info: &ProcessInfo) -> Option<DateTime<Utc>> {
+        let injector = process_info.injector.as_ref()?;
+        let guard = injector.try_lock().ok()?;
+        let heartbeat = guard.get_stats().get("last_heartbeat")?.as_str()?.to_string();
+        DateTime::parse_from_rfc3339(&heartbeat).ok().map(|t| t.with_timezone(&Utc))
+    }
+
+    /// Aggiorna la liveness dei target e rimuove quelli morti (processo terminato) o con
+    /// IPC bloccato oltre il timeout, fermando l'injector e ripristinando la memoria
+    fn drop_dead_targets(
+        config: &MultiProcessConfig,
+        processes: &mut HashMap<u32, ProcessInfo>,
+        current_pids: &[u32],
+    ) -> Vec<TargetDroppedEvent> {
+        let now = Utc::now();
+        let mut dropped = Vec::new();
+        
+        for (pid, process_info) in processes.iter_mut() {
+            if let Some(heartbeat) = Self::injector_heartbeat(process_info) {
+                process_info.last_ipc_at = Some(heartbeat);
+            }
+            
+            let reason = if !current_pids.contains(pid) || !is_process_running(*pid) {
+                log::info!("🔴 Processo terminato: {} (PID: {})", process_info.name, pid);
+                TargetDropReason::ProcessExited
+            } else if process_info.injection_active
+                && process_info.last_ipc_at.is_some_and(|t| (now - t).num_seconds() > config.ipc_timeout())
+            {
+                log::warn!("⏳ IPC bloccato per {} (PID: {}), target rimosso", process_info.name, pid);
+                TargetDropReason::IpcStalled
+            } else {
+                continue;
+            };
+            dropped.push((*pid, reason));
+        }
+        
+        dropped.into_iter()
+            .filter_map(|(pid, reason)| {
+                let mut process_info = processes.remove(&pid)?;
+                let was_injected = process_info.injection_active;
+                // Ferma injection se attiva; se l'injector è bloccato ripristina la memoria direttamente
+                if let Some(injector) = process_info.injector.take() {
+                    match injector.try_lock() {
+                        Ok(mut injector_guard) => {
+                            let _ = injector_guard.stop();
+                        }
+                        Err(_) => {
+                            restore_process_hooks(pid, None);
+                        }
+                    }
+                }
+                Some(TargetDroppedEvent {
+                    game_name: config.game_name.clone(),
+                    pid,
+                    name: process_info.name,
+                    is_primary: process_info.is_primary,
+                    was_injected,
+                    reason,
+                    last_ipc_at: process_info.last_ipc_at,
+                    dropped_at: now,
+                })
+            })
+            .collect()
+    }
+
     fn synchronize_translations(
         active_processes: &Arc<Mutex<HashMap<u32, ProcessInfo>>>,
         _translation_cache: &Arc<Mutex<HashMap<String, String>>>
@@ -372,14 +802,63 @@ impl MultiProcessInjekt {
         }
     }
 
-    pub fn get_active_processes(&self) -> Vec<(u32, String, bool, bool)> {
-        if let Ok(processes) = self.active_processes.lock() {
-            processes.iter()
-                .map(|(pid, info)| (*pid, info.name.clone(), info.is_primary, info.injection_active))
-                .collect()
-        } else {
-            Vec::new()
+    /// PID di tutti i processi tracciati da questa istanza
+    pub fn tracked_pids(&self) -> Vec<u32> {
+        self.active_processes.lock()
+            .map(|processes| processes.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Processi tracciati con stato di salute (processo vivo, ultimo scambio IPC, blocco)
+    pub fn get_target_liveness(&self) -> Vec<TargetLiveness> {
+        let now = Utc::now();
+        let timeout = self.config.ipc_timeout();
+        let mut targets: Vec<TargetLiveness> = self.active_processes.lock()
+            .map(|processes| processes.values()
+                .map(|info| {
+                    let last_ipc_at = Self::injector_heartbeat(info).or(info.last_ipc_at);
+                    let ipc_age_seconds = last_ipc_at.map(|t| (now - t).num_seconds().max(0));
+                    TargetLiveness {
+                        pid: info.pid,
+                        name: info.name.clone(),
+                        is_primary: info.is_primary,
+                        injection_active: info.injection_active,
+                        alive: is_process_running(info.pid),
+                        last_seen: info.last_seen,
+                        last_ipc_at,
+                        ipc_age_seconds,
+                        stalled: info.injection_active && ipc_age_seconds.is_some_and(|age| age > timeout),
+                        skip_reason: info.skip_reason.clone(),
+                    }
+                })
+                .collect())
+            .unwrap_or_default();
+        targets.sort_by_key(|t| t.pid);
+        targets
+    }
+
+    pub fn ipc_timeout_secs(&self) -> i64 {
+        self.config.ipc_timeout()
+    }
+
+    /// Ferma l'injection di un singolo processo senza arrestare l'istanza.
+    /// Non attende un injector bloccato: in quel caso il chiamante ripristina la memoria direttamente.
+    pub fn stop_process_injection(&mut self, pid: u32) -> bool {
+        let Ok(mut processes) = self.active_processes.lock() else {
+            return false;
+        };
+        let Some(process_info) = processes.get_mut(&pid) else {
+            return false;
+        };
+        process_info.injection_active = false;
+        let Some(injector) = process_info.injector.take() else {
+            return false;
+        };
+        if let Ok(mut injector_guard) = injector.try_lock() {
+            return injector_guard.stop().is_ok();
         }
+        log::warn!("⚠️ Injector del PID {} bloccato, arresto saltato", pid);
+        false
     }
 
     pub fn force_inject_process(&mut self, pid: u32) -> Result<(), Box<dyn Error>> {
@@ -388,6 +867,10 @@ impl MultiProcessInjekt {
         if let Ok(mut processes) = self.active_processes.lock() {
             if let Some(process_info) = processes.get_mut(&pid) {
                 if !process_info.injection_active {
+                    // Forzare salta l'allowlist, ma mai la denylist
+                    check_injection_target(pid, &process_info.name, self.config.app_id.as_deref(), false, self.base_injection_config.force_anti_cheat_override, &AntiCheatManager::new())
+                        .map_err(|reason| format!("Injection rifiutata per PID {}: {}", pid, reason))?;
+                    process_info.skip_reason = None;
                     Self::start_injection_for_process(
                         process_info,
                         &self.base_injection_config,
@@ -405,3 +888,64 @@ impl MultiProcessInjekt {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn injection_retry_delay_doubles_up_to_the_cap() {
+        let mut attempts = InjectionAttempts { failures: 0, retry_at: at(0), running_since: None };
+
+        let mut delays = Vec::new();
+        for _ in 0..7 {
+            attempts.record_failure(at(0));
+            delays.push((attempts.retry_at - at(0)).num_seconds());
+        }
+        assert_eq!(delays, vec![5, 10, 20, 40, 80, 120, 120]);
+    }
+
+    #[test]
+    fn injection_is_abandoned_after_repeated_failures() {
+        let mut attempts = InjectionAttempts::default();
+
+        attempts.record_failure(at(0));
+        assert!(!attempts.ready(at(4)));
+        assert!(attempts.ready(at(5)));
+
+        attempts.record_failure(at(5));
+        assert!(attempts.ready(at(15)));
+        assert!(!attempts.exhausted());
+
+        attempts.record_failure(at(15));
+        assert!(attempts.exhausted());
+        // Nessun nuovo tentativo, nemmeno a backoff scaduto
+        assert!(!attempts.ready(at(10_000)));
+        assert!(attempts.describe().contains("abbandonata"));
+    }
+
+    #[test]
+    fn injection_attempts_reset_only_after_a_healthy_window() {
+        let window = chrono::Duration::seconds(DEFAULT_IPC_TIMEOUT_SECS as i64);
+        let mut attempts = InjectionAttempts::default();
+        attempts.record_failure(at(0));
+        attempts.record_failure(at(5));
+        assert!(!attempts.recovered(at(100), window));
+
+        // Avvio riuscito seguito da IPC bloccato: il fallimento continua a contare
+        attempts.record_start(at(20));
+        attempts.record_failure(at(45));
+        assert!(attempts.exhausted());
+        assert!(!attempts.recovered(at(1_000), window));
+
+        let mut attempts = InjectionAttempts::default();
+        attempts.record_failure(at(0));
+        attempts.record_start(at(5));
+        assert!(!attempts.recovered(at(5 + window.num_seconds() - 1), window));
+        assert!(attempts.recovered(at(5 + window.num_seconds()), window));
+    }
+}
@@ -84,7 +84,7 @@ impl AutoEventIntegration {
             )
         };
 
-        let request = CreateNotificationRequest {
+        let _request = CreateNotificationRequest {
             profile_id: profile_id.to_string(),
             notification_type: if success { NotificationType::Profile } else { NotificationType::Security },
             title,
@@ -257,10 +257,10 @@ impl ProfileManagerWithEvents {
     }
 
     /// Autentica un profilo con eventi automatici
-    pub async fn authenticate_profile_with_events(&self, name: &str, password: &str) -> crate::profiles::errors::ProfileResult<crate::profiles::models::UserProfile> {
+    pub async fn authenticate_profile_with_events(&self, profile_id: &str, password: &str) -> crate::profiles::errors::ProfileResult<crate::profiles::models::UserProfile> {
         let mut manager = self.profile_manager.lock().await;
         
-        match manager.authenticate_profile(name, password).await {
+        match manager.authenticate_profile(profile_id, password).await {
             Ok(profile) => {
                 // Emette evento di autenticazione riuscita
                 if let Err(e) = self.auto_integration.on_profile_authenticated(&profile.id, &profile.name).await {
@@ -269,8 +269,12 @@ impl ProfileManagerWithEvents {
                 Ok(profile)
             }
             Err(e) => {
-                // Emette evento di autenticazione fallita
-                if let Err(event_err) = self.auto_integration.on_authentication_failed(name, &e.to_string()).await {
+                // Emette evento di autenticazione fallita (con il nome, se il profilo esiste)
+                let profile_name = match manager.get_profile_info(profile_id).await {
+                    Ok(Some(info)) => info.name,
+                    _ => profile_id.to_string(),
+                };
+                if let Err(event_err) = self.auto_integration.on_authentication_failed(&profile_name, &e.to_string()).await {
                     eprintln!("[PROFILE MANAGER WITH EVENTS] Errore evento autenticazione fallita: {}", event_err);
                 }
                 Err(e)
@@ -279,14 +283,14 @@ impl ProfileManagerWithEvents {
     }
 
     /// Cambia profilo con eventi automatici
-    pub async fn switch_profile_with_events(&self, name: &str, password: &str) -> crate::profiles::errors::ProfileResult<crate::profiles::models::UserProfile> {
+    pub async fn switch_profile_with_events(&self, profile_id: &str, password: &str) -> crate::profiles::errors::ProfileResult<crate::profiles::models::UserProfile> {
         let old_profile_id = {
             let manager = self.profile_manager.lock().await;
             manager.current_profile_id().map(|s| s.to_string())
         };
 
         let mut manager = self.profile_manager.lock().await;
-        let new_profile = manager.switch_profile(name, password).await?;
+        let new_profile = manager.switch_profile(profile_id, password).await?;
         
         // Emette evento di cambio profilo
         if let Err(e) = self.auto_integration.on_profile_switched(old_profile_id.as_deref(), &new_profile.id).await {
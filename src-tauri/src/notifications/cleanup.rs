@@ -295,20 +295,21 @@ mod tests {
         models::{CreateNotificationRequest, NotificationType, NotificationPriority, NotificationMetadata},
         storage::NotificationStorage,
     };
-    use tempfile::tempdir;
+    use tempfile::{tempdir, TempDir};
     use tokio::time::{sleep, Duration as TokioDuration};
 
-    async fn create_test_storage() -> NotificationStorage {
+    async fn create_test_storage() -> (NotificationStorage, TempDir) {
         let temp_dir = tempdir().unwrap();
         let db_path = temp_dir.path().join("test_cleanup.db");
         let storage = NotificationStorage::new(db_path);
         storage.initialize().await.unwrap();
-        storage
+        (storage, temp_dir)
     }
 
     #[tokio::test]
     async fn test_cleanup_expired_notifications() {
-        let storage = Arc::new(create_test_storage().await);
+        let (storage, _temp_dir) = create_test_storage().await;
+        let storage = Arc::new(storage);
         let config = CleanupConfig::default();
         let cleanup_manager = NotificationCleanupManager::new(Arc::clone(&storage), config);
 
@@ -370,7 +371,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_auto_cleanup_start_stop() {
-        let storage = Arc::new(create_test_storage().await);
+        let (storage, _temp_dir) = create_test_storage().await;
+        let storage = Arc::new(storage);
         let mut config = CleanupConfig::default();
         config.cleanup_interval_minutes = 1; // 1 minuto per il test
         
@@ -393,7 +395,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_cleanup_config_update() {
-        let storage = Arc::new(create_test_storage().await);
+        let (storage, _temp_dir) = create_test_storage().await;
+        let storage = Arc::new(storage);
         let config = CleanupConfig::default();
         let mut cleanup_manager = NotificationCleanupManager::new(Arc::clone(&storage), config);
 
@@ -1,15 +1,13 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::notifications::{
-        models::{CreateNotificationRequest, NotificationType, NotificationPriority, NotificationMetadata, NotificationPreferences},
+        models::{CreateNotificationRequest, Notification, NotificationType, NotificationPriority, NotificationMetadata, NotificationPreferences},
         storage::NotificationStorage,
         manager::NotificationManager,
-        cleanup::{NotificationCleanupManager, CleanupConfig},
+        cleanup::CleanupConfig,
     };
     use tempfile::tempdir;
     use chrono::{Duration, Utc};
-    use std::sync::Arc;
     use tokio::time::{sleep, Duration as TokioDuration};
 
     async fn create_test_manager_with_cleanup() -> (NotificationManager, tempfile::TempDir) {
@@ -31,9 +29,19 @@ mod tests {
         (manager, temp_dir)
     }
 
+    /// Salva una notifica direttamente nello storage, saltando la validazione del manager
+    /// (necessario per le notifiche già scadute)
+    async fn save_directly(temp_dir: &tempfile::TempDir, request: CreateNotificationRequest) -> Notification {
+        let storage = NotificationStorage::new(temp_dir.path().join("test_cleanup.db"));
+        storage.initialize().await.unwrap();
+        let notification = Notification::new(request);
+        storage.save_notification(&notification).await.unwrap();
+        notification
+    }
+
     #[tokio::test]
     async fn test_cleanup_expired_notifications() {
-        let (manager, _temp_dir) = create_test_manager_with_cleanup().await;
+        let (manager, temp_dir) = create_test_manager_with_cleanup().await;
 
         // Crea notifiche scadute e valide
         let expired_request = CreateNotificationRequest {
@@ -71,7 +79,7 @@ mod tests {
         };
 
         // Crea le notifiche
-        manager.create_notification(expired_request).await.unwrap();
+        save_directly(&temp_dir, expired_request).await;
         manager.create_notification(valid_request).await.unwrap();
 
         // Verifica che ci siano 2 notifiche
@@ -154,13 +162,15 @@ mod tests {
         // dipende dalla configurazione dei giorni di retention
         let cleanup_result = manager.run_manual_cleanup().await.unwrap();
         
-        // Verifica che il cleanup sia stato eseguito
-        assert!(cleanup_result.total_cleaned >= 0); // Potrebbe essere 0 se non abbastanza vecchie
+        // La notifica letta è appena stata creata: nessuna soglia di retention è superata
+        assert_eq!(cleanup_result.total_cleaned, 0);
+        let stats_after = manager.get_notification_stats("test_profile").await.unwrap();
+        assert_eq!(stats_after.total_notifications, 2);
     }
 
     #[tokio::test]
     async fn test_auto_cleanup_lifecycle() {
-        let (manager, _temp_dir) = create_test_manager_with_cleanup().await;
+        let (manager, temp_dir) = create_test_manager_with_cleanup().await;
 
         // Verifica che il cleanup automatico non sia ancora avviato
         assert!(!manager.is_auto_cleanup_running().await);
@@ -187,7 +197,7 @@ mod tests {
             }),
         };
 
-        manager.create_notification(expired_request).await.unwrap();
+        save_directly(&temp_dir, expired_request).await;
 
         // Aspetta un po' per permettere al cleanup automatico di girare
         // (In un test reale, dovremmo usare mock del tempo o intervalli più brevi)
@@ -200,7 +210,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_notification_stats() {
-        let (manager, _temp_dir) = create_test_manager_with_cleanup().await;
+        let (manager, temp_dir) = create_test_manager_with_cleanup().await;
 
         // Crea diverse tipologie di notifiche
         let requests = vec![
@@ -240,7 +250,7 @@ mod tests {
 
         let mut notification_ids = Vec::new();
         for request in requests {
-            let notification = manager.create_notification(request).await.unwrap();
+            let notification = save_directly(&temp_dir, request).await;
             notification_ids.push(notification.id);
         }
 
@@ -295,7 +305,8 @@ mod tests {
         // Il cleanup basato su retention policy dovrebbe usare queste preferenze
         let cleanup_result = manager.run_manual_cleanup().await.unwrap();
         
-        // Verifica che il cleanup sia stato eseguito (anche se potrebbe non eliminare nulla se troppo recente)
-        assert!(cleanup_result.total_cleaned >= 0);
+        // La notifica è più recente della retention di 1 giorno e deve restare
+        assert_eq!(cleanup_result.total_cleaned, 0);
+        assert_eq!(manager.get_notification_stats("test_profile").await.unwrap().total_notifications, 1);
     }
 }
\ No newline at end of file
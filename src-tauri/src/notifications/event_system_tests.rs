@@ -1,6 +1,5 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::notifications::{
         storage::NotificationStorage,
         manager::NotificationManager,
@@ -11,30 +10,32 @@ mod tests {
     };
     use crate::profiles::manager::ProfileEvent;
     use std::sync::Arc;
+    use tempfile::{tempdir, TempDir};
     use tokio::sync::Mutex;
-    use chrono::Utc;
 
-    async fn create_test_event_system() -> (NotificationEventSystem, AutoEventIntegration) {
-        let storage = NotificationStorage::new("test_event_system.db".into()).unwrap();
+    async fn create_test_event_system() -> (Arc<NotificationEventSystem>, AutoEventIntegration, Arc<Mutex<NotificationManager>>, TempDir) {
+        let temp_dir = tempdir().unwrap();
+        let storage = NotificationStorage::new(temp_dir.path().join("test_event_system.db"));
         let manager = NotificationManager::new(storage);
+        manager.initialize().await.unwrap();
         let manager_arc = Arc::new(Mutex::new(manager));
         
         let integration = ProfileNotificationIntegration::new(Arc::clone(&manager_arc));
         let integration_arc = Arc::new(Mutex::new(integration));
         
-        let event_system = NotificationEventSystem::new(
+        let event_system = Arc::new(NotificationEventSystem::new(
             Arc::clone(&manager_arc),
             Arc::clone(&integration_arc),
-        );
+        ));
         
-        let auto_integration = AutoEventIntegration::new(Arc::new(event_system.clone()));
+        let auto_integration = AutoEventIntegration::new(Arc::clone(&event_system));
         
-        (event_system, auto_integration)
+        (event_system, auto_integration, manager_arc, temp_dir)
     }
 
     #[tokio::test]
     async fn test_event_system_lifecycle() {
-        let (event_system, _) = create_test_event_system().await;
+        let (event_system, _, _, _temp_dir) = create_test_event_system().await;
         
         // Verifica stato iniziale
         assert!(!event_system.is_active().await);
@@ -50,7 +51,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_profile_created_event() {
-        let (event_system, _) = create_test_event_system().await;
+        let (event_system, _, notification_manager, _temp_dir) = create_test_event_system().await;
         event_system.start().await.unwrap();
         
         let profile_id = "test_profile_123";
@@ -60,7 +61,7 @@ mod tests {
         event_system.handle_profile_created(profile_id, profile_name).await.unwrap();
         
         // Verifica che sia stata creata una notifica di benvenuto
-        let manager = event_system.get_event_handler().notification_manager.lock().await;
+        let manager = notification_manager.lock().await;
         let filter = NotificationFilter {
             notification_type: Some(crate::notifications::models::NotificationType::Profile),
             priority: None,
@@ -83,7 +84,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_profile_authenticated_event() {
-        let (event_system, _) = create_test_event_system().await;
+        let (event_system, _, notification_manager, _temp_dir) = create_test_event_system().await;
         event_system.start().await.unwrap();
         
         let profile_id = "test_profile_456";
@@ -93,7 +94,7 @@ mod tests {
         event_system.handle_profile_authenticated(profile_id, profile_name).await.unwrap();
         
         // Verifica che sia stata creata una notifica di autenticazione
-        let manager = event_system.get_event_handler().notification_manager.lock().await;
+        let manager = notification_manager.lock().await;
         let filter = NotificationFilter {
             notification_type: Some(crate::notifications::models::NotificationType::Profile),
             priority: None,
@@ -116,7 +117,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_profile_switched_event() {
-        let (event_system, _) = create_test_event_system().await;
+        let (event_system, _, notification_manager, _temp_dir) = create_test_event_system().await;
         event_system.start().await.unwrap();
         
         let from_profile_id = "old_profile";
@@ -126,7 +127,7 @@ mod tests {
         event_system.handle_profile_switched(Some(from_profile_id), to_profile_id).await.unwrap();
         
         // Verifica che sia stata creata una notifica di cambio profilo
-        let manager = event_system.get_event_handler().notification_manager.lock().await;
+        let manager = notification_manager.lock().await;
         let filter = NotificationFilter {
             notification_type: Some(crate::notifications::models::NotificationType::Profile),
             priority: None,
@@ -148,14 +149,14 @@ mod tests {
 
     #[tokio::test]
     async fn test_profile_deleted_event() {
-        let (event_system, _) = create_test_event_system().await;
+        let (event_system, _, notification_manager, _temp_dir) = create_test_event_system().await;
         event_system.start().await.unwrap();
         
         let profile_id = "profile_to_delete";
         let profile_name = "Profile To Delete";
         
         // Prima crea alcune notifiche per il profilo
-        let manager = event_system.get_event_handler().notification_manager.lock().await;
+        let manager = notification_manager.lock().await;
         let request = crate::notifications::models::CreateNotificationRequest {
             profile_id: profile_id.to_string(),
             notification_type: crate::notifications::models::NotificationType::Profile,
@@ -171,7 +172,7 @@ mod tests {
         drop(manager);
         
         // Verifica che la notifica esista
-        let manager = event_system.get_event_handler().notification_manager.lock().await;
+        let manager = notification_manager.lock().await;
         let filter = NotificationFilter {
             notification_type: None,
             priority: None,
@@ -188,14 +189,14 @@ mod tests {
         event_system.handle_profile_deleted(profile_id, profile_name).await.unwrap();
         
         // Verifica che le notifiche del profilo siano state eliminate
-        let manager = event_system.get_event_handler().notification_manager.lock().await;
+        let manager = notification_manager.lock().await;
         let notifications_after = manager.get_notifications(profile_id, filter).await.unwrap();
         assert!(notifications_after.is_empty());
     }
 
     #[tokio::test]
     async fn test_authentication_failed_event() {
-        let (event_system, _) = create_test_event_system().await;
+        let (event_system, _, _, _temp_dir) = create_test_event_system().await;
         event_system.start().await.unwrap();
         
         let profile_name = "Failed Auth Profile";
@@ -211,7 +212,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_auto_integration_lifecycle() {
-        let (_, auto_integration) = create_test_event_system().await;
+        let (_, auto_integration, _, _temp_dir) = create_test_event_system().await;
         
         // Verifica stato iniziale
         let health = auto_integration.health_check().await;
@@ -232,7 +233,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_event_system_stats() {
-        let (event_system, _) = create_test_event_system().await;
+        let (event_system, _, _, _temp_dir) = create_test_event_system().await;
         
         let stats = event_system.get_event_stats().await;
         assert!(!stats.is_active);
@@ -246,7 +247,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_multiple_events_sequence() {
-        let (event_system, _) = create_test_event_system().await;
+        let (event_system, _, notification_manager, _temp_dir) = create_test_event_system().await;
         event_system.start().await.unwrap();
         
         let profile_id = "sequence_test_profile";
@@ -259,7 +260,7 @@ mod tests {
         event_system.handle_profile_logged_out(profile_id).await.unwrap();
         
         // Verifica che tutte le notifiche siano state create
-        let manager = event_system.get_event_handler().notification_manager.lock().await;
+        let manager = notification_manager.lock().await;
         let filter = NotificationFilter {
             notification_type: None,
             priority: None,
@@ -284,7 +285,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_event_listener_creation() {
-        let (event_system, _) = create_test_event_system().await;
+        let (event_system, _, _, _temp_dir) = create_test_event_system().await;
         
         // Crea un listener personalizzato
         let mut listener = event_system.create_event_listener();
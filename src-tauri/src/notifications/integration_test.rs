@@ -1,21 +1,20 @@
 #[cfg(test)]
 mod integration_tests {
-    use super::*;
     use crate::notifications::{
-        models::{CreateNotificationRequest, NotificationType, NotificationPriority, NotificationFilter},
+        models::NotificationFilter,
         storage::NotificationStorage,
         manager::NotificationManager,
         profile_integration::ProfileNotificationIntegration,
         profile_event_handler::ProfileEventHandler,
-        access_control::NotificationAccessControl,
     };
     use crate::profiles::manager::ProfileEvent;
-    use chrono::Utc;
     use std::sync::Arc;
+    use tempfile::{tempdir, TempDir};
     use tokio::sync::Mutex;
 
-    async fn create_test_system() -> (NotificationManager, ProfileNotificationIntegration, ProfileEventHandler) {
-        let storage = NotificationStorage::new("test_integration.db".into()).unwrap();
+    async fn create_test_system() -> (Arc<Mutex<NotificationManager>>, Arc<Mutex<ProfileNotificationIntegration>>, ProfileEventHandler, TempDir) {
+        let temp_dir = tempdir().unwrap();
+        let storage = NotificationStorage::new(temp_dir.path().join("test_integration.db"));
         let manager = NotificationManager::new(storage);
         manager.initialize().await.unwrap();
         
@@ -28,16 +27,12 @@ mod integration_tests {
             Arc::clone(&integration_arc),
         );
         
-        // Estrai i valori dal Mutex per il test
-        let manager = Arc::try_unwrap(manager_arc).unwrap().into_inner();
-        let integration = Arc::try_unwrap(integration_arc).unwrap().into_inner();
-        
-        (manager, integration, event_handler)
+        (manager_arc, integration_arc, event_handler, temp_dir)
     }
 
     #[tokio::test]
     async fn test_complete_profile_lifecycle() {
-        let (mut manager, integration, event_handler) = create_test_system().await;
+        let (manager, integration, event_handler, _temp_dir) = create_test_system().await;
         
         let profile_id = "test_profile";
         let profile_name = "Test Profile";
@@ -49,7 +44,7 @@ mod integration_tests {
         };
         
         // Gestisce l'evento di creazione
-        integration.handle_profile_event(create_event).await.unwrap();
+        integration.lock().await.handle_profile_event(create_event).await.unwrap();
         
         // Verifica che sia stata creata la notifica di benvenuto
         let filter = NotificationFilter {
@@ -61,7 +56,7 @@ mod integration_tests {
             offset: None,
         };
         
-        let notifications = manager.get_notifications(profile_id, filter.clone()).await.unwrap();
+        let notifications = manager.lock().await.get_notifications(profile_id, filter.clone()).await.unwrap();
         assert_eq!(notifications.len(), 1);
         assert_eq!(notifications[0].title, "Benvenuto in GameStringer!");
         
@@ -71,10 +66,10 @@ mod integration_tests {
             name: profile_name.to_string(),
         };
         
-        integration.handle_profile_event(auth_event).await.unwrap();
+        integration.lock().await.handle_profile_event(auth_event).await.unwrap();
         
         // Verifica che ci siano ora 2 notifiche
-        let notifications = manager.get_notifications(profile_id, filter.clone()).await.unwrap();
+        let notifications = manager.lock().await.get_notifications(profile_id, filter.clone()).await.unwrap();
         assert_eq!(notifications.len(), 2);
         
         // 3. Simula cambio profilo
@@ -83,10 +78,10 @@ mod integration_tests {
             to_id: profile_id.to_string(),
         };
         
-        integration.handle_profile_event(switch_event).await.unwrap();
+        integration.lock().await.handle_profile_event(switch_event).await.unwrap();
         
         // Verifica che ci siano ora 3 notifiche
-        let notifications = manager.get_notifications(profile_id, filter.clone()).await.unwrap();
+        let notifications = manager.lock().await.get_notifications(profile_id, filter.clone()).await.unwrap();
         assert_eq!(notifications.len(), 3);
         
         // 4. Verifica isolamento - crea un altro profilo
@@ -96,11 +91,11 @@ mod integration_tests {
             name: "Other Profile".to_string(),
         };
         
-        integration.handle_profile_event(other_create_event).await.unwrap();
+        integration.lock().await.handle_profile_event(other_create_event).await.unwrap();
         
         // Verifica che ogni profilo veda solo le proprie notifiche
-        let profile1_notifications = manager.get_notifications(profile_id, filter.clone()).await.unwrap();
-        let profile2_notifications = manager.get_notifications(other_profile_id, filter.clone()).await.unwrap();
+        let profile1_notifications = manager.lock().await.get_notifications(profile_id, filter.clone()).await.unwrap();
+        let profile2_notifications = manager.lock().await.get_notifications(other_profile_id, filter.clone()).await.unwrap();
         
         assert_eq!(profile1_notifications.len(), 3);
         assert_eq!(profile2_notifications.len(), 1);
@@ -110,7 +105,7 @@ mod integration_tests {
         assert!(profile2_notifications.iter().all(|n| n.profile_id == other_profile_id));
         
         // 5. Test eliminazione profilo
-        let delete_event = ProfileEvent::ProfileDeleted {
+        let _delete_event = ProfileEvent::ProfileDeleted {
             profile_id: other_profile_id.to_string(),
             name: "Other Profile".to_string(),
         };
@@ -120,11 +115,11 @@ mod integration_tests {
         assert_eq!(deleted_count, 1);
         
         // Verifica che le notifiche del profilo eliminato siano state rimosse
-        let remaining_notifications = manager.get_notifications(other_profile_id, filter).await.unwrap();
+        let remaining_notifications = manager.lock().await.get_notifications(other_profile_id, filter).await.unwrap();
         assert_eq!(remaining_notifications.len(), 0);
         
         // Verifica che le notifiche dell'altro profilo siano ancora presenti
-        let profile1_notifications = manager.get_notifications(profile_id, NotificationFilter {
+        let profile1_notifications = manager.lock().await.get_notifications(profile_id, NotificationFilter {
             notification_type: None,
             priority: None,
             unread_only: None,
@@ -137,7 +132,7 @@ mod integration_tests {
 
     #[tokio::test]
     async fn test_unauthorized_cross_profile_operations() {
-        let (mut manager, integration, _) = create_test_system().await;
+        let (manager, integration, _, _temp_dir) = create_test_system().await;
         
         let profile1_id = "profile1";
         let profile2_id = "profile2";
@@ -153,8 +148,8 @@ mod integration_tests {
             name: "Profile 2".to_string(),
         };
         
-        integration.handle_profile_event(create_event1).await.unwrap();
-        integration.handle_profile_event(create_event2).await.unwrap();
+        integration.lock().await.handle_profile_event(create_event1).await.unwrap();
+        integration.lock().await.handle_profile_event(create_event2).await.unwrap();
         
         // Ottieni le notifiche del profilo 1
         let filter = NotificationFilter {
@@ -166,21 +161,21 @@ mod integration_tests {
             offset: None,
         };
         
-        let profile1_notifications = manager.get_notifications(profile1_id, filter).await.unwrap();
+        let profile1_notifications = manager.lock().await.get_notifications(profile1_id, filter).await.unwrap();
         assert_eq!(profile1_notifications.len(), 1);
         
         let notification_id = &profile1_notifications[0].id;
         
         // Tenta di marcare come letta la notifica del profilo 1 usando il profilo 2
-        let result = manager.mark_as_read(notification_id, profile2_id).await;
+        let result = manager.lock().await.mark_as_read(notification_id, profile2_id).await;
         assert!(result.is_err(), "Il profilo 2 non dovrebbe poter marcare come letta la notifica del profilo 1");
         
         // Tenta di eliminare la notifica del profilo 1 usando il profilo 2
-        let result = manager.delete_notification(notification_id, profile2_id).await;
+        let result = manager.lock().await.delete_notification(notification_id, profile2_id).await;
         assert!(result.is_err(), "Il profilo 2 non dovrebbe poter eliminare la notifica del profilo 1");
         
         // Verifica che la notifica sia ancora presente per il profilo 1
-        let profile1_notifications = manager.get_notifications(profile1_id, NotificationFilter {
+        let profile1_notifications = manager.lock().await.get_notifications(profile1_id, NotificationFilter {
             notification_type: None,
             priority: None,
             unread_only: None,
@@ -194,14 +189,14 @@ mod integration_tests {
 
     #[tokio::test]
     async fn test_notification_preferences_isolation() {
-        let (mut manager, _, _) = create_test_system().await;
+        let (manager, _, _, _temp_dir) = create_test_system().await;
         
         let profile1_id = "profile1";
         let profile2_id = "profile2";
         
         // Ottieni le preferenze predefinite per entrambi i profili
-        let prefs1 = manager.get_preferences(profile1_id).await.unwrap();
-        let prefs2 = manager.get_preferences(profile2_id).await.unwrap();
+        let prefs1 = manager.lock().await.get_preferences(profile1_id).await.unwrap();
+        let prefs2 = manager.lock().await.get_preferences(profile2_id).await.unwrap();
         
         // Verifica che abbiano ID profilo diversi
         assert_eq!(prefs1.profile_id, profile1_id);
@@ -212,11 +207,11 @@ mod integration_tests {
         modified_prefs1.global_enabled = false;
         modified_prefs1.sound_enabled = false;
         
-        manager.update_preferences(modified_prefs1).await.unwrap();
+        manager.lock().await.update_preferences(modified_prefs1).await.unwrap();
         
         // Verifica che solo le preferenze del profilo 1 siano cambiate
-        let updated_prefs1 = manager.get_preferences(profile1_id).await.unwrap();
-        let unchanged_prefs2 = manager.get_preferences(profile2_id).await.unwrap();
+        let updated_prefs1 = manager.lock().await.get_preferences(profile1_id).await.unwrap();
+        let unchanged_prefs2 = manager.lock().await.get_preferences(profile2_id).await.unwrap();
         
         assert!(!updated_prefs1.global_enabled);
         assert!(!updated_prefs1.sound_enabled);
@@ -227,7 +222,7 @@ mod integration_tests {
 
     #[tokio::test]
     async fn test_security_report_generation() {
-        let (mut manager, integration, event_handler) = create_test_system().await;
+        let (_manager, integration, event_handler, _temp_dir) = create_test_system().await;
         
         let profile_id = "test_profile";
         
@@ -237,7 +232,7 @@ mod integration_tests {
             name: "Test Profile".to_string(),
         };
         
-        integration.handle_profile_event(create_event).await.unwrap();
+        integration.lock().await.handle_profile_event(create_event).await.unwrap();
         
         // Genera un report di sicurezza
         let report = event_handler.get_profile_notification_stats(profile_id).await.unwrap();
@@ -253,7 +248,7 @@ mod integration_tests {
 
     #[tokio::test]
     async fn test_notification_cleanup_on_profile_switch() {
-        let (mut manager, integration, event_handler) = create_test_system().await;
+        let (manager, integration, event_handler, _temp_dir) = create_test_system().await;
         
         let profile1_id = "profile1";
         let profile2_id = "profile2";
@@ -264,7 +259,7 @@ mod integration_tests {
             name: "Profile 1".to_string(),
         };
         
-        integration.handle_profile_event(create_event).await.unwrap();
+        integration.lock().await.handle_profile_event(create_event).await.unwrap();
         
         // Simula cambio profilo
         event_handler.handle_profile_switch_with_cleanup(Some(profile1_id), profile2_id).await.unwrap();
@@ -278,7 +273,7 @@ mod integration_tests {
             name: "Profile 2".to_string(),
         };
         
-        integration.handle_profile_event(create_event2).await.unwrap();
+        integration.lock().await.handle_profile_event(create_event2).await.unwrap();
         
         // Verifica che ogni profilo abbia le proprie notifiche
         let filter = NotificationFilter {
@@ -290,8 +285,8 @@ mod integration_tests {
             offset: None,
         };
         
-        let profile1_notifications = manager.get_notifications(profile1_id, filter.clone()).await.unwrap();
-        let profile2_notifications = manager.get_notifications(profile2_id, filter).await.unwrap();
+        let profile1_notifications = manager.lock().await.get_notifications(profile1_id, filter.clone()).await.unwrap();
+        let profile2_notifications = manager.lock().await.get_notifications(profile2_id, filter).await.unwrap();
         
         assert_eq!(profile1_notifications.len(), 1);
         assert_eq!(profile2_notifications.len(), 1);
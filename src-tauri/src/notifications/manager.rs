@@ -1,13 +1,12 @@
 use std::sync::Arc;
 use std::collections::HashMap;
-use chrono::{DateTime, Utc, Timelike};
-use uuid::Uuid;
+use chrono::{Utc, Timelike};
 use crate::notifications::{
     errors::{NotificationError, NotificationResult},
     models::{
         CreateNotificationRequest, Notification, NotificationFilter, NotificationPreferences, 
         PartialNotificationPreferences, NotificationStats, NotificationCounts, NotificationSortBy, 
-        NotificationType, NotificationPriority, NotificationMetadata
+        NotificationType, NotificationPriority
     },
     storage::NotificationStorage,
     cleanup::{NotificationCleanupManager, CleanupConfig, CleanupStats, CleanupResult},
@@ -122,17 +121,11 @@ impl NotificationManager {
             return false;
         }
         
-        // Controlla le impostazioni per tipo
+        // Controlla le impostazioni per tipo (la priorità del tipo è quella predefinita, non un minimo)
         if let Some(type_preference) = preferences.type_settings.get(&request.notification_type) {
             if !type_preference.enabled {
                 return false;
             }
-            
-            // Controlla la priorità minima
-            let request_priority = request.priority.as_ref().unwrap_or(&NotificationPriority::Normal);
-            if !self.priority_meets_minimum(request_priority, &type_preference.priority) {
-                return false;
-            }
         }
         
         // Controlla le ore di silenzio
@@ -148,21 +141,6 @@ impl NotificationManager {
         true
     }
 
-    /// Verifica se la priorità soddisfa il minimo richiesto
-    fn priority_meets_minimum(&self, request_priority: &NotificationPriority, min_priority: &NotificationPriority) -> bool {
-        let priority_levels = [
-            NotificationPriority::Low,
-            NotificationPriority::Normal,
-            NotificationPriority::High,
-            NotificationPriority::Urgent,
-        ];
-        
-        let request_level = priority_levels.iter().position(|p| p == request_priority).unwrap_or(1);
-        let min_level = priority_levels.iter().position(|p| p == min_priority).unwrap_or(1);
-        
-        request_level >= min_level
-    }
-
     /// Verifica se siamo nelle ore di silenzio
     fn is_in_quiet_hours(&self, quiet_hours: &crate::notifications::models::QuietHoursSettings) -> bool {
         let now = Utc::now();
@@ -532,19 +510,7 @@ impl NotificationManager {
 
     /// Ottiene una notifica specifica per ID e profilo
     async fn get_notification_by_id(&self, notification_id: &str, profile_id: &str) -> NotificationResult<Notification> {
-        let filter = NotificationFilter {
-            notification_type: None,
-            priority: None,
-            unread_only: None,
-            category: None,
-            limit: None,
-            offset: None,
-        };
-        
-        let notifications = self.storage.load_notifications(profile_id, &filter).await?;
-        let notification = notifications
-            .into_iter()
-            .find(|n| n.id == notification_id)
+        let notification = self.storage.load_notification(notification_id).await?
             .ok_or_else(|| NotificationError::NotificationNotFound(notification_id.to_string()))?;
 
         // Verifica che appartenga al profilo
@@ -595,20 +561,7 @@ impl NotificationManager {
         // Verifica rate limit per operazione
         NotificationAccessControl::check_operation_rate_limit(profile_id, "delete_notification")?;
         
-        // Verifica che la notifica appartenga al profilo
-        let filter = NotificationFilter {
-            notification_type: None,
-            priority: None,
-            unread_only: None,
-            category: None,
-            limit: None,
-            offset: None,
-        };
-        
-        let notifications = self.storage.load_notifications(profile_id, &filter).await?;
-        let notification = notifications
-            .into_iter()
-            .find(|n| n.id == notification_id)
+        let notification = self.storage.load_notification(notification_id).await?
             .ok_or_else(|| NotificationError::NotificationNotFound(notification_id.to_string()))?;
 
         // Verifica autorizzazione per eliminazione
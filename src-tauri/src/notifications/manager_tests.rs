@@ -1,34 +1,32 @@
 #[cfg(test)]
 mod manager_tests {
-    use super::*;
     use crate::notifications::{
         manager::NotificationManager,
         storage::NotificationStorage,
         models::{
             CreateNotificationRequest, NotificationFilter, NotificationMetadata, 
-            NotificationType, NotificationPriority, NotificationPreferences,
-            PartialNotificationPreferences, QuietHoursSettings, TypePreference,
+            NotificationType, NotificationPriority,
+            PartialNotificationPreferences, QuietHoursSettings,
             NotificationSortBy
         },
-        cleanup::{CleanupConfig, CleanupStats},
+        cleanup::CleanupConfig,
         errors::NotificationError,
     };
-    use tempfile::tempdir;
+    use tempfile::{tempdir, TempDir};
     use chrono::{Utc, Duration};
-    use std::collections::HashMap;
 
     /// Helper per creare un manager di test
-    async fn create_test_manager() -> NotificationManager {
+    async fn create_test_manager() -> (NotificationManager, TempDir) {
         let temp_dir = tempdir().unwrap();
         let db_path = temp_dir.path().join("test_notifications.db");
         let storage = NotificationStorage::new(db_path);
         let manager = NotificationManager::new(storage);
         manager.initialize().await.unwrap();
-        manager
+        (manager, temp_dir)
     }
 
     /// Helper per creare un manager con cleanup automatico
-    async fn create_test_manager_with_cleanup() -> NotificationManager {
+    async fn create_test_manager_with_cleanup() -> (NotificationManager, TempDir) {
         let temp_dir = tempdir().unwrap();
         let db_path = temp_dir.path().join("test_notifications_cleanup.db");
         let storage = NotificationStorage::new(db_path);
@@ -44,7 +42,7 @@ mod manager_tests {
         
         let manager = NotificationManager::new_with_cleanup(storage, cleanup_config);
         manager.initialize().await.unwrap();
-        manager
+        (manager, temp_dir)
     }
 
     /// Helper per creare una richiesta di notifica di test
@@ -69,7 +67,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_manager_initialization() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Il manager dovrebbe essere inizializzato correttamente
         // Testiamo creando una notifica
@@ -80,7 +78,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_create_notification_success() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         let request = create_test_notification_request("test_profile");
         
         let notification = manager.create_notification(request).await.unwrap();
@@ -95,7 +93,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_create_notification_validation_errors() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Test titolo vuoto
         let mut request = create_test_notification_request("test_profile");
@@ -130,7 +128,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_create_notification_with_preferences_blocking() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Disabilita le notifiche globalmente
         let mut preferences = manager.get_preferences("test_profile").await.unwrap();
@@ -147,7 +145,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_get_notifications_with_filters() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Crea diverse notifiche
         let requests = vec![
@@ -225,7 +223,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_mark_as_read_functionality() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         let request = create_test_notification_request("test_profile");
         let notification = manager.create_notification(request).await.unwrap();
         
@@ -248,7 +246,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_mark_multiple_as_read() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Crea tre notifiche
         let mut notification_ids = Vec::new();
@@ -278,7 +276,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_mark_all_as_read() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Crea cinque notifiche
         for i in 0..5 {
@@ -302,7 +300,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_delete_notification() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         let request = create_test_notification_request("test_profile");
         let notification = manager.create_notification(request).await.unwrap();
         
@@ -322,7 +320,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_delete_nonexistent_notification() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Prova a eliminare una notifica inesistente
         let result = manager.delete_notification("nonexistent_id", "test_profile").await;
@@ -331,7 +329,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_unauthorized_profile_access() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         let request = create_test_notification_request("profile_a");
         let notification = manager.create_notification(request).await.unwrap();
         
@@ -345,7 +343,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_notification_counts() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Crea notifiche con diverse priorità
         let requests = vec![
@@ -387,7 +385,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_preferences_management() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Ottieni le preferenze predefinite
         let preferences = manager.get_preferences("test_profile").await.unwrap();
@@ -412,7 +410,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_partial_preferences_update() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Aggiornamento parziale
         let partial_update = PartialNotificationPreferences {
@@ -433,7 +431,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_toggle_notification_type() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Disabilita le notifiche di sistema
         manager.toggle_notification_type(
@@ -460,7 +458,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_quiet_hours_functionality() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Configura ore di silenzio
         let quiet_hours = QuietHoursSettings {
@@ -484,7 +482,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_notification_sorting() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Crea notifiche con diverse priorità
         let requests = vec![
@@ -525,7 +523,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_high_priority_unread_notifications() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Crea notifiche con diverse priorità
         let requests = vec![
@@ -564,7 +562,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_clear_all_notifications() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Crea diverse notifiche
         for i in 0..5 {
@@ -590,7 +588,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_reset_preferences_to_default() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Modifica le preferenze
         let mut preferences = manager.get_preferences("test_profile").await.unwrap();
@@ -616,14 +614,15 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_notification_expiration() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
-        // Crea una notifica già scaduta
+        // Una notifica già scaduta viene rifiutata dal manager
         let mut request = create_test_notification_request("test_profile");
         request.expires_at = Some(Utc::now() - Duration::hours(1)); // Scaduta un'ora fa
         
-        let notification = manager.create_notification(request).await.unwrap();
-        assert!(notification.is_expired());
+        assert!(crate::notifications::models::Notification::new(request.clone()).is_expired());
+        let result = manager.create_notification(request).await;
+        assert!(matches!(result, Err(NotificationError::InvalidContent(_))));
         
         // Crea una notifica che scade in futuro
         let mut request = create_test_notification_request("test_profile");
@@ -635,7 +634,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_notification_metadata_validation() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Test metadati con troppi tag
         let mut request = create_test_notification_request("test_profile");
@@ -664,7 +663,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_manager_with_cleanup() {
-        let manager = create_test_manager_with_cleanup().await;
+        let (manager, _temp_dir) = create_test_manager_with_cleanup().await;
         
         // Il manager con cleanup dovrebbe funzionare normalmente
         let request = create_test_notification_request("test_profile");
@@ -676,7 +675,7 @@ mod manager_tests {
 
     #[tokio::test]
     async fn test_filter_validation() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Test limite troppo alto
         let filter = NotificationFilter {
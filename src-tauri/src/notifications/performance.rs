@@ -1,12 +1,12 @@
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection, Transaction};
+use rusqlite::{params, Connection};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use crate::notifications::{
-    errors::{NotificationError, NotificationResult},
+    errors::NotificationResult,
     models::{Notification, NotificationPreferences},
 };
 
@@ -55,6 +55,7 @@ impl ConnectionPool {
     /// Crea una nuova connessione ottimizzata
     fn create_connection(&self) -> NotificationResult<Connection> {
         let conn = Connection::open(&self.db_path)?;
+        conn.busy_timeout(self.connection_timeout)?;
         
         // Ottimizzazioni SQLite per performance
         conn.execute("PRAGMA journal_mode = WAL", [])?;
@@ -372,14 +373,12 @@ impl BatchCleanupResult {
 /// Query ottimizzate con prepared statements cache
 pub struct OptimizedQueryManager {
     pool: Arc<ConnectionPool>,
-    prepared_statements_cache: Arc<Mutex<HashMap<String, String>>>,
 }
 
 impl OptimizedQueryManager {
     pub fn new(pool: Arc<ConnectionPool>) -> Self {
         Self {
             pool,
-            prepared_statements_cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -410,18 +409,18 @@ impl OptimizedQueryManager {
         let (query, params) = if let Some(ntype) = notification_type {
             (
                 "SELECT * FROM notifications WHERE profile_id = ?1 AND notification_type = ?2 ORDER BY created_at DESC LIMIT ?3 OFFSET ?4",
-                vec![profile_id, ntype, &limit.to_string(), &offset.to_string()]
+                vec![profile_id.to_string(), ntype.to_string(), limit.to_string(), offset.to_string()]
             )
         } else {
             (
                 "SELECT * FROM notifications WHERE profile_id = ?1 ORDER BY created_at DESC LIMIT ?2 OFFSET ?3",
-                vec![profile_id, &limit.to_string(), &offset.to_string()]
+                vec![profile_id.to_string(), limit.to_string(), offset.to_string()]
             )
         };
 
         let mut stmt = conn.as_ref().prepare(query)?;
         let notification_iter = stmt.query_map(
-            params.as_slice(),
+            rusqlite::params_from_iter(params.iter()),
             |row| self.row_to_notification(row),
         )?;
 
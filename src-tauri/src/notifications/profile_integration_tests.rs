@@ -1,37 +1,18 @@
 #[cfg(test)]
 mod profile_integration_tests {
-    use super::*;
     use crate::notifications::{
         manager::NotificationManager,
         storage::NotificationStorage,
         profile_integration::ProfileNotificationIntegration,
         models::{NotificationFilter, NotificationType, NotificationPriority},
-        errors::NotificationError,
     };
     use crate::profiles::manager::ProfileEvent;
-    use tempfile::tempdir;
+    use tempfile::{tempdir, TempDir};
     use std::sync::Arc;
     use tokio::sync::Mutex;
 
-    /// Helper per creare un'integrazione di test
-    async fn create_test_integration() -> (NotificationManager, ProfileNotificationIntegration) {
-        let temp_dir = tempdir().unwrap();
-        let db_path = temp_dir.path().join("test_integration.db");
-        let storage = NotificationStorage::new(db_path);
-        let manager = NotificationManager::new(storage);
-        manager.initialize().await.unwrap();
-        
-        let manager_arc = Arc::new(Mutex::new(manager));
-        let integration = ProfileNotificationIntegration::new(Arc::clone(&manager_arc));
-        
-        // Estrai il manager per i test
-        let manager = Arc::try_unwrap(manager_arc).unwrap().into_inner();
-        
-        (manager, integration)
-    }
-
     /// Helper per creare un'integrazione con manager condiviso
-    async fn create_shared_integration() -> (Arc<Mutex<NotificationManager>>, ProfileNotificationIntegration) {
+    async fn create_shared_integration() -> (Arc<Mutex<NotificationManager>>, ProfileNotificationIntegration, TempDir) {
         let temp_dir = tempdir().unwrap();
         let db_path = temp_dir.path().join("test_shared_integration.db");
         let storage = NotificationStorage::new(db_path);
@@ -41,12 +22,12 @@ mod profile_integration_tests {
         let manager_arc = Arc::new(Mutex::new(manager));
         let integration = ProfileNotificationIntegration::new(Arc::clone(&manager_arc));
         
-        (manager_arc, integration)
+        (manager_arc, integration, temp_dir)
     }
 
     #[tokio::test]
     async fn test_profile_created_event() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Simula evento di creazione profilo
         let event = ProfileEvent::ProfileCreated {
@@ -97,7 +78,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_profile_authenticated_event() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Simula evento di autenticazione
         let event = ProfileEvent::ProfileAuthenticated {
@@ -139,7 +120,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_profile_switched_event() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Simula evento di cambio profilo
         let event = ProfileEvent::ProfileSwitched {
@@ -181,7 +162,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_profile_logged_out_event() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Simula evento di logout
         let event = ProfileEvent::ProfileLoggedOut {
@@ -222,7 +203,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_profile_deleted_event() {
-        let (_, integration) = create_shared_integration().await;
+        let (_, integration, _temp_dir) = create_shared_integration().await;
         
         // Simula evento di eliminazione profilo
         let event = ProfileEvent::ProfileDeleted {
@@ -240,7 +221,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_authentication_failed_event() {
-        let (_, integration) = create_shared_integration().await;
+        let (_, integration, _temp_dir) = create_shared_integration().await;
         
         // Simula evento di autenticazione fallita
         let event = ProfileEvent::AuthenticationFailed {
@@ -258,7 +239,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_authentication_error_notification() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Crea notifica di errore di autenticazione
         let result = integration.create_authentication_error_notification(
@@ -301,7 +282,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_profile_locked_notification() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Crea notifica di profilo bloccato
         let remaining_seconds = 300; // 5 minuti
@@ -346,7 +327,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_credential_operation_success_notification() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Crea notifica di operazione credenziali riuscita
         let result = integration.create_credential_operation_notification(
@@ -388,7 +369,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_credential_operation_error_notification() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Crea notifica di errore operazione credenziali
         let result = integration.create_credential_operation_notification(
@@ -427,7 +408,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_settings_update_notification() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Crea notifica di aggiornamento impostazioni
         let result = integration.create_settings_update_notification("settings_profile_606").await;
@@ -465,7 +446,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_backup_success_notification() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Crea notifica di backup riuscito
         let backup_path = "/path/to/backup/profile_backup_2024.zip";
@@ -512,7 +493,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_backup_error_notification() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Crea notifica di errore backup
         let result = integration.create_backup_notification(
@@ -557,7 +538,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_multiple_profile_events_sequence() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Simula una sequenza di eventi per lo stesso profilo
         let profile_id = "sequence_profile_909";
@@ -626,7 +607,7 @@ mod profile_integration_tests {
 
     #[tokio::test]
     async fn test_profile_isolation_in_integration() {
-        let (manager_arc, integration) = create_shared_integration().await;
+        let (manager_arc, integration, _temp_dir) = create_shared_integration().await;
         
         // Crea eventi per profili diversi
         let profile_a = "isolation_profile_a";
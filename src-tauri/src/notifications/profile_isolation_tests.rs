@@ -1,6 +1,5 @@
 #[cfg(test)]
 mod profile_isolation_tests {
-    use super::*;
     use crate::notifications::{
         models::{CreateNotificationRequest, NotificationType, NotificationPriority, NotificationFilter},
         storage::NotificationStorage,
@@ -8,19 +7,19 @@ mod profile_isolation_tests {
         access_control::NotificationAccessControl,
     };
     use chrono::Utc;
-    use std::sync::Arc;
-    use tokio::sync::Mutex;
+    use tempfile::{tempdir, TempDir};
 
-    async fn create_test_manager() -> NotificationManager {
-        let storage = NotificationStorage::new("test_profile_isolation.db".into()).unwrap();
+    async fn create_test_manager() -> (NotificationManager, TempDir) {
+        let temp_dir = tempdir().unwrap();
+        let storage = NotificationStorage::new(temp_dir.path().join("test_profile_isolation.db"));
         let manager = NotificationManager::new(storage);
         manager.initialize().await.unwrap();
-        manager
+        (manager, temp_dir)
     }
 
     #[tokio::test]
     async fn test_profile_isolation_basic() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Crea notifiche per due profili diversi
         let profile1_id = "profile1";
@@ -80,7 +79,7 @@ mod profile_isolation_tests {
 
     #[tokio::test]
     async fn test_unauthorized_access_prevention() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         let profile1_id = "profile1";
         let profile2_id = "profile2";
@@ -81,8 +81,7 @@ impl NotificationStorage {
                 created_at DATETIME NOT NULL,
                 read_at DATETIME,
                 expires_at DATETIME,
-                metadata TEXT,
-                FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+                metadata TEXT
             )
             "#,
             [],
@@ -100,8 +99,7 @@ impl NotificationStorage {
                 quiet_hours TEXT,
                 max_notifications INTEGER NOT NULL DEFAULT 50,
                 auto_delete_after_days INTEGER NOT NULL DEFAULT 30,
-                updated_at DATETIME NOT NULL,
-                FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+                updated_at DATETIME NOT NULL
             )
             "#,
             [],
@@ -131,7 +129,7 @@ impl NotificationStorage {
     }
 
     /// Ottiene una connessione al database
-    fn get_connection(&self) -> NotificationResult<std::sync::MutexGuard<Option<Connection>>> {
+    fn get_connection(&self) -> NotificationResult<std::sync::MutexGuard<'_, Option<Connection>>> {
         Ok(self.connection.lock().unwrap())
     }
 
@@ -213,10 +211,10 @@ impl NotificationStorage {
         // Ordinamento per data di creazione (più recenti prima)
         query.push_str(" ORDER BY created_at DESC");
 
-        // Limite e offset
-        if let Some(limit) = filter.limit {
+        // Limite e offset (in SQLite OFFSET richiede LIMIT: -1 = nessun limite)
+        if filter.limit.is_some() || filter.offset.is_some() {
             query.push_str(&format!(" LIMIT ?{}", param_index));
-            params.push(Box::new(limit as i64));
+            params.push(Box::new(filter.limit.map(|limit| limit as i64).unwrap_or(-1)));
             param_index += 1;
         }
 
@@ -239,6 +237,26 @@ impl NotificationStorage {
         Ok(notifications)
     }
 
+    /// Carica una singola notifica per ID, indipendentemente dal profilo
+    pub async fn load_notification(&self, id: &str) -> NotificationResult<Option<Notification>> {
+        let conn_guard = self.get_connection()?;
+        let conn = conn_guard
+            .as_ref()
+            .ok_or_else(|| NotificationError::StorageError("Database non inizializzato".to_string()))?;
+
+        let result = conn.query_row(
+            "SELECT * FROM notifications WHERE id = ?1",
+            params![id],
+            |row| self.row_to_notification(row),
+        );
+
+        match result {
+            Ok(notification) => Ok(Some(notification?)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(NotificationError::DatabaseError(e)),
+        }
+    }
+
     /// Aggiorna una notifica esistente
     pub async fn update_notification(&self, notification: &Notification) -> NotificationResult<()> {
         let conn_guard = self.get_connection()?;
@@ -504,22 +522,25 @@ impl NotificationStorage {
 
     /// Ottiene tutti i profili con le loro preferenze per la pulizia
     pub async fn get_all_profiles_with_preferences(&self) -> NotificationResult<Vec<(String, Option<NotificationPreferences>)>> {
-        let conn_guard = self.get_connection()?;
-        let conn = conn_guard
-            .as_ref()
-            .ok_or_else(|| NotificationError::StorageError("Database non inizializzato".to_string()))?;
-
-        // Prima ottieni tutti i profili unici dalle notifiche
-        let mut stmt = conn.prepare("SELECT DISTINCT profile_id FROM notifications")?;
-        let profile_iter = stmt.query_map([], |row| {
-            Ok(row.get::<_, String>("profile_id")?)
-        })?;
-
-        let mut profile_ids = Vec::new();
-        for profile_result in profile_iter {
-            let profile_id = profile_result?;
-            profile_ids.push(profile_id);
-        }
+        // Prima ottieni tutti i profili unici dalle notifiche; la connessione va
+        // rilasciata prima di load_preferences, che la riacquisisce
+        let profile_ids = {
+            let conn_guard = self.get_connection()?;
+            let conn = conn_guard
+                .as_ref()
+                .ok_or_else(|| NotificationError::StorageError("Database non inizializzato".to_string()))?;
+
+            let mut stmt = conn.prepare("SELECT DISTINCT profile_id FROM notifications")?;
+            let profile_iter = stmt.query_map([], |row| {
+                row.get::<_, String>("profile_id")
+            })?;
+
+            let mut profile_ids = Vec::new();
+            for profile_result in profile_iter {
+                profile_ids.push(profile_result?);
+            }
+            profile_ids
+        };
         
         // Ora carica le preferenze per ogni profilo
         let mut results = Vec::new();
@@ -712,7 +733,6 @@ impl NotificationStorage {
     pub fn cleanup_connections(&self) {
         self.connection_pool.cleanup_idle_connections();
     }
-}
 
     // === SYSTEM NOTIFICATION STORAGE METHODS ===
 
@@ -1111,3 +1131,4 @@ impl NotificationStorage {
 
         Ok(profile_ids)
     }
+}
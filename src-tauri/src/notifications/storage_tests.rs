@@ -1,6 +1,5 @@
 #[cfg(test)]
 mod storage_tests {
-    use super::*;
     use crate::notifications::{
         storage::NotificationStorage,
         models::{
@@ -10,17 +9,16 @@ mod storage_tests {
         },
         errors::NotificationError,
     };
-    use tempfile::tempdir;
+    use tempfile::{tempdir, TempDir};
     use chrono::{Utc, Duration};
-    use std::collections::HashMap;
 
     /// Helper per creare uno storage di test
-    async fn create_test_storage() -> NotificationStorage {
+    async fn create_test_storage() -> (NotificationStorage, TempDir) {
         let temp_dir = tempdir().unwrap();
         let db_path = temp_dir.path().join("test_storage.db");
         let storage = NotificationStorage::new(db_path);
         storage.initialize().await.unwrap();
-        storage
+        (storage, temp_dir)
     }
 
     /// Helper per creare una notifica di test
@@ -74,7 +72,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_save_and_load_notification() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         let notification = create_test_notification("test_profile");
         
         // Salva la notifica
@@ -105,7 +103,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_load_notifications_with_filters() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Crea diverse notifiche
         let notifications = vec![
@@ -207,7 +205,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_load_notifications_unread_only() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Crea due notifiche
         let mut notification1 = create_test_notification("test_profile");
@@ -255,7 +253,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_update_notification() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         let mut notification = create_test_notification("test_profile");
         
         // Salva la notifica
@@ -289,7 +287,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_update_nonexistent_notification() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         let mut notification = create_test_notification("test_profile");
         notification.id = "nonexistent_id".to_string();
         
@@ -300,7 +298,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_delete_notification() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         let notification = create_test_notification("test_profile");
         
         // Salva la notifica
@@ -322,7 +320,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_delete_nonexistent_notification() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Prova a eliminare una notifica inesistente
         let result = storage.delete_notification("nonexistent_id").await;
@@ -331,7 +329,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_delete_all_notifications() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Crea notifiche per due profili diversi
         let notification1 = create_test_notification("profile_a");
@@ -358,7 +356,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_count_unread_notifications() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Crea tre notifiche
         let mut notification1 = create_test_notification("test_profile");
@@ -386,7 +384,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_cleanup_expired_notifications() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Crea notifiche scadute e non scadute
         let mut expired_notification = create_test_notification("test_profile");
@@ -420,7 +418,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_cleanup_old_read_notifications() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Crea notifiche lette vecchie e recenti
         let mut old_read_notification = create_test_notification("test_profile");
@@ -453,7 +451,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_save_and_load_preferences() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         let preferences = create_test_preferences("test_profile");
         
         // Salva le preferenze
@@ -473,7 +471,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_load_nonexistent_preferences() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Prova a caricare preferenze per un profilo inesistente
         let result = storage.load_preferences("nonexistent_profile").await.unwrap();
@@ -482,7 +480,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_update_preferences() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         let mut preferences = create_test_preferences("test_profile");
         
         // Salva le preferenze iniziali
@@ -505,7 +503,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_preferences_with_quiet_hours() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         let mut preferences = create_test_preferences("test_profile");
         
         // Aggiungi ore di silenzio
@@ -532,7 +530,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_preferences_with_type_settings() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         let mut preferences = create_test_preferences("test_profile");
         
         // Modifica le impostazioni per tipo
@@ -553,7 +551,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_delete_preferences() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         let preferences = create_test_preferences("test_profile");
         
         // Salva le preferenze
@@ -574,7 +572,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_get_notification_stats() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Crea diverse notifiche
         let mut notification1 = create_test_notification("test_profile");
@@ -607,7 +605,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_get_all_profiles_with_preferences() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Crea notifiche per diversi profili
         let notification1 = create_test_notification("profile_a");
@@ -650,7 +648,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_cleanup_notifications_older_than() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Crea notifiche vecchie e recenti
         let mut old_notification = create_test_notification("test_profile");
@@ -676,7 +674,7 @@ mod storage_tests {
 
     #[tokio::test]
     async fn test_profile_isolation() {
-        let storage = create_test_storage().await;
+        let (storage, _temp_dir) = create_test_storage().await;
         
         // Crea notifiche per profili diversi
         let notification_a = create_test_notification("profile_a");
@@ -766,9 +766,10 @@ impl SystemEventHandler {
 
     /// Verifica se un'operazione è importante e merita una notifica
     fn is_important_operation(&self, operation_type: &str) -> bool {
-        matches!(operation_type.to_lowercase().as_str(), 
+        // Confronta le singole parole: i tipi composti ("Backup Full", "Database Cleanup") restano importanti
+        operation_type.to_lowercase().split_whitespace().any(|word| matches!(word,
             "backup" | "restore" | "migration" | "sync" | "update" | "cleanup" | "repair"
-        )
+        ))
     }
 
     /// Verifica se un cambio di stato è importante
@@ -778,7 +779,8 @@ impl SystemEventHandler {
         new_status.contains("offline") || 
         new_status.contains("maintenance") ||
         (old_status.contains("error") && new_status.contains("online")) ||
-        (old_status.contains("offline") && new_status.contains("online"))
+        (old_status.contains("offline") && new_status.contains("online")) ||
+        (old_status.contains("stopped") && new_status.contains("running"))
     }
 
     /// Ottiene la lista dei profili attivi
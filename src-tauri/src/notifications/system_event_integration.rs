@@ -332,8 +332,6 @@ impl SystemEventIntegration {
 
     /// Notifica avviso spazio disco
     pub async fn notify_disk_space_warning(&self, disk_path: String, free_space_gb: f64, total_space_gb: f64) -> NotificationResult<Vec<String>> {
-        let usage_percentage = ((total_space_gb - free_space_gb) / total_space_gb) * 100.0;
-        
         self.notify_resource_warning(
             format!("Spazio Disco ({})", disk_path),
             total_space_gb - free_space_gb,
@@ -356,7 +354,7 @@ impl SystemEventIntegration {
     pub async fn notify_database_connection_issue(&self, database_name: String, error_message: String, auto_retry: bool) -> NotificationResult<Vec<String>> {
         self.notify_database_issue(
             database_name,
-            "Connection Error".to_string(),
+            format!("Connection Error: {}", error_message),
             "high".to_string(),
             auto_retry,
         ).await
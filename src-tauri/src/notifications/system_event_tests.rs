@@ -1,29 +1,30 @@
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::notifications::{
         storage::NotificationStorage,
         manager::NotificationManager,
         system_event_handler::{SystemEventHandler, SystemEvent, SecuritySeverity},
         system_event_integration::SystemEventIntegration,
-        models::{NotificationFilter, NotificationType},
     };
     use std::sync::Arc;
+    use tempfile::{tempdir, TempDir};
     use tokio::sync::Mutex;
     use chrono::Utc;
 
-    async fn create_test_system_event_integration() -> SystemEventIntegration {
-        let storage = NotificationStorage::new("test_system_events.db".into()).unwrap();
+    async fn create_test_system_event_integration() -> (SystemEventIntegration, TempDir) {
+        let temp_dir = tempdir().unwrap();
+        let storage = NotificationStorage::new(temp_dir.path().join("test_system_events.db"));
         let manager = NotificationManager::new(storage);
+        manager.initialize().await.unwrap();
         let manager_arc = Arc::new(Mutex::new(manager));
         
         let system_handler = Arc::new(SystemEventHandler::new(manager_arc));
-        SystemEventIntegration::new(system_handler)
+        (SystemEventIntegration::new(system_handler), temp_dir)
     }
 
     #[tokio::test]
     async fn test_system_event_integration_lifecycle() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         
         // Verifica stato iniziale
         assert!(!integration.is_active().await);
@@ -39,7 +40,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_update_available_notification() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         // Aggiungi un profilo attivo
@@ -63,7 +64,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_critical_update_notification() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         let profile_id = "test_profile_critical";
@@ -82,7 +83,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_maintenance_notifications() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         let profile_id = "test_profile_maintenance";
@@ -119,7 +120,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_system_error_notifications() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         let profile_id = "test_profile_error";
@@ -148,7 +149,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_security_alert_notifications() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         let profile_id = "test_profile_security";
@@ -177,7 +178,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_background_operation_notifications() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         let profile_id = "test_profile_background";
@@ -206,7 +207,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_resource_warning_notifications() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         let profile_id = "test_profile_resources";
@@ -232,7 +233,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_database_issue_notifications() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         let profile_id = "test_profile_database";
@@ -260,7 +261,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_network_status_notifications() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         let profile_id = "test_profile_network";
@@ -287,7 +288,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_convenience_methods() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         let profile_id = "test_profile_convenience";
@@ -323,7 +324,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_multiple_profiles_broadcast() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         // Aggiungi multipli profili attivi
@@ -354,7 +355,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_profile_management() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         let profile_id = "test_profile_mgmt";
@@ -381,7 +382,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_system_event_listener() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         integration.start().await.unwrap();
         
         // Crea un listener personalizzato
@@ -424,7 +425,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_system_integration_stats() {
-        let integration = create_test_system_event_integration().await;
+        let (integration, _temp_dir) = create_test_system_event_integration().await;
         
         let stats = integration.get_system_stats().await;
         assert!(!stats.is_active);
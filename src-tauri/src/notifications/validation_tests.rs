@@ -1,6 +1,5 @@
 #[cfg(test)]
 mod validation_tests {
-    use super::*;
     use crate::notifications::{
         manager::NotificationManager,
         storage::NotificationStorage,
@@ -11,18 +10,18 @@ mod validation_tests {
         },
         errors::NotificationError,
     };
-    use tempfile::tempdir;
+    use tempfile::{tempdir, TempDir};
     use chrono::{Utc, Duration};
     use std::collections::HashMap;
 
     /// Helper per creare un manager di test
-    async fn create_test_manager() -> NotificationManager {
+    async fn create_test_manager() -> (NotificationManager, TempDir) {
         let temp_dir = tempdir().unwrap();
         let db_path = temp_dir.path().join("test_validation.db");
         let storage = NotificationStorage::new(db_path);
         let manager = NotificationManager::new(storage);
         manager.initialize().await.unwrap();
-        manager
+        (manager, temp_dir)
     }
 
     #[tokio::test]
@@ -159,7 +158,7 @@ mod validation_tests {
 
     #[tokio::test]
     async fn test_manager_advanced_validation() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Test profile_id troppo lungo
         let mut request = CreateNotificationRequest {
@@ -235,7 +234,7 @@ mod validation_tests {
 
     #[tokio::test]
     async fn test_preferences_validation() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Test preferenze con max_notifications troppo basso
         let mut preferences = NotificationPreferences::default();
@@ -243,22 +242,20 @@ mod validation_tests {
         preferences.max_notifications = 0; // Troppo basso
         
         let result = manager.update_preferences(preferences).await;
-        // Nota: il manager potrebbe non validare questo specifico caso,
-        // ma testiamo comunque la struttura
+        assert!(matches!(result, Err(NotificationError::InvalidContent(_))));
         
         // Test preferenze con auto_delete_after_days troppo basso
         let mut preferences = NotificationPreferences::default();
         preferences.profile_id = "test_profile".to_string();
         preferences.auto_delete_after_days = 0; // Troppo basso
         
-        // Il manager dovrebbe accettare questi valori ma potrebbero causare comportamenti inaspettati
         let result = manager.update_preferences(preferences).await;
-        assert!(result.is_ok()); // Il manager non valida questi limiti specifici
+        assert!(matches!(result, Err(NotificationError::InvalidContent(_))));
     }
 
     #[tokio::test]
     async fn test_quiet_hours_validation() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Test ore di silenzio con formato orario non valido
         let invalid_quiet_hours = QuietHoursSettings {
@@ -270,7 +267,7 @@ mod validation_tests {
         
         // Il manager potrebbe non validare il formato orario direttamente,
         // ma testiamo la struttura
-        let result = manager.update_quiet_hours("test_profile", Some(invalid_quiet_hours)).await;
+        let _result = manager.update_quiet_hours("test_profile", Some(invalid_quiet_hours)).await;
         // Nota: la validazione del formato orario potrebbe essere implementata in futuro
         
         // Test ore di silenzio valide
@@ -351,7 +348,7 @@ mod validation_tests {
         assert_eq!(notification.read_at.unwrap(), first_read_time);
         
         // Test notifica scaduta
-        let mut expired_request = CreateNotificationRequest {
+        let expired_request = CreateNotificationRequest {
             profile_id: "test_profile".to_string(),
             notification_type: NotificationType::System,
             title: "Expired Notification".to_string(),
@@ -441,7 +438,7 @@ mod validation_tests {
 
     #[tokio::test]
     async fn test_complex_validation_scenarios() {
-        let manager = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
         
         // Scenario 1: Notifica con tutti i campi opzionali
         let complex_request = CreateNotificationRequest {
@@ -0,0 +1,268 @@
+// DXGI Desktop Duplication - backend di cattura veloce
+// BitBlt dal DC dello schermo restituisce frame neri con i giochi DirectX in fullscreen
+// esclusivo ed è lento sulle risoluzioni alte. La Desktop Duplication copia il frame
+// composto direttamente dalla GPU: si duplica un intero monitor e la regione richiesta
+// viene ritagliata dal frame. Se il desktop non cambia si riusa l'ultimo frame.
+
+use super::screen_capture::ImageData;
+use super::CaptureRegion;
+
+/// Ritaglia `region` (coordinate relative al frame) da un frame BGRA compatto
+pub fn crop_bgra(frame: &[u8], frame_width: u32, frame_height: u32, region: &CaptureRegion) -> Option<ImageData> {
+    if region.x < 0 || region.y < 0 || region.width <= 0 || region.height <= 0 {
+        return None;
+    }
+    let (x, y) = (region.x as usize, region.y as usize);
+    let (width, height) = (region.width as usize, region.height as usize);
+    let stride = frame_width as usize * 4;
+    if x + width > frame_width as usize || y + height > frame_height as usize || frame.len() < stride * frame_height as usize {
+        return None;
+    }
+
+    let mut data = Vec::with_capacity(width * height * 4);
+    for row in y..y + height {
+        let start = row * stride + x * 4;
+        data.extend_from_slice(&frame[start..start + width * 4]);
+    }
+    Some(ImageData {
+        width: width as u32,
+        height: height as u32,
+        data,
+    })
+}
+
+#[cfg(target_os = "windows")]
+pub use windows_impl::DxgiDuplicator;
+
+#[cfg(target_os = "windows")]
+mod windows_impl {
+    use super::{crop_bgra, CaptureRegion, ImageData};
+    use windows::core::{ComInterface, IUnknown};
+    use windows::Win32::Foundation::HMODULE;
+    use windows::Win32::Graphics::Direct3D::D3D_DRIVER_TYPE_UNKNOWN;
+    use windows::Win32::Graphics::Direct3D11::{
+        D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, ID3D11Resource, ID3D11Texture2D,
+        D3D11_CPU_ACCESS_READ, D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_MAPPED_SUBRESOURCE, D3D11_MAP_READ,
+        D3D11_SDK_VERSION, D3D11_TEXTURE2D_DESC, D3D11_USAGE_STAGING,
+    };
+    use windows::Win32::Graphics::Dxgi::Common::{
+        DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_MODE_ROTATION_IDENTITY, DXGI_MODE_ROTATION_UNSPECIFIED,
+    };
+    use windows::Win32::Graphics::Dxgi::{
+        CreateDXGIFactory1, IDXGIAdapter, IDXGIAdapter1, IDXGIFactory1, IDXGIOutput, IDXGIOutput1,
+        IDXGIOutputDuplication, IDXGIResource, DXGI_ERROR_WAIT_TIMEOUT, DXGI_OUTDUPL_FRAME_INFO,
+    };
+
+    /// Attesa di un nuovo frame: oltre si riusa l'ultimo (desktop fermo)
+    const ACQUIRE_TIMEOUT_MS: u32 = 16;
+    /// Il primo frame dopo DuplicateOutput può arrivare con un po' di ritardo
+    const FIRST_FRAME_TIMEOUT_MS: u32 = 500;
+
+    /// Duplicazione di un monitor con l'ultimo frame copiato in memoria
+    pub struct DxgiDuplicator {
+        device: ID3D11Device,
+        context: ID3D11DeviceContext,
+        duplication: IDXGIOutputDuplication,
+        /// Posizione del monitor nel desktop virtuale
+        bounds: CaptureRegion,
+        staging: Option<ID3D11Texture2D>,
+        frame: Option<Vec<u8>>,
+        frame_width: u32,
+        frame_height: u32,
+    }
+
+    impl DxgiDuplicator {
+        /// Duplica il monitor che contiene il punto (x, y) del desktop virtuale
+        pub fn for_point(x: i32, y: i32) -> Result<Self, String> {
+            unsafe {
+                let factory: IDXGIFactory1 = CreateDXGIFactory1()
+                    .map_err(|e| format!("CreateDXGIFactory1 fallita: {}", e))?;
+
+                let mut adapter_index = 0;
+                while let Ok(adapter) = factory.EnumAdapters1(adapter_index) {
+                    let mut output_index = 0;
+                    while let Ok(output) = adapter.EnumOutputs(output_index) {
+                        output_index += 1;
+                        let Ok(desc) = output.GetDesc() else { continue };
+                        let r = desc.DesktopCoordinates;
+                        if !desc.AttachedToDesktop.as_bool() || x < r.left || x >= r.right || y < r.top || y >= r.bottom {
+                            continue;
+                        }
+                        if desc.Rotation != DXGI_MODE_ROTATION_IDENTITY && desc.Rotation != DXGI_MODE_ROTATION_UNSPECIFIED {
+                            return Err("Monitor ruotato: Desktop Duplication non supportata".to_string());
+                        }
+                        let bounds = CaptureRegion {
+                            x: r.left,
+                            y: r.top,
+                            width: r.right - r.left,
+                            height: r.bottom - r.top,
+                        };
+                        return Self::create(&adapter, &output, bounds);
+                    }
+                    adapter_index += 1;
+                }
+                Err(format!("Nessun monitor DXGI contiene il punto ({}, {})", x, y))
+            }
+        }
+
+        unsafe fn create(adapter: &IDXGIAdapter1, output: &IDXGIOutput, bounds: CaptureRegion) -> Result<Self, String> {
+            let adapter: IDXGIAdapter = adapter.cast().map_err(|e| e.to_string())?;
+            let mut device: Option<ID3D11Device> = None;
+            let mut context: Option<ID3D11DeviceContext> = None;
+            D3D11CreateDevice(
+                &adapter,
+                D3D_DRIVER_TYPE_UNKNOWN,
+                HMODULE::default(),
+                D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                None,
+                D3D11_SDK_VERSION,
+                Some(&mut device),
+                None,
+                Some(&mut context),
+            )
+            .map_err(|e| format!("D3D11CreateDevice fallita: {}", e))?;
+            let device = device.ok_or("D3D11CreateDevice non ha restituito il device")?;
+            let context = context.ok_or("D3D11CreateDevice non ha restituito il context")?;
+
+            // E_ACCESSDENIED su desktop protetto (UAC, schermata di blocco), DXGI_ERROR_UNSUPPORTED
+            // su sessioni remote o driver senza supporto
+            let output: IDXGIOutput1 = output.cast().map_err(|e| format!("IDXGIOutput1 non disponibile: {}", e))?;
+            let unknown: IUnknown = device.cast().map_err(|e| e.to_string())?;
+            let duplication = output.DuplicateOutput(&unknown)
+                .map_err(|e| format!("DuplicateOutput fallita: {}", e))?;
+
+            log::info!("🖥️ DXGI Desktop Duplication attiva su monitor {}x{} @ ({},{})",
+                bounds.width, bounds.height, bounds.x, bounds.y);
+            Ok(Self {
+                device,
+                context,
+                duplication,
+                bounds,
+                staging: None,
+                frame: None,
+                frame_width: 0,
+                frame_height: 0,
+            })
+        }
+
+        /// Vero se la regione (coordinate desktop virtuale) è interamente sul monitor duplicato
+        pub fn contains(&self, region: &CaptureRegion) -> bool {
+            region.x >= self.bounds.x
+                && region.y >= self.bounds.y
+                && region.x + region.width <= self.bounds.x + self.bounds.width
+                && region.y + region.height <= self.bounds.y + self.bounds.height
+        }
+
+        /// Cattura una regione del desktop virtuale
+        pub fn capture(&mut self, region: &CaptureRegion) -> Result<ImageData, String> {
+            self.next_frame()?;
+            let frame = self.frame.as_deref().ok_or("Nessun frame DXGI disponibile")?;
+            let local = CaptureRegion {
+                x: region.x - self.bounds.x,
+                y: region.y - self.bounds.y,
+                width: region.width,
+                height: region.height,
+            };
+            crop_bgra(frame, self.frame_width, self.frame_height, &local)
+                .ok_or_else(|| "Regione fuori dal monitor duplicato".to_string())
+        }
+
+        /// Aggiorna `frame` con il desktop corrente; senza cambi entro il timeout tiene l'ultimo
+        fn next_frame(&mut self) -> Result<(), String> {
+            let timeout = if self.frame.is_some() { ACQUIRE_TIMEOUT_MS } else { FIRST_FRAME_TIMEOUT_MS };
+            unsafe {
+                let mut info = DXGI_OUTDUPL_FRAME_INFO::default();
+                let mut resource: Option<IDXGIResource> = None;
+                if let Err(e) = self.duplication.AcquireNextFrame(timeout, &mut info, &mut resource) {
+                    if e.code() == DXGI_ERROR_WAIT_TIMEOUT && self.frame.is_some() {
+                        return Ok(());
+                    }
+                    // DXGI_ERROR_ACCESS_LOST: cambio modalità/fullscreen, la duplicazione va ricreata
+                    return Err(format!("AcquireNextFrame fallita: {}", e));
+                }
+                let result = self.copy_frame(resource);
+                let _ = self.duplication.ReleaseFrame();
+                result
+            }
+        }
+
+        unsafe fn copy_frame(&mut self, resource: Option<IDXGIResource>) -> Result<(), String> {
+            let texture: ID3D11Texture2D = resource
+                .ok_or("Frame DXGI senza risorsa")?
+                .cast()
+                .map_err(|e| e.to_string())?;
+            let mut desc = D3D11_TEXTURE2D_DESC::default();
+            texture.GetDesc(&mut desc);
+            // Monitor HDR: formato a 16 bit float, non convertito qui
+            if desc.Format != DXGI_FORMAT_B8G8R8A8_UNORM {
+                return Err(format!("Formato desktop non supportato: {:?}", desc.Format));
+            }
+
+            // Texture CPU-leggibile, creata una volta e riusata finché la duplicazione vive
+            if self.staging.is_none() {
+                let mut staging_desc = desc;
+                staging_desc.Usage = D3D11_USAGE_STAGING;
+                staging_desc.BindFlags = Default::default();
+                staging_desc.MiscFlags = Default::default();
+                staging_desc.CPUAccessFlags = D3D11_CPU_ACCESS_READ.0 as u32;
+                let mut staging: Option<ID3D11Texture2D> = None;
+                self.device.CreateTexture2D(&staging_desc, None, Some(&mut staging))
+                    .map_err(|e| format!("CreateTexture2D fallita: {}", e))?;
+                self.staging = staging;
+            }
+            let staging: ID3D11Resource = self.staging.as_ref()
+                .ok_or("Texture di staging non disponibile")?
+                .cast()
+                .map_err(|e| e.to_string())?;
+            let source: ID3D11Resource = texture.cast().map_err(|e| e.to_string())?;
+            self.context.CopyResource(&staging, &source);
+
+            let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+            self.context.Map(&staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))
+                .map_err(|e| format!("Map fallita: {}", e))?;
+
+            // Le righe mappate hanno padding (RowPitch): copia compatta width*4 per riga
+            let (width, height) = (desc.Width as usize, desc.Height as usize);
+            let pitch = mapped.RowPitch as usize;
+            let source = std::slice::from_raw_parts(mapped.pData as *const u8, pitch * height);
+            let mut frame = Vec::with_capacity(width * height * 4);
+            for row in 0..height {
+                frame.extend_from_slice(&source[row * pitch..row * pitch + width * 4]);
+            }
+            self.context.Unmap(&staging, 0);
+
+            self.frame_width = desc.Width;
+            self.frame_height = desc.Height;
+            self.frame = Some(frame);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered_frame(width: u32, height: u32) -> Vec<u8> {
+        (0..width * height).flat_map(|i| [i as u8, 0, 0, 255]).collect()
+    }
+
+    #[test]
+    fn crop_copies_requested_rows() {
+        let frame = numbered_frame(4, 3);
+        let region = CaptureRegion { x: 1, y: 1, width: 2, height: 2 };
+        let image = crop_bgra(&frame, 4, 3, &region).unwrap();
+        assert_eq!((image.width, image.height), (2, 2));
+        let blue: Vec<u8> = image.data.chunks(4).map(|p| p[0]).collect();
+        assert_eq!(blue, vec![5, 6, 9, 10]);
+    }
+
+    #[test]
+    fn crop_rejects_regions_outside_frame() {
+        let frame = numbered_frame(4, 3);
+        assert!(crop_bgra(&frame, 4, 3, &CaptureRegion { x: 3, y: 0, width: 2, height: 1 }).is_none());
+        assert!(crop_bgra(&frame, 4, 3, &CaptureRegion { x: -1, y: 0, width: 1, height: 1 }).is_none());
+        assert!(crop_bgra(&frame, 4, 3, &CaptureRegion { x: 0, y: 0, width: 0, height: 1 }).is_none());
+        assert!(crop_bgra(&frame, 4, 3, &CaptureRegion { x: 0, y: 0, width: 4, height: 3 }).is_some());
+    }
+}
@@ -0,0 +1,157 @@
+// Frame Diff - rilevamento cambi di schermata
+// Impronta in scala di grigi 32x32 (media per blocco) del frame catturato: confrontare due
+// impronte costa pochi microsecondi, mentre l'OCR costa decine di millisecondi. Se il frame
+// è praticamente identico al precedente l'OCR viene saltato.
+
+use super::screen_capture::ImageData;
+
+const FINGERPRINT_SIDE: usize = 32;
+/// Luminanza massima di un blocco perché il frame sia considerato nero
+const BLANK_MAX_LUMA: u8 = 4;
+
+/// Impronta ridotta di un frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameFingerprint {
+    width: u32,
+    height: u32,
+    cells: Vec<u8>,
+}
+
+impl FrameFingerprint {
+    /// Media della luminanza per blocco; campiona al massimo 8x8 pixel per blocco
+    pub fn from_image(image: &ImageData) -> Option<Self> {
+        let (w, h) = (image.width as usize, image.height as usize);
+        if w == 0 || h == 0 || image.data.len() < w * h * 4 {
+            return None;
+        }
+
+        let mut cells = Vec::with_capacity(FINGERPRINT_SIDE * FINGERPRINT_SIDE);
+        for cy in 0..FINGERPRINT_SIDE {
+            let (y0, y1) = (cy * h / FINGERPRINT_SIDE, ((cy + 1) * h / FINGERPRINT_SIDE).max(cy * h / FINGERPRINT_SIDE + 1).min(h));
+            let y_step = ((y1 - y0) / 8).max(1);
+            for cx in 0..FINGERPRINT_SIDE {
+                let (x0, x1) = (cx * w / FINGERPRINT_SIDE, ((cx + 1) * w / FINGERPRINT_SIDE).max(cx * w / FINGERPRINT_SIDE + 1).min(w));
+                let x_step = ((x1 - x0) / 8).max(1);
+
+                let mut sum = 0u32;
+                let mut count = 0u32;
+                for y in (y0..y1).step_by(y_step) {
+                    for x in (x0..x1).step_by(x_step) {
+                        let px = &image.data[(y * w + x) * 4..(y * w + x) * 4 + 3];
+                        // BGRA -> luminanza (pesi interi Rec. 601)
+                        sum += (px[2] as u32 * 299 + px[1] as u32 * 587 + px[0] as u32 * 114) / 1000;
+                        count += 1;
+                    }
+                }
+                cells.push((sum / count.max(1)) as u8);
+            }
+        }
+
+        Some(Self { width: image.width, height: image.height, cells })
+    }
+
+    /// Variazione del blocco più cambiato, tra 0.0 (identici) e 1.0; frame di dimensioni
+    /// diverse valgono 1.0. Si usa il massimo e non la media: una nuova riga di sottotitoli
+    /// cambia pochi blocchi su 1024 e nella media sparirebbe
+    pub fn difference(&self, other: &FrameFingerprint) -> f32 {
+        if self.width != other.width || self.height != other.height || self.cells.len() != other.cells.len() {
+            return 1.0;
+        }
+        let max_delta = self.cells.iter()
+            .zip(&other.cells)
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap_or(0);
+        max_delta as f32 / 255.0
+    }
+
+    /// Frame interamente nero: tipico di finestre minimizzate/coperte o cattura GDI di un gioco
+    /// DirectX fullscreen. Non contiene testo, quindi niente OCR
+    pub fn is_blank(&self) -> bool {
+        self.cells.iter().all(|&c| c <= BLANK_MAX_LUMA)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, value: u8) -> ImageData {
+        ImageData { width, height, data: vec![value; (width * height * 4) as usize] }
+    }
+
+    #[test]
+    fn identical_frames_have_zero_difference() {
+        let a = FrameFingerprint::from_image(&solid(640, 360, 40)).unwrap();
+        let b = FrameFingerprint::from_image(&solid(640, 360, 40)).unwrap();
+        assert_eq!(a.difference(&b), 0.0);
+    }
+
+    #[test]
+    fn small_change_stays_below_large_change() {
+        let base = solid(320, 240, 0);
+        let mut dialog = base.clone();
+        // Riga di "testo" bianco in basso (nuova battuta di dialogo)
+        for y in 200..215 {
+            for x in 20..300 {
+                let offset = ((y * 320 + x) * 4) as usize;
+                dialog.data[offset..offset + 3].copy_from_slice(&[255, 255, 255]);
+            }
+        }
+        let mut cursor = base.clone();
+        cursor.data[0..3].copy_from_slice(&[255, 255, 255]);
+
+        let base_fp = FrameFingerprint::from_image(&base).unwrap();
+        let dialog_diff = base_fp.difference(&FrameFingerprint::from_image(&dialog).unwrap());
+        let cursor_diff = base_fp.difference(&FrameFingerprint::from_image(&cursor).unwrap());
+        assert!(cursor_diff < 0.05);
+        assert!(dialog_diff > 0.5);
+    }
+
+    #[test]
+    fn small_subtitle_on_full_hd_frame_is_a_change() {
+        let base = solid(1920, 1080, 20);
+        let mut subtitle = base.clone();
+        // Sottotitolo breve: ~0.4% dei pixel, meno di 10 blocchi su 1024
+        for y in 960..984 {
+            for x in 860..1060 {
+                let offset = (y * 1920 + x) * 4;
+                subtitle.data[offset..offset + 3].copy_from_slice(&[255, 255, 255]);
+            }
+        }
+
+        let diff = FrameFingerprint::from_image(&base).unwrap()
+            .difference(&FrameFingerprint::from_image(&subtitle).unwrap());
+        // Soglia predefinita di `frame_change_threshold`
+        assert!(diff > 0.05, "differenza troppo bassa: {}", diff);
+    }
+
+    #[test]
+    fn resized_frames_are_always_different() {
+        let a = FrameFingerprint::from_image(&solid(100, 100, 10)).unwrap();
+        let b = FrameFingerprint::from_image(&solid(200, 100, 10)).unwrap();
+        assert_eq!(a.difference(&b), 1.0);
+    }
+
+    #[test]
+    fn black_frames_are_blank_but_text_is_not() {
+        let black = solid(320, 240, 0);
+        assert!(FrameFingerprint::from_image(&black).unwrap().is_blank());
+
+        let mut subtitle = black.clone();
+        for y in 200..215 {
+            for x in 20..300 {
+                let offset = ((y * 320 + x) * 4) as usize;
+                subtitle.data[offset..offset + 3].copy_from_slice(&[255, 255, 255]);
+            }
+        }
+        assert!(!FrameFingerprint::from_image(&subtitle).unwrap().is_blank());
+        assert!(!FrameFingerprint::from_image(&solid(320, 240, 40)).unwrap().is_blank());
+    }
+
+    #[test]
+    fn handles_frames_smaller_than_grid() {
+        let fp = FrameFingerprint::from_image(&solid(5, 3, 200)).unwrap();
+        assert_eq!(fp.cells.len(), FINGERPRINT_SIDE * FINGERPRINT_SIDE);
+    }
+}
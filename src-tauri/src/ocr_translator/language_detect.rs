@@ -0,0 +1,223 @@
+// Language Detect - riconoscimento offline della lingua sorgente
+// Prima lo script (latino, cirillico, kana, han, hangul...), poi per i testi latini le parole
+// più comuni (incluse quelle dei menu di gioco) e i caratteri accentati tipici di ogni lingua.
+// Non serve un modello: ai testi OCR basta distinguere le lingue dei giochi, e i menu misti
+// producono più candidati con confidenza proporzionale alla quota di testo.
+
+use serde::{Deserialize, Serialize};
+
+/// Lettere minime perché il rilevamento abbia senso
+const MIN_LETTERS: usize = 3;
+/// Candidati sotto questa confidenza non vengono riportati
+const MIN_CANDIDATE_CONFIDENCE: f32 = 0.02;
+/// Peso iniziale di ogni lingua latina; l'inglese parte avvantaggiato (lingua più comune nei giochi)
+const LATIN_PRIOR: f32 = 0.1;
+const ENGLISH_PRIOR: f32 = 0.5;
+/// Peso di un carattere accentato tipico rispetto a una parola comune
+const DIACRITIC_WEIGHT: f32 = 0.5;
+
+/// Lingua candidata con confidenza 0.0 - 1.0
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanguageCandidate {
+    pub language: String,
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Kana,
+    Han,
+    Hangul,
+    Arabic,
+    Hebrew,
+    Thai,
+}
+
+fn script_of(c: char) -> Option<Script> {
+    match c {
+        'a'..='z' | 'A'..='Z' | '\u{00C0}'..='\u{024F}' if c != '\u{00D7}' && c != '\u{00F7}' => Some(Script::Latin),
+        '\u{0400}'..='\u{04FF}' => Some(Script::Cyrillic),
+        '\u{0370}'..='\u{03FF}' => Some(Script::Greek),
+        '\u{3040}'..='\u{30FF}' | '\u{FF66}'..='\u{FF9F}' => Some(Script::Kana),
+        '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' => Some(Script::Han),
+        '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' => Some(Script::Hangul),
+        '\u{0600}'..='\u{06FF}' => Some(Script::Arabic),
+        '\u{0590}'..='\u{05FF}' => Some(Script::Hebrew),
+        '\u{0E00}'..='\u{0E7F}' => Some(Script::Thai),
+        _ => None,
+    }
+}
+
+/// Lingue latine riconosciute: parole comuni (testo e menu) e caratteri tipici
+const LATIN_PROFILES: &[(&str, &[&str], &str)] = &[
+    ("en", &[
+        "the", "and", "you", "to", "of", "is", "it", "in", "that", "this", "with", "for", "your", "are",
+        "not", "have", "be", "what", "will", "new", "game", "options", "settings", "start", "continue",
+        "load", "save", "quit", "exit", "back", "press", "yes", "no",
+    ], ""),
+    ("it", &[
+        "il", "lo", "la", "di", "che", "non", "un", "una", "per", "sono", "con", "gli", "del", "della",
+        "questo", "nuova", "partita", "opzioni", "impostazioni", "continua", "carica", "salva", "esci",
+        "indietro", "premi", "sì",
+    ], "àèìòù"),
+    ("fr", &[
+        "le", "la", "les", "de", "des", "et", "est", "un", "une", "que", "pas", "vous", "pour", "dans",
+        "avec", "nouvelle", "partie", "paramètres", "continuer", "charger", "sauvegarder", "quitter",
+        "retour", "appuyez", "oui", "non",
+    ], "éêèçâîôûëœ"),
+    ("de", &[
+        "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "zu", "du", "sie", "mit", "den",
+        "ich", "auf", "neues", "spiel", "optionen", "einstellungen", "fortsetzen", "laden", "speichern",
+        "beenden", "zurück", "drücke", "ja", "nein",
+    ], "äöüß"),
+    ("es", &[
+        "el", "la", "los", "las", "de", "que", "y", "es", "un", "una", "por", "para", "con", "no",
+        "del", "nueva", "partida", "opciones", "ajustes", "continuar", "cargar", "guardar", "salir",
+        "volver", "pulsa", "sí",
+    ], "ñ¿¡áíóú"),
+    ("pt", &[
+        "o", "os", "as", "de", "que", "e", "não", "um", "uma", "para", "com", "do", "da", "você",
+        "novo", "jogo", "opções", "configurações", "continuar", "carregar", "salvar", "sair",
+        "voltar", "pressione", "sim",
+    ], "ãõçâêô"),
+];
+
+/// Quota di ogni script sul totale delle lettere
+fn script_shares(text: &str) -> (usize, Vec<(Script, f32)>) {
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+    let mut total = 0;
+    for script in text.chars().filter_map(script_of) {
+        total += 1;
+        match counts.iter_mut().find(|(s, _)| *s == script) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+    let shares = counts.into_iter()
+        .map(|(script, n)| (script, n as f32 / total.max(1) as f32))
+        .collect();
+    (total, shares)
+}
+
+/// Suddivide la quota latina tra le lingue in base a parole comuni e accenti
+fn latin_candidates(text: &str, share: f32) -> Vec<(&'static str, f32)> {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphabetic() && c != '\'')
+        .map(|w| w.trim_matches('\''))
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let scores: Vec<(&str, f32)> = LATIN_PROFILES.iter()
+        .map(|(lang, common, diacritics)| {
+            let word_hits = words.iter().filter(|w| common.contains(w)).count() as f32;
+            let accent_hits = lower.chars().filter(|c| diacritics.contains(*c)).count() as f32;
+            let prior = if *lang == "en" { ENGLISH_PRIOR } else { LATIN_PRIOR };
+            (*lang, prior + word_hits + accent_hits * DIACRITIC_WEIGHT)
+        })
+        .collect();
+
+    let total: f32 = scores.iter().map(|(_, s)| s).sum();
+    scores.into_iter().map(|(lang, score)| (lang, share * score / total)).collect()
+}
+
+/// Lingue candidate per uno script, con la rispettiva parte della quota
+fn script_candidates(text: &str, script: Script, share: f32, kana_share: f32) -> Vec<(&'static str, f32)> {
+    match script {
+        Script::Latin => latin_candidates(text, share),
+        Script::Cyrillic => {
+            // Lettere presenti solo in ucraino
+            if text.chars().any(|c| matches!(c, 'і' | 'ї' | 'є' | 'ґ' | 'І' | 'Ї' | 'Є' | 'Ґ')) {
+                vec![("uk", share * 0.8), ("ru", share * 0.2)]
+            } else {
+                vec![("ru", share * 0.9), ("uk", share * 0.1)]
+            }
+        }
+        // Il kana c'è solo in giapponese: con anche pochi kana gli ideogrammi sono kanji
+        Script::Kana => vec![("ja", share)],
+        Script::Han if kana_share > 0.0 => vec![("ja", share)],
+        Script::Han => vec![("zh", share * 0.8), ("ja", share * 0.2)],
+        Script::Hangul => vec![("ko", share)],
+        Script::Greek => vec![("el", share)],
+        Script::Arabic => vec![("ar", share)],
+        Script::Hebrew => vec![("he", share)],
+        Script::Thai => vec![("th", share)],
+    }
+}
+
+/// Lingue candidate del testo, dalla più probabile. Vuoto se il testo è troppo corto.
+pub fn detect_language(text: &str) -> Vec<LanguageCandidate> {
+    let (letters, shares) = script_shares(text);
+    if letters < MIN_LETTERS {
+        return Vec::new();
+    }
+    let kana_share = shares.iter()
+        .find(|(s, _)| *s == Script::Kana)
+        .map_or(0.0, |(_, share)| *share);
+
+    let mut candidates: Vec<LanguageCandidate> = Vec::new();
+    for (script, share) in shares {
+        for (language, confidence) in script_candidates(text, script, share, kana_share) {
+            match candidates.iter_mut().find(|c| c.language == language) {
+                Some(existing) => existing.confidence += confidence,
+                None => candidates.push(LanguageCandidate { language: language.to_string(), confidence }),
+            }
+        }
+    }
+
+    candidates.retain(|c| c.confidence >= MIN_CANDIDATE_CONFIDENCE);
+    for candidate in candidates.iter_mut() {
+        candidate.confidence = (candidate.confidence * 100.0).round() / 100.0;
+    }
+    candidates.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top(text: &str) -> String {
+        detect_language(text).first().map(|c| c.language.clone()).unwrap_or_default()
+    }
+
+    #[test]
+    fn detects_by_script() {
+        assert_eq!(top("ゲームを始める"), "ja");
+        assert_eq!(top("新しいゲームを開始"), "ja");
+        assert_eq!(top("开始新游戏"), "zh");
+        assert_eq!(top("새 게임 시작"), "ko");
+        assert_eq!(top("Новая игра"), "ru");
+        assert_eq!(top("Нова гра, їжа"), "uk");
+    }
+
+    #[test]
+    fn detects_latin_languages() {
+        assert_eq!(top("Press start to continue the game"), "en");
+        assert_eq!(top("Nuova partita - Opzioni - Esci"), "it");
+        assert_eq!(top("Neues Spiel laden und speichern"), "de");
+        assert_eq!(top("Appuyez sur une touche pour continuer"), "fr");
+        assert_eq!(top("Nueva partida, cargar o salir"), "es");
+        assert_eq!(top("Novo jogo e configurações"), "pt");
+        // Nessun indizio: inglese come ripiego
+        assert_eq!(top("Xyzzy"), "en");
+    }
+
+    #[test]
+    fn mixed_text_gives_ranked_candidates() {
+        let candidates = detect_language("New Game 新しいゲーム");
+        let languages: Vec<&str> = candidates.iter().map(|c| c.language.as_str()).collect();
+        assert!(languages.contains(&"en") && languages.contains(&"ja"));
+        assert!(candidates.windows(2).all(|w| w[0].confidence >= w[1].confidence));
+        assert!(candidates.iter().map(|c| c.confidence).sum::<f32>() <= 1.01);
+    }
+
+    #[test]
+    fn short_text_is_not_detected() {
+        assert!(detect_language("OK").is_empty());
+        assert!(detect_language("123 !!").is_empty());
+    }
+}
@@ -2,9 +2,16 @@
 // Cattura schermo → OCR → Traduzione → Overlay
 
 mod screen_capture;
+mod dxgi_capture;
 mod ocr_engine;
 mod overlay;
 pub mod retro_preprocessor;
+pub mod region_picker;
+pub mod frame_diff;
+pub mod text_stabilizer;
+mod language_detect;
+mod ocr_profiles;
+mod timing;
 
 // Usati internamente dal modulo
 #[allow(unused_imports)]
@@ -16,15 +23,32 @@ use overlay::*;
 
 use tauri::command;
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::collections::HashMap;
 use once_cell::sync::Lazy;
 
 static OCR_RUNNING: AtomicBool = AtomicBool::new(false);
 static LAST_TEXTS: Lazy<Mutex<Vec<DetectedText>>> = Lazy::new(|| Mutex::new(Vec::new()));
+/// Origine sul desktop della cattura da cui provengono `LAST_TEXTS`
+static LAST_CAPTURE_ORIGIN: Lazy<Mutex<(i32, i32)>> = Lazy::new(|| Mutex::new((0, 0)));
 static TRANSLATION_CACHE: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
 static OCR_CONFIG: Lazy<Mutex<Option<OcrConfig>>> = Lazy::new(|| Mutex::new(None));
+/// Testi raccolti nella sessione corrente (chiave: testo ripulito), con la confidenza migliore
+static SESSION_TEXTS: Lazy<Mutex<HashMap<String, DetectedText>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+/// Testi massimi per sessione: oltre, un testo nuovo sostituisce quello meno affidabile
+const MAX_SESSION_TEXTS: usize = 5000;
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+static CAPTURE_STATUS: Lazy<Mutex<CaptureStatus>> = Lazy::new(|| Mutex::new(CaptureStatus::default()));
+static TIMINGS: Lazy<Mutex<timing::PipelineTimings>> = Lazy::new(|| Mutex::new(timing::PipelineTimings::default()));
+
+/// Valore di `OcrConfig.language` che attiva il rilevamento automatico della lingua
+pub const AUTO_LANGUAGE: &str = "auto";
+/// Lingua sorgente usata in modalità "auto" finché non viene rilevata
+const AUTO_FALLBACK_LANGUAGE: &str = "en";
+/// Confidenza minima per adottare (o cambiare) la lingua rilevata
+const AUTO_DETECT_MIN_CONFIDENCE: f32 = 0.5;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DetectedText {
@@ -39,15 +63,104 @@ pub struct DetectedText {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OcrConfig {
-    pub language: String,           // "en", "ja", "zh", etc.
+    pub language: String,           // "en", "ja", "zh", etc. ("auto" = rilevata dai testi letti)
     pub target_language: String,    // Lingua di traduzione
     pub capture_interval_ms: u64,   // Intervallo cattura (default 500ms)
     pub min_confidence: f32,        // Confidenza minima OCR (0.0-1.0)
     pub region: Option<CaptureRegion>, // Regione specifica o tutto schermo
     pub target_window: Option<isize>, // HWND della finestra da catturare (None = schermo intero)
+    /// Titolo (o parte) della finestra da catturare: l'HWND viene risolto a ogni avvio e
+    /// ricercato se la finestra si chiude. Ha la precedenza su `target_window`
+    #[serde(default)]
+    pub target_window_title: Option<String>,
+    /// Variazione minima (0.0-1.0) del blocco più cambiato tra due frame per rilanciare l'OCR;
+    /// 0 = OCR a ogni cattura
+    #[serde(default = "default_frame_change_threshold")]
+    pub frame_change_threshold: f32,
+    /// Ogni quanto rifare comunque l'OCR su schermata ferma (cambi sfuggiti alla soglia)
+    #[serde(default = "default_static_refresh_ms")]
+    pub static_refresh_ms: u64,
+    /// Ignora maiuscole/minuscole nella chiave della cache traduzioni
+    #[serde(default)]
+    pub cache_case_insensitive: bool,
+    /// Frame su cui fondere le letture della stessa regione (1 = nessuna fusione)
+    #[serde(default = "default_stabilization_frames")]
+    pub stabilization_frames: usize,
+    /// Metodo di cattura (default: DXGI con ripiego automatico su GDI)
+    #[serde(default)]
+    pub capture_method: CaptureMethod,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Metodo di cattura dello schermo
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureMethod {
+    /// DXGI Desktop Duplication, GDI quando DXGI fallisce (accesso negato, HDR, più monitor)
+    #[default]
+    Auto,
+    /// Solo DXGI: errore se non disponibile (diagnostica)
+    Dxgi,
+    /// Solo GDI BitBlt (comportamento storico; frame neri con DirectX fullscreen)
+    Gdi,
+}
+
+/// Stato del ciclo di cattura OCR
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureState {
+    /// OCR non avviato
+    #[default]
+    Idle,
+    /// Finestra bersaglio non ancora trovata
+    WaitingForWindow,
+    Capturing,
+    /// Finestra minimizzata o nascosta: OCR in pausa
+    Minimized,
+    /// Finestra coperta da altre finestre: OCR in pausa
+    Occluded,
+    /// Frame completamente nero: OCR in pausa
+    BlankFrame,
+    /// Cattura fallita (vedi `last_error`)
+    Error,
+}
+
+impl CaptureState {
+    fn is_paused(self) -> bool {
+        matches!(self, CaptureState::Minimized | CaptureState::Occluded | CaptureState::BlankFrame)
+    }
+}
+
+/// Stato della cattura nella sessione OCR corrente
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CaptureStatus {
+    pub state: CaptureState,
+    /// Inizio dello stato corrente (RFC 3339)
+    pub state_since: Option<String>,
+    pub method: CaptureMethod,
+    /// Backend dell'ultima cattura riuscita
+    pub backend: Option<screen_capture::CaptureBackend>,
+    /// Perché si sta usando GDI invece di DXGI
+    pub fallback_reason: Option<String>,
+    pub last_error: Option<String>,
+    pub frames_captured: u64,
+    /// Lingua sorgente rilevata (solo con `language: "auto"`)
+    pub detected_language: Option<String>,
+}
+
+fn default_frame_change_threshold() -> f32 {
+    // ~13 livelli di luminanza: sopra il rumore di compressione, sotto qualsiasi testo
+    0.05
+}
+
+fn default_static_refresh_ms() -> u64 {
+    5000
+}
+
+fn default_stabilization_frames() -> usize {
+    5
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CaptureRegion {
     pub x: i32,
     pub y: i32,
@@ -64,21 +177,47 @@ impl Default for OcrConfig {
             min_confidence: 0.5,
             region: None,
             target_window: None,
+            target_window_title: None,
+            frame_change_threshold: default_frame_change_threshold(),
+            static_refresh_ms: default_static_refresh_ms(),
+            cache_case_insensitive: false,
+            stabilization_frames: default_stabilization_frames(),
+            capture_method: CaptureMethod::default(),
         }
     }
 }
 
-/// Avvia il sistema OCR translator
+/// Avvia il sistema OCR translator. Senza `config` usa il profilo salvato di `game_id`
+/// (o la configurazione di default se il gioco non ne ha uno).
 #[command]
-pub async fn start_ocr_translator(config: OcrConfig) -> Result<String, String> {
+pub async fn start_ocr_translator(config: Option<OcrConfig>, game_id: Option<String>) -> Result<String, String> {
     if OCR_RUNNING.load(Ordering::SeqCst) {
         return Ok("OCR già in esecuzione".to_string());
     }
     
+    let game_id = game_id.as_deref().map(str::trim).filter(|id| !id.is_empty());
+    let config = match (config, game_id) {
+        (Some(config), _) => config,
+        (None, Some(game_id)) => match ocr_profiles::load_profile(game_id)? {
+            Some(profile) => {
+                log::info!("📂 Profilo OCR caricato per {} (aggiornato {})", game_id, profile.updated_at);
+                profile.config
+            }
+            None => {
+                log::info!("ℹ️ Nessun profilo OCR per {}, uso la configurazione di default", game_id);
+                OcrConfig::default()
+            }
+        },
+        (None, None) => OcrConfig::default(),
+    };
+    
     // Salva config globalmente
     if let Ok(mut cfg) = OCR_CONFIG.lock() {
         *cfg = Some(config.clone());
     }
+    if let Ok(mut session) = SESSION_TEXTS.lock() {
+        session.clear();
+    }
     
     OCR_RUNNING.store(true, Ordering::SeqCst);
     log::info!("🔍 Avvio OCR Translator (source: {}, target: {})", config.language, config.target_language);
@@ -107,6 +246,143 @@ pub async fn get_detected_texts() -> Result<Vec<DetectedText>, String> {
     Ok(texts.clone())
 }
 
+/// Testi rilevati in coordinate CSS della finestra overlay (posizione e scala DPI già applicate),
+/// con la dimensione del font suggerita per ogni riquadro
+#[command]
+pub async fn get_overlay_texts(app: tauri::AppHandle) -> Result<overlay::OverlayLayout, String> {
+    use tauri::Manager;
+    
+    let window = app.get_webview_window("ocr-overlay")
+        .ok_or_else(|| "Finestra overlay non trovata".to_string())?;
+    let position = window.inner_position().map_err(|e| e.to_string())?;
+    let size = window.inner_size().map_err(|e| e.to_string())?;
+    let geometry = overlay::OverlayGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        scale_factor: window.scale_factor().map_err(|e| e.to_string())?,
+    };
+    
+    let texts = LAST_TEXTS.lock().map_err(|e| e.to_string())?.clone();
+    let origin = *LAST_CAPTURE_ORIGIN.lock().map_err(|e| e.to_string())?;
+    Ok(overlay::layout_texts(&texts, origin, &geometry))
+}
+
+/// Statistiche della cache traduzioni OCR
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Percentuale di hit (0.0-1.0), 0 se non ci sono ancora lookup
+    pub hit_rate: f32,
+}
+
+/// Statistiche della cache traduzioni (dall'avvio dell'app)
+#[command]
+pub async fn get_ocr_cache_stats() -> Result<OcrCacheStats, String> {
+    let entries = TRANSLATION_CACHE.lock().map_err(|e| e.to_string())?.len();
+    let hits = CACHE_HITS.load(Ordering::Relaxed);
+    let misses = CACHE_MISSES.load(Ordering::Relaxed);
+    let lookups = hits + misses;
+    Ok(OcrCacheStats {
+        entries,
+        hits,
+        misses,
+        hit_rate: if lookups > 0 { hits as f32 / lookups as f32 } else { 0.0 },
+    })
+}
+
+/// Confidenza minima di default per l'export in patch
+const DEFAULT_EXPORT_MIN_CONFIDENCE: f32 = 0.7;
+
+/// Esporta i testi raccolti nella sessione OCR nella patch del gioco (creata se non esiste)
+#[command]
+pub async fn export_ocr_session_to_patch(game_id: String, min_confidence: Option<f32>) -> Result<crate::commands::patches::PatchUpsertResult, String> {
+    let threshold = min_confidence.unwrap_or(DEFAULT_EXPORT_MIN_CONFIDENCE);
+    let (source_lang, target_lang) = OCR_CONFIG.lock().map_err(|e| e.to_string())?
+        .as_ref()
+        .map(|c| (c.language.clone(), c.target_language.clone()))
+        .ok_or_else(|| "Nessuna sessione OCR avviata".to_string())?;
+    let source_lang = if source_lang.eq_ignore_ascii_case(AUTO_LANGUAGE) {
+        CAPTURE_STATUS.lock().ok()
+            .and_then(|s| s.detected_language.clone())
+            .unwrap_or_else(|| AUTO_FALLBACK_LANGUAGE.to_string())
+    } else {
+        source_lang
+    };
+    
+    let mut entries: Vec<crate::commands::patches::SourcedTranslation> = {
+        let session = SESSION_TEXTS.lock().map_err(|e| e.to_string())?;
+        session.iter()
+            .filter(|(_, t)| t.confidence >= threshold)
+            .map(|(original, t)| crate::commands::patches::SourcedTranslation {
+                original: original.clone(),
+                translated: t.translated.clone(),
+                confidence: t.confidence,
+            })
+            .collect()
+    };
+    if entries.is_empty() {
+        return Err(format!("Nessun testo OCR con confidenza ≥ {:.2} da esportare", threshold));
+    }
+    entries.sort_by(|a, b| a.original.cmp(&b.original));
+    
+    log::info!("📤 Export sessione OCR → patch {} ({} testi)", game_id, entries.len());
+    crate::commands::patches::upsert_sourced_translations(&game_id, "ocr", &source_lang, &target_lang, &entries)
+}
+
+/// Stato della cattura (in corso, in pausa per finestra minimizzata/coperta, ...), backend
+/// usato (DXGI o GDI) ed eventuale motivo del ripiego
+#[command]
+pub async fn get_ocr_capture_status() -> Result<CaptureStatus, String> {
+    CAPTURE_STATUS.lock().map(|s| s.clone()).map_err(|e| e.to_string())
+}
+
+/// Durate delle fasi del ciclo OCR (cattura, riconoscimento, traduzione, overlay) sulle
+/// ultime iterazioni: min/media/max/p95 in millisecondi
+#[command]
+pub async fn get_ocr_timing_stats() -> Result<timing::OcrTimingStats, String> {
+    TIMINGS.lock().map(|t| t.stats()).map_err(|e| e.to_string())
+}
+
+/// Lingue candidate di un testo (rilevamento offline), dalla più probabile
+#[command]
+pub async fn detect_language(text: String) -> Result<Vec<language_detect::LanguageCandidate>, String> {
+    Ok(language_detect::detect_language(&text))
+}
+
+/// Salva la configurazione OCR di un gioco (l'HWND non viene salvato, il titolo finestra sì)
+#[command]
+pub async fn save_ocr_profile(game_id: String, config: OcrConfig) -> Result<ocr_profiles::OcrProfile, String> {
+    let profile = ocr_profiles::save_profile(&game_id, config)?;
+    log::info!("💾 Profilo OCR salvato per {}", profile.game_id);
+    Ok(profile)
+}
+
+/// Profilo OCR salvato di un gioco
+#[command]
+pub async fn load_ocr_profile(game_id: String) -> Result<Option<ocr_profiles::OcrProfile>, String> {
+    ocr_profiles::load_profile(&game_id)
+}
+
+/// Tutti i profili OCR salvati
+#[command]
+pub async fn list_ocr_profiles() -> Result<Vec<ocr_profiles::OcrProfile>, String> {
+    ocr_profiles::list_profiles()
+}
+
+/// Elimina il profilo OCR di un gioco
+#[command]
+pub async fn delete_ocr_profile(game_id: String) -> Result<bool, String> {
+    let deleted = ocr_profiles::delete_profile(&game_id)?;
+    if deleted {
+        log::info!("🗑️ Profilo OCR eliminato per {}", game_id);
+    }
+    Ok(deleted)
+}
+
 /// Stato OCR
 #[command]
 pub async fn is_ocr_running() -> bool {
@@ -119,6 +395,101 @@ pub async fn list_capture_windows() -> Result<Vec<screen_capture::WindowInfo>, S
     Ok(screen_capture::list_windows())
 }
 
+/// Screenshot dell'intero desktop virtuale per scegliere la regione OCR
+#[derive(Debug, Clone, Serialize)]
+pub struct FullScreenshot {
+    /// PNG in base64 (senza prefisso `data:`)
+    pub png_base64: String,
+    /// Origine del desktop virtuale: il pixel (0,0) dell'immagine corrisponde a (origin_x, origin_y)
+    pub origin_x: i32,
+    pub origin_y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub monitors: Vec<region_picker::MonitorBounds>,
+}
+
+/// Esito della validazione di una regione di cattura
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureRegionValidation {
+    pub valid: bool,
+    pub issues: Vec<String>,
+    /// Parte della regione effettivamente visibile (da proporre se la regione esce dallo schermo)
+    pub clamped_region: Option<CaptureRegion>,
+    /// Monitor che contiene la regione (indice in `monitors`)
+    pub monitor_index: Option<usize>,
+    /// Anteprima PNG in base64 dei pixel che verrebbero catturati
+    pub thumbnail_base64: Option<String>,
+    pub monitors: Vec<region_picker::MonitorBounds>,
+}
+
+fn virtual_screen() -> Result<(CaptureRegion, Vec<region_picker::MonitorBounds>), String> {
+    let monitors = screen_capture::list_monitors();
+    let bounds = region_picker::virtual_bounds(&monitors)
+        .ok_or("Nessun monitor rilevato (cattura supportata solo su Windows)")?;
+    Ok((bounds, monitors))
+}
+
+/// Cattura tutti i monitor in un'unica immagine, per disegnare la regione OCR
+#[command]
+pub async fn capture_full_screenshot() -> Result<FullScreenshot, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let (bounds, monitors) = virtual_screen()?;
+        let image = screen_capture::capture_screen(&Some(bounds.clone()))?;
+        log::info!("📸 Screenshot desktop virtuale {}x{} ({} monitor)", image.width, image.height, monitors.len());
+        
+        Ok(FullScreenshot {
+            png_base64: region_picker::encode_png_base64(&image)?,
+            origin_x: bounds.x,
+            origin_y: bounds.y,
+            width: image.width,
+            height: image.height,
+            monitors,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Verifica che la regione sia dentro i monitor e restituisce l'anteprima dei pixel catturati
+#[command]
+pub async fn validate_capture_region(region: CaptureRegion, thumbnail_size: Option<u32>) -> Result<CaptureRegionValidation, String> {
+    let max_side = thumbnail_size.unwrap_or(320).clamp(32, 2048);
+    
+    tauri::async_runtime::spawn_blocking(move || {
+        let (bounds, monitors) = virtual_screen()?;
+        let check = region_picker::check_region(&region, &bounds, &monitors);
+        
+        let thumbnail_base64 = match &check.clamped {
+            Some(visible) => {
+                let image = screen_capture::capture_screen(&Some(visible.clone()))?;
+                Some(region_picker::encode_png_base64(&region_picker::thumbnail(&image, max_side))?)
+            }
+            None => None,
+        };
+        
+        if !check.issues.is_empty() {
+            log::warn!("⚠️ Regione OCR non valida: {}", check.issues.join("; "));
+        }
+        
+        Ok(CaptureRegionValidation {
+            valid: check.issues.is_empty(),
+            issues: check.issues,
+            clamped_region: check.clamped,
+            monitor_index: check.monitor_index,
+            thumbnail_base64,
+            monitors,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Cerca una finestra per titolo (anche parziale) tra quelle catturabili
+#[command]
+pub async fn find_window_by_title(substring: String) -> Result<Option<screen_capture::WindowInfo>, String> {
+    Ok(screen_capture::find_window_by_title(&substring))
+}
+
 /// Mostra/nasconde la finestra overlay OCR
 #[command]
 pub async fn toggle_ocr_overlay(app: tauri::AppHandle, show: bool) -> Result<(), String> {
@@ -215,40 +586,150 @@ pub async fn position_overlay_on_window(app: tauri::AppHandle, hwnd: isize) -> R
     Ok(())
 }
 
+/// Intervallo di ricerca della finestra quando il gioco non è (ancora) aperto
+const WINDOW_POLL_INTERVAL_MS: u64 = 1000;
+
+/// Attende che compaia una finestra con il titolo indicato (finché l'OCR è attivo)
+fn wait_for_window(title: &str) -> Option<isize> {
+    set_capture_state(CaptureState::WaitingForWindow);
+    let mut logged = false;
+    while OCR_RUNNING.load(Ordering::SeqCst) {
+        if let Some(window) = screen_capture::find_window_by_title(title) {
+            log::info!("🪟 Finestra \"{}\" trovata: {} (HWND {})", title, window.title, window.hwnd);
+            return Some(window.hwnd);
+        }
+        if !logged {
+            log::info!("⏳ In attesa della finestra \"{}\"...", title);
+            logged = true;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(WINDOW_POLL_INTERVAL_MS));
+    }
+    None
+}
+
 fn run_ocr_loop(config: OcrConfig) {
-    log::info!("📷 OCR loop avviato (target_window: {:?})", config.target_window);
+    log::info!("📷 OCR loop avviato (target_window: {:?}, titolo: {:?})", config.target_window, config.target_window_title);
+    if let Ok(mut status) = CAPTURE_STATUS.lock() {
+        *status = CaptureStatus { method: config.capture_method, ..Default::default() };
+    }
+    if let Ok(mut timings) = TIMINGS.lock() {
+        *timings = timing::PipelineTimings::default();
+    }
+    
+    let window_title = config.target_window_title.as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+    let mut target_window = match window_title {
+        Some(title) => wait_for_window(title),
+        None => config.target_window,
+    };
+    
+    // Impronta dell'ultimo frame passato all'OCR
+    let mut last_fingerprint: Option<frame_diff::FrameFingerprint> = None;
+    let mut last_ocr_at = std::time::Instant::now();
+    let mut stabilizer = text_stabilizer::TextStabilizer::new(config.stabilization_frames);
+    let mut capture = screen_capture::CaptureSession::new(config.capture_method);
+    let auto_language = config.language.eq_ignore_ascii_case(AUTO_LANGUAGE);
+    let mut detected_language: Option<String> = None;
     
     while OCR_RUNNING.load(Ordering::SeqCst) {
+        // Finestra chiusa (es. gioco riavviato): ricerca per titolo
+        if let (Some(title), Some(hwnd)) = (window_title, target_window) {
+            if !screen_capture::is_window_alive(hwnd) {
+                log::info!("🪟 Finestra \"{}\" chiusa, nuova ricerca...", title);
+                target_window = wait_for_window(title);
+                last_fingerprint = None;
+                stabilizer.reset();
+                continue;
+            }
+        }
+        
+        // Finestra minimizzata o coperta: la cattura darebbe pixel spazzatura, OCR in pausa
+        let paused_state = target_window.and_then(|hwnd| match screen_capture::window_visibility(hwnd) {
+            screen_capture::WindowVisibility::Visible => None,
+            screen_capture::WindowVisibility::Minimized | screen_capture::WindowVisibility::Hidden => Some(CaptureState::Minimized),
+            screen_capture::WindowVisibility::Occluded => Some(CaptureState::Occluded),
+        });
+        if let Some(state) = paused_state {
+            pause_capture(state, &mut last_fingerprint, &mut stabilizer);
+            std::thread::sleep(std::time::Duration::from_millis(config.capture_interval_ms));
+            continue;
+        }
+        
         // 1. Cattura schermo o finestra specifica
-        let capture_result = if let Some(hwnd) = config.target_window {
-            screen_capture::capture_window(hwnd)
-        } else {
-            screen_capture::capture_screen(&config.region)
-        };
+        let phase_start = std::time::Instant::now();
+        let capture_origin = screen_capture::capture_origin(target_window, &config.region);
+        let capture_result = capture.capture(target_window, &config.region);
+        record_timing(timing::Phase::Capture, phase_start);
+        record_capture_status(&capture_result, &capture);
         
         match capture_result {
-            Ok(image_data) => {
+            Ok((image_data, _)) => {
+                let fingerprint = frame_diff::FrameFingerprint::from_image(&image_data);
+                if fingerprint.as_ref().is_some_and(|f| f.is_blank()) {
+                    pause_capture(CaptureState::BlankFrame, &mut last_fingerprint, &mut stabilizer);
+                    std::thread::sleep(std::time::Duration::from_millis(config.capture_interval_ms));
+                    continue;
+                }
+                set_capture_state(CaptureState::Capturing);
+                
+                // Schermata ferma: salta l'OCR finché non scade il refresh periodico
+                let unchanged = config.frame_change_threshold > 0.0
+                    && matches!((&last_fingerprint, &fingerprint),
+                                (Some(prev), Some(curr)) if prev.difference(curr) < config.frame_change_threshold);
+                let refresh_due = last_ocr_at.elapsed().as_millis() as u64 >= config.static_refresh_ms;
+                if unchanged && !refresh_due {
+                    std::thread::sleep(std::time::Duration::from_millis(config.capture_interval_ms));
+                    continue;
+                }
+                last_fingerprint = fingerprint;
+                last_ocr_at = std::time::Instant::now();
+                
                 // 2. OCR
-                match ocr_engine::recognize_text(&image_data, &config.language) {
+                // In modalità "auto" l'OCR usa le lingue del profilo finché non ne rileva una
+                let ocr_language = detected_language.as_deref().unwrap_or(&config.language);
+                let phase_start = std::time::Instant::now();
+                match ocr_engine::recognize_text(&image_data, ocr_language) {
                     Ok(texts) => {
                         // Filtra per confidenza
-                        let mut filtered: Vec<DetectedText> = texts
+                        let filtered: Vec<DetectedText> = texts
                             .into_iter()
                             .filter(|t| t.confidence >= config.min_confidence)
                             .collect();
                         
+                        // Fonde con le letture dei frame precedenti (niente sfarfallio)
+                        let mut filtered = stabilizer.update(filtered);
+                        
+                        if auto_language {
+                            update_detected_language(&filtered, &mut detected_language);
+                        }
+                        let source_language = match (&detected_language, auto_language) {
+                            (Some(language), _) => language.as_str(),
+                            (None, true) => AUTO_FALLBACK_LANGUAGE,
+                            (None, false) => config.language.as_str(),
+                        };
+                        record_timing(timing::Phase::Ocr, phase_start);
+                        
                         // 3. Traduci i testi
-                        translate_detected_texts(&mut filtered, &config.target_language);
+                        let phase_start = std::time::Instant::now();
+                        translate_detected_texts(&mut filtered, source_language, &config.target_language, config.cache_case_insensitive);
+                        record_timing(timing::Phase::Translate, phase_start);
                         
                         if !filtered.is_empty() {
                             let translated_count = filtered.iter().filter(|t| t.translated.is_some()).count();
                             log::debug!("🔤 Rilevati {} testi, {} tradotti", filtered.len(), translated_count);
                         }
                         
-                        // Salva risultati
+                        // 4. Aggiorna i testi letti dall'overlay
+                        let phase_start = std::time::Instant::now();
+                        record_session_texts(&filtered);
                         if let Ok(mut last) = LAST_TEXTS.lock() {
                             *last = filtered;
                         }
+                        if let Ok(mut origin) = LAST_CAPTURE_ORIGIN.lock() {
+                            *origin = capture_origin;
+                        }
+                        record_timing(timing::Phase::Overlay, phase_start);
                     }
                     Err(e) => {
                         log::warn!("OCR error: {}", e);
@@ -264,87 +745,174 @@ fn run_ocr_loop(config: OcrConfig) {
         std::thread::sleep(std::time::Duration::from_millis(config.capture_interval_ms));
     }
     
+    set_capture_state(CaptureState::Idle);
     log::info!("📷 OCR loop terminato");
 }
 
-/// Traduce i testi rilevati usando Translation Memory + dizionario locale
-fn translate_detected_texts(texts: &mut Vec<DetectedText>, target_lang: &str) {
+/// Aggiorna lo stato del backend di cattura, loggando i cambi di backend
+fn record_capture_status(
+    result: &Result<(screen_capture::ImageData, screen_capture::CaptureBackend), String>,
+    capture: &screen_capture::CaptureSession,
+) {
+    let Ok(mut status) = CAPTURE_STATUS.lock() else { return };
+    match result {
+        Ok((_, backend)) => {
+            if status.backend != Some(*backend) {
+                log::info!("📷 Backend di cattura: {:?}", backend);
+            }
+            status.backend = Some(*backend);
+            status.frames_captured += 1;
+            status.last_error = None;
+        }
+        Err(e) => status.last_error = Some(e.clone()),
+    }
+    status.fallback_reason = capture.fallback_reason.clone();
+    drop(status);
+    if result.is_err() {
+        set_capture_state(CaptureState::Error);
+    }
+}
+
+fn record_timing(phase: timing::Phase, started: std::time::Instant) {
+    if let Ok(mut timings) = TIMINGS.lock() {
+        timings.record(phase, started.elapsed());
+    }
+}
+
+/// Rileva la lingua dai testi letti e la adotta se abbastanza sicura
+fn update_detected_language(texts: &[DetectedText], detected: &mut Option<String>) {
+    let joined = texts.iter().map(|t| t.text.as_str()).collect::<Vec<_>>().join(" ");
+    let Some(best) = language_detect::detect_language(&joined).into_iter().next() else { return };
+    if best.confidence < AUTO_DETECT_MIN_CONFIDENCE || detected.as_deref() == Some(best.language.as_str()) {
+        return;
+    }
+    log::info!("🌍 Lingua sorgente rilevata: {} ({:.0}%)", best.language, best.confidence * 100.0);
+    if let Ok(mut status) = CAPTURE_STATUS.lock() {
+        status.detected_language = Some(best.language.clone());
+    }
+    *detected = Some(best.language);
+}
+
+/// Cambia lo stato della cattura, loggando pause e riprese
+fn set_capture_state(state: CaptureState) {
+    let Ok(mut status) = CAPTURE_STATUS.lock() else { return };
+    if status.state == state {
+        return;
+    }
+    if state.is_paused() {
+        log::info!("⏸️ Cattura OCR in pausa: {:?}", state);
+    } else if status.state.is_paused() {
+        log::info!("▶️ Cattura OCR ripresa ({:?})", state);
+    }
+    status.state = state;
+    status.state_since = Some(chrono::Utc::now().to_rfc3339());
+}
+
+/// Mette in pausa l'OCR: svuota i testi mostrati dall'overlay e riparte da zero alla ripresa
+fn pause_capture(
+    state: CaptureState,
+    last_fingerprint: &mut Option<frame_diff::FrameFingerprint>,
+    stabilizer: &mut text_stabilizer::TextStabilizer,
+) {
+    set_capture_state(state);
+    *last_fingerprint = None;
+    stabilizer.reset();
+    if let Ok(mut last) = LAST_TEXTS.lock() {
+        last.clear();
+    }
+}
+
+/// Accumula i testi del frame nella sessione, deduplicati per testo ripulito
+fn record_session_texts(texts: &[DetectedText]) {
+    let Ok(mut session) = SESSION_TEXTS.lock() else { return };
+    for text in texts {
+        let key = clean_ocr_text(&text.text);
+        if key.is_empty() {
+            continue;
+        }
+        match session.get_mut(&key) {
+            Some(existing) => {
+                if text.confidence > existing.confidence {
+                    existing.confidence = text.confidence;
+                }
+                if text.translated.is_some() {
+                    existing.translated = text.translated.clone();
+                }
+            }
+            None => {
+                if session.len() >= MAX_SESSION_TEXTS {
+                    // Sessioni lunghe (menu animati, HUD che cambiano) non crescono senza limite
+                    let weakest = session.iter()
+                        .min_by(|(_, a), (_, b)| a.confidence.total_cmp(&b.confidence))
+                        .filter(|(_, t)| t.confidence < text.confidence)
+                        .map(|(k, _)| k.clone());
+                    match weakest {
+                        Some(weakest) => { session.remove(&weakest); }
+                        None => continue,
+                    }
+                }
+                session.insert(key, text.clone());
+            }
+        }
+    }
+}
+
+/// Caratteri che l'OCR aggiunge spesso ai bordi del testo (bordi di finestre, cursori, rumore)
+const OCR_EDGE_NOISE: &[char] = &['|', '¦', '_', '~', '`', '•', '·', '\'', '"', '«', '»', '[', ']', '{', '}'];
+
+/// Testo ripulito per la traduzione: spazi collassati e rumore ai bordi rimosso
+fn clean_ocr_text(text: &str) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    collapsed
+        .trim_matches(|c: char| OCR_EDGE_NOISE.contains(&c) || c.is_whitespace())
+        .to_string()
+}
+
+/// Chiave della cache traduzioni: testo ripulito e tipografia uniformata, opzionalmente in minuscolo
+fn translation_cache_key(text: &str, case_insensitive: bool) -> String {
+    let cleaned = clean_ocr_text(text);
+    let cleaned = crate::string_id::lookup_text(&cleaned);
+    if case_insensitive {
+        cleaned.to_lowercase()
+    } else {
+        cleaned.into_owned()
+    }
+}
+
+/// Traduce i testi rilevati con la traduzione offline (TM, glossario, dizionario integrato)
+/// Il testo originale resta invariato per la visualizzazione; la cache usa la chiave normalizzata
+fn translate_detected_texts(texts: &mut [DetectedText], source_lang: &str, target_lang: &str, case_insensitive: bool) {
     let mut cache = match TRANSLATION_CACHE.lock() {
         Ok(c) => c,
         Err(_) => return,
     };
     
-    // Carica traduzioni dalla TM se disponibile
-    let tm_translations = load_tm_translations(target_lang);
-    
     for text in texts.iter_mut() {
-        // 1. Cerca in cache runtime
-        if let Some(translated) = cache.get(&text.text) {
-            text.translated = Some(translated.clone());
+        let key = translation_cache_key(&text.text, case_insensitive);
+        if key.is_empty() {
             continue;
         }
         
-        // 2. Cerca nella Translation Memory (priorità)
-        if let Some(translated) = tm_translations.get(&text.text.to_lowercase()) {
-            cache.insert(text.text.clone(), translated.clone());
+        // 1. Cerca in cache runtime
+        if let Some(translated) = cache.get(&key) {
+            CACHE_HITS.fetch_add(1, Ordering::Relaxed);
             text.translated = Some(translated.clone());
             continue;
         }
+        CACHE_MISSES.fetch_add(1, Ordering::Relaxed);
         
-        // 3. Fallback: dizionario integrato
-        let translated = simple_translate(&text.text, target_lang);
-        if let Some(ref t) = translated {
-            cache.insert(text.text.clone(), t.clone());
+        // 2. Traduzione offline (unico punto di ingresso condiviso con UE bridge)
+        let cleaned = clean_ocr_text(&text.text);
+        let result = crate::commands::translation::translate_offline_text(&cleaned, source_lang, target_lang, None, None);
+        if let Some(ref t) = result.translated {
+            cache.insert(key, t.clone());
         }
-        text.translated = translated;
+        text.translated = result.translated;
     }
 }
 
-/// Carica traduzioni dalla Translation Memory
-fn load_tm_translations(target_lang: &str) -> HashMap<String, String> {
-    use std::fs;
-    
-    let mut translations = HashMap::new();
-    
-    // Percorso TM
-    let local_app_data = match std::env::var("LOCALAPPDATA") {
-        Ok(p) => p,
-        Err(_) => return translations,
-    };
-    
-    let tm_path = std::path::PathBuf::from(local_app_data)
-        .join("GameStringer")
-        .join("translation_memory")
-        .join(format!("tm_en_{}.json", target_lang));
-    
-    if !tm_path.exists() {
-        return translations;
-    }
-    
-    if let Ok(content) = fs::read_to_string(&tm_path) {
-        if let Ok(tm) = serde_json::from_str::<serde_json::Value>(&content) {
-            if let Some(units) = tm.get("units").and_then(|u| u.as_array()) {
-                for unit in units {
-                    if let (Some(source), Some(target)) = (
-                        unit.get("sourceText").and_then(|s| s.as_str()),
-                        unit.get("targetText").and_then(|t| t.as_str()),
-                    ) {
-                        translations.insert(source.to_lowercase(), target.to_string());
-                    }
-                }
-            }
-        }
-    }
-    
-    if !translations.is_empty() {
-        log::debug!("📚 Caricate {} traduzioni dalla TM", translations.len());
-    }
-    
-    translations
-}
-
 /// Traduzione semplice usando dizionario integrato (per demo)
-fn simple_translate(text: &str, target_lang: &str) -> Option<String> {
+pub(crate) fn simple_translate(text: &str, target_lang: &str) -> Option<String> {
     if target_lang != "it" {
         return None;
     }
@@ -588,6 +1156,10 @@ fn simple_translate(text: &str, target_lang: &str) -> Option<String> {
         ("Main Menu", "Menu Principale"),
     ].iter().cloned().collect();
     
+    // Le chiavi del dizionario usano la tipografia ASCII
+    let text = crate::string_id::lookup_text(text);
+    let text = &*text;
+    
     // Match esatto
     if let Some(t) = dict.get(text) {
         return Some(t.to_string());
@@ -603,3 +1175,32 @@ fn simple_translate(text: &str, target_lang: &str) -> Option<String> {
     
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_key_ignores_ocr_jitter() {
+        let base = translation_cache_key("Press START to continue", false);
+        assert_eq!(translation_cache_key("  Press  START\tto continue ", false), base);
+        assert_eq!(translation_cache_key("| Press START to continue_", false), base);
+        assert_ne!(translation_cache_key("press start to continue", false), base);
+        assert_eq!(translation_cache_key("press start to continue", true),
+                   translation_cache_key("Press START to continue", true));
+    }
+
+    #[test]
+    fn cache_key_keeps_sentence_punctuation() {
+        assert_eq!(translation_cache_key("Wait!", false), "Wait!");
+        assert_eq!(translation_cache_key("\"Really?\"", false), "Really?");
+        assert_eq!(translation_cache_key(" | ", false), "");
+    }
+
+    #[test]
+    fn cache_key_and_dictionary_normalize_typography() {
+        crate::string_id::set_typography_normalization(true);
+        assert_eq!(translation_cache_key("Don\u{2019}t go\u{2026}", false), "Don't go...");
+        assert_eq!(simple_translate("Game\u{00A0}Over", "it").as_deref(), Some("Fine Partita"));
+    }
+}
@@ -4,6 +4,8 @@ use super::{DetectedText, screen_capture::ImageData};
 
 #[cfg(target_os = "windows")]
 use windows::{
+    core::HSTRING,
+    Globalization::Language,
     Media::Ocr::OcrEngine,
     Graphics::Imaging::{BitmapPixelFormat, SoftwareBitmap},
 };
@@ -25,10 +27,9 @@ pub fn recognize_text(image: &ImageData, language: &str) -> Result<Vec<DetectedT
 }
 
 #[cfg(target_os = "windows")]
-async fn recognize_text_async(image: &ImageData, _language: &str) -> Result<Vec<DetectedText>, String> {
+async fn recognize_text_async(image: &ImageData, language: &str) -> Result<Vec<DetectedText>, String> {
     // Crea OcrEngine per la lingua
-    let engine = OcrEngine::TryCreateFromUserProfileLanguages()
-        .map_err(|e| format!("Failed to create OCR engine: {:?}", e))?;
+    let engine = create_engine(language)?;
     
     // Converti BGRA a SoftwareBitmap
     let bitmap = create_software_bitmap(image)?;
@@ -48,24 +49,7 @@ async fn recognize_text_async(image: &ImageData, _language: &str) -> Result<Vec<
                 if let Ok(text) = line.Text() {
                     let text_str = text.to_string();
                     if !text_str.trim().is_empty() {
-                        // Ottieni bounding box dalla prima parola
-                        let (x, y, w, h) = if let Ok(words) = line.Words() {
-                            if words.Size().unwrap_or(0) > 0 {
-                                if let Ok(word) = words.GetAt(0) {
-                                    if let Ok(rect) = word.BoundingRect() {
-                                        (rect.X as i32, rect.Y as i32, rect.Width as i32, rect.Height as i32)
-                                    } else {
-                                        (0, 0, 0, 0)
-                                    }
-                                } else {
-                                    (0, 0, 0, 0)
-                                }
-                            } else {
-                                (0, 0, 0, 0)
-                            }
-                        } else {
-                            (0, 0, 0, 0)
-                        };
+                        let (x, y, w, h) = line_bounds(&line);
                         
                         detected.push(DetectedText {
                             text: text_str,
@@ -86,6 +70,43 @@ async fn recognize_text_async(image: &ImageData, _language: &str) -> Result<Vec<
     Ok(detected)
 }
 
+/// Bounding box della riga: unione dei riquadri di tutte le parole (0 se non disponibili)
+#[cfg(target_os = "windows")]
+fn line_bounds(line: &windows::Media::Ocr::OcrLine) -> (i32, i32, i32, i32) {
+    let Ok(words) = line.Words() else { return (0, 0, 0, 0) };
+    let mut bounds: Option<(f32, f32, f32, f32)> = None;
+    for i in 0..words.Size().unwrap_or(0) {
+        let Some(rect) = words.GetAt(i).ok().and_then(|word| word.BoundingRect().ok()) else { continue };
+        let (right, bottom) = (rect.X + rect.Width, rect.Y + rect.Height);
+        bounds = Some(match bounds {
+            Some((left, top, r, b)) => (left.min(rect.X), top.min(rect.Y), r.max(right), b.max(bottom)),
+            None => (rect.X, rect.Y, right, bottom),
+        });
+    }
+    bounds.map_or((0, 0, 0, 0), |(left, top, right, bottom)| {
+        (left as i32, top as i32, (right - left).ceil() as i32, (bottom - top).ceil() as i32)
+    })
+}
+
+/// OcrEngine per la lingua richiesta; lingue del profilo utente con "auto" o se la lingua
+/// non è installata in Windows
+#[cfg(target_os = "windows")]
+fn create_engine(language: &str) -> Result<OcrEngine, String> {
+    if !language.is_empty() && !language.eq_ignore_ascii_case(super::AUTO_LANGUAGE) {
+        // Windows OCR distingue cinese semplificato e tradizionale
+        let tag = if language.eq_ignore_ascii_case("zh") { "zh-Hans" } else { language };
+        let engine = Language::CreateLanguage(&HSTRING::from(tag)).ok()
+            .filter(|lang| OcrEngine::IsLanguageSupported(lang).unwrap_or(false))
+            .and_then(|lang| OcrEngine::TryCreateFromLanguage(&lang).ok());
+        match engine {
+            Some(engine) => return Ok(engine),
+            None => log::debug!("Lingua OCR {} non installata, uso le lingue del profilo", tag),
+        }
+    }
+    OcrEngine::TryCreateFromUserProfileLanguages()
+        .map_err(|e| format!("Failed to create OCR engine: {:?}", e))
+}
+
 #[cfg(target_os = "windows")]
 fn create_software_bitmap(image: &ImageData) -> Result<SoftwareBitmap, String> {
     use windows::Graphics::Imaging::BitmapAlphaMode;
@@ -0,0 +1,175 @@
+// OCR Profiles - configurazione OCR salvata per gioco
+// Regione, intervallo, confidenza, lingue e titolo della finestra vengono salvati in
+// `GameStringer/ocr_profiles/<game_id codificato>.json`, così `start_ocr_translator` può ripartire
+// con la stessa configurazione dopo un riavvio dell'app.
+
+use super::OcrConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Configurazione OCR salvata per un gioco
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrProfile {
+    pub game_id: String,
+    pub config: OcrConfig,
+    pub updated_at: String,
+}
+
+fn profiles_dir() -> Result<PathBuf, String> {
+    let app_data = dirs::data_local_dir()
+        .ok_or("Impossibile trovare la directory dei dati locali")?;
+    let dir = app_data.join("GameStringer").join("ocr_profiles");
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Errore creazione directory profili OCR: {}", e))?;
+    }
+    Ok(dir)
+}
+
+/// Nome file del profilo: gli id dei giochi possono contenere `:` o `/` (es. "steam:123").
+/// I caratteri fuori da `[A-Za-z0-9_-]` vengono codificati come `%XX` byte per byte, così
+/// due id diversi non finiscono mai nello stesso file
+fn profile_file_name(game_id: &str) -> String {
+    let mut name = String::with_capacity(game_id.len() + 5);
+    for byte in game_id.trim().bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    name.push_str(".json");
+    name
+}
+
+fn check_game_id(game_id: &str) -> Result<&str, String> {
+    let game_id = game_id.trim();
+    if game_id.is_empty() {
+        return Err("game_id mancante".to_string());
+    }
+    Ok(game_id)
+}
+
+/// Legge un profilo solo se appartiene davvero a `game_id` (su filesystem case-insensitive
+/// due giochi possono condividere lo stesso file)
+fn read_profile_for(path: &Path, game_id: &str) -> Result<Option<OcrProfile>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Errore lettura profilo OCR: {}", e))?;
+    let profile: OcrProfile = serde_json::from_str(&content)
+        .map_err(|e| format!("Errore parsing profilo OCR: {}", e))?;
+    if profile.game_id != game_id {
+        log::warn!("⚠️ Profilo OCR {:?} appartiene a '{}', non a '{}'", path, profile.game_id, game_id);
+        return Ok(None);
+    }
+    Ok(Some(profile))
+}
+
+fn save_profile_in(dir: &Path, game_id: &str, mut config: OcrConfig) -> Result<OcrProfile, String> {
+    let game_id = check_game_id(game_id)?;
+    // L'HWND vale solo per la sessione corrente: al prossimo avvio la finestra si ritrova per titolo
+    config.target_window = None;
+    let profile = OcrProfile {
+        game_id: game_id.to_string(),
+        config,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let json = serde_json::to_string_pretty(&profile)
+        .map_err(|e| format!("Errore serializzazione profilo OCR: {}", e))?;
+    crate::commands::file_manager::write_atomic(&dir.join(profile_file_name(game_id)), json.as_bytes())
+        .map_err(|e| format!("Errore salvataggio profilo OCR: {}", e))?;
+    Ok(profile)
+}
+
+fn load_profile_in(dir: &Path, game_id: &str) -> Result<Option<OcrProfile>, String> {
+    let game_id = check_game_id(game_id)?;
+    read_profile_for(&dir.join(profile_file_name(game_id)), game_id)
+}
+
+fn delete_profile_in(dir: &Path, game_id: &str) -> Result<bool, String> {
+    let game_id = check_game_id(game_id)?;
+    let path = dir.join(profile_file_name(game_id));
+    if read_profile_for(&path, game_id).ok().flatten().is_none() {
+        return Ok(false);
+    }
+    fs::remove_file(&path)
+        .map_err(|e| format!("Errore eliminazione profilo OCR: {}", e))?;
+    Ok(true)
+}
+
+/// Salva (o sovrascrive) il profilo OCR di un gioco
+pub fn save_profile(game_id: &str, config: OcrConfig) -> Result<OcrProfile, String> {
+    save_profile_in(&profiles_dir()?, game_id, config)
+}
+
+/// Profilo OCR di un gioco (None se non è mai stato salvato)
+pub fn load_profile(game_id: &str) -> Result<Option<OcrProfile>, String> {
+    load_profile_in(&profiles_dir()?, game_id)
+}
+
+/// Tutti i profili salvati, ordinati per gioco; i file non validi vengono saltati
+pub fn list_profiles() -> Result<Vec<OcrProfile>, String> {
+    let entries = fs::read_dir(profiles_dir()?)
+        .map_err(|e| format!("Errore lettura directory profili OCR: {}", e))?;
+
+    let mut profiles: Vec<OcrProfile> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            serde_json::from_str(&content)
+                .inspect_err(|e| log::warn!("⚠️ Profilo OCR non valido {:?}: {}", path, e))
+                .ok()
+        })
+        .collect();
+    profiles.sort_by(|a, b| a.game_id.cmp(&b.game_id));
+    Ok(profiles)
+}
+
+/// Elimina il profilo di un gioco. Ritorna false se non esisteva.
+pub fn delete_profile(game_id: &str) -> Result<bool, String> {
+    delete_profile_in(&profiles_dir()?, game_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_name_is_safe_for_any_game_id() {
+        assert_eq!(profile_file_name("steam:1091500"), "steam%3A1091500.json");
+        assert_eq!(profile_file_name("../../etc/passwd"), "%2E%2E%2F%2E%2E%2Fetc%2Fpasswd.json");
+        assert_eq!(profile_file_name(" Hell Clock "), "Hell%20Clock.json");
+        assert_eq!(profile_file_name("caffè"), "caff%C3%A8.json");
+    }
+
+    #[test]
+    fn file_name_is_injective() {
+        // Sostituendo i caratteri non sicuri con `_` finirebbero tutti in "steam_1.json"
+        let ids = ["steam:1", "steam/1", "steam_1", "steam 1", "steam%3A1", "steam.1"];
+        let names: std::collections::HashSet<String> = ids.iter().map(|id| profile_file_name(id)).collect();
+        assert_eq!(names.len(), ids.len());
+    }
+
+    #[test]
+    fn profiles_of_colliding_ids_stay_separate() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let config = |title: &str| OcrConfig { target_window_title: Some(title.to_string()), ..OcrConfig::default() };
+        save_profile_in(dir.path(), "steam:1", config("Steam")).unwrap();
+        save_profile_in(dir.path(), "steam/1", config("Epic")).unwrap();
+
+        let first = load_profile_in(dir.path(), "steam:1").unwrap().unwrap();
+        assert_eq!(first.config.target_window_title.as_deref(), Some("Steam"));
+        let second = load_profile_in(dir.path(), "steam/1").unwrap().unwrap();
+        assert_eq!(second.config.target_window_title.as_deref(), Some("Epic"));
+
+        assert!(delete_profile_in(dir.path(), "steam:1").unwrap());
+        assert!(load_profile_in(dir.path(), "steam:1").unwrap().is_none());
+        assert!(load_profile_in(dir.path(), "steam/1").unwrap().is_some());
+    }
+}
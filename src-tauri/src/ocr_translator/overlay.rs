@@ -1,6 +1,12 @@
 // Overlay Module - Gestione finestra overlay trasparente
 
 use serde::{Deserialize, Serialize};
+use super::DetectedText;
+
+/// Dimensione del font rispetto all'altezza del riquadro OCR (che include ascendenti e discendenti)
+const FONT_SIZE_RATIO: f64 = 0.75;
+const MIN_FONT_SIZE: f64 = 10.0;
+const MAX_FONT_SIZE: f64 = 96.0;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OverlayConfig {
@@ -45,7 +51,7 @@ pub fn create_overlay_window() -> Result<(), String> {
 
 /// Aggiorna il contenuto dell'overlay
 #[allow(dead_code)]
-pub fn update_overlay_content(texts: &[super::DetectedText]) -> Result<(), String> {
+pub fn update_overlay_content(texts: &[DetectedText]) -> Result<(), String> {
     if texts.is_empty() {
         return Ok(());
     }
@@ -54,3 +60,100 @@ pub fn update_overlay_content(texts: &[super::DetectedText]) -> Result<(), Strin
     // Il frontend riceverà i testi via get_detected_texts() e li mostrerà
     Ok(())
 }
+
+/// Posizione (area client) e scala della finestra overlay, in pixel fisici del desktop
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlayGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+}
+
+/// Testo rilevato in coordinate CSS dell'overlay, pronto da disegnare
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverlayText {
+    pub text: String,
+    pub translated: Option<String>,
+    pub confidence: f32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    /// Dimensione del font suggerita (px CSS) per occupare il riquadro originale
+    pub font_size: f64,
+    /// false se il riquadro cade fuori dall'overlay (es. overlay non allineato alla finestra)
+    pub visible: bool,
+}
+
+/// Testi da disegnare nell'overlay con la geometria usata per la trasformazione
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverlayLayout {
+    pub scale_factor: f64,
+    /// Dimensioni dell'overlay in px CSS
+    pub width: f64,
+    pub height: f64,
+    pub texts: Vec<OverlayText>,
+}
+
+/// Porta i riquadri OCR (pixel dell'immagine catturata, con origine `capture_origin` sul
+/// desktop) nelle coordinate CSS dell'overlay: sottrae la posizione dell'overlay e divide
+/// per il fattore di scala DPI del suo monitor.
+pub fn layout_texts(texts: &[DetectedText], capture_origin: (i32, i32), overlay: &OverlayGeometry) -> OverlayLayout {
+    let scale = if overlay.scale_factor > 0.0 { overlay.scale_factor } else { 1.0 };
+    let (width, height) = (overlay.width as f64 / scale, overlay.height as f64 / scale);
+
+    let texts = texts.iter()
+        .map(|t| {
+            let x = (capture_origin.0 + t.x - overlay.x) as f64 / scale;
+            let y = (capture_origin.1 + t.y - overlay.y) as f64 / scale;
+            let (w, h) = (t.width as f64 / scale, t.height as f64 / scale);
+            OverlayText {
+                text: t.text.clone(),
+                translated: t.translated.clone(),
+                confidence: t.confidence,
+                x,
+                y,
+                width: w,
+                height: h,
+                font_size: (h * FONT_SIZE_RATIO).round().clamp(MIN_FONT_SIZE, MAX_FONT_SIZE),
+                visible: w > 0.0 && h > 0.0 && x + w > 0.0 && y + h > 0.0 && x < width && y < height,
+            }
+        })
+        .collect();
+
+    OverlayLayout { scale_factor: scale, width, height, texts }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detected(x: i32, y: i32, width: i32, height: i32) -> DetectedText {
+        DetectedText { text: "Start".to_string(), translated: Some("Inizia".to_string()), x, y, width, height, confidence: 0.9 }
+    }
+
+    #[test]
+    fn converts_to_overlay_css_pixels() {
+        // Finestra catturata a (1000, 200), overlay sopra la stessa finestra su un monitor al 150%
+        let overlay = OverlayGeometry { x: 1000, y: 200, width: 1920, height: 1080, scale_factor: 1.5 };
+        let layout = layout_texts(&[detected(300, 150, 240, 48)], (1000, 200), &overlay);
+        let text = &layout.texts[0];
+        assert_eq!((text.x, text.y, text.width, text.height), (200.0, 100.0, 160.0, 32.0));
+        assert_eq!(text.font_size, 24.0);
+        assert!(text.visible);
+        assert_eq!((layout.width, layout.height), (1280.0, 720.0));
+    }
+
+    #[test]
+    fn accounts_for_overlay_offset() {
+        // Overlay spostato rispetto all'area catturata (es. bordo finestra o overlay a schermo intero)
+        let overlay = OverlayGeometry { x: 8, y: 31, width: 800, height: 600, scale_factor: 1.0 };
+        let layout = layout_texts(&[detected(10, 40, 100, 8), detected(-500, 40, 100, 20)], (0, 0), &overlay);
+        assert_eq!((layout.texts[0].x, layout.texts[0].y), (2.0, 9.0));
+        assert_eq!(layout.texts[0].font_size, MIN_FONT_SIZE);
+        assert!(layout.texts[0].visible);
+        assert!(!layout.texts[1].visible);
+    }
+}
@@ -0,0 +1,245 @@
+// Region Picker - supporto alla selezione della regione OCR
+// Screenshot del desktop virtuale (tutti i monitor), validazione della regione rispetto
+// ai bordi dei monitor e anteprima in PNG dei pixel che verrebbero catturati.
+// Le coordinate sono quelle del desktop virtuale di Windows: il monitor principale parte
+// da (0,0), quelli a sinistra o sopra hanno coordinate negative.
+
+use super::screen_capture::ImageData;
+use super::CaptureRegion;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// Bordi di un monitor nel desktop virtuale
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MonitorBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub is_primary: bool,
+}
+
+/// Esito del confronto tra regione e monitor
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionCheck {
+    /// Problemi trovati (vuoto = regione valida)
+    pub issues: Vec<String>,
+    /// Parte della regione visibile sul desktop virtuale
+    pub clamped: Option<CaptureRegion>,
+    /// Indice del monitor che contiene interamente la regione
+    pub monitor_index: Option<usize>,
+}
+
+/// Regione minima sensata per l'OCR (sotto non c'è testo leggibile)
+const MIN_REGION_SIZE: i32 = 8;
+
+fn intersect(a: &CaptureRegion, b: &CaptureRegion) -> Option<CaptureRegion> {
+    let left = a.x.max(b.x);
+    let top = a.y.max(b.y);
+    let right = (a.x + a.width).min(b.x + b.width);
+    let bottom = (a.y + a.height).min(b.y + b.height);
+    (right > left && bottom > top).then(|| CaptureRegion { x: left, y: top, width: right - left, height: bottom - top })
+}
+
+fn contains(outer: &CaptureRegion, inner: &CaptureRegion) -> bool {
+    inner.x >= outer.x
+        && inner.y >= outer.y
+        && inner.x + inner.width <= outer.x + outer.width
+        && inner.y + inner.height <= outer.y + outer.height
+}
+
+fn monitor_rect(monitor: &MonitorBounds) -> CaptureRegion {
+    CaptureRegion { x: monitor.x, y: monitor.y, width: monitor.width, height: monitor.height }
+}
+
+/// Verifica che la regione sia dentro il desktop virtuale e non cada negli spazi vuoti
+/// tra monitor di risoluzione diversa
+pub fn check_region(region: &CaptureRegion, virtual_screen: &CaptureRegion, monitors: &[MonitorBounds]) -> RegionCheck {
+    let mut issues = Vec::new();
+
+    if region.width < MIN_REGION_SIZE || region.height < MIN_REGION_SIZE {
+        issues.push(format!("Regione troppo piccola ({}x{}, minimo {}x{})",
+                            region.width, region.height, MIN_REGION_SIZE, MIN_REGION_SIZE));
+        return RegionCheck { issues, clamped: None, monitor_index: None };
+    }
+
+    let clamped = intersect(region, virtual_screen);
+    match &clamped {
+        None => issues.push("La regione è completamente fuori dallo schermo".to_string()),
+        Some(visible) if visible != region => issues.push(format!(
+            "La regione esce dallo schermo: visibile solo {}x{} a ({}, {})",
+            visible.width, visible.height, visible.x, visible.y
+        )),
+        Some(_) => {}
+    }
+
+    let monitor_index = monitors.iter().position(|m| contains(&monitor_rect(m), region));
+    if monitor_index.is_none() && clamped.as_ref() == Some(region) {
+        // Dentro il desktop virtuale ma a cavallo di più monitor: va bene solo se ogni pixel
+        // appartiene a un monitor (gli angoli vuoti vengono catturati neri)
+        let covered: i64 = monitors.iter()
+            .filter_map(|m| intersect(region, &monitor_rect(m)))
+            .map(|r| r.width as i64 * r.height as i64)
+            .sum();
+        if covered < region.width as i64 * region.height as i64 {
+            issues.push("La regione include aree fuori dai monitor (verrebbero catturate nere)".to_string());
+        }
+    }
+
+    RegionCheck { issues, clamped, monitor_index }
+}
+
+/// Rettangolo che contiene tutti i monitor
+pub fn virtual_bounds(monitors: &[MonitorBounds]) -> Option<CaptureRegion> {
+    let left = monitors.iter().map(|m| m.x).min()?;
+    let top = monitors.iter().map(|m| m.y).min()?;
+    let right = monitors.iter().map(|m| m.x + m.width).max()?;
+    let bottom = monitors.iter().map(|m| m.y + m.height).max()?;
+    Some(CaptureRegion { x: left, y: top, width: right - left, height: bottom - top })
+}
+
+/// Ridimensiona (nearest neighbor) perché il lato maggiore non superi `max_side`
+pub fn thumbnail(image: &ImageData, max_side: u32) -> ImageData {
+    let longest = image.width.max(image.height);
+    if max_side == 0 || longest <= max_side {
+        return image.clone();
+    }
+
+    let width = ((image.width as u64 * max_side as u64) / longest as u64).max(1) as u32;
+    let height = ((image.height as u64 * max_side as u64) / longest as u64).max(1) as u32;
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+
+    for y in 0..height {
+        let src_y = (y as u64 * image.height as u64 / height as u64) as usize;
+        for x in 0..width {
+            let src_x = (x as u64 * image.width as u64 / width as u64) as usize;
+            let offset = (src_y * image.width as usize + src_x) * 4;
+            data.extend_from_slice(&image.data[offset..offset + 4]);
+        }
+    }
+
+    ImageData { width, height, data }
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], payload: &[u8]) {
+    out.extend((payload.len() as u32).to_be_bytes());
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(kind);
+    hasher.update(payload);
+    out.extend(kind);
+    out.extend(payload);
+    out.extend(hasher.finalize().to_be_bytes());
+}
+
+/// Codifica un'immagine BGRA (formato della cattura GDI) in PNG RGB
+pub fn encode_png(image: &ImageData) -> Result<Vec<u8>, String> {
+    let expected = image.width as usize * image.height as usize * 4;
+    if image.width == 0 || image.height == 0 || image.data.len() < expected {
+        return Err("Immagine non valida per la codifica PNG".to_string());
+    }
+
+    // Righe con filtro 0 (nessuno); la cattura GDI ha alpha sempre 0, quindi niente canale alpha
+    let mut raw = Vec::with_capacity(image.height as usize * (1 + image.width as usize * 3));
+    for row in image.data[..expected].chunks_exact(image.width as usize * 4) {
+        raw.push(0);
+        for px in row.chunks_exact(4) {
+            raw.extend_from_slice(&[px[2], px[1], px[0]]);
+        }
+    }
+
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::fast());
+    encoder.write_all(&raw).map_err(|e| e.to_string())?;
+    let compressed = encoder.finish().map_err(|e| e.to_string())?;
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(image.width.to_be_bytes());
+    header.extend(image.height.to_be_bytes());
+    header.extend([8, 2, 0, 0, 0]); // 8 bit, RGB, deflate, filtro adattivo, no interlace
+
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    png_chunk(&mut png, b"IHDR", &header);
+    png_chunk(&mut png, b"IDAT", &compressed);
+    png_chunk(&mut png, b"IEND", &[]);
+    Ok(png)
+}
+
+/// PNG in base64 (senza prefisso `data:`)
+pub fn encode_png_base64(image: &ImageData) -> Result<String, String> {
+    use base64::{engine::general_purpose, Engine as _};
+    Ok(general_purpose::STANDARD.encode(encode_png(image)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(x: i32, y: i32, width: i32, height: i32) -> CaptureRegion {
+        CaptureRegion { x, y, width, height }
+    }
+
+    fn dual_monitors() -> Vec<MonitorBounds> {
+        // Principale 1920x1080, secondario 1280x1024 a sinistra
+        vec![
+            MonitorBounds { x: 0, y: 0, width: 1920, height: 1080, is_primary: true },
+            MonitorBounds { x: -1280, y: 0, width: 1280, height: 1024, is_primary: false },
+        ]
+    }
+
+    #[test]
+    fn accepts_region_on_secondary_monitor() {
+        let monitors = dual_monitors();
+        let virtual_screen = virtual_bounds(&monitors).unwrap();
+        assert_eq!(virtual_screen, region(-1280, 0, 3200, 1080));
+
+        let check = check_region(&region(-1000, 100, 400, 200), &virtual_screen, &monitors);
+        assert!(check.issues.is_empty());
+        assert_eq!(check.monitor_index, Some(1));
+    }
+
+    #[test]
+    fn reports_and_clamps_out_of_bounds() {
+        let monitors = dual_monitors();
+        let virtual_screen = virtual_bounds(&monitors).unwrap();
+
+        let check = check_region(&region(1800, 1000, 400, 200), &virtual_screen, &monitors);
+        assert_eq!(check.issues.len(), 1);
+        assert_eq!(check.clamped, Some(region(1800, 1000, 120, 80)));
+
+        let outside = check_region(&region(5000, 5000, 100, 100), &virtual_screen, &monitors);
+        assert_eq!(outside.clamped, None);
+        assert!(!outside.issues.is_empty());
+    }
+
+    #[test]
+    fn detects_gap_between_monitors() {
+        let monitors = dual_monitors();
+        let virtual_screen = virtual_bounds(&monitors).unwrap();
+
+        // Attraversa i due monitor sotto i 1024px del secondario: angolo vuoto
+        let check = check_region(&region(-100, 1000, 200, 60), &virtual_screen, &monitors);
+        assert_eq!(check.monitor_index, None);
+        assert_eq!(check.issues.len(), 1);
+
+        // Attraversa i due monitor in una zona coperta da entrambi
+        let spanning = check_region(&region(-100, 100, 200, 60), &virtual_screen, &monitors);
+        assert!(spanning.issues.is_empty());
+    }
+
+    #[test]
+    fn encodes_valid_png() {
+        let image = ImageData { width: 2, height: 1, data: vec![0, 0, 255, 0, 255, 0, 0, 0] };
+        let png = encode_png(&image).unwrap();
+        assert_eq!(&png[..8], &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 2);
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn thumbnail_keeps_aspect_ratio() {
+        let image = ImageData { width: 400, height: 100, data: vec![128; 400 * 100 * 4] };
+        let thumb = thumbnail(&image, 200);
+        assert_eq!((thumb.width, thumb.height), (200, 50));
+        assert_eq!(thumb.data.len(), 200 * 50 * 4);
+    }
+}
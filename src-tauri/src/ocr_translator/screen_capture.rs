@@ -1,7 +1,8 @@
 // Screen Capture Module - Windows DXGI/GDI
 
-use super::CaptureRegion;
+use super::{CaptureMethod, CaptureRegion};
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 
 #[cfg(target_os = "windows")]
 use std::ptr::null_mut;
@@ -13,6 +14,176 @@ pub struct ImageData {
     pub data: Vec<u8>, // BGRA format
 }
 
+/// Backend usato effettivamente per una cattura
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureBackend {
+    Gdi,
+    Dxgi,
+}
+
+/// Dopo un errore DXGI, attesa prima di ritentare (nel frattempo si usa GDI)
+const DXGI_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Cattura ripetuta del loop OCR con il metodo configurato: mantiene la duplicazione DXGI
+/// tra un frame e l'altro e in modalità Auto ripiega su GDI quando DXGI non è disponibile
+pub struct CaptureSession {
+    method: CaptureMethod,
+    #[cfg(target_os = "windows")]
+    dxgi: Option<super::dxgi_capture::DxgiDuplicator>,
+    dxgi_retry_at: Option<Instant>,
+    /// Motivo dell'ultimo ripiego su GDI (None se DXGI funziona o non è richiesto)
+    pub fallback_reason: Option<String>,
+}
+
+impl CaptureSession {
+    pub fn new(method: CaptureMethod) -> Self {
+        Self {
+            method,
+            #[cfg(target_os = "windows")]
+            dxgi: None,
+            dxgi_retry_at: None,
+            fallback_reason: None,
+        }
+    }
+    
+    /// Cattura la finestra (se indicata) o la regione/schermo principale
+    pub fn capture(&mut self, target_window: Option<isize>, region: &Option<CaptureRegion>) -> Result<(ImageData, CaptureBackend), String> {
+        if self.method != CaptureMethod::Gdi {
+            match self.capture_dxgi(target_window, region) {
+                Ok(image) => {
+                    self.fallback_reason = None;
+                    return Ok((image, CaptureBackend::Dxgi));
+                }
+                Err(e) if self.method == CaptureMethod::Dxgi => return Err(e),
+                Err(e) => {
+                    if self.fallback_reason.as_deref() != Some(e.as_str()) {
+                        log::warn!("⚠️ Cattura DXGI non disponibile, uso GDI: {}", e);
+                    }
+                    self.fallback_reason = Some(e);
+                }
+            }
+        }
+        
+        let image = match target_window {
+            Some(hwnd) => capture_window(hwnd)?,
+            None => capture_screen(region)?,
+        };
+        Ok((image, CaptureBackend::Gdi))
+    }
+    
+    #[cfg(target_os = "windows")]
+    fn capture_dxgi(&mut self, target_window: Option<isize>, region: &Option<CaptureRegion>) -> Result<ImageData, String> {
+        use super::dxgi_capture::DxgiDuplicator;
+        
+        let area = match (target_window, region) {
+            (Some(hwnd), _) => window_rect(hwnd).ok_or("Impossibile ottenere posizione finestra")?,
+            (None, Some(region)) => region.clone(),
+            (None, None) => primary_monitor_region().ok_or("Monitor principale non trovato")?,
+        };
+        
+        // Finestra/regione spostata su un altro monitor: duplica quello nuovo
+        if self.dxgi.as_ref().is_some_and(|d| !d.contains(&area)) {
+            self.dxgi = None;
+        }
+        if self.dxgi.is_none() {
+            if self.dxgi_retry_at.is_some_and(|at| Instant::now() < at) {
+                return Err(self.fallback_reason.clone().unwrap_or_else(|| "DXGI in attesa di nuovo tentativo".to_string()));
+            }
+            let duplicator = DxgiDuplicator::for_point(area.x, area.y)
+                .and_then(|d| if d.contains(&area) {
+                    Ok(d)
+                } else {
+                    Err("La regione si estende su più monitor".to_string())
+                })
+                .inspect_err(|_| self.dxgi_retry_at = Some(Instant::now() + DXGI_RETRY_INTERVAL))?;
+            self.dxgi_retry_at = None;
+            self.dxgi = Some(duplicator);
+        }
+        
+        let result = match self.dxgi.as_mut() {
+            Some(duplicator) => duplicator.capture(&area),
+            None => Err("Duplicazione DXGI non disponibile".to_string()),
+        };
+        if result.is_err() {
+            // Accesso perso (cambio modalità, UAC, fullscreen): si ricrea al prossimo tentativo
+            self.dxgi = None;
+            self.dxgi_retry_at = Some(Instant::now() + DXGI_RETRY_INTERVAL);
+        }
+        result
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    fn capture_dxgi(&mut self, _target_window: Option<isize>, _region: &Option<CaptureRegion>) -> Result<ImageData, String> {
+        Err("DXGI supportato solo su Windows".to_string())
+    }
+}
+
+/// Regione del monitor principale nel desktop virtuale
+#[cfg(target_os = "windows")]
+fn primary_monitor_region() -> Option<CaptureRegion> {
+    list_monitors()
+        .into_iter()
+        .find(|m| m.is_primary)
+        .map(|m| CaptureRegion { x: m.x, y: m.y, width: m.width, height: m.height })
+}
+
+/// Rettangolo visibile di una finestra in coordinate schermo. `GetWindowRect` include i bordi
+/// di ridimensionamento invisibili di Windows 10+ (una finestra massimizzata sborda di qualche
+/// pixel sui monitor vicini): si usano i bordi del frame DWM, con `GetWindowRect` come ripiego
+#[cfg(target_os = "windows")]
+pub fn window_rect(hwnd: isize) -> Option<CaptureRegion> {
+    #[repr(C)]
+    struct Rect {
+        left: i32,
+        top: i32,
+        right: i32,
+        bottom: i32,
+    }
+    
+    #[link(name = "user32")]
+    extern "system" {
+        fn GetWindowRect(hwnd: *mut std::ffi::c_void, rect: *mut Rect) -> i32;
+    }
+    
+    #[link(name = "dwmapi")]
+    extern "system" {
+        fn DwmGetWindowAttribute(hwnd: *mut std::ffi::c_void, attribute: u32, value: *mut std::ffi::c_void, size: u32) -> i32;
+    }
+    
+    const DWMWA_EXTENDED_FRAME_BOUNDS: u32 = 9;
+    
+    let mut rect = Rect { left: 0, top: 0, right: 0, bottom: 0 };
+    unsafe {
+        let hwnd = hwnd as *mut std::ffi::c_void;
+        let dwm_ok = DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_EXTENDED_FRAME_BOUNDS,
+            &mut rect as *mut Rect as *mut std::ffi::c_void,
+            std::mem::size_of::<Rect>() as u32,
+        ) == 0;
+        // DWM non disponibile (composizione disattivata) o finestra non gestita da DWM
+        if !dwm_ok && GetWindowRect(hwnd, &mut rect) == 0 {
+            return None;
+        }
+    }
+    let (width, height) = (rect.right - rect.left, rect.bottom - rect.top);
+    (width > 0 && height > 0).then_some(CaptureRegion { x: rect.left, y: rect.top, width, height })
+}
+
+/// Angolo in alto a sinistra dell'area catturata da `CaptureSession::capture`, in coordinate
+/// del desktop virtuale: i riquadri OCR sono relativi a questo punto
+pub fn capture_origin(target_window: Option<isize>, region: &Option<CaptureRegion>) -> (i32, i32) {
+    #[cfg(target_os = "windows")]
+    if let Some(rect) = target_window.and_then(window_rect) {
+        return (rect.x, rect.y);
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = target_window;
+    // Senza regione si cattura il monitor principale, che ha sempre origine (0, 0)
+    region.as_ref().map_or((0, 0), |r| (r.x, r.y))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowInfo {
     pub hwnd: isize,
@@ -94,7 +265,6 @@ pub fn capture_window(hwnd: isize) -> Result<ImageData, String> {
     extern "system" {
         fn GetDC(hwnd: *mut c_void) -> *mut c_void;
         fn ReleaseDC(hwnd: *mut c_void, hdc: *mut c_void) -> i32;
-        fn GetWindowRect(hwnd: *mut c_void, rect: *mut Rect) -> i32;
     }
     
     #[link(name = "gdi32")]
@@ -110,14 +280,6 @@ pub fn capture_window(hwnd: isize) -> Result<ImageData, String> {
         fn DeleteDC(hdc: *mut c_void) -> i32;
     }
     
-    #[repr(C)]
-    struct Rect {
-        left: i32,
-        top: i32,
-        right: i32,
-        bottom: i32,
-    }
-    
     #[repr(C)]
     struct BitmapInfoHeader {
         size: u32,
@@ -144,22 +306,8 @@ pub fn capture_window(hwnd: isize) -> Result<ImageData, String> {
     const DIB_RGB_COLORS: u32 = 0;
     
     unsafe {
-        let hwnd_ptr = hwnd as *mut c_void;
-        
-        // Ottieni posizione finestra sullo schermo
-        let mut rect: Rect = zeroed();
-        if GetWindowRect(hwnd_ptr, &mut rect) == 0 {
-            return Err("Failed to get window rect".to_string());
-        }
-        
-        let x = rect.left;
-        let y = rect.top;
-        let width = rect.right - rect.left;
-        let height = rect.bottom - rect.top;
-        
-        if width <= 0 || height <= 0 {
-            return Err("Invalid window dimensions".to_string());
-        }
+        // Stessa area di DXGI e di `capture_origin`: il rettangolo visibile della finestra
+        let CaptureRegion { x, y, width, height } = window_rect(hwnd).ok_or("Failed to get window rect")?;
         
         // Cattura dalla posizione dello schermo (funziona con DirectX)
         let screen_dc = GetDC(null_mut());
@@ -228,11 +376,191 @@ pub fn list_windows() -> Vec<WindowInfo> {
     Vec::new()
 }
 
+/// Cerca una finestra visibile per titolo (case-insensitive): preferisce il titolo identico,
+/// altrimenti il titolo più corto che contiene la stringa (es. "Hell Clock" e non "Hell Clock - Guida")
+pub fn find_window_by_title(substring: &str) -> Option<WindowInfo> {
+    let needle = substring.trim().to_lowercase();
+    if needle.is_empty() {
+        return None;
+    }
+    
+    let mut matches: Vec<WindowInfo> = list_windows()
+        .into_iter()
+        .filter(|w| w.title.to_lowercase().contains(&needle))
+        .collect();
+    
+    if let Some(pos) = matches.iter().position(|w| w.title.trim().to_lowercase() == needle) {
+        return Some(matches.swap_remove(pos));
+    }
+    matches.into_iter().min_by_key(|w| w.title.len())
+}
+
+/// Visibilità di una finestra bersaglio della cattura
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowVisibility {
+    Visible,
+    Minimized,
+    /// Nascosta (es. ridotta nella tray)
+    Hidden,
+    /// Coperta completamente da altre finestre
+    Occluded,
+}
+
+/// Stato di visibilità della finestra: minimizzata, nascosta o coperta in tutti i punti campione.
+/// L'overlay click-through di GameStringer non conta come copertura.
+#[cfg(target_os = "windows")]
+pub fn window_visibility(hwnd: isize) -> WindowVisibility {
+    use std::ffi::c_void;
+    
+    #[repr(C)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+    
+    #[link(name = "user32")]
+    extern "system" {
+        fn IsIconic(hwnd: *mut c_void) -> i32;
+        fn IsWindowVisible(hwnd: *mut c_void) -> i32;
+        fn WindowFromPoint(point: Point) -> *mut c_void;
+        fn GetAncestor(hwnd: *mut c_void, flags: u32) -> *mut c_void;
+        fn GetWindowThreadProcessId(hwnd: *mut c_void, pid: *mut u32) -> u32;
+        fn GetWindowLongW(hwnd: *mut c_void, index: i32) -> i32;
+    }
+    
+    const GA_ROOT: u32 = 2;
+    const GWL_EXSTYLE: i32 = -20;
+    const WS_EX_TRANSPARENT: i32 = 0x00000020;
+    /// Griglia di punti campione (SAMPLE_GRID x SAMPLE_GRID) sulla finestra
+    const SAMPLE_GRID: i32 = 3;
+    
+    unsafe {
+        let target = hwnd as *mut c_void;
+        if IsIconic(target) != 0 {
+            return WindowVisibility::Minimized;
+        }
+        if IsWindowVisible(target) == 0 {
+            return WindowVisibility::Hidden;
+        }
+        let Some(rect) = window_rect(hwnd) else {
+            return WindowVisibility::Hidden;
+        };
+        
+        let own_pid = std::process::id();
+        for gy in 0..SAMPLE_GRID {
+            for gx in 0..SAMPLE_GRID {
+                let point = Point {
+                    x: rect.x + rect.width * (2 * gx + 1) / (2 * SAMPLE_GRID),
+                    y: rect.y + rect.height * (2 * gy + 1) / (2 * SAMPLE_GRID),
+                };
+                let hit = WindowFromPoint(point);
+                if hit.is_null() {
+                    continue;
+                }
+                let root = GetAncestor(hit, GA_ROOT);
+                if root == target || hit == target {
+                    return WindowVisibility::Visible;
+                }
+                let mut pid = 0u32;
+                GetWindowThreadProcessId(root, &mut pid);
+                if pid == own_pid && GetWindowLongW(root, GWL_EXSTYLE) & WS_EX_TRANSPARENT != 0 {
+                    return WindowVisibility::Visible;
+                }
+            }
+        }
+        WindowVisibility::Occluded
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn window_visibility(_hwnd: isize) -> WindowVisibility {
+    WindowVisibility::Visible
+}
+
+/// Vero se l'handle appartiene ancora a una finestra esistente
+#[cfg(target_os = "windows")]
+pub fn is_window_alive(hwnd: isize) -> bool {
+    #[link(name = "user32")]
+    extern "system" {
+        fn IsWindow(hwnd: *mut std::ffi::c_void) -> i32;
+    }
+    
+    unsafe { IsWindow(hwnd as *mut std::ffi::c_void) != 0 }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn is_window_alive(_hwnd: isize) -> bool {
+    false
+}
+
 #[cfg(not(target_os = "windows"))]
 pub fn capture_window(_hwnd: isize) -> Result<ImageData, String> {
     Err("Window capture supportato solo su Windows".to_string())
 }
 
+/// Monitor collegati con le coordinate nel desktop virtuale
+#[cfg(target_os = "windows")]
+pub fn list_monitors() -> Vec<super::region_picker::MonitorBounds> {
+    use std::ffi::c_void;
+    use super::region_picker::MonitorBounds;
+    
+    #[repr(C)]
+    struct Rect {
+        left: i32,
+        top: i32,
+        right: i32,
+        bottom: i32,
+    }
+    
+    #[repr(C)]
+    struct MonitorInfo {
+        size: u32,
+        monitor: Rect,
+        work: Rect,
+        flags: u32,
+    }
+    
+    #[link(name = "user32")]
+    extern "system" {
+        fn EnumDisplayMonitors(hdc: *mut c_void, clip: *const Rect,
+                               callback: extern "system" fn(*mut c_void, *mut c_void, *mut Rect, isize) -> i32,
+                               data: isize) -> i32;
+        fn GetMonitorInfoW(monitor: *mut c_void, info: *mut MonitorInfo) -> i32;
+    }
+    
+    const MONITORINFOF_PRIMARY: u32 = 1;
+    
+    extern "system" fn enum_callback(monitor: *mut c_void, _: *mut c_void, _: *mut Rect, data: isize) -> i32 {
+        unsafe {
+            let monitors = &mut *(data as *mut Vec<MonitorBounds>);
+            let mut info: MonitorInfo = std::mem::zeroed();
+            info.size = std::mem::size_of::<MonitorInfo>() as u32;
+            if GetMonitorInfoW(monitor, &mut info) != 0 {
+                monitors.push(MonitorBounds {
+                    x: info.monitor.left,
+                    y: info.monitor.top,
+                    width: info.monitor.right - info.monitor.left,
+                    height: info.monitor.bottom - info.monitor.top,
+                    is_primary: info.flags & MONITORINFOF_PRIMARY != 0,
+                });
+            }
+            1
+        }
+    }
+    
+    let mut monitors: Vec<MonitorBounds> = Vec::new();
+    unsafe {
+        EnumDisplayMonitors(null_mut(), std::ptr::null(), enum_callback, &mut monitors as *mut _ as isize);
+    }
+    monitors
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn list_monitors() -> Vec<super::region_picker::MonitorBounds> {
+    Vec::new()
+}
+
 /// Cattura lo schermo (o una regione specifica)
 #[cfg(target_os = "windows")]
 pub fn capture_screen(region: &Option<CaptureRegion>) -> Result<ImageData, String> {
@@ -0,0 +1,196 @@
+// Text Stabilizer - fusione delle letture OCR tra frame
+// La stessa scritta letta a 0.9 in un frame e a 0.4 nel successivo sfarfalla. Ogni regione
+// di testo diventa una "traccia" (abbinata per vicinanza, le coordinate oscillano di qualche
+// pixel) che conserva le letture degli ultimi N frame; viene emessa la lettura con la
+// confidenza più alta, pesata con un decadimento per età così le letture vecchie scadono.
+// Se nella stessa posizione compare un testo diverso (nuova battuta) la traccia riparte.
+
+use super::DetectedText;
+use crate::commands::translation_memory::calculate_similarity;
+use std::collections::VecDeque;
+
+/// Peso applicato alla confidenza per ogni frame di età della lettura
+const CONFIDENCE_DECAY: f32 = 0.85;
+/// Tolleranza minima sullo spostamento del centro (pixel)
+const MIN_POSITION_TOLERANCE: i32 = 8;
+/// Similarità minima con l'ultima lettura perché sia la stessa scritta letta male e non
+/// una nuova battuta nella stessa posizione
+const MIN_TEXT_SIMILARITY: f64 = 0.5;
+
+struct Track {
+    /// Letture (numero frame, testo) dalla più vecchia alla più recente
+    readings: VecDeque<(u64, DetectedText)>,
+}
+
+impl Track {
+    fn latest(&self) -> &DetectedText {
+        &self.readings.back().expect("traccia senza letture").1
+    }
+
+    /// Stessa regione se i centri sono vicini rispetto alle dimensioni del testo
+    fn matches(&self, text: &DetectedText) -> bool {
+        let last = self.latest();
+        let y_tolerance = (last.height.min(text.height) / 2).max(MIN_POSITION_TOLERANCE);
+        let x_tolerance = (last.width.min(text.width) / 4).max(y_tolerance);
+        // Distanze tra i centri, raddoppiate per restare in aritmetica intera
+        let dx = (last.x * 2 + last.width) - (text.x * 2 + text.width);
+        let dy = (last.y * 2 + last.height) - (text.y * 2 + text.height);
+        dx.abs() <= x_tolerance * 2 && dy.abs() <= y_tolerance * 2
+    }
+
+    /// Stessa scritta a meno di errori OCR (maiuscole e spazi ai bordi ignorati)
+    fn same_text(&self, text: &DetectedText) -> bool {
+        let last = self.latest().text.trim().to_lowercase();
+        calculate_similarity(&last, &text.text.trim().to_lowercase()) >= MIN_TEXT_SIMILARITY
+    }
+}
+
+/// Storico delle letture per regione su una finestra scorrevole di frame
+pub struct TextStabilizer {
+    window: u64,
+    frame: u64,
+    tracks: Vec<Track>,
+}
+
+impl TextStabilizer {
+    /// `window_frames` <= 1 disattiva la fusione (passa il frame così com'è)
+    pub fn new(window_frames: usize) -> Self {
+        Self { window: window_frames as u64, frame: 0, tracks: Vec::new() }
+    }
+
+    pub fn reset(&mut self) {
+        self.frame = 0;
+        self.tracks.clear();
+    }
+
+    /// Aggiunge le letture del frame e restituisce quelle stabilizzate
+    pub fn update(&mut self, texts: Vec<DetectedText>) -> Vec<DetectedText> {
+        if self.window <= 1 {
+            return texts;
+        }
+        self.frame += 1;
+
+        let mut matched = vec![false; self.tracks.len()];
+        for text in texts {
+            let existing = self.tracks.iter()
+                .enumerate()
+                .position(|(i, track)| !matched[i] && track.matches(&text));
+            match existing {
+                Some(i) => {
+                    matched[i] = true;
+                    let track = &mut self.tracks[i];
+                    if !track.same_text(&text) {
+                        // Testo cambiato: le letture precedenti non riguardano più questa scritta
+                        track.readings.clear();
+                    }
+                    track.readings.push_back((self.frame, text));
+                }
+                None => {
+                    self.tracks.push(Track { readings: VecDeque::from([(self.frame, text)]) });
+                    matched.push(true);
+                }
+            }
+        }
+
+        // Scarta le letture fuori finestra e le tracce rimaste vuote
+        let oldest = self.frame.saturating_sub(self.window - 1);
+        for track in &mut self.tracks {
+            while track.readings.front().is_some_and(|(frame, _)| *frame < oldest) {
+                track.readings.pop_front();
+            }
+        }
+        self.tracks.retain(|t| !t.readings.is_empty());
+
+        let mut result: Vec<DetectedText> = self.tracks.iter()
+            .map(|track| self.best_reading(track))
+            .collect();
+        result.sort_by_key(|t| (t.y, t.x));
+        result
+    }
+
+    /// Lettura con la confidenza pesata più alta, nella posizione più recente
+    fn best_reading(&self, track: &Track) -> DetectedText {
+        let (_, best) = track.readings.iter()
+            .max_by(|(fa, a), (fb, b)| {
+                let wa = a.confidence * CONFIDENCE_DECAY.powi((self.frame - fa) as i32);
+                let wb = b.confidence * CONFIDENCE_DECAY.powi((self.frame - fb) as i32);
+                wa.total_cmp(&wb)
+            })
+            .expect("traccia senza letture");
+        let latest = track.latest();
+        DetectedText {
+            x: latest.x,
+            y: latest.y,
+            width: latest.width,
+            height: latest.height,
+            ..best.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reading(text: &str, x: i32, y: i32, confidence: f32) -> DetectedText {
+        DetectedText { text: text.to_string(), translated: None, x, y, width: 200, height: 24, confidence }
+    }
+
+    #[test]
+    fn keeps_best_reading_despite_jitter() {
+        let mut stabilizer = TextStabilizer::new(5);
+        stabilizer.update(vec![reading("Open the door", 100, 400, 0.9)]);
+        let out = stabilizer.update(vec![reading("0pen the d00r", 103, 398, 0.4)]);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].text, "Open the door");
+        // Posizione aggiornata all'ultima lettura
+        assert_eq!((out[0].x, out[0].y), (103, 398));
+    }
+
+    #[test]
+    fn stale_readings_expire() {
+        let mut stabilizer = TextStabilizer::new(3);
+        stabilizer.update(vec![reading("Old line", 100, 400, 0.95)]);
+        stabilizer.update(vec![]);
+        stabilizer.update(vec![]);
+        assert!(stabilizer.update(vec![]).is_empty());
+
+        // Una lettura recente di poco meno confidente batte una vecchia
+        stabilizer.update(vec![reading("Hello", 100, 100, 0.8)]);
+        stabilizer.update(vec![reading("Hallo", 100, 100, 0.5)]);
+        stabilizer.update(vec![reading("Hello!", 100, 100, 0.75)]);
+        let out = stabilizer.update(vec![reading("Hello!", 100, 100, 0.75)]);
+        assert_eq!(out[0].text, "Hello!");
+    }
+
+    #[test]
+    fn new_text_at_same_position_replaces_old_reading() {
+        let mut stabilizer = TextStabilizer::new(5);
+        stabilizer.update(vec![reading("Open the door", 100, 400, 0.95)]);
+        // Nuova battuta nello stesso riquadro, letta con confidenza più bassa
+        let out = stabilizer.update(vec![reading("Where are you going?", 101, 400, 0.5)]);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].text, "Where are you going?");
+
+        // La battuta precedente non torna alle letture successive
+        let out = stabilizer.update(vec![reading("Where are y0u going?", 100, 401, 0.4)]);
+        assert_eq!(out[0].text, "Where are you going?");
+    }
+
+    #[test]
+    fn separate_regions_stay_separate() {
+        let mut stabilizer = TextStabilizer::new(4);
+        let out = stabilizer.update(vec![reading("Top", 100, 100, 0.9), reading("Bottom", 100, 400, 0.9)]);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].text, "Top");
+        assert_eq!(out[1].text, "Bottom");
+    }
+
+    #[test]
+    fn disabled_passes_through() {
+        let mut stabilizer = TextStabilizer::new(1);
+        stabilizer.update(vec![reading("A", 0, 0, 0.9)]);
+        let out = stabilizer.update(vec![reading("B", 0, 0, 0.1)]);
+        assert_eq!(out[0].text, "B");
+    }
+}
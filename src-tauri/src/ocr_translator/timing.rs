@@ -0,0 +1,142 @@
+// Pipeline Timing - durate delle fasi del ciclo OCR
+// Ogni fase (cattura, OCR, traduzione, aggiornamento overlay) conserva le ultime
+// `WINDOW_SIZE` durate in un buffer circolare: registrare costa un push, le statistiche
+// (min/media/max/p95) si calcolano solo quando il frontend le chiede.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Campioni conservati per fase
+pub const WINDOW_SIZE: usize = 200;
+
+/// Fase del ciclo OCR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Capture,
+    Ocr,
+    Translate,
+    Overlay,
+}
+
+/// Statistiche di una fase in millisecondi (0 se non ci sono campioni)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhaseStats {
+    pub samples: usize,
+    pub min_ms: f64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+    pub p95_ms: f64,
+    pub last_ms: f64,
+}
+
+/// Statistiche di tutte le fasi sulle ultime `window_size` iterazioni
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OcrTimingStats {
+    pub window_size: usize,
+    pub capture: PhaseStats,
+    pub ocr: PhaseStats,
+    pub translate: PhaseStats,
+    pub overlay: PhaseStats,
+}
+
+#[derive(Default)]
+struct PhaseWindow {
+    /// Durate in microsecondi, dalla più vecchia
+    samples: VecDeque<u32>,
+}
+
+impl PhaseWindow {
+    fn record(&mut self, duration: Duration) {
+        if self.samples.len() == WINDOW_SIZE {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(duration.as_micros().min(u32::MAX as u128) as u32);
+    }
+
+    fn stats(&self) -> PhaseStats {
+        let Some(&last) = self.samples.back() else {
+            return PhaseStats::default();
+        };
+        let mut sorted: Vec<u32> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        let to_ms = |micros: u32| micros as f64 / 1000.0;
+        let total: u64 = sorted.iter().map(|&s| s as u64).sum();
+        // Nearest-rank: il più piccolo campione che copre il 95% dei valori
+        let p95_index = (sorted.len() * 95).div_ceil(100).saturating_sub(1);
+        PhaseStats {
+            samples: sorted.len(),
+            min_ms: to_ms(sorted[0]),
+            avg_ms: total as f64 / sorted.len() as f64 / 1000.0,
+            max_ms: to_ms(sorted[sorted.len() - 1]),
+            p95_ms: to_ms(sorted[p95_index]),
+            last_ms: to_ms(last),
+        }
+    }
+}
+
+/// Durate recenti delle fasi del ciclo OCR
+#[derive(Default)]
+pub struct PipelineTimings {
+    capture: PhaseWindow,
+    ocr: PhaseWindow,
+    translate: PhaseWindow,
+    overlay: PhaseWindow,
+}
+
+impl PipelineTimings {
+    pub fn record(&mut self, phase: Phase, duration: Duration) {
+        let window = match phase {
+            Phase::Capture => &mut self.capture,
+            Phase::Ocr => &mut self.ocr,
+            Phase::Translate => &mut self.translate,
+            Phase::Overlay => &mut self.overlay,
+        };
+        window.record(duration);
+    }
+
+    pub fn stats(&self) -> OcrTimingStats {
+        OcrTimingStats {
+            window_size: WINDOW_SIZE,
+            capture: self.capture.stats(),
+            ocr: self.ocr.stats(),
+            translate: self.translate.stats(),
+            overlay: self.overlay.stats(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_cover_min_avg_max_p95() {
+        let mut timings = PipelineTimings::default();
+        for ms in 1..=100u64 {
+            timings.record(Phase::Ocr, Duration::from_millis(ms));
+        }
+        let stats = timings.stats().ocr;
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.min_ms, 1.0);
+        assert_eq!(stats.max_ms, 100.0);
+        assert_eq!(stats.avg_ms, 50.5);
+        assert_eq!(stats.p95_ms, 95.0);
+        assert_eq!(stats.last_ms, 100.0);
+        assert_eq!(timings.stats().capture, PhaseStats::default());
+    }
+
+    #[test]
+    fn window_keeps_only_recent_samples() {
+        let mut timings = PipelineTimings::default();
+        for _ in 0..WINDOW_SIZE {
+            timings.record(Phase::Capture, Duration::from_millis(500));
+        }
+        for _ in 0..WINDOW_SIZE {
+            timings.record(Phase::Capture, Duration::from_millis(2));
+        }
+        let stats = timings.stats().capture;
+        assert_eq!(stats.samples, WINDOW_SIZE);
+        assert_eq!(stats.max_ms, 2.0);
+    }
+}
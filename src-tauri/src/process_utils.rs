@@ -6,8 +6,12 @@ use winapi::um::handleapi::CloseHandle;
 use winapi::um::processthreadsapi::OpenProcess;
 
 use winapi::um::tlhelp32::{CreateToolhelp32Snapshot, Process32First, Process32Next, PROCESSENTRY32, TH32CS_SNAPPROCESS};
-use winapi::um::winnt::PROCESS_QUERY_INFORMATION;
-use winapi::um::winuser::{EnumWindows, GetWindowTextW, GetWindowThreadProcessId, IsWindowVisible};
+use winapi::um::winbase::QueryFullProcessImageNameW;
+use winapi::um::winnt::{PROCESS_QUERY_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION};
+use winapi::um::winuser::{
+    EnumWindows, GetClassNameW, GetWindow, GetWindowLongW, GetWindowRect, GetWindowTextW,
+    GetWindowThreadProcessId, IsIconic, IsWindow, IsWindowVisible, GWL_EXSTYLE, GW_OWNER, WS_EX_TOOLWINDOW,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessInfo {
@@ -33,6 +37,28 @@ const GAME_PROCESSES: &[&str] = &[
     "rpcs3.exe", "yuzu.exe", "ryujinx.exe", "cemu.exe", "pcsx2.exe",
 ];
 
+/// Finestra di primo livello che l'utente può scegliere come bersaglio dell'injection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowTarget {
+    /// Handle della finestra (HWND) come intero, da ripassare al backend
+    pub hwnd: isize,
+    pub pid: u32,
+    pub exe: String,
+    pub exe_path: Option<String>,
+    pub title: String,
+    pub class_name: String,
+    pub width: i32,
+    pub height: i32,
+    pub minimized: bool,
+}
+
+/// Classi di finestre di shell, console e terminali: mai bersagli di un gioco
+const IGNORED_WINDOW_CLASSES: &[&str] = &[
+    "Progman", "WorkerW", "Shell_TrayWnd", "Shell_SecondaryTrayWnd", "ConsoleWindowClass",
+    "CASCADIA_HOSTING_WINDOW_CLASS", "Windows.UI.Core.CoreWindow", "ApplicationFrameWindow",
+    "NotifyIconOverflowWindow", "tooltips_class32",
+];
+
 // Struttura per passare dati al callback di EnumWindows
 struct WindowEnumData {
     pid: DWORD,
@@ -73,6 +99,115 @@ unsafe fn get_window_title_for_process(pid: DWORD) -> Option<String> {
     data.window_title
 }
 
+unsafe fn window_text(hwnd: winapi::shared::windef::HWND) -> String {
+    let mut buffer: [u16; 512] = [0; 512];
+    let len = GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+    String::from_utf16_lossy(&buffer[..len.max(0) as usize])
+}
+
+unsafe fn window_class(hwnd: winapi::shared::windef::HWND) -> String {
+    let mut buffer: [u16; 256] = [0; 256];
+    let len = GetClassNameW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+    String::from_utf16_lossy(&buffer[..len.max(0) as usize])
+}
+
+/// Percorso completo dell'eseguibile di un processo (richiede solo PROCESS_QUERY_LIMITED_INFORMATION)
+pub fn process_image_path(pid: u32) -> Option<String> {
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid);
+        if handle.is_null() {
+            return None;
+        }
+        let mut buffer: [u16; 1024] = [0; 1024];
+        let mut size = buffer.len() as DWORD;
+        let ok = QueryFullProcessImageNameW(handle, 0, buffer.as_mut_ptr(), &mut size);
+        CloseHandle(handle);
+        (ok != FALSE).then(|| String::from_utf16_lossy(&buffer[..size as usize]))
+    }
+}
+
+// Callback per list_top_level_windows: raccoglie gli HWND candidati
+unsafe extern "system" fn collect_windows_callback(hwnd: winapi::shared::windef::HWND, lparam: winapi::shared::minwindef::LPARAM) -> winapi::shared::minwindef::BOOL {
+    let handles = &mut *(lparam as *mut Vec<winapi::shared::windef::HWND>);
+    handles.push(hwnd);
+    1
+}
+
+/// Finestre di primo livello visibili con titolo, escluse tool window, finestre di proprietà
+/// di altre finestre (dialoghi), console e shell. Ordinate per titolo.
+pub fn list_top_level_windows() -> Vec<WindowTarget> {
+    let own_pid = std::process::id();
+    let mut handles: Vec<winapi::shared::windef::HWND> = Vec::new();
+    let mut targets = Vec::new();
+
+    unsafe {
+        EnumWindows(Some(collect_windows_callback), &mut handles as *mut _ as winapi::shared::minwindef::LPARAM);
+
+        for hwnd in handles {
+            if IsWindowVisible(hwnd) == FALSE || !GetWindow(hwnd, GW_OWNER).is_null() {
+                continue;
+            }
+            if (GetWindowLongW(hwnd, GWL_EXSTYLE) as u32) & WS_EX_TOOLWINDOW != 0 {
+                continue;
+            }
+            let title = window_text(hwnd);
+            let class_name = window_class(hwnd);
+            if title.trim().is_empty() || IGNORED_WINDOW_CLASSES.contains(&class_name.as_str()) {
+                continue;
+            }
+
+            let mut pid: DWORD = 0;
+            GetWindowThreadProcessId(hwnd, &mut pid);
+            if pid == 0 || pid == own_pid {
+                continue;
+            }
+
+            let mut rect: winapi::shared::windef::RECT = mem::zeroed();
+            GetWindowRect(hwnd, &mut rect);
+            let minimized = IsIconic(hwnd) != FALSE;
+            let (width, height) = (rect.right - rect.left, rect.bottom - rect.top);
+            // Finestre invisibili "di servizio" (0x0) ma non i giochi minimizzati
+            if !minimized && (width <= 1 || height <= 1) {
+                continue;
+            }
+
+            let exe_path = process_image_path(pid);
+            let exe = exe_path.as_deref()
+                .and_then(|p| std::path::Path::new(p).file_name())
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            targets.push(WindowTarget {
+                hwnd: hwnd as isize,
+                pid,
+                exe,
+                exe_path,
+                title,
+                class_name,
+                width,
+                height,
+                minimized,
+            });
+        }
+    }
+
+    targets.sort_by_key(|w| w.title.to_lowercase());
+    targets
+}
+
+/// PID del processo proprietario di una finestra (None se l'HWND non esiste più)
+pub fn window_process_id(hwnd: isize) -> Option<u32> {
+    unsafe {
+        let hwnd = hwnd as winapi::shared::windef::HWND;
+        if IsWindow(hwnd) == FALSE {
+            return None;
+        }
+        let mut pid: DWORD = 0;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        (pid != 0).then_some(pid)
+    }
+}
+
 // Trova tutti i processi di gioco in esecuzione
 pub fn find_game_processes() -> Result<Vec<ProcessInfo>, Box<dyn Error>> {
     unsafe {
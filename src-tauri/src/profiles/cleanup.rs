@@ -0,0 +1,134 @@
+//! Riconciliazione dei dati su disco con i profili esistenti
+//!
+//! Individua file rimasti dopo l'eliminazione di profili (settings, file profilo
+//! crittografati con le credenziali salvate, avatar) e su conferma li sposta in quarantena.
+
+use crate::profiles::errors::{ProfileError, ProfileResult};
+use crate::profiles::manager::ProfileManager;
+use crate::profiles::settings_manager::ProfileSettingsManager;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+
+/// Tipo di file orfano
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanedFileKind {
+    /// Settings `settings/<id>.json` di un profilo eliminato
+    Settings,
+    /// File profilo crittografato (include le credenziali store) non presente nell'indice
+    ProfileData,
+    /// Avatar non referenziato da nessun profilo
+    Avatar,
+}
+
+/// File senza profilo associato
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedFile {
+    pub path: PathBuf,
+    pub kind: OrphanedFileKind,
+    /// ID profilo ricavato dal nome file (se deducibile)
+    pub profile_id: Option<String>,
+    pub size_bytes: u64,
+}
+
+/// Report della pulizia dati orfani
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanCleanupReport {
+    /// Nessun file eliminato: solo elenco
+    pub dry_run: bool,
+    pub orphaned_files: Vec<OrphanedFile>,
+    /// Dimensione totale dei file orfani trovati
+    pub total_bytes: u64,
+    /// Cartella di quarantena usata (None in dry-run)
+    pub quarantine_dir: Option<PathBuf>,
+    /// File effettivamente spostati in quarantena
+    pub quarantined_files: usize,
+    /// Byte spostati in quarantena (0 in dry-run)
+    pub quarantined_bytes: u64,
+    /// Errori di spostamento (percorso: errore)
+    pub errors: Vec<String>,
+}
+
+/// Elenca (e con `dry_run = false` sposta in quarantena) i dati dei profili che non esistono più.
+/// Richiede una sessione autenticata; i profili esistenti sono ricavati da `list_profiles`.
+pub async fn cleanup_orphaned_profile_data(
+    manager: &ProfileManager,
+    settings_manager: &ProfileSettingsManager,
+    dry_run: bool,
+) -> ProfileResult<OrphanCleanupReport> {
+    let current_id = manager.current_profile_id().ok_or(ProfileError::Unauthorized)?;
+
+    let profiles = manager.list_profiles().await?;
+    let known_ids: HashSet<String> = profiles.iter().map(|p| p.id.clone()).collect();
+    if !known_ids.contains(current_id) {
+        return Err(ProfileError::Unauthorized);
+    }
+
+    let mut orphaned_files = settings_manager.find_orphaned_settings(&known_ids).await?;
+    orphaned_files.extend(manager.find_orphaned_storage_files().await?);
+
+    let total_bytes = orphaned_files.iter().map(|f| f.size_bytes).sum();
+    let mut report = OrphanCleanupReport {
+        dry_run,
+        orphaned_files,
+        total_bytes,
+        quarantine_dir: None,
+        quarantined_files: 0,
+        quarantined_bytes: 0,
+        errors: Vec::new(),
+    };
+
+    if dry_run {
+        println!("[PROFILE CLEANUP] 🔍 Dry-run: {} file orfani trovati ({} byte)",
+            report.orphaned_files.len(), report.total_bytes);
+        return Ok(report);
+    }
+
+    if report.orphaned_files.is_empty() {
+        return Ok(report);
+    }
+
+    // Una sottocartella per esecuzione: i file restano recuperabili a mano
+    let quarantine_dir = manager.orphan_quarantine_dir()
+        .join(chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string());
+
+    for file in &report.orphaned_files {
+        match quarantine_file(&quarantine_dir, file).await {
+            Ok(_) => {
+                report.quarantined_files += 1;
+                report.quarantined_bytes += file.size_bytes;
+            }
+            Err(e) => {
+                println!("[PROFILE CLEANUP] ❌ Errore spostamento in quarantena {}: {}", file.path.display(), e);
+                report.errors.push(format!("{}: {}", file.path.display(), e));
+            }
+        }
+    }
+
+    println!("[PROFILE CLEANUP] 🧹 Spostati {} file orfani ({} byte) in {}",
+        report.quarantined_files, report.quarantined_bytes, quarantine_dir.display());
+    report.quarantine_dir = Some(quarantine_dir);
+
+    Ok(report)
+}
+
+/// Sposta un file orfano in `<quarantena>/<tipo>/<nome file>`
+async fn quarantine_file(quarantine_dir: &Path, file: &OrphanedFile) -> std::io::Result<()> {
+    let kind_dir = match file.kind {
+        OrphanedFileKind::Settings => "settings",
+        OrphanedFileKind::ProfileData => "profiles",
+        OrphanedFileKind::Avatar => "avatars",
+    };
+    let file_name = file.path.file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "nome file mancante"))?;
+    let target_dir = quarantine_dir.join(kind_dir);
+    fs::create_dir_all(&target_dir).await?;
+    fs::rename(&file.path, target_dir.join(file_name)).await
+}
+
+/// Dimensione di un file orfano (0 se non leggibile)
+pub(crate) async fn orphan_file_size(path: &std::path::Path) -> u64 {
+    fs::metadata(path).await.map(|m| m.len()).unwrap_or(0)
+}
@@ -1,4 +1,4 @@
-// Modulo per crittografia profili con AES-256-GCM e PBKDF2
+// Modulo per crittografia profili con AES-256-GCM e PBKDF2 (Argon2id per le passphrase di export)
 use crate::profiles::errors::{ProfileError, ProfileResult};
 use crate::profiles::secure_memory::SecureMemory;
 use crate::profiles::models::UserProfile;
@@ -20,6 +20,15 @@ const SALT_LENGTH: usize = 32; // 256 bit salt
 const NONCE_LENGTH: usize = 12; // 96 bit nonce per AES-GCM
 const KEY_LENGTH: usize = 32; // 256 bit key
 
+/// Parametri Argon2id per le passphrase di export (valori raccomandati OWASP)
+const ARGON2_MEMORY_KIB: u32 = 19_456;
+const ARGON2_ITERATIONS: u32 = 2;
+const ARGON2_PARALLELISM: u32 = 1;
+
+/// Versioni formato `EncryptedData`
+const FORMAT_PBKDF2: u32 = 1;
+const FORMAT_ARGON2ID: u32 = 2;
+
 /// Struttura per dati crittografati
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EncryptedData {
@@ -31,7 +40,7 @@ pub struct EncryptedData {
     pub nonce: Vec<u8>,
     /// Dati crittografati con tag di autenticazione
     pub ciphertext: Vec<u8>,
-    /// Numero iterazioni PBKDF2 usate
+    /// Numero iterazioni del KDF (PBKDF2 per v1, t_cost Argon2id per v2)
     pub iterations: u32,
 }
 
@@ -66,13 +75,28 @@ impl ProfileEncryption {
         // Deriva chiave da password usando PBKDF2
         let key = self.derive_key(password, &salt, PBKDF2_ITERATIONS)?;
 
+        self.encrypt_with_key(data, &key, salt, FORMAT_PBKDF2, PBKDF2_ITERATIONS)
+    }
+
+    /// Crittografa un export con una passphrase scelta dall'utente (chiave Argon2id)
+    pub fn encrypt_with_passphrase(&self, data: &[u8], passphrase: &str) -> ProfileResult<Vec<u8>> {
+        let mut salt = vec![0u8; SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
+
+        let key = self.derive_key_argon2(passphrase, &salt, ARGON2_ITERATIONS)?;
+
+        self.encrypt_with_key(data, &key, salt, FORMAT_ARGON2ID, ARGON2_ITERATIONS)
+    }
+
+    /// AES-256-GCM con chiave già derivata; serializza il risultato in `EncryptedData`
+    fn encrypt_with_key(&self, data: &[u8], key: &Key<Aes256Gcm>, salt: Vec<u8>, version: u32, iterations: u32) -> ProfileResult<Vec<u8>> {
         // Genera nonce casuale
         let mut nonce_bytes = vec![0u8; NONCE_LENGTH];
         OsRng.fill_bytes(&mut nonce_bytes);
         let nonce = Nonce::from_slice(&nonce_bytes);
 
         // Inizializza cipher AES-256-GCM
-        let cipher = Aes256Gcm::new(&key);
+        let cipher = Aes256Gcm::new(key);
 
         // Crittografa i dati
         let ciphertext = cipher
@@ -81,11 +105,11 @@ impl ProfileEncryption {
 
         // Crea struttura dati crittografati
         let encrypted_data = EncryptedData {
-            version: 1,
+            version,
             salt,
             nonce: nonce_bytes,
             ciphertext,
-            iterations: PBKDF2_ITERATIONS,
+            iterations,
         };
 
         // Serializza in binario
@@ -103,7 +127,7 @@ impl ProfileEncryption {
             .map_err(|e| ProfileError::EncryptionError(format!("Errore deserializzazione: {}", e)))?;
 
         // Verifica versione formato
-        if encrypted.version != 1 {
+        if encrypted.version != FORMAT_PBKDF2 && encrypted.version != FORMAT_ARGON2ID {
             return Err(ProfileError::DataFormatError(format!(
                 "Versione formato non supportata: {}",
                 encrypted.version
@@ -127,8 +151,12 @@ impl ProfileEncryption {
             )));
         }
 
-        // Deriva chiave da password
-        let key = self.derive_key(password, &encrypted.salt, encrypted.iterations)?;
+        // Deriva chiave con il KDF indicato dal formato
+        let key = if encrypted.version == FORMAT_ARGON2ID {
+            self.derive_key_argon2(password, &encrypted.salt, encrypted.iterations)?
+        } else {
+            self.derive_key(password, &encrypted.salt, encrypted.iterations)?
+        };
 
         // Prepara nonce
         let nonce = Nonce::from_slice(&encrypted.nonce);
@@ -202,6 +230,42 @@ impl ProfileEncryption {
         Ok(*key)
     }
 
+    /// Deriva chiave da passphrase usando Argon2id
+    fn derive_key_argon2(&self, passphrase: &str, salt: &[u8], iterations: u32) -> ProfileResult<Key<Aes256Gcm>> {
+        use argon2::{Algorithm, Argon2, Params, Version};
+
+        if passphrase.is_empty() {
+            return Err(ProfileError::WeakPassword("Passphrase vuota".to_string()));
+        }
+
+        if salt.len() != SALT_LENGTH {
+            return Err(ProfileError::EncryptionError(format!(
+                "Salt lunghezza invalida: {}",
+                salt.len()
+            )));
+        }
+
+        let params = Params::new(ARGON2_MEMORY_KIB, iterations, ARGON2_PARALLELISM, Some(KEY_LENGTH))
+            .map_err(|e| ProfileError::EncryptionError(format!("Parametri Argon2 non validi: {}", e)))?;
+        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+        let mut key_bytes = [0u8; KEY_LENGTH];
+        argon2
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key_bytes)
+            .map_err(|e| ProfileError::EncryptionError(format!("Errore Argon2: {}", e)))?;
+
+        let key = *Key::<Aes256Gcm>::from_slice(&key_bytes);
+        key_bytes.iter_mut().for_each(|b| *b = 0);
+        Ok(key)
+    }
+
+    /// true se i dati sono protetti da passphrase (formato Argon2id)
+    pub fn is_passphrase_protected(&self, encrypted_data: &[u8]) -> bool {
+        bincode::deserialize::<EncryptedData>(encrypted_data)
+            .map(|e| e.version == FORMAT_ARGON2ID)
+            .unwrap_or(false)
+    }
+
     /// Verifica forza password
     #[allow(dead_code)] // API per validazione password - utilizzata nei test e per sicurezza
     pub fn validate_password_strength(&self, password: &str) -> ProfileResult<()> {
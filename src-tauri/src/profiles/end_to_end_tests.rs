@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod end_to_end_tests {
+    use crate::profiles::test_support::profile_id_by_name;
     use crate::profiles::manager::ProfileManager;
     use crate::profiles::storage::ProfileStorage;
     use crate::profiles::models::{CreateProfileRequest, ProfileSettings, Theme, NotificationSettings, LibrarySettings, SecuritySettings, EncryptedCredential};
@@ -43,6 +44,8 @@ mod end_to_end_tests {
                 },
                 security: SecuritySettings {
                     session_timeout: 60,
+                    max_session_duration: 720,
+                    session_expiry_warning: 120,
                     require_password_for_sensitive: true,
                     auto_lock_failed_attempts: 5,
                     lock_duration: 30,
@@ -73,7 +76,7 @@ mod end_to_end_tests {
         println!("=== FASE 2: AUTENTICAZIONE ===");
         
         // Autentica il profilo
-        let authenticated_profile = manager.authenticate_profile("GameUser", "SecureGamePass123!")
+        let authenticated_profile = manager.authenticate_profile(&profile_id_by_name(&manager, "GameUser").await, "SecureGamePass123!")
             .await.expect("Failed to authenticate profile");
         
         assert_eq!(authenticated_profile.name, "GameUser");
@@ -136,6 +139,8 @@ mod end_to_end_tests {
             },
             security: SecuritySettings {
                 session_timeout: 120,
+                max_session_duration: 720,
+                session_expiry_warning: 120,
                 require_password_for_sensitive: false,
                 auto_lock_failed_attempts: 3,
                 lock_duration: 15,
@@ -170,7 +175,7 @@ mod end_to_end_tests {
         assert!(manager.current_profile().is_none());
         
         // Riautentica e verifica che i dati siano ancora presenti
-        let reauth_profile = manager.authenticate_profile("GameUser", "SecureGamePass123!")
+        let reauth_profile = manager.authenticate_profile(&profile_id_by_name(&manager, "GameUser").await, "SecureGamePass123!")
             .await.expect("Failed to re-authenticate");
         
         assert_eq!(reauth_profile.name, "GameUser");
@@ -211,7 +216,7 @@ mod end_to_end_tests {
         println!("=== FASE 2: CONFIGURAZIONE PROFILO GAMING ===");
         
         // Autentica profilo Gaming
-        manager.authenticate_profile("GamingProfile", "GamingPass123!")
+        manager.authenticate_profile(&profile_id_by_name(&manager, "GamingProfile").await, "GamingPass123!")
             .await.expect("Failed to authenticate gaming profile");
         
         // Configura impostazioni Gaming
@@ -236,6 +241,8 @@ mod end_to_end_tests {
             },
             security: SecuritySettings {
                 session_timeout: 30,
+                max_session_duration: 720,
+                session_expiry_warning: 120,
                 require_password_for_sensitive: true,
                 auto_lock_failed_attempts: 5,
                 lock_duration: 30,
@@ -277,7 +284,7 @@ mod end_to_end_tests {
         println!("=== FASE 3: CAMBIO A PROFILO WORK ===");
         
         // Cambia a profilo Work
-        manager.switch_profile("WorkProfile", "WorkPass456!")
+        manager.switch_profile(&profile_id_by_name(&manager, "WorkProfile").await, "WorkPass456!")
             .await.expect("Failed to switch to work profile");
         
         assert_eq!(manager.current_profile().unwrap().name, "WorkProfile");
@@ -304,6 +311,8 @@ mod end_to_end_tests {
             },
             security: SecuritySettings {
                 session_timeout: 240,
+                max_session_duration: 720,
+                session_expiry_warning: 120,
                 require_password_for_sensitive: false,
                 auto_lock_failed_attempts: 10,
                 lock_duration: 5,
@@ -364,7 +373,7 @@ mod end_to_end_tests {
         println!("=== FASE 5: RITORNO A PROFILO GAMING ===");
         
         // Torna al profilo Gaming
-        manager.switch_profile("GamingProfile", "GamingPass123!")
+        manager.switch_profile(&profile_id_by_name(&manager, "GamingProfile").await, "GamingPass123!")
             .await.expect("Failed to switch back to gaming profile");
         
         assert_eq!(manager.current_profile().unwrap().name, "GamingProfile");
@@ -392,7 +401,7 @@ mod end_to_end_tests {
         manager.logout().expect("Failed to logout");
         
         // Riautentica Gaming
-        manager.authenticate_profile("GamingProfile", "GamingPass123!")
+        manager.authenticate_profile(&profile_id_by_name(&manager, "GamingProfile").await, "GamingPass123!")
             .await.expect("Failed to re-authenticate gaming");
         
         // Verifica che i dati Gaming siano ancora isolati
@@ -402,7 +411,7 @@ mod end_to_end_tests {
         assert!(manager.get_credential("slack").is_none(), "Slack credential should not be visible");
         
         // Cambia a Work e verifica isolamento
-        manager.switch_profile("WorkProfile", "WorkPass456!")
+        manager.switch_profile(&profile_id_by_name(&manager, "WorkProfile").await, "WorkPass456!")
             .await.expect("Failed to switch to work after restart");
         
         assert!(manager.get_credential("github").is_some(), "GitHub credential should persist");
@@ -425,7 +434,7 @@ mod end_to_end_tests {
         let _profile = source_manager.create_profile(request).await.expect("Failed to create profile");
         
         // Autentica
-        source_manager.authenticate_profile("ComplexUser", "ComplexPass123!")
+        source_manager.authenticate_profile(&profile_id_by_name(&source_manager, "ComplexUser").await, "ComplexPass123!")
             .await.expect("Failed to authenticate");
         
         // Configura impostazioni dettagliate
@@ -450,6 +459,8 @@ mod end_to_end_tests {
             },
             security: SecuritySettings {
                 session_timeout: 180,
+                max_session_duration: 720,
+                session_expiry_warning: 120,
                 require_password_for_sensitive: true,
                 auto_lock_failed_attempts: 7,
                 lock_duration: 20,
@@ -521,7 +532,7 @@ mod end_to_end_tests {
             .await.expect("Failed to import profile");
         
         // Autentica il profilo importato
-        target_manager.authenticate_profile("ComplexUser", "ComplexPass123!")
+        target_manager.authenticate_profile(&profile_id_by_name(&target_manager, "ComplexUser").await, "ComplexPass123!")
             .await.expect("Failed to authenticate imported profile");
         
         // Ricrea le credenziali (simulando l'import)
@@ -624,7 +635,7 @@ mod end_to_end_tests {
             .await.expect("Failed to create secure imported profile");
         
         // Verifica che il profilo con password diversa funzioni
-        target_manager.authenticate_profile("ComplexUserSecure", "NewSecurePass456!")
+        target_manager.authenticate_profile(&profile_id_by_name(&target_manager, "ComplexUserSecure").await, "NewSecurePass456!")
             .await.expect("Failed to authenticate secure imported profile");
         
         let secure_settings = target_manager.get_settings().unwrap();
@@ -673,6 +684,8 @@ mod end_to_end_tests {
                 },
                 security: SecuritySettings {
                     session_timeout: 60,
+                    max_session_duration: 720,
+                    session_expiry_warning: 120,
                     require_password_for_sensitive: true,
                     auto_lock_failed_attempts: 5,
                     lock_duration: 30,
@@ -684,7 +697,7 @@ mod end_to_end_tests {
             .expect("Failed to create MainUser profile");
         
         // Autentica profilo
-        let auth_profile1 = manager.authenticate_profile("MainUser", "MainUserPass123!").await
+        let auth_profile1 = manager.authenticate_profile(&profile_id_by_name(&manager, "MainUser").await, "MainUserPass123!").await
             .expect("Failed to authenticate MainUser");
         
         assert_eq!(auth_profile1.name, "MainUser");
@@ -750,6 +763,8 @@ mod end_to_end_tests {
                 },
                 security: SecuritySettings {
                     session_timeout: 120,
+                    max_session_duration: 720,
+                    session_expiry_warning: 120,
                     require_password_for_sensitive: false,
                     auto_lock_failed_attempts: 3,
                     lock_duration: 15,
@@ -761,7 +776,7 @@ mod end_to_end_tests {
             .expect("Failed to create SecondUser profile");
 
         // Cambia al secondo profilo
-        manager.switch_profile("SecondUser", "SecondUserPass456!").await
+        manager.switch_profile(&profile_id_by_name(&manager, "SecondUser").await, "SecondUserPass456!").await
             .expect("Failed to switch to SecondUser");
 
         assert_eq!(manager.current_profile().unwrap().name, "SecondUser");
@@ -804,7 +819,7 @@ mod end_to_end_tests {
         println!("--- Step 3: Verifica isolamento bidirezionale ---");
         
         // Torna a MainUser
-        manager.switch_profile("MainUser", "MainUserPass123!").await
+        manager.switch_profile(&profile_id_by_name(&manager, "MainUser").await, "MainUserPass123!").await
             .expect("Failed to switch back to MainUser");
 
         // Verifica che MainUser non veda le credenziali di SecondUser
@@ -842,7 +857,7 @@ mod end_to_end_tests {
         };
 
         // Cambia a SecondUser ed export
-        manager.switch_profile("SecondUser", "SecondUserPass456!").await
+        manager.switch_profile(&profile_id_by_name(&manager, "SecondUser").await, "SecondUserPass456!").await
             .expect("Failed to switch to SecondUser for export");
 
         let second_export_data = ExportedProfileData {
@@ -869,7 +884,7 @@ mod end_to_end_tests {
         let _imported_main = import_manager.create_profile(main_import_request).await
             .expect("Failed to import MainUser profile");
 
-        import_manager.authenticate_profile("MainUser", "MainUserPass123!").await
+        import_manager.authenticate_profile(&profile_id_by_name(&import_manager, "MainUser").await, "MainUserPass123!").await
             .expect("Failed to authenticate imported MainUser");
 
         // Ricrea credenziali MainUser
@@ -889,7 +904,7 @@ mod end_to_end_tests {
         let _imported_second = import_manager.create_profile(second_import_request).await
             .expect("Failed to import SecondUser profile");
 
-        import_manager.switch_profile("SecondUser", "SecondUserPass456!").await
+        import_manager.switch_profile(&profile_id_by_name(&import_manager, "SecondUser").await, "SecondUserPass456!").await
             .expect("Failed to switch to imported SecondUser");
 
         // Ricrea credenziali SecondUser
@@ -904,7 +919,7 @@ mod end_to_end_tests {
         println!("--- Step 5: Verifica integrità post-import ---");
         
         // Verifica MainUser importato
-        import_manager.authenticate_profile("MainUser", "MainUserPass123!").await
+        import_manager.authenticate_profile(&profile_id_by_name(&import_manager, "MainUser").await, "MainUserPass123!").await
             .expect("Failed to authenticate imported MainUser");
 
         let imported_main_settings = import_manager.get_settings().unwrap();
@@ -918,7 +933,7 @@ mod end_to_end_tests {
         assert!(import_manager.get_credential("origin").is_none());
 
         // Verifica SecondUser importato
-        import_manager.switch_profile("SecondUser", "SecondUserPass456!").await
+        import_manager.switch_profile(&profile_id_by_name(&import_manager, "SecondUser").await, "SecondUserPass456!").await
             .expect("Failed to switch to imported SecondUser");
 
         let imported_second_settings = import_manager.get_settings().unwrap();
@@ -941,7 +956,7 @@ mod end_to_end_tests {
         assert!(!import_manager.is_profile_active());
 
         // Riautentica e verifica persistenza
-        import_manager.authenticate_profile("MainUser", "MainUserPass123!").await
+        import_manager.authenticate_profile(&profile_id_by_name(&import_manager, "MainUser").await, "MainUserPass123!").await
             .expect("Failed to re-authenticate MainUser after restart");
 
         let persistent_settings = import_manager.get_settings().unwrap();
@@ -1004,7 +1019,7 @@ mod end_to_end_tests {
         println!("=== CONFIGURAZIONE PROFILI SPECIFICI ===");
         
         // Configura Dad (Gamer adulto)
-        manager.authenticate_profile("Dad", "DadSecurePass123!")
+        manager.authenticate_profile(&profile_id_by_name(&manager, "Dad").await, "DadSecurePass123!")
             .await.expect("Failed to authenticate Dad");
         
         let dad_credentials = vec![
@@ -1031,7 +1046,7 @@ mod end_to_end_tests {
         println!("✅ Dad configurato con Steam, Epic, Origin");
 
         // Configura Mom (Casual gamer)
-        manager.switch_profile("Mom", "MomSecurePass456!")
+        manager.switch_profile(&profile_id_by_name(&manager, "Mom").await, "MomSecurePass456!")
             .await.expect("Failed to switch to Mom");
         
         let mom_credentials = vec![
@@ -1057,7 +1072,7 @@ mod end_to_end_tests {
         println!("✅ Mom configurata con Steam e Mobile Games");
 
         // Configura Teen (Heavy gamer)
-        manager.switch_profile("Teen", "TeenGamePass789!")
+        manager.switch_profile(&profile_id_by_name(&manager, "Teen").await, "TeenGamePass789!")
             .await.expect("Failed to switch to Teen");
         
         let teen_credentials = vec![
@@ -1085,7 +1100,7 @@ mod end_to_end_tests {
         println!("✅ Teen configurato con Steam, Epic, Discord, Twitch");
 
         // Configura Kid (Limitato)
-        manager.switch_profile("Kid", "KidSafePass012!")
+        manager.switch_profile(&profile_id_by_name(&manager, "Kid").await, "KidSafePass012!")
             .await.expect("Failed to switch to Kid");
         
         let kid_credentials = vec![
@@ -1113,7 +1128,7 @@ mod end_to_end_tests {
         println!("=== VERIFICA ISOLAMENTO COMPLETO ===");
         
         // Test isolamento Dad
-        manager.switch_profile("Dad", "DadSecurePass123!")
+        manager.switch_profile(&profile_id_by_name(&manager, "Dad").await, "DadSecurePass123!")
             .await.expect("Failed to switch to Dad for isolation test");
         
         assert!(manager.get_credential("steam").is_some(), "Dad should have Steam");
@@ -1124,7 +1139,7 @@ mod end_to_end_tests {
         assert!(manager.get_credential("educational_games").is_none(), "Dad should not see Kid's educational games");
         
         // Test isolamento Mom
-        manager.switch_profile("Mom", "MomSecurePass456!")
+        manager.switch_profile(&profile_id_by_name(&manager, "Mom").await, "MomSecurePass456!")
             .await.expect("Failed to switch to Mom for isolation test");
         
         assert!(manager.get_credential("steam").is_some(), "Mom should have Steam");
@@ -1134,7 +1149,7 @@ mod end_to_end_tests {
         assert!(manager.get_credential("educational_games").is_none(), "Mom should not see Kid's educational games");
         
         // Test isolamento Teen
-        manager.switch_profile("Teen", "TeenGamePass789!")
+        manager.switch_profile(&profile_id_by_name(&manager, "Teen").await, "TeenGamePass789!")
             .await.expect("Failed to switch to Teen for isolation test");
         
         assert!(manager.get_credential("steam").is_some(), "Teen should have Steam");
@@ -1146,7 +1161,7 @@ mod end_to_end_tests {
         assert!(manager.get_credential("educational_games").is_none(), "Teen should not see Kid's educational games");
         
         // Test isolamento Kid
-        manager.switch_profile("Kid", "KidSafePass012!")
+        manager.switch_profile(&profile_id_by_name(&manager, "Kid").await, "KidSafePass012!")
             .await.expect("Failed to switch to Kid for isolation test");
         
         assert!(manager.get_credential("educational_games").is_some(), "Kid should have educational games");
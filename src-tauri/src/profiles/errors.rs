@@ -44,6 +44,18 @@ pub enum ProfileError {
     
     #[error("Credenziali non valide")]
     InvalidCredentials,
+    
+    #[error("Export creato da una versione più recente di GameStringer (schema v{found}, supportato fino a v{supported}): aggiorna l'app per importarlo")]
+    ExportFromNewerVersion { found: u32, supported: u32 },
+    
+    #[error("Questo export è protetto da passphrase: inseriscila per importarlo")]
+    ExportPassphraseRequired,
+    
+    #[error("Passphrase dell'export errata")]
+    InvalidExportPassphrase,
+    
+    #[error("Sessione scaduta: effettua di nuovo l'accesso")]
+    SessionExpired,
 }
 
 /// Risultato per operazioni sui profili
@@ -64,6 +76,9 @@ pub enum StorageError {
     #[error("Spazio insufficiente")]
     InsufficientSpace,
     
+    #[error("Indice profili non utilizzabile: {0}")]
+    IndexUnavailable(String),
+    
     #[error("Errore I/O: {0}")]
     IoError(#[from] std::io::Error),
     
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod profile_manager_integration_tests {
+    use crate::profiles::test_support::profile_id_by_name;
     use crate::profiles::manager::ProfileManager;
     use crate::profiles::storage::ProfileStorage;
     use crate::profiles::models::{EncryptedCredential, CreateProfileRequest, ProfileSettings, Theme, NotificationSettings, LibrarySettings, SecuritySettings};
@@ -46,6 +47,8 @@ mod profile_manager_integration_tests {
                 },
                 security: SecuritySettings {
                     session_timeout: 60,
+                    max_session_duration: 720,
+                    session_expiry_warning: 120,
                     require_password_for_sensitive: true,
                     auto_lock_failed_attempts: 5,
                     lock_duration: 30,
@@ -73,7 +76,7 @@ mod profile_manager_integration_tests {
         assert_eq!(profiles[0].name, "TestUser");
 
         // Test: Autenticazione profilo
-        let authenticated = manager.authenticate_profile("TestUser", "SecurePass123!").await.expect("Failed to authenticate");
+        let authenticated = manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "SecurePass123!").await.expect("Failed to authenticate");
         assert_eq!(authenticated.name, "TestUser");
         assert!(manager.is_profile_active());
 
@@ -101,11 +104,11 @@ mod profile_manager_integration_tests {
         manager.create_profile(request).await.expect("Failed to create profile");
 
         // Test: Password errata
-        let result = manager.authenticate_profile("TestUser", "WrongPassword").await;
+        let result = manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "WrongPassword").await;
         assert!(matches!(result, Err(ProfileError::InvalidCredentials)));
 
         // Test: Utente inesistente
-        let result = manager.authenticate_profile("NonExistentUser", "AnyPassword").await;
+        let result = manager.authenticate_profile(&profile_id_by_name(&manager, "NonExistentUser").await, "AnyPassword").await;
         assert!(matches!(result, Err(ProfileError::ProfileNotFound(_))));
     }
 
@@ -130,21 +133,21 @@ mod profile_manager_integration_tests {
 
         // Test: Primi tentativi falliti dovrebbero essere consentiti
         for _ in 0..3 {
-            let result = manager.authenticate_profile("TestUser", "WrongPassword").await;
+            let result = manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "WrongPassword").await;
             assert!(matches!(result, Err(ProfileError::InvalidCredentials)));
         }
 
         // Test: Il quarto tentativo dovrebbe essere bloccato
-        let result = manager.authenticate_profile("TestUser", "WrongPassword").await;
+        let result = manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "WrongPassword").await;
         assert!(matches!(result, Err(ProfileError::TooManyAttempts(_))));
 
         // Test: Anche con password corretta dovrebbe essere bloccato
-        let result = manager.authenticate_profile("TestUser", "SecurePass123!").await;
+        let result = manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "SecurePass123!").await;
         assert!(matches!(result, Err(ProfileError::TooManyAttempts(_))));
 
         // Test: Reset manuale dei tentativi
         manager.reset_login_attempts(&profile.id);
-        let result = manager.authenticate_profile("TestUser", "SecurePass123!").await;
+        let result = manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "SecurePass123!").await;
         assert!(result.is_ok());
     }
 
@@ -174,7 +177,7 @@ mod profile_manager_integration_tests {
         // Crea e autentica profilo
         let request = create_test_profile_request("TestUser", "SecurePass123!");
         manager.create_profile(request).await.expect("Failed to create profile");
-        manager.authenticate_profile("TestUser", "SecurePass123!").await.expect("Failed to authenticate");
+        manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "SecurePass123!").await.expect("Failed to authenticate");
 
         // Test: Aggiornamento impostazioni
         let new_settings = ProfileSettings {
@@ -198,6 +201,8 @@ mod profile_manager_integration_tests {
             },
             security: SecuritySettings {
                 session_timeout: 30,
+                max_session_duration: 720,
+                session_expiry_warning: 120,
                 require_password_for_sensitive: false,
                 auto_lock_failed_attempts: 3,
                 lock_duration: 15,
@@ -223,7 +228,7 @@ mod profile_manager_integration_tests {
         // Crea e autentica profilo
         let request = create_test_profile_request("TestUser", "SecurePass123!");
         manager.create_profile(request).await.expect("Failed to create profile");
-        manager.authenticate_profile("TestUser", "SecurePass123!").await.expect("Failed to authenticate");
+        manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "SecurePass123!").await.expect("Failed to authenticate");
 
         // Test: Aggiunta credenziale
         let credential = EncryptedCredential {
@@ -256,7 +261,7 @@ mod profile_manager_integration_tests {
         // Crea e autentica profilo
         let request = create_test_profile_request("TestUser", "SecurePass123!");
         manager.create_profile(request).await.expect("Failed to create profile");
-        manager.authenticate_profile("TestUser", "SecurePass123!").await.expect("Failed to authenticate");
+        manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "SecurePass123!").await.expect("Failed to authenticate");
 
         // Test: Statistiche sessione
         let stats = manager.get_session_stats().expect("No session stats");
@@ -338,10 +343,10 @@ mod profile_manager_integration_tests {
         assert_eq!(all_profiles.len(), 3);
 
         // Test: Cambio profilo
-        manager.authenticate_profile("User1", "Pass1_Secure!").await.expect("Failed to authenticate User1");
+        manager.authenticate_profile(&profile_id_by_name(&manager, "User1").await, "Pass1_Secure!").await.expect("Failed to authenticate User1");
         assert_eq!(manager.current_profile().unwrap().name, "User1");
 
-        manager.switch_profile("User2", "Pass2_Secure!").await.expect("Failed to switch to User2");
+        manager.switch_profile(&profile_id_by_name(&manager, "User2").await, "Pass2_Secure!").await.expect("Failed to switch to User2");
         assert_eq!(manager.current_profile().unwrap().name, "User2");
 
         // Test: Isolamento dati tra profili
@@ -358,12 +363,12 @@ mod profile_manager_integration_tests {
         manager.add_credential(credential, "Pass2_Secure!").await.expect("Failed to add credential to User2");
 
         // Cambia a User3 e verifica che non veda le credenziali di User2
-        manager.switch_profile("User3", "Pass3_Secure!").await.expect("Failed to switch to User3");
+        manager.switch_profile(&profile_id_by_name(&manager, "User3").await, "Pass3_Secure!").await.expect("Failed to switch to User3");
         let user3_credential = manager.get_credential("steam");
         assert!(user3_credential.is_none());
 
         // Torna a User2 e verifica che le credenziali ci siano ancora
-        manager.switch_profile("User2", "Pass2_Secure!").await.expect("Failed to switch back to User2");
+        manager.switch_profile(&profile_id_by_name(&manager, "User2").await, "Pass2_Secure!").await.expect("Failed to switch back to User2");
         let user2_credential = manager.get_credential("steam");
         assert!(user2_credential.is_some());
         assert_eq!(user2_credential.unwrap().encrypted_data, "user2_encrypted_data");
@@ -392,7 +397,7 @@ mod profile_manager_integration_tests {
         // Test: Eliminazione profilo attivo
         let request = create_test_profile_request("TestUser", "SecurePass123!");
         let profile = manager.create_profile(request).await.expect("Failed to create profile");
-        manager.authenticate_profile("TestUser", "SecurePass123!").await.expect("Failed to authenticate");
+        manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "SecurePass123!").await.expect("Failed to authenticate");
 
         let result = manager.delete_profile(&profile.id, "SecurePass123!").await;
         assert!(matches!(result, Err(ProfileError::Unauthorized)));
@@ -417,7 +422,7 @@ mod profile_manager_integration_tests {
         
         // Logout e riautentica per forzare il caricamento da storage
         manager.logout().expect("Failed to logout");
-        let reloaded_profile = manager.authenticate_profile("TestUser", "SecurePass123!").await.expect("Failed to reload profile");
+        let reloaded_profile = manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "SecurePass123!").await.expect("Failed to reload profile");
         
         assert_eq!(reloaded_profile.name, profile.name);
         assert_eq!(reloaded_profile.id, profile.id);
@@ -425,7 +430,7 @@ mod profile_manager_integration_tests {
 
         // Test: Password errata dovrebbe fallire la decrittografia
         manager.logout().expect("Failed to logout");
-        let result = manager.authenticate_profile("TestUser", "WrongPassword").await;
+        let result = manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "WrongPassword").await;
         assert!(result.is_err());
     }
 
@@ -436,7 +441,7 @@ mod profile_manager_integration_tests {
         // Crea profilo
         let request = create_test_profile_request("TestUser", "SecurePass123!");
         manager.create_profile(request).await.expect("Failed to create profile");
-        manager.authenticate_profile("TestUser", "SecurePass123!").await.expect("Failed to authenticate");
+        manager.authenticate_profile(&profile_id_by_name(&manager, "TestUser").await, "SecurePass123!").await.expect("Failed to authenticate");
 
         // Test: Operazioni multiple simultanee (simulazione)
         // In un test reale, queste operazioni dovrebbero essere thread-safe
@@ -499,6 +504,8 @@ mod profile_manager_integration_tests {
                 },
                 security: SecuritySettings {
                     session_timeout: 120,
+                    max_session_duration: 720,
+                    session_expiry_warning: 120,
                     require_password_for_sensitive: true,
                     auto_lock_failed_attempts: 3,
                     lock_duration: 60,
@@ -511,7 +518,7 @@ mod profile_manager_integration_tests {
         assert_eq!(created_profile.avatar_path, Some("/path/to/avatar.png".to_string()));
 
         // FASE 2: Autenticazione e verifica stato
-        let authenticated_profile = manager.authenticate_profile("CompleteUser", "CompletePass123!").await
+        let authenticated_profile = manager.authenticate_profile(&profile_id_by_name(&manager, "CompleteUser").await, "CompletePass123!").await
             .expect("Failed to authenticate complete profile");
         
         assert_eq!(authenticated_profile.name, "CompleteUser");
@@ -576,7 +583,7 @@ mod profile_manager_integration_tests {
             .expect("Failed to create second profile");
 
         // Cambia al secondo profilo
-        manager.switch_profile("SecondUser", "SecondPass456!").await
+        manager.switch_profile(&profile_id_by_name(&manager, "SecondUser").await, "SecondPass456!").await
             .expect("Failed to switch to second profile");
 
         // Verifica che il secondo profilo non veda le credenziali del primo
@@ -590,7 +597,7 @@ mod profile_manager_integration_tests {
         assert_eq!(second_settings.language, "it");
 
         // FASE 5: Torna al primo profilo e verifica persistenza
-        manager.switch_profile("CompleteUser", "CompletePass123!").await
+        manager.switch_profile(&profile_id_by_name(&manager, "CompleteUser").await, "CompletePass123!").await
             .expect("Failed to switch back to first profile");
 
         // Verifica che tutte le credenziali siano ancora presenti
@@ -627,7 +634,7 @@ mod profile_manager_integration_tests {
         assert_eq!(imported_profile.avatar_path, Some("/path/to/avatar.png".to_string()));
 
         // Autentica il profilo importato
-        import_manager.authenticate_profile("CompleteUser", "CompletePass123!").await
+        import_manager.authenticate_profile(&profile_id_by_name(&import_manager, "CompleteUser").await, "CompletePass123!").await
             .expect("Failed to authenticate imported profile");
 
         // Verifica che le impostazioni siano state importate correttamente
@@ -678,7 +685,7 @@ mod profile_manager_integration_tests {
         // Test isolamento per ogni profilo
         for (i, (profile, password)) in created_profiles.iter().enumerate() {
             // Autentica profilo corrente
-            manager.authenticate_profile(&profile.name, password).await
+            manager.authenticate_profile(&profile_id_by_name(&manager, &profile.name).await, password).await
                 .expect(&format!("Failed to authenticate {}", profile.name));
 
             // Aggiungi credenziali specifiche per questo profilo
@@ -708,7 +715,7 @@ mod profile_manager_integration_tests {
 
         // Verifica isolamento: ogni profilo dovrebbe vedere solo i propri dati
         for (i, (profile, password)) in created_profiles.iter().enumerate() {
-            manager.authenticate_profile(&profile.name, password).await
+            manager.authenticate_profile(&profile_id_by_name(&manager, &profile.name).await, password).await
                 .expect(&format!("Failed to re-authenticate {}", profile.name));
 
             // Verifica che veda solo la propria credenziale
@@ -766,6 +773,8 @@ mod profile_manager_integration_tests {
                 },
                 security: SecuritySettings {
                     session_timeout: 90,
+                    max_session_duration: 720,
+                    session_expiry_warning: 120,
                     require_password_for_sensitive: false,
                     auto_lock_failed_attempts: 7,
                     lock_duration: 120,
@@ -776,7 +785,7 @@ mod profile_manager_integration_tests {
         let _profile = source_manager.create_profile(request).await
             .expect("Failed to create export test profile");
         
-        source_manager.authenticate_profile("ExportTestUser", "ExportPass123!").await
+        source_manager.authenticate_profile(&profile_id_by_name(&source_manager, "ExportTestUser").await, "ExportPass123!").await
             .expect("Failed to authenticate for export");
 
         // Aggiungi dati complessi
@@ -821,7 +830,7 @@ mod profile_manager_integration_tests {
         assert_eq!(imported_profile.avatar_path, Some("/path/to/complex/avatar.jpg".to_string()));
 
         // Autentica profilo importato
-        target_manager.authenticate_profile("ExportTestUser", "ExportPass123!").await
+        target_manager.authenticate_profile(&profile_id_by_name(&target_manager, "ExportTestUser").await, "ExportPass123!").await
             .expect("Failed to authenticate imported complex profile");
 
         // Verifica impostazioni dettagliate
@@ -1,10 +1,11 @@
 // Modulo per gestione profili - ProfileManager core
 use crate::profiles::models::{UserProfile, ProfileInfo, CreateProfileRequest, ProfileSettings, EncryptedCredential, ProfileUsageStats, ProfilesSystemStats, SystemUsageStats, ProfilesHealthCheck, HealthCheckResult, HealthStatus, ProfilesSystemConfig};
-use crate::profiles::storage::ProfileStorage;
+use crate::profiles::storage::{BackupSchedule, ProfileStorage, ScheduledBackupInfo};
 use crate::profiles::encryption::ProfileEncryption;
-use crate::profiles::errors::{ProfileError, ProfileResult};
-use crate::profiles::validation::{ProfileValidator, ValidationConfig, ProfileNameValidationResult, PasswordValidationResult};
-use crate::profiles::rate_limiter::{RateLimiter, RateLimiterConfig, RateLimitResult};
+use crate::profiles::errors::{ProfileError, ProfileResult, StorageError};
+use crate::profiles::validation::{ProfileValidator, ValidationConfig, ProfileNameValidationResult, PasswordValidationResult, SanitizedInput};
+use crate::profiles::password_strength::PasswordStrengthEstimate;
+use crate::profiles::rate_limiter::{LockoutStatus, RateLimiter, RateLimiterConfig, RateLimitResult};
 use crate::profiles::secure_memory::SecureMemory;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -44,6 +45,58 @@ pub struct ProfileSessionStats {
     pub total_session_time: u64,
 }
 
+/// Limite che determina la scadenza della sessione
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionExpiryReason {
+    /// Nessuna attività entro l'idle timeout
+    Idle,
+    /// Raggiunta la durata massima assoluta della sessione
+    MaxDuration,
+}
+
+/// Stato della sessione corrente rispetto a idle timeout e durata massima
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionStatus {
+    /// Esiste una sessione autenticata
+    pub active: bool,
+    /// La sessione è scaduta (per inattività o durata massima)
+    pub expired: bool,
+    /// Manca meno del preavviso configurato alla scadenza
+    pub near_expiry: bool,
+    /// Idle timeout configurato (secondi, None = disabilitato)
+    pub idle_timeout_seconds: Option<u64>,
+    /// Durata massima configurata (secondi, None = illimitata)
+    pub max_session_seconds: Option<u64>,
+    /// Secondi prima della scadenza per inattività
+    pub idle_seconds_remaining: Option<u64>,
+    /// Secondi prima del raggiungimento della durata massima
+    pub absolute_seconds_remaining: Option<u64>,
+    /// Secondi prima della scadenza effettiva (il minimo dei due limiti)
+    pub seconds_remaining: Option<u64>,
+    /// Istante della scadenza effettiva
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Limite che farà (o ha fatto) scadere la sessione
+    pub expiry_reason: Option<SessionExpiryReason>,
+}
+
+impl SessionStatus {
+    fn inactive() -> Self {
+        Self {
+            active: false,
+            expired: true,
+            near_expiry: false,
+            idle_timeout_seconds: None,
+            max_session_seconds: None,
+            idle_seconds_remaining: None,
+            absolute_seconds_remaining: None,
+            seconds_remaining: None,
+            expires_at: None,
+            expiry_reason: None,
+        }
+    }
+}
+
 /// Statistiche di autenticazione
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthStats {
@@ -61,10 +114,16 @@ pub struct AuthStats {
     pub current_session_duration: Option<u64>,
 }
 
+/// Versione corrente dello schema di export profili.
+/// v1: payload = JSON del profilo; v2: payload = {"schema_version", "profile"}
+/// e `schema_version` nei metadati (incluso nella firma);
+/// v3: flag `passphrase_protected` nei metadati (incluso nella firma).
+pub const EXPORT_SCHEMA_VERSION: u32 = 3;
+
 /// Dati profilo per export/import
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportedProfile {
-    /// Versione formato export
+    /// Versione schema export (primo campo: leggibile prima di decodificare il resto)
     pub version: u32,
     /// Dati profilo crittografati
     pub encrypted_data: Vec<u8>,
@@ -91,6 +150,192 @@ pub struct ExportMetadata {
     pub has_avatar: bool,
     /// Numero credenziali
     pub credentials_count: usize,
+    /// Versione schema (assente negli export v1, ricostruita a 1 in lettura)
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Payload cifrato con passphrase di export (Argon2id) invece che con la password del profilo
+    #[serde(default)]
+    pub passphrase_protected: bool,
+}
+
+/// Esito di un export su file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportSummary {
+    pub file_path: String,
+    pub passphrase_protected: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Envelope degli schemi precedenti (layout bincode posizionale, metadati `M`)
+#[derive(Debug, Clone, Deserialize)]
+struct LegacyExportedProfile<M> {
+    version: u32,
+    encrypted_data: Vec<u8>,
+    export_metadata: M,
+    signature: String,
+}
+
+/// Metadati v1: senza `schema_version`
+#[derive(Debug, Clone, Deserialize)]
+struct ExportMetadataV1 {
+    profile_name: String,
+    exported_at: DateTime<Utc>,
+    app_version: String,
+    original_size: usize,
+    integrity_hash: String,
+    has_avatar: bool,
+    credentials_count: usize,
+}
+
+/// Metadati v2: con `schema_version`, senza `passphrase_protected`
+#[derive(Debug, Clone, Deserialize)]
+struct ExportMetadataV2 {
+    profile_name: String,
+    exported_at: DateTime<Utc>,
+    app_version: String,
+    original_size: usize,
+    integrity_hash: String,
+    has_avatar: bool,
+    credentials_count: usize,
+    schema_version: u32,
+}
+
+impl From<ExportMetadataV1> for ExportMetadata {
+    fn from(m: ExportMetadataV1) -> Self {
+        ExportMetadata {
+            profile_name: m.profile_name,
+            exported_at: m.exported_at,
+            app_version: m.app_version,
+            original_size: m.original_size,
+            integrity_hash: m.integrity_hash,
+            has_avatar: m.has_avatar,
+            credentials_count: m.credentials_count,
+            schema_version: 1,
+            passphrase_protected: false,
+        }
+    }
+}
+
+impl From<ExportMetadataV2> for ExportMetadata {
+    fn from(m: ExportMetadataV2) -> Self {
+        ExportMetadata {
+            profile_name: m.profile_name,
+            exported_at: m.exported_at,
+            app_version: m.app_version,
+            original_size: m.original_size,
+            integrity_hash: m.integrity_hash,
+            has_avatar: m.has_avatar,
+            credentials_count: m.credentials_count,
+            schema_version: m.schema_version,
+            passphrase_protected: false,
+        }
+    }
+}
+
+impl<M: Into<ExportMetadata>> From<LegacyExportedProfile<M>> for ExportedProfile {
+    fn from(legacy: LegacyExportedProfile<M>) -> Self {
+        ExportedProfile {
+            version: legacy.version,
+            encrypted_data: legacy.encrypted_data,
+            export_metadata: legacy.export_metadata.into(),
+            signature: legacy.signature,
+        }
+    }
+}
+
+/// Esito della validazione di un file export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportValidation {
+    pub metadata: ExportMetadata,
+    /// Versione schema del file
+    pub schema_version: u32,
+    /// Versione schema supportata da questa build
+    pub current_schema_version: u32,
+    /// Importabile da questa build
+    pub compatible: bool,
+    /// Motivo dell'incompatibilità (None se compatibile)
+    pub incompatibility: Option<String>,
+    /// Verrà aggiornato allo schema corrente durante l'import
+    pub requires_migration: bool,
+    /// L'import richiede la passphrase di export
+    pub passphrase_protected: bool,
+    /// Esito della verifica passphrase (None se non fornita o non necessaria)
+    pub passphrase_verified: Option<bool>,
+}
+
+/// Decodifica un file export di qualsiasi schema supportato.
+/// La versione è letta dai primi 4 byte (u32 LE di bincode) prima di decodificare
+/// il resto, così un export più recente dà un errore chiaro invece di "file corrotto".
+fn decode_export_file(export_data: &[u8]) -> ProfileResult<ExportedProfile> {
+    let version_bytes: [u8; 4] = export_data.get(..4)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| ProfileError::DataFormatError("File export troppo corto".to_string()))?;
+    let version = u32::from_le_bytes(version_bytes);
+    
+    if version > EXPORT_SCHEMA_VERSION {
+        return Err(ProfileError::ExportFromNewerVersion { found: version, supported: EXPORT_SCHEMA_VERSION });
+    }
+    
+    match version {
+        1 => bincode::deserialize::<LegacyExportedProfile<ExportMetadataV1>>(export_data)
+            .map(ExportedProfile::from)
+            .map_err(|e| ProfileError::DataFormatError(format!("File export v1 corrotto: {}", e))),
+        2 => bincode::deserialize::<LegacyExportedProfile<ExportMetadataV2>>(export_data)
+            .map(ExportedProfile::from)
+            .map_err(|e| ProfileError::DataFormatError(format!("File export v2 corrotto: {}", e))),
+        3 => bincode::deserialize::<ExportedProfile>(export_data)
+            .map_err(|e| ProfileError::DataFormatError(format!("File export corrotto: {}", e))),
+        other => Err(ProfileError::DataFormatError(format!("Versione formato export non valida: {}", other))),
+    }
+}
+
+/// Major della versione applicazione ("1.0.8" → 1); None se non leggibile
+fn app_major_version(version: &str) -> Option<u64> {
+    version.trim().trim_start_matches('v').split('.').next()?.parse().ok()
+}
+
+/// Verifica che l'export sia importabile da questa build: schema supportato e coerente con
+/// i metadati firmati, creato da una versione dell'app con major non successiva
+fn check_export_compatibility(exported: &ExportedProfile) -> Result<(), String> {
+    if exported.version == 0 || exported.version > EXPORT_SCHEMA_VERSION {
+        return Err(format!("Schema export v{} non supportato (supportato fino a v{})",
+            exported.version, EXPORT_SCHEMA_VERSION));
+    }
+    let metadata = &exported.export_metadata;
+    if metadata.schema_version != exported.version {
+        return Err(format!("Schema dei metadati (v{}) diverso da quello del file (v{})",
+            metadata.schema_version, exported.version));
+    }
+    let current = env!("CARGO_PKG_VERSION");
+    match (app_major_version(&metadata.app_version), app_major_version(current)) {
+        (Some(export_major), Some(current_major)) if export_major > current_major => Err(format!(
+            "Export creato con GameStringer {}, più recente di questa versione ({})",
+            metadata.app_version, current)),
+        (None, _) => Err(format!("Versione applicazione dell'export non valida: '{}'", metadata.app_version)),
+        _ => Ok(()),
+    }
+}
+
+/// Catena di migrazione del payload decrittografato: v1 → v2 → … → corrente
+fn migrate_export_payload(mut payload: serde_json::Value, from_version: u32) -> ProfileResult<serde_json::Value> {
+    let mut version = from_version;
+    while version < EXPORT_SCHEMA_VERSION {
+        payload = match version {
+            // v1 → v2: il profilo viene racchiuso nell'envelope versionato
+            1 => serde_json::json!({ "schema_version": 2, "profile": payload }),
+            // v2 → v3: cambia solo l'envelope (flag passphrase), payload invariato
+            2 => {
+                payload["schema_version"] = serde_json::json!(3);
+                payload
+            }
+            other => return Err(ProfileError::DataFormatError(format!(
+                "Nessuna migrazione disponibile dallo schema v{}", other
+            ))),
+        };
+        version += 1;
+        println!("[PROFILE MANAGER] 🔄 Payload export migrato a schema v{}", version);
+    }
+    Ok(payload)
 }
 
 /// Risultato migrazione credenziali legacy
@@ -213,11 +458,12 @@ pub struct ProfileManager {
 impl ProfileManager {
     /// Crea nuovo ProfileManager
     pub fn new(storage: ProfileStorage) -> Self {
+        let validator = Self::load_validator(&storage);
         Self {
             current_profile: None,
             storage,
             encryption: ProfileEncryption::new(),
-            validator: ProfileValidator::with_default_config(),
+            validator,
             session_stats: None,
             profile_cache: HashMap::new(),
             cache_last_refresh: None,
@@ -229,11 +475,12 @@ impl ProfileManager {
     /// Crea ProfileManager con configurazione personalizzata
     #[allow(dead_code)] // API pubblica per configurazione avanzata
     pub fn with_config(storage: ProfileStorage, cache_duration_seconds: u64) -> Self {
+        let validator = Self::load_validator(&storage);
         Self {
             current_profile: None,
             storage,
             encryption: ProfileEncryption::new(),
-            validator: ProfileValidator::with_default_config(),
+            validator,
             session_stats: None,
             profile_cache: HashMap::new(),
             cache_last_refresh: None,
@@ -242,6 +489,21 @@ impl ProfileManager {
         }
     }
 
+    /// Validatore con la configurazione salvata dell'installazione (default se assente o illeggibile)
+    fn load_validator(storage: &ProfileStorage) -> ProfileValidator {
+        match storage.load_validation_config() {
+            Ok(Some(config)) => {
+                println!("[PROFILE MANAGER] 📋 Configurazione validazione caricata");
+                ProfileValidator::new(config)
+            }
+            Ok(None) => ProfileValidator::with_default_config(),
+            Err(e) => {
+                println!("[PROFILE MANAGER] ⚠️ Configurazione validazione illeggibile, uso default: {}", e);
+                ProfileValidator::with_default_config()
+            }
+        }
+    }
+
     /// Lista tutti i profili disponibili
     pub async fn list_profiles(&self) -> ProfileResult<Vec<ProfileInfo>> {
         // Controlla cache
@@ -593,13 +855,14 @@ impl ProfileManager {
 
     // finalize_session_stats rimosso - non utilizzato
 
-    /// Autentica un profilo con nome e password
-    pub async fn authenticate_profile(&mut self, name: &str, password: &str) -> ProfileResult<UserProfile> {
-        // Trova profilo per nome
+    /// Autentica un profilo con ID e password.
+    /// Il nome non identifica il profilo: la configurazione può ammettere nomi duplicati
+    pub async fn authenticate_profile(&mut self, profile_id: &str, password: &str) -> ProfileResult<UserProfile> {
         let profiles = self.list_profiles().await?;
         let profile_info = profiles.iter()
-            .find(|p| p.name.to_lowercase() == name.to_lowercase())
-            .ok_or_else(|| ProfileError::ProfileNotFound(name.to_string()))?;
+            .find(|p| p.id == profile_id)
+            .ok_or_else(|| ProfileError::ProfileNotFound(profile_id.to_string()))?;
+        let name = profile_info.name.as_str();
 
         // Verifica rate limiting
         match self.rate_limiter.check_rate_limit(&profile_info.id) {
@@ -630,6 +893,11 @@ impl ProfileManager {
                 // Registra il tentativo riuscito nel rate limiter
                 self.rate_limiter.register_successful_attempt(&profile_info.id);
 
+                // Azzera i tentativi falliti legacy nell'indice
+                if let Err(e) = self.storage.clear_failed_attempts_count(&profile_info.id).await {
+                    println!("[PROFILE MANAGER] ⚠️ Impossibile azzerare i tentativi falliti di '{}': {}", name, e);
+                }
+
                 // Aggiorna il timestamp di ultimo accesso
                 profile.update_last_access();
                 
@@ -673,19 +941,8 @@ impl ProfileManager {
         }
     }
 
-    /// Autentica un profilo tramite ID
-    #[allow(dead_code)] // API per autenticazione tramite ID
-    pub async fn authenticate_profile_by_id(&mut self, profile_id: &str, password: &str) -> ProfileResult<UserProfile> {
-        // Verifica se profilo esiste
-        let profile_info = self.get_profile_info(profile_id).await?
-            .ok_or_else(|| ProfileError::ProfileNotFound(profile_id.to_string()))?;
-
-        // Usa il metodo principale con il nome
-        self.authenticate_profile(&profile_info.name, password).await
-    }
-
     /// Cambia profilo attivo (con logout del precedente)
-    pub async fn switch_profile(&mut self, name: &str, password: &str) -> ProfileResult<UserProfile> {
+    pub async fn switch_profile(&mut self, profile_id: &str, password: &str) -> ProfileResult<UserProfile> {
         // Effettua logout del profilo corrente se presente
         if self.current_profile.is_some() {
             let old_profile_id = self.current_profile_id().map(|s| s.to_string());
@@ -697,7 +954,7 @@ impl ProfileManager {
         }
 
         // Autentica nuovo profilo
-        let new_profile = self.authenticate_profile(name, password).await?;
+        let new_profile = self.authenticate_profile(profile_id, password).await?;
         
         println!("[PROFILE MANAGER] 🔄 Cambio profilo completato: {} -> {}", 
                  "precedente", new_profile.name);
@@ -705,15 +962,6 @@ impl ProfileManager {
         Ok(new_profile)
     }
 
-    /// Cambia profilo tramite ID
-    #[allow(dead_code)] // API per cambio profilo tramite ID
-    pub async fn switch_profile_by_id(&mut self, profile_id: &str, password: &str) -> ProfileResult<UserProfile> {
-        let profile_info = self.get_profile_info(profile_id).await?
-            .ok_or_else(|| ProfileError::ProfileNotFound(profile_id.to_string()))?;
-        
-        self.switch_profile(&profile_info.name, password).await
-    }
-
     /// Verifica se un profilo può essere autenticato senza effettuare login
     pub async fn can_authenticate(&self, name: &str) -> ProfileResult<bool> {
         let profiles = self.list_profiles().await?;
@@ -735,6 +983,50 @@ impl ProfileManager {
         Ok(profile_info.failed_attempts)
     }
 
+    /// Ottiene lo stato di blocco di un profilo (tempo di attesa e tentativi rimasti)
+    pub async fn get_lockout_status(&self, name: &str) -> ProfileResult<LockoutStatus> {
+        let profiles = self.list_profiles().await?;
+        let profile_info = profiles.iter()
+            .find(|p| p.name.to_lowercase() == name.to_lowercase())
+            .ok_or_else(|| ProfileError::ProfileNotFound(name.to_string()))?;
+
+        let mut status = self.rate_limiter.get_lockout_status(&profile_info.id);
+
+        // Il blocco legacy non scade da solo: serve unlock_profile
+        if profile_info.is_locked && !status.locked {
+            status.locked = true;
+            status.attempts_remaining = 0;
+        }
+
+        Ok(status)
+    }
+
+    /// Azzera i tentativi falliti di un profilo senza sbloccarlo.
+    /// Richiede una sessione autenticata per quel profilo: altrimenti basterebbe alternare
+    /// password sbagliate e reset per aggirare il blocco. Un blocco attivo resta in vigore.
+    pub async fn reset_failed_attempts(&mut self, name: &str) -> ProfileResult<()> {
+        let profiles = self.list_profiles().await?;
+        let profile_info = profiles.iter()
+            .find(|p| p.name.to_lowercase() == name.to_lowercase())
+            .ok_or_else(|| ProfileError::ProfileNotFound(name.to_string()))?;
+
+        if self.current_profile_id() != Some(profile_info.id.as_str()) {
+            println!("[PROFILE MANAGER] ⛔ Reset tentativi rifiutato per '{}': sessione non autenticata", name);
+            return Err(ProfileError::Unauthorized);
+        }
+
+        self.rate_limiter.clear_failed_attempts(&profile_info.id);
+
+        self.storage.clear_failed_attempts_count(&profile_info.id).await
+            .map_err(|e| ProfileError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+
+        // Invalida cache
+        self.invalidate_cache();
+
+        println!("[PROFILE MANAGER] 🔄 Tentativi falliti azzerati per '{}'", name);
+        Ok(())
+    }
+
     /// Sblocca un profilo (reset tentativi falliti)
     pub async fn unlock_profile(&mut self, name: &str) -> ProfileResult<()> {
         let profiles = self.list_profiles().await?;
@@ -764,6 +1056,78 @@ impl ProfileManager {
         }
     }
 
+    /// Calcola lo stato della sessione usando le impostazioni di sicurezza del profilo corrente
+    pub fn get_session_status(&self) -> SessionStatus {
+        self.session_status_at(Utc::now())
+    }
+
+    /// Calcola lo stato della sessione a un istante dato
+    pub(crate) fn session_status_at(&self, now: DateTime<Utc>) -> SessionStatus {
+        let (profile, stats) = match (&self.current_profile, &self.session_stats) {
+            (Some(profile), Some(stats)) => (profile, stats),
+            _ => return SessionStatus::inactive(),
+        };
+
+        let security = &profile.settings.security;
+        let idle_timeout = (security.session_timeout > 0).then(|| security.session_timeout as u64 * 60);
+        let max_session = (security.max_session_duration > 0).then(|| security.max_session_duration as u64 * 60);
+
+        let idle_deadline = idle_timeout.map(|secs| stats.last_activity + chrono::Duration::seconds(secs as i64));
+        let absolute_deadline = max_session.map(|secs| stats.session_start + chrono::Duration::seconds(secs as i64));
+        let remaining = |deadline: DateTime<Utc>| (deadline - now).num_seconds().max(0) as u64;
+
+        let (expires_at, expiry_reason) = match (idle_deadline, absolute_deadline) {
+            (Some(idle), Some(absolute)) if absolute <= idle => (Some(absolute), Some(SessionExpiryReason::MaxDuration)),
+            (Some(idle), _) => (Some(idle), Some(SessionExpiryReason::Idle)),
+            (None, Some(absolute)) => (Some(absolute), Some(SessionExpiryReason::MaxDuration)),
+            (None, None) => (None, None),
+        };
+        let seconds_remaining = expires_at.map(remaining);
+        let expired = expires_at.map_or(false, |deadline| deadline <= now);
+
+        SessionStatus {
+            active: true,
+            expired,
+            near_expiry: !expired && seconds_remaining.map_or(false, |secs| secs <= security.session_expiry_warning as u64),
+            idle_timeout_seconds: idle_timeout,
+            max_session_seconds: max_session,
+            idle_seconds_remaining: idle_deadline.map(remaining),
+            absolute_seconds_remaining: absolute_deadline.map(remaining),
+            seconds_remaining,
+            expires_at,
+            expiry_reason,
+        }
+    }
+
+    /// Registra attività utente e fa scorrere l'idle timeout.
+    /// La durata massima non viene estesa: a scadenza è richiesto un nuovo login.
+    pub fn touch_session(&mut self) -> ProfileResult<SessionStatus> {
+        if self.current_profile.is_none() {
+            return Err(ProfileError::Unauthorized);
+        }
+
+        if self.get_session_status().expired {
+            self.logout()?;
+            println!("[PROFILE MANAGER] ⏰ Sessione scaduta, richiesto nuovo accesso");
+            return Err(ProfileError::SessionExpired);
+        }
+
+        self.update_session_activity();
+        Ok(self.get_session_status())
+    }
+
+    /// Effettua logout se la sessione è scaduta secondo le impostazioni del profilo
+    pub fn expire_session_if_needed(&mut self) -> ProfileResult<Option<SessionStatus>> {
+        let status = self.get_session_status();
+        if status.active && status.expired {
+            self.logout()?;
+            println!("[PROFILE MANAGER] ⏰ Logout automatico: sessione scaduta ({:?})", status.expiry_reason);
+            Ok(Some(status))
+        } else {
+            Ok(None)
+        }
+    }
+
     /// Rinnova sessione (aggiorna attività)
     pub fn renew_session(&mut self) -> ProfileResult<()> {
         if self.current_profile.is_some() {
@@ -1087,21 +1451,28 @@ impl ProfileManager {
         })
     }
 
-    /// Esporta un profilo in formato crittografato
-    pub async fn export_profile(&self, profile_id: &str, password: &str, export_password: Option<&str>) -> ProfileResult<ExportedProfile> {
+    /// Esporta un profilo in formato crittografato.
+    /// Con `passphrase` il payload è cifrato con chiave Argon2id derivata dalla passphrase,
+    /// altrimenti con la password del profilo.
+    pub async fn export_profile(&self, profile_id: &str, password: &str, passphrase: Option<&str>) -> ProfileResult<ExportedProfile> {
         // Carica il profilo per verificare password
         let profile = self.storage.load_profile(profile_id, password).await
             .map_err(|e| ProfileError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
 
-        // Serializza il profilo completo
-        let profile_json = serde_json::to_string_pretty(&profile)
+        // Serializza il profilo completo nel payload versionato
+        let payload = serde_json::json!({ "schema_version": EXPORT_SCHEMA_VERSION, "profile": &profile });
+        let profile_json = serde_json::to_string_pretty(&payload)
             .map_err(|e| ProfileError::EncryptionError(format!("Errore serializzazione profilo: {}", e)))?;
 
-        // Usa password export o quella del profilo
-        let final_password = export_password.unwrap_or(password);
-
-        // Crittografa i dati del profilo
-        let encrypted_data = self.encryption.encrypt_profile_data(profile_json.as_bytes(), final_password)?;
+        // Crittografa i dati del profilo: passphrase di export o password del profilo
+        let passphrase = passphrase.filter(|p| !p.is_empty());
+        let encrypted_data = match passphrase {
+            Some(passphrase) => {
+                self.encryption.validate_password_strength(passphrase)?;
+                self.encryption.encrypt_with_passphrase(profile_json.as_bytes(), passphrase)?
+            }
+            None => self.encryption.encrypt_profile_data(profile_json.as_bytes(), password)?,
+        };
 
         // Calcola hash integrità
         let integrity_hash = self.calculate_data_hash(&profile_json.as_bytes());
@@ -1115,13 +1486,15 @@ impl ProfileManager {
             integrity_hash: integrity_hash.clone(),
             has_avatar: profile.avatar_path.is_some(),
             credentials_count: profile.credentials.len(),
+            schema_version: EXPORT_SCHEMA_VERSION,
+            passphrase_protected: passphrase.is_some(),
         };
 
         // Genera firma digitale
         let signature = self.generate_export_signature(&export_metadata, &encrypted_data);
 
         let exported = ExportedProfile {
-            version: 1,
+            version: EXPORT_SCHEMA_VERSION,
             encrypted_data,
             export_metadata,
             signature,
@@ -1134,8 +1507,19 @@ impl ProfileManager {
     }
 
     /// Esporta profilo in file
-    pub async fn export_profile_to_file(&self, profile_id: &str, password: &str, file_path: &str, export_password: Option<&str>) -> ProfileResult<()> {
-        let exported = self.export_profile(profile_id, password, export_password).await?;
+    pub async fn export_profile_to_file(&self, profile_id: &str, password: &str, file_path: &str, passphrase: Option<&str>) -> ProfileResult<ExportSummary> {
+        let exported = self.export_profile(profile_id, password, passphrase).await?;
+        
+        let mut warnings = Vec::new();
+        let metadata = &exported.export_metadata;
+        if !metadata.passphrase_protected && metadata.credentials_count > 0 {
+            let warning = format!(
+                "Export senza passphrase: {} credenziali store sono protette solo dalla password del profilo. Usa una passphrase prima di condividere il file.",
+                metadata.credentials_count
+            );
+            println!("[PROFILE MANAGER] ⚠️ {}", warning);
+            warnings.push(warning);
+        }
         
         // Serializza in formato binario compatto
         let export_data = bincode::serialize(&exported)
@@ -1146,18 +1530,21 @@ impl ProfileManager {
             .map_err(|e| ProfileError::IoError(e))?;
 
         println!("[PROFILE MANAGER] ✅ Profilo esportato in file: {}", file_path);
-        Ok(())
+        Ok(ExportSummary {
+            file_path: file_path.to_string(),
+            passphrase_protected: exported.export_metadata.passphrase_protected,
+            warnings,
+        })
     }
 
-    /// Importa un profilo da dati crittografati
+    /// Importa un profilo da dati crittografati.
+    /// Per export protetti da passphrase `import_password` è la passphrase di export.
     pub async fn import_profile(&mut self, exported: ExportedProfile, import_password: &str, new_name: Option<String>) -> ProfileResult<UserProfile> {
-        // Verifica versione formato
-        if exported.version != 1 {
-            return Err(ProfileError::DataFormatError(format!(
-                "Versione formato export non supportata: {}",
-                exported.version
-            )));
+        // Verifica versione formato: le versioni precedenti vengono migrate
+        if exported.version > EXPORT_SCHEMA_VERSION {
+            return Err(ProfileError::ExportFromNewerVersion { found: exported.version, supported: EXPORT_SCHEMA_VERSION });
         }
+        check_export_compatibility(&exported).map_err(ProfileError::DataFormatError)?;
 
         // Verifica firma digitale
         let expected_signature = self.generate_export_signature(&exported.export_metadata, &exported.encrypted_data);
@@ -1166,7 +1553,15 @@ impl ProfileManager {
         }
 
         // Decrittografa i dati
-        let decrypted_data = self.encryption.decrypt_profile_data(&exported.encrypted_data, import_password)?;
+        let passphrase_protected = exported.export_metadata.passphrase_protected;
+        if passphrase_protected && import_password.is_empty() {
+            return Err(ProfileError::ExportPassphraseRequired);
+        }
+        let decrypted_data = self.encryption.decrypt_profile_data(&exported.encrypted_data, import_password)
+            .map_err(|e| match e {
+                ProfileError::InvalidPassword if passphrase_protected => ProfileError::InvalidExportPassphrase,
+                other => other,
+            })?;
         
         // Verifica integrità
         let data_hash = self.calculate_data_hash(&decrypted_data);
@@ -1178,7 +1573,11 @@ impl ProfileManager {
         let profile_json = String::from_utf8(decrypted_data)
             .map_err(|e| ProfileError::CorruptedProfile(format!("Dati profilo corrotti: {}", e)))?;
         
-        let mut imported_profile: UserProfile = serde_json::from_str(&profile_json)
+        let payload: serde_json::Value = serde_json::from_str(&profile_json)
+            .map_err(|e| ProfileError::CorruptedProfile(format!("Formato profilo invalido: {}", e)))?;
+        let mut payload = migrate_export_payload(payload, exported.version)?;
+        
+        let mut imported_profile: UserProfile = serde_json::from_value(payload["profile"].take())
             .map_err(|e| ProfileError::CorruptedProfile(format!("Formato profilo invalido: {}", e)))?;
 
         // Genera nuovo ID per evitare conflitti
@@ -1189,11 +1588,8 @@ impl ProfileManager {
             imported_profile.name = name;
         }
 
-        // Verifica che il nome non esista già
-        let existing_profiles = self.list_profiles().await?;
-        if existing_profiles.iter().any(|p| p.name.to_lowercase() == imported_profile.name.to_lowercase()) {
-            return Err(ProfileError::ProfileAlreadyExists(imported_profile.name.clone()));
-        }
+        // Verifica che il nome non esista già (se richiesto dalla configurazione)
+        self.validate_unique_profile_name(&imported_profile.name).await?;
 
         // Aggiorna timestamp
         imported_profile.created_at = Utc::now();
@@ -1216,31 +1612,21 @@ impl ProfileManager {
         let export_data = tokio::fs::read(file_path).await
             .map_err(|e| ProfileError::IoError(e))?;
 
-        // Deserializza export
-        let exported: ExportedProfile = bincode::deserialize(&export_data)
-            .map_err(|e| ProfileError::DataFormatError(format!("File export corrotto: {}", e)))?;
+        // Deserializza export (qualsiasi schema supportato)
+        let exported = decode_export_file(&export_data)?;
 
         // Importa profilo
         self.import_profile(exported, import_password, new_name).await
     }
 
-    /// Valida un file export senza importarlo
-    pub async fn validate_export_file(&self, file_path: &str) -> ProfileResult<ExportMetadata> {
+    /// Valida un file export senza importarlo; se fornita, verifica anche la passphrase
+    pub async fn validate_export_file(&self, file_path: &str, passphrase: Option<&str>) -> ProfileResult<ExportValidation> {
         // Leggi file
         let export_data = tokio::fs::read(file_path).await
             .map_err(|e| ProfileError::IoError(e))?;
 
-        // Deserializza export
-        let exported: ExportedProfile = bincode::deserialize(&export_data)
-            .map_err(|e| ProfileError::DataFormatError(format!("File export corrotto: {}", e)))?;
-
-        // Verifica versione
-        if exported.version != 1 {
-            return Err(ProfileError::DataFormatError(format!(
-                "Versione formato non supportata: {}",
-                exported.version
-            )));
-        }
+        // Deserializza export: errore esplicito se lo schema è più recente
+        let exported = decode_export_file(&export_data)?;
 
         // Verifica firma
         let expected_signature = self.generate_export_signature(&exported.export_metadata, &exported.encrypted_data);
@@ -1248,8 +1634,32 @@ impl ProfileManager {
             return Err(ProfileError::CorruptedProfile("Firma digitale non valida".to_string()));
         }
 
-        println!("[PROFILE MANAGER] File export valido: {}", file_path);
-        Ok(exported.export_metadata)
+        // Verifica passphrase decifrando il payload (nessun dato viene salvato)
+        let passphrase_protected = exported.export_metadata.passphrase_protected;
+        let passphrase_verified = match passphrase.filter(|p| !p.is_empty()) {
+            Some(passphrase) if passphrase_protected => {
+                match self.encryption.decrypt_profile_data(&exported.encrypted_data, passphrase) {
+                    Ok(_) => Some(true),
+                    Err(ProfileError::InvalidPassword) => Some(false),
+                    Err(e) => return Err(e),
+                }
+            }
+            _ => None,
+        };
+
+        let incompatibility = check_export_compatibility(&exported).err();
+        println!("[PROFILE MANAGER] File export valido: {} (schema v{}, app {}{})", file_path, exported.version,
+            exported.export_metadata.app_version, if incompatibility.is_some() { ", non compatibile" } else { "" });
+        Ok(ExportValidation {
+            passphrase_protected,
+            passphrase_verified,
+            metadata: exported.export_metadata,
+            schema_version: exported.version,
+            current_schema_version: EXPORT_SCHEMA_VERSION,
+            compatible: incompatibility.is_none(),
+            incompatibility,
+            requires_migration: exported.version < EXPORT_SCHEMA_VERSION,
+        })
     }
 
     /// Crea backup automatico di un profilo
@@ -1271,6 +1681,81 @@ impl ProfileManager {
 
 
 
+    /// Pianificazione dei backup automatici
+    pub async fn get_backup_schedule(&self) -> ProfileResult<BackupSchedule> {
+        self.storage.load_backup_schedule().await
+            .map_err(|e| ProfileError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))
+    }
+
+    /// Aggiorna la pianificazione; riduce subito i backup esistenti se `keep_last` è diminuito.
+    /// La rotazione tocca i backup di tutti i profili: serve una sessione autenticata.
+    pub async fn set_backup_schedule(&self, schedule: BackupSchedule) -> ProfileResult<BackupSchedule> {
+        if !self.is_profile_active() {
+            return Err(ProfileError::Unauthorized);
+        }
+
+        let schedule = schedule.normalized();
+        self.storage.save_backup_schedule(&schedule).await
+            .map_err(|e| ProfileError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+
+        for profile in self.list_profiles().await? {
+            let _ = self.storage.rotate_scheduled_backups(&profile.id, schedule.keep_last).await;
+        }
+        println!("[PROFILE MANAGER] ✅ Pianificazione backup aggiornata: {:?}", schedule);
+        Ok(schedule)
+    }
+
+    /// Crea un backup pianificato con rotazione (impostazioni già serializzate dal chiamante)
+    pub async fn create_scheduled_backup(&self, profile_id: &str, settings_json: Option<&str>) -> ProfileResult<ScheduledBackupInfo> {
+        let schedule = self.get_backup_schedule().await?;
+        self.storage.create_scheduled_backup(profile_id, settings_json, schedule.keep_last).await
+            .map_err(|e| ProfileError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))
+    }
+
+    /// Backup pianificati di un profilo (o di tutti), dal più recente
+    /// Senza profilo indicato elenca quelli del profilo attivo. Serve la sessione del profilo,
+    /// tranne per un profilo eliminato (il ripristino richiede comunque la sua password).
+    pub async fn list_scheduled_backups(&self, profile_id: Option<&str>) -> ProfileResult<Vec<ScheduledBackupInfo>> {
+        let profile_id = match profile_id {
+            Some(id) => id,
+            None => self.current_profile_id().ok_or(ProfileError::Unauthorized)?,
+        };
+        self.require_session_if_exists(profile_id).await?;
+
+        self.storage.list_scheduled_backups(Some(profile_id)).await
+            .map_err(|e| ProfileError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))
+    }
+
+    /// Un profilo esistente è accessibile solo dalla sua sessione autenticata
+    async fn require_session_if_exists(&self, profile_id: &str) -> ProfileResult<()> {
+        let exists = self.list_profiles().await?.iter().any(|p| p.id == profile_id);
+        if exists && self.current_profile_id() != Some(profile_id) {
+            return Err(ProfileError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Ripristina un backup pianificato. Serve la sessione del profilo (se esiste ancora) e
+    /// la password del profilo salvato nel backup. Se il profilo è quello attivo viene
+    /// disconnesso, così il prossimo login carica i dati ripristinati invece di sovrascriverli.
+    pub async fn restore_scheduled_backup(&mut self, backup_id: &str, password: &str) -> ProfileResult<(ScheduledBackupInfo, Option<String>)> {
+        let backup = self.storage.scheduled_backup_info(backup_id).await
+            .map_err(|e| ProfileError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+        self.require_session_if_exists(&backup.profile_id).await?;
+
+        let (info, settings_json) = self.storage.restore_scheduled_backup(backup_id, password).await
+            .map_err(|e| match e {
+                StorageError::PermissionDenied(_) => ProfileError::InvalidCredentials,
+                e => ProfileError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())),
+            })?;
+
+        self.invalidate_cache();
+        if self.current_profile_id() == Some(info.profile_id.as_str()) {
+            self.logout()?;
+        }
+        Ok((info, settings_json))
+    }
+
     /// Calcola hash integrità per i dati
     fn calculate_data_hash(&self, data: &[u8]) -> String {
         use sha2::{Sha256, Digest};
@@ -1289,6 +1774,14 @@ impl ProfileManager {
         hasher.update(&metadata.exported_at.to_rfc3339().as_bytes());
         hasher.update(&metadata.app_version.as_bytes());
         hasher.update(&metadata.integrity_hash.as_bytes());
+        // Dalla v2 la versione schema è firmata (gli export v1 non la includono)
+        if metadata.schema_version >= 2 {
+            hasher.update(&metadata.schema_version.to_le_bytes());
+        }
+        // Dalla v3 anche la modalità di cifratura è firmata
+        if metadata.schema_version >= 3 {
+            hasher.update(&[metadata.passphrase_protected as u8]);
+        }
         hasher.update(encrypted_data);
         
         format!("{:x}", hasher.finalize())
@@ -1311,6 +1804,16 @@ impl ProfileManager {
         self.validator.validate_password(password)
     }
 
+    /// Stima entropia e tempo di crack della password
+    pub fn estimate_password_strength(&self, password: &str) -> PasswordStrengthEstimate {
+        self.validator.estimate_password_strength(password)
+    }
+
+    /// Genera passphrase conformi alla policy di validazione
+    pub fn generate_passphrases(&self, count: usize, word_count: usize) -> Vec<String> {
+        self.validator.generate_passphrases(count, word_count)
+    }
+
     /// Valida che un nome profilo sia unico
     pub async fn validate_unique_profile_name(&self, name: &str) -> ProfileResult<()> {
         let profiles = self.list_profiles().await?;
@@ -1323,14 +1826,31 @@ impl ProfileManager {
         self.validator.sanitize_input(input)
     }
 
+    /// Sanitizza input generico riportando i caratteri rimossi
+    pub fn sanitize_input_detailed(&self, input: &str) -> SanitizedInput {
+        self.validator.sanitize_input_detailed(input)
+    }
+
     /// Ottiene configurazione validazione
     pub fn get_validation_config(&self) -> &ValidationConfig {
         &self.validator.config
     }
 
-    /// Aggiorna configurazione validazione
-    pub fn update_validation_config(&mut self, config: ValidationConfig) {
+    /// Aggiorna e salva la configurazione validazione dell'installazione.
+    /// Vale per tutti i profili: serve una sessione autenticata
+    pub async fn update_validation_config(&mut self, config: ValidationConfig) -> ProfileResult<()> {
+        if !self.is_profile_active() {
+            return Err(ProfileError::Unauthorized);
+        }
+        config.validate()?;
+        let config = config.normalized();
+
+        self.storage.save_validation_config(&config).await
+            .map_err(|e| ProfileError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))?;
+
         self.validator = ProfileValidator::new(config);
+        println!("[PROFILE MANAGER] ✅ Configurazione validazione aggiornata");
+        Ok(())
     }
 
     /// Salva credenziale per il profilo attivo
@@ -1691,6 +2211,17 @@ impl ProfileManager {
         }
     }
 
+    /// Trova file profilo e avatar su disco non referenziati dall'indice
+    pub async fn find_orphaned_storage_files(&self) -> ProfileResult<Vec<crate::profiles::cleanup::OrphanedFile>> {
+        self.storage.find_orphaned_files().await
+            .map_err(|e| ProfileError::IoError(std::io::Error::new(std::io::ErrorKind::Other, e.to_string())))
+    }
+
+    /// Directory di quarantena per i file orfani
+    pub fn orphan_quarantine_dir(&self) -> std::path::PathBuf {
+        self.storage.orphan_quarantine_dir().to_path_buf()
+    }
+
     /// Ottiene il rate limiter
     #[allow(dead_code)] // API per accesso rate limiter
     pub fn rate_limiter(&self) -> &RateLimiter {
@@ -1718,12 +2249,7 @@ impl ProfileManager {
     /// Aggiunge un metodo di login che è un alias per authenticate_profile
     #[allow(dead_code)] // API alias per autenticazione
     pub async fn login(&mut self, profile_id: &str, password: &str) -> ProfileResult<UserProfile> {
-        // Trova il profilo per ID
-        let profile_info = self.get_profile_info(profile_id).await?
-            .ok_or_else(|| ProfileError::ProfileNotFound(profile_id.to_string()))?;
-        
-        // Usa authenticate_profile con il nome del profilo
-        self.authenticate_profile(&profile_info.name, password).await
+        self.authenticate_profile(profile_id, password).await
     }
 }
 
@@ -8,6 +8,8 @@ pub mod settings_manager;
 pub mod validation;
 pub mod rate_limiter;
 pub mod secure_memory;
+pub mod cleanup;
+pub mod password_strength;
 
 #[cfg(test)]
 mod tests;
@@ -17,6 +19,8 @@ mod settings_tests;
 mod integration_tests;
 #[cfg(test)]
 mod end_to_end_tests;
+#[cfg(test)]
+mod test_support;
 
 pub use models::*;
 pub use storage::*;
@@ -28,4 +32,6 @@ pub use settings_manager::*;
 pub use validation::*;
 pub use rate_limiter::*;
 pub use secure_memory::*;
+pub use cleanup::*;
+pub use password_strength::*;
 
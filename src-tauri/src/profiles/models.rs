@@ -53,6 +53,14 @@ fn default_settings_version() -> u32 {
     1
 }
 
+fn default_max_session_duration() -> u32 {
+    720 // 12 ore
+}
+
+fn default_session_expiry_warning() -> u32 {
+    120 // 2 minuti
+}
+
 /// Richiesta per creare un nuovo profilo
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateProfileRequest {
@@ -143,8 +151,14 @@ pub enum LibrarySort {
 /// Impostazioni sicurezza
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecuritySettings {
-    /// Timeout sessione (minuti)
+    /// Timeout sessione per inattività (minuti, 0 = disabilitato)
     pub session_timeout: u32,
+    /// Durata massima assoluta della sessione (minuti, 0 = illimitata)
+    #[serde(default = "default_max_session_duration")]
+    pub max_session_duration: u32,
+    /// Preavviso prima della scadenza sessione (secondi)
+    #[serde(default = "default_session_expiry_warning")]
+    pub session_expiry_warning: u32,
     /// Richiedi password per operazioni sensibili
     pub require_password_for_sensitive: bool,
     /// Blocco automatico dopo tentativi falliti
@@ -243,6 +257,8 @@ impl Default for SecuritySettings {
     fn default() -> Self {
         Self {
             session_timeout: 60, // 1 ora
+            max_session_duration: default_max_session_duration(),
+            session_expiry_warning: default_session_expiry_warning(),
             require_password_for_sensitive: true,
             auto_lock_failed_attempts: 5,
             lock_duration: 15, // 15 minuti
@@ -326,7 +342,7 @@ impl CreateProfileRequest {
             return Err(ProfileError::InvalidProfileName("Nome profilo vuoto".to_string()));
         }
         
-        if self.name.len() > 50 {
+        if self.name.chars().count() > crate::profiles::validation::PROFILE_NAME_HARD_LIMIT {
             return Err(ProfileError::InvalidProfileName("Nome profilo troppo lungo (max 50 caratteri)".to_string()));
         }
         
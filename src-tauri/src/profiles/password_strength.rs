@@ -0,0 +1,570 @@
+//! Stima della forza password in stile zxcvbn
+//!
+//! La password viene scomposta in pattern prevedibili (password comuni, parole del
+//! dizionario anche con sostituzioni l33t, sequenze, ripetizioni, righe della tastiera,
+//! anni) e i caratteri restanti sono stimati a forza bruta. La somma dei bit dà
+//! l'entropia stimata e il tempo di crack offline. Nessuna regex né allocazione pesante:
+//! il costo è lineare nella lunghezza e può essere chiamata a ogni battitura.
+
+use crate::profiles::validation::ValidationConfig;
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Tentativi al secondo di un attacco offline su hash veloce (GPU)
+pub const OFFLINE_GUESSES_PER_SECOND: f64 = 1e10;
+
+/// Lunghezza consigliata sotto la quale suggeriamo di allungare la password
+pub const RECOMMENDED_PASSWORD_LENGTH: usize = 12;
+
+/// Numero di parole predefinito per le passphrase generate
+pub const DEFAULT_PASSPHRASE_WORDS: usize = 4;
+
+/// Lunghezza minima di un pattern riconosciuto
+const MIN_PATTERN_LENGTH: usize = 3;
+
+/// Password più diffuse, in ordine di frequenza
+const COMMON_PASSWORDS: &[&str] = &[
+    "123456", "password", "12345678", "qwerty", "123456789", "12345", "1234", "111111",
+    "1234567", "dragon", "123123", "baseball", "abc123", "football", "monkey", "letmein",
+    "696969", "shadow", "master", "666666", "qwertyuiop", "123321", "mustang", "1234567890",
+    "michael", "654321", "superman", "1qaz2wsx", "7777777", "121212", "000000", "qazwsx",
+    "123qwe", "killer", "trustno1", "jordan", "jennifer", "zxcvbnm", "asdfgh", "hunter",
+    "buster", "soccer", "harley", "batman", "andrew", "tigger", "sunshine", "iloveyou",
+    "2000", "charlie", "robert", "thomas", "hockey", "ranger", "daniel", "starwars",
+    "klaster", "112233", "george", "computer", "michelle", "jessica", "pepper", "1111",
+    "zxcvbn", "555555", "11111111", "131313", "freedom", "777777", "pass", "maggie",
+    "159753", "aaaaaa", "ginger", "princess", "joshua", "cheese", "amanda", "summer",
+    "love", "ashley", "nicole", "chelsea", "biteme", "matthew", "access", "yankees",
+    "987654321", "dallas", "austin", "thunder", "taylor", "matrix", "admin", "welcome",
+    "login", "passw0rd", "starwars", "whatever", "qwerty123", "password1", "ciao", "ciaociao",
+    "juventus", "francesca", "alessandro", "giuseppe", "napoli", "andrea", "martina", "roma",
+    "lorenzo", "giovanni", "antonio", "milano", "forzanapoli", "forzajuve", "amore", "tesoro",
+];
+
+/// Parole comuni (inglese e italiano) usate spesso come base delle password
+const DICTIONARY_WORDS: &[&str] = &[
+    "love", "game", "gamer", "player", "steam", "epic", "games", "secret", "hello", "world",
+    "money", "magic", "happy", "angel", "devil", "power", "super", "star", "dark", "light",
+    "blue", "black", "white", "green", "red", "orange", "silver", "golden", "summer", "winter",
+    "spring", "autumn", "house", "home", "family", "friend", "baby", "honey", "sweet", "cookie",
+    "chocolate", "coffee", "pizza", "music", "guitar", "dance", "anime", "ninja", "pirate", "zombie",
+    "wizard", "knight", "warrior", "hunter", "sniper", "gaming", "pokemon", "minecraft", "fortnite", "mario",
+    "zelda", "sonic", "halo", "doom", "cat", "dog", "tiger", "lion", "wolf", "eagle",
+    "shark", "dragon", "phoenix", "thunder", "storm", "fire", "water", "earth", "wind", "ice",
+    "amore", "casa", "gatto", "cane", "sole", "luna", "mare", "cielo", "stella", "fiore",
+    "vita", "tempo", "mondo", "ciao", "bella", "bello", "dolce", "cuore", "sogno", "gioco",
+    "giochi", "calcio", "scuola", "lavoro", "estate", "inverno", "nero", "bianco", "rosso", "verde",
+    "azzurro", "oro", "famiglia", "amico", "amica", "principessa", "tesoro", "fragola", "mamma", "papa",
+    "qwerty", "admin", "user", "guest", "test", "root", "default", "changeme", "secure", "private",
+];
+
+/// Righe della tastiera (QWERTY, QWERTZ, AZERTY e simboli)
+const KEYBOARD_ROWS: &[&str] = &[
+    "`1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./",
+    "~!@#$%^&*()_+", "qwertzuiop", "yxcvbnm", "azertyuiop", "qsdfghjklm", "wxcvbn",
+];
+
+/// Parole per la generazione di passphrase (256 voci = 8 bit per parola)
+const PASSPHRASE_WORDS: &[&str] = &[
+    "abete", "acero", "acqua", "agave", "aglio", "airone", "albero", "alce", "alga", "alloro",
+    "alpaca", "ambra", "ancora", "anfora", "anguilla", "anice", "antenna", "ape", "aquila", "arancia",
+    "arco", "argento", "armadio", "arpa", "asino", "astro", "atlante", "aurora", "avena", "balena",
+    "bambu", "banana", "barca", "basilico", "battello", "becco", "biscotto", "bisonte", "borraccia", "bosco",
+    "bottone", "braccio", "brezza", "brina", "bussola", "cactus", "caffe", "calamaro", "camino", "campana",
+    "canoa", "canguro", "cannella", "capanna", "carota", "carrozza", "castagna", "castoro", "cavallo", "cedro",
+    "ciliegia", "cipresso", "cirro", "cobalto", "coccinella", "cometa", "condor", "conchiglia", "coniglio", "corallo",
+    "corda", "corvo", "cratere", "cristallo", "cuscino", "delfino", "deserto", "diamante", "duna", "edera",
+    "elefante", "elica", "falco", "faro", "farfalla", "felce", "fenice", "ferro", "fiocco", "fiordo",
+    "foca", "foglia", "fontana", "foresta", "formica", "fragola", "frassino", "fulmine", "fungo", "gabbiano",
+    "galassia", "gambero", "gazza", "gelso", "geyser", "ghiaccio", "ghianda", "giacca", "giaguaro", "giglio",
+    "ginepro", "giraffa", "girasole", "granchio", "grillo", "grotta", "gufo", "iceberg", "icona", "isola",
+    "lago", "lampada", "lampone", "lanterna", "larice", "lavanda", "lemure", "leone", "libellula", "limone",
+    "lince", "lontra", "lucciola", "lumaca", "magnolia", "mandorla", "mango", "mantello", "marmotta", "medusa",
+    "melone", "menta", "meteora", "miele", "mirtillo", "montagna", "muschio", "nebbia", "nespola", "nido",
+    "nocciola", "noce", "nuvola", "oasi", "oceano", "olivo", "ombrello", "onda", "orca", "orchidea",
+    "orologio", "orso", "ortica", "ostrica", "pagaia", "palma", "panda", "papavero", "pappagallo", "pavone",
+    "pepe", "pera", "pesca", "pettirosso", "pianeta", "pigna", "pinguino", "pino", "pioggia", "pistacchio",
+    "platano", "polpo", "ponte", "prato", "prugna", "puma", "quarzo", "quercia", "radice", "ragno",
+    "rana", "ribes", "riccio", "rondine", "rosmarino", "rubino", "ruscello", "salvia", "scoglio", "scoiattolo",
+    "sentiero", "sequoia", "sirena", "slitta", "smeraldo", "sorgente", "spiga", "spugna", "stagno", "stambecco",
+    "stivale", "struzzo", "susina", "tamburo", "tartaruga", "tasso", "tavolo", "teiera", "tempesta", "tigre",
+    "timo", "topazio", "torre", "tramonto", "trifoglio", "tromba", "trota", "tucano", "tulipano", "uva",
+    "vaniglia", "vela", "ventaglio", "vento", "vetro", "viola", "vipera", "vulcano", "zaffiro", "zafferano",
+    "zanzara", "zattera", "zebra", "zenzero", "zucca", "zucchero", "abisso", "acciaio", "aliante", "ametista",
+    "anatra", "arnia", "bacca", "baobab", "betulla", "cammello",
+];
+
+/// Tipo di pattern riconosciuto nella password
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PatternKind {
+    CommonPassword,
+    Dictionary,
+    Keyboard,
+    Sequence,
+    Repeat,
+    Year,
+}
+
+/// Pattern trovato a una certa posizione
+#[derive(Debug, Clone, Copy)]
+struct PatternMatch {
+    kind: PatternKind,
+    len: usize,
+    bits: f64,
+    l33t: bool,
+    capitalized_only: bool,
+}
+
+/// Stima della forza password per il feedback in tempo reale
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordStrengthEstimate {
+    /// Entropia stimata (log2 dei tentativi necessari)
+    pub entropy_bits: f64,
+    /// Tempo stimato per un attacco offline a `OFFLINE_GUESSES_PER_SECOND`
+    pub crack_time_seconds: f64,
+    /// Tempo di crack in forma leggibile (es. "3 giorni", "secoli")
+    pub crack_time_display: String,
+    /// Suggerimenti specifici per migliorare la password
+    pub hints: Vec<String>,
+}
+
+/// Stima entropia, tempo di crack e suggerimenti per una password.
+/// `min_length` è la lunghezza minima configurata: la lunghezza consigliata non scende sotto.
+pub fn estimate_password_strength(password: &str, min_length: usize) -> PasswordStrengthEstimate {
+    let chars: Vec<char> = password.chars().collect();
+    if chars.is_empty() {
+        return PasswordStrengthEstimate {
+            entropy_bits: 0.0,
+            crack_time_seconds: 0.0,
+            crack_time_display: format_crack_time(0.0),
+            hints: vec!["Inserisci una password".to_string()],
+        };
+    }
+
+    let lower: Vec<char> = chars.iter().map(|c| c.to_ascii_lowercase()).collect();
+    let unleet: Vec<char> = lower.iter().map(|&c| unleet_char(c)).collect();
+    let bruteforce_bits = (char_pool_size(&chars) as f64).log2();
+
+    let mut entropy_bits = 0.0;
+    let mut found: Vec<PatternMatch> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match best_match_at(&chars, &lower, &unleet, i) {
+            Some(m) => {
+                entropy_bits += m.bits;
+                i += m.len;
+                found.push(m);
+            }
+            None => {
+                entropy_bits += bruteforce_bits;
+                i += 1;
+            }
+        }
+    }
+
+    let crack_time_seconds = 2f64.powf(entropy_bits.min(1000.0)) / OFFLINE_GUESSES_PER_SECOND;
+    let hints = build_hints(&chars, &found, entropy_bits, min_length);
+
+    PasswordStrengthEstimate {
+        entropy_bits: (entropy_bits * 10.0).round() / 10.0,
+        crack_time_seconds,
+        crack_time_display: format_crack_time(crack_time_seconds),
+        hints,
+    }
+}
+
+/// Genera una passphrase di parole casuali che rispetta la policy configurata:
+/// parole con iniziale maiuscola, separatore preso dai caratteri speciali consentiti
+/// e un numero finale. Aggiunge parole finché non raggiunge la lunghezza minima.
+pub fn generate_passphrase(config: &ValidationConfig, word_count: usize) -> String {
+    let mut rng = OsRng;
+    let separators: Vec<char> = config.allowed_special_chars.chars().collect();
+    let separator = separators.choose(&mut rng).copied().unwrap_or('-');
+    let number = rng.gen_range(10..100).to_string();
+
+    let mut words: Vec<String> = Vec::new();
+    loop {
+        let word = PASSPHRASE_WORDS.choose(&mut rng).copied().unwrap_or("vento");
+        words.push(capitalize(word));
+
+        let mut passphrase = words.join(&separator.to_string());
+        passphrase.push(separator);
+        passphrase.push_str(&number);
+
+        let length = passphrase.chars().count();
+        let long_enough = words.len() >= word_count.max(1) && length >= config.min_password_length;
+        if long_enough || length >= config.max_password_length {
+            return passphrase.chars().take(config.max_password_length).collect();
+        }
+    }
+}
+
+/// Cerca il pattern più lungo (a parità, il più prevedibile) che inizia in `i`
+fn best_match_at(chars: &[char], lower: &[char], unleet: &[char], i: usize) -> Option<PatternMatch> {
+    let candidates = [
+        dictionary_match(chars, lower, unleet, i),
+        repeat_match(chars, i),
+        sequence_match(lower, i),
+        keyboard_match(lower, i),
+        year_match(chars, i),
+    ];
+
+    candidates
+        .iter()
+        .flatten()
+        .copied()
+        .filter(|m| m.len >= MIN_PATTERN_LENGTH)
+        .fold(None, |best: Option<PatternMatch>, m| match best {
+            Some(b) if b.len > m.len || (b.len == m.len && b.bits <= m.bits) => Some(b),
+            _ => Some(m),
+        })
+}
+
+/// Password comuni e parole del dizionario, anche con sostituzioni l33t.
+/// Include le parole delle passphrase: un attaccante conosce le wordlist.
+fn dictionary_match(chars: &[char], lower: &[char], unleet: &[char], i: usize) -> Option<PatternMatch> {
+    let mut best: Option<PatternMatch> = None;
+    let words = COMMON_PASSWORDS.iter().chain(DICTIONARY_WORDS.iter()).chain(PASSPHRASE_WORDS.iter());
+
+    for (rank, word) in words.enumerate() {
+        let len = word.len();
+        if len < MIN_PATTERN_LENGTH || i + len > lower.len() || best.is_some_and(|b| b.len >= len) {
+            continue;
+        }
+
+        let direct = lower[i..i + len].iter().copied().eq(word.chars());
+        let l33t = !direct && unleet[i..i + len].iter().copied().eq(word.chars());
+        if !direct && !l33t {
+            continue;
+        }
+
+        let token = &chars[i..i + len];
+        let (case_bits, capitalized_only) = uppercase_variations(token);
+        let kind = if rank < COMMON_PASSWORDS.len() { PatternKind::CommonPassword } else { PatternKind::Dictionary };
+
+        best = Some(PatternMatch {
+            kind,
+            len,
+            bits: ((rank + 1) as f64).log2() + case_bits + if l33t { 1.0 } else { 0.0 },
+            l33t,
+            capitalized_only,
+        });
+    }
+
+    best
+}
+
+/// Stesso carattere ripetuto (es. "aaaa", "1111")
+fn repeat_match(chars: &[char], i: usize) -> Option<PatternMatch> {
+    let len = chars[i..].iter().take_while(|&&c| c == chars[i]).count();
+    let pool = char_pool_size(&chars[i..i + 1]) as f64;
+    Some(simple_match(PatternKind::Repeat, len, (pool * len as f64).log2()))
+}
+
+/// Sequenze crescenti o decrescenti (es. "abcd", "4321")
+fn sequence_match(lower: &[char], i: usize) -> Option<PatternMatch> {
+    if i + 1 >= lower.len() {
+        return None;
+    }
+
+    let delta = lower[i + 1] as i64 - lower[i] as i64;
+    if delta.abs() != 1 {
+        return None;
+    }
+
+    let mut len = 2;
+    while i + len < lower.len() && lower[i + len] as i64 - lower[i + len - 1] as i64 == delta {
+        len += 1;
+    }
+
+    // Partenze ovvie (a, z, 0, 1) sono le prime che un attaccante prova
+    let first = lower[i];
+    let start_guesses = if matches!(first, 'a' | 'z' | '0' | '1') {
+        4.0
+    } else if first.is_ascii_digit() {
+        10.0
+    } else {
+        26.0
+    };
+    let direction = if delta < 0 { 2.0 } else { 1.0 };
+
+    Some(simple_match(PatternKind::Sequence, len, (start_guesses * direction * len as f64).log2()))
+}
+
+/// Tratti contigui di una riga della tastiera (es. "qwerty", "asdf")
+fn keyboard_match(lower: &[char], i: usize) -> Option<PatternMatch> {
+    let mut best_len = 0;
+
+    for row in KEYBOARD_ROWS {
+        let row: Vec<char> = row.chars().collect();
+        let Some(pos) = row.iter().position(|&c| c == lower[i]) else {
+            continue;
+        };
+
+        let forward = (0..)
+            .take_while(|&k| i + k < lower.len() && pos + k < row.len() && lower[i + k] == row[pos + k])
+            .count();
+        let backward = (0..)
+            .take_while(|&k| i + k < lower.len() && k <= pos && lower[i + k] == row[pos - k])
+            .count();
+
+        best_len = best_len.max(forward).max(backward);
+    }
+
+    let guesses = (KEYBOARD_ROWS.len() * 2 * 12 * best_len.max(1)) as f64;
+    Some(simple_match(PatternKind::Keyboard, best_len, guesses.log2()))
+}
+
+/// Anni tra il 1900 e il 2099
+fn year_match(chars: &[char], i: usize) -> Option<PatternMatch> {
+    if i + 4 > chars.len() || !chars[i..i + 4].iter().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let prefix: String = chars[i..i + 2].iter().collect();
+    if prefix != "19" && prefix != "20" {
+        return None;
+    }
+
+    Some(simple_match(PatternKind::Year, 4, 200f64.log2()))
+}
+
+fn simple_match(kind: PatternKind, len: usize, bits: f64) -> PatternMatch {
+    PatternMatch { kind, len, bits, l33t: false, capitalized_only: false }
+}
+
+/// Bit aggiunti dalle maiuscole di una parola e se si tratta della sola iniziale
+fn uppercase_variations(token: &[char]) -> (f64, bool) {
+    let upper = token.iter().filter(|c| c.is_uppercase()).count();
+    let lower = token.iter().filter(|c| c.is_lowercase()).count();
+
+    if upper == 0 {
+        return (0.0, false);
+    }
+
+    let first_only = upper == 1 && token.first().is_some_and(|c| c.is_uppercase());
+    if first_only || lower == 0 || (upper == 1 && token.last().is_some_and(|c| c.is_uppercase())) {
+        return (1.0, first_only);
+    }
+
+    // Somma dei coefficienti binomiali C(n, k) per k = 1..min(upper, lower)
+    let n = (upper + lower) as f64;
+    let mut variations = 0.0;
+    let mut binomial = 1.0;
+    for k in 1..=upper.min(lower) {
+        binomial = binomial * (n - k as f64 + 1.0) / k as f64;
+        variations += binomial;
+    }
+
+    (variations.log2(), false)
+}
+
+/// Dimensione dell'alfabeto usato, per la stima a forza bruta
+fn char_pool_size(chars: &[char]) -> u32 {
+    let mut pool = 0;
+    if chars.iter().any(|c| c.is_ascii_lowercase()) {
+        pool += 26;
+    }
+    if chars.iter().any(|c| c.is_ascii_uppercase()) {
+        pool += 26;
+    }
+    if chars.iter().any(|c| c.is_ascii_digit()) {
+        pool += 10;
+    }
+    if chars.iter().any(|c| c.is_ascii_punctuation() || *c == ' ') {
+        pool += 33;
+    }
+    if chars.iter().any(|c| !c.is_ascii()) {
+        pool += 100;
+    }
+    pool.max(10)
+}
+
+/// Sostituzioni l33t più comuni riportate alla lettera originale
+fn unleet_char(c: char) -> char {
+    match c {
+        '4' | '@' => 'a',
+        '8' => 'b',
+        '3' => 'e',
+        '6' | '9' => 'g',
+        '1' | '!' | '|' => 'i',
+        '0' => 'o',
+        '5' | '$' => 's',
+        '7' | '+' => 't',
+        '2' => 'z',
+        _ => c,
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Suggerimenti specifici in base ai pattern trovati
+fn build_hints(chars: &[char], found: &[PatternMatch], entropy_bits: f64, min_length: usize) -> Vec<String> {
+    let mut hints = Vec::new();
+    let has = |kind: PatternKind| found.iter().any(|m| m.kind == kind);
+
+    let recommended = RECOMMENDED_PASSWORD_LENGTH.max(min_length);
+    if chars.len() < recommended {
+        hints.push(format!(
+            "Aggiungi almeno altri {} caratteri: la lunghezza conta più della complessità",
+            recommended - chars.len()
+        ));
+    }
+    if has(PatternKind::CommonPassword) {
+        hints.push("Evita le password più usate, anche come parte di una password più lunga".to_string());
+    }
+    if has(PatternKind::Dictionary) {
+        hints.push("Evita parole del dizionario da sole: combina più parole non correlate".to_string());
+    }
+    if found.iter().any(|m| m.l33t) {
+        hints.push("Sostituzioni prevedibili come @ per a o 0 per o non aiutano molto".to_string());
+    }
+    if found.iter().any(|m| m.capitalized_only) {
+        hints.push("Una maiuscola solo all'inizio è prevedibile: distribuiscile nella password".to_string());
+    }
+    if has(PatternKind::Keyboard) {
+        hints.push("Evita sequenze della tastiera come qwerty o asdf".to_string());
+    }
+    if has(PatternKind::Sequence) {
+        hints.push("Evita sequenze come abc o 1234".to_string());
+    }
+    if has(PatternKind::Repeat) {
+        hints.push("Evita caratteri ripetuti come aaa o 111".to_string());
+    }
+    if has(PatternKind::Year) {
+        hints.push("Evita anni e date, facili da indovinare".to_string());
+    }
+    if hints.is_empty() && entropy_bits < 60.0 {
+        hints.push("Aggiungi un'altra parola o qualche carattere per renderla più robusta".to_string());
+    }
+
+    hints
+}
+
+/// Tempo di crack in forma leggibile
+fn format_crack_time(seconds: f64) -> String {
+    const MINUTE: f64 = 60.0;
+    const HOUR: f64 = MINUTE * 60.0;
+    const DAY: f64 = HOUR * 24.0;
+    const MONTH: f64 = DAY * 31.0;
+    const YEAR: f64 = DAY * 365.0;
+    const CENTURY: f64 = YEAR * 100.0;
+
+    let plural = |value: f64, singular: &str, plural: &str| {
+        let n = value.round().max(1.0) as u64;
+        format!("{} {}", n, if n == 1 { singular } else { plural })
+    };
+
+    if seconds < 1.0 {
+        "meno di un secondo".to_string()
+    } else if seconds < MINUTE {
+        plural(seconds, "secondo", "secondi")
+    } else if seconds < HOUR {
+        plural(seconds / MINUTE, "minuto", "minuti")
+    } else if seconds < DAY {
+        plural(seconds / HOUR, "ora", "ore")
+    } else if seconds < MONTH {
+        plural(seconds / DAY, "giorno", "giorni")
+    } else if seconds < YEAR {
+        plural(seconds / MONTH, "mese", "mesi")
+    } else if seconds < CENTURY {
+        plural(seconds / YEAR, "anno", "anni")
+    } else {
+        "secoli".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_passwords_have_low_entropy() {
+        let estimate = estimate_password_strength("password", 4);
+        assert!(estimate.entropy_bits < 10.0);
+        assert_eq!(estimate.crack_time_display, "meno di un secondo");
+        assert!(estimate.hints.iter().any(|h| h.contains("più usate")));
+
+        let estimate = estimate_password_strength("P@ssw0rd", 4);
+        assert!(estimate.entropy_bits < 16.0);
+        assert!(estimate.hints.iter().any(|h| h.contains("Sostituzioni")));
+    }
+
+    #[test]
+    fn test_patterns_produce_specific_hints() {
+        let estimate = estimate_password_strength("sdfghj1999", 4);
+        assert!(estimate.hints.iter().any(|h| h.contains("tastiera")));
+        assert!(estimate.hints.iter().any(|h| h.contains("anni")));
+
+        let estimate = estimate_password_strength("xxxxxx", 4);
+        assert!(estimate.hints.iter().any(|h| h.contains("ripetuti")));
+
+        let estimate = estimate_password_strength("abcdefg", 4);
+        assert!(estimate.hints.iter().any(|h| h.contains("sequenze come abc")));
+    }
+
+    #[test]
+    fn test_random_password_has_high_entropy() {
+        let estimate = estimate_password_strength("kX9#mQ2$vL7!pR4w", 4);
+        assert!(estimate.entropy_bits > 80.0);
+        assert_eq!(estimate.crack_time_display, "secoli");
+        assert!(estimate.hints.is_empty());
+
+        let short = estimate_password_strength("kX9#mQ", 4);
+        assert!(short.entropy_bits < estimate.entropy_bits);
+        assert!(short.hints.iter().any(|h| h.contains("altri 6 caratteri")));
+    }
+
+    #[test]
+    fn test_empty_password() {
+        let estimate = estimate_password_strength("", 4);
+        assert_eq!(estimate.entropy_bits, 0.0);
+        assert_eq!(estimate.hints.len(), 1);
+    }
+
+    #[test]
+    fn test_crack_time_display() {
+        assert_eq!(format_crack_time(0.5), "meno di un secondo");
+        assert_eq!(format_crack_time(1.0), "1 secondo");
+        assert_eq!(format_crack_time(120.0), "2 minuti");
+        assert_eq!(format_crack_time(86400.0 * 3.0), "3 giorni");
+        assert_eq!(format_crack_time(86400.0 * 365.0 * 1000.0), "secoli");
+    }
+
+    #[test]
+    fn test_passphrase_words_are_in_dictionary() {
+        // Le passphrase generate non devono risultare più forti di quanto sono
+        let estimate = estimate_password_strength("Lampone-Trota-Vento-Cedro-42", 4);
+        assert!(estimate.entropy_bits < 80.0);
+        assert!(estimate.entropy_bits > 40.0);
+    }
+
+    #[test]
+    fn test_passphrase_meets_policy() {
+        let config = ValidationConfig {
+            min_password_length: 20,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_numbers: true,
+            require_special_chars: true,
+            ..ValidationConfig::default()
+        };
+
+        for _ in 0..20 {
+            let passphrase = generate_passphrase(&config, DEFAULT_PASSPHRASE_WORDS);
+            assert!(passphrase.chars().count() >= 20);
+            assert!(passphrase.chars().any(|c| c.is_uppercase()));
+            assert!(passphrase.chars().any(|c| c.is_lowercase()));
+            assert!(passphrase.chars().any(|c| c.is_ascii_digit()));
+            assert!(passphrase.chars().any(|c| config.allowed_special_chars.contains(c)));
+        }
+    }
+}
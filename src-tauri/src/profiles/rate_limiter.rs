@@ -63,6 +63,21 @@ pub enum RateLimitResult {
     },
 }
 
+/// Stato di blocco di un identificatore, calcolato sulla finestra del rate limiter
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LockoutStatus {
+    /// Accesso attualmente bloccato
+    pub locked: bool,
+    /// Tentativi falliti ancora validi nella finestra di reset
+    pub failed_attempts: u32,
+    /// Tentativi rimasti prima del prossimo blocco
+    pub attempts_remaining: u32,
+    /// Secondi rimanenti prima di poter ritentare (0 se non bloccato)
+    pub seconds_remaining: u64,
+    /// Momento dal quale è consentito un nuovo tentativo (None se già consentito)
+    pub next_attempt_allowed_at: Option<DateTime<Utc>>,
+}
+
 /// Rate limiter per tentativi di accesso
 pub struct RateLimiter {
     /// Configurazione
@@ -212,6 +227,69 @@ impl RateLimiter {
         attempts.get(identifier).cloned()
     }
 
+    /// Calcola lo stato di blocco corrente per un identificatore.
+    /// I tentativi più vecchi di `reset_after_seconds` non vengono conteggiati,
+    /// coerentemente con `register_failed_attempt`.
+    pub fn get_lockout_status(&self, identifier: &str) -> LockoutStatus {
+        let attempts = self.attempts.lock().unwrap();
+        let now = Utc::now();
+        let max_attempts = self.config.max_attempts;
+
+        let info = match attempts.get(identifier) {
+            Some(info) => info,
+            None => {
+                return LockoutStatus {
+                    locked: false,
+                    failed_attempts: 0,
+                    attempts_remaining: max_attempts,
+                    seconds_remaining: 0,
+                    next_attempt_allowed_at: None,
+                };
+            }
+        };
+
+        let blocked_until = info.blocked_until.filter(|until| *until > now);
+        let window_expired = (now - info.last_attempt).num_seconds() > self.config.reset_after_seconds as i64;
+        let failed_attempts = if window_expired && blocked_until.is_none() {
+            0
+        } else {
+            info.failed_attempts
+        };
+
+        LockoutStatus {
+            locked: blocked_until.is_some(),
+            failed_attempts,
+            attempts_remaining: max_attempts.saturating_sub(failed_attempts),
+            seconds_remaining: blocked_until
+                .map(|until| (until - now).num_seconds().max(0) as u64)
+                .unwrap_or(0),
+            next_attempt_allowed_at: blocked_until,
+        }
+    }
+
+    /// Azzera il contatore dei tentativi falliti senza rimuovere un blocco attivo.
+    /// Il numero di blocchi precedenti viene mantenuto per il backoff esponenziale.
+    pub fn clear_failed_attempts(&self, identifier: &str) {
+        let mut attempts = self.attempts.lock().unwrap();
+        let now = Utc::now();
+
+        let remove = match attempts.get_mut(identifier) {
+            Some(info) => {
+                info.failed_attempts = 0;
+                let still_blocked = info.blocked_until.map_or(false, |until| until > now);
+                if !still_blocked {
+                    info.blocked_until = None;
+                }
+                !still_blocked && info.block_count == 0
+            }
+            None => false,
+        };
+
+        if remove {
+            attempts.remove(identifier);
+        }
+    }
+
     /// Resetta i tentativi di accesso per un identificatore
     pub fn reset_attempts(&self, identifier: &str) {
         let mut attempts = self.attempts.lock().unwrap();
@@ -3,6 +3,177 @@
 use std::ptr;
 use std::ops::{Deref, DerefMut};
 use std::fmt;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+/// Azzera `len` byte a partire da `ptr` con scritture volatili, che il compilatore
+/// non può eliminare anche se la memoria non viene più letta (es. subito prima del free)
+///
+/// # Safety
+/// `ptr` deve puntare a un'allocazione valida e scrivibile di almeno `len` byte.
+unsafe fn volatile_zero(ptr: *mut u8, len: usize) {
+    for i in 0..len {
+        ptr::write_volatile(ptr.add(i), 0);
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Azzera l'intera capacità di un Vec (inclusa la parte non inizializzata,
+/// che può contenere residui di contenuti precedenti) e ne resetta la lunghezza
+fn zeroize_vec(bytes: &mut Vec<u8>) {
+    let capacity = bytes.capacity();
+    unsafe {
+        volatile_zero(bytes.as_mut_ptr(), capacity);
+    }
+    bytes.clear();
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Esito dell'ultima verifica di azzeramento eseguita nel Drop di SecureBytes (solo test)
+    static LAST_DROP_ZEROED: std::cell::Cell<Option<bool>> = std::cell::Cell::new(None);
+}
+
+/// Restituisce (e resetta) l'esito della verifica eseguita nell'ultimo Drop di SecureBytes
+#[cfg(test)]
+pub(crate) fn take_last_drop_check() -> Option<bool> {
+    LAST_DROP_ZEROED.with(|cell| cell.take())
+}
+
+/// Buffer di byte sensibili azzerato con scritture volatili al Drop.
+/// A differenza di `SecureMemory<Vec<u8>>` non espone `DerefMut` sul Vec,
+/// così il buffer non può essere riallocato lasciando copie in memoria.
+pub struct SecureBytes {
+    data: Vec<u8>,
+}
+
+impl SecureBytes {
+    /// Prende possesso del buffer senza copiarlo
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+
+    /// Copia i byte in un buffer di capacità esatta
+    pub fn from_slice(bytes: &[u8]) -> Self {
+        let mut data = Vec::with_capacity(bytes.len());
+        data.extend_from_slice(bytes);
+        Self { data }
+    }
+
+    /// Accesso in sola lettura ai byte
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Azzera il buffer (intera capacità) mantenendo l'allocazione
+    pub fn wipe(&mut self) {
+        zeroize_vec(&mut self.data);
+    }
+
+    /// Puntatore e capacità del buffer sottostante (per verificare l'azzeramento nei test)
+    #[cfg(test)]
+    pub(crate) fn raw_parts(&self) -> (*const u8, usize) {
+        (self.data.as_ptr(), self.data.capacity())
+    }
+}
+
+impl Drop for SecureBytes {
+    fn drop(&mut self) {
+        self.wipe();
+
+        // Nei test rilegge il buffer prima della deallocazione per verificare l'azzeramento
+        #[cfg(test)]
+        {
+            let (ptr, capacity) = (self.data.as_ptr(), self.data.capacity());
+            let zeroed = (0..capacity).all(|i| unsafe { ptr::read_volatile(ptr.add(i)) } == 0);
+            LAST_DROP_ZEROED.with(|cell| cell.set(Some(zeroed)));
+        }
+    }
+}
+
+impl Clone for SecureBytes {
+    fn clone(&self) -> Self {
+        Self::from_slice(&self.data)
+    }
+}
+
+impl fmt::Debug for SecureBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[DATI SENSIBILI]")
+    }
+}
+
+/// Stringa sensibile (API key, password) azzerata al Drop.
+/// Non implementa `Display`: il contenuto va letto esplicitamente con `expose()`.
+#[derive(Clone)]
+pub struct SecureString {
+    bytes: SecureBytes,
+}
+
+impl SecureString {
+    /// Copia una stringa in un buffer sicuro di capacità esatta
+    pub fn from_str_copy(value: &str) -> Self {
+        Self { bytes: SecureBytes::from_slice(value.as_bytes()) }
+    }
+
+    /// Accesso esplicito al contenuto in chiaro
+    pub fn expose(&self) -> &str {
+        // Invariante: i byte provengono sempre da una &str o String valida
+        unsafe { std::str::from_utf8_unchecked(self.bytes.as_slice()) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Azzera il contenuto mantenendo l'allocazione
+    pub fn wipe(&mut self) {
+        self.bytes.wipe();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn raw_parts(&self) -> (*const u8, usize) {
+        self.bytes.raw_parts()
+    }
+}
+
+/// Prende possesso della String senza copiarla
+impl From<String> for SecureString {
+    fn from(value: String) -> Self {
+        Self { bytes: SecureBytes::new(value.into_bytes()) }
+    }
+}
+
+impl Deref for SecureString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.expose()
+    }
+}
+
+impl PartialEq for SecureString {
+    fn eq(&self, other: &Self) -> bool {
+        self.expose() == other.expose()
+    }
+}
+
+impl fmt::Debug for SecureString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[DATI SENSIBILI]")
+    }
+}
 
 /// Wrapper per dati sensibili che vengono puliti dalla memoria quando escono dallo scope
 #[derive(Clone)]
@@ -110,37 +281,22 @@ impl<T: Default + Clone> fmt::Display for SecureMemory<T> {
 
 /// Funzione per pulire in modo sicuro una stringa
 pub fn secure_clear_string(s: &mut String) {
-    let len = s.len();
-    
-    // Sovrascrivi con zeri
+    // Sovrascrivi con zeri (scritture volatili sull'intera capacità)
     unsafe {
-        let ptr = s.as_mut_ptr();
-        ptr::write_bytes(ptr, 0, len);
+        zeroize_vec(s.as_mut_vec());
     }
-    
-    // Reimposta la stringa
-    s.clear();
 }
 
 /// Funzione per pulire in modo sicuro un vettore di byte
 pub fn secure_clear_bytes(bytes: &mut Vec<u8>) {
-    let len = bytes.len();
-    
-    // Sovrascrivi con zeri
-    unsafe {
-        let ptr = bytes.as_mut_ptr();
-        ptr::write_bytes(ptr, 0, len);
-    }
-    
-    // Reimposta il vettore
-    bytes.clear();
+    // Sovrascrivi con zeri (scritture volatili sull'intera capacità)
+    zeroize_vec(bytes);
 }
 
 /// Funzione per pulire in modo sicuro un array di byte
 pub fn secure_clear_array<const N: usize>(arr: &mut [u8; N]) {
     // Sovrascrivi con zeri
     unsafe {
-        let ptr = arr.as_mut_ptr();
-        ptr::write_bytes(ptr, 0, N);
+        volatile_zero(arr.as_mut_ptr(), N);
     }
 }
\ No newline at end of file
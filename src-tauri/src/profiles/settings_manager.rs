@@ -1,8 +1,10 @@
 // Modulo per gestione settings per profili
 use crate::profiles::models::{ProfileSettings, Theme, LibraryView, LibrarySort};
 use crate::profiles::errors::{ProfileError, ProfileResult};
+use crate::profiles::cleanup::{orphan_file_size, OrphanedFile, OrphanedFileKind};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashSet;
 use std::path::PathBuf;
 use tokio::fs;
 use chrono::Utc;
@@ -311,7 +313,7 @@ impl ProfileSettingsManager {
             if path.is_file() && path.extension().map_or(false, |ext| ext == "json") {
                 if let Some(file_name) = path.file_stem() {
                     let profile_id = file_name.to_string_lossy().to_string();
-                    if profile_id != "global" {
+                    if profile_id != "global" && !profile_id.starts_with("legacy_backup_") {
                         profiles.push(profile_id);
                     }
                 }
@@ -320,6 +322,26 @@ impl ProfileSettingsManager {
 
         Ok(profiles)
     }
+
+    /// Trova i file settings di profili non più esistenti
+    pub async fn find_orphaned_settings(&self, known_profile_ids: &HashSet<String>) -> ProfileResult<Vec<OrphanedFile>> {
+        let mut orphaned = Vec::new();
+
+        for profile_id in self.list_profiles_with_settings().await? {
+            if known_profile_ids.contains(&profile_id) {
+                continue;
+            }
+            let path = self.settings_dir.join(format!("{}.json", profile_id));
+            orphaned.push(OrphanedFile {
+                size_bytes: orphan_file_size(&path).await,
+                path,
+                kind: OrphanedFileKind::Settings,
+                profile_id: Some(profile_id),
+            });
+        }
+
+        Ok(orphaned)
+    }
 }
 
 /// Implementazioni Default
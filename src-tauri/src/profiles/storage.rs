@@ -2,9 +2,11 @@ use crate::profiles::models::{UserProfile, ProfileInfo};
 use crate::profiles::errors::{StorageError, StorageResult};
 use crate::profiles::encryption::ProfileEncryption;
 use crate::profiles::secure_memory::SecureMemory;
+use crate::profiles::cleanup::{orphan_file_size, OrphanedFile, OrphanedFileKind};
+use crate::profiles::validation::ValidationConfig;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tokio::fs as async_fs;
@@ -71,6 +73,14 @@ pub struct ProfileStorage {
     backups_dir: PathBuf,
     /// Percorso file indice
     index_path: PathBuf,
+    /// Configurazione validazione dell'installazione
+    validation_config_path: PathBuf,
+    /// Directory backup pianificati (una sottocartella per profilo)
+    scheduled_backups_dir: PathBuf,
+    /// Pianificazione backup automatici
+    backup_schedule_path: PathBuf,
+    /// Quarantena dei file orfani rimossi dalla pulizia
+    orphan_quarantine_dir: PathBuf,
     /// Sistema crittografia
     encryption: ProfileEncryption,
 }
@@ -82,12 +92,20 @@ impl ProfileStorage {
         let avatars_dir = base_dir.join("avatars");
         let backups_dir = base_dir.join("backups").join("exported_profiles");
         let index_path = profiles_dir.join("profiles.index");
+        let validation_config_path = profiles_dir.join("validation_config.json");
+        let scheduled_backups_dir = base_dir.join("backups").join("scheduled_profiles");
+        let backup_schedule_path = profiles_dir.join("backup_schedule.json");
+        let orphan_quarantine_dir = base_dir.join("backups").join("orphaned_quarantine");
         
         let storage = Self {
             profiles_dir,
             avatars_dir,
             backups_dir,
             index_path,
+            validation_config_path,
+            scheduled_backups_dir,
+            backup_schedule_path,
+            orphan_quarantine_dir,
             encryption: ProfileEncryption::new(),
         };
         
@@ -102,6 +120,7 @@ impl ProfileStorage {
         fs::create_dir_all(&self.profiles_dir)?;
         fs::create_dir_all(&self.avatars_dir)?;
         fs::create_dir_all(&self.backups_dir)?;
+        fs::create_dir_all(&self.scheduled_backups_dir)?;
         Ok(())
     }
     
@@ -185,15 +204,9 @@ impl ProfileStorage {
         // Aggiorna ultimo accesso
         profile.update_last_access();
         
-        // Salva aggiornamento (senza password per evitare loop)
-        tokio::spawn({
-            let storage = ProfileStorage::new(self.profiles_dir.parent().unwrap().to_path_buf()).unwrap();
-            let profile_clone = profile.clone();
-            let password = password.to_string();
-            async move {
-                let _ = storage.save_profile(&profile_clone, &password).await;
-            }
-        });
+        // Salva aggiornamento prima di restituire il profilo: un salvataggio in background
+        // si sovrapporrebbe a quelli successivi (credenziali, impostazioni, indice)
+        self.save_profile(&profile, password).await?;
         
         println!("[PROFILE STORAGE] ✅ Profilo '{}' caricato", profile.name);
         Ok(profile)
@@ -314,6 +327,197 @@ impl ProfileStorage {
         Ok(backup_path)
     }
 
+    /// Carica la pianificazione dei backup automatici (default: disattivata)
+    pub async fn load_backup_schedule(&self) -> StorageResult<BackupSchedule> {
+        if !self.backup_schedule_path.exists() {
+            return Ok(BackupSchedule::default());
+        }
+
+        let data = async_fs::read_to_string(&self.backup_schedule_path).await?;
+        let schedule: BackupSchedule = serde_json::from_str(&data)?;
+        Ok(schedule.normalized())
+    }
+
+    /// Salva la pianificazione dei backup automatici
+    pub async fn save_backup_schedule(&self, schedule: &BackupSchedule) -> StorageResult<()> {
+        let json = serde_json::to_string_pretty(&schedule.clone().normalized())?;
+        async_fs::write(&self.backup_schedule_path, json).await?;
+        Ok(())
+    }
+
+    /// Crea un backup pianificato: file profilo cifrato (credenziali incluse, restano cifrate),
+    /// impostazioni e metadati dell'indice. Poi mantiene solo gli ultimi `keep_last` backup.
+    pub async fn create_scheduled_backup(&self, id: &str, settings_json: Option<&str>, keep_last: usize) -> StorageResult<ScheduledBackupInfo> {
+        let index = self.load_index().await?;
+        let entry = index.profiles.get(id)
+            .ok_or_else(|| StorageError::FileNotFound(format!("Profilo {} non trovato", id)))?;
+
+        let created_at = Utc::now();
+        let short_id: String = id.chars().take(8).collect();
+        let backup_id = format!("{}_{}", created_at.format("%Y%m%d_%H%M%S%3f"), short_id);
+        let backup_dir = self.scheduled_backups_dir.join(id).join(&backup_id);
+        async_fs::create_dir_all(&backup_dir).await?;
+
+        let mut size_bytes = async_fs::copy(self.profiles_dir.join(&entry.file_path), backup_dir.join(SCHEDULED_PROFILE_FILE)).await?;
+        if let Some(settings) = settings_json {
+            async_fs::write(backup_dir.join(SCHEDULED_SETTINGS_FILE), settings).await?;
+            size_bytes += settings.len() as u64;
+        }
+        if let Some(avatar_path) = &entry.avatar_path {
+            let source_avatar = self.avatars_dir.join(avatar_path);
+            if source_avatar.exists() {
+                size_bytes += async_fs::copy(&source_avatar, backup_dir.join(avatar_path)).await.unwrap_or(0);
+            }
+        }
+
+        let manifest = ScheduledBackupManifest {
+            info: ScheduledBackupInfo {
+                id: backup_id,
+                profile_id: id.to_string(),
+                profile_name: entry.name.clone(),
+                created_at,
+                size_bytes,
+                has_settings: settings_json.is_some(),
+            },
+            index_entry: entry.clone(),
+        };
+        async_fs::write(backup_dir.join(SCHEDULED_MANIFEST_FILE), serde_json::to_string_pretty(&manifest)?).await?;
+
+        let removed = self.rotate_scheduled_backups(id, keep_last).await?;
+        println!("[PROFILE STORAGE] ✅ Backup pianificato creato: {} ({} vecchi rimossi)", backup_dir.display(), removed);
+        Ok(manifest.info)
+    }
+
+    /// Manifest dei backup pianificati di un profilo, dal più recente
+    async fn scheduled_manifests(&self, id: &str) -> StorageResult<Vec<(PathBuf, ScheduledBackupManifest)>> {
+        let profile_dir = self.scheduled_backups_dir.join(id);
+        let mut manifests = Vec::new();
+        if !profile_dir.exists() {
+            return Ok(manifests);
+        }
+
+        let mut entries = async_fs::read_dir(&profile_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let manifest_path = entry.path().join(SCHEDULED_MANIFEST_FILE);
+            let Ok(data) = async_fs::read_to_string(&manifest_path).await else { continue };
+            match serde_json::from_str::<ScheduledBackupManifest>(&data) {
+                Ok(manifest) => manifests.push((entry.path(), manifest)),
+                Err(e) => println!("[PROFILE STORAGE] ⚠️ Manifest backup non valido {}: {}", manifest_path.display(), e),
+            }
+        }
+        manifests.sort_by(|a, b| b.1.info.created_at.cmp(&a.1.info.created_at));
+        Ok(manifests)
+    }
+
+    /// Elimina i backup pianificati oltre gli ultimi `keep_last`; restituisce quanti ne ha rimossi
+    pub async fn rotate_scheduled_backups(&self, id: &str, keep_last: usize) -> StorageResult<usize> {
+        let manifests = self.scheduled_manifests(id).await?;
+        let mut removed = 0;
+        for (dir, _) in manifests.into_iter().skip(keep_last.max(1)) {
+            async_fs::remove_dir_all(&dir).await?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Backup pianificati di un profilo (o di tutti), dal più recente
+    pub async fn list_scheduled_backups(&self, id: Option<&str>) -> StorageResult<Vec<ScheduledBackupInfo>> {
+        let profile_ids: Vec<String> = match id {
+            Some(id) => vec![id.to_string()],
+            None => {
+                let mut ids = Vec::new();
+                let mut entries = async_fs::read_dir(&self.scheduled_backups_dir).await?;
+                while let Some(entry) = entries.next_entry().await? {
+                    if entry.path().is_dir() {
+                        ids.push(entry.file_name().to_string_lossy().to_string());
+                    }
+                }
+                ids
+            }
+        };
+
+        let mut backups = Vec::new();
+        for profile_id in profile_ids {
+            backups.extend(self.scheduled_manifests(&profile_id).await?.into_iter().map(|(_, m)| m.info));
+        }
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(backups)
+    }
+
+    /// Cerca un backup pianificato per id
+    async fn find_scheduled_backup(&self, backup_id: &str) -> StorageResult<(PathBuf, ScheduledBackupManifest)> {
+        for info in self.list_scheduled_backups(None).await? {
+            if info.id == backup_id {
+                if let Some(found) = self.scheduled_manifests(&info.profile_id).await?
+                    .into_iter()
+                    .find(|(_, m)| m.info.id == backup_id)
+                {
+                    return Ok(found);
+                }
+            }
+        }
+        Err(StorageError::FileNotFound(format!("Backup {} non trovato", backup_id)))
+    }
+
+    /// Metadati di un backup pianificato
+    pub async fn scheduled_backup_info(&self, backup_id: &str) -> StorageResult<ScheduledBackupInfo> {
+        Ok(self.find_scheduled_backup(backup_id).await?.1.info)
+    }
+
+    /// Ripristina un backup pianificato, anche se il profilo è stato eliminato.
+    /// La password deve aprire il profilo salvato nel backup: così un ripristino non può
+    /// riportare il profilo a una vecchia password all'insaputa dell'utente.
+    /// Restituisce i metadati e le impostazioni salvate (JSON) da riapplicare.
+    pub async fn restore_scheduled_backup(&self, backup_id: &str, password: &str) -> StorageResult<(ScheduledBackupInfo, Option<String>)> {
+        let (backup_dir, manifest) = self.find_scheduled_backup(backup_id).await?;
+
+        let backup_data = async_fs::read(backup_dir.join(SCHEDULED_PROFILE_FILE)).await?;
+        let opens = self.encryption.decrypt_profile_data(&backup_data, password).ok()
+            .and_then(|data| serde_json::from_slice::<UserProfile>(&data).ok())
+            .is_some();
+        if !opens {
+            return Err(StorageError::PermissionDenied(format!("Password errata per il backup {}", backup_id)));
+        }
+
+        // Copia di sicurezza del file attuale prima di sovrascriverlo
+        let mut entry = manifest.index_entry.clone();
+        let profile_path = self.profiles_dir.join(&entry.file_path);
+        if profile_path.exists() {
+            async_fs::copy(&profile_path, profile_path.with_extension("enc.pre_restore")).await?;
+        }
+        async_fs::copy(backup_dir.join(SCHEDULED_PROFILE_FILE), &profile_path).await?;
+
+        if let Some(avatar_path) = &entry.avatar_path {
+            let backup_avatar = backup_dir.join(avatar_path);
+            if backup_avatar.exists() {
+                async_fs::copy(&backup_avatar, self.avatars_dir.join(avatar_path)).await?;
+            }
+        }
+
+        // Reinserisce l'entry nell'indice con l'hash del file ripristinato.
+        // Blocco e tentativi falliti restano quelli attuali: il ripristino non deve azzerarli
+        entry.file_hash = self.calculate_file_hash(&profile_path).await?;
+        let mut index = self.load_index().await?;
+        if let Some(current) = index.profiles.get(&entry.id) {
+            entry.is_locked = current.is_locked;
+            entry.failed_attempts = current.failed_attempts;
+            entry.last_failed_attempt = current.last_failed_attempt;
+        }
+        index.profiles.insert(entry.id.clone(), entry);
+        index.last_updated = Utc::now();
+        self.save_index(&index).await?;
+
+        let settings_path = backup_dir.join(SCHEDULED_SETTINGS_FILE);
+        let settings_json = if settings_path.exists() {
+            Some(async_fs::read_to_string(&settings_path).await?)
+        } else {
+            None
+        };
+
+        println!("[PROFILE STORAGE] ✅ Backup {} ripristinato per '{}'", backup_id, manifest.info.profile_name);
+        Ok((manifest.info, settings_json))
+    }
+
     /// Carica avatar profilo
     #[allow(dead_code)] // API per gestione avatar
     pub async fn load_avatar(&self, avatar_filename: &str) -> StorageResult<Vec<u8>> {
@@ -380,6 +584,23 @@ impl ProfileStorage {
         Ok(failed_attempts)
     }
 
+    /// Azzera il contatore dei tentativi falliti mantenendo l'eventuale blocco
+    pub async fn clear_failed_attempts_count(&self, id: &str) -> StorageResult<()> {
+        let mut index = self.load_index().await?;
+        match index.profiles.get_mut(id) {
+            Some(entry) => {
+                entry.failed_attempts = 0;
+                entry.last_failed_attempt = None;
+            }
+            None => return Err(StorageError::FileNotFound(format!("Profilo {} non trovato", id))),
+        }
+
+        index.last_updated = Utc::now();
+        self.save_index(&index).await?;
+
+        Ok(())
+    }
+
     /// Carica indice profili
     async fn load_index(&self) -> StorageResult<ProfileIndex> {
         if !self.index_path.exists() {
@@ -398,6 +619,24 @@ impl ProfileStorage {
         Ok(index)
     }
     
+    /// Carica la configurazione di validazione salvata (None se mai personalizzata).
+    /// Sincrono: usato alla creazione del ProfileManager.
+    pub fn load_validation_config(&self) -> StorageResult<Option<ValidationConfig>> {
+        if !self.validation_config_path.exists() {
+            return Ok(None);
+        }
+
+        let data = fs::read_to_string(&self.validation_config_path)?;
+        Ok(Some(serde_json::from_str(&data)?))
+    }
+
+    /// Salva la configurazione di validazione per questa installazione
+    pub async fn save_validation_config(&self, config: &ValidationConfig) -> StorageResult<()> {
+        let json = serde_json::to_string_pretty(config)?;
+        async_fs::write(&self.validation_config_path, json).await?;
+        Ok(())
+    }
+
     /// Salva indice profili
     async fn save_index(&self, index: &ProfileIndex) -> StorageResult<()> {
         let index_json = serde_json::to_string_pretty(index)?;
@@ -408,7 +647,9 @@ impl ProfileStorage {
     /// Aggiorna o crea entry nell'indice
     async fn update_index_entry(&self, profile: &UserProfile, filename: &str, file_hash: &str) -> StorageResult<()> {
         let mut index = self.load_index().await?;
+        let current = index.profiles.get(&profile.id);
         
+        // Blocco e tentativi falliti si gestiscono a parte: salvare il profilo non li azzera
         let entry = ProfileIndexEntry {
             id: profile.id.clone(),
             name: profile.name.clone(),
@@ -416,9 +657,9 @@ impl ProfileStorage {
             avatar_path: profile.avatar_path.clone(),
             created_at: profile.created_at,
             last_accessed: profile.last_accessed,
-            is_locked: false,
-            failed_attempts: 0,
-            last_failed_attempt: None,
+            is_locked: current.map_or(false, |e| e.is_locked),
+            failed_attempts: current.map_or(0, |e| e.failed_attempts),
+            last_failed_attempt: current.and_then(|e| e.last_failed_attempt),
             file_hash: file_hash.to_string(),
             has_credentials: !profile.credentials.is_empty(),
             settings_version: profile.settings.version,
@@ -443,6 +684,70 @@ impl ProfileStorage {
         Ok(format!("{:x}", hash))
     }
     
+    /// Directory in cui la pulizia sposta i file orfani
+    pub fn orphan_quarantine_dir(&self) -> &Path {
+        &self.orphan_quarantine_dir
+    }
+
+    /// Trova file profilo e avatar non referenziati dall'indice.
+    /// Un indice mancante (load_index lo ricreerebbe vuoto) o senza profili renderebbe
+    /// orfani tutti i file: in quel caso la ricerca viene rifiutata.
+    pub async fn find_orphaned_files(&self) -> StorageResult<Vec<OrphanedFile>> {
+        if !self.index_path.exists() {
+            return Err(StorageError::IndexUnavailable("indice assente".to_string()));
+        }
+        let index = self.load_index().await?;
+        if index.profiles.is_empty() {
+            return Err(StorageError::IndexUnavailable("nessun profilo nell'indice".to_string()));
+        }
+        let known_files: HashSet<&str> = index.profiles.values()
+            .map(|entry| entry.file_path.as_str())
+            .collect();
+        let known_avatars: HashSet<&str> = index.profiles.values()
+            .filter_map(|entry| entry.avatar_path.as_deref())
+            .collect();
+
+        let mut orphaned = Vec::new();
+
+        // File profilo crittografati (contengono anche le credenziali store)
+        let mut entries = async_fs::read_dir(&self.profiles_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let profile_id = match file_name.strip_prefix("profile_").and_then(|rest| rest.strip_suffix(".json.enc")) {
+                Some(id) => id.to_string(),
+                None => continue,
+            };
+            if path.is_file() && !known_files.contains(file_name.as_str()) {
+                orphaned.push(OrphanedFile {
+                    size_bytes: orphan_file_size(&path).await,
+                    path,
+                    kind: OrphanedFileKind::ProfileData,
+                    profile_id: Some(profile_id),
+                });
+            }
+        }
+
+        // Avatar non più associati a un profilo
+        if self.avatars_dir.exists() {
+            let mut entries = async_fs::read_dir(&self.avatars_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if path.is_file() && !known_avatars.contains(file_name.as_str()) {
+                    orphaned.push(OrphanedFile {
+                        size_bytes: orphan_file_size(&path).await,
+                        path,
+                        kind: OrphanedFileKind::Avatar,
+                        profile_id: None,
+                    });
+                }
+            }
+        }
+
+        Ok(orphaned)
+    }
+
     /// Ottiene statistiche storage
     #[allow(dead_code)] // API per statistiche storage - mantenuta per future use
     pub async fn get_storage_stats(&self) -> StorageResult<StorageStats> {
@@ -488,6 +793,54 @@ pub struct StorageStats {
     pub last_updated: DateTime<Utc>,
 }
 
+/// File contenuti in ogni backup pianificato
+const SCHEDULED_PROFILE_FILE: &str = "profile.json.enc";
+const SCHEDULED_SETTINGS_FILE: &str = "settings.json";
+const SCHEDULED_MANIFEST_FILE: &str = "backup.json";
+
+/// Pianificazione dei backup automatici dei profili
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupSchedule {
+    pub enabled: bool,
+    /// Intervallo tra due backup del profilo attivo
+    pub interval_hours: u32,
+    /// Backup da conservare per profilo (i più vecchi vengono eliminati)
+    pub keep_last: usize,
+}
+
+impl Default for BackupSchedule {
+    fn default() -> Self {
+        Self { enabled: false, interval_hours: 24, keep_last: 7 }
+    }
+}
+
+impl BackupSchedule {
+    /// Riporta i valori in intervalli sensati (1 ora - 30 giorni, 1-100 backup)
+    pub fn normalized(mut self) -> Self {
+        self.interval_hours = self.interval_hours.clamp(1, 24 * 30);
+        self.keep_last = self.keep_last.clamp(1, 100);
+        self
+    }
+}
+
+/// Backup pianificato di un profilo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledBackupInfo {
+    pub id: String,
+    pub profile_id: String,
+    pub profile_name: String,
+    pub created_at: DateTime<Utc>,
+    pub size_bytes: u64,
+    pub has_settings: bool,
+}
+
+/// Manifest salvato nel backup: serve anche a ricreare l'entry dell'indice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScheduledBackupManifest {
+    info: ScheduledBackupInfo,
+    index_entry: ProfileIndexEntry,
+}
+
 impl Default for ProfileIndex {
     fn default() -> Self {
         Self {
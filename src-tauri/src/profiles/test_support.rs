@@ -0,0 +1,11 @@
+//! Helper comuni ai test dei profili
+
+use crate::profiles::manager::ProfileManager;
+
+/// ID del profilo con questo nome (il nome stesso se non esiste, per i test di profilo mancante)
+pub(crate) async fn profile_id_by_name(manager: &ProfileManager, name: &str) -> String {
+    manager.list_profiles().await.unwrap().into_iter()
+        .find(|p| p.name == name)
+        .map(|p| p.id)
+        .unwrap_or_else(|| name.to_string())
+}
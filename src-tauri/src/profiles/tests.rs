@@ -1,12 +1,13 @@
 #[cfg(test)]
 mod tests {
+    use crate::profiles::test_support::profile_id_by_name;
     use crate::profiles::encryption::ProfileEncryption;
-    use crate::profiles::manager::ProfileManager;
+    use crate::profiles::manager::{ProfileManager, SessionExpiryReason, EXPORT_SCHEMA_VERSION};
     use crate::profiles::storage::ProfileStorage;
     use crate::profiles::models::{CreateProfileRequest, ProfileSettings, Theme, NotificationSettings, LibrarySettings, SecuritySettings, LibrarySort, LibraryView};
     use crate::profiles::errors::ProfileError;
     use crate::profiles::rate_limiter::RateLimiterConfig;
-    use crate::profiles::secure_memory::SecureMemory;
+    use crate::profiles::secure_memory::{secure_clear_string, take_last_drop_check, SecureBytes, SecureMemory, SecureString};
     use tempfile::TempDir;
     use tokio;
     #[allow(unused_imports)]
@@ -213,7 +214,7 @@ mod tests {
         manager.create_profile(request).await.unwrap();
 
         // Test autenticazione corretta
-        let profile = manager.authenticate_profile("Auth Test", "AuthKey123!").await.unwrap();
+        let profile = manager.authenticate_profile(&profile_id_by_name(&manager, "Auth Test").await, "AuthKey123!").await.unwrap();
         assert_eq!(profile.name, "Auth Test");
         assert!(manager.is_profile_active());
         assert!(manager.current_profile().is_some());
@@ -238,7 +239,7 @@ mod tests {
         manager.create_profile(request).await.unwrap();
 
         // Test autenticazione con password sbagliata
-        let result = manager.authenticate_profile("Auth Test", "WrongKey456$").await;
+        let result = manager.authenticate_profile(&profile_id_by_name(&manager, "Auth Test").await, "WrongKey456$").await;
         assert!(result.is_err());
         
         if let Err(ProfileError::InvalidCredentials) = result {
@@ -259,7 +260,7 @@ mod tests {
         let mut manager = ProfileManager::new(storage);
 
         // Test autenticazione profilo inesistente
-        let result = manager.authenticate_profile("Nonexistent", "SomeKey123!").await;
+        let result = manager.authenticate_profile(&profile_id_by_name(&manager, "Nonexistent").await, "SomeKey123!").await;
         assert!(result.is_err());
         
         if let Err(ProfileError::ProfileNotFound(name)) = result {
@@ -293,11 +294,11 @@ mod tests {
         manager.create_profile(request2).await.unwrap();
 
         // Autentica primo profilo
-        manager.authenticate_profile("User One", "UserOneKey123!").await.unwrap();
+        manager.authenticate_profile(&profile_id_by_name(&manager, "User One").await, "UserOneKey123!").await.unwrap();
         assert_eq!(manager.current_profile().unwrap().name, "User One");
 
         // Cambia al secondo profilo
-        let switched_profile = manager.switch_profile("User Two", "UserTwoKey456$").await.unwrap();
+        let switched_profile = manager.switch_profile(&profile_id_by_name(&manager, "User Two").await, "UserTwoKey456$").await.unwrap();
         assert_eq!(switched_profile.name, "User Two");
         assert_eq!(manager.current_profile().unwrap().name, "User Two");
     }
@@ -322,7 +323,7 @@ mod tests {
         assert_eq!(attempts, 0);
 
         // Tenta autenticazione con password sbagliata
-        let _ = manager.authenticate_profile("Fail Test", "WrongKey").await;
+        let _ = manager.authenticate_profile(&profile_id_by_name(&manager, "Fail Test").await, "WrongKey").await;
         let attempts = manager.get_failed_attempts("Fail Test").await.unwrap();
         assert_eq!(attempts, 1);
 
@@ -344,7 +345,7 @@ mod tests {
             settings: None,
         };
         manager.create_profile(request).await.unwrap();
-        manager.authenticate_profile("Timeout Test", "TimeoutKey123!").await.unwrap();
+        manager.authenticate_profile(&profile_id_by_name(&manager, "Timeout Test").await, "TimeoutKey123!").await.unwrap();
 
         // Verifica che la sessione non sia scaduta immediatamente
         assert!(!manager.is_session_expired(60));
@@ -358,6 +359,59 @@ mod tests {
         assert!(manager.renew_session().is_ok());
     }
 
+    #[tokio::test]
+    async fn test_session_idle_and_max_duration() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+
+        // Idle timeout 60 minuti, durata massima 30 minuti, preavviso 2 minuti
+        let mut settings = ProfileSettings::default();
+        settings.security.session_timeout = 60;
+        settings.security.max_session_duration = 30;
+        settings.security.session_expiry_warning = 120;
+
+        let request = CreateProfileRequest {
+            name: "Session Test".to_string(),
+            password: "SessionKey123!".to_string(),
+            avatar_path: None,
+            settings: Some(settings),
+        };
+        manager.create_profile(request).await.unwrap();
+        // La creazione apre già una sessione: la chiudiamo per partire da zero
+        manager.logout().unwrap();
+
+        // Senza login la sessione non è attiva
+        assert!(!manager.get_session_status().active);
+        assert!(matches!(manager.touch_session(), Err(ProfileError::Unauthorized)));
+
+        manager.authenticate_profile(&profile_id_by_name(&manager, "Session Test").await, "SessionKey123!").await.unwrap();
+        let now = chrono::Utc::now();
+
+        let status = manager.session_status_at(now);
+        assert!(status.active && !status.expired && !status.near_expiry);
+        assert_eq!(status.idle_timeout_seconds, Some(3600));
+        assert_eq!(status.max_session_seconds, Some(1800));
+        assert_eq!(status.expiry_reason, Some(SessionExpiryReason::MaxDuration));
+
+        // L'attività fa scorrere solo l'idle timeout, non la durata massima
+        let touched = manager.touch_session().unwrap();
+        assert_eq!(touched.expiry_reason, Some(SessionExpiryReason::MaxDuration));
+        assert!(touched.absolute_seconds_remaining.unwrap() <= 1800);
+
+        let near = manager.session_status_at(now + chrono::Duration::minutes(29));
+        assert!(near.near_expiry && !near.expired);
+
+        let expired = manager.session_status_at(now + chrono::Duration::minutes(31));
+        assert!(expired.expired);
+        assert_eq!(expired.seconds_remaining, Some(0));
+        assert!(expired.idle_seconds_remaining.unwrap() > 0);
+
+        // Nessun logout finché la sessione è valida
+        assert!(manager.expire_session_if_needed().unwrap().is_none());
+        assert!(manager.current_profile().is_some());
+    }
+
     #[tokio::test]
     async fn test_profile_export_import() {
         let temp_dir = TempDir::new().unwrap();
@@ -380,7 +434,8 @@ mod tests {
         assert_eq!(exported.export_metadata.profile_name, "Export Test");
         assert_eq!(exported.export_metadata.credentials_count, 0);
         assert!(!exported.export_metadata.has_avatar);
-        assert_eq!(exported.version, 1);
+        assert_eq!(exported.version, EXPORT_SCHEMA_VERSION);
+        assert_eq!(exported.export_metadata.schema_version, EXPORT_SCHEMA_VERSION);
 
         // Importa profilo con nuovo nome
         let imported_profile = manager.import_profile(exported, "ExportKey123!", Some("Imported Test".to_string())).await.unwrap();
@@ -418,14 +473,194 @@ mod tests {
         assert!(export_path.exists());
 
         // Valida file export
-        let metadata = manager.validate_export_file(export_path.to_str().unwrap()).await.unwrap();
-        assert_eq!(metadata.profile_name, "File Export Test");
+        let validation = manager.validate_export_file(export_path.to_str().unwrap(), None).await.unwrap();
+        assert_eq!(validation.metadata.profile_name, "File Export Test");
+        assert_eq!(validation.schema_version, EXPORT_SCHEMA_VERSION);
+        assert!(validation.compatible);
+        assert_eq!(validation.incompatibility, None);
+        assert!(!validation.requires_migration);
 
         // Importa da file
         let imported = manager.import_profile_from_file(export_path.to_str().unwrap(), "FileKey123!", Some("File Imported".to_string())).await.unwrap();
         assert_eq!(imported.name, "File Imported");
     }
 
+    #[tokio::test]
+    async fn test_profile_import_v1_export_file() {
+        use sha2::{Sha256, Digest};
+
+        // Layout dell'envelope v1 (metadati senza schema_version)
+        #[derive(serde::Serialize)]
+        struct MetadataV1 {
+            profile_name: String,
+            exported_at: chrono::DateTime<chrono::Utc>,
+            app_version: String,
+            original_size: usize,
+            integrity_hash: String,
+            has_avatar: bool,
+            credentials_count: usize,
+        }
+        #[derive(serde::Serialize)]
+        struct ExportV1 {
+            version: u32,
+            encrypted_data: Vec<u8>,
+            export_metadata: MetadataV1,
+            signature: String,
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+
+        let request = CreateProfileRequest {
+            name: "Legacy Export".to_string(),
+            password: "LegacyKey123!".to_string(),
+            avatar_path: None,
+            settings: None,
+        };
+        let profile = manager.create_profile(request).await.unwrap();
+        let exported = manager.export_profile(&profile.id, "LegacyKey123!", None).await.unwrap();
+
+        // Ricostruisce un export v1: payload = JSON del profilo senza envelope
+        let encryption = ProfileEncryption::new();
+        let decrypted = encryption.decrypt_profile_data(&exported.encrypted_data, "LegacyKey123!").unwrap();
+        let payload: serde_json::Value = serde_json::from_slice(&decrypted).unwrap();
+        let profile_json = serde_json::to_string_pretty(&payload["profile"]).unwrap();
+        let encrypted_data = encryption.encrypt_profile_data(profile_json.as_bytes(), "LegacyKey123!").unwrap();
+        let integrity_hash = format!("{:x}", Sha256::digest(profile_json.as_bytes()));
+
+        let metadata = MetadataV1 {
+            profile_name: "Legacy Export".to_string(),
+            exported_at: exported.export_metadata.exported_at,
+            app_version: "0.9.0".to_string(),
+            original_size: profile_json.len(),
+            integrity_hash,
+            has_avatar: false,
+            credentials_count: 0,
+        };
+        let mut hasher = Sha256::new();
+        hasher.update(metadata.profile_name.as_bytes());
+        hasher.update(metadata.exported_at.to_rfc3339().as_bytes());
+        hasher.update(metadata.app_version.as_bytes());
+        hasher.update(metadata.integrity_hash.as_bytes());
+        hasher.update(&encrypted_data);
+        let signature = format!("{:x}", hasher.finalize());
+
+        let v1 = ExportV1 { version: 1, encrypted_data, export_metadata: metadata, signature };
+        let export_path = temp_dir.path().join("legacy_v1.profile");
+        std::fs::write(&export_path, bincode::serialize(&v1).unwrap()).unwrap();
+
+        // Validazione: schema v1 compatibile, da migrare
+        let validation = manager.validate_export_file(export_path.to_str().unwrap(), None).await.unwrap();
+        assert_eq!(validation.schema_version, 1);
+        assert!(validation.compatible);
+        assert!(validation.requires_migration);
+
+        // Import con migrazione v1 → corrente
+        let imported = manager.import_profile_from_file(export_path.to_str().unwrap(), "LegacyKey123!", Some("Legacy Imported".to_string())).await.unwrap();
+        assert_eq!(imported.name, "Legacy Imported");
+    }
+
+    #[tokio::test]
+    async fn test_profile_export_with_passphrase() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+
+        let request = CreateProfileRequest {
+            name: "Passphrase Export".to_string(),
+            password: "ProfileKey123!".to_string(),
+            avatar_path: None,
+            settings: None,
+        };
+        let profile = manager.create_profile(request).await.unwrap();
+
+        let export_path = temp_dir.path().join("protected.profile");
+        let summary = manager.export_profile_to_file(&profile.id, "ProfileKey123!", export_path.to_str().unwrap(), Some("Share-Phrase-42")).await.unwrap();
+        assert!(summary.passphrase_protected);
+        assert!(summary.warnings.is_empty());
+
+        // Validazione: passphrase richiesta e verificata
+        let validation = manager.validate_export_file(export_path.to_str().unwrap(), None).await.unwrap();
+        assert!(validation.passphrase_protected);
+        assert_eq!(validation.passphrase_verified, None);
+        let validation = manager.validate_export_file(export_path.to_str().unwrap(), Some("Wrong-Phrase-00")).await.unwrap();
+        assert_eq!(validation.passphrase_verified, Some(false));
+        let validation = manager.validate_export_file(export_path.to_str().unwrap(), Some("Share-Phrase-42")).await.unwrap();
+        assert_eq!(validation.passphrase_verified, Some(true));
+
+        // La password del profilo non basta per importare
+        let result = manager.import_profile_from_file(export_path.to_str().unwrap(), "ProfileKey123!", Some("Imported A".to_string())).await;
+        assert!(matches!(result, Err(ProfileError::InvalidExportPassphrase)));
+        let result = manager.import_profile_from_file(export_path.to_str().unwrap(), "", Some("Imported B".to_string())).await;
+        assert!(matches!(result, Err(ProfileError::ExportPassphraseRequired)));
+
+        let imported = manager.import_profile_from_file(export_path.to_str().unwrap(), "Share-Phrase-42", Some("Imported C".to_string())).await.unwrap();
+        assert_eq!(imported.name, "Imported C");
+    }
+
+    #[tokio::test]
+    async fn test_profile_export_from_newer_app_is_incompatible() {
+        use crate::profiles::manager::ExportedProfile;
+        use sha2::{Sha256, Digest};
+
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+
+        let request = CreateProfileRequest {
+            name: "Future Export".to_string(),
+            password: "FutureKey123!".to_string(),
+            avatar_path: None,
+            settings: None,
+        };
+        let profile = manager.create_profile(request).await.unwrap();
+        let export_path = temp_dir.path().join("future_app.profile");
+        manager.export_profile_to_file(&profile.id, "FutureKey123!", export_path.to_str().unwrap(), None).await.unwrap();
+
+        // Stesso schema, ma creato da una major successiva dell'app (firma ricalcolata)
+        let mut exported: ExportedProfile = bincode::deserialize(&std::fs::read(&export_path).unwrap()).unwrap();
+        exported.export_metadata.app_version = "99.0.0".to_string();
+        let metadata = &exported.export_metadata;
+        let mut hasher = Sha256::new();
+        hasher.update(metadata.profile_name.as_bytes());
+        hasher.update(metadata.exported_at.to_rfc3339().as_bytes());
+        hasher.update(metadata.app_version.as_bytes());
+        hasher.update(metadata.integrity_hash.as_bytes());
+        hasher.update(metadata.schema_version.to_le_bytes());
+        hasher.update([metadata.passphrase_protected as u8]);
+        hasher.update(&exported.encrypted_data);
+        exported.signature = format!("{:x}", hasher.finalize());
+        std::fs::write(&export_path, bincode::serialize(&exported).unwrap()).unwrap();
+
+        let validation = manager.validate_export_file(export_path.to_str().unwrap(), None).await.unwrap();
+        assert_eq!(validation.schema_version, EXPORT_SCHEMA_VERSION);
+        assert!(!validation.compatible);
+        assert!(validation.incompatibility.unwrap().contains("99.0.0"));
+
+        let result = manager.import_profile_from_file(export_path.to_str().unwrap(), "FutureKey123!", Some("Future Imported".to_string())).await;
+        assert!(matches!(result, Err(ProfileError::DataFormatError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_profile_import_newer_schema_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+
+        // Envelope di uno schema futuro: solo la versione è leggibile
+        let mut export_data = (EXPORT_SCHEMA_VERSION + 1).to_le_bytes().to_vec();
+        export_data.extend_from_slice(&[0u8; 64]);
+        let export_path = temp_dir.path().join("future.profile");
+        std::fs::write(&export_path, export_data).unwrap();
+
+        let result = manager.validate_export_file(export_path.to_str().unwrap(), None).await;
+        assert!(matches!(result, Err(ProfileError::ExportFromNewerVersion { found, .. }) if found == EXPORT_SCHEMA_VERSION + 1));
+
+        let result = manager.import_profile_from_file(export_path.to_str().unwrap(), "AnyKey123!", None).await;
+        assert!(matches!(result, Err(ProfileError::ExportFromNewerVersion { .. })));
+    }
+
     #[tokio::test]
     async fn test_profile_export_wrong_password() {
         let temp_dir = TempDir::new().unwrap();
@@ -475,6 +710,92 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_cleanup_orphaned_profile_data() {
+        use crate::profiles::cleanup::{cleanup_orphaned_profile_data, OrphanedFileKind};
+        use crate::profiles::settings_manager::ProfileSettingsManager;
+
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+        let settings_manager = ProfileSettingsManager::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let request = CreateProfileRequest {
+            name: "Kept Profile".to_string(),
+            password: "KeptKey123!".to_string(),
+            avatar_path: None,
+            settings: None,
+        };
+        let kept = manager.create_profile(request).await.unwrap();
+
+        // Settings del profilo esistente e di un profilo eliminato
+        settings_manager.save_profile_settings(&kept.id, &ProfileSettings::default()).await.unwrap();
+        settings_manager.save_profile_settings("deleted-profile", &ProfileSettings::default()).await.unwrap();
+        settings_manager.save_global_settings(&Default::default()).await.unwrap();
+
+        // File profilo e avatar rimasti senza indice
+        let stale_profile = temp_dir.path().join("profiles").join("profile_deleted-profile.json.enc");
+        let stale_avatar = temp_dir.path().join("avatars").join("avatar_stale.png");
+        std::fs::write(&stale_profile, vec![1u8; 100]).unwrap();
+        std::fs::write(&stale_avatar, vec![2u8; 50]).unwrap();
+
+        // Senza sessione autenticata la pulizia viene rifiutata
+        manager.logout().unwrap();
+        assert!(matches!(
+            cleanup_orphaned_profile_data(&manager, &settings_manager, true).await,
+            Err(ProfileError::Unauthorized)
+        ));
+        manager.authenticate_profile(&profile_id_by_name(&manager, "Kept Profile").await, "KeptKey123!").await.unwrap();
+
+        // Dry-run: elenca senza spostare
+        let report = cleanup_orphaned_profile_data(&manager, &settings_manager, true).await.unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.orphaned_files.len(), 3);
+        assert_eq!(report.quarantined_files, 0);
+        assert!(report.quarantine_dir.is_none());
+        assert!(report.orphaned_files.iter().any(|f| f.kind == OrphanedFileKind::Settings
+            && f.profile_id.as_deref() == Some("deleted-profile")));
+        assert!(report.orphaned_files.iter().any(|f| f.kind == OrphanedFileKind::ProfileData));
+        assert!(report.orphaned_files.iter().any(|f| f.kind == OrphanedFileKind::Avatar));
+        assert!(stale_profile.exists() && stale_avatar.exists());
+
+        // Conferma: sposta in quarantena invece di eliminare
+        let report = cleanup_orphaned_profile_data(&manager, &settings_manager, false).await.unwrap();
+        assert_eq!(report.quarantined_files, 3);
+        assert_eq!(report.quarantined_bytes, report.total_bytes);
+        assert!(report.quarantined_bytes >= 150);
+        assert!(!stale_profile.exists() && !stale_avatar.exists());
+        let quarantine_dir = report.quarantine_dir.unwrap();
+        assert!(quarantine_dir.starts_with(temp_dir.path().join("backups").join("orphaned_quarantine")));
+        assert!(quarantine_dir.join("profiles").join("profile_deleted-profile.json.enc").exists());
+        assert!(quarantine_dir.join("avatars").join("avatar_stale.png").exists());
+
+        // I dati dei profili esistenti restano intatti
+        assert_eq!(settings_manager.list_profiles_with_settings().await.unwrap(), vec![kept.id.clone()]);
+        assert!(manager.authenticate_profile(&profile_id_by_name(&manager, "Kept Profile").await, "KeptKey123!").await.is_ok());
+
+        let report = cleanup_orphaned_profile_data(&manager, &settings_manager, true).await.unwrap();
+        assert!(report.orphaned_files.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_orphaned_files_refuses_missing_or_empty_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let profile_file = temp_dir.path().join("profiles").join("profile_lost-index.json.enc");
+        std::fs::write(&profile_file, vec![1u8; 10]).unwrap();
+
+        // Indice assente: non va ricreato vuoto, tutti i profili risulterebbero orfani
+        assert!(storage.find_orphaned_files().await.is_err());
+        assert!(!temp_dir.path().join("profiles").join("profiles.index").exists());
+
+        // Indice presente ma senza profili
+        storage.list_profile_info().await.unwrap();
+        assert!(temp_dir.path().join("profiles").join("profiles.index").exists());
+        assert!(storage.find_orphaned_files().await.is_err());
+        assert!(profile_file.exists());
+    }
+
     #[tokio::test]
     async fn test_profile_backup() {
         let temp_dir = TempDir::new().unwrap();
@@ -497,6 +818,101 @@ mod tests {
         println!("Backup creato: {}", backup_path);
     }
 
+    #[tokio::test]
+    async fn test_scheduled_backup_rotation_and_restore() {
+        use crate::profiles::storage::BackupSchedule;
+
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+
+        let request = CreateProfileRequest {
+            name: "Scheduled Backup".to_string(),
+            password: "ScheduledKey123!".to_string(),
+            avatar_path: None,
+            settings: None,
+        };
+        let profile = manager.create_profile(request).await.unwrap();
+        manager.logout().unwrap();
+
+        // La pianificazione vale per tutti i profili: senza sessione è rifiutata
+        let schedule = BackupSchedule { enabled: true, interval_hours: 0, keep_last: 2 };
+        assert!(matches!(manager.set_backup_schedule(schedule.clone()).await, Err(ProfileError::Unauthorized)));
+
+        manager.authenticate_profile(&profile.id, "ScheduledKey123!").await.unwrap();
+        let schedule = manager.set_backup_schedule(schedule).await.unwrap();
+        assert_eq!(schedule.interval_hours, 1);
+
+        // Tre backup, ne restano solo gli ultimi due
+        for _ in 0..3 {
+            manager.create_scheduled_backup(&profile.id, Some("{}")).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let backups = manager.list_scheduled_backups(Some(&profile.id)).await.unwrap();
+        assert_eq!(backups.len(), 2);
+        assert!(backups[0].created_at >= backups[1].created_at);
+        assert!(backups[0].has_settings);
+        manager.logout().unwrap();
+
+        // Senza sessione del profilo elenco e ripristino sono rifiutati
+        assert!(matches!(manager.list_scheduled_backups(None).await, Err(ProfileError::Unauthorized)));
+        assert!(matches!(
+            manager.restore_scheduled_backup(&backups[1].id, "ScheduledKey123!").await,
+            Err(ProfileError::Unauthorized)
+        ));
+
+        // Con la sessione ma con la password sbagliata il profilo non viene toccato
+        manager.authenticate_profile(&profile_id_by_name(&manager, "Scheduled Backup").await, "ScheduledKey123!").await.unwrap();
+        assert_eq!(manager.list_scheduled_backups(None).await.unwrap().len(), 2);
+        assert!(matches!(
+            manager.restore_scheduled_backup(&backups[1].id, "WrongKey123!").await,
+            Err(ProfileError::InvalidCredentials)
+        ));
+
+        // Dopo il ripristino l'hash nell'indice corrisponde e il profilo si apre
+        let (info, settings) = manager.restore_scheduled_backup(&backups[1].id, "ScheduledKey123!").await.unwrap();
+        assert_eq!(info.profile_id, profile.id);
+        assert_eq!(settings.as_deref(), Some("{}"));
+        assert!(!manager.is_profile_active());
+        manager.authenticate_profile(&profile_id_by_name(&manager, "Scheduled Backup").await, "ScheduledKey123!").await.unwrap();
+        assert!(manager.restore_scheduled_backup("inesistente", "ScheduledKey123!").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_backup_restore_keeps_lockout_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+
+        let request = CreateProfileRequest {
+            name: "Restore Lockout".to_string(),
+            password: "RestoreKey123!".to_string(),
+            avatar_path: None,
+            settings: None,
+        };
+        let profile = manager.create_profile(request).await.unwrap();
+        manager.logout().unwrap();
+
+        // Il backup fotografa l'indice con due tentativi falliti
+        for _ in 0..2 {
+            let _ = manager.authenticate_profile(&profile.id, "WrongPassword").await;
+        }
+        assert_eq!(manager.get_failed_attempts("Restore Lockout").await.unwrap(), 2);
+        let backup = manager.create_scheduled_backup(&profile.id, None).await.unwrap();
+
+        // Il login riuscito azzera il contatore e i salvataggi del profilo non lo toccano
+        manager.authenticate_profile(&profile.id, "RestoreKey123!").await.unwrap();
+        assert_eq!(manager.get_failed_attempts("Restore Lockout").await.unwrap(), 0);
+
+        // Il ripristino mantiene lo stato attuale, non quello del backup
+        manager.restore_scheduled_backup(&backup.id, "RestoreKey123!").await.unwrap();
+        assert_eq!(manager.get_failed_attempts("Restore Lockout").await.unwrap(), 0);
+
+        // Un nuovo errore dopo il ripristino riparte dallo stato attuale
+        let _ = manager.authenticate_profile(&profile.id, "WrongPassword").await;
+        assert_eq!(manager.get_failed_attempts("Restore Lockout").await.unwrap(), 1);
+    }
+
     #[tokio::test]
     async fn test_credential_manager() {
         use crate::profiles::credential_manager::{ProfileCredentialManager, PlainCredential, StoreType};
@@ -515,7 +931,7 @@ mod tests {
         let _profile = profile_manager.create_profile(request).await.unwrap();
         
         // Autentica profilo
-        profile_manager.authenticate_profile("Credential Test", "CredKey123!").await.unwrap();
+        profile_manager.authenticate_profile(&profile_id_by_name(&profile_manager, "Credential Test").await, "CredKey123!").await.unwrap();
 
         // Crea credential manager
         let mut cred_manager = ProfileCredentialManager::new();
@@ -568,7 +984,7 @@ mod tests {
         let profile_id = profile.id.clone();
 
         // Test autenticazione con password corretta
-        let auth_result = manager.authenticate_profile("Auth Test", "AuthKey123!").await;
+        let auth_result = manager.authenticate_profile(&profile_id_by_name(&manager, "Auth Test").await, "AuthKey123!").await;
         assert!(auth_result.is_ok());
         
         let authenticated_profile = auth_result.unwrap();
@@ -599,7 +1015,7 @@ mod tests {
         manager.create_profile(request).await.unwrap();
 
         // Test autenticazione con password sbagliata
-        let auth_result = manager.authenticate_profile("Wrong Pass Test", "WrongKey456$").await;
+        let auth_result = manager.authenticate_profile(&profile_id_by_name(&manager, "Wrong Pass Test").await, "WrongKey456$").await;
         assert!(auth_result.is_err());
         
         if let Err(ProfileError::InvalidCredentials) = auth_result {
@@ -641,7 +1057,7 @@ mod tests {
 
         // Effettua tentativi falliti fino al blocco
         for i in 1..=3 {
-            let result = manager.authenticate_profile("Rate Limit Test", "WrongPassword").await;
+            let result = manager.authenticate_profile(&profile_id_by_name(&manager, "Rate Limit Test").await, "WrongPassword").await;
             assert!(result.is_err());
             
             if i < 3 {
@@ -660,7 +1076,7 @@ mod tests {
         }
 
         // Verifica che il profilo sia bloccato
-        let blocked_result = manager.authenticate_profile("Rate Limit Test", "RateKey123!").await;
+        let blocked_result = manager.authenticate_profile(&profile_id_by_name(&manager, "Rate Limit Test").await, "RateKey123!").await;
         assert!(blocked_result.is_err());
         
         if let Err(ProfileError::TooManyAttempts(_)) = blocked_result {
@@ -673,10 +1089,93 @@ mod tests {
         manager.reset_login_attempts(&profile.id);
 
         // Ora dovrebbe funzionare con la password corretta
-        let success_result = manager.authenticate_profile("Rate Limit Test", "RateKey123!").await;
+        let success_result = manager.authenticate_profile(&profile_id_by_name(&manager, "Rate Limit Test").await, "RateKey123!").await;
         assert!(success_result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_profile_lockout_status_and_reset() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+
+        let config = RateLimiterConfig {
+            max_attempts: 3,
+            block_duration_seconds: 60,
+            reset_after_seconds: 600,
+            exponential_backoff: false,
+            backoff_factor: 1.0,
+            max_block_duration_seconds: 60,
+        };
+        manager.set_rate_limiter_config(config);
+
+        let request = CreateProfileRequest {
+            name: "Lockout Test".to_string(),
+            password: "LockoutKey123!".to_string(),
+            avatar_path: None,
+            settings: None,
+        };
+        manager.create_profile(request).await.unwrap();
+        manager.logout().unwrap();
+
+        // Stato iniziale
+        let status = manager.get_lockout_status("Lockout Test").await.unwrap();
+        assert!(!status.locked);
+        assert_eq!(status.failed_attempts, 0);
+        assert_eq!(status.attempts_remaining, 3);
+        assert!(status.next_attempt_allowed_at.is_none());
+
+        // Due tentativi falliti: non ancora bloccato
+        for _ in 0..2 {
+            let _ = manager.authenticate_profile(&profile_id_by_name(&manager, "Lockout Test").await, "WrongPassword").await;
+        }
+        let status = manager.get_lockout_status("Lockout Test").await.unwrap();
+        assert!(!status.locked);
+        assert_eq!(status.failed_attempts, 2);
+        assert_eq!(status.attempts_remaining, 1);
+
+        // Senza sessione autenticata il reset è rifiutato e il contatore resta
+        assert!(matches!(
+            manager.reset_failed_attempts("Lockout Test").await,
+            Err(ProfileError::Unauthorized)
+        ));
+        let status = manager.get_lockout_status("Lockout Test").await.unwrap();
+        assert_eq!(status.failed_attempts, 2);
+
+        // Con la sessione del profilo il reset è consentito
+        manager.authenticate_profile(&profile_id_by_name(&manager, "Lockout Test").await, "LockoutKey123!").await.unwrap();
+        manager.reset_failed_attempts("Lockout Test").await.unwrap();
+        let status = manager.get_lockout_status("Lockout Test").await.unwrap();
+        assert_eq!(status.failed_attempts, 0);
+        assert_eq!(manager.get_failed_attempts("Lockout Test").await.unwrap(), 0);
+        manager.logout().unwrap();
+
+        // Tre tentativi falliti: bloccato con tempo di attesa
+        for _ in 0..3 {
+            let _ = manager.authenticate_profile(&profile_id_by_name(&manager, "Lockout Test").await, "WrongPassword").await;
+        }
+        let status = manager.get_lockout_status("Lockout Test").await.unwrap();
+        assert!(status.locked);
+        assert!(status.seconds_remaining > 0 && status.seconds_remaining <= 60);
+        assert!(status.next_attempt_allowed_at.is_some());
+
+        // Da bloccato, un reset non autenticato non aggira il blocco
+        assert!(matches!(
+            manager.reset_failed_attempts("Lockout Test").await,
+            Err(ProfileError::Unauthorized)
+        ));
+        let status = manager.get_lockout_status("Lockout Test").await.unwrap();
+        assert!(status.locked);
+        assert_eq!(status.failed_attempts, 3);
+        assert!(matches!(
+            manager.authenticate_profile(&profile_id_by_name(&manager, "Lockout Test").await, "LockoutKey123!").await,
+            Err(ProfileError::TooManyAttempts(_))
+        ));
+
+        // Nome inesistente
+        assert!(manager.get_lockout_status("Missing").await.is_err());
+    }
+
     #[tokio::test]
     async fn test_profile_manager_secure_memory() {
         let temp_dir = TempDir::new().unwrap();
@@ -699,6 +1198,71 @@ mod tests {
         }
     }
 
+    /// Legge con letture volatili i byte di un buffer ancora allocato
+    fn all_zero(ptr: *const u8, len: usize) -> bool {
+        (0..len).all(|i| unsafe { std::ptr::read_volatile(ptr.add(i)) } == 0)
+    }
+
+    #[test]
+    fn test_secure_bytes_zeroed_on_drop() {
+        take_last_drop_check();
+
+        {
+            let _secret = SecureBytes::new(vec![0xAB; 64]);
+        }
+        assert_eq!(take_last_drop_check(), Some(true));
+
+        {
+            let _secret = SecureString::from("0123456789ABCDEF0123456789ABCDEF".to_string());
+        }
+        assert_eq!(take_last_drop_check(), Some(true));
+    }
+
+    #[test]
+    fn test_secure_bytes_wipe_covers_spare_capacity() {
+        // Residui oltre la lunghezza corrente (es. dopo truncate) devono essere azzerati
+        let mut buffer = Vec::with_capacity(64);
+        buffer.extend_from_slice(&[0xCD; 48]);
+        buffer.truncate(8);
+
+        let mut secret = SecureBytes::new(buffer);
+        let (ptr, capacity) = secret.raw_parts();
+        assert!(capacity >= 48);
+        assert!(!all_zero(ptr, capacity));
+
+        secret.wipe();
+        let (wiped_ptr, wiped_capacity) = secret.raw_parts();
+        assert_eq!(wiped_ptr, ptr);
+        assert!(all_zero(wiped_ptr, wiped_capacity));
+        assert!(secret.is_empty());
+    }
+
+    #[test]
+    fn test_secure_string_takes_ownership_without_copy() {
+        let plain = "0123456789abcdef0123456789abcdef".to_string();
+        let original_ptr = plain.as_ptr();
+
+        let mut secret = SecureString::from(plain);
+        assert_eq!(secret.raw_parts().0, original_ptr);
+        assert_eq!(secret.expose(), "0123456789abcdef0123456789abcdef");
+        assert_eq!(secret.len(), 32);
+        assert_eq!(format!("{:?}", secret), "[DATI SENSIBILI]");
+
+        let copy = SecureString::from_str_copy(secret.expose());
+        assert_eq!(copy, secret);
+        assert_ne!(copy.raw_parts().0, original_ptr);
+
+        secret.wipe();
+        let (ptr, capacity) = secret.raw_parts();
+        assert!(all_zero(ptr, capacity));
+        assert_eq!(secret.expose(), "");
+
+        let mut plain = "SensitiveData".to_string();
+        secure_clear_string(&mut plain);
+        assert!(plain.is_empty());
+        assert!(all_zero(plain.as_ptr(), plain.capacity()));
+    }
+
     #[tokio::test]
     async fn test_profile_manager_crud_operations() {
         let temp_dir = TempDir::new().unwrap();
@@ -731,6 +1295,8 @@ mod tests {
                 },
                 security: SecuritySettings {
                     session_timeout: 3600,
+                    max_session_duration: 720,
+                    session_expiry_warning: 120,
                     require_password_for_sensitive: true,
                     auto_lock_failed_attempts: 3,
                     lock_duration: 300,
@@ -754,7 +1320,7 @@ mod tests {
         assert_eq!(profile_info.unwrap().name, "CRUD Test");
 
         // UPDATE - Autentica e aggiorna impostazioni
-        manager.authenticate_profile("CRUD Test", "CrudKey123!").await.unwrap();
+        manager.authenticate_profile(&profile_id_by_name(&manager, "CRUD Test").await, "CrudKey123!").await.unwrap();
         
         let mut new_settings = created_profile.settings.clone();
         new_settings.theme = Theme::Light;
@@ -782,7 +1348,7 @@ mod tests {
         let mut manager = ProfileManager::new(storage);
 
         // Test profilo non esistente
-        let auth_result = manager.authenticate_profile("NonExistent", "password").await;
+        let auth_result = manager.authenticate_profile(&profile_id_by_name(&manager, "NonExistent").await, "password").await;
         assert!(auth_result.is_err());
         if let Err(ProfileError::ProfileNotFound(name)) = auth_result {
             assert_eq!(name, "NonExistent");
@@ -813,4 +1379,73 @@ mod tests {
         let create_result = manager.create_profile(invalid_request).await;
         assert!(create_result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_validation_config_persisted_per_install() {
+        use crate::profiles::validation::ValidationConfig;
+
+        let temp_dir = TempDir::new().unwrap();
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+
+        // La configurazione vale per tutta l'installazione: serve una sessione
+        assert!(matches!(
+            manager.update_validation_config(ValidationConfig::default()).await,
+            Err(ProfileError::Unauthorized)
+        ));
+        let request = CreateProfileRequest {
+            name: "Amministratore".to_string(),
+            password: "AdminKey123!".to_string(),
+            avatar_path: None,
+            settings: None,
+        };
+        manager.create_profile(request).await.unwrap();
+
+        // Configurazione non valida rifiutata e non salvata
+        let invalid = ValidationConfig { min_profile_name_length: 20, max_profile_name_length: 10, ..ValidationConfig::default() };
+        assert!(manager.update_validation_config(invalid).await.is_err());
+        assert!(!temp_dir.path().join("profiles").join("validation_config.json").exists());
+
+        let mut config = ValidationConfig::default();
+        config.max_profile_name_length = 12;
+        config.reserved_names.insert("Ospite".to_string());
+        config.enforce_unique_names = false;
+        manager.update_validation_config(config).await.unwrap();
+
+        // Nuova istanza (riavvio app): la configurazione viene ricaricata
+        let storage = ProfileStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut manager = ProfileManager::new(storage);
+        let loaded = manager.get_validation_config();
+        assert_eq!(loaded.max_profile_name_length, 12);
+        assert!(loaded.reserved_names.contains("ospite"));
+        assert!(!manager.validate_profile_name("ospite").is_valid);
+        assert!(!manager.validate_profile_name("NomeMoltoLungo1").is_valid);
+
+        // Nomi duplicati consentiti quando l'unicità è disattivata
+        let mut squad_ids = Vec::new();
+        for password in ["SquadraKey123!", "SquadraKey456!"] {
+            let request = CreateProfileRequest {
+                name: "Squadra".to_string(),
+                password: password.to_string(),
+                avatar_path: None,
+                settings: None,
+            };
+            squad_ids.push(manager.create_profile(request).await.unwrap().id);
+        }
+        assert_eq!(manager.list_profiles().await.unwrap().len(), 3);
+
+        // Con nomi duplicati l'autenticazione usa l'ID del profilo
+        manager.logout().unwrap();
+        let second = manager.authenticate_profile(&squad_ids[1], "SquadraKey456!").await.unwrap();
+        assert_eq!(second.id, squad_ids[1]);
+        manager.logout().unwrap();
+        assert!(matches!(
+            manager.authenticate_profile(&squad_ids[0], "SquadraKey456!").await,
+            Err(ProfileError::InvalidCredentials)
+        ));
+        assert!(matches!(
+            manager.authenticate_profile("Squadra", "SquadraKey123!").await,
+            Err(ProfileError::ProfileNotFound(_))
+        ));
+    }
 }
@@ -1,8 +1,13 @@
 // Modulo per validazione input profili
 use crate::profiles::errors::{ProfileError, ProfileResult};
+use crate::profiles::password_strength::{self, PasswordStrengthEstimate};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use unicode_normalization::UnicodeNormalization;
+
+/// Limite assoluto lunghezza nome profilo (caratteri), indipendente dalla configurazione
+pub const PROFILE_NAME_HARD_LIMIT: usize = 50;
 
 /// Configurazione validazione profili
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +32,139 @@ pub struct ValidationConfig {
     pub allowed_special_chars: String,
     /// Nomi profilo riservati
     pub reserved_names: HashSet<String>,
+    /// Classi di caratteri ammesse nei nomi profilo
+    #[serde(default)]
+    pub allowed_name_chars: NameCharClasses,
+    /// Pattern regex opzionale per convenzioni di nomenclatura (es. `^team-[a-z]+$`)
+    #[serde(default)]
+    pub name_pattern: Option<String>,
+    /// Richiede nomi profilo univoci (confronto senza maiuscole/minuscole)
+    #[serde(default = "default_enforce_unique_names")]
+    pub enforce_unique_names: bool,
+}
+
+fn default_enforce_unique_names() -> bool {
+    true
+}
+
+impl ValidationConfig {
+    /// Controlla che la configurazione sia coerente prima di applicarla
+    pub fn validate(&self) -> ProfileResult<()> {
+        if self.min_profile_name_length == 0 {
+            return Err(ProfileError::InvalidInput("La lunghezza minima del nome deve essere almeno 1".to_string()));
+        }
+        if self.max_profile_name_length > PROFILE_NAME_HARD_LIMIT {
+            return Err(ProfileError::InvalidInput(format!(
+                "La lunghezza massima del nome non può superare {} caratteri",
+                PROFILE_NAME_HARD_LIMIT
+            )));
+        }
+        if self.min_profile_name_length > self.max_profile_name_length {
+            return Err(ProfileError::InvalidInput(format!(
+                "Lunghezza minima nome ({}) maggiore della massima ({})",
+                self.min_profile_name_length, self.max_profile_name_length
+            )));
+        }
+        if self.min_password_length > self.max_password_length {
+            return Err(ProfileError::InvalidInput(format!(
+                "Lunghezza minima password ({}) maggiore della massima ({})",
+                self.min_password_length, self.max_password_length
+            )));
+        }
+        if !self.allowed_name_chars.letters && !self.allowed_name_chars.digits && self.allowed_name_chars.extra_chars.is_empty() {
+            return Err(ProfileError::InvalidInput("I nomi profilo devono ammettere almeno lettere o numeri".to_string()));
+        }
+        if let Some(pattern) = &self.name_pattern {
+            Regex::new(pattern)
+                .map_err(|e| ProfileError::InvalidInput(format!("Pattern nome non valido: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// Normalizza i nomi riservati (minuscolo, senza spazi esterni) e il pattern vuoto
+    pub fn normalized(mut self) -> Self {
+        self.reserved_names = self.reserved_names
+            .iter()
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect();
+        if self.name_pattern.as_deref().is_some_and(|p| p.trim().is_empty()) {
+            self.name_pattern = None;
+        }
+        self
+    }
+}
+
+/// Classi di caratteri ammesse nei nomi profilo
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct NameCharClasses {
+    /// Lettere di qualsiasi alfabeto, con i segni diacritici combinanti
+    pub letters: bool,
+    /// Cifre
+    pub digits: bool,
+    /// Spazi
+    pub spaces: bool,
+    /// Trattini `-`
+    pub hyphens: bool,
+    /// Underscore `_`
+    pub underscores: bool,
+    /// Caratteri aggiuntivi ammessi (es. ".")
+    pub extra_chars: String,
+}
+
+impl Default for NameCharClasses {
+    fn default() -> Self {
+        Self {
+            letters: true,
+            digits: true,
+            spaces: true,
+            hyphens: true,
+            underscores: true,
+            extra_chars: String::new(),
+        }
+    }
+}
+
+impl NameCharClasses {
+    /// Il carattere appartiene a una delle classi abilitate
+    pub fn allows(&self, c: char) -> bool {
+        (self.letters && (c.is_alphabetic() || unicode_normalization::char::is_combining_mark(c)))
+            || (self.digits && c.is_numeric())
+            || (self.spaces && c == ' ')
+            || (self.hyphens && c == '-')
+            || (self.underscores && c == '_')
+            || self.extra_chars.contains(c)
+    }
+
+    /// Descrizione delle classi abilitate per i messaggi di errore
+    pub fn description(&self) -> String {
+        let mut classes = Vec::new();
+        if self.letters {
+            classes.push("lettere".to_string());
+        }
+        if self.digits {
+            classes.push("numeri".to_string());
+        }
+        if self.spaces {
+            classes.push("spazi".to_string());
+        }
+        if self.hyphens {
+            classes.push("trattini".to_string());
+        }
+        if self.underscores {
+            classes.push("underscore".to_string());
+        }
+        if !self.extra_chars.is_empty() {
+            classes.push(format!("\"{}\"", self.extra_chars));
+        }
+
+        match classes.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} e {}", rest.join(", "), last),
+            Some((last, _)) => last.clone(),
+            None => String::new(),
+        }
+    }
 }
 
 impl Default for ValidationConfig {
@@ -52,6 +190,9 @@ impl Default for ValidationConfig {
             require_special_chars: false,
             allowed_special_chars: "!@#$%^&*()_+-=[]{}|;:,.<>?".to_string(),
             reserved_names,
+            allowed_name_chars: NameCharClasses::default(),
+            name_pattern: None,
+            enforce_unique_names: true,
         }
     }
 }
@@ -75,6 +216,114 @@ pub struct PasswordValidationResult {
     pub suggestions: Vec<String>,
 }
 
+/// Motivo per cui un carattere è stato rimosso dalla sanitizzazione
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RemovedCharReason {
+    /// Carattere di controllo (es. NUL, ESC)
+    Control,
+    /// Carattere invisibile a larghezza zero (es. U+200B, BOM)
+    ZeroWidth,
+    /// Controllo di direzione del testo (es. U+202E), usato per camuffare i nomi
+    BidiControl,
+}
+
+impl RemovedCharReason {
+    pub fn description(&self) -> &'static str {
+        match self {
+            RemovedCharReason::Control => "carattere di controllo",
+            RemovedCharReason::ZeroWidth => "carattere invisibile",
+            RemovedCharReason::BidiControl => "controllo direzione testo",
+        }
+    }
+}
+
+/// Carattere rimosso durante la sanitizzazione
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RemovedChar {
+    /// Code point in formato `U+XXXX`
+    pub code_point: String,
+    /// Posizione (in caratteri) nel testo normalizzato
+    pub position: usize,
+    pub reason: RemovedCharReason,
+}
+
+/// Risultato della sanitizzazione Unicode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SanitizedInput {
+    /// Testo normalizzato NFC e ripulito
+    pub value: String,
+    /// Caratteri rimossi (controllo, invisibili, bidi)
+    pub removed: Vec<RemovedChar>,
+    /// La normalizzazione NFC ha modificato il testo
+    pub normalized: bool,
+}
+
+impl SanitizedInput {
+    /// Nessun carattere rimosso
+    pub fn is_clean(&self) -> bool {
+        self.removed.is_empty()
+    }
+
+    /// Elenco leggibile dei caratteri rimossi (es. "U+202E (controllo direzione testo)")
+    pub fn removed_summary(&self) -> String {
+        self.removed
+            .iter()
+            .map(|r| format!("{} ({})", r.code_point, r.reason.description()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Classifica i caratteri da rimuovere sempre, in qualsiasi alfabeto
+pub fn unsafe_char_reason(c: char) -> Option<RemovedCharReason> {
+    match c {
+        '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' | '\u{200E}' | '\u{200F}' | '\u{061C}' => {
+            Some(RemovedCharReason::BidiControl)
+        }
+        '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{180E}' | '\u{00AD}' => {
+            Some(RemovedCharReason::ZeroWidth)
+        }
+        c if c.is_control() => Some(RemovedCharReason::Control),
+        _ => None,
+    }
+}
+
+/// Normalizza in NFC e rimuove caratteri di controllo, invisibili e bidi.
+/// ZWNJ/ZWJ sono mantenuti tra due lettere (necessari in persiano, indiano, emoji);
+/// `keep_whitespace_controls` mantiene `\n` e `\t`. Lettere di tutti gli alfabeti restano intatte.
+pub fn sanitize_unicode(input: &str, keep_whitespace_controls: bool) -> SanitizedInput {
+    let normalized: String = input.nfc().collect();
+    let chars: Vec<char> = normalized.chars().collect();
+    let mut value = String::with_capacity(normalized.len());
+    let mut removed = Vec::new();
+
+    for (position, &c) in chars.iter().enumerate() {
+        let joiner_between_letters = matches!(c, '\u{200C}' | '\u{200D}')
+            && position > 0
+            && chars[position - 1].is_alphanumeric()
+            && chars.get(position + 1).is_some_and(|n| n.is_alphanumeric());
+        let whitespace_control = keep_whitespace_controls && (c == '\n' || c == '\t');
+
+        match unsafe_char_reason(c) {
+            Some(reason) if !joiner_between_letters && !whitespace_control => {
+                removed.push(RemovedChar {
+                    code_point: format!("U+{:04X}", c as u32),
+                    position,
+                    reason,
+                });
+            }
+            _ => value.push(c),
+        }
+    }
+
+    SanitizedInput {
+        normalized: normalized != input,
+        value,
+        removed,
+    }
+}
+
 /// Livelli di forza password
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum PasswordStrength {
@@ -117,11 +366,20 @@ impl PasswordStrength {
 /// Validatore profili
 pub struct ProfileValidator {
     pub config: ValidationConfig,
+    /// Pattern nome compilato una sola volta
+    name_regex: Option<Regex>,
 }
 
 impl ProfileValidator {
     pub fn new(config: ValidationConfig) -> Self {
-        Self { config }
+        let name_regex = config.name_pattern.as_deref().and_then(|pattern| match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                println!("[VALIDATION] ⚠️ Pattern nome ignorato ({}): {}", pattern, e);
+                None
+            }
+        });
+        Self { config, name_regex }
     }
 
     pub fn with_default_config() -> Self {
@@ -134,14 +392,21 @@ impl ProfileValidator {
         let mut warnings = Vec::new();
         
         // Sanitizza il nome
-        let sanitized_name = self.sanitize_profile_name(name);
+        let sanitized = self.sanitize_profile_name_detailed(name);
+        let sanitized_name = sanitized.value.clone();
+        let name_length = sanitized_name.chars().count();
+
+        // Caratteri invisibili o di controllo: rifiuta invece di correggere in silenzio
+        if !sanitized.is_clean() {
+            errors.push(format!("Il nome contiene caratteri invisibili o di controllo non consentiti: {}", sanitized.removed_summary()));
+        }
         
         // Controlla lunghezza
-        if sanitized_name.len() < self.config.min_profile_name_length {
+        if name_length < self.config.min_profile_name_length {
             errors.push(format!("Il nome deve essere di almeno {} caratteri", self.config.min_profile_name_length));
         }
         
-        if sanitized_name.len() > self.config.max_profile_name_length {
+        if name_length > self.config.max_profile_name_length {
             errors.push(format!("Il nome non può superare {} caratteri", self.config.max_profile_name_length));
         }
         
@@ -150,10 +415,23 @@ impl ProfileValidator {
             errors.push("Il nome non può essere vuoto".to_string());
         }
         
-        // Controlla caratteri validi
-        let valid_chars_regex = Regex::new(r"^[a-zA-Z0-9_\-\s]+$").unwrap();
-        if !valid_chars_regex.is_match(&sanitized_name) {
-            errors.push("Il nome può contenere solo lettere, numeri, spazi, trattini e underscore".to_string());
+        // Controlla caratteri validi secondo le classi configurate
+        let allowed = &self.config.allowed_name_chars;
+        let mut invalid_chars: Vec<char> = sanitized_name.chars().filter(|c| !allowed.allows(*c)).collect();
+        invalid_chars.dedup();
+        if !invalid_chars.is_empty() {
+            errors.push(format!(
+                "Il nome può contenere solo {} (caratteri non ammessi: {})",
+                allowed.description(),
+                invalid_chars.iter().collect::<String>()
+            ));
+        }
+
+        // Controlla convenzione di nomenclatura
+        if let Some(regex) = &self.name_regex {
+            if !regex.is_match(&sanitized_name) {
+                errors.push(format!("Il nome non rispetta la convenzione richiesta ({})", regex.as_str()));
+            }
         }
         
         // Controlla nomi riservati
@@ -283,19 +561,41 @@ impl ProfileValidator {
         }
     }
 
+    /// Stima entropia, tempo di crack e suggerimenti (abbastanza veloce per ogni battitura)
+    pub fn estimate_password_strength(&self, password: &str) -> PasswordStrengthEstimate {
+        password_strength::estimate_password_strength(password, self.config.min_password_length)
+    }
+
+    /// Genera passphrase casuali che superano la validazione con la configurazione corrente
+    pub fn generate_passphrases(&self, count: usize, word_count: usize) -> Vec<String> {
+        let mut passphrases = Vec::with_capacity(count);
+        let mut attempts = 0;
+
+        while passphrases.len() < count && attempts < count * 10 {
+            attempts += 1;
+            let passphrase = password_strength::generate_passphrase(&self.config, word_count);
+            if self.validate_password(&passphrase).is_valid {
+                passphrases.push(passphrase);
+            }
+        }
+
+        passphrases
+    }
+
     /// Sanitizza nome profilo
     fn sanitize_profile_name(&self, name: &str) -> String {
-        // Rimuove caratteri di controllo e spazi extra
-        let sanitized = name
-            .chars()
-            .filter(|c| !c.is_control())
-            .collect::<String>()
-            .trim()
-            .to_string();
+        self.sanitize_profile_name_detailed(name).value
+    }
+
+    /// Sanitizza nome profilo riportando i caratteri rimossi
+    fn sanitize_profile_name_detailed(&self, name: &str) -> SanitizedInput {
+        // Normalizza NFC e rimuove caratteri di controllo, invisibili e bidi
+        let mut sanitized = sanitize_unicode(name, false);
 
         // Sostituisce spazi multipli con spazio singolo
         let space_regex = Regex::new(r"\s+").unwrap();
-        space_regex.replace_all(&sanitized, " ").to_string()
+        sanitized.value = space_regex.replace_all(sanitized.value.trim(), " ").to_string();
+        sanitized
     }
 
     /// Controlla se è una password comune debole
@@ -350,16 +650,22 @@ impl ProfileValidator {
 
     /// Valida input generico (sanitizzazione)
     pub fn sanitize_input(&self, input: &str) -> String {
-        input
-            .chars()
-            .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
-            .collect::<String>()
-            .trim()
-            .to_string()
+        self.sanitize_input_detailed(input).value
+    }
+
+    /// Sanitizzazione Unicode di input generico riportando i caratteri rimossi
+    pub fn sanitize_input_detailed(&self, input: &str) -> SanitizedInput {
+        let mut sanitized = sanitize_unicode(input, true);
+        sanitized.value = sanitized.value.trim().to_string();
+        sanitized
     }
 
     /// Valida che un profilo non esista già
     pub fn validate_unique_profile_name(&self, name: &str, existing_names: &[String]) -> ProfileResult<()> {
+        if !self.config.enforce_unique_names {
+            return Ok(());
+        }
+
         let sanitized_name = self.sanitize_profile_name(name);
         
         if existing_names.iter().any(|existing| existing.to_lowercase() == sanitized_name.to_lowercase()) {
@@ -419,6 +725,157 @@ mod tests {
         assert!(result.suggestions.iter().any(|s| s.contains("comuni")));
     }
 
+    #[test]
+    fn test_generated_passphrases_pass_validation() {
+        let validator = ProfileValidator::new(ValidationConfig {
+            min_password_length: 16,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_numbers: true,
+            require_special_chars: true,
+            ..ValidationConfig::default()
+        });
+
+        let passphrases = validator.generate_passphrases(5, 4);
+        assert_eq!(passphrases.len(), 5);
+        for passphrase in &passphrases {
+            assert!(validator.validate_password(passphrase).is_valid);
+            assert!(validator.estimate_password_strength(passphrase).entropy_bits > 40.0);
+        }
+    }
+
+    #[test]
+    fn test_unicode_profile_names() {
+        let validator = ProfileValidator::with_default_config();
+
+        // Lettere accentate e altri alfabeti sono validi
+        for name in ["Café", "ゲーム", "Jürgen_99", "Ελένη", "Андрей-2"] {
+            let result = validator.validate_profile_name(name);
+            assert!(result.is_valid, "{} dovrebbe essere valido: {:?}", name, result.errors);
+            assert_eq!(result.sanitized_name, name);
+        }
+
+        // Forma decomposta (e + accento combinante) normalizzata in NFC
+        let result = validator.validate_profile_name("Cafe\u{0301}");
+        assert!(result.is_valid);
+        assert_eq!(result.sanitized_name, "Café");
+
+        // Caratteri invisibili e bidi rifiutati, non rimossi in silenzio
+        let result = validator.validate_profile_name("admin\u{202E}txt");
+        assert!(!result.is_valid);
+        assert!(result.errors.iter().any(|e| e.contains("U+202E")));
+
+        let result = validator.validate_profile_name("Te\u{200B}st");
+        assert!(!result.is_valid);
+        assert!(result.errors.iter().any(|e| e.contains("invisibil")));
+    }
+
+    #[test]
+    fn test_sanitize_unicode_reports_removed_chars() {
+        let sanitized = sanitize_unicode("Gioco\u{200B}\u{202E}\x07 ゲーム", false);
+        assert_eq!(sanitized.value, "Gioco ゲーム");
+        assert_eq!(sanitized.removed.len(), 3);
+        assert_eq!(sanitized.removed[0].code_point, "U+200B");
+        assert_eq!(sanitized.removed[0].reason, RemovedCharReason::ZeroWidth);
+        assert_eq!(sanitized.removed[1].reason, RemovedCharReason::BidiControl);
+        assert_eq!(sanitized.removed[2].reason, RemovedCharReason::Control);
+
+        // ZWNJ tra due lettere è parte della scrittura (es. persiano)
+        let sanitized = sanitize_unicode("می\u{200C}خواهم", false);
+        assert!(sanitized.is_clean());
+
+        // A capo e tab mantenuti solo se richiesto
+        assert!(sanitize_unicode("riga\nriga", true).is_clean());
+        assert!(!sanitize_unicode("riga\nriga", false).is_clean());
+    }
+
+    #[test]
+    fn test_profile_name_length_boundaries() {
+        let validator = ProfileValidator::new(ValidationConfig {
+            min_profile_name_length: 3,
+            max_profile_name_length: 8,
+            ..ValidationConfig::default()
+        });
+
+        assert!(!validator.validate_profile_name("ab").is_valid);
+        assert!(validator.validate_profile_name("abc").is_valid);
+        assert!(validator.validate_profile_name("abcdefgh").is_valid);
+        assert!(!validator.validate_profile_name("abcdefghi").is_valid);
+
+        // Lunghezza in caratteri, non in byte
+        assert!(!validator.validate_profile_name("ゲームゲームゲーム").is_valid);
+        assert!(validator.validate_profile_name("ゲームゲーム").is_valid);
+    }
+
+    #[test]
+    fn test_configurable_reserved_names_and_char_classes() {
+        let mut reserved_names = HashSet::new();
+        reserved_names.insert("  Moderatore ".to_string());
+        let config = ValidationConfig {
+            reserved_names,
+            allowed_name_chars: NameCharClasses {
+                spaces: false,
+                extra_chars: ".".to_string(),
+                ..NameCharClasses::default()
+            },
+            ..ValidationConfig::default()
+        }
+        .normalized();
+        let validator = ProfileValidator::new(config);
+
+        // Blocklist personalizzata, senza distinzione maiuscole/minuscole
+        let result = validator.validate_profile_name("MODERATORE");
+        assert!(!result.is_valid);
+        assert!(result.errors.iter().any(|e| e.contains("riservato")));
+        assert!(validator.validate_profile_name("admin").is_valid);
+
+        // Spazi disabilitati, punto ammesso
+        let result = validator.validate_profile_name("mario rossi");
+        assert!(!result.is_valid);
+        assert!(result.errors.iter().any(|e| e.contains("caratteri non ammessi:  ")));
+        assert!(validator.validate_profile_name("mario.rossi").is_valid);
+    }
+
+    #[test]
+    fn test_name_pattern_and_uniqueness_toggle() {
+        let validator = ProfileValidator::new(ValidationConfig {
+            name_pattern: Some(r"^team-[a-z]+$".to_string()),
+            enforce_unique_names: false,
+            ..ValidationConfig::default()
+        });
+
+        assert!(validator.validate_profile_name("team-alpha").is_valid);
+        let result = validator.validate_profile_name("alpha");
+        assert!(!result.is_valid);
+        assert!(result.errors.iter().any(|e| e.contains("convenzione")));
+
+        let existing = vec!["team-alpha".to_string()];
+        assert!(validator.validate_unique_profile_name("team-alpha", &existing).is_ok());
+
+        let validator = ProfileValidator::with_default_config();
+        assert!(validator.validate_unique_profile_name("TEAM-ALPHA", &existing).is_err());
+    }
+
+    #[test]
+    fn test_validation_config_validate() {
+        assert!(ValidationConfig::default().validate().is_ok());
+
+        let config = ValidationConfig { min_profile_name_length: 10, max_profile_name_length: 5, ..ValidationConfig::default() };
+        assert!(config.validate().is_err());
+
+        let config = ValidationConfig { max_profile_name_length: PROFILE_NAME_HARD_LIMIT + 1, ..ValidationConfig::default() };
+        assert!(config.validate().is_err());
+
+        let config = ValidationConfig { name_pattern: Some("[".to_string()), ..ValidationConfig::default() };
+        assert!(config.validate().is_err());
+
+        let config = ValidationConfig {
+            allowed_name_chars: NameCharClasses { letters: false, digits: false, ..NameCharClasses::default() },
+            ..ValidationConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_sanitization() {
         let validator = ProfileValidator::with_default_config();
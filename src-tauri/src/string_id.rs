@@ -0,0 +1,133 @@
+// 🔑 Id canonico dei testi sorgente
+// OCR, runtime UE ed estrazione da file producono lo stesso testo con piccole differenze
+// (CRLF, spazi doppi, caratteri invisibili, forme Unicode diverse). `string_id` normalizza
+// il testo e ne calcola un hash stabile, così patch, TM e bridge possono riferirsi alla
+// stessa voce e il frontend può unire i dati delle diverse pipeline.
+//
+// Non sostituisce l'hash FNV del bridge: quello deve restare identico al plugin C#.
+//
+// `normalize_typography` è separata dall'id: uniforma virgolette, trattini e puntini di
+// sospensione solo per i lookup (TM, glossario, dizionari), così gli id già salvati non cambiano.
+
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+use unicode_normalization::UnicodeNormalization;
+
+/// Prefisso degli id canonici (riconoscibile nei file di patch)
+const STRING_ID_PREFIX: &str = "s_";
+
+/// Caratteri invisibili che OCR e dump dei giochi inseriscono o perdono a caso
+fn is_invisible(c: char) -> bool {
+    matches!(c, '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}')
+}
+
+/// Forma normalizzata del testo: NFC, a capo unificati, spazi compressi per riga,
+/// niente spazi ai bordi. Maiuscole e punteggiatura restano invariate.
+pub fn normalize_source_text(text: &str) -> String {
+    let text: String = text.nfc().filter(|c| !is_invisible(*c)).collect();
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Id canonico di un testo sorgente (es. "s_3f2a9c0d81b7e645")
+pub fn string_id(text: &str) -> String {
+    let digest = Sha256::digest(normalize_source_text(text).as_bytes());
+    let hex: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}{}", STRING_ID_PREFIX, hex)
+}
+
+/// Preferenza `normalize_typography`, letta da disco al primo lookup
+static TYPOGRAPHY_NORMALIZATION: Lazy<AtomicBool> =
+    Lazy::new(|| AtomicBool::new(crate::commands::preferences::load_typography_preference()));
+
+/// Uniforma la tipografia: virgolette curve e apici -> `'` `"`, trattini lunghi -> `-`,
+/// `…` -> `...`, spazi non separabili -> spazio. Il resto del testo resta invariato.
+pub fn normalize_typography(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => result.push('\''),
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => result.push('"'),
+            '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}' | '\u{2212}' => result.push('-'),
+            '\u{2026}' => result.push_str("..."),
+            '\u{00A0}' | '\u{202F}' => result.push(' '),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Testo da usare come chiave di lookup (tipografia uniformata se la preferenza è attiva)
+pub fn lookup_text(text: &str) -> Cow<'_, str> {
+    if typography_normalization_enabled() {
+        Cow::Owned(normalize_typography(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+pub fn typography_normalization_enabled() -> bool {
+    TYPOGRAPHY_NORMALIZATION.load(Ordering::Relaxed)
+}
+
+/// Aggiorna la preferenza dopo il salvataggio (nessun riavvio necessario)
+pub fn set_typography_normalization(enabled: bool) {
+    if TYPOGRAPHY_NORMALIZATION.swap(enabled, Ordering::Relaxed) != enabled {
+        log::info!("🔤 Normalizzazione tipografica {}", if enabled { "attivata" } else { "disattivata" });
+    }
+}
+
+/// Id canonici di più testi, nello stesso ordine
+#[tauri::command]
+pub fn compute_string_ids(texts: Vec<String>) -> Vec<String> {
+    texts.iter().map(|t| string_id(t)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_logical_text_same_id() {
+        let ocr = "  Open   the door\r\n";
+        let file = "Open the door";
+        let runtime = "Open the\u{200B} door";
+        assert_eq!(string_id(ocr), string_id(file));
+        assert_eq!(string_id(runtime), string_id(file));
+        // "é" composto e decomposto
+        assert_eq!(string_id("Caf\u{00E9}"), string_id("Cafe\u{0301}"));
+    }
+
+    #[test]
+    fn meaningful_differences_change_id() {
+        assert_ne!(string_id("Open the door"), string_id("Open the door!"));
+        assert_ne!(string_id("OK"), string_id("Ok"));
+        // Le righe restano distinte
+        assert_ne!(string_id("Line one\nLine two"), string_id("Line one Line two"));
+    }
+
+    #[test]
+    fn id_format_is_stable() {
+        let id = string_id("Hello");
+        assert!(id.starts_with(STRING_ID_PREFIX));
+        assert_eq!(id.len(), STRING_ID_PREFIX.len() + 16);
+        assert_eq!(compute_string_ids(vec!["Hello".to_string()]), vec![id]);
+    }
+
+    #[test]
+    fn typography_is_unified() {
+        assert_eq!(normalize_typography("\u{201C}Don\u{2019}t\u{201D} \u{2014} wait\u{2026}"), "\"Don't\" - wait...");
+        assert_eq!(normalize_typography("10\u{00A0}HP \u{2013} 5\u{2032}"), "10 HP - 5'");
+        assert_eq!(normalize_typography("Plain text!"), "Plain text!");
+        // L'id canonico non dipende dalla preferenza
+        assert_ne!(string_id("Don\u{2019}t"), string_id("Don't"));
+    }
+}
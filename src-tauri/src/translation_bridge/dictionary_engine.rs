@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use tracing::info;
 
 use super::protocol::TranslationRequest;
+use crate::string_id::{normalize_typography, string_id, typography_normalization_enabled};
 
 /// Entry di traduzione
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +35,10 @@ pub struct LanguageDictionary {
     translations_by_hash: HashMap<u64, TranslationEntry>,
     /// Traduzioni indicizzate per testo originale (fallback)
     translations_by_text: HashMap<String, TranslationEntry>,
+    /// Traduzioni indicizzate per id canonico (testo con spazi/a capo diversi)
+    translations_by_id: HashMap<String, TranslationEntry>,
+    /// Come `translations_by_id` ma con tipografia uniformata (virgolette, trattini, `…`)
+    translations_by_typography: HashMap<String, TranslationEntry>,
     /// Numero di traduzioni
     count: usize,
 }
@@ -43,6 +48,15 @@ impl LanguageDictionary {
         Self::default()
     }
     
+    /// Costruisce un dizionario completo da coppie (originale, tradotto)
+    pub fn from_pairs(translations: Vec<(String, String)>) -> Self {
+        let mut dict = Self::new();
+        for (original, translated) in translations {
+            dict.add(original, translated);
+        }
+        dict
+    }
+    
     /// Aggiunge una traduzione
     pub fn add(&mut self, original: String, translated: String) {
         let hash = TranslationRequest::compute_hash(&original);
@@ -54,6 +68,8 @@ impl LanguageDictionary {
         };
         
         self.translations_by_hash.insert(hash, entry.clone());
+        self.translations_by_id.insert(string_id(&original), entry.clone());
+        self.translations_by_typography.insert(string_id(&normalize_typography(&original)), entry.clone());
         self.translations_by_text.insert(original, entry);
         self.count += 1;
     }
@@ -68,6 +84,31 @@ impl LanguageDictionary {
         self.translations_by_text.get(text)
     }
     
+    /// Cerca traduzione per id canonico (testo normalizzato)
+    pub fn get_by_string_id(&self, id: &str) -> Option<&TranslationEntry> {
+        self.translations_by_id.get(id)
+    }
+    
+    /// Cerca in ordine per hash, testo esatto, id canonico e (se attiva) tipografia uniformata
+    pub fn find(&self, hash: u64, original_text: &str) -> Option<&TranslationEntry> {
+        // L'hash può collidere: vale solo se il testo coincide
+        self.get_by_hash(hash)
+            .filter(|entry| entry.original == original_text)
+            .or_else(|| self.get_by_text(original_text))
+            // Stesso testo con spazi o a capo diversi (es. da OCR o da un altro dump)
+            .or_else(|| self.get_by_string_id(&string_id(original_text)))
+            // Stesso testo con virgolette curve/dritte, trattini o puntini diversi
+            .or_else(|| self.get_by_typography(original_text))
+    }
+    
+    /// Cerca per tipografia uniformata (solo se la preferenza è attiva)
+    fn get_by_typography(&self, text: &str) -> Option<&TranslationEntry> {
+        if !typography_normalization_enabled() {
+            return None;
+        }
+        self.translations_by_typography.get(&string_id(&normalize_typography(text)))
+    }
+    
     /// Numero di traduzioni
     pub fn len(&self) -> usize {
         self.count
@@ -83,10 +124,71 @@ impl LanguageDictionary {
     pub fn clear(&mut self) {
         self.translations_by_hash.clear();
         self.translations_by_text.clear();
+        self.translations_by_id.clear();
+        self.translations_by_typography.clear();
         self.count = 0;
     }
 }
 
+/// Traduzioni lette da un file JSON, con la coppia di lingue a cui appartengono
+pub struct JsonDictionary {
+    pub source: String,
+    pub target: String,
+    pub translations: Vec<(String, String)>,
+}
+
+/// Legge un dizionario JSON; la coppia di lingue di default vale se il file non la dichiara
+pub fn read_json_dictionary(path: &str, default_source: &str, default_target: &str) -> Result<JsonDictionary, String> {
+    let path = Path::new(path);
+    
+    if !path.exists() {
+        return Err(format!("File non trovato: {}", path.display()));
+    }
+    
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Errore lettura file: {}", e))?;
+    
+    parse_json_dictionary(&content, default_source, default_target)
+}
+
+/// Interpreta il contenuto di un dizionario JSON.
+/// Formato 1: { "original": "translated", ... }
+/// Formato 2: [ { "original": "...", "translated": "..." }, ... ]
+/// Formato 3: { "source": "en", "target": "it", "translations": {...} }
+pub fn parse_json_dictionary(content: &str, default_source: &str, default_target: &str) -> Result<JsonDictionary, String> {
+    let with_defaults = |translations| JsonDictionary {
+        source: default_source.to_string(),
+        target: default_target.to_string(),
+        translations,
+    };
+    
+    if let Ok(simple_map) = serde_json::from_str::<HashMap<String, String>>(content) {
+        return Ok(with_defaults(simple_map.into_iter().collect()));
+    }
+    
+    if let Ok(array) = serde_json::from_str::<Vec<TranslationEntry>>(content) {
+        return Ok(with_defaults(array.into_iter().map(|e| (e.original, e.translated)).collect()));
+    }
+    
+    // Formato con metadata
+    #[derive(Deserialize)]
+    struct DictionaryFile {
+        source: Option<String>,
+        target: Option<String>,
+        translations: HashMap<String, String>,
+    }
+    
+    if let Ok(dict_file) = serde_json::from_str::<DictionaryFile>(content) {
+        return Ok(JsonDictionary {
+            source: dict_file.source.unwrap_or_else(|| default_source.to_string()),
+            target: dict_file.target.unwrap_or_else(|| default_target.to_string()),
+            translations: dict_file.translations.into_iter().collect(),
+        });
+    }
+    
+    Err("Formato JSON non riconosciuto".to_string())
+}
+
 /// Dictionary Engine - Gestisce tutti i dizionari
 #[derive(Debug, Default)]
 pub struct DictionaryEngine {
@@ -137,51 +239,21 @@ impl DictionaryEngine {
         count
     }
     
+    /// Coppia di lingue attiva (sorgente, target)
+    pub fn active_languages(&self) -> (String, String) {
+        (self.active_source.clone(), self.active_target.clone())
+    }
+    
     /// Carica traduzioni da file JSON
     pub fn load_from_json(&mut self, path: &str) -> Result<usize, String> {
-        let path = Path::new(path);
-        
-        if !path.exists() {
-            return Err(format!("File non trovato: {}", path.display()));
-        }
-        
-        let content = fs::read_to_string(path)
-            .map_err(|e| format!("Errore lettura file: {}", e))?;
-        
-        // Supporta diversi formati JSON
-        // Formato 1: { "original": "translated", ... }
-        // Formato 2: [ { "original": "...", "translated": "..." }, ... ]
-        // Formato 3: { "source": "en", "target": "it", "translations": {...} }
-        
-        if let Ok(simple_map) = serde_json::from_str::<HashMap<String, String>>(&content) {
-            let translations: Vec<(String, String)> = simple_map.into_iter().collect();
-            return Ok(self.load_translations(&self.active_source.clone(), &self.active_target.clone(), translations));
-        }
-        
-        if let Ok(array) = serde_json::from_str::<Vec<TranslationEntry>>(&content) {
-            let translations: Vec<(String, String)> = array
-                .into_iter()
-                .map(|e| (e.original, e.translated))
-                .collect();
-            return Ok(self.load_translations(&self.active_source.clone(), &self.active_target.clone(), translations));
-        }
-        
-        // Formato con metadata
-        #[derive(Deserialize)]
-        struct DictionaryFile {
-            source: Option<String>,
-            target: Option<String>,
-            translations: HashMap<String, String>,
-        }
-        
-        if let Ok(dict_file) = serde_json::from_str::<DictionaryFile>(&content) {
-            let source = dict_file.source.unwrap_or_else(|| self.active_source.clone());
-            let target = dict_file.target.unwrap_or_else(|| self.active_target.clone());
-            let translations: Vec<(String, String)> = dict_file.translations.into_iter().collect();
-            return Ok(self.load_translations(&source, &target, translations));
-        }
-        
-        Err("Formato JSON non riconosciuto".to_string())
+        let file = read_json_dictionary(path, &self.active_source, &self.active_target)?;
+        Ok(self.load_translations(&file.source, &file.target, file.translations))
+    }
+    
+    /// Sostituisce in un colpo solo il dizionario di una coppia di lingue.
+    /// Ritorna il dizionario precedente, da liberare fuori dal lock.
+    pub fn replace_dictionary(&mut self, source: &str, target: &str, dict: LanguageDictionary) -> Option<LanguageDictionary> {
+        self.dictionaries.insert(Self::get_key(source, target), dict)
     }
     
     /// Carica traduzioni da file CSV
@@ -222,44 +294,18 @@ impl DictionaryEngine {
     /// Cerca una traduzione (usa la coppia di lingue attiva)
     pub fn get_translation(&self, hash: u64, original_text: &str) -> Option<String> {
         let key = Self::get_key(&self.active_source, &self.active_target);
-        
-        if let Some(dict) = self.dictionaries.get(&key) {
-            // Prima prova con hash (più veloce)
-            if let Some(entry) = dict.get_by_hash(hash) {
-                // Verifica che l'hash non sia una collisione
-                if entry.original == original_text {
-                    return Some(entry.translated.clone());
-                }
-            }
-            
-            // Fallback su ricerca testuale
-            if let Some(entry) = dict.get_by_text(original_text) {
-                return Some(entry.translated.clone());
-            }
-        }
-        
-        None
+        self.dictionaries.get(&key)?
+            .find(hash, original_text)
+            .map(|entry| entry.translated.clone())
     }
     
     /// Cerca traduzione con coppia di lingue specifica
-    #[allow(dead_code)]
     pub fn get_translation_for(&self, source: &str, target: &str, original_text: &str) -> Option<String> {
         let key = Self::get_key(source, target);
         let hash = TranslationRequest::compute_hash(original_text);
-        
-        if let Some(dict) = self.dictionaries.get(&key) {
-            if let Some(entry) = dict.get_by_hash(hash) {
-                if entry.original == original_text {
-                    return Some(entry.translated.clone());
-                }
-            }
-            
-            if let Some(entry) = dict.get_by_text(original_text) {
-                return Some(entry.translated.clone());
-            }
-        }
-        
-        None
+        self.dictionaries.get(&key)?
+            .find(hash, original_text)
+            .map(|entry| entry.translated.clone())
     }
     
     /// Aggiunge una singola traduzione
@@ -269,6 +315,25 @@ impl DictionaryEngine {
         dict.add(original, translated);
     }
     
+    /// Aggiunge più traduzioni alla coppia attiva in un colpo solo.
+    /// I testi già presenti (o ripetuti nel lotto) vengono saltati: ritorna (aggiunte, duplicati)
+    pub fn add_translations(&mut self, pairs: Vec<(String, String)>) -> (usize, usize) {
+        let key = Self::get_key(&self.active_source, &self.active_target);
+        let dict = self.dictionaries.entry(key).or_insert_with(LanguageDictionary::new);
+        
+        let mut added = 0;
+        let mut duplicates = 0;
+        for (original, translated) in pairs {
+            if dict.get_by_text(&original).is_some() {
+                duplicates += 1;
+                continue;
+            }
+            dict.add(original, translated);
+            added += 1;
+        }
+        (added, duplicates)
+    }
+    
     /// Ottieni statistiche
     pub fn get_stats(&self) -> DictionaryStats {
         let mut total_entries = 0;
@@ -354,6 +419,50 @@ mod tests {
         assert_eq!(engine.get_translation(hash2, "World"), Some("Mondo".to_string()));
     }
     
+    #[test]
+    fn test_bulk_add_skips_duplicates() {
+        let mut engine = DictionaryEngine::new();
+        engine.set_active_languages("en", "it");
+        engine.add_translation("Hello".to_string(), "Ciao".to_string());
+        
+        let (added, duplicates) = engine.add_translations(vec![
+            ("Hello".to_string(), "Salve".to_string()),
+            ("World".to_string(), "Mondo".to_string()),
+            ("World".to_string(), "Terra".to_string()),
+            ("Door".to_string(), "Porta".to_string()),
+        ]);
+        assert_eq!((added, duplicates), (2, 2));
+        assert_eq!(engine.get_stats().total_entries, 3);
+        
+        let hash = TranslationRequest::compute_hash("Hello");
+        assert_eq!(engine.get_translation(hash, "Hello"), Some("Ciao".to_string()));
+        let hash = TranslationRequest::compute_hash("World");
+        assert_eq!(engine.get_translation(hash, "World"), Some("Mondo".to_string()));
+    }
+    
+    #[test]
+    fn test_replace_dictionary_swaps_whole_pair() {
+        let mut engine = DictionaryEngine::new();
+        engine.set_active_languages("en", "it");
+        engine.load_translations("en", "it", vec![
+            ("Hello".to_string(), "Ciao".to_string()),
+            ("World".to_string(), "Mondo".to_string()),
+        ]);
+        
+        let file = parse_json_dictionary(r#"{"source": "en", "target": "it", "translations": {"Hello": "Salve"}}"#, "en", "de").unwrap();
+        assert_eq!((file.source.as_str(), file.target.as_str()), ("en", "it"));
+        
+        let old = engine.replace_dictionary(&file.source, &file.target, LanguageDictionary::from_pairs(file.translations));
+        assert_eq!(old.map(|d| d.len()), Some(2));
+        
+        let hash = TranslationRequest::compute_hash("Hello");
+        assert_eq!(engine.get_translation(hash, "Hello"), Some("Salve".to_string()));
+        let hash = TranslationRequest::compute_hash("World");
+        assert_eq!(engine.get_translation(hash, "World"), None);
+        
+        assert!(parse_json_dictionary("{ non valido", "en", "it").is_err());
+    }
+    
     #[test]
     fn test_dictionary_not_found() {
         let engine = DictionaryEngine::new();
@@ -396,4 +505,29 @@ mod tests {
         engine.set_active_languages("en", "de");
         assert_eq!(engine.get_translation(hash, "Hello"), Some("Hallo".to_string()));
     }
+    
+    #[test]
+    fn test_normalized_text_fallback() {
+        let mut engine = DictionaryEngine::new();
+        engine.set_active_languages("en", "it");
+        engine.add_translation("Open the door".to_string(), "Apri la porta".to_string());
+        
+        let text = "Open  the door\r\n";
+        let hash = TranslationRequest::compute_hash(text);
+        assert_eq!(engine.get_translation(hash, text), Some("Apri la porta".to_string()));
+        assert_eq!(engine.get_translation_for("en", "it", text), Some("Apri la porta".to_string()));
+    }
+    
+    #[test]
+    fn test_typography_fallback() {
+        crate::string_id::set_typography_normalization(true);
+        let mut engine = DictionaryEngine::new();
+        engine.set_active_languages("en", "it");
+        engine.add_translation("Don't go... now - ok?".to_string(), "Non andare... ora - ok?".to_string());
+        
+        let text = "Don\u{2019}t go\u{2026} now \u{2014} ok?";
+        let hash = TranslationRequest::compute_hash(text);
+        assert_eq!(engine.get_translation(hash, text), Some("Non andare... ora - ok?".to_string()));
+        assert_eq!(engine.get_translation_for("en", "it", text), Some("Non andare... ora - ok?".to_string()));
+    }
 }
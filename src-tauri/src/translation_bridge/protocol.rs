@@ -2,14 +2,48 @@
 //! 
 //! Definisce il formato dei messaggi scambiati tra il plugin C# (Satellite)
 //! e il backend Rust attraverso la shared memory.
+//! 
+//! Versioni del protocollo (`PROTOCOL_VERSION`):
+//! - 1: header, ring buffer e slot
+//! - 2: handshake nell'header condiviso. Il plugin scrive `client_capabilities` e poi
+//!   `client_version`; il backend risponde con `handshake_status` e
+//!   `negotiated_capabilities`. Le versioni diverse vengono rifiutate prima di scambiare
+//!   traduzioni. Il nome della shared memory contiene la versione, così un plugin v1
+//!   non apre un buffer con un layout che non conosce
 
 use serde::{Deserialize, Serialize};
 
 /// Magic number per identificare il buffer GameStringer
 pub const MAGIC_NUMBER: u32 = 0x47535452; // "GSTR"
 
-/// Versione del protocollo
-pub const PROTOCOL_VERSION: u8 = 1;
+/// Versione del protocollo (da incrementare a ogni modifica del formato, vedi intestazione)
+pub const PROTOCOL_VERSION: u8 = 2;
+
+/// Versione minima del plugin accettata dall'handshake
+pub const MIN_SUPPORTED_PROTOCOL_VERSION: u8 = 2;
+
+/// Capability: le richieste includono il contesto (es. nome del GameObject)
+pub const CAP_CONTEXT: u32 = 1 << 0;
+/// Capability: le richieste includono la lingua sorgente
+pub const CAP_SOURCE_LANGUAGE: u32 = 1 << 1;
+/// Capability: il plugin aggiorna le statistiche nell'header condiviso
+pub const CAP_HEADER_STATS: u32 = 1 << 2;
+/// Capability: il plugin ricarica le traduzioni senza riavviare il gioco
+pub const CAP_HOT_RELOAD: u32 = 1 << 3;
+
+/// Capability supportate dal backend Rust
+pub const SERVER_CAPABILITIES: u32 = CAP_CONTEXT | CAP_SOURCE_LANGUAGE | CAP_HEADER_STATS | CAP_HOT_RELOAD;
+
+/// Nome della shared memory (deve coincidere con `SHMEM_NAME` nel plugin C#)
+#[allow(dead_code)]
+pub const SHARED_MEMORY_NAME: &str = "GameStringer_TranslationBridge_v2";
+
+/// `handshake_status`: il plugin non si è ancora presentato o attende la risposta
+pub const HANDSHAKE_PENDING: u8 = 0;
+/// `handshake_status`: versione compatibile, le capability negoziate sono valide
+pub const HANDSHAKE_ACCEPTED: u8 = 1;
+/// `handshake_status`: versione incompatibile, il plugin non deve inviare richieste
+pub const HANDSHAKE_REJECTED: u8 = 2;
 
 /// Dimensione massima di una stringa (64KB)
 #[allow(dead_code)]
@@ -22,24 +56,33 @@ pub const RING_BUFFER_SIZE: usize = 4 * 1024 * 1024;
 /// Numero massimo di slot nel ring buffer
 pub const MAX_SLOTS: usize = 1024;
 
-/// Header del buffer condiviso
+/// Header del buffer condiviso. Gli offset dei campi sono letti dal plugin C#:
+/// vanno aggiornati in entrambi i lati insieme a `PROTOCOL_VERSION`
 #[repr(C)]
 #[derive(Debug, Clone, Copy)]
 pub struct SharedMemoryHeader {
-    /// Magic number per validazione
+    /// Magic number per validazione (offset 0)
     pub magic: u32,
-    /// Versione del protocollo
+    /// Versione del protocollo del backend (offset 4)
     pub version: u8,
-    /// Flag: 1 = server attivo
+    /// Flag: 1 = server attivo (offset 5)
     pub server_active: u8,
-    /// Padding per allineamento
-    pub _padding: [u8; 2],
+    /// Versione del protocollo del plugin, 0 = nessun handshake (offset 6, scritto da C#)
+    pub client_version: u8,
+    /// `HANDSHAKE_*` (offset 7, scritto da Rust)
+    pub handshake_status: u8,
     /// Indice di scrittura (C# scrive qui)
     pub write_index: u32,
     /// Indice di lettura (Rust legge qui)
     pub read_index: u32,
     /// Numero di slot attivi
     pub slot_count: u32,
+    /// Capability del backend (offset 20)
+    pub server_capabilities: u32,
+    /// Capability del plugin (offset 24, scritto da C# prima di `client_version`)
+    pub client_capabilities: u32,
+    /// Capability attive dopo l'handshake (offset 28)
+    pub negotiated_capabilities: u32,
     /// Statistiche: richieste totali
     pub total_requests: u64,
     /// Statistiche: traduzioni trovate (cache hit)
@@ -54,20 +97,44 @@ impl SharedMemoryHeader {
             magic: MAGIC_NUMBER,
             version: PROTOCOL_VERSION,
             server_active: 0,
-            _padding: [0; 2],
+            client_version: 0,
+            handshake_status: HANDSHAKE_PENDING,
             write_index: 0,
             read_index: 0,
             slot_count: MAX_SLOTS as u32,
+            server_capabilities: SERVER_CAPABILITIES,
+            client_capabilities: 0,
+            negotiated_capabilities: 0,
             total_requests: 0,
             cache_hits: 0,
             cache_misses: 0,
         }
     }
     
+    /// Buffer creato da questo backend (la versione è quella del server, non del plugin)
     #[allow(dead_code)]
     pub fn is_valid(&self) -> bool {
         self.magic == MAGIC_NUMBER && self.version == PROTOCOL_VERSION
     }
+    
+    /// Handshake scritto dal plugin e non ancora valutato
+    pub fn pending_handshake(&self) -> Option<HandshakeRequest> {
+        if self.client_version == 0 || self.handshake_status != HANDSHAKE_PENDING {
+            return None;
+        }
+        Some(HandshakeRequest {
+            magic: self.magic,
+            protocol_version: self.client_version,
+            capabilities: self.client_capabilities,
+            plugin_version: None,
+        })
+    }
+    
+    /// Scrive l'esito dell'handshake per il plugin
+    pub fn apply_handshake(&mut self, response: &HandshakeResponse) {
+        self.negotiated_capabilities = response.capabilities;
+        self.handshake_status = if response.accepted { HANDSHAKE_ACCEPTED } else { HANDSHAKE_REJECTED };
+    }
 }
 
 impl Default for SharedMemoryHeader {
@@ -158,6 +225,68 @@ impl Default for TranslationSlot {
     }
 }
 
+/// Primo messaggio del plugin: identifica il protocollo che parla
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandshakeRequest {
+    /// Deve valere `MAGIC_NUMBER`
+    pub magic: u32,
+    pub protocol_version: u8,
+    /// Capability del plugin (`CAP_*`)
+    #[serde(default)]
+    pub capabilities: u32,
+    /// Versione del plugin (solo diagnostica)
+    #[serde(default)]
+    pub plugin_version: Option<String>,
+}
+
+/// Risposta all'handshake: se rifiutato, il plugin non deve inviare richieste
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandshakeResponse {
+    pub accepted: bool,
+    /// Versione del protocollo del backend
+    pub protocol_version: u8,
+    /// Capability attive: intersezione tra plugin e backend
+    pub capabilities: u32,
+    /// Motivo del rifiuto
+    pub error: Option<String>,
+}
+
+impl HandshakeResponse {
+    fn rejected(error: String) -> Self {
+        Self {
+            accepted: false,
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: 0,
+            error: Some(error),
+        }
+    }
+}
+
+/// Verifica la compatibilità del plugin e negozia le capability
+pub fn negotiate_handshake(request: &HandshakeRequest) -> HandshakeResponse {
+    if request.magic != MAGIC_NUMBER {
+        return HandshakeResponse::rejected(format!(
+            "Magic number non valido (0x{:08X}): il client non è un plugin GameStringer", request.magic));
+    }
+    let plugin = request.plugin_version.as_deref().unwrap_or("sconosciuta");
+    if request.protocol_version < MIN_SUPPORTED_PROTOCOL_VERSION {
+        return HandshakeResponse::rejected(format!(
+            "Plugin troppo vecchio (versione {}, protocollo {}): GameStringer richiede il protocollo {}. Aggiorna il plugin nel gioco.",
+            plugin, request.protocol_version, PROTOCOL_VERSION));
+    }
+    if request.protocol_version > PROTOCOL_VERSION {
+        return HandshakeResponse::rejected(format!(
+            "Plugin più recente di GameStringer (versione {}, protocollo {}, supportato {}). Aggiorna GameStringer.",
+            plugin, request.protocol_version, PROTOCOL_VERSION));
+    }
+    HandshakeResponse {
+        accepted: true,
+        protocol_version: PROTOCOL_VERSION,
+        capabilities: request.capabilities & SERVER_CAPABILITIES,
+        error: None,
+    }
+}
+
 /// Richiesta di traduzione (usata internamente in Rust)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TranslationRequest {
@@ -252,6 +381,71 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
     
+    fn handshake(version: u8, capabilities: u32) -> HandshakeRequest {
+        HandshakeRequest { magic: MAGIC_NUMBER, protocol_version: version, capabilities, plugin_version: Some("1.0.0".to_string()) }
+    }
+    
+    #[test]
+    fn test_handshake_accepts_current_version() {
+        let response = negotiate_handshake(&handshake(PROTOCOL_VERSION, CAP_CONTEXT | CAP_HOT_RELOAD | (1 << 31)));
+        assert!(response.accepted);
+        assert_eq!(response.error, None);
+        // Le capability sconosciute al backend vengono scartate
+        assert_eq!(response.capabilities, CAP_CONTEXT | CAP_HOT_RELOAD);
+    }
+    
+    #[test]
+    fn test_handshake_rejects_mismatches() {
+        let old = negotiate_handshake(&handshake(MIN_SUPPORTED_PROTOCOL_VERSION - 1, SERVER_CAPABILITIES));
+        assert!(!old.accepted);
+        assert_eq!(old.capabilities, 0);
+        assert!(old.error.unwrap().contains("Aggiorna il plugin"));
+        
+        let newer = negotiate_handshake(&handshake(PROTOCOL_VERSION + 1, 0));
+        assert!(!newer.accepted);
+        assert!(newer.error.unwrap().contains("Aggiorna GameStringer"));
+        
+        let foreign = negotiate_handshake(&HandshakeRequest { magic: 0xDEADBEEF, ..handshake(PROTOCOL_VERSION, 0) });
+        assert!(!foreign.accepted);
+    }
+    
+    #[test]
+    fn test_header_layout_matches_plugin_offsets() {
+        let header = SharedMemoryHeader::new();
+        let base = &header as *const SharedMemoryHeader as usize;
+        let offset = |field: *const u8| field as usize - base;
+        assert_eq!(offset(std::ptr::addr_of!(header.version)), 4);
+        assert_eq!(offset(std::ptr::addr_of!(header.client_version)), 6);
+        assert_eq!(offset(std::ptr::addr_of!(header.handshake_status)), 7);
+        assert_eq!(offset(std::ptr::addr_of!(header.server_capabilities) as *const u8), 20);
+        assert_eq!(offset(std::ptr::addr_of!(header.client_capabilities) as *const u8), 24);
+        assert_eq!(offset(std::ptr::addr_of!(header.negotiated_capabilities) as *const u8), 28);
+        assert_eq!(offset(std::ptr::addr_of!(header.total_requests) as *const u8), 32);
+    }
+    
+    #[test]
+    fn test_header_handshake() {
+        let mut header = SharedMemoryHeader::new();
+        assert!(header.is_valid());
+        assert_eq!(header.pending_handshake(), None);
+        
+        header.client_capabilities = CAP_CONTEXT | (1 << 31);
+        header.client_version = PROTOCOL_VERSION;
+        let request = header.pending_handshake().unwrap();
+        header.apply_handshake(&negotiate_handshake(&request));
+        assert_eq!(header.handshake_status, HANDSHAKE_ACCEPTED);
+        assert_eq!(header.negotiated_capabilities, CAP_CONTEXT);
+        // Già valutato: non viene riproposto
+        assert_eq!(header.pending_handshake(), None);
+        
+        let mut header = SharedMemoryHeader::new();
+        header.client_version = 1;
+        let request = header.pending_handshake().unwrap();
+        header.apply_handshake(&negotiate_handshake(&request));
+        assert_eq!(header.handshake_status, HANDSHAKE_REJECTED);
+        assert_eq!(header.negotiated_capabilities, 0);
+    }
+    
     #[test]
     fn test_hash_different() {
         let hash1 = TranslationRequest::compute_hash("Hello");
@@ -4,16 +4,25 @@
 //! - Dictionary engine thread-safe per lookup O(1)
 //! - Statistiche in tempo reale
 //! - Hot-reload senza riavvio
+//! - Handshake con il plugin nell'header della shared memory creata all'avvio
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{fence, AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use parking_lot::RwLock;
 use serde::Serialize;
-use tracing::info;
+use tracing::{info, warn};
 
-use super::dictionary_engine::DictionaryEngine;
+use super::dictionary_engine::{read_json_dictionary, DictionaryEngine, LanguageDictionary};
+use super::protocol::{
+    negotiate_handshake, HandshakeRequest, HandshakeResponse, SharedMemoryHeader, TranslationSlot,
+    MAX_SLOTS, RING_BUFFER_SIZE,
+};
+
+/// Intervallo di lettura dell'header condiviso (il plugin attende la risposta fino a 2 s)
+const HEADER_POLL_INTERVAL: Duration = Duration::from_millis(20);
 
 /// Translation Bridge - Backend per traduzione in-game
 /// 
@@ -27,6 +36,30 @@ pub struct TranslationBridge {
     stats: Arc<RwLock<BridgeStats>>,
     /// Timestamp di avvio
     start_time: Option<Instant>,
+    /// Ultimo handshake del plugin (None finché il plugin non si presenta)
+    handshake: Arc<RwLock<Option<PluginHandshake>>>,
+    /// Shared memory aperta dal plugin (presente mentre il server è attivo)
+    shared: Option<Arc<SharedRegion>>,
+    /// Thread che legge l'header condiviso e risponde all'handshake
+    header_poller: Option<JoinHandle<()>>,
+}
+
+/// Esito della ricarica di un dizionario
+#[derive(Debug, Clone, Serialize)]
+pub struct DictionaryReload {
+    pub source: String,
+    pub target: String,
+    pub old_entries: usize,
+    pub new_entries: usize,
+}
+
+/// Esito dell'handshake con il plugin C#
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginHandshake {
+    pub request: HandshakeRequest,
+    pub response: HandshakeResponse,
+    /// Momento dell'handshake (RFC 3339)
+    pub at: String,
 }
 
 /// Statistiche del bridge
@@ -48,18 +81,47 @@ impl TranslationBridge {
             running: Arc::new(AtomicBool::new(false)),
             stats: Arc::new(RwLock::new(BridgeStats::default())),
             start_time: None,
+            handshake: Arc::new(RwLock::new(None)),
+            shared: None,
+            header_poller: None,
         }
     }
     
-    /// Avvia il server di traduzione
+    /// Avvia il server di traduzione: crea la shared memory con l'header e avvia
+    /// il thread che risponde all'handshake del plugin
     pub fn start(&mut self) -> Result<(), String> {
         if self.running.load(Ordering::SeqCst) {
             return Err("Server già in esecuzione".to_string());
         }
         
+        let shared = Arc::new(SharedRegion::create()?);
+        let mut header = SharedMemoryHeader::new();
+        header.server_active = 1;
+        shared.write_header(&header);
+        
         self.running.store(true, Ordering::SeqCst);
         self.start_time = Some(Instant::now());
         
+        let running = Arc::clone(&self.running);
+        let handshake = Arc::clone(&self.handshake);
+        let region = Arc::clone(&shared);
+        let poller = std::thread::Builder::new()
+            .name("translation-bridge-header".to_string())
+            .spawn(move || {
+                while running.load(Ordering::SeqCst) {
+                    poll_shared_header(&region, &handshake);
+                    std::thread::sleep(HEADER_POLL_INTERVAL);
+                }
+            })
+            .map_err(|e| {
+                self.running.store(false, Ordering::SeqCst);
+                self.start_time = None;
+                format!("Impossibile avviare il thread dell'header condiviso: {}", e)
+            })?;
+        
+        self.shared = Some(shared);
+        self.header_poller = Some(poller);
+        
         info!("[TranslationBridge] ✅ Server di traduzione avviato");
         Ok(())
     }
@@ -71,7 +133,15 @@ impl TranslationBridge {
         }
         
         self.running.store(false, Ordering::SeqCst);
+        if let Some(poller) = self.header_poller.take() {
+            let _ = poller.join();
+        }
+        if let Some(shared) = self.shared.take() {
+            shared.set_server_active(false);
+        }
         self.start_time = None;
+        // Al prossimo avvio il plugin deve ripresentarsi
+        *self.handshake.write() = None;
         
         info!("[TranslationBridge] ✅ Server arrestato");
     }
@@ -90,9 +160,38 @@ impl TranslationBridge {
         dict.load_from_json(path)
     }
     
+    /// Ricarica da file il dizionario di una coppia di lingue senza mai esporre uno stato parziale:
+    /// il nuovo dizionario viene costruito fuori dal lock e poi scambiato in un colpo solo.
+    /// Se il file non è valido il dizionario in uso resta invariato.
+    pub fn reload_dictionary_from_file(&self, path: &str) -> Result<DictionaryReload, String> {
+        let (default_source, default_target) = self.dictionary.read().active_languages();
+        let file = read_json_dictionary(path, &default_source, &default_target)?;
+        let fresh = LanguageDictionary::from_pairs(file.translations);
+        let new_entries = fresh.len();
+        
+        // Il write lock dura solo lo scambio; il vecchio dizionario viene liberato dopo
+        let old = self.dictionary.write().replace_dictionary(&file.source, &file.target, fresh);
+        let old_entries = old.map_or(0, |dict| dict.len());
+        
+        info!("[TranslationBridge] 🔄 Dizionario {} -> {} ricaricato: {} -> {} traduzioni",
+            file.source, file.target, old_entries, new_entries);
+        Ok(DictionaryReload { source: file.source, target: file.target, old_entries, new_entries })
+    }
+    
+    /// Ultimo handshake del plugin
+    pub fn plugin_handshake(&self) -> Option<PluginHandshake> {
+        self.handshake.read().clone()
+    }
+    
     /// Cerca una traduzione
     #[allow(dead_code)]
     pub fn translate(&self, text: &str) -> Option<String> {
+        // Plugin incompatibile: meglio nessuna traduzione che dati letti con il formato sbagliato
+        if self.handshake.read().as_ref().is_some_and(|h| !h.response.accepted) {
+            self.stats.write().errors += 1;
+            return None;
+        }
+        
         let start = Instant::now();
         let dict = self.dictionary.read();
         
@@ -149,6 +248,146 @@ impl Default for TranslationBridge {
     }
 }
 
+impl Drop for TranslationBridge {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Registra l'handshake del plugin: le versioni incompatibili vengono rifiutate
+fn record_handshake(slot: &RwLock<Option<PluginHandshake>>, request: HandshakeRequest) -> HandshakeResponse {
+    let response = negotiate_handshake(&request);
+    if response.accepted {
+        info!("[TranslationBridge] 🤝 Plugin connesso (versione {}, protocollo {}, capability 0x{:X})",
+            request.plugin_version.as_deref().unwrap_or("?"), request.protocol_version, response.capabilities);
+    } else {
+        warn!("[TranslationBridge] ❌ Handshake rifiutato: {}", response.error.as_deref().unwrap_or("?"));
+    }
+    *slot.write() = Some(PluginHandshake {
+        request,
+        response: response.clone(),
+        at: chrono::Utc::now().to_rfc3339(),
+    });
+    response
+}
+
+/// Valuta l'handshake scritto dal plugin nell'header condiviso e vi scrive la risposta.
+/// None se non c'è nulla di nuovo
+fn poll_shared_header(region: &SharedRegion, slot: &RwLock<Option<PluginHandshake>>) -> Option<HandshakeResponse> {
+    let mut header = region.read_header();
+    let request = header.pending_handshake()?;
+    let response = record_handshake(slot, request);
+    header.apply_handshake(&response);
+    region.write_handshake_result(&header);
+    Some(response)
+}
+
+/// Dimensione della shared memory: header, slot e ring buffer dei dati
+const SHARED_MEMORY_SIZE: usize = std::mem::size_of::<SharedMemoryHeader>()
+    + MAX_SLOTS * std::mem::size_of::<TranslationSlot>()
+    + RING_BUFFER_SIZE;
+
+/// Shared memory con il plugin C#. Su Windows è il mapping nominato `SHARED_MEMORY_NAME`
+/// che il plugin apre con `OpenExisting`; sugli altri sistemi (test) è memoria del processo
+/// con lo stesso layout. Il plugin scrive nell'header in parallelo: si accede solo con
+/// letture/scritture volatili e si scrivono solo i campi di competenza del backend.
+struct SharedRegion {
+    header: *mut SharedMemoryHeader,
+    #[cfg(windows)]
+    mapping: winapi::um::winnt::HANDLE,
+    #[cfg(not(windows))]
+    _buffer: Vec<u64>,
+}
+
+// Il puntatore resta valido finché la regione vive; gli accessi sono volatili
+unsafe impl Send for SharedRegion {}
+unsafe impl Sync for SharedRegion {}
+
+impl SharedRegion {
+    #[cfg(windows)]
+    fn create() -> Result<Self, String> {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+        use winapi::um::memoryapi::{CreateFileMappingW, MapViewOfFile, FILE_MAP_ALL_ACCESS};
+        use winapi::um::winnt::PAGE_READWRITE;
+        
+        let name: Vec<u16> = std::ffi::OsStr::new(super::protocol::SHARED_MEMORY_NAME)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        unsafe {
+            let mapping = CreateFileMappingW(
+                INVALID_HANDLE_VALUE,
+                std::ptr::null_mut(),
+                PAGE_READWRITE,
+                (SHARED_MEMORY_SIZE as u64 >> 32) as u32,
+                SHARED_MEMORY_SIZE as u32,
+                name.as_ptr(),
+            );
+            if mapping.is_null() {
+                return Err(format!("Impossibile creare la shared memory: {}", std::io::Error::last_os_error()));
+            }
+            let view = MapViewOfFile(mapping, FILE_MAP_ALL_ACCESS, 0, 0, SHARED_MEMORY_SIZE);
+            if view.is_null() {
+                let error = std::io::Error::last_os_error();
+                CloseHandle(mapping);
+                return Err(format!("Impossibile mappare la shared memory: {}", error));
+            }
+            Ok(Self { header: view as *mut SharedMemoryHeader, mapping })
+        }
+    }
+    
+    #[cfg(not(windows))]
+    fn create() -> Result<Self, String> {
+        let mut buffer = vec![0u64; SHARED_MEMORY_SIZE.div_ceil(8)];
+        let header = buffer.as_mut_ptr() as *mut SharedMemoryHeader;
+        Ok(Self { header, _buffer: buffer })
+    }
+    
+    fn read_header(&self) -> SharedMemoryHeader {
+        unsafe { std::ptr::read_volatile(self.header) }
+    }
+    
+    /// Scrive l'header completo: solo alla creazione, prima che il plugin possa aprirlo
+    fn write_header(&self, header: &SharedMemoryHeader) {
+        unsafe { std::ptr::write_volatile(self.header, *header) }
+    }
+    
+    /// Pubblica l'esito dell'handshake: le capability prima dello stato, che il plugin attende
+    fn write_handshake_result(&self, header: &SharedMemoryHeader) {
+        unsafe {
+            std::ptr::write_volatile(std::ptr::addr_of_mut!((*self.header).negotiated_capabilities), header.negotiated_capabilities);
+            fence(Ordering::SeqCst);
+            std::ptr::write_volatile(std::ptr::addr_of_mut!((*self.header).handshake_status), header.handshake_status);
+        }
+    }
+    
+    fn set_server_active(&self, active: bool) {
+        unsafe { std::ptr::write_volatile(std::ptr::addr_of_mut!((*self.header).server_active), active as u8) }
+    }
+    
+    /// Scrive i campi del plugin come farebbe `Plugin.cs` (capability prima della versione)
+    #[cfg(test)]
+    fn write_client_hello(&self, version: u8, capabilities: u32) {
+        unsafe {
+            std::ptr::write_volatile(std::ptr::addr_of_mut!((*self.header).client_capabilities), capabilities);
+            fence(Ordering::SeqCst);
+            std::ptr::write_volatile(std::ptr::addr_of_mut!((*self.header).handshake_status), super::protocol::HANDSHAKE_PENDING);
+            std::ptr::write_volatile(std::ptr::addr_of_mut!((*self.header).client_version), version);
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for SharedRegion {
+    fn drop(&mut self) {
+        unsafe {
+            winapi::um::memoryapi::UnmapViewOfFile(self.header as *const _);
+            winapi::um::handleapi::CloseHandle(self.mapping);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,6 +407,56 @@ mod tests {
         assert!(!bridge.is_running());
     }
     
+    /// Attende che il thread dell'header risponda, come fa il plugin (timeout 2 s)
+    fn wait_handshake_status(bridge: &TranslationBridge) -> u8 {
+        use crate::translation_bridge::protocol::HANDSHAKE_PENDING;
+        
+        let shared = bridge.shared.as_ref().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(2);
+        loop {
+            let status = shared.read_header().handshake_status;
+            if status != HANDSHAKE_PENDING || Instant::now() > deadline {
+                return status;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+    
+    #[test]
+    fn test_incompatible_plugin_is_rejected() {
+        use crate::translation_bridge::protocol::{
+            CAP_CONTEXT, CAP_HOT_RELOAD, HANDSHAKE_ACCEPTED, HANDSHAKE_REJECTED, PROTOCOL_VERSION,
+        };
+        
+        let mut bridge = TranslationBridge::new();
+        bridge.start().unwrap();
+        bridge.load_dictionary("en", "it", vec![("Hello".to_string(), "Ciao".to_string())]);
+        bridge.dictionary().write().set_active_languages("en", "it");
+        
+        // Header pubblicato all'avvio, in attesa del plugin
+        let header = bridge.shared.as_ref().unwrap().read_header();
+        assert!(header.is_valid());
+        assert_eq!(header.server_active, 1);
+        assert!(bridge.plugin_handshake().is_none());
+        
+        // Plugin con protocollo più recente: rifiutato dal thread dell'header
+        bridge.shared.as_ref().unwrap().write_client_hello(PROTOCOL_VERSION + 1, CAP_CONTEXT);
+        assert_eq!(wait_handshake_status(&bridge), HANDSHAKE_REJECTED);
+        assert!(!bridge.plugin_handshake().unwrap().response.accepted);
+        assert_eq!(bridge.translate("Hello"), None);
+        assert_eq!(bridge.get_stats().errors, 1);
+        
+        // Il plugin aggiornato si ripresenta sullo stesso header
+        bridge.shared.as_ref().unwrap().write_client_hello(PROTOCOL_VERSION, CAP_CONTEXT | CAP_HOT_RELOAD | (1 << 31));
+        assert_eq!(wait_handshake_status(&bridge), HANDSHAKE_ACCEPTED);
+        assert_eq!(bridge.shared.as_ref().unwrap().read_header().negotiated_capabilities, CAP_CONTEXT | CAP_HOT_RELOAD);
+        assert_eq!(bridge.translate("Hello"), Some("Ciao".to_string()));
+        
+        bridge.stop();
+        assert!(bridge.shared.is_none());
+        assert!(bridge.plugin_handshake().is_none());
+    }
+    
     #[test]
     fn test_dictionary_loading() {
         let bridge = TranslationBridge::new();
@@ -178,4 +467,24 @@ mod tests {
         let count = bridge.load_dictionary("en", "it", translations);
         assert_eq!(count, 2);
     }
+    
+    #[test]
+    fn test_reload_from_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("bridge_reload.json");
+        std::fs::write(&path, r#"{"Hello": "Salve", "Door": "Porta"}"#).unwrap();
+        let path_str = path.to_string_lossy().to_string();
+        
+        let bridge = TranslationBridge::new();
+        bridge.load_dictionary("en", "it", vec![("Hello".to_string(), "Ciao".to_string())]);
+        
+        let reload = bridge.reload_dictionary_from_file(&path_str).unwrap();
+        assert_eq!((reload.old_entries, reload.new_entries), (1, 2));
+        assert_eq!(bridge.translate("Hello"), Some("Salve".to_string()));
+        
+        // File rovinato a metà salvataggio: il dizionario in uso non cambia
+        std::fs::write(&path, r#"{"Hello": "#).unwrap();
+        assert!(bridge.reload_dictionary_from_file(&path_str).is_err());
+        assert_eq!(bridge.translate("Door"), Some("Porta".to_string()));
+    }
 }
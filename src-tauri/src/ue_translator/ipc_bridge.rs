@@ -7,7 +7,10 @@ use std::collections::HashMap;
 use tokio::sync::mpsc;
 use serde::{Deserialize, Serialize};
 
-use super::{TranslationRequest, TranslationResponse};
+use crate::commands::glossary::GameGlossary;
+use crate::commands::translation::{load_offline_glossary, translate_offline_text};
+
+use super::{TranslationRequest, TranslationResponse, UETranslatorConfig};
 
 /// Nome del pipe per la comunicazione
 pub const PIPE_NAME: &str = r"\\.\pipe\GameStringerUETranslator";
@@ -26,6 +29,9 @@ pub struct IPCBridge {
     translation_cache: Arc<Mutex<HashMap<String, String>>>,
     request_tx: Option<mpsc::Sender<TranslationRequest>>,
     response_rx: Option<mpsc::Receiver<TranslationResponse>>,
+    config: UETranslatorConfig,
+    /// Glossario del gioco, caricato una volta in `set_config`
+    glossary: Option<GameGlossary>,
 }
 
 impl IPCBridge {
@@ -39,7 +45,18 @@ impl IPCBridge {
             translation_cache: Arc::new(Mutex::new(HashMap::new())),
             request_tx: None,
             response_rx: None,
+            config: UETranslatorConfig::default(),
+            glossary: None,
+        }
+    }
+    
+    /// Imposta lingue e opzioni del translator e carica il glossario del gioco
+    pub fn set_config(&mut self, config: UETranslatorConfig, game_id: Option<&str>) {
+        self.glossary = load_offline_glossary(game_id, &config.source_language, &config.target_language);
+        if let Some(glossary) = &self.glossary {
+            log::info!("📖 Glossario caricato per {}: {} voci", glossary.game_name, glossary.entries.len());
         }
+        self.config = config;
     }
     
     /// Avvia il server IPC
@@ -92,8 +109,8 @@ impl IPCBridge {
         // Traduci usando il servizio di traduzione di GameStringer
         let translated = self.translate_text(original, &request.context).await;
         
-        // Salva in cache
-        {
+        // Salva in cache (solo se tradotto: i testi senza match vengono ritentati)
+        if self.config.cache_enabled && &translated != original {
             let mut cache = self.translation_cache.lock().unwrap();
             cache.insert(original.clone(), translated.clone());
         }
@@ -105,14 +122,20 @@ impl IPCBridge {
         }
     }
     
-    /// Traduce testo usando il servizio configurato
+    /// Traduce testo con la traduzione offline (TM, glossario, dizionario integrato)
     async fn translate_text(&self, text: &str, _context: &Option<String>) -> String {
-        // Per ora placeholder - verrà collegato al neural translator
         log::info!("🌐 Traduzione richiesta: {}", truncate_text(text, 50));
         
-        // TODO: Collegare al NeuralTranslator o Google Translate
-        // Per ora ritorna il testo originale con marker
-        format!("[IT] {}", text)
+        let result = translate_offline_text(
+            text,
+            &self.config.source_language,
+            &self.config.target_language,
+            self.glossary.as_ref(),
+            None,
+        );
+        
+        // Nessun match: il gioco mostra il testo originale
+        result.translated.unwrap_or_else(|| text.to_string())
     }
     
     /// Avvia server Named Pipe su Windows
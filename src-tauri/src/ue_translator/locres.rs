@@ -0,0 +1,433 @@
+//! Lettura/scrittura dei file `.locres` di Unreal Engine
+//!
+//! Formato (FTextLocalizationResource):
+//! - Legacy (UE < 4.17): nessun header, stringhe localizzate inline per ogni chiave
+//! - Versionato: magic GUID + byte di versione, poi namespace/chiavi con indice
+//!   nella tabella di stringhe localizzate (salvata in coda, offset nell'header)
+//!   * 1 Compact: tabella stringhe deduplicata
+//!   * 2 Optimized_CRC32: hash CRC32 di namespace/chiave e refcount delle stringhe
+//!   * 3 Optimized_CityHash64_UTF16: come 2 ma hash CityHash64
+//!
+//! In scrittura produciamo Legacy, Compact o Optimized_CRC32: le versioni recenti
+//! dell'engine ricalcolano gli hash quando caricano un file più vecchio, quindi un
+//! file v3 viene riscritto come v2 senza perdere compatibilità.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+/// Magic GUID dei file locres versionati (4 × u32 little endian)
+const LOCRES_MAGIC: [u32; 4] = [0x7574140E, 0xFC034A67, 0x9D90154A, 0x1B7F37C3];
+
+/// Limite di sicurezza per lunghezze/conteggi letti dal file (evita allocazioni enormi su file corrotti)
+const MAX_ITEMS: u32 = 10_000_000;
+
+/// Versione del formato locres
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocresVersion {
+    Legacy = 0,
+    Compact = 1,
+    OptimizedCrc32 = 2,
+    OptimizedCityHash64 = 3,
+}
+
+impl LocresVersion {
+    fn from_byte(value: u8) -> Result<Self, String> {
+        match value {
+            0 => Ok(Self::Legacy),
+            1 => Ok(Self::Compact),
+            2 => Ok(Self::OptimizedCrc32),
+            3 => Ok(Self::OptimizedCityHash64),
+            v => Err(format!("Versione locres non supportata: {}", v)),
+        }
+    }
+}
+
+/// Singola voce traducibile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocresEntry {
+    pub namespace: String,
+    pub key: String,
+    /// Hash della stringa sorgente (usato dall'engine per capire se la traduzione è aggiornata)
+    pub source_string_hash: u32,
+    pub localized_string: String,
+}
+
+/// Contenuto completo di un file locres
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocresFile {
+    pub version: LocresVersion,
+    pub entries: Vec<LocresEntry>,
+}
+
+/// Legge le voci di un file `.locres`
+pub fn parse_locres(path: &Path) -> Result<Vec<LocresEntry>, String> {
+    parse_locres_file(path).map(|file| file.entries)
+}
+
+/// Legge un file `.locres` mantenendo la versione (per riscriverlo nello stesso formato)
+pub fn parse_locres_file(path: &Path) -> Result<LocresFile, String> {
+    let data = fs::read(path).map_err(|e| format!("Impossibile leggere {}: {}", path.display(), e))?;
+    parse_locres_bytes(&data)
+}
+
+/// Scrive le voci in un file `.locres` (formato Optimized_CRC32)
+pub fn write_locres(entries: &[LocresEntry], path: &Path) -> Result<(), String> {
+    write_locres_with_version(entries, path, LocresVersion::OptimizedCrc32)
+}
+
+/// Scrive le voci nel formato richiesto (CityHash64 viene scritto come Optimized_CRC32)
+pub fn write_locres_with_version(entries: &[LocresEntry], path: &Path, version: LocresVersion) -> Result<(), String> {
+    let data = locres_to_bytes(entries, version)?;
+    fs::write(path, data).map_err(|e| format!("Impossibile scrivere {}: {}", path.display(), e))
+}
+
+/// Hash della stringa sorgente come calcolato da UE (`FCrc::StrCrc32`)
+pub fn source_string_hash(text: &str) -> u32 {
+    str_crc32(text)
+}
+
+pub fn parse_locres_bytes(data: &[u8]) -> Result<LocresFile, String> {
+    let mut reader = Cursor::new(data);
+
+    let mut magic = [0u32; 4];
+    let has_magic = data.len() >= 17 && {
+        for part in magic.iter_mut() {
+            *part = read_u32(&mut reader)?;
+        }
+        magic == LOCRES_MAGIC
+    };
+
+    let version = if has_magic {
+        LocresVersion::from_byte(read_u8(&mut reader)?)?
+    } else {
+        reader.set_position(0);
+        LocresVersion::Legacy
+    };
+
+    // Tabella delle stringhe localizzate (solo formati versionati)
+    let mut localized_strings = Vec::new();
+    if version >= LocresVersion::Compact {
+        let offset = read_i64(&mut reader)?;
+        if offset < 0 || offset as u64 > data.len() as u64 {
+            return Err(format!("Offset tabella stringhe non valido: {}", offset));
+        }
+
+        let header_end = reader.position();
+        reader.set_position(offset as u64);
+        let count = read_count(&mut reader)?;
+        localized_strings.reserve(count as usize);
+        for _ in 0..count {
+            localized_strings.push(read_fstring(&mut reader)?);
+            if version >= LocresVersion::OptimizedCrc32 {
+                let _ref_count = read_i32(&mut reader)?;
+            }
+        }
+        reader.set_position(header_end);
+    }
+
+    if version >= LocresVersion::OptimizedCrc32 {
+        let _entries_count = read_u32(&mut reader)?;
+    }
+
+    let mut entries = Vec::new();
+    let namespace_count = read_count(&mut reader)?;
+    for _ in 0..namespace_count {
+        if version >= LocresVersion::OptimizedCrc32 {
+            let _namespace_hash = read_u32(&mut reader)?;
+        }
+        let namespace = read_fstring(&mut reader)?;
+
+        let key_count = read_count(&mut reader)?;
+        for _ in 0..key_count {
+            if version >= LocresVersion::OptimizedCrc32 {
+                let _key_hash = read_u32(&mut reader)?;
+            }
+            let key = read_fstring(&mut reader)?;
+            let source_string_hash = read_u32(&mut reader)?;
+
+            let localized_string = if version >= LocresVersion::Compact {
+                let index = read_i32(&mut reader)?;
+                usize::try_from(index).ok()
+                    .and_then(|i| localized_strings.get(i))
+                    .cloned()
+                    .ok_or_else(|| format!("Indice stringa non valido {} per {}::{}", index, namespace, key))?
+            } else {
+                read_fstring(&mut reader)?
+            };
+
+            entries.push(LocresEntry {
+                namespace: namespace.clone(),
+                key,
+                source_string_hash,
+                localized_string,
+            });
+        }
+    }
+
+    Ok(LocresFile { version, entries })
+}
+
+pub fn locres_to_bytes(entries: &[LocresEntry], version: LocresVersion) -> Result<Vec<u8>, String> {
+    let version = version.min(LocresVersion::OptimizedCrc32);
+
+    // Raggruppa per namespace mantenendo l'ordine di prima apparizione
+    let mut namespaces: Vec<(&str, Vec<&LocresEntry>)> = Vec::new();
+    for entry in entries {
+        match namespaces.iter_mut().find(|(ns, _)| *ns == entry.namespace) {
+            Some((_, keys)) => keys.push(entry),
+            None => namespaces.push((entry.namespace.as_str(), vec![entry])),
+        }
+    }
+
+    // Tabella stringhe deduplicata con refcount
+    let mut string_table: Vec<(&str, i32)> = Vec::new();
+    let mut string_index: HashMap<&str, usize> = HashMap::new();
+    for entry in entries {
+        let index = *string_index.entry(entry.localized_string.as_str()).or_insert_with(|| {
+            string_table.push((entry.localized_string.as_str(), 0));
+            string_table.len() - 1
+        });
+        string_table[index].1 += 1;
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    let mut offset_position = 0;
+
+    if version >= LocresVersion::Compact {
+        for part in LOCRES_MAGIC {
+            write_u32(&mut out, part)?;
+        }
+        write_bytes(&mut out, &[version as u8])?;
+        offset_position = out.position();
+        write_bytes(&mut out, &0i64.to_le_bytes())?;
+    }
+
+    if version >= LocresVersion::OptimizedCrc32 {
+        write_u32(&mut out, entries.len() as u32)?;
+    }
+
+    write_u32(&mut out, namespaces.len() as u32)?;
+    for (namespace, keys) in &namespaces {
+        if version >= LocresVersion::OptimizedCrc32 {
+            write_u32(&mut out, str_crc32(namespace))?;
+        }
+        write_fstring(&mut out, namespace)?;
+        write_u32(&mut out, keys.len() as u32)?;
+
+        for entry in keys {
+            if version >= LocresVersion::OptimizedCrc32 {
+                write_u32(&mut out, str_crc32(&entry.key))?;
+            }
+            write_fstring(&mut out, &entry.key)?;
+            write_u32(&mut out, entry.source_string_hash)?;
+
+            if version >= LocresVersion::Compact {
+                let index = string_index[entry.localized_string.as_str()];
+                write_bytes(&mut out, &(index as i32).to_le_bytes())?;
+            } else {
+                write_fstring(&mut out, &entry.localized_string)?;
+            }
+        }
+    }
+
+    if version >= LocresVersion::Compact {
+        let table_offset = out.position();
+        write_u32(&mut out, string_table.len() as u32)?;
+        for (text, ref_count) in &string_table {
+            write_fstring(&mut out, text)?;
+            if version >= LocresVersion::OptimizedCrc32 {
+                write_bytes(&mut out, &ref_count.to_le_bytes())?;
+            }
+        }
+
+        out.seek(SeekFrom::Start(offset_position)).map_err(|e| e.to_string())?;
+        write_bytes(&mut out, &(table_offset as i64).to_le_bytes())?;
+    }
+
+    Ok(out.into_inner())
+}
+
+/// `FCrc::StrCrc32`: CRC32 di ogni carattere UTF-16 esteso a 4 byte
+fn str_crc32(text: &str) -> u32 {
+    let mut crc = !0u32;
+    for unit in text.encode_utf16() {
+        let mut ch = unit as u32;
+        for _ in 0..4 {
+            crc = (crc >> 8) ^ CRC_TABLE[((crc ^ ch) & 0xFF) as usize];
+            ch >>= 8;
+        }
+    }
+    !crc
+}
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+// --- Primitive di lettura/scrittura (little endian) ---
+
+fn read_exact<const N: usize>(reader: &mut Cursor<&[u8]>) -> Result<[u8; N], String> {
+    let mut buf = [0u8; N];
+    reader.read_exact(&mut buf).map_err(|_| "File locres troncato".to_string())?;
+    Ok(buf)
+}
+
+fn read_u8(reader: &mut Cursor<&[u8]>) -> Result<u8, String> {
+    Ok(read_exact::<1>(reader)?[0])
+}
+
+fn read_u32(reader: &mut Cursor<&[u8]>) -> Result<u32, String> {
+    Ok(u32::from_le_bytes(read_exact(reader)?))
+}
+
+fn read_i32(reader: &mut Cursor<&[u8]>) -> Result<i32, String> {
+    Ok(i32::from_le_bytes(read_exact(reader)?))
+}
+
+fn read_i64(reader: &mut Cursor<&[u8]>) -> Result<i64, String> {
+    Ok(i64::from_le_bytes(read_exact(reader)?))
+}
+
+fn read_count(reader: &mut Cursor<&[u8]>) -> Result<u32, String> {
+    let count = read_u32(reader)?;
+    if count > MAX_ITEMS {
+        return Err(format!("Conteggio non valido nel file locres: {}", count));
+    }
+    Ok(count)
+}
+
+/// FString di UE: lunghezza positiva = Latin-1, negativa = UTF-16LE, terminatore incluso
+fn read_fstring(reader: &mut Cursor<&[u8]>) -> Result<String, String> {
+    let length = read_i32(reader)?;
+    if length.unsigned_abs() > MAX_ITEMS {
+        return Err(format!("Lunghezza stringa non valida: {}", length));
+    }
+
+    let mut text = if length > 0 {
+        let mut bytes = vec![0u8; length as usize];
+        reader.read_exact(&mut bytes).map_err(|_| "File locres troncato".to_string())?;
+        bytes.iter().map(|&b| b as char).collect::<String>()
+    } else if length < 0 {
+        let mut bytes = vec![0u8; length.unsigned_abs() as usize * 2];
+        reader.read_exact(&mut bytes).map_err(|_| "File locres troncato".to_string())?;
+        let units: Vec<u16> = bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        String::new()
+    };
+
+    if text.ends_with('\0') {
+        text.pop();
+    }
+    Ok(text)
+}
+
+fn write_bytes(out: &mut Cursor<Vec<u8>>, bytes: &[u8]) -> Result<(), String> {
+    out.write_all(bytes).map_err(|e| e.to_string())
+}
+
+fn write_u32(out: &mut Cursor<Vec<u8>>, value: u32) -> Result<(), String> {
+    write_bytes(out, &value.to_le_bytes())
+}
+
+fn write_fstring(out: &mut Cursor<Vec<u8>>, text: &str) -> Result<(), String> {
+    if text.is_empty() {
+        return write_bytes(out, &0i32.to_le_bytes());
+    }
+
+    if text.is_ascii() {
+        write_bytes(out, &(text.len() as i32 + 1).to_le_bytes())?;
+        write_bytes(out, text.as_bytes())?;
+        write_bytes(out, &[0])
+    } else {
+        let units: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+        write_bytes(out, &(-(units.len() as i32)).to_le_bytes())?;
+        for unit in units {
+            write_bytes(out, &unit.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_entries() -> Vec<LocresEntry> {
+        vec![
+            LocresEntry {
+                namespace: String::new(),
+                key: "MENU_START".to_string(),
+                source_string_hash: source_string_hash("Start"),
+                localized_string: "Inizia".to_string(),
+            },
+            LocresEntry {
+                namespace: "UI".to_string(),
+                key: "MENU_QUIT".to_string(),
+                source_string_hash: source_string_hash("Quit"),
+                localized_string: "Esci dal gioco — à bientôt".to_string(),
+            },
+            LocresEntry {
+                namespace: "UI".to_string(),
+                key: "MENU_EXIT".to_string(),
+                source_string_hash: source_string_hash("Exit"),
+                localized_string: "Inizia".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_roundtrip_all_writable_versions() {
+        for version in [LocresVersion::Legacy, LocresVersion::Compact, LocresVersion::OptimizedCrc32] {
+            let bytes = locres_to_bytes(&sample_entries(), version).unwrap();
+            let parsed = parse_locres_bytes(&bytes).unwrap();
+            assert_eq!(parsed.version, version);
+            assert_eq!(parsed.entries, sample_entries());
+        }
+    }
+
+    #[test]
+    fn test_cityhash_version_is_written_as_crc32() {
+        let bytes = locres_to_bytes(&sample_entries(), LocresVersion::OptimizedCityHash64).unwrap();
+        assert_eq!(parse_locres_bytes(&bytes).unwrap().version, LocresVersion::OptimizedCrc32);
+    }
+
+    #[test]
+    fn test_str_crc32_matches_ue() {
+        // Tabella CRC32 standard (vettore di riferimento "123456789")
+        assert_eq!(crc32_bytes(b"123456789"), 0xCBF43926);
+        // Ogni carattere viene esteso a 4 byte
+        assert_eq!(str_crc32(""), 0);
+        assert_eq!(str_crc32("a"), crc32_bytes(&[b'a', 0, 0, 0]));
+    }
+
+    fn crc32_bytes(bytes: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for &b in bytes {
+            crc = (crc >> 8) ^ CRC_TABLE[((crc ^ b as u32) & 0xFF) as usize];
+        }
+        !crc
+    }
+
+    #[test]
+    fn test_truncated_file_is_rejected() {
+        let bytes = locres_to_bytes(&sample_entries(), LocresVersion::OptimizedCrc32).unwrap();
+        assert!(parse_locres_bytes(&bytes[..bytes.len() - 3]).is_err());
+    }
+}
@@ -11,6 +11,7 @@
 
 pub mod injector;
 pub mod ipc_bridge;
+pub mod locres;
 pub mod translation_cache;
 
 use std::path::PathBuf;
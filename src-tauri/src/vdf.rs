@@ -0,0 +1,461 @@
+// 📜 Parser VDF (KeyValues testuale di Valve) tollerante
+// Un solo parser per ACF, libraryfolders, loginusers e localconfig: gestisce tab e
+// spazi, commenti `//`, condizionali `[$WIN32]`, virgolette e backslash escapati,
+// chiavi non quotate e file troncati (le graffe mancanti vengono chiuse in automatico).
+// Le chiavi sono confrontate senza distinzione maiuscole/minuscole, come fa Steam.
+
+use std::path::Path;
+
+/// Valore VDF: stringa o oggetto annidato
+#[derive(Debug, Clone, PartialEq)]
+pub enum VdfValue {
+    Str(String),
+    Obj(VdfObject),
+}
+
+impl VdfValue {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            VdfValue::Str(s) => Some(s),
+            VdfValue::Obj(_) => None,
+        }
+    }
+
+    pub fn as_obj(&self) -> Option<&VdfObject> {
+        match self {
+            VdfValue::Obj(obj) => Some(obj),
+            VdfValue::Str(_) => None,
+        }
+    }
+}
+
+/// Oggetto VDF: coppie chiave/valore in ordine di lettura (le chiavi duplicate sono mantenute)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VdfObject {
+    entries: Vec<(String, VdfValue)>,
+}
+
+impl VdfObject {
+    /// Primo valore con la chiave indicata (case-insensitive)
+    pub fn get(&self, key: &str) -> Option<&VdfValue> {
+        self.entries.iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(VdfValue::as_str)
+    }
+
+    pub fn get_obj(&self, key: &str) -> Option<&VdfObject> {
+        self.get(key).and_then(VdfValue::as_obj)
+    }
+
+    /// Valore numerico (le stringhe vuote o non numeriche danno None)
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.get_str(key).and_then(|s| s.trim().parse().ok())
+    }
+
+    /// Oggetto annidato seguendo un percorso di chiavi
+    pub fn get_path(&self, path: &[&str]) -> Option<&VdfObject> {
+        path.iter().try_fold(self, |obj, key| obj.get_obj(key))
+    }
+
+    /// Aggiunge una coppia in coda (usato anche dal parser binario di appinfo.vdf)
+    pub fn push(&mut self, key: String, value: VdfValue) {
+        self.entries.push((key, value));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &VdfValue)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Str(String),
+    Open,
+    Close,
+}
+
+/// Una virgoletta chiude la stringa solo se seguita (sulla stessa riga) da fine riga,
+/// un'altra stringa, una graffa, un condizionale o un commento: così `"The "Best" Game"`
+/// resta un unico valore
+fn closes_string(rest: &str) -> bool {
+    let next = rest.chars().find(|c| *c != ' ' && *c != '\t');
+    matches!(next, None | Some('\r' | '\n' | '"' | '{' | '}' | '[' | '/'))
+}
+
+/// `\"` a fine riga è quasi sempre un path Windows non escapato (`"C:\Games\"`)
+fn is_trailing_backslash(rest: &str) -> bool {
+    rest.split('\n').next().unwrap_or("").trim().is_empty()
+}
+
+fn read_quoted(input: &str, pos: &mut usize) -> String {
+    let mut value = String::new();
+    let mut chars = input[*pos..].char_indices();
+
+    while let Some((offset, c)) = chars.next() {
+        let after = *pos + offset + c.len_utf8();
+        match c {
+            '"' if closes_string(&input[after..]) => {
+                *pos = after;
+                return value;
+            }
+            '\\' => match chars.clone().next() {
+                Some((_, '"')) if is_trailing_backslash(&input[after + 1..]) => {
+                    value.push('\\');
+                    chars.next();
+                    *pos = after + 1;
+                    return value;
+                }
+                Some((_, escaped @ ('"' | '\\'))) => {
+                    value.push(escaped);
+                    chars.next();
+                }
+                Some((_, 'n')) => {
+                    value.push('\n');
+                    chars.next();
+                }
+                Some((_, 't')) => {
+                    value.push('\t');
+                    chars.next();
+                }
+                // Escape sconosciuto: il backslash resta letterale (`C:\Games`)
+                _ => value.push('\\'),
+            },
+            _ => value.push(c),
+        }
+    }
+
+    // Stringa non terminata: si prende tutto fino alla fine
+    *pos = input.len();
+    value
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let input = input.trim_start_matches('\u{feff}');
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < input.len() {
+        let rest = &input[pos..];
+        let Some(c) = rest.chars().next() else { break };
+
+        match c {
+            _ if c.is_whitespace() => pos += c.len_utf8(),
+            '/' if rest.starts_with("//") => {
+                pos += rest.find('\n').unwrap_or(rest.len());
+            }
+            '{' => {
+                tokens.push(Token::Open);
+                pos += 1;
+            }
+            '}' => {
+                tokens.push(Token::Close);
+                pos += 1;
+            }
+            // Condizionali di piattaforma: ignorati
+            '[' => {
+                pos += rest.find(']').map(|end| end + 1).unwrap_or(rest.len());
+            }
+            '"' => {
+                pos += 1;
+                tokens.push(Token::Str(read_quoted(input, &mut pos)));
+            }
+            _ => {
+                let end = rest
+                    .find(|ch: char| ch.is_whitespace() || matches!(ch, '"' | '{' | '}'))
+                    .unwrap_or(rest.len());
+                tokens.push(Token::Str(rest[..end].to_string()));
+                pos += end;
+            }
+        }
+    }
+
+    tokens
+}
+
+fn parse_object(tokens: &mut std::iter::Peekable<std::vec::IntoIter<Token>>, nested: bool) -> VdfObject {
+    let mut object = VdfObject::default();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Close if nested => return object,
+            // Graffa di chiusura in eccesso alla radice: ignorata
+            Token::Close => {}
+            // Blocco senza chiave: le sue voci vengono unite all'oggetto corrente
+            Token::Open => object.entries.extend(parse_object(tokens, true).entries),
+            Token::Str(key) => match tokens.peek() {
+                Some(Token::Open) => {
+                    tokens.next();
+                    let child = parse_object(tokens, true);
+                    object.entries.push((key, VdfValue::Obj(child)));
+                }
+                Some(Token::Str(_)) => {
+                    if let Some(Token::Str(value)) = tokens.next() {
+                        object.entries.push((key, VdfValue::Str(value)));
+                    }
+                }
+                // Chiave senza valore prima di `}` o a fine file
+                Some(Token::Close) | None => object.entries.push((key, VdfValue::Str(String::new()))),
+            },
+        }
+    }
+
+    object
+}
+
+/// Parsa un documento VDF; non fallisce mai, al massimo restituisce un albero parziale
+pub fn parse(input: &str) -> VdfObject {
+    let mut tokens = tokenize(input).into_iter().peekable();
+    parse_object(&mut tokens, false)
+}
+
+/// Legge e parsa un file VDF (gli UTF-8 non validi vengono sostituiti)
+pub fn parse_file(path: &Path) -> Result<VdfObject, String> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| format!("Errore lettura {}: {}", path.display(), e))?;
+    Ok(parse(&String::from_utf8_lossy(&bytes)))
+}
+
+// ============================================================================
+// File Steam tipizzati
+// ============================================================================
+
+/// `steamapps/appmanifest_<appid>.acf`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppManifest {
+    pub appid: u32,
+    pub name: Option<String>,
+    pub install_dir: Option<String>,
+    pub state_flags: Option<u32>,
+    pub last_updated: Option<u64>,
+    pub size_on_disk: Option<u64>,
+    pub buildid: Option<u32>,
+    pub last_owner: Option<String>,
+}
+
+impl AppManifest {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let root = parse(content);
+        let state = root.get_obj("AppState").ok_or("Sezione AppState non trovata")?;
+        let appid = state.get_u64("appid")
+            .and_then(|id| u32::try_from(id).ok())
+            .ok_or("AppID non trovato")?;
+        let text = |key: &str| state.get_str(key).filter(|s| !s.is_empty()).map(str::to_string);
+
+        Ok(Self {
+            appid,
+            name: text("name"),
+            install_dir: text("installdir"),
+            state_flags: state.get_u64("StateFlags").and_then(|v| u32::try_from(v).ok()),
+            last_updated: state.get_u64("LastUpdated"),
+            size_on_disk: state.get_u64("SizeOnDisk"),
+            buildid: state.get_u64("buildid").and_then(|v| u32::try_from(v).ok()),
+            last_owner: text("LastOwner"),
+        })
+    }
+
+    pub fn parse_file(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read(path)
+            .map_err(|e| format!("Errore lettura file ACF: {}", e))?;
+        Self::parse(&String::from_utf8_lossy(&content))
+    }
+
+    /// StateFlags con bit 4 = completamente installato
+    pub fn is_fully_installed(&self) -> bool {
+        self.state_flags.is_some_and(|flags| flags & 4 != 0)
+    }
+}
+
+/// Voce di `steamapps/libraryfolders.vdf`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibraryFolder {
+    pub path: String,
+    pub label: Option<String>,
+    pub mounted: bool,
+    pub tool: Option<String>,
+}
+
+/// Librerie Steam; supporta sia il formato attuale (oggetti con "path") sia il vecchio
+/// (`"1" "D:\\SteamLibrary"`). La directory principale di Steam non è inclusa.
+pub fn parse_library_folders(content: &str) -> Vec<LibraryFolder> {
+    let root = parse(content);
+    let Some(folders) = root.get_obj("libraryfolders") else {
+        return Vec::new();
+    };
+
+    folders.iter()
+        .filter(|(key, _)| key.chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|(_, value)| match value {
+            VdfValue::Str(path) => Some(LibraryFolder {
+                path: path.clone(),
+                label: None,
+                mounted: true,
+                tool: None,
+            }),
+            VdfValue::Obj(folder) => Some(LibraryFolder {
+                path: folder.get_str("path")?.to_string(),
+                label: folder.get_str("label").filter(|l| !l.is_empty()).map(str::to_string),
+                mounted: folder.get_str("mounted") != Some("0"),
+                tool: folder.get_str("tool").map(str::to_string),
+            }),
+        })
+        .filter(|folder| !folder.path.is_empty())
+        .collect()
+}
+
+/// Utente di `config/loginusers.vdf`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoginUser {
+    pub steam_id: String,
+    pub account_name: Option<String>,
+    pub persona_name: Option<String>,
+    pub most_recent: bool,
+}
+
+pub fn parse_login_users(content: &str) -> Vec<LoginUser> {
+    let root = parse(content);
+    let Some(users) = root.get_obj("users") else {
+        return Vec::new();
+    };
+
+    users.iter()
+        .filter_map(|(steam_id, value)| {
+            let user = value.as_obj()?;
+            let text = |key: &str| user.get_str(key).filter(|s| !s.is_empty()).map(str::to_string);
+            Some(LoginUser {
+                steam_id: steam_id.to_string(),
+                account_name: text("AccountName"),
+                persona_name: text("PersonaName"),
+                most_recent: user.get_str("MostRecent") == Some("1"),
+            })
+        })
+        .collect()
+}
+
+/// Gioco presente in `userdata/<id>/config/localconfig.vdf`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalConfigApp {
+    pub appid: u32,
+    pub last_played: Option<u64>,
+    pub playtime_minutes: Option<u64>,
+}
+
+pub fn parse_localconfig_apps(content: &str) -> Vec<LocalConfigApp> {
+    let root = parse(content);
+    let Some(apps) = root.get_path(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps"]) else {
+        return Vec::new();
+    };
+
+    apps.iter()
+        .filter_map(|(key, value)| {
+            let appid = key.parse::<u32>().ok()?;
+            let app = value.as_obj();
+            Some(LocalConfigApp {
+                appid,
+                last_played: app.and_then(|a| a.get_u64("LastPlayed")).filter(|t| *t > 0),
+                playtime_minutes: app.and_then(|a| a.get_u64("Playtime")),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_acf_with_tabs_and_spaces() {
+        let acf = "\"AppState\"\n{\n\t\"appid\"\t\t\"620\"\n    \"name\"    \"Portal 2\"\n\t\"StateFlags\"\t\t\"4\"\n\t\"installdir\"\t\t\"Portal 2\"\n\t\"SizeOnDisk\"\t\t\"12345\"\n\t\"UserConfig\"\n\t{\n\t\t\"language\"\t\t\"italian\"\n\t}\n}\n";
+        let manifest = AppManifest::parse(acf).unwrap();
+        assert_eq!(manifest.appid, 620);
+        assert_eq!(manifest.name.as_deref(), Some("Portal 2"));
+        assert_eq!(manifest.install_dir.as_deref(), Some("Portal 2"));
+        assert_eq!(manifest.size_on_disk, Some(12345));
+        assert!(manifest.is_fully_installed());
+    }
+
+    #[test]
+    fn handles_escapes_and_nested_quotes() {
+        let root = parse(r#""root" { "quoted" "Say \"hi\"" "path" "C:\\Games\\Steam" "nested" "The "Best" Game" }"#);
+        let obj = root.get_obj("root").unwrap();
+        assert_eq!(obj.get_str("quoted"), Some("Say \"hi\""));
+        assert_eq!(obj.get_str("path"), Some("C:\\Games\\Steam"));
+        assert_eq!(obj.get_str("nested"), Some("The \"Best\" Game"));
+    }
+
+    #[test]
+    fn keeps_unescaped_windows_paths() {
+        let root = parse("\"libraryfolders\"\n{\n\t\"1\"\t\t\"D:\\SteamLibrary\\\"\n\t\"2\"\t\t\"E:\\Games\"\n}\n");
+        let folders = root.get_obj("libraryfolders").unwrap();
+        assert_eq!(folders.get_str("1"), Some("D:\\SteamLibrary\\"));
+        assert_eq!(folders.get_str("2"), Some("E:\\Games"));
+    }
+
+    #[test]
+    fn skips_comments_conditionals_and_bom() {
+        let root = parse("\u{feff}// intestazione\n\"root\"\n{\n\t\"a\" \"1\" [$WIN32] // commento\n\tunquoted value\n}\n");
+        let obj = root.get_obj("ROOT").unwrap();
+        assert_eq!(obj.get_str("a"), Some("1"));
+        assert_eq!(obj.get_str("unquoted"), Some("value"));
+        assert_eq!(obj.len(), 2);
+    }
+
+    #[test]
+    fn recovers_from_truncated_and_unbalanced_input() {
+        let root = parse("\"AppState\"\n{\n\t\"appid\" \"70\"\n\t\"UserConfig\"\n\t{\n\t\t\"language\" \"english\"\n");
+        let state = root.get_obj("AppState").unwrap();
+        assert_eq!(state.get_u64("appid"), Some(70));
+        assert_eq!(state.get_path(&["UserConfig"]).and_then(|c| c.get_str("language")), Some("english"));
+
+        let root = parse("}\n\"a\" \"1\"\n\"b\"");
+        assert_eq!(root.get_str("a"), Some("1"));
+        assert_eq!(root.get_str("b"), Some(""));
+    }
+
+    #[test]
+    fn parses_library_folders_both_formats() {
+        let modern = "\"libraryfolders\"\n{\n\t\"0\"\n\t{\n\t\t\"path\"\t\t\"C:\\\\Program Files (x86)\\\\Steam\"\n\t\t\"label\"\t\t\"\"\n\t\t\"apps\"\n\t\t{\n\t\t\t\"228980\"\t\t\"1234\"\n\t\t}\n\t}\n\t\"1\"\n\t{\n\t\t\"path\"\t\t\"D:\\\\SteamLibrary\"\n\t\t\"label\"\t\t\"Giochi\"\n\t\t\"mounted\"\t\t\"0\"\n\t}\n}\n";
+        let folders = parse_library_folders(modern);
+        assert_eq!(folders.len(), 2);
+        assert_eq!(folders[0].path, "C:\\Program Files (x86)\\Steam");
+        assert_eq!(folders[0].label, None);
+        assert_eq!(folders[1].label.as_deref(), Some("Giochi"));
+        assert!(!folders[1].mounted);
+
+        let legacy = "\"LibraryFolders\"\n{\n\t\"TimeNextStatsReport\"\t\t\"1700000000\"\n\t\"ContentStatsID\"\t\t\"-123\"\n\t\"1\"\t\t\"D:\\\\SteamLibrary\"\n}\n";
+        let folders = parse_library_folders(legacy);
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].path, "D:\\SteamLibrary");
+    }
+
+    #[test]
+    fn parses_login_users() {
+        let content = "\"users\"\n{\n\t\"76561198000000001\"\n\t{\n\t\t\"AccountName\"\t\t\"alpha\"\n\t\t\"PersonaName\"\t\t\"Alpha \\\"A\\\"\"\n\t\t\"MostRecent\"\t\t\"0\"\n\t}\n\t\"76561198000000002\"\n\t{\n\t\t\"AccountName\"\t\t\"beta\"\n\t\t\"mostrecent\"\t\t\"1\"\n\t}\n}\n";
+        let users = parse_login_users(content);
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].persona_name.as_deref(), Some("Alpha \"A\""));
+        assert!(!users[0].most_recent);
+        assert!(users[1].most_recent);
+        assert_eq!(users[1].account_name.as_deref(), Some("beta"));
+    }
+
+    #[test]
+    fn parses_localconfig_apps_with_mixed_case_sections() {
+        let content = "\"UserLocalConfigStore\"\n{\n\t\"Software\"\n\t{\n\t\t\"valve\"\n\t\t{\n\t\t\t\"Steam\"\n\t\t\t{\n\t\t\t\t\"Apps\"\n\t\t\t\t{\n\t\t\t\t\t\"440\"\n\t\t\t\t\t{\n\t\t\t\t\t\t\"LastPlayed\"\t\t\"1700000000\"\n\t\t\t\t\t\t\"Playtime\"\t\t\"90\"\n\t\t\t\t\t\t\"cloud\"\n\t\t\t\t\t\t{\n\t\t\t\t\t\t\t\"quota\"\t\t\"1\"\n\t\t\t\t\t\t}\n\t\t\t\t\t}\n\t\t\t\t\t\"570\"\n\t\t\t\t\t{\n\t\t\t\t\t\t\"LastPlayed\"\t\t\"0\"\n\t\t\t\t\t}\n\t\t\t\t}\n\t\t\t}\n\t\t}\n\t}\n\t\"friends\"\n\t{\n\t\t\"12345678\"\n\t\t{\n\t\t\t\"name\"\t\t\"x\"\n\t\t}\n\t}\n}\n";
+        let apps = parse_localconfig_apps(content);
+        assert_eq!(apps.len(), 2);
+        assert_eq!(apps[0], LocalConfigApp { appid: 440, last_played: Some(1_700_000_000), playtime_minutes: Some(90) });
+        assert_eq!(apps[1].last_played, None);
+    }
+}
@@ -1,18 +1,37 @@
-// Test disabilitato: modulo notifications non disponibile
-// Il modulo notifications è stato rinominato in notifications_disabled
-// Questo test non può essere eseguito fino a quando il modulo non viene riabilitato
-
-/*
 use gamestringer::notifications::{
-    models::{CreateNotificationRequest, NotificationFilter, NotificationMetadata, NotificationType, NotificationPriority},
+    models::{CreateNotificationRequest, NotificationFilter, NotificationType},
     storage::NotificationStorage,
     manager::NotificationManager,
 };
 use tempfile::tempdir;
-use tokio;
 
 #[tokio::test]
 async fn test_notification_system_basic() {
-    // Test completo commentato - modulo notifications disabilitato
+    let temp_dir = tempdir().unwrap();
+    let manager = NotificationManager::new(NotificationStorage::new(temp_dir.path().join("notifications.db")));
+    manager.initialize().await.unwrap();
+
+    let notification = manager.create_notification(CreateNotificationRequest {
+        profile_id: "profile_test".to_string(),
+        notification_type: NotificationType::Security,
+        title: "Accesso non riuscito".to_string(),
+        message: "Password errata".to_string(),
+        icon: None,
+        action_url: None,
+        priority: None,
+        expires_at: None,
+        metadata: None,
+    }).await.unwrap();
+    assert_eq!(manager.get_unread_count("profile_test").await.unwrap(), 1);
+
+    manager.mark_as_read(&notification.id, "profile_test").await.unwrap();
+    assert_eq!(manager.get_unread_count("profile_test").await.unwrap(), 0);
+
+    let notifications = manager.get_notifications("profile_test", NotificationFilter::default()).await.unwrap();
+    assert_eq!(notifications.len(), 1);
+
+    manager.delete_notification(&notification.id, "profile_test").await.unwrap();
+    assert!(manager.get_notifications("profile_test", NotificationFilter::default()).await.unwrap().is_empty());
+    // Le notifiche di altri profili non sono visibili
+    assert!(manager.get_notifications("other", NotificationFilter::default()).await.unwrap().is_empty());
 }
-*/
\ No newline at end of file
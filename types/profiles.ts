@@ -55,6 +55,8 @@ export interface LibrarySettings {
 
 export interface SecuritySettings {
   session_timeout: number;
+  max_session_duration?: number;
+  session_expiry_warning?: number;
   require_password_for_sensitive: boolean;
   auto_lock_failed_attempts: number;
   lock_duration: number;
@@ -104,6 +106,12 @@ export interface WindowSize {
   height: number;
 }
 
+export interface ExportSummary {
+  file_path: string;
+  passphrase_protected: boolean;
+  warnings: string[];
+}
+
 export interface AuthStats {
   total_profiles: number;
   locked_profiles: number;
@@ -113,6 +121,44 @@ export interface AuthStats {
   last_failed_login?: string;
 }
 
+export interface LockoutStatus {
+  locked: boolean;
+  failed_attempts: number;
+  attempts_remaining: number;
+  seconds_remaining: number;
+  next_attempt_allowed_at?: string;
+}
+
+export interface SessionStatus {
+  active: boolean;
+  expired: boolean;
+  near_expiry: boolean;
+  idle_timeout_seconds?: number;
+  max_session_seconds?: number;
+  idle_seconds_remaining?: number;
+  absolute_seconds_remaining?: number;
+  seconds_remaining?: number;
+  expires_at?: string;
+  expiry_reason?: 'idle' | 'max_duration';
+}
+
+export interface OrphanedFile {
+  path: string;
+  kind: 'settings' | 'profile_data' | 'avatar';
+  profile_id?: string;
+  size_bytes: number;
+}
+
+export interface OrphanCleanupReport {
+  dry_run: boolean;
+  orphaned_files: OrphanedFile[];
+  total_bytes: number;
+  quarantine_dir?: string;
+  quarantined_files: number;
+  quarantined_bytes: number;
+  errors: string[];
+}
+
 export interface ProfileResponse<T> {
   success: boolean;
   data?: T;
@@ -124,8 +170,8 @@ export interface TauriProfileAPI {
   // Gestione profili
   list_profiles(): Promise<ProfileResponse<ProfileInfo[]>>;
   create_profile(request: CreateProfileRequest): Promise<ProfileResponse<UserProfile>>;
-  authenticate_profile(name: string, password: string): Promise<ProfileResponse<UserProfile>>;
-  switch_profile(name: string, password: string): Promise<ProfileResponse<UserProfile>>;
+  authenticate_profile(profileId: string, password: string): Promise<ProfileResponse<UserProfile>>;
+  switch_profile(profileId: string, password: string): Promise<ProfileResponse<UserProfile>>;
   get_current_profile(): Promise<ProfileResponse<UserProfile | null>>;
   logout(): Promise<ProfileResponse<boolean>>;
   
@@ -143,17 +189,22 @@ export interface TauriProfileAPI {
   can_authenticate(name: string): Promise<ProfileResponse<boolean>>;
   unlock_profile(name: string): Promise<ProfileResponse<boolean>>;
   get_failed_attempts(name: string): Promise<ProfileResponse<number>>;
+  cleanup_orphaned_profile_data(confirm?: boolean): Promise<ProfileResponse<OrphanCleanupReport>>;
+  get_lockout_status(name: string): Promise<ProfileResponse<LockoutStatus>>;
+  reset_failed_attempts(name: string): Promise<ProfileResponse<boolean>>;
   get_auth_stats(): Promise<ProfileResponse<AuthStats>>;
   
   // Sessione
   is_session_expired(timeout_seconds: number): Promise<ProfileResponse<boolean>>;
   renew_session(): Promise<ProfileResponse<boolean>>;
   get_session_time_remaining(timeout_seconds: number): Promise<ProfileResponse<number | null>>;
+  get_session_status(): Promise<ProfileResponse<SessionStatus>>;
+  touch_session(): Promise<ProfileResponse<SessionStatus>>;
   
   // Import/Export
-  export_profile(profile_id: string, password: string, export_path: string, export_password?: string): Promise<ProfileResponse<string>>;
+  export_profile(profile_id: string, password: string, export_path: string, passphrase?: string): Promise<ProfileResponse<ExportSummary>>;
   import_profile(file_path: string, import_password: string, new_name?: string): Promise<ProfileResponse<UserProfile>>;
-  validate_export_file(file_path: string): Promise<ProfileResponse<any>>;
+  validate_export_file(file_path: string, passphrase?: string): Promise<ProfileResponse<any>>;
   create_profile_backup(profile_id: string, password: string): Promise<ProfileResponse<string>>;
 }
 
@@ -166,8 +217,8 @@ export interface UseProfilesReturn {
   
   // Actions
   createProfile: (request: CreateProfileRequest) => Promise<boolean>;
-  authenticateProfile: (name: string, password: string) => Promise<boolean>;
-  switchProfile: (name: string, password: string) => Promise<boolean>;
+  authenticateProfile: (profileId: string, password: string) => Promise<boolean>;
+  switchProfile: (profileId: string, password: string) => Promise<boolean>;
   logout: () => Promise<boolean>;
   refreshProfiles: () => Promise<void>;
   deleteProfile: (profileId: string, password: string) => Promise<boolean>;
@@ -208,6 +259,8 @@ export const DEFAULT_PROFILE_SETTINGS: ProfileSettings = {
   },
   security: {
     session_timeout: 60,
+    max_session_duration: 720,
+    session_expiry_warning: 120,
     require_password_for_sensitive: true,
     auto_lock_failed_attempts: 3,
     lock_duration: 15,